@@ -0,0 +1,237 @@
+use std::fmt::Write;
+
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use tryvial::try_fn;
+
+/// A single collision shape extracted from the cooked PhysX data inside an ALOC.
+pub struct CollisionMesh {
+	pub kind: CollisionMeshKind,
+	pub vertices: Vec<[f32; 3]>,
+
+	/// Each face is a list of vertex indices; convex hull polygons can have more than three vertices.
+	pub faces: Vec<Vec<u32>>
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CollisionMeshKind {
+	Convex,
+	Triangle
+}
+
+const CONVEX_HEADER: &[u8] = b"NXS\x01CVXM";
+const TRIANGLE_HEADER: &[u8] = b"NXS\x01MESH";
+
+// Triangle mesh serialisation flags
+const IMSF_8BIT_INDICES: u32 = 1 << 2;
+const IMSF_16BIT_INDICES: u32 = 1 << 3;
+
+struct Reader<'a> {
+	data: &'a [u8],
+	pos: usize
+}
+
+impl<'a> Reader<'a> {
+	fn new(data: &'a [u8], pos: usize) -> Self {
+		Self { data, pos }
+	}
+
+	fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+		let slice = self
+			.data
+			.get(self.pos..self.pos + len)
+			.context("Unexpected end of collision data")?;
+
+		self.pos += len;
+
+		Ok(slice)
+	}
+
+	fn u8(&mut self) -> Result<u8> {
+		Ok(self.bytes(1)?[0])
+	}
+
+	fn u16(&mut self) -> Result<u16> {
+		Ok(u16::from_le_bytes(self.bytes(2)?.try_into()?))
+	}
+
+	fn u32(&mut self) -> Result<u32> {
+		Ok(u32::from_le_bytes(self.bytes(4)?.try_into()?))
+	}
+
+	fn f32(&mut self) -> Result<f32> {
+		Ok(f32::from_le_bytes(self.bytes(4)?.try_into()?))
+	}
+
+	fn vertices(&mut self, count: u32) -> Result<Vec<[f32; 3]>> {
+		(0..count)
+			.map(|_| Ok([self.f32()?, self.f32()?, self.f32()?]))
+			.collect()
+	}
+}
+
+/// Parse the collision meshes contained within an ALOC resource.
+///
+/// Only cooked convex and triangle meshes are extracted; primitive shapes (boxes, spheres, capsules) are skipped.
+#[try_fn]
+#[context("Couldn't parse ALOC data")]
+pub fn parse_aloc(data: &[u8]) -> Result<Vec<CollisionMesh>> {
+	let mut meshes = vec![];
+
+	for (pos, window) in data.windows(CONVEX_HEADER.len()).enumerate() {
+		if window == CONVEX_HEADER {
+			meshes.push(parse_convex_mesh(&mut Reader::new(data, pos + CONVEX_HEADER.len()))?);
+		} else if window == TRIANGLE_HEADER {
+			meshes.push(parse_triangle_mesh(&mut Reader::new(
+				data,
+				pos + TRIANGLE_HEADER.len()
+			))?);
+		}
+	}
+
+	meshes
+}
+
+#[try_fn]
+#[context("Couldn't parse convex mesh")]
+fn parse_convex_mesh(reader: &mut Reader) -> Result<CollisionMesh> {
+	let _version = reader.u32()?;
+	let _serial_flags = reader.u32()?;
+
+	if reader.bytes(8)? != b"ICE\x01CLHL" {
+		bail!("Convex mesh did not contain hull data");
+	}
+
+	let _hull_version = reader.u32()?;
+
+	let vertex_count = reader.u32()?;
+	let _edge_count = reader.u32()?;
+	let polygon_count = reader.u32()?;
+	let vertex_ref_count = reader.u32()?;
+
+	let vertices = reader.vertices(vertex_count)?;
+
+	// Plane (4 floats), vertex ref offset, vertex count, min index
+	let polygons = (0..polygon_count)
+		.map(|_| {
+			reader.bytes(16)?;
+			let vref = reader.u16()?;
+			let count = reader.u8()?;
+			reader.u8()?;
+
+			Ok((vref as usize, count as usize))
+		})
+		.collect::<Result<Vec<_>>>()?;
+
+	let vertex_refs = reader.bytes(vertex_ref_count as usize)?;
+
+	let faces = polygons
+		.into_iter()
+		.map(|(vref, count)| {
+			Ok(vertex_refs
+				.get(vref..vref + count)
+				.context("Convex polygon referenced out of bounds vertices")?
+				.iter()
+				.map(|&x| x as u32)
+				.collect::<Vec<_>>())
+		})
+		.collect::<Result<_>>()?;
+
+	CollisionMesh {
+		kind: CollisionMeshKind::Convex,
+		vertices,
+		faces
+	}
+}
+
+#[try_fn]
+#[context("Couldn't parse triangle mesh")]
+fn parse_triangle_mesh(reader: &mut Reader) -> Result<CollisionMesh> {
+	let version = reader.u32()?;
+
+	if version >= 14 {
+		let _midphase_id = reader.u32()?;
+	}
+
+	let serial_flags = reader.u32()?;
+
+	let vertex_count = reader.u32()?;
+	let triangle_count = reader.u32()?;
+
+	let vertices = reader.vertices(vertex_count)?;
+
+	let faces = (0..triangle_count)
+		.map(|_| {
+			(0..3)
+				.map(|_| {
+					if serial_flags & IMSF_8BIT_INDICES != 0 {
+						Ok(reader.u8()? as u32)
+					} else if serial_flags & IMSF_16BIT_INDICES != 0 {
+						Ok(reader.u16()? as u32)
+					} else {
+						reader.u32()
+					}
+				})
+				.collect::<Result<Vec<_>>>()
+		})
+		.collect::<Result<_>>()?;
+
+	CollisionMesh {
+		kind: CollisionMeshKind::Triangle,
+		vertices,
+		faces
+	}
+}
+
+/// Convert collision meshes to a wireframe OBJ (using line elements), returning the OBJ and its bounding box.
+#[try_fn]
+#[context("Couldn't convert collision to OBJ")]
+pub fn collision_to_obj(meshes: &[CollisionMesh]) -> Result<(String, [f32; 6])> {
+	let mut obj = String::new();
+	let mut bounding_box = [f32::MAX, f32::MAX, f32::MAX, f32::MIN, f32::MIN, f32::MIN];
+
+	// OBJ indices are 1-based
+	let mut previous_vertex_count: u32 = 1;
+
+	for (idx, mesh) in meshes.iter().enumerate() {
+		match mesh.kind {
+			CollisionMeshKind::Convex => writeln!(obj, "o convex.{:03}", idx)?,
+			CollisionMeshKind::Triangle => writeln!(obj, "o triangle.{:03}", idx)?
+		}
+
+		for [x, y, z] in &mesh.vertices {
+			writeln!(obj, "v {} {} {}", x, y, z)?;
+
+			bounding_box[0] = bounding_box[0].min(*x);
+			bounding_box[1] = bounding_box[1].min(*y);
+			bounding_box[2] = bounding_box[2].min(*z);
+
+			bounding_box[3] = bounding_box[3].max(*x);
+			bounding_box[4] = bounding_box[4].max(*y);
+			bounding_box[5] = bounding_box[5].max(*z);
+		}
+
+		for face in &mesh.faces {
+			if let Some(first) = face.first() {
+				// Close the loop back to the first vertex
+				writeln!(
+					obj,
+					"l {} {}",
+					face.iter()
+						.map(|x| (x + previous_vertex_count).to_string())
+						.collect::<Vec<_>>()
+						.join(" "),
+					first + previous_vertex_count
+				)?;
+			}
+		}
+
+		previous_vertex_count += mesh.vertices.len() as u32;
+	}
+
+	if meshes.iter().all(|x| x.vertices.is_empty()) {
+		bounding_box = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+	}
+
+	(obj, bounding_box)
+}
@@ -0,0 +1,270 @@
+use std::{
+	fs,
+	io::{Cursor, Read},
+	path::{Component, Path}
+};
+
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use hitman_commons::{
+	metadata::{PathedID, RuntimeID},
+	rpkg_tool::RpkgResourceMeta
+};
+use rpkg_rs::resource::partition_manager::PartitionManager;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::{
+	audio_import::{WemEncoder, convert_to_wem},
+	dropped_files::{get_content_chunk_folder, register_content_folder},
+	rpkg::{extract_latest_metadata, extract_latest_resource}
+};
+
+/// The resources generated for a new sound, named after it.
+pub struct AudioEventStub {
+	/// The name of the Wwise event, which is also used for the zaudioevententity
+	pub name: String,
+
+	pub wwev_path: String,
+	pub wwem_path: String
+}
+
+/// The parts of a template WWEV which are kept in the generated one.
+struct TemplateEvent {
+	name: String,
+	soundbank: Vec<u8>,
+	max_attenuation: f32,
+	dependency_index: u32,
+	wem_id: u32
+}
+
+#[try_fn]
+fn read_u32(cursor: &mut Cursor<&[u8]>) -> Result<u32> {
+	let mut bytes = [0; 4];
+	cursor.read_exact(&mut bytes).context("Unexpected end of WWEV")?;
+	u32::from_le_bytes(bytes)
+}
+
+/// Wwise's short ID for a name: the 32-bit FNV-1 hash of the name in lowercase.
+fn wwise_short_id(name: &str) -> u32 {
+	name.to_lowercase()
+		.bytes()
+		.fold(0x811C9DC5, |hash, byte| hash.wrapping_mul(0x01000193) ^ byte as u32)
+}
+
+/// Replace every occurrence of a little-endian u32 in some data.
+fn replace_u32(data: &mut [u8], from: u32, to: u32) {
+	let from = from.to_le_bytes();
+	let to = to.to_le_bytes();
+
+	let mut index = 0;
+
+	while index + 4 <= data.len() {
+		if data[index..index + 4] == from {
+			data[index..index + 4].copy_from_slice(&to);
+			index += 4;
+		} else {
+			index += 1;
+		}
+	}
+}
+
+/// Read a WWEV with exactly one streamed object. WWEVs are laid out as the event's name (u32 length including the null
+/// terminator, then the string), its soundbank (u32 length, then the data), the maximum attenuation radius (f32), the
+/// number of non-streamed objects (u32) and, if there are none, the number of streamed objects (u32) followed by each
+/// one's dependency index, WEM ID and prefetch size (u32s) and prefetch data.
+#[try_fn]
+#[context("Couldn't read template WWEV")]
+fn parse_template_event(data: &[u8]) -> Result<TemplateEvent> {
+	let mut cursor = Cursor::new(data);
+
+	let mut name = vec![0; read_u32(&mut cursor)? as usize];
+	cursor.read_exact(&mut name).context("Unexpected end of WWEV")?;
+
+	let name = String::from_utf8(name.into_iter().take_while(|&x| x != 0).collect()).context("Invalid event name")?;
+
+	let mut soundbank = vec![0; read_u32(&mut cursor)? as usize];
+	cursor.read_exact(&mut soundbank).context("Unexpected end of WWEV")?;
+
+	let max_attenuation = f32::from_bits(read_u32(&mut cursor)?);
+
+	if read_u32(&mut cursor)? != 0 || read_u32(&mut cursor)? != 1 {
+		bail!("The template must play a single streamed sound");
+	}
+
+	let dependency_index = read_u32(&mut cursor)?;
+	let wem_id = read_u32(&mut cursor)?;
+
+	TemplateEvent {
+		name,
+		soundbank,
+		max_attenuation,
+		dependency_index,
+		wem_id
+	}
+}
+
+/// Write a WWEV with a single streamed object and no prefetch data.
+fn generate_event(name: &str, soundbank: &[u8], max_attenuation: f32, wem_id: u32) -> Vec<u8> {
+	let mut data = vec![];
+
+	data.extend_from_slice(&(name.len() as u32 + 1).to_le_bytes());
+	data.extend_from_slice(name.as_bytes());
+	data.push(0);
+
+	data.extend_from_slice(&(soundbank.len() as u32).to_le_bytes());
+	data.extend_from_slice(soundbank);
+
+	data.extend_from_slice(&max_attenuation.to_le_bytes());
+
+	// No non-streamed objects, one streamed object
+	data.extend_from_slice(&0u32.to_le_bytes());
+	data.extend_from_slice(&1u32.to_le_bytes());
+
+	// Dependency index, WEM ID, prefetch size
+	data.extend_from_slice(&0u32.to_le_bytes());
+	data.extend_from_slice(&wem_id.to_le_bytes());
+	data.extend_from_slice(&0u32.to_le_bytes());
+
+	data
+}
+
+/// Generate a WWEM and a WWEV playing it for a new sound, and write them with their meta files to the project's content
+/// folder, registering it with the manifest. The audio is a WEM file, or a WAV file which is converted with the given
+/// encoder. The WWEV's soundbank is copied from a vanilla template WWEV playing a single streamed sound, with the
+/// template's event and WEM IDs replaced by those of the new sound so that the two don't clash.
+#[try_fn]
+#[context("Couldn't generate audio event for {}", name)]
+pub async fn generate_audio_event_stub(
+	app: &AppHandle,
+	encoder: Option<&WemEncoder>,
+	game_files: &PartitionManager,
+	project_path: &Path,
+	template: RuntimeID,
+	audio: &Path,
+	name: &str
+) -> Result<AudioEventStub> {
+	let file_name = name
+		.chars()
+		.filter(|x| x.is_ascii_alphanumeric() || *x == ' ' || *x == '_')
+		.collect::<String>()
+		.trim()
+		.replace(' ', "_")
+		.to_lowercase();
+
+	if file_name.is_empty() {
+		bail!("The sound needs a name");
+	}
+
+	let (mut wwev_meta, wwev_data) = extract_latest_resource(game_files, template)?;
+
+	if wwev_meta.core_info.resource_type != "WWEV" {
+		bail!(
+			"The template ({}) is a {}, not a WWEV",
+			template,
+			wwev_meta.core_info.resource_type
+		);
+	}
+
+	let template_event = parse_template_event(&wwev_data)?;
+
+	let mut wem_reference = wwev_meta
+		.core_info
+		.references
+		.get(template_event.dependency_index as usize)
+		.context("No such WWEM dependency")?
+		.to_owned();
+
+	let mut wwem_meta = extract_latest_metadata(game_files, wem_reference.resource.get_id())?;
+
+	let wwev_path = format!(
+		"[assembly:/sound/wwise/exportedwwisedata/events/_pro/{}.wwiseevent].pc_wwisebank",
+		file_name
+	);
+
+	let wwem_path = format!(
+		"[assembly:/sound/wwise/exportedwwisedata/media/_pro/{}.wem].pc_wem",
+		file_name
+	);
+
+	let wwev_hash = RuntimeID::from_any(&wwev_path)?;
+	let wwem_hash = RuntimeID::from_any(&wwem_path)?;
+
+	let mut soundbank = template_event.soundbank;
+	replace_u32(
+		&mut soundbank,
+		wwise_short_id(&template_event.name),
+		wwise_short_id(&file_name)
+	);
+	replace_u32(&mut soundbank, template_event.wem_id, wwise_short_id(&wwem_path));
+
+	wem_reference.resource = PathedID::Path(wwem_path.to_owned());
+
+	wwev_meta.core_info.id = wwev_hash;
+	wwev_meta.core_info.references = vec![wem_reference];
+
+	wwem_meta.core_info.id = wwem_hash;
+
+	let content_folder = get_content_chunk_folder(project_path)?;
+	fs::create_dir_all(project_path.join(&content_folder))?;
+
+	let wwem_destination = project_path.join(&content_folder).join(format!("{}.WWEM", wwem_hash));
+
+	if audio
+		.extension()
+		.is_some_and(|x| x.to_string_lossy().eq_ignore_ascii_case("wav"))
+	{
+		let encoder = encoder.context("No WEM encoder has been set to convert WAV files with")?;
+
+		let temp_folder = app.path().app_data_dir().context("Couldn't get data dir")?.join("temp");
+		fs::create_dir_all(&temp_folder)?;
+
+		let temp_path = temp_folder.join(format!("{}.wem", Uuid::new_v4()));
+
+		convert_to_wem(app, encoder, audio, &temp_path).await?;
+
+		fs::copy(&temp_path, &wwem_destination)?;
+		fs::remove_file(&temp_path)?;
+	} else {
+		fs::copy(audio, &wwem_destination)?;
+	}
+
+	fs::write(
+		project_path
+			.join(&content_folder)
+			.join(format!("{}.WWEM.meta", wwem_hash)),
+		RpkgResourceMeta::from_resource_metadata(wwem_meta, false)
+			.to_binary()
+			.context("Couldn't serialise meta file")?
+	)?;
+
+	fs::write(
+		project_path.join(&content_folder).join(format!("{}.WWEV", wwev_hash)),
+		generate_event(
+			&file_name,
+			&soundbank,
+			template_event.max_attenuation,
+			wwise_short_id(&wwem_path)
+		)
+	)?;
+
+	fs::write(
+		project_path
+			.join(&content_folder)
+			.join(format!("{}.WWEV.meta", wwev_hash)),
+		RpkgResourceMeta::from_resource_metadata(wwev_meta, false)
+			.to_binary()
+			.context("Couldn't serialise meta file")?
+	)?;
+
+	if let Some(Component::Normal(root)) = content_folder.components().next() {
+		register_content_folder(project_path, &root.to_string_lossy())?;
+	}
+
+	AudioEventStub {
+		name: file_name,
+		wwev_path,
+		wwem_path
+	}
+}
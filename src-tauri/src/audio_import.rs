@@ -0,0 +1,170 @@
+use std::{
+	fs,
+	path::{Component, Path, PathBuf}
+};
+
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use hitman_commons::{metadata::RuntimeID, rpkg_tool::RpkgResourceMeta};
+use itertools::Itertools;
+use rpkg_rs::resource::partition_manager::PartitionManager;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::ShellExt;
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::{
+	RunCommandExt,
+	dropped_files::{get_content_chunk_folder, register_content_folder},
+	external_tools::split_arguments,
+	rpkg::extract_latest_metadata,
+	tasks::{CancellableTask, update_task}
+};
+
+/// A user-configured program which converts a WAV file to WEM, like WwiseConsole or a script wrapping it.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WemEncoder {
+	pub path: PathBuf,
+
+	/// The arguments to pass to the encoder; `{input}` is replaced with the WAV file and `{output}` with where the WEM
+	/// should be written. Arguments are separated by spaces, and can be wrapped in double quotes to include spaces.
+	pub arguments: String
+}
+
+/// A WAV file in a folder being imported, with the WWEM it replaces if that can be worked out from its name.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct WavFile {
+	pub name: String,
+	pub target: Option<String>
+}
+
+/// List the WAV files in a folder. Files named after a hash, like `00123456789ABCDE.wav`, are assumed to replace it.
+#[try_fn]
+#[context("Couldn't list WAV files in {}", folder.display())]
+pub fn find_wav_files(folder: &Path) -> Result<Vec<WavFile>> {
+	fs::read_dir(folder)?
+		.filter_map(|x| x.ok())
+		.filter(|x| x.file_type().is_ok_and(|x| x.is_file()))
+		.map(|x| x.file_name().to_string_lossy().into_owned())
+		.filter(|x| x.to_lowercase().ends_with(".wav"))
+		.sorted()
+		.map(|name| {
+			let stem = &name[..name.len() - ".wav".len()];
+
+			WavFile {
+				target: (stem.len() == 16 && stem.chars().all(|x| x.is_ascii_hexdigit())).then(|| stem.to_uppercase()),
+				name
+			}
+		})
+		.collect()
+}
+
+/// Convert a WAV file to WEM with the configured encoder.
+#[try_fn]
+#[context("Couldn't convert {} to WEM", input.display())]
+pub async fn convert_to_wem(app: &AppHandle, encoder: &WemEncoder, input: &Path, output: &Path) -> Result<()> {
+	let args = split_arguments(&encoder.arguments)
+		.into_iter()
+		.map(|x| {
+			x.replace("{input}", &input.to_string_lossy())
+				.replace("{output}", &output.to_string_lossy())
+		})
+		.collect::<Vec<_>>();
+
+	let mut command = app.shell().command(&encoder.path).args(args);
+
+	if let Some(folder) = encoder.path.parent().filter(|x| x.is_dir()) {
+		command = command.current_dir(folder);
+	}
+
+	command.run().await?;
+
+	if !output.is_file() {
+		bail!("The encoder didn't write {}", output.display());
+	}
+}
+
+/// Convert WAV files in a folder to WEM and place them in the project's content folder as replacements for the given
+/// WWEMs, with meta files copied from the originals, registering the content folder with the manifest. Every target is
+/// checked before anything is converted. Returns how many files were imported, which is fewer than requested if the
+/// task was cancelled.
+#[try_fn]
+#[context("Couldn't import WAV files from {}", folder.display())]
+pub async fn import_wav_files(
+	app: &AppHandle,
+	task: &CancellableTask,
+	encoder: &WemEncoder,
+	game_files: &PartitionManager,
+	project_path: &Path,
+	folder: &Path,
+	mapping: &[(String, String)]
+) -> Result<usize> {
+	let mut targets = vec![];
+
+	for (name, target) in mapping {
+		let target = RuntimeID::from_any(target).with_context(|| format!("Invalid target for {}", name))?;
+
+		let metadata = extract_latest_metadata(game_files, target)
+			.with_context(|| format!("The target of {} ({}) isn't in the game files", name, target))?;
+
+		if metadata.core_info.resource_type != "WWEM" {
+			bail!(
+				"The target of {} ({}) is a {}, not a WWEM",
+				name,
+				target,
+				metadata.core_info.resource_type
+			);
+		}
+
+		targets.push((name, target, metadata));
+	}
+
+	let content_folder = get_content_chunk_folder(project_path)?;
+	fs::create_dir_all(project_path.join(&content_folder))?;
+
+	let temp_folder = app.path().app_data_dir().context("Couldn't get data dir")?.join("temp");
+	fs::create_dir_all(&temp_folder)?;
+
+	let mut imported = 0;
+
+	for (name, target, metadata) in targets {
+		if task.is_cancelled() {
+			break;
+		}
+
+		update_task(
+			app,
+			task.id,
+			format!("Importing audio ({}/{}): {}", imported + 1, mapping.len(), name)
+		)?;
+
+		let temp_path = temp_folder.join(format!("{}.wem", Uuid::new_v4()));
+
+		convert_to_wem(app, encoder, &folder.join(name), &temp_path).await?;
+
+		let destination = project_path.join(&content_folder).join(format!("{}.WWEM", target));
+
+		fs::copy(&temp_path, &destination)?;
+		fs::remove_file(&temp_path)?;
+
+		fs::write(
+			project_path.join(&content_folder).join(format!("{}.WWEM.meta", target)),
+			RpkgResourceMeta::from_resource_metadata(metadata, false)
+				.to_binary()
+				.context("Couldn't serialise meta file")?
+		)?;
+
+		imported += 1;
+	}
+
+	if imported > 0
+		&& let Some(Component::Normal(root)) = content_folder.components().next()
+	{
+		register_content_folder(project_path, &root.to_string_lossy())?;
+	}
+
+	imported
+}
@@ -0,0 +1,249 @@
+use std::{fs, iter};
+
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use hashbrown::HashSet;
+use hitman_commons::{game::GameVersion, hash_list::HashList, metadata::RuntimeID};
+use hitman_formats::wwev::WwiseEvent;
+use indexmap::{IndexMap, IndexSet};
+use itertools::Itertools;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rpkg_rs::resource::partition_manager::PartitionManager;
+use serde::{Deserialize, Serialize};
+use serde_json::to_vec;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::{
+	event_handling::resource_overview::get_wwev_switch_groups,
+	model::{AppState, AudioNamesRequest, Request, ToolRequest},
+	rpkg::{extract_latest_metadata, extract_latest_resource},
+	send_request,
+	soundbank::{HIRC_ACTION, HIRC_EVENT, get_hierarchy_objects, read_u16, read_u32}
+};
+
+/// Names resolved locally which the downloaded hash lists don't have, layered over them. Stored in the app data folder.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct LocalHashList {
+	/// Names of Wwise objects like events and switches, by their IDs
+	#[serde(default)]
+	pub wwise_names: IndexMap<u32, String>
+}
+
+const FNV_OFFSET_BASIS: u32 = 0x811C9DC5;
+const FNV_PRIME: u32 = 0x01000193;
+
+const ACTION_SET_STATE: u16 = 0x1204;
+const ACTION_SET_SWITCH: u16 = 0x1901;
+
+/// The 32-bit FNV-1 hash of a lowercased name, which Wwise uses as the ID of named objects like events and switches.
+pub fn wwise_hash(name: &str) -> u32 {
+	name.to_lowercase().bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+		hash.wrapping_mul(FNV_PRIME) ^ byte as u32
+	})
+}
+
+/// Hash each word, alone and after each of the prefixes, returning the names which match any of the targets.
+pub fn brute_force_names(targets: &HashSet<u32>, words: &[String], prefixes: &[String]) -> Vec<(u32, String)> {
+	words
+		.par_iter()
+		.flat_map_iter(|word| {
+			iter::once(word.to_owned()).chain(prefixes.iter().map(move |prefix| format!("{prefix}{word}")))
+		})
+		.filter_map(|name| {
+			let hash = wwise_hash(&name);
+			targets.contains(&hash).then_some((hash, name))
+		})
+		.collect()
+}
+
+/// Get the IDs in a Wwise soundbank which are hashes of names: those of its events, and of the switches and states
+/// which its actions set. Anything after a malformed object is skipped.
+pub fn get_bank_name_hashes(bank: &[u8]) -> Vec<u32> {
+	let mut ids = vec![];
+
+	for (object_type, object) in get_hierarchy_objects(bank) {
+		match object_type {
+			HIRC_EVENT => ids.extend(read_u32(object, 0)),
+			HIRC_ACTION => ids.extend(read_action_name_hashes(object).into_iter().flatten()),
+			_ => {}
+		}
+	}
+
+	ids
+}
+
+/// The group and value IDs of an action which sets a switch or state.
+fn read_action_name_hashes(object: &[u8]) -> Option<[u32; 2]> {
+	let action_type = read_u16(object, 4)?;
+
+	if action_type != ACTION_SET_STATE && action_type != ACTION_SET_SWITCH {
+		return None;
+	}
+
+	// The action's ID, type, target and whether the target is a bus
+	let mut offset = 4 + 2 + 4 + 1;
+
+	// Property bundles are a count, then the property IDs, then their values; ranged properties have a minimum and a
+	// maximum
+	let property_count = *object.get(offset)? as usize;
+	offset += 1 + property_count * 5;
+
+	let ranged_property_count = *object.get(offset)? as usize;
+	offset += 1 + ranged_property_count * 9;
+
+	Some([read_u32(object, offset)?, read_u32(object, offset + 4)?])
+}
+
+/// Get the IDs of named Wwise objects used by a WWEV or SDEF resource, from the soundbanks of the WWEV or of each WWEV
+/// the SDEF uses. The names of those which the resources themselves contain, like the names of the events and of the
+/// switches they depend on, are returned as well.
+#[try_fn]
+#[context("Couldn't find audio IDs in {}", hash)]
+fn find_audio_ids(
+	game_files: &PartitionManager,
+	hash_list: &HashList,
+	game_version: GameVersion,
+	hash: RuntimeID
+) -> Result<(IndexSet<u32>, IndexMap<u32, String>)> {
+	let get_references = |hash: RuntimeID| -> Result<Vec<RuntimeID>> {
+		Ok(extract_latest_metadata(game_files, hash)?
+			.core_info
+			.references
+			.iter()
+			.map(|x| x.resource.get_id())
+			.collect())
+	};
+
+	let is_type = |hash: &RuntimeID, resource_type: &str| {
+		hash_list
+			.entries
+			.get(hash)
+			.is_some_and(|x| x.resource_type == resource_type)
+	};
+
+	let events = if is_type(&hash, "WWEV") {
+		vec![hash]
+	} else if is_type(&hash, "SDEF") {
+		get_references(hash)?
+			.into_iter()
+			.filter(|x| is_type(x, "WWEV"))
+			.unique()
+			.collect()
+	} else {
+		bail!("Only WWEV and SDEF resources contain audio IDs");
+	};
+
+	let mut banks = IndexSet::new();
+	let mut names = vec![];
+
+	for event in events {
+		let references = get_references(event)?;
+
+		names.push(WwiseEvent::parse(&extract_latest_resource(game_files, event)?.1)?.name);
+
+		for switch_group in get_wwev_switch_groups(game_files, hash_list, game_version, &references, None)? {
+			names.push(switch_group.name);
+			names.extend(switch_group.switches.into_iter().map(|(name, _)| name));
+		}
+
+		banks.extend(references.into_iter().filter(|x| is_type(x, "WBNK")));
+	}
+
+	let mut ids = IndexSet::new();
+
+	for bank in banks {
+		ids.extend(get_bank_name_hashes(&extract_latest_resource(game_files, bank)?.1));
+	}
+
+	let names = names
+		.into_iter()
+		.map(|name| (wwise_hash(&name), name))
+		.filter(|(id, _)| ids.contains(id))
+		.collect();
+
+	(ids, names)
+}
+
+/// Get the IDs of named Wwise objects used by a WWEV or SDEF resource which don't have a known name, along with the
+/// names of any which can be resolved from names in the resources themselves.
+#[try_fn]
+#[context("Couldn't gather audio IDs from {}", hash)]
+pub fn gather_audio_ids(
+	game_files: &PartitionManager,
+	hash_list: &HashList,
+	game_version: GameVersion,
+	local_hash_list: &LocalHashList,
+	hash: RuntimeID
+) -> Result<(Vec<u32>, Vec<(u32, String)>)> {
+	let (ids, names) = find_audio_ids(game_files, hash_list, game_version, hash)?;
+
+	(
+		ids.into_iter()
+			.filter(|x| !local_hash_list.wwise_names.contains_key(x) && !names.contains_key(x))
+			.collect(),
+		names
+			.into_iter()
+			.filter(|(id, _)| !local_hash_list.wwise_names.contains_key(id))
+			.collect()
+	)
+}
+
+/// Get the IDs of named Wwise objects used by a WWEV or SDEF resource, with their names if they're known.
+#[try_fn]
+#[context("Couldn't get audio names for {}", hash)]
+pub fn get_audio_names(
+	game_files: &PartitionManager,
+	hash_list: &HashList,
+	game_version: GameVersion,
+	local_hash_list: &LocalHashList,
+	hash: RuntimeID
+) -> Result<Vec<(u32, Option<String>)>> {
+	let (ids, names) = find_audio_ids(game_files, hash_list, game_version, hash)?;
+
+	ids.into_iter()
+		.map(|id| {
+			(
+				id,
+				local_hash_list.wwise_names.get(&id).or_else(|| names.get(&id)).cloned()
+			)
+		})
+		.collect()
+}
+
+/// Send the known audio names to the audio names tool.
+#[try_fn]
+#[context("Couldn't send audio names")]
+pub fn send_audio_names(app: &AppHandle) -> Result<()> {
+	send_request(
+		app,
+		Request::Tool(ToolRequest::AudioNames(AudioNamesRequest::SetNames(
+			app.state::<AppState>()
+				.local_hash_list
+				.load()
+				.wwise_names
+				.iter()
+				.map(|(id, name)| (*id, name.to_owned()))
+				.collect()
+		)))
+	)?;
+}
+
+/// Save the given audio names to the local hash list and update the audio names tool.
+#[try_fn]
+#[context("Couldn't store audio names")]
+pub fn store_audio_names(app: &AppHandle, names: IndexMap<u32, String>) -> Result<()> {
+	let local_hash_list = LocalHashList { wwise_names: names };
+
+	fs::write(
+		app.path()
+			.app_data_dir()
+			.context("Couldn't get app data dir")?
+			.join("local_hash_list.json"),
+		to_vec(&local_hash_list)?
+	)?;
+
+	app.state::<AppState>().local_hash_list.store(local_hash_list.into());
+
+	send_audio_names(app)?;
+}
@@ -0,0 +1,115 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	time::{SystemTime, UNIX_EPOCH}
+};
+
+use anyhow::{Context, Result};
+use fn_error_context::context;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::model::AppState;
+
+/// The folder, relative to the project, that previous versions of saved files are kept in.
+pub const BACKUP_FOLDER: &str = ".gkbackups";
+
+pub fn default_backup_count() -> u32 {
+	10
+}
+
+/// A previous version of a project file.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct FileBackup {
+	/// When the version was replaced, in seconds since the Unix epoch; identifies the backup
+	pub timestamp: u32,
+
+	/// In bytes
+	pub size: u32
+}
+
+/// The folder the backups of a project file are kept in, or None if the file isn't in the project.
+fn get_backup_folder(project_path: &Path, file: &Path) -> Option<PathBuf> {
+	let relative = file.strip_prefix(project_path).ok()?;
+
+	if relative.starts_with(BACKUP_FOLDER) {
+		return None;
+	}
+
+	Some(project_path.join(BACKUP_FOLDER).join(relative))
+}
+
+/// List the backups of a project file, newest first.
+#[try_fn]
+#[context("Couldn't list backups of {}", file.display())]
+pub fn list_backups(project_path: &Path, file: &Path) -> Result<Vec<FileBackup>> {
+	let Some(folder) = get_backup_folder(project_path, file).filter(|x| x.is_dir()) else {
+		return Ok(vec![]);
+	};
+
+	fs::read_dir(folder)?
+		.filter_map(|x| x.ok())
+		.filter_map(|x| {
+			Some(FileBackup {
+				timestamp: x.path().file_stem()?.to_str()?.parse().ok()?,
+				size: u32::try_from(x.metadata().ok()?.len()).unwrap_or(u32::MAX)
+			})
+		})
+		.sorted_by_key(|x| std::cmp::Reverse(x.timestamp))
+		.collect()
+}
+
+/// Copy a project file into the backup folder before it's overwritten, then remove its oldest backups beyond the
+/// given count. Files which don't exist yet or aren't in the project aren't backed up, and a count of 0 turns backups
+/// off. Saving twice in the same second keeps only the most recent of the two versions.
+#[try_fn]
+#[context("Couldn't back up {}", file.display())]
+pub fn back_up_file(project_path: &Path, file: &Path, count: u32) -> Result<()> {
+	if count == 0 || !file.is_file() {
+		return Ok(());
+	}
+
+	let Some(folder) = get_backup_folder(project_path, file) else {
+		return Ok(());
+	};
+
+	fs::create_dir_all(&folder)?;
+
+	let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+	fs::copy(file, folder.join(format!("{}.bak", timestamp)))?;
+
+	for backup in list_backups(project_path, file)?.into_iter().skip(count as usize) {
+		fs::remove_file(folder.join(format!("{}.bak", backup.timestamp)))?;
+	}
+}
+
+/// Back up a file in the open project before it's overwritten, according to the project's settings.
+#[try_fn]
+#[context("Couldn't back up {} before saving", file.display())]
+pub fn back_up_project_file(app: &AppHandle, file: &Path) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	if let Some(project) = app_state.project.load().as_ref() {
+		back_up_file(&project.path, file, project.settings.load().backup_count)?;
+	}
+}
+
+/// Replace a project file with one of its backups, backing up its current contents first so the restore can be undone.
+#[try_fn]
+#[context("Couldn't restore backup {} of {}", timestamp, file.display())]
+pub fn restore_backup(project_path: &Path, file: &Path, timestamp: u32, count: u32) -> Result<()> {
+	let backup = get_backup_folder(project_path, file)
+		.context("File isn't in the project")?
+		.join(format!("{}.bak", timestamp));
+
+	// Read first, as backing up the current contents may remove the oldest backup
+	let contents = fs::read(&backup).context("No such backup")?;
+
+	back_up_file(project_path, file, count)?;
+
+	fs::write(file, contents)?;
+}
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+
+/// Keys which give the type of a node in ResourceLib's JSON, in order of preference.
+const NODE_TYPE_KEYS: [&str; 4] = ["m_eNodeType", "m_eType", "m_nType", "type"];
+
+/// Keys which give a readable name for a node in ResourceLib's JSON, in order of preference.
+const NODE_NAME_KEYS: [&str; 3] = ["m_sName", "m_sEntityName", "name"];
+
+/// A node of a behaviour tree, built from the JSON ResourceLib converts the resource to.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct BehaviourTreeNode {
+	/// The key or index of the node in its parent, with its name if it has one
+	pub label: String,
+
+	/// The node's type if it has one, otherwise the kind of JSON value it is (Object, Array, String and so on)
+	pub kind: String,
+
+	/// The value of a leaf node
+	pub value: Option<String>,
+
+	pub children: Vec<BehaviourTreeNode>
+}
+
+impl BehaviourTreeNode {
+	/// Build a node (and its descendants) from a JSON value.
+	pub fn from_json(label: String, json: &Value) -> Self {
+		match json {
+			Value::Object(map) => {
+				let kind = NODE_TYPE_KEYS
+					.iter()
+					.find_map(|key| map.get(*key).and_then(|x| x.as_str()))
+					.or_else(|| {
+						map.iter()
+							.find(|(key, _)| key.ends_with("Type"))
+							.and_then(|(_, value)| value.as_str())
+					})
+					.unwrap_or("Object")
+					.to_owned();
+
+				let label = match NODE_NAME_KEYS
+					.iter()
+					.find_map(|key| map.get(*key).and_then(|x| x.as_str()))
+				{
+					Some(name) if !name.is_empty() => format!("{}: {}", label, name),
+					_ => label
+				};
+
+				Self {
+					label,
+					kind,
+					value: None,
+					children: map
+						.iter()
+						.map(|(key, value)| Self::from_json(key.to_owned(), value))
+						.collect()
+				}
+			}
+
+			Value::Array(values) => Self {
+				label,
+				kind: "Array".into(),
+				value: None,
+				children: values
+					.iter()
+					.enumerate()
+					.map(|(index, value)| Self::from_json(format!("[{}]", index), value))
+					.collect()
+			},
+
+			Value::String(value) => Self {
+				label,
+				kind: "String".into(),
+				value: Some(value.to_owned()),
+				children: vec![]
+			},
+
+			Value::Number(value) => Self {
+				label,
+				kind: "Number".into(),
+				value: Some(value.to_string()),
+				children: vec![]
+			},
+
+			Value::Bool(value) => Self {
+				label,
+				kind: "Boolean".into(),
+				value: Some(value.to_string()),
+				children: vec![]
+			},
+
+			Value::Null => Self {
+				label,
+				kind: "Null".into(),
+				value: Some("null".into()),
+				children: vec![]
+			}
+		}
+	}
+
+	/// Convert the tree to a Graphviz DOT graph. Leaf values are listed in their parent's label rather than given nodes
+	/// of their own, so that the graph shows only the structure of the tree.
+	pub fn to_dot(&self) -> String {
+		let mut dot = String::from("digraph BehaviourTree {\n\tnode [shape=box, fontname=\"Fira Code\"];\n");
+
+		self.write_dot(&mut dot, &mut 0);
+
+		dot.push_str("}\n");
+
+		dot
+	}
+
+	/// Write this node and its descendants to a DOT graph, returning the index of this node.
+	fn write_dot(&self, dot: &mut String, next_index: &mut usize) -> usize {
+		let index = *next_index;
+		*next_index += 1;
+
+		let mut label = format!("{}\\n({})", escape_dot(&self.label), escape_dot(&self.kind));
+
+		for child in &self.children {
+			if let Some(value) = child.value.as_ref() {
+				label.push_str(&format!("\\l{} = {}", escape_dot(&child.label), escape_dot(value)));
+			}
+		}
+
+		dot.push_str(&format!("\tn{} [label=\"{}\"];\n", index, label));
+
+		for child in self.children.iter().filter(|x| x.value.is_none()) {
+			let child_index = child.write_dot(dot, next_index);
+			dot.push_str(&format!("\tn{} -> n{};\n", index, child_index));
+		}
+
+		index
+	}
+}
+
+fn escape_dot(value: &str) -> String {
+	value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -0,0 +1,152 @@
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tryvial::try_fn;
+
+/// The size of a bone definition: centre, parent index, size, name and body part.
+const BONE_DEFINITION_SIZE: usize = 64;
+
+/// The size of a bind pose transform: a quaternion followed by a position padded to four floats.
+const BIND_POSE_TRANSFORM_SIZE: usize = 32;
+
+/// A bone of a BORG's skeleton in its bind pose.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct Bone {
+	pub name: String,
+
+	/// The index of the parent bone, or None for a root bone
+	pub parent: Option<u32>,
+
+	/// In model space
+	pub position: [f32; 3],
+
+	/// The bind pose relative to the parent bone; the rotation is a quaternion (x, y, z, w)
+	pub local_position: [f32; 3],
+	pub local_rotation: [f32; 4]
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Result<u32> {
+	Ok(u32::from_le_bytes(
+		data.get(pos..pos + 4)
+			.context("Unexpected end of BORG data")?
+			.try_into()?
+	))
+}
+
+fn read_f32(data: &[u8], pos: usize) -> Result<f32> {
+	Ok(f32::from_le_bytes(
+		data.get(pos..pos + 4)
+			.context("Unexpected end of BORG data")?
+			.try_into()?
+	))
+}
+
+/// Rotate a vector by a unit quaternion (x, y, z, w).
+fn rotate(quat: [f32; 4], vec: [f32; 3]) -> [f32; 3] {
+	let [qx, qy, qz, qw] = quat;
+	let [vx, vy, vz] = vec;
+
+	// t = 2 * cross(q.xyz, v)
+	let tx = 2.0 * (qy * vz - qz * vy);
+	let ty = 2.0 * (qz * vx - qx * vz);
+	let tz = 2.0 * (qx * vy - qy * vx);
+
+	// v + w * t + cross(q.xyz, t)
+	[
+		vx + qw * tx + (qy * tz - qz * ty),
+		vy + qw * ty + (qz * tx - qx * tz),
+		vz + qw * tz + (qx * ty - qy * tx)
+	]
+}
+
+fn multiply(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+	let [ax, ay, az, aw] = a;
+	let [bx, by, bz, bw] = b;
+
+	[
+		aw * bx + ax * bw + ay * bz - az * by,
+		aw * by - ax * bz + ay * bw + az * bx,
+		aw * bz + ax * by - ay * bx + az * bw,
+		aw * bw - ax * bx - ay * by - az * bz
+	]
+}
+
+/// Parse the bones of a BORG (bone rig), with their bind pose positions resolved to model space.
+#[try_fn]
+#[context("Couldn't parse BORG data")]
+pub fn parse_bone_rig(data: &[u8]) -> Result<Vec<Bone>> {
+	let header = read_u32(data, 0)? as usize;
+
+	let bone_count = read_u32(data, header)? as usize;
+	let bone_definitions = read_u32(data, header + 8)? as usize;
+	let bind_pose = read_u32(data, header + 12)? as usize;
+
+	if bone_count == 0
+		|| bone_definitions + bone_count * BONE_DEFINITION_SIZE > data.len()
+		|| bind_pose + bone_count * BIND_POSE_TRANSFORM_SIZE > data.len()
+	{
+		bail!("Invalid BORG header");
+	}
+
+	let mut bones: Vec<Bone> = vec![];
+	let mut rotations: Vec<[f32; 4]> = vec![];
+
+	for index in 0..bone_count {
+		let definition = bone_definitions + index * BONE_DEFINITION_SIZE;
+
+		let parent = i32::from_le_bytes(data[definition + 12..definition + 16].try_into()?);
+
+		let name = &data[definition + 28..definition + 62];
+		let name =
+			String::from_utf8_lossy(&name[..name.iter().position(|&x| x == 0).unwrap_or(name.len())]).into_owned();
+
+		let transform = bind_pose + index * BIND_POSE_TRANSFORM_SIZE;
+
+		let rotation = [
+			read_f32(data, transform)?,
+			read_f32(data, transform + 4)?,
+			read_f32(data, transform + 8)?,
+			read_f32(data, transform + 12)?
+		];
+
+		let position = [
+			read_f32(data, transform + 16)?,
+			read_f32(data, transform + 20)?,
+			read_f32(data, transform + 24)?
+		];
+
+		// Bind pose transforms are relative to the parent, which always comes before its children
+		let parent = usize::try_from(parent).ok().filter(|&x| x < index);
+
+		let (local_rotation, local_position) = (rotation, position);
+
+		let (rotation, position) = if let Some(parent) = parent {
+			let offset = rotate(rotations[parent], position);
+			let parent_position = bones[parent].position;
+
+			(
+				multiply(rotations[parent], rotation),
+				[
+					parent_position[0] + offset[0],
+					parent_position[1] + offset[1],
+					parent_position[2] + offset[2]
+				]
+			)
+		} else {
+			(rotation, position)
+		};
+
+		rotations.push(rotation);
+
+		bones.push(Bone {
+			name,
+			parent: parent.map(|x| x as u32),
+			position,
+			local_position,
+			local_rotation
+		});
+	}
+
+	bones
+}
@@ -0,0 +1,144 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use fn_error_context::context;
+use hitman_commons::metadata::RuntimeID;
+use quickentity_rs::qn_structs::Entity;
+use rpkg_rs::resource::{partition_manager::PartitionManager, runtime_resource_id::RuntimeResourceID};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, from_slice, json, to_string};
+use specta::Type;
+use tryvial::try_fn;
+
+use crate::biome::format_json;
+
+/// The partition a new entity should be loaded in, worked out from the partition of the scenario it's used in.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct ChunkAssignment {
+	/// The entity's template as written in packagedefinition.txt, like `[assembly:/_pro/my_mod.entitytemplate]`
+	pub entity_path: String,
+
+	/// The partition as named in packagedefinition.txt, like `season2`
+	pub partition: String,
+
+	/// The chunk the partition's resources are stored in
+	pub chunk: u32,
+
+	/// Whether the manifest already loads the entity in this partition
+	pub already_assigned: bool
+}
+
+/// Get the path of the template an entity file defines, from its factory or from the project's custom paths if the
+/// factory is only a hash.
+fn get_entity_template_path(entity: &Entity, custom_paths: &[String]) -> Option<String> {
+	let factory_path = if entity.factory_hash.starts_with('[') {
+		entity.factory_hash.to_owned()
+	} else {
+		let factory = RuntimeID::from_any(&entity.factory_hash).ok()?;
+
+		custom_paths
+			.iter()
+			.find(|x| RuntimeID::from_any(x).is_ok_and(|x| x == factory))?
+			.to_owned()
+	};
+
+	factory_path.strip_suffix(".pc_entitytype").map(|x| x.to_owned())
+}
+
+fn get_packagedefinition(manifest: &Value) -> impl Iterator<Item = &Value> {
+	manifest
+		.get("packagedefinition")
+		.and_then(|x| x.as_array())
+		.into_iter()
+		.flatten()
+}
+
+/// Suggest the partition an entity file in the project should be loaded in, so that it's streamed along with the
+/// scenario it's used in rather than being loaded everywhere.
+#[try_fn]
+#[context("Couldn't suggest a partition for {}", entity_file.display())]
+pub fn suggest_chunk_assignment(
+	game_files: &PartitionManager,
+	project_path: &Path,
+	custom_paths: &[String],
+	entity_file: &Path,
+	scenario: &str
+) -> Result<ChunkAssignment> {
+	let entity: Entity = from_slice(&fs::read(entity_file)?).context("Invalid entity")?;
+
+	let entity_path = get_entity_template_path(&entity, custom_paths)
+		.context("The entity's factory has no path; give it one in the project's custom paths first")?;
+
+	let scenario =
+		RuntimeResourceID::from(RuntimeID::from_any(scenario.trim()).context("Invalid scenario hash or path")?);
+
+	let (index, partition) = game_files
+		.partitions
+		.iter()
+		.enumerate()
+		.find(|(_, x)| x.contains(&scenario))
+		.context("The scenario isn't in any partition of the game files")?;
+
+	let partition_id = partition.partition_info().id.to_string();
+
+	let partition_name = partition
+		.partition_info()
+		.name
+		.to_owned()
+		.unwrap_or_else(|| partition_id.to_owned());
+
+	// Partitions are identified like chunk3, but older games' partitions may not be numbered
+	let chunk = partition_id
+		.trim_start_matches(|x: char| !x.is_ascii_digit())
+		.parse()
+		.unwrap_or(index as u32);
+
+	let manifest: Value =
+		from_slice(&fs::read(project_path.join("manifest.json")).context("Couldn't read manifest.json")?)
+			.context("Invalid manifest")?;
+
+	let already_assigned = get_packagedefinition(&manifest).any(|x| {
+		x.get("type").and_then(|x| x.as_str()) == Some("entity")
+			&& x.get("path").and_then(|x| x.as_str()) == Some(entity_path.as_str())
+			&& x.get("partition").and_then(|x| x.as_str()) == Some(partition_name.as_str())
+	});
+
+	ChunkAssignment {
+		entity_path,
+		partition: partition_name,
+		chunk,
+		already_assigned
+	}
+}
+
+/// Record a partition assignment in the manifest's packagedefinition changes, replacing any previous assignment of the
+/// same entity.
+#[try_fn]
+#[context("Couldn't record partition assignment for {}", assignment.entity_path)]
+pub fn record_chunk_assignment(project_path: &Path, assignment: &ChunkAssignment) -> Result<()> {
+	let manifest_path = project_path.join("manifest.json");
+
+	let mut manifest: Value =
+		from_slice(&fs::read(&manifest_path).context("Couldn't read manifest.json")?).context("Invalid manifest")?;
+
+	let packagedefinition = manifest
+		.as_object_mut()
+		.context("Manifest is not an object")?
+		.entry("packagedefinition")
+		.or_insert_with(|| Value::Array(vec![]))
+		.as_array_mut()
+		.context("packagedefinition is not an array")?;
+
+	packagedefinition.retain(|x| {
+		x.get("type").and_then(|x| x.as_str()) != Some("entity")
+			|| x.get("path").and_then(|x| x.as_str()) != Some(assignment.entity_path.as_str())
+	});
+
+	packagedefinition.push(json!({
+		"type": "entity",
+		"partition": assignment.partition,
+		"path": assignment.entity_path
+	}));
+
+	fs::write(&manifest_path, format_json(&to_string(&manifest)?)?)?;
+}
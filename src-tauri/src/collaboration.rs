@@ -0,0 +1,767 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Error, Result, anyhow, bail};
+use arc_swap::{ArcSwap, ArcSwapOption};
+use dashmap::DashMap;
+use fn_error_context::context;
+use futures_util::{SinkExt, StreamExt};
+use hashbrown::{HashMap, HashSet};
+use indexmap::IndexMap;
+use log::warn;
+use parking_lot::Mutex;
+use quickentity_rs::{
+	apply_patch, generate_patch,
+	patch_structs::{Patch, PatchOperation},
+	qn_structs::{Entity, Ref}
+};
+use rand::{Rng, rng};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{
+	AppHandle, Manager,
+	async_runtime::{JoinHandle, spawn}
+};
+use tokio::{
+	io::{AsyncRead, AsyncWrite},
+	net::TcpListener,
+	sync::mpsc,
+	time::{interval, timeout}
+};
+use tokio_tungstenite::{WebSocketStream, accept_async, connect_async, tungstenite::Message};
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::{
+	Notification, NotificationKind,
+	entity::{get_diff_info, get_operation_sub_entity},
+	event_handling::entity::tree,
+	model::{
+		AppState, EditorData, EditorRequest, EntityEditorRequest, EntityMonacoRequest, EntityTreeRequest,
+		GlobalRequest, Request, SettingsRequest, ToolRequest
+	},
+	send_notification, send_request,
+	telemetry::is_offline
+};
+
+/// How often edited entities are checked for changes to send to the other instance.
+const SYNC_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long to wait for the other instance to say hello after connecting.
+const HELLO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The state of the connection to another GlacierKit instance for co-editing entities.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "type", content = "data")]
+pub enum CollaborationStatus {
+	Disconnected,
+
+	/// Waiting for another instance to connect on the given port
+	Hosting(u16),
+
+	/// Connecting to the given address
+	Connecting(String),
+
+	/// Connected to another instance, whose user has the given name
+	Connected(String)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "type", content = "data")]
+enum CollaborationMessage {
+	/// Sent first by the connecting instance, and by the host only once it has checked the pairing code, so that the
+	/// code isn't given away to anything that connects
+	Hello { name: String, secret: String },
+
+	/// Changes made to an entity, which is identified by its factory hash as both instances may have it at different
+	/// paths
+	Changes { factory_hash: String, changes: Vec<Change> }
+}
+
+/// Patch operations made to one sub-entity at the same time, or to the entity itself if there's no sub-entity.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct Change {
+	sub_entity: Option<String>,
+
+	/// Milliseconds since the Unix epoch and the ID of the instance the change was made on; of two conflicting changes,
+	/// the one with the greater version wins
+	version: (u64, u64),
+
+	operations: Vec<PatchOperation>
+}
+
+struct SyncState {
+	/// The entity as of the last changes sent or received, which the current entity is compared to
+	synced: Box<Entity>,
+
+	/// The version of the last change to each sub-entity (or the entity itself, for None)
+	versions: HashMap<Option<String>, (u64, u64)>
+}
+
+struct Session {
+	outgoing: mpsc::UnboundedSender<CollaborationMessage>,
+
+	/// Keyed by editor ID
+	editors: DashMap<Uuid, SyncState>,
+
+	/// Editors which have been edited since they were last compared to their synced state
+	changed: Mutex<HashSet<Uuid>>
+}
+
+/// An opt-in connection to another GlacierKit instance over a WebSocket, keeping the entities open in both in sync.
+/// Conflicting changes are resolved per sub-entity, with the last change winning.
+pub struct Collaboration {
+	/// Random ID of this instance, which breaks ties between changes made at the same moment
+	instance_id: u64,
+
+	status: ArcSwap<CollaborationStatus>,
+	session: ArcSwapOption<Session>,
+	tasks: Mutex<Vec<JoinHandle<()>>>
+}
+
+impl Default for Collaboration {
+	fn default() -> Self {
+		Self {
+			instance_id: rng().random(),
+			status: ArcSwap::new(CollaborationStatus::Disconnected.into()),
+			session: ArcSwapOption::empty(),
+			tasks: Mutex::new(vec![])
+		}
+	}
+}
+
+impl Collaboration {
+	pub fn status(&self) -> CollaborationStatus {
+		(**self.status.load()).to_owned()
+	}
+
+	/// Mark an editor as edited, so that its entity is checked for changes to send on the next sync.
+	pub fn mark_changed(&self, editor_id: Uuid) {
+		if let Some(session) = self.session.load().as_ref() {
+			session.changed.lock().insert(editor_id);
+		}
+	}
+}
+
+fn now() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|x| x.as_millis() as u64)
+		.unwrap_or_default()
+}
+
+/// The parts of a sub-entity shown in the tree, which need the tree to be rebuilt if they change.
+fn get_tree_info(entity: &Entity, id: &str) -> Option<(Ref, String, String)> {
+	entity
+		.entities
+		.get(id)
+		.map(|x| (x.parent.to_owned(), x.name.to_owned(), x.factory.to_owned()))
+}
+
+#[try_fn]
+#[context("Couldn't send collaboration status")]
+fn set_status(app: &AppHandle, status: CollaborationStatus) -> Result<()> {
+	app.state::<AppState>()
+		.collaboration
+		.status
+		.store(status.to_owned().into());
+
+	send_request(
+		app,
+		Request::Tool(ToolRequest::Settings(SettingsRequest::SetCollaborationStatus(status)))
+	)?;
+}
+
+/// Disconnect from the other instance or stop waiting for one.
+#[try_fn]
+#[context("Couldn't stop collaboration")]
+pub fn stop_collaboration(app: &AppHandle) -> Result<()> {
+	let collaboration = &app.state::<AppState>().collaboration;
+
+	collaboration.session.store(None);
+
+	for task in collaboration.tasks.lock().drain(..) {
+		task.abort();
+	}
+
+	set_status(app, CollaborationStatus::Disconnected)?;
+}
+
+/// Tell the user that collaboration can't be used, returning true, if offline mode is enabled.
+#[try_fn]
+#[context("Couldn't check offline mode for collaboration")]
+fn refuse_if_offline(app: &AppHandle) -> Result<bool> {
+	if is_offline() {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Info,
+				title: "Collaboration unavailable".into(),
+				subtitle: "Collaboration can't be used while offline mode is enabled.".into(),
+				link: None
+			}
+		)?;
+
+		return Ok(true);
+	}
+
+	false
+}
+
+/// Wait for another instance with the same pairing code to connect, listening on the given interface address (or
+/// `0.0.0.0` for all of them) and port. Connections which don't give the right pairing code are closed.
+#[try_fn]
+#[context("Couldn't host collaboration")]
+pub fn host_collaboration(app: &AppHandle, name: String, secret: String, interface: String, port: u16) -> Result<()> {
+	if refuse_if_offline(app)? {
+		return Ok(());
+	}
+
+	if secret.is_empty() {
+		bail!("A pairing code is required to host collaboration");
+	}
+
+	stop_collaboration(app)?;
+
+	set_status(app, CollaborationStatus::Hosting(port))?;
+
+	let app = app.to_owned();
+
+	let task = spawn(async move {
+		if let Err::<_, Error>(e) = try {
+			let listener = TcpListener::bind((interface.as_str(), port))
+				.await
+				.with_context(|| format!("Couldn't listen on {} port {}", interface, port))?;
+
+			loop {
+				let (stream, peer) = listener.accept().await?;
+
+				let Ok(mut ws) = accept_async(stream).await else {
+					continue;
+				};
+
+				if let Some(other_name) = exchange_hellos(&mut ws, &name, &secret, true).await {
+					run_session(&app, other_name, ws).await?;
+					break;
+				}
+
+				let _ = ws.close(None).await;
+
+				send_notification(
+					&app,
+					Notification {
+						kind: NotificationKind::Warning,
+						title: "Collaboration connection rejected".into(),
+						subtitle: format!(
+							"{} didn't give the right pairing code. Still waiting for another instance to connect.",
+							peer.ip()
+						),
+						link: None
+					}
+				)?;
+			}
+		} {
+			handle_session_error(&app, e);
+		}
+	});
+
+	app.state::<AppState>().collaboration.tasks.lock().push(task);
+}
+
+/// Connect to another instance which is hosting, at an address like `192.168.1.2:46735`, using the pairing code it was
+/// given.
+#[try_fn]
+#[context("Couldn't connect to collaboration host")]
+pub fn connect_collaboration(app: &AppHandle, name: String, secret: String, address: String) -> Result<()> {
+	if refuse_if_offline(app)? {
+		return Ok(());
+	}
+
+	if secret.is_empty() {
+		bail!("A pairing code is required to connect to another instance");
+	}
+
+	stop_collaboration(app)?;
+
+	set_status(app, CollaborationStatus::Connecting(address.to_owned()))?;
+
+	let app = app.to_owned();
+
+	let task = spawn(async move {
+		if let Err::<_, Error>(e) = try {
+			let (mut ws, _) = connect_async(format!("ws://{}", address))
+				.await
+				.with_context(|| format!("Couldn't connect to {}", address))?;
+
+			let other_name = exchange_hellos(&mut ws, &name, &secret, false)
+				.await
+				.context("The host didn't accept the pairing code")?;
+
+			run_session(&app, other_name, ws).await?;
+		} {
+			handle_session_error(&app, e);
+		}
+	});
+
+	app.state::<AppState>().collaboration.tasks.lock().push(task);
+}
+
+fn handle_session_error(app: &AppHandle, error: Error) {
+	let _ = send_notification(
+		app,
+		Notification {
+			kind: NotificationKind::Error,
+			title: "Collaboration stopped".into(),
+			subtitle: format!("{:#}", error),
+			link: None
+		}
+	);
+
+	let _ = stop_collaboration(app);
+}
+
+/// Say hello to the other instance and wait for it to do the same, returning the name of its user, or None if it
+/// didn't say hello in time or gave a different pairing code. The connecting instance says hello first.
+async fn exchange_hellos<S: AsyncRead + AsyncWrite + Unpin>(
+	ws: &mut WebSocketStream<S>,
+	name: &str,
+	secret: &str,
+	is_host: bool
+) -> Option<String> {
+	let hello = Message::Text(
+		serde_json::to_string(&CollaborationMessage::Hello {
+			name: name.to_owned(),
+			secret: secret.to_owned()
+		})
+		.ok()?
+		.into()
+	);
+
+	if !is_host {
+		ws.send(hello.to_owned()).await.ok()?;
+	}
+
+	let Ok(Some(Ok(Message::Text(text)))) = timeout(HELLO_TIMEOUT, ws.next()).await else {
+		return None;
+	};
+
+	let Ok(CollaborationMessage::Hello {
+		name: other_name,
+		secret: other_secret
+	}) = serde_json::from_str(text.as_str())
+	else {
+		return None;
+	};
+
+	if other_secret != secret {
+		return None;
+	}
+
+	if is_host {
+		ws.send(hello).await.ok()?;
+	}
+
+	Some(other_name)
+}
+
+/// Exchange changes with the other instance, whose user has the given name, until either side disconnects.
+#[try_fn]
+#[context("Collaboration session failed")]
+async fn run_session<S: AsyncRead + AsyncWrite + Unpin + Send + 'static>(
+	app: &AppHandle,
+	other_name: String,
+	ws: WebSocketStream<S>
+) -> Result<()> {
+	let app_state = app.state::<AppState>();
+	let collaboration = &app_state.collaboration;
+
+	let (mut write, mut read) = ws.split();
+	let (outgoing, mut outgoing_rx) = mpsc::unbounded_channel::<CollaborationMessage>();
+
+	// Only changes made from now on are synced, so both sides should open the entities in the same state
+	let editors = DashMap::new();
+
+	for editor in app_state.editor_states.iter() {
+		if let EditorData::QNEntity { ref entity, .. }
+		| EditorData::QNPatch {
+			current: ref entity, ..
+		} = editor.data
+		{
+			editors.insert(
+				editor.key().to_owned(),
+				SyncState {
+					synced: entity.to_owned(),
+					versions: HashMap::new()
+				}
+			);
+		}
+	}
+
+	collaboration.session.store(Some(
+		Session {
+			outgoing,
+			editors,
+			changed: Mutex::new(HashSet::new())
+		}
+		.into()
+	));
+
+	set_status(app, CollaborationStatus::Connected(other_name.to_owned()))?;
+
+	send_notification(
+		app,
+		Notification {
+			kind: NotificationKind::Info,
+			title: format!("Collaborating with {}", other_name),
+			subtitle: "Changes to entities you both have open will be synced.".into(),
+			link: None
+		}
+	)?;
+
+	let writer = spawn(async move {
+		while let Some(message) = outgoing_rx.recv().await {
+			let Ok(message) = serde_json::to_string(&message) else {
+				continue;
+			};
+
+			if write.send(Message::Text(message.into())).await.is_err() {
+				break;
+			}
+		}
+	});
+
+	let sync_app = app.to_owned();
+	let syncer = spawn(async move {
+		let mut interval = interval(SYNC_INTERVAL);
+
+		loop {
+			interval.tick().await;
+
+			if let Err(e) = send_local_changes(&sync_app) {
+				handle_session_error(&sync_app, e);
+				break;
+			}
+		}
+	});
+
+	collaboration.tasks.lock().extend([writer, syncer]);
+
+	while let Some(message) = read.next().await {
+		match message? {
+			Message::Close(_) => break,
+
+			Message::Text(text) => {
+				let message: CollaborationMessage =
+					serde_json::from_str(text.as_str()).context("Invalid collaboration message")?;
+
+				if let CollaborationMessage::Changes { factory_hash, changes } = message {
+					apply_remote_changes(app, &other_name, &factory_hash, changes).await?;
+				}
+			}
+
+			_ => {}
+		}
+	}
+
+	send_notification(
+		app,
+		Notification {
+			kind: NotificationKind::Info,
+			title: "Collaboration ended".into(),
+			subtitle: "The other instance disconnected.".into(),
+			link: None
+		}
+	)?;
+
+	stop_collaboration(app)?;
+}
+
+/// Compare each entity which has been edited since the last sync to its synced state and send any changes to the other
+/// instance.
+#[try_fn]
+#[context("Couldn't send local changes")]
+fn send_local_changes(app: &AppHandle) -> Result<()> {
+	let app_state = app.state::<AppState>();
+	let collaboration = &app_state.collaboration;
+
+	let Some(session) = collaboration.session.load_full() else {
+		return Ok(());
+	};
+
+	// Editor states are always locked before sync states, so the open editors are collected first
+	let open_editors = app_state
+		.editor_states
+		.iter()
+		.map(|x| x.key().to_owned())
+		.collect::<HashSet<_>>();
+	session.editors.retain(|x, _| open_editors.contains(x));
+
+	// Newly opened editors start from their current state
+	for editor in app_state.editor_states.iter() {
+		if let EditorData::QNEntity { ref entity, .. }
+		| EditorData::QNPatch {
+			current: ref entity, ..
+		} = editor.data
+			&& !session.editors.contains_key(editor.key())
+		{
+			session.editors.insert(
+				editor.key().to_owned(),
+				SyncState {
+					synced: entity.to_owned(),
+					versions: HashMap::new()
+				}
+			);
+		}
+	}
+
+	let changed = std::mem::take(&mut *session.changed.lock());
+
+	for editor_id in changed {
+		let Some(editor) = app_state.editor_states.get(&editor_id) else {
+			continue;
+		};
+
+		let (EditorData::QNEntity { ref entity, .. }
+		| EditorData::QNPatch {
+			current: ref entity, ..
+		}) = editor.data
+		else {
+			continue;
+		};
+
+		let Some(mut state) = session.editors.get_mut(&editor_id) else {
+			continue;
+		};
+
+		let patch = generate_patch(&state.synced, entity).map_err(|x| anyhow!(x))?;
+
+		if patch.patch.is_empty() {
+			continue;
+		}
+
+		let version = (now(), collaboration.instance_id);
+
+		let mut operations: IndexMap<Option<String>, Vec<PatchOperation>> = IndexMap::new();
+
+		for operation in patch.patch {
+			operations
+				.entry(get_operation_sub_entity(&operation))
+				.or_default()
+				.push(operation);
+		}
+
+		let changes = operations
+			.into_iter()
+			.map(|(sub_entity, operations)| {
+				state.versions.insert(sub_entity.to_owned(), version);
+
+				Change {
+					sub_entity,
+					version,
+					operations
+				}
+			})
+			.collect();
+
+		state.synced = entity.to_owned();
+
+		session.outgoing.send(CollaborationMessage::Changes {
+			factory_hash: entity.factory_hash.to_owned(),
+			changes
+		})?;
+	}
+}
+
+/// Apply changes from the other instance to the open editors for the entity, skipping any to sub-entities which have
+/// been changed more recently here. Changes which can't be applied are skipped and reported.
+#[try_fn]
+#[context("Couldn't apply changes from other instance")]
+async fn apply_remote_changes(
+	app: &AppHandle,
+	other_name: &str,
+	factory_hash: &str,
+	changes: Vec<Change>
+) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let Some(session) = app_state.collaboration.session.load_full() else {
+		return Ok(());
+	};
+
+	// Changes made here since the last sync have to be sent first, so that they aren't mistaken for the other
+	// instance's changes once those are applied to the synced state
+	send_local_changes(app)?;
+
+	let editor_ids = app_state
+		.editor_states
+		.iter()
+		.filter(|x| match x.data {
+			EditorData::QNEntity { ref entity, .. }
+			| EditorData::QNPatch {
+				current: ref entity, ..
+			} => entity.factory_hash == factory_hash,
+
+			_ => false
+		})
+		.map(|x| x.key().to_owned())
+		.collect::<Vec<_>>();
+
+	let mut failed_changes = 0;
+
+	for editor_id in editor_ids {
+		let mut changed_sub_entities = vec![];
+		let mut tree_changed = false;
+
+		{
+			let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref mut entity, .. } => entity,
+				EditorData::QNPatch { ref mut current, .. } => current,
+				_ => continue
+			};
+
+			let mut state = session.editors.entry(editor_id).or_insert_with(|| SyncState {
+				synced: entity.to_owned(),
+				versions: HashMap::new()
+			});
+
+			for change in &changes {
+				if state
+					.versions
+					.get(&change.sub_entity)
+					.is_some_and(|x| *x > change.version)
+				{
+					continue;
+				}
+
+				let tree_info = change.sub_entity.as_ref().and_then(|x| get_tree_info(entity, x));
+
+				// Applied to copies first so that the entity and its synced state are only updated if both succeed
+				let patched = [&**entity, &*state.synced].map(|target| {
+					let mut target = target.to_owned();
+
+					apply_patch(
+						&mut target,
+						Patch {
+							factory_hash: String::new(),
+							blueprint_hash: String::new(),
+							patch: change.operations.to_owned(),
+							patch_version: 6
+						},
+						true
+					)
+					.map(|_| target)
+					.map_err(|x| anyhow!(x))
+				});
+
+				let (patched_entity, patched_synced) = match patched {
+					[Ok(patched_entity), Ok(patched_synced)] => (patched_entity, patched_synced),
+
+					[Err(e), _] | [_, Err(e)] => {
+						warn!(
+							"Skipping change from other instance to {:?}: {:?}",
+							change.sub_entity, e
+						);
+						failed_changes += 1;
+						continue;
+					}
+				};
+
+				**entity = patched_entity;
+				state.synced = Box::new(patched_synced);
+
+				state.versions.insert(change.sub_entity.to_owned(), change.version);
+
+				if let Some(sub_entity) = change.sub_entity.as_ref() {
+					tree_changed |= get_tree_info(entity, sub_entity) != tree_info;
+					changed_sub_entities.push(sub_entity.to_owned());
+				} else {
+					tree_changed = true;
+				}
+			}
+
+			if changed_sub_entities.is_empty() && !tree_changed {
+				continue;
+			}
+
+			send_request(
+				app,
+				Request::Global(GlobalRequest::SetTabUnsaved {
+					id: editor_id,
+					unsaved: true
+				})
+			)?;
+
+			for sub_entity in &changed_sub_entities {
+				if let Some(data) = entity.entities.get(sub_entity) {
+					let mut buf = Vec::new();
+					let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+					let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+					data.serialize(&mut ser)?;
+
+					send_request(
+						app,
+						Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
+							EntityMonacoRequest::ReplaceContentIfSameEntityID {
+								editor_id,
+								entity_id: sub_entity.to_owned(),
+								content: String::from_utf8(buf)?
+							}
+						)))
+					)?;
+				} else {
+					send_request(
+						app,
+						Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
+							EntityMonacoRequest::DeselectIfSelected {
+								editor_id,
+								entity_ids: vec![sub_entity.to_owned()]
+							}
+						)))
+					)?;
+				}
+			}
+
+			if let EditorData::QNPatch {
+				ref base, ref current, ..
+			} = editor_state.data
+			{
+				send_request(
+					app,
+					Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+						EntityTreeRequest::SetDiffInfo {
+							editor_id,
+							diff_info: get_diff_info(base, current)
+						}
+					)))
+				)?;
+			}
+		}
+
+		if tree_changed {
+			tree::initialise(app, editor_id).await?;
+		}
+	}
+
+	if failed_changes > 0 {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Warning,
+				title: format!("Some of {}'s changes couldn't be applied", other_name),
+				subtitle: format!(
+					"{} change{} to {} couldn't be applied here and {} skipped. Reopen the entity on both sides to \
+					 get back in sync.",
+					failed_changes,
+					if failed_changes == 1 { "" } else { "s" },
+					factory_hash,
+					if failed_changes == 1 { "was" } else { "were" }
+				),
+				link: None
+			}
+		)?;
+	}
+}
@@ -0,0 +1,89 @@
+use std::fs;
+
+use anyhow::{Context, Result};
+use fn_error_context::context;
+use hitman_commons::{hash_list::HashList, metadata::RuntimeID};
+use indexmap::IndexMap;
+use rpkg_rs::resource::{partition_manager::PartitionManager, runtime_resource_id::RuntimeResourceID};
+use serde_json::to_vec;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::{
+	model::{AppState, GameBrowserEntry, GameBrowserRequest, Request, ResourceCollection, ToolRequest},
+	rpkg::get_partition_dlc,
+	send_request
+};
+
+/// Named collections of resources, stored in the app data folder.
+pub type Collections = IndexMap<String, Vec<RuntimeID>>;
+
+/// Get the game browser entry for a resource, if it is in the hash list and the game files.
+pub fn get_game_browser_entry(
+	game_files: &PartitionManager,
+	hash_list: &HashList,
+	hash: RuntimeID
+) -> Option<GameBrowserEntry> {
+	let entry = hash_list.entries.get(&hash)?;
+
+	let rrid = RuntimeResourceID::from(hash);
+	let partition = game_files.partitions.iter().find(|x| x.contains(&rrid))?;
+
+	Some(GameBrowserEntry {
+		hash,
+		path: entry.path.to_owned(),
+		hint: entry.hint.to_owned(),
+		filetype: entry.resource_type,
+		partition: (
+			partition.partition_info().id.to_string(),
+			partition.partition_info().name.to_owned().unwrap_or("<unnamed>".into())
+		),
+		dlc: get_partition_dlc(partition)
+	})
+}
+
+/// Send the current collections to the game browser.
+#[try_fn]
+#[context("Couldn't send collections")]
+pub fn send_collections(app: &AppHandle) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	if let Some(game_files) = app_state.game_files.load().as_ref()
+		&& let Some(hash_list) = app_state.hash_list.load().as_ref()
+	{
+		send_request(
+			app,
+			Request::Tool(ToolRequest::GameBrowser(GameBrowserRequest::SetCollections(
+				app_state
+					.collections
+					.load()
+					.iter()
+					.map(|(name, hashes)| ResourceCollection {
+						name: name.to_owned(),
+						entries: hashes
+							.iter()
+							.filter_map(|hash| get_game_browser_entry(game_files, hash_list, *hash))
+							.collect()
+					})
+					.collect()
+			)))
+		)?;
+	}
+}
+
+/// Save the given collections to the app data folder and update the game browser.
+#[try_fn]
+#[context("Couldn't store collections")]
+pub fn store_collections(app: &AppHandle, collections: Collections) -> Result<()> {
+	fs::write(
+		app.path()
+			.app_data_dir()
+			.context("Couldn't get app data dir")?
+			.join("collections.json"),
+		to_vec(&collections)?
+	)?;
+
+	app.state::<AppState>().collections.store(collections.into());
+
+	send_collections(app)?;
+}
@@ -0,0 +1,265 @@
+use anyhow::Result;
+use arc_swap::ArcSwap;
+use fn_error_context::context;
+use hashbrown::HashMap;
+use hitman_commons::{game::GameVersion, hash_list::HashList, metadata::RuntimeID};
+use itertools::Itertools;
+use quickentity_rs::qn_structs::{Entity, RefMaybeConstantValue, RefWithConstantValue, SubEntity};
+use rpkg_rs::resource::partition_manager::PartitionManager;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::{
+	Notification, NotificationKind,
+	entity::get_local_reference,
+	entity_cache::EntityCache,
+	get_loaded_game_version,
+	intellisense::Intellisense,
+	model::{AppSettings, AppState, EntityProblem},
+	send_notification
+};
+
+/// How many issues are listed in the notification shown when saving; the rest are only counted.
+const MAX_LISTED_ISSUES: usize = 5;
+
+/// The local sub-entity a pin connection targets, if it targets one.
+fn get_connection_target(reference: &RefMaybeConstantValue) -> Option<String> {
+	get_local_reference(match reference {
+		RefMaybeConstantValue::Ref(r) => r,
+		RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => entity_ref
+	})
+}
+
+/// Check an entity against the loaded game files for factories, blueprints, properties and pins which don't exist in
+/// the loaded game version, like when an entity made for HITMAN 3 is used with an older game. Factories and blueprints
+/// are only flagged if they're known game resources, so custom resources in the project aren't; properties and pins are
+/// only checked for sub-entities whose factory is in the game files.
+#[try_fn]
+#[context("Couldn't check entity {} for compatibility", entity.factory_hash)]
+pub fn find_compatibility_issues(
+	intellisense: &Intellisense,
+	game_files: &PartitionManager,
+	cached_entities: &EntityCache,
+	hash_list: &HashList,
+	game_version: GameVersion,
+	entity: &Entity
+) -> Result<Vec<EntityProblem>> {
+	let is_missing = |resource: &str| {
+		RuntimeID::from_any(resource)
+			.is_ok_and(|id| hash_list.entries.contains_key(&id) && !intellisense.file_types.contains_key(&id))
+	};
+
+	let is_in_game =
+		|resource: &str| RuntimeID::from_any(resource).is_ok_and(|id| intellisense.file_types.contains_key(&id));
+
+	// The pins given by each sub-entity's factory, without those which only exist because of connections in this entity
+	let unconnected = Entity {
+		entities: entity
+			.entities
+			.iter()
+			.map(|(entity_id, sub_entity)| {
+				(
+					entity_id.to_owned(),
+					SubEntity {
+						events: None,
+						input_copying: None,
+						output_copying: None,
+						..sub_entity.to_owned()
+					}
+				)
+			})
+			.collect(),
+		..entity.to_owned()
+	};
+
+	let mut factory_pins: HashMap<String, Option<(Vec<String>, Vec<String>)>> = HashMap::new();
+
+	let mut get_factory_pins = |entity_id: &str| -> Option<(Vec<String>, Vec<String>)> {
+		factory_pins
+			.entry_ref(entity_id)
+			.or_insert_with(|| {
+				is_in_game(&unconnected.entities.get(entity_id)?.factory)
+					.then(|| {
+						intellisense
+							.get_pins(
+								game_files,
+								cached_entities,
+								hash_list,
+								game_version,
+								&unconnected,
+								entity_id,
+								true
+							)
+							.ok()
+					})
+					.flatten()
+					.filter(|(input, output)| !input.is_empty() || !output.is_empty())
+			})
+			.to_owned()
+	};
+
+	let mut problems = vec![];
+
+	for (entity_id, sub_entity) in entity.entities.iter().sorted_by_key(|(id, _)| *id) {
+		let mut push = |message: String| {
+			problems.push(EntityProblem {
+				entity_id: entity_id.to_owned(),
+				name: sub_entity.name.to_owned(),
+				message
+			})
+		};
+
+		if is_missing(&sub_entity.factory) {
+			push(format!("The factory {} doesn't exist in this game", sub_entity.factory));
+		}
+
+		if is_missing(&sub_entity.blueprint) {
+			push(format!(
+				"The blueprint {} doesn't exist in this game",
+				sub_entity.blueprint
+			));
+		}
+
+		if !is_in_game(&sub_entity.factory) {
+			continue;
+		}
+
+		if let Ok(properties) = intellisense.get_properties(
+			game_files,
+			cached_entities,
+			hash_list,
+			game_version,
+			entity,
+			entity_id,
+			true
+		) {
+			let own_properties = sub_entity
+				.properties
+				.iter()
+				.flat_map(|x| x.keys())
+				.chain(
+					sub_entity
+						.platform_specific_properties
+						.iter()
+						.flat_map(|x| x.values())
+						.flat_map(|x| x.keys())
+				)
+				.unique();
+
+			for property in own_properties {
+				if !properties.iter().any(|(name, ..)| name == property) {
+					push(format!(
+						"The property {} doesn't exist on this entity in this game",
+						property
+					));
+				}
+			}
+		}
+
+		// Events and output copying are from output pins, input copying is from input pins
+		let connections = sub_entity
+			.events
+			.iter()
+			.flat_map(|x| x.iter())
+			.map(|x| (false, x))
+			.chain(
+				sub_entity
+					.input_copying
+					.iter()
+					.flat_map(|x| x.iter())
+					.map(|x| (true, x))
+			)
+			.chain(
+				sub_entity
+					.output_copying
+					.iter()
+					.flat_map(|x| x.iter())
+					.map(|x| (false, x))
+			);
+
+		for (is_input, (pin, connections)) in connections {
+			if let Some((factory_inputs, factory_outputs)) = get_factory_pins(entity_id)
+				&& !(if is_input { factory_inputs } else { factory_outputs }).contains(pin)
+			{
+				push(format!(
+					"The {} pin {} doesn't exist on this entity in this game",
+					if is_input { "input" } else { "output" },
+					pin
+				));
+			}
+
+			for (input, references) in connections {
+				for target in references.iter().filter_map(get_connection_target).unique() {
+					if let Some((factory_inputs, _)) = get_factory_pins(&target)
+						&& !factory_inputs.contains(input)
+					{
+						push(format!(
+							"The input pin {} doesn't exist on {} in this game",
+							input,
+							entity.entities.get(&target).map(|x| x.name.as_str()).unwrap_or(&target)
+						));
+					}
+				}
+			}
+		}
+	}
+
+	problems
+}
+
+/// Check an entity for compatibility with the loaded game version if it's HITMAN 2016 or HITMAN 2, warning about
+/// anything which doesn't exist in that game. Nothing is checked for HITMAN 3 or if the game files aren't loaded.
+#[try_fn]
+#[context("Couldn't warn about incompatibilities in entity {}", entity.factory_hash)]
+pub fn warn_about_incompatibilities(app: &AppHandle, entity: &Entity) -> Result<()> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+	let app_state = app.state::<AppState>();
+
+	if let Some(intellisense) = app_state.intellisense.load().as_ref()
+		&& let Some(game_files) = app_state.game_files.load().as_ref()
+		&& let Some(hash_list) = app_state.hash_list.load().as_ref()
+		&& let Some(install) = app_settings.load().game_install.as_ref()
+	{
+		let game_version = get_loaded_game_version(app, install)?;
+
+		let game_name = match game_version {
+			GameVersion::H1 => "HITMAN 2016",
+			GameVersion::H2 => "HITMAN 2",
+			GameVersion::H3 => return Ok(())
+		};
+
+		let issues = find_compatibility_issues(
+			intellisense,
+			game_files,
+			&app_state.cached_entities,
+			hash_list,
+			game_version,
+			entity
+		)?;
+
+		if !issues.is_empty() {
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Warning,
+					title: format!(
+						"{} compatibility issue{} in {}",
+						issues.len(),
+						if issues.len() == 1 { "" } else { "s" },
+						game_name
+					),
+					subtitle: issues
+						.iter()
+						.take(MAX_LISTED_ISSUES)
+						.map(|x| format!("{} ({}): {}", x.name, x.entity_id, x.message))
+						.chain(
+							(issues.len() > MAX_LISTED_ISSUES)
+								.then(|| format!("...and {} more", issues.len() - MAX_LISTED_ISSUES))
+						)
+						.join("; "),
+					link: None
+				}
+			)?;
+		}
+	}
+}
@@ -0,0 +1,344 @@
+use std::{
+	fs::{self, File},
+	io::BufReader,
+	path::{Path, PathBuf}
+};
+
+use anyhow::{Context, Result, anyhow};
+use fn_error_context::context;
+use hitman_commons::metadata::RuntimeID;
+use indexmap::IndexMap;
+use log::warn;
+use quickentity_rs::generate_patch;
+use serde::Deserialize;
+use serde_json::{Value, from_reader, from_slice, to_value};
+use tryvial::try_fn;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use crate::{
+	backups::BACKUP_FOLDER,
+	model::{AppState, EditorData, PatchConflict, PatchConflictOperation},
+	orphaned_files::ORPHANED_FILES_FOLDER,
+	packaging::DIST_FOLDER
+};
+
+pub const REPOSITORY_HASH: &str = "00204D1AFD76AB13";
+pub const UNLOCKABLES_HASH: &str = "0057C2C3941115CA";
+
+/// What a patch modifies, used to find other patches of the same thing.
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum PatchTarget {
+	Entity(RuntimeID),
+	Repository,
+	Unlockables
+}
+
+/// Get the target of each operation in a QuickEntity patch, mapped to the operation itself.
+///
+/// Operations which can be applied alongside each other without issue (adding distinct pin connections, for
+/// instance) get distinct targets, so only operations which touch the same data can conflict.
+fn get_entity_operations(patch: &Value) -> IndexMap<String, Value> {
+	let mut operations = IndexMap::new();
+
+	for operation in patch.as_array().map(|x| x.as_slice()).unwrap_or_default() {
+		let Some((kind, data)) = operation.as_object().and_then(|x| x.iter().next()) else {
+			continue;
+		};
+
+		let target = match kind.as_str() {
+			"AddEntity" | "RemoveEntityByID" => format!(
+				"Entity {}",
+				data.as_array()
+					.and_then(|x| x.first())
+					.unwrap_or(data)
+					.as_str()
+					.unwrap_or_default()
+			),
+
+			"SubEntityOperation" => {
+				let entity = data.get(0).and_then(|x| x.as_str()).unwrap_or_default();
+
+				let Some((sub_kind, sub_data)) = data.get(1).and_then(|x| x.as_object()).and_then(|x| x.iter().next())
+				else {
+					continue;
+				};
+
+				match sub_kind.as_str() {
+					"AddProperty"
+					| "SetPropertyType"
+					| "PatchArrayPropertyValue"
+					| "SetPropertyPostInit"
+					| "RemovePropertyByName" => format!(
+						"Entity {} property {}",
+						entity,
+						sub_data.get(0).unwrap_or(sub_data).as_str().unwrap_or_default()
+					),
+
+					"SetPropertyValue" => format!(
+						"Entity {} property {}",
+						entity,
+						sub_data
+							.get("property_name")
+							.and_then(|x| x.as_str())
+							.unwrap_or_default()
+					),
+
+					"SetParent" | "SetName" | "SetFactory" | "SetFactoryFlag" | "SetBlueprint" | "SetEditorOnly" => {
+						format!("Entity {} {}", entity, sub_kind)
+					}
+
+					_ => format!("Entity {} {} {}", entity, sub_kind, sub_data)
+				}
+			}
+
+			"SetRootEntity" | "SetSubType" => kind.to_owned(),
+
+			_ => format!("{} {}", kind, data)
+		};
+
+		operations.insert(target, operation.to_owned());
+	}
+
+	operations
+}
+
+/// Get the modified properties of repository or unlockables items, keyed by "item.property".
+fn get_item_operations<'a>(
+	base: impl Iterator<Item = (String, &'a IndexMap<String, Value>)>,
+	current: impl Iterator<Item = (String, &'a IndexMap<String, Value>)>
+) -> IndexMap<String, Value> {
+	let base = base.collect::<IndexMap<_, _>>();
+	let mut operations = IndexMap::new();
+
+	for (id, data) in current {
+		let base_data = base.get(&id);
+
+		for (key, value) in data {
+			if base_data.and_then(|x| x.get(key)) != Some(value) {
+				operations.insert(format!("{}.{}", id, key), value.to_owned());
+			}
+		}
+	}
+
+	operations
+}
+
+/// Get the modified properties from a saved repository/unlockables merge patch or JSON patch.
+fn get_item_file_operations(data: &Value) -> IndexMap<String, Value> {
+	let mut operations = IndexMap::new();
+
+	if let Some(patch) = data.get("patch").and_then(|x| x.as_array()) {
+		for operation in patch {
+			if let Some(path) = operation.get("path").and_then(|x| x.as_str()) {
+				let mut segments = path.trim_start_matches('/').split('/');
+
+				if let (Some(id), Some(key)) = (segments.next(), segments.next()) {
+					operations.insert(
+						format!("{}.{}", id, key),
+						operation.get("value").cloned().unwrap_or(Value::Null)
+					);
+				}
+			}
+		}
+	} else if let Some(items) = data.as_object() {
+		for (id, item) in items {
+			for (key, value) in item.as_object().into_iter().flatten() {
+				operations.insert(format!("{}.{}", id, key), value.to_owned());
+			}
+		}
+	}
+
+	operations
+}
+
+/// Get what the patch in an editor modifies, without working out its operations.
+fn get_editor_target(data: &EditorData) -> Option<PatchTarget> {
+	match data {
+		EditorData::QNPatch { base, .. } => Some(PatchTarget::Entity(RuntimeID::from_any(&base.factory_hash).ok()?)),
+		EditorData::RepositoryPatch { .. } => Some(PatchTarget::Repository),
+		EditorData::UnlockablesPatch { .. } => Some(PatchTarget::Unlockables),
+		_ => None
+	}
+}
+
+/// Get the operations of the patch in an editor.
+#[try_fn]
+#[context("Couldn't get patch operations for editor")]
+fn get_editor_operations(data: &EditorData) -> Result<IndexMap<String, Value>> {
+	match data {
+		EditorData::QNPatch { base, current, .. } => get_entity_operations(
+			&to_value(generate_patch(base, current).map_err(|x| anyhow!(x))?)?
+				.get("patch")
+				.cloned()
+				.unwrap_or_default()
+		),
+
+		EditorData::RepositoryPatch { base, current, .. } => get_item_operations(
+			base.iter().map(|x| (x.id.to_string(), &x.data)),
+			current.iter().map(|x| (x.id.to_string(), &x.data))
+		),
+
+		// Unlockables patches are keyed by their Id rather than their GUID
+		EditorData::UnlockablesPatch { base, current, .. } => get_item_operations(
+			base.iter()
+				.filter_map(|x| Some((x.data.get("Id")?.as_str()?.to_owned(), &x.data))),
+			current
+				.iter()
+				.filter_map(|x| Some((x.data.get("Id")?.as_str()?.to_owned(), &x.data)))
+		),
+
+		_ => IndexMap::new()
+	}
+}
+
+/// The parts of a patch file which identify what it modifies; the rest of the file is skipped when reading this.
+#[derive(Deserialize)]
+struct PatchFileHeader {
+	#[serde(rename = "tempHash")]
+	temp_hash: Option<String>,
+
+	file: Option<String>
+}
+
+/// Get the operations of a patch file in the project, if it's a patch of the given target. Patches of other targets
+/// are only read far enough to tell what they modify.
+fn get_file_operations(path: &Path, target: PatchTarget) -> Option<IndexMap<String, Value>> {
+	let name = path.file_name()?.to_string_lossy().to_lowercase();
+
+	let file_target = if name.ends_with(".repository.json") {
+		PatchTarget::Repository
+	} else if name.ends_with(".unlockables.json") {
+		PatchTarget::Unlockables
+	} else if name.ends_with(".entity.patch.json") || name.ends_with(".json.patch.json") {
+		let header: PatchFileHeader = from_reader(BufReader::new(File::open(path).ok()?)).ok()?;
+
+		if name.ends_with(".entity.patch.json") {
+			PatchTarget::Entity(RuntimeID::from_any(&header.temp_hash?).ok()?)
+		} else {
+			match header.file?.as_str() {
+				REPOSITORY_HASH => PatchTarget::Repository,
+				UNLOCKABLES_HASH => PatchTarget::Unlockables,
+				_ => return None
+			}
+		}
+	} else {
+		return None;
+	};
+
+	if file_target != target {
+		return None;
+	}
+
+	let data: Value = from_slice(&fs::read(path).ok()?).ok()?;
+
+	if name.ends_with(".entity.patch.json") {
+		Some(get_entity_operations(data.get("patch")?))
+	} else {
+		Some(get_item_file_operations(&data))
+	}
+}
+
+/// Find the operations in `theirs` which modify the same data as `ours` but with a different result.
+fn get_conflicting_operations(
+	ours: &IndexMap<String, Value>,
+	theirs: &IndexMap<String, Value>
+) -> Vec<PatchConflictOperation> {
+	ours.iter()
+		.filter_map(|(target, our_operation)| {
+			let their_operation = theirs.get(target)?;
+
+			(our_operation != their_operation).then(|| PatchConflictOperation {
+				target: target.to_owned(),
+				ours: our_operation.to_owned(),
+				theirs: their_operation.to_owned()
+			})
+		})
+		.collect()
+}
+
+/// Find other open editors and project files which patch the same resource as the given editor in a conflicting way.
+#[try_fn]
+#[context("Couldn't find patch conflicts")]
+pub fn find_patch_conflicts(app_state: &AppState, editor_id: Uuid) -> Result<Vec<PatchConflict>> {
+	let (own_file, target, ours) = {
+		let editor = app_state.editor_states.get(&editor_id).context("No such editor")?;
+
+		let Some(target) = get_editor_target(&editor.data) else {
+			return Ok(vec![]);
+		};
+
+		(editor.file.to_owned(), target, get_editor_operations(&editor.data)?)
+	};
+
+	let mut conflicts = vec![];
+	let mut open_files: Vec<PathBuf> = vec![];
+
+	for editor in app_state.editor_states.iter() {
+		if *editor.key() == editor_id {
+			continue;
+		}
+
+		if let Some(file) = editor.file.as_ref() {
+			open_files.push(file.to_owned());
+		}
+
+		if get_editor_target(&editor.data) != Some(target) {
+			continue;
+		}
+
+		// A problem with another editor shouldn't stop this one from being saved
+		let theirs = match get_editor_operations(&editor.data) {
+			Ok(theirs) => theirs,
+
+			Err(e) => {
+				warn!("Skipping editor {} when checking for conflicts: {:?}", editor.key(), e);
+				continue;
+			}
+		};
+
+		let operations = get_conflicting_operations(&ours, &theirs);
+
+		if !operations.is_empty() {
+			conflicts.push(PatchConflict {
+				file: editor.file.to_owned(),
+				editor: Some(*editor.key()),
+				operations
+			});
+		}
+	}
+
+	if let Some(project) = app_state.project.load().as_ref() {
+		for entry in WalkDir::new(&project.path)
+			.into_iter()
+			.filter_entry(|x| {
+				x.depth() != 1
+					|| (x.file_name() != ".git"
+						&& x.file_name() != DIST_FOLDER
+						&& x.file_name() != BACKUP_FOLDER
+						&& x.file_name() != ORPHANED_FILES_FOLDER)
+			})
+			.filter_map(|x| x.ok())
+			.filter(|x| x.file_type().is_file())
+		{
+			// Open editors have already been checked using their unsaved state
+			if Some(entry.path()) == own_file.as_deref() || open_files.iter().any(|x| x == entry.path()) {
+				continue;
+			}
+
+			if let Some(theirs) = get_file_operations(entry.path(), target) {
+				let operations = get_conflicting_operations(&ours, &theirs);
+
+				if !operations.is_empty() {
+					conflicts.push(PatchConflict {
+						file: Some(entry.path().to_owned()),
+						editor: None,
+						operations
+					});
+				}
+			}
+		}
+	}
+
+	conflicts
+}
@@ -0,0 +1,98 @@
+use hitman_commons::{hash_list::HashList, metadata::RuntimeID};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use uuid::Uuid;
+
+use crate::audio_names::wwise_hash;
+
+/// A value converted into another form.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct Conversion {
+	/// What the input was treated as and what it was converted to
+	pub label: String,
+	pub value: String
+}
+
+/// The hash of an IOI path; the first 8 bytes of the MD5 of the lowercased path with the top byte cleared.
+pub fn path_hash(path: &str) -> u64 {
+	let digest = md5::compute(path.to_lowercase().as_bytes());
+
+	u64::from_be_bytes(digest.0[0..8].try_into().expect("MD5 digests are 16 bytes")) & 0x00FF_FFFF_FFFF_FFFF
+}
+
+/// Convert the input into every form it can be converted to: paths into hashes, entity IDs and hashes between hex
+/// and decimal, GUIDs into their various formats and anything into its FNV-1 hash. Hashes are looked up in the hash
+/// list if one is given.
+pub fn convert(input: &str, hash_list: Option<&HashList>) -> Vec<Conversion> {
+	let input = input.trim();
+
+	if input.is_empty() {
+		return vec![];
+	}
+
+	let mut conversions = vec![];
+
+	let mut push = |label: &str, value: String| {
+		conversions.push(Conversion {
+			label: label.into(),
+			value
+		})
+	};
+
+	if input.starts_with('[') || input.contains(".pc_") {
+		let hash = path_hash(input);
+
+		push("Path hash (hex)", format!("{:016X}", hash));
+		push("Path hash (decimal)", hash.to_string());
+	}
+
+	let hex = input.trim_start_matches("0x").trim_start_matches("0X");
+
+	if !hex.is_empty()
+		&& hex.len() <= 16
+		&& let Ok(value) = u64::from_str_radix(hex, 16)
+	{
+		push("Hex to decimal", value.to_string());
+
+		if let Some(hash_list) = hash_list
+			&& let Ok(hash) = RuntimeID::from_any(&format!("{:016X}", value))
+			&& let Some(entry) = hash_list.entries.get(&hash)
+		{
+			if let Some(path) = entry.path.as_ref() {
+				push("Path from hash list", path.to_owned());
+			}
+
+			if let Some(hint) = entry.hint.as_ref() {
+				push("Hint from hash list", hint.to_owned());
+			}
+
+			push("Resource type", entry.resource_type.into());
+		}
+	}
+
+	if input.chars().all(|x| x.is_ascii_digit())
+		&& let Ok(value) = input.parse::<u64>()
+	{
+		push("Decimal to hex", format!("{:X}", value));
+		push("Entity ID (hex)", format!("{:016x}", value));
+		push("Hash (hex)", format!("{:016X}", value));
+	}
+
+	if let Ok(guid) = Uuid::try_parse(input) {
+		push("GUID", guid.hyphenated().to_string());
+		push("GUID (uppercase)", guid.hyphenated().to_string().to_uppercase());
+		push("GUID (no hyphens)", guid.simple().to_string());
+		push("GUID (braced)", guid.braced().to_string());
+		push(
+			"GUID (as unlockable ID)",
+			guid.hyphenated().to_string().to_uppercase().replace('-', "_")
+		);
+	}
+
+	let fnv = wwise_hash(input);
+
+	push("FNV-1 hash of lowercase (hex)", format!("{:08X}", fnv));
+	push("FNV-1 hash of lowercase (decimal)", fnv.to_string());
+
+	conversions
+}
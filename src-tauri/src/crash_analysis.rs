@@ -0,0 +1,337 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	sync::LazyLock
+};
+
+use anyhow::{Context, Result};
+use fn_error_context::context;
+use hashbrown::HashMap;
+use hitman_commons::{hash_list::HashList, metadata::RuntimeID};
+use regex::Regex;
+use rpkg_rs::resource::partition_manager::PartitionManager;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, from_str};
+use specta::Type;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+use walkdir::WalkDir;
+
+use crate::{
+	finish_task,
+	hash_resolution::{HASH_OR_PATH, ResolvedResource, resolve_hashes},
+	model::{AppState, CrashAnalysisRequest, Request, ToolRequest},
+	send_request, start_task
+};
+
+/// The shortest run of printable characters in a minidump which is treated as a string.
+const MIN_STRING_LENGTH: usize = 6;
+
+/// A module and offset in a stack trace, like `HITMAN3.exe+0x1A2B3C`.
+static MODULE_OFFSET: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r"(?i)\b([\w.-]+\.(?:exe|dll))\s*\+\s*(?:0x)?([0-9a-f]+)\b").expect("Regex is valid"));
+
+/// A resource mentioned in a crash, along with the project files which have to do with it.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct CrashResource {
+	pub resource: ResolvedResource,
+
+	/// Project files which define or override the resource, like an entity with it as its factory
+	pub defined_in: Vec<PathBuf>,
+
+	/// Project files which refer to the resource by hash or path
+	pub referenced_in: Vec<PathBuf>
+}
+
+/// An address in a module which appeared in the crash.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct ModuleOffset {
+	pub module: String,
+
+	/// The offset from the start of the module, like `0x1A2B3C`
+	pub offset: String,
+
+	/// How many times the address appeared
+	pub occurrences: u32
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct CrashAnalysis {
+	pub path: PathBuf,
+
+	/// Whether the file was a minidump, in which case only its readable strings could be searched
+	pub minidump: bool,
+
+	pub resources: Vec<CrashResource>,
+	pub module_offsets: Vec<ModuleOffset>,
+
+	/// Every project file which defines or refers to a resource in the crash
+	pub involved_files: Vec<PathBuf>
+}
+
+/// The resources defined by a project file and those it refers to.
+struct ProjectFileResources {
+	path: PathBuf,
+	defines: Vec<RuntimeID>,
+	references: Vec<RuntimeID>
+}
+
+/// Get the printable runs of ASCII and UTF-16 characters in binary data, one per line, as `strings` would.
+fn extract_strings(data: &[u8]) -> String {
+	let mut strings = vec![];
+
+	let mut current = String::new();
+
+	for &byte in data {
+		if byte.is_ascii_graphic() || byte == b' ' {
+			current.push(byte as char);
+		} else {
+			if current.len() >= MIN_STRING_LENGTH {
+				strings.push(current.to_owned());
+			}
+
+			current.clear();
+		}
+	}
+
+	if current.len() >= MIN_STRING_LENGTH {
+		strings.push(current.to_owned());
+	}
+
+	current.clear();
+
+	for character in char::decode_utf16(data.chunks_exact(2).map(|x| u16::from_le_bytes([x[0], x[1]]))) {
+		match character {
+			Ok(character) if character.is_ascii_graphic() || character == ' ' => {
+				current.push(character);
+			}
+
+			_ => {
+				if current.len() >= MIN_STRING_LENGTH {
+					strings.push(current.to_owned());
+				}
+
+				current.clear();
+			}
+		}
+	}
+
+	if current.len() >= MIN_STRING_LENGTH {
+		strings.push(current);
+	}
+
+	strings.join("\n")
+}
+
+fn find_module_offsets(text: &str) -> Vec<ModuleOffset> {
+	let mut offsets: Vec<ModuleOffset> = vec![];
+
+	for captures in MODULE_OFFSET.captures_iter(text) {
+		let Ok(offset) = u64::from_str_radix(&captures[2], 16) else {
+			continue;
+		};
+
+		let module = captures[1].to_owned();
+		let offset = format!("0x{:X}", offset);
+
+		if let Some(existing) = offsets
+			.iter_mut()
+			.find(|x| x.module.eq_ignore_ascii_case(&module) && x.offset == offset)
+		{
+			existing.occurrences += 1;
+		} else {
+			offsets.push(ModuleOffset {
+				module,
+				offset,
+				occurrences: 1
+			});
+		}
+	}
+
+	offsets
+}
+
+/// Find what each project file defines and refers to. Files named after a hash define that resource, and entities and
+/// patches define their factory and blueprint. The project's custom paths and any paths used by entities are added to
+/// `paths` so that resources only the project knows about can be named.
+fn scan_project(project_path: &Path, paths: &mut HashMap<RuntimeID, String>) -> Vec<ProjectFileResources> {
+	let mut files = vec![];
+
+	for entry in WalkDir::new(project_path)
+		.into_iter()
+		.filter_entry(|x| x.file_name() != ".git")
+		.filter_map(|x| x.ok())
+		.filter(|x| x.file_type().is_file())
+	{
+		let file_name = entry.file_name().to_string_lossy();
+
+		let mut defines = vec![];
+		let mut references = vec![];
+
+		if let Some((stem, _)) = file_name.split_once('.')
+			&& stem.len() == 16
+			&& let Ok(hash) = RuntimeID::from_any(stem)
+		{
+			defines.push(hash);
+		}
+
+		if file_name.ends_with(".json")
+			&& let Ok(contents) = fs::read_to_string(entry.path())
+		{
+			if file_name.ends_with(".entity.json") || file_name.ends_with(".entity.patch.json") {
+				let entity = from_str::<Value>(&contents).ok();
+
+				for key in ["factoryHash", "blueprintHash"] {
+					if let Some(value) = entity.as_ref().and_then(|x| x.get(key)).and_then(|x| x.as_str())
+						&& let Ok(hash) = RuntimeID::from_any(value)
+					{
+						if value.starts_with('[') {
+							paths.insert(hash, value.to_owned());
+						}
+
+						defines.push(hash);
+					}
+				}
+			}
+
+			for found in HASH_OR_PATH.find_iter(&contents) {
+				if let Ok(hash) = RuntimeID::from_any(found.as_str())
+					&& !defines.contains(&hash)
+					&& !references.contains(&hash)
+				{
+					references.push(hash);
+				}
+			}
+		}
+
+		if !defines.is_empty() || !references.is_empty() {
+			files.push(ProjectFileResources {
+				path: entry.path().to_owned(),
+				defines,
+				references
+			});
+		}
+	}
+
+	files
+}
+
+/// Analyse a crash log or minidump from the game: find the resources and module offsets it mentions, resolve the
+/// resources against the hash list, game files and project, and work out which project files were involved. Minidumps
+/// are only searched for readable strings, so module offsets will generally only be found in crash logs. Hashes which
+/// can't be resolved anywhere are left out, as crash dumps are full of hex which only looks like a hash.
+#[try_fn]
+#[context("Couldn't analyse crash {}", path.display())]
+pub fn analyse_crash(
+	path: &Path,
+	project: Option<(&Path, &[String])>,
+	hash_list: Option<&HashList>,
+	game_files: Option<&PartitionManager>,
+	reverse_dependencies: Option<&HashMap<RuntimeID, Vec<RuntimeID>>>
+) -> Result<CrashAnalysis> {
+	let data = fs::read(path).context("Couldn't read crash file")?;
+
+	let minidump = data.starts_with(b"MDMP");
+
+	let text = if minidump {
+		extract_strings(&data)
+	} else {
+		String::from_utf8_lossy(&data).into_owned()
+	};
+
+	let mut paths = HashMap::new();
+
+	let project_files = if let Some((project_path, custom_paths)) = project {
+		for custom_path in custom_paths {
+			if let Ok(hash) = RuntimeID::from_any(custom_path) {
+				paths.insert(hash, custom_path.to_owned());
+			}
+		}
+
+		scan_project(project_path, &mut paths)
+	} else {
+		vec![]
+	};
+
+	let mut involved_files: Vec<PathBuf> = vec![];
+
+	let resources = resolve_hashes(&text, hash_list, game_files, reverse_dependencies)
+		.into_iter()
+		.filter_map(|mut resource| {
+			let hash = RuntimeID::from_any(&resource.hash).ok()?;
+
+			if resource.path.is_none() {
+				resource.path = paths.get(&hash).cloned();
+			}
+
+			let defined_in = project_files
+				.iter()
+				.filter(|x| x.defines.contains(&hash))
+				.map(|x| x.path.to_owned())
+				.collect::<Vec<_>>();
+
+			let referenced_in = project_files
+				.iter()
+				.filter(|x| x.references.contains(&hash))
+				.map(|x| x.path.to_owned())
+				.collect::<Vec<_>>();
+
+			let known = resource.resource_type.is_some() || resource.path.is_some() || resource.chunk.is_some();
+
+			if !known && defined_in.is_empty() && referenced_in.is_empty() {
+				return None;
+			}
+
+			for file in defined_in.iter().chain(referenced_in.iter()) {
+				if !involved_files.contains(file) {
+					involved_files.push(file.to_owned());
+				}
+			}
+
+			Some(CrashResource {
+				resource,
+				defined_in,
+				referenced_in
+			})
+		})
+		.collect();
+
+	CrashAnalysis {
+		path: path.to_owned(),
+		minidump,
+		resources,
+		module_offsets: find_module_offsets(&text),
+		involved_files
+	}
+}
+
+/// Analyse a crash against the loaded hash list, game files and project, and show the results in the crash analysis
+/// tool.
+#[try_fn]
+#[context("Couldn't send crash analysis")]
+pub fn send_crash_analysis(app: &AppHandle, path: &Path) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let task = start_task(app, "Analysing crash")?;
+
+	let project = app_state.project.load();
+	let project_settings = project.as_ref().map(|x| x.settings.load());
+
+	let analysis = analyse_crash(
+		path,
+		project
+			.as_ref()
+			.zip(project_settings.as_ref())
+			.map(|(project, settings)| (project.path.as_path(), settings.custom_paths.as_slice())),
+		app_state.hash_list.load().as_deref(),
+		app_state.game_files.load().as_deref(),
+		app_state.resource_reverse_dependencies.load().as_deref()
+	);
+
+	finish_task(app, task)?;
+
+	send_request(
+		app,
+		Request::Tool(ToolRequest::CrashAnalysis(CrashAnalysisRequest::SetAnalysis(analysis?)))
+	)?;
+}
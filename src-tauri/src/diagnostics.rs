@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use serde_json::to_vec;
+use specta::Type;
+use uuid::Uuid;
+
+use crate::{
+	model::{AppState, DeletedEntities, EditorData},
+	tasks::TaskTiming
+};
+
+/// The approximate memory taken up by an editor's state.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct EditorMemoryUsage {
+	pub id: Uuid,
+
+	/// The editor's file name, or its kind if it doesn't have a file
+	pub name: String,
+
+	/// The split views of this editor
+	pub split_tabs: Vec<Uuid>,
+
+	/// Name and approximate size in bytes of each part of the state
+	pub parts: Vec<(String, u32)>,
+
+	pub total: u32
+}
+
+/// Where memory and time are going in the current session, to help track down a session growing unexpectedly large.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct SessionDiagnostics {
+	/// Largest first
+	pub editors: Vec<EditorMemoryUsage>,
+
+	/// Name and approximate size in bytes of each shared cache
+	pub caches: Vec<(String, u32)>,
+
+	pub tasks: Vec<TaskTiming>
+}
+
+/// Sizes are exported as u32, so anything 4 GiB or larger is reported as u32::MAX.
+fn clamp_size(size: usize) -> u32 {
+	u32::try_from(size).unwrap_or(u32::MAX)
+}
+
+/// The size of a value as JSON, which is a reasonable approximation of how much memory it takes up.
+fn json_size(value: &impl Serialize) -> usize {
+	to_vec(value).map(|x| x.len()).unwrap_or_default()
+}
+
+fn recycle_bin_size(recycle_bin: &[DeletedEntities]) -> usize {
+	recycle_bin
+		.iter()
+		.map(|x| json_size(&x.entities) + json_size(&x.references))
+		.sum()
+}
+
+/// The kind of an editor and the approximate size of each part of its state.
+fn get_editor_parts(data: &EditorData) -> (&'static str, Vec<(String, usize)>) {
+	match data {
+		EditorData::Nil => ("Empty", vec![]),
+
+		EditorData::ResourceOverview { .. } => ("Resource overview", vec![]),
+
+		EditorData::Text { content, .. } => ("Text", vec![("Content".into(), content.len())]),
+
+		EditorData::QNEntity {
+			entity, recycle_bin, ..
+		} => (
+			"Entity",
+			vec![
+				("Entity".into(), json_size(entity)),
+				("Recycle bin".into(), recycle_bin_size(recycle_bin)),
+			]
+		),
+
+		EditorData::QNPatch {
+			base,
+			current,
+			recycle_bin,
+			..
+		} => (
+			"Entity patch",
+			vec![
+				("Base".into(), json_size(base)),
+				("Current".into(), json_size(current)),
+				("Recycle bin".into(), recycle_bin_size(recycle_bin)),
+			]
+		),
+
+		EditorData::RepositoryPatch { base, current, .. } => (
+			"Repository patch",
+			vec![("Base".into(), json_size(base)), ("Current".into(), json_size(current))]
+		),
+
+		EditorData::UnlockablesPatch { base, current, .. } => (
+			"Unlockables patch",
+			vec![("Base".into(), json_size(base)), ("Current".into(), json_size(current))]
+		),
+
+		EditorData::ContentSearchResults { results } => {
+			("Content search results", vec![("Results".into(), json_size(results))])
+		}
+
+		EditorData::Enum { enums } => ("Enums", vec![("Enums".into(), json_size(enums))]),
+
+		EditorData::Dialogue { dialogue } => ("Dialogue", vec![("Dialogue".into(), json_size(dialogue))]),
+
+		EditorData::JsonPatch { content, base } => (
+			"JSON patch",
+			[("Content".into(), content.len())]
+				.into_iter()
+				.chain(base.as_ref().map(|x| ("Target resource".into(), json_size(&x.data))))
+				.collect()
+		),
+
+		EditorData::JsonResource { base, content, .. } => (
+			"JSON resource",
+			vec![("Base".into(), json_size(base)), ("Content".into(), content.len())]
+		)
+	}
+}
+
+/// Measure the editor states, caches and recent tasks of the session. Sizes are measured by serialising everything, so
+/// this can take a moment for sessions with many large entities open.
+pub fn get_session_diagnostics(app_state: &AppState) -> SessionDiagnostics {
+	let mut editors = app_state
+		.editor_states
+		.iter()
+		.map(|editor| {
+			let (kind, parts) = get_editor_parts(&editor.data);
+			let total = parts.iter().map(|(_, size)| size).sum::<usize>();
+
+			EditorMemoryUsage {
+				id: *editor.key(),
+				name: editor
+					.file
+					.as_ref()
+					.and_then(|x| x.file_name())
+					.map(|x| x.to_string_lossy().into())
+					.unwrap_or_else(|| kind.into()),
+				split_tabs: app_state
+					.split_views
+					.iter()
+					.filter(|x| x.value() == editor.key())
+					.map(|x| *x.key())
+					.collect(),
+				total: clamp_size(total),
+				parts: parts.into_iter().map(|(name, size)| (name, clamp_size(size))).collect()
+			}
+		})
+		.collect::<Vec<_>>();
+
+	editors.sort_by_key(|x| std::cmp::Reverse(x.total));
+
+	let mut caches = vec![(
+		format!("Entity cache ({} entities)", app_state.cached_entities.len()),
+		clamp_size(app_state.cached_entities.memory())
+	)];
+
+	if let Some(repository) = app_state.repository.load().as_ref() {
+		caches.push(("Repository".into(), clamp_size(json_size(repository))));
+	}
+
+	SessionDiagnostics {
+		editors,
+		caches,
+		tasks: app_state.tasks.timings()
+	}
+}
@@ -0,0 +1,316 @@
+use std::{
+	fs,
+	path::{Component, Path, PathBuf}
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use fn_error_context::context;
+use hitman_commons::{
+	resourcelib::{EntityBlueprint, EntityBlueprintLegacy, EntityFactory, EntityFactoryLegacy},
+	rpkg_tool::RpkgResourceMeta
+};
+use quickentity_rs::{convert_to_qn, qn_structs::Entity};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, from_slice, to_string, to_vec};
+use specta::Type;
+use tryvial::try_fn;
+
+use crate::biome::format_json;
+
+/// The content folder imported files are placed in if the manifest doesn't have one.
+const DEFAULT_CONTENT_FOLDER: &str = "content";
+
+/// The folder, relative to the project, that audio files are placed in.
+const AUDIO_FOLDER: &str = "audio";
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DroppedFileKind {
+	/// A ResourceLib TEMP JSON, which is converted to a QuickEntity entity along with its TBLU
+	ResourceLibEntity,
+
+	Entity,
+
+	/// A TGA texture and its meta file
+	Texture,
+
+	Audio,
+	Unsupported
+}
+
+/// A file dropped into the window and how it would be imported into the project.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct DroppedFile {
+	pub path: PathBuf,
+	pub kind: DroppedFileKind,
+
+	/// Where the file will be written, relative to the project, or None if it can't be imported
+	pub destination: Option<PathBuf>,
+
+	/// Shown alongside the file, like why it can't be imported or what to do after importing it
+	pub note: Option<String>
+}
+
+/// Find a file in the same folder as the given path whose name matches case-insensitively.
+fn find_sibling(path: &Path, name: &str) -> Option<PathBuf> {
+	fs::read_dir(path.parent()?)
+		.ok()?
+		.filter_map(|x| x.ok())
+		.find(|x| x.file_name().to_string_lossy().eq_ignore_ascii_case(name))
+		.map(|x| x.path())
+}
+
+/// Read a ResourceLib TEMP JSON, which is in a different format for H2016.
+#[try_fn]
+#[context("Couldn't read factory {}", path.display())]
+fn read_factory(path: &Path) -> Result<EntityFactory> {
+	let data = fs::read(path)?;
+
+	from_slice::<EntityFactory>(&data)
+		.or_else(|_| from_slice::<EntityFactoryLegacy>(&data).map(|x| x.into_modern()))
+		.context("Invalid TEMP JSON")?
+}
+
+/// Read a ResourceLib TBLU JSON, which is in a different format for H2016.
+#[try_fn]
+#[context("Couldn't read blueprint {}", path.display())]
+fn read_blueprint(path: &Path) -> Result<EntityBlueprint> {
+	let data = fs::read(path)?;
+
+	from_slice::<EntityBlueprint>(&data)
+		.or_else(|_| from_slice::<EntityBlueprintLegacy>(&data).map(|x| x.into_modern()))
+		.context("Invalid TBLU JSON")?
+}
+
+/// The files needed to convert a ResourceLib TEMP JSON: its meta, the TBLU JSON and the TBLU's meta.
+#[try_fn]
+#[context("Couldn't find the files needed to convert {}", path.display())]
+fn find_entity_files(path: &Path) -> Result<(PathBuf, PathBuf, PathBuf)> {
+	let name = path.file_name().context("No file name")?.to_string_lossy();
+	let stem = &name[..name.len() - ".TEMP.json".len()];
+
+	let factory_meta_path = find_sibling(path, &format!("{}.TEMP.meta.json", stem))
+		.with_context(|| format!("{}.TEMP.meta.JSON is missing", stem))?;
+
+	let factory = read_factory(path)?;
+
+	let factory_meta: RpkgResourceMeta =
+		from_slice(&fs::read(&factory_meta_path)?).context("Invalid TEMP meta JSON")?;
+
+	let blueprint_hash = &factory_meta
+		.hash_reference_data
+		.get(factory.blueprint_index_in_resource_header as usize)
+		.context("Blueprint referenced in factory does not exist in dependencies")?
+		.hash;
+
+	let blueprint_path = find_sibling(path, &format!("{}.TBLU.json", blueprint_hash))
+		.with_context(|| format!("{}.TBLU.json is missing", blueprint_hash))?;
+
+	let blueprint_meta_path = find_sibling(path, &format!("{}.TBLU.meta.json", blueprint_hash))
+		.with_context(|| format!("{}.TBLU.meta.JSON is missing", blueprint_hash))?;
+
+	(factory_meta_path, blueprint_path, blueprint_meta_path)
+}
+
+/// Get the folder imported content files are placed in, relative to the project: chunk0 of the manifest's first
+/// content folder, or of the default content folder if there isn't one.
+#[try_fn]
+#[context("Couldn't get content folder")]
+pub fn get_content_chunk_folder(project_path: &Path) -> Result<PathBuf> {
+	let manifest: Value =
+		from_slice(&fs::read(project_path.join("manifest.json")).context("Couldn't read manifest.json")?)
+			.context("Invalid manifest")?;
+
+	PathBuf::from(
+		manifest
+			.get("contentFolders")
+			.and_then(|x| x.as_array())
+			.and_then(|x| x.first())
+			.and_then(|x| x.as_str())
+			.unwrap_or(DEFAULT_CONTENT_FOLDER)
+	)
+	.join("chunk0")
+}
+
+/// Work out what kind of file each dropped file is and where it would be imported to. Texture meta files dropped along
+/// with their texture are left out, since they're imported with it.
+#[try_fn]
+#[context("Couldn't detect dropped files")]
+pub fn detect_dropped_files(project_path: &Path, paths: &[PathBuf]) -> Result<Vec<DroppedFile>> {
+	let content_folder = get_content_chunk_folder(project_path)?;
+
+	let mut files = vec![];
+
+	for path in paths {
+		let name = path.file_name().context("No file name")?.to_string_lossy();
+		let lowercase_name = name.to_lowercase();
+
+		if lowercase_name.ends_with(".texture.tga.meta") {
+			let texture = &name[..name.len() - ".meta".len()];
+
+			if paths.iter().any(|x| {
+				x.parent() == path.parent()
+					&& x.file_name()
+						.is_some_and(|x| x.to_string_lossy().eq_ignore_ascii_case(texture))
+			}) {
+				continue;
+			}
+		}
+
+		files.push(if lowercase_name.ends_with(".temp.json") {
+			match find_entity_files(path) {
+				Ok(_) => DroppedFile {
+					path: path.to_owned(),
+					kind: DroppedFileKind::ResourceLibEntity,
+					destination: Some(
+						content_folder.join(format!("{}.entity.json", &name[..name.len() - ".TEMP.json".len()]))
+					),
+					note: None
+				},
+
+				Err(e) => DroppedFile {
+					path: path.to_owned(),
+					kind: DroppedFileKind::ResourceLibEntity,
+					destination: None,
+					note: Some(format!("{:#}", e))
+				}
+			}
+		} else if lowercase_name.ends_with(".entity.json") {
+			DroppedFile {
+				path: path.to_owned(),
+				kind: DroppedFileKind::Entity,
+				destination: Some(content_folder.join(name.as_ref())),
+				note: None
+			}
+		} else if lowercase_name.ends_with(".texture.tga") {
+			let has_meta = find_sibling(path, &format!("{}.meta", name)).is_some();
+
+			DroppedFile {
+				path: path.to_owned(),
+				kind: DroppedFileKind::Texture,
+				destination: has_meta.then(|| content_folder.join(name.as_ref())),
+				note: (!has_meta).then(|| format!("{}.meta is missing", name))
+			}
+		} else if lowercase_name.ends_with(".wav") {
+			DroppedFile {
+				path: path.to_owned(),
+				kind: DroppedFileKind::Audio,
+				destination: Some(PathBuf::from(AUDIO_FOLDER).join(name.as_ref())),
+				note: Some("WAV files need to be converted to WEM with Wwise before the game can use them".into())
+			}
+		} else {
+			DroppedFile {
+				path: path.to_owned(),
+				kind: DroppedFileKind::Unsupported,
+				destination: None,
+				note: Some("This type of file can't be imported, but it can be opened".into())
+			}
+		});
+	}
+
+	files
+}
+
+/// Add a folder to the manifest's content folders if it isn't one already.
+#[try_fn]
+#[context("Couldn't register content folder {}", folder)]
+pub fn register_content_folder(project_path: &Path, folder: &str) -> Result<()> {
+	let manifest_path = project_path.join("manifest.json");
+
+	let mut manifest: Value =
+		from_slice(&fs::read(&manifest_path).context("Couldn't read manifest.json")?).context("Invalid manifest")?;
+
+	let content_folders = manifest
+		.as_object_mut()
+		.context("Manifest is not an object")?
+		.entry("contentFolders")
+		.or_insert_with(|| Value::Array(vec![]))
+		.as_array_mut()
+		.context("contentFolders is not an array")?;
+
+	if !content_folders.iter().any(|x| x.as_str() == Some(folder)) {
+		content_folders.push(Value::String(folder.to_owned()));
+
+		fs::write(&manifest_path, format_json(&to_string(&manifest)?)?)?;
+	}
+}
+
+/// Import a dropped file into the project at its destination, converting it if necessary and registering the content
+/// folder it's placed in with the manifest. Returns the path of the imported file.
+#[try_fn]
+#[context("Couldn't import {}", file.path.display())]
+pub fn import_dropped_file(project_path: &Path, file: &DroppedFile) -> Result<PathBuf> {
+	let relative_destination = file.destination.as_ref().context("File can't be imported")?;
+
+	if relative_destination.is_absolute()
+		|| relative_destination
+			.components()
+			.any(|x| matches!(x, Component::ParentDir))
+	{
+		bail!("{} is not inside the project", relative_destination.display());
+	}
+
+	let destination = project_path.join(relative_destination);
+
+	if destination.exists() {
+		bail!("{} already exists", relative_destination.display());
+	}
+
+	fs::create_dir_all(destination.parent().context("No parent")?)?;
+
+	match file.kind {
+		DroppedFileKind::ResourceLibEntity => {
+			let (factory_meta_path, blueprint_path, blueprint_meta_path) = find_entity_files(&file.path)?;
+
+			let factory_meta: RpkgResourceMeta =
+				from_slice(&fs::read(factory_meta_path)?).context("Invalid TEMP meta JSON")?;
+
+			let blueprint_meta: RpkgResourceMeta =
+				from_slice(&fs::read(blueprint_meta_path)?).context("Invalid TBLU meta JSON")?;
+
+			let entity = convert_to_qn(
+				&read_factory(&file.path)?,
+				&factory_meta,
+				&read_blueprint(&blueprint_path)?,
+				&blueprint_meta,
+				false
+			)
+			.map_err(|x| anyhow!("QuickEntity error: {:?}", x))?;
+
+			fs::write(&destination, to_vec(&entity)?)?;
+		}
+
+		DroppedFileKind::Entity => {
+			from_slice::<Entity>(&fs::read(&file.path)?).context("Invalid entity")?;
+
+			fs::copy(&file.path, &destination)?;
+		}
+
+		DroppedFileKind::Texture => {
+			let name = file.path.file_name().context("No file name")?.to_string_lossy();
+
+			let meta_path = find_sibling(&file.path, &format!("{}.meta", name))
+				.with_context(|| format!("{}.meta is missing", name))?;
+
+			fs::copy(&file.path, &destination)?;
+			fs::copy(&meta_path, format!("{}.meta", destination.display()))?;
+		}
+
+		DroppedFileKind::Audio => {
+			fs::copy(&file.path, &destination)?;
+		}
+
+		DroppedFileKind::Unsupported => {
+			bail!("This type of file can't be imported");
+		}
+	}
+
+	if matches!(
+		file.kind,
+		DroppedFileKind::ResourceLibEntity | DroppedFileKind::Entity | DroppedFileKind::Texture
+	) && let Some(Component::Normal(folder)) = relative_destination.components().next()
+	{
+		register_content_folder(project_path, &folder.to_string_lossy())?;
+	}
+
+	destination
+}
@@ -3,10 +3,11 @@ use std::{
 		Arc,
 		atomic::{AtomicBool, Ordering}
 	},
-	time::Duration
+	time::{Duration, Instant}
 };
 
 use anyhow::{Context, Error, Result, anyhow};
+use arc_swap::ArcSwap;
 use debounced::debounced;
 use fn_error_context::context;
 use futures_util::{SinkExt, StreamExt, stream::SplitSink};
@@ -23,7 +24,8 @@ use specta::Type;
 use tauri::{AppHandle, Manager, async_runtime::spawn};
 use tokio::{
 	net::TcpStream,
-	sync::{RwLock, broadcast}
+	sync::{Notify, RwLock, broadcast},
+	time::{sleep, timeout}
 };
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message};
@@ -32,8 +34,8 @@ use tryvial::try_fn;
 use crate::{
 	Notification, NotificationKind, handle_event,
 	model::{
-		AppState, EditorConnectionEvent, EditorData, EditorRequest, EntityEditorRequest, EntityMonacoRequest,
-		EntityTreeRequest, Event, GlobalRequest, Request
+		AppSettings, AppState, EditorConnectionEvent, EditorData, EditorRequest, EntityEditorRequest,
+		EntityMonacoRequest, EntityTreeRequest, Event, GlobalRequest, Request, SettingsRequest, ToolRequest
 	},
 	send_notification, send_request
 };
@@ -224,7 +226,13 @@ pub enum EntityDetails {
 #[serde(tag = "type")]
 #[serde(rename_all = "camelCase")]
 pub enum SDKEditorEvent {
-	Welcome,
+	Welcome {
+		/// Not sent by older versions of the SDK
+		version: Option<String>,
+
+		/// Not sent by older versions of the SDK
+		capabilities: Option<Vec<String>>
+	},
 
 	Error {
 		message: String,
@@ -299,18 +307,148 @@ pub enum SDKEditorEvent {
 	EntityTreeRebuilt
 }
 
-pub struct EditorConnection {
-	sender: Arc<RwLock<Option<SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>>>>,
+/// The delay before the first attempt to reconnect, which is doubled after each failed attempt.
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// The longest delay between attempts to reconnect.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// How long to wait for a game to welcome GlacierKit after connecting before giving up on the connection.
+const WELCOME_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The address the SDK's editor server listens on by default.
+const DEFAULT_EDITOR_SERVER_URL: &str = "ws://localhost:46735";
+
+/// A game running the SDK's editor server, which the editor connection can connect to.
+#[derive(Type, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EditorConnectionGame {
+	/// Shown in the UI to tell connections apart
+	pub name: String,
+
+	/// The WebSocket URL of the game's editor server
+	pub url: String
+}
+
+pub fn default_editor_connection_games() -> Vec<EditorConnectionGame> {
+	vec![EditorConnectionGame {
+		name: "Game".into(),
+		url: DEFAULT_EDITOR_SERVER_URL.into()
+	}]
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditorConnectionStatus {
+	Disabled,
+	Disconnected,
+	Connected
+}
+
+/// The state of the connection to a game's SDK editor server, for troubleshooting.
+#[derive(Type, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EditorConnectionDiagnostics {
+	pub game: EditorConnectionGame,
+	pub status: EditorConnectionStatus,
+
+	/// The version of the SDK, if it reported one when connecting
+	pub sdk_version: Option<String>,
+
+	/// The features the SDK reported supporting when connecting, if it reported any
+	pub capabilities: Option<Vec<String>>,
+
+	/// Failed attempts to connect since the connection was last established
+	pub failed_attempts: u32,
+
+	/// Seconds until the next attempt to connect, if one is scheduled
+	pub next_attempt_in: Option<u32>,
+
+	pub last_error: Option<String>
+}
+
+/// A connection to the editor server of a single game.
+struct GameConnection {
+	game: EditorConnectionGame,
+	sender: RwLock<Option<SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>>>,
 	events: broadcast::Sender<SDKEditorEvent>,
+	entity_tree_loaded: AtomicBool,
+	diagnostics: RwLock<EditorConnectionDiagnostics>,
+
+	/// When to next try to connect, and how long to wait after that if it fails
+	backoff: RwLock<(Instant, Duration)>
+}
+
+pub struct EditorConnection {
+	games: RwLock<Vec<Arc<GameConnection>>>,
+
+	/// The name of the game that commands like selecting entities and setting properties are sent to, or None to send
+	/// them to every connected game
+	target: RwLock<Option<String>>,
+
 	debounced_events: tokio::sync::mpsc::Sender<SDKEditorEvent>,
-	entity_tree_loaded: Arc<AtomicBool>,
+	reconnect_signal: Notify,
 	app: AppHandle
 }
 
+/// Keep the editor connection open to every configured game while it's enabled, reconnecting automatically whenever a
+/// connection is lost (like when the game is restarted). The delay between failed attempts doubles each time, up to a
+/// limit.
+pub async fn auto_reconnect(app: AppHandle) {
+	loop {
+		let app_state = app.state::<AppState>();
+		let editor_connection = &app_state.editor_connection;
+		let settings = app.state::<ArcSwap<AppSettings>>().load_full();
+
+		editor_connection.sync_games(&settings.editor_connection_games).await;
+
+		let games = editor_connection.games.read().await.to_owned();
+
+		for game in games {
+			if !settings.editor_connection {
+				*game.backoff.write().await = (Instant::now(), MIN_RECONNECT_DELAY);
+
+				if game.is_connected().await {
+					let _ = game.disconnect().await;
+				}
+
+				let _ = game
+					.update_diagnostics(&app, |x| {
+						x.status = EditorConnectionStatus::Disabled;
+						x.failed_attempts = 0;
+						x.next_attempt_in = None;
+					})
+					.await;
+			} else if game.is_connected().await {
+				*game.backoff.write().await = (Instant::now(), MIN_RECONNECT_DELAY);
+			} else {
+				let (next_attempt, delay) = *game.backoff.read().await;
+
+				if Instant::now() < next_attempt {
+					continue;
+				}
+
+				if let Err(e) = game.connect(&app, &editor_connection.debounced_events).await {
+					*game.backoff.write().await = (Instant::now() + delay, (delay * 2).min(MAX_RECONNECT_DELAY));
+
+					let _ = game
+						.update_diagnostics(&app, |x| {
+							x.status = EditorConnectionStatus::Disconnected;
+							x.failed_attempts += 1;
+							x.next_attempt_in = Some(delay.as_secs() as u32);
+							x.last_error = Some(format!("{e:#}"));
+						})
+						.await;
+				}
+			}
+		}
+
+		tokio::select! {
+			_ = sleep(MIN_RECONNECT_DELAY) => {}
+			_ = editor_connection.reconnect_signal.notified() => {}
+		}
+	}
+}
+
 impl EditorConnection {
 	pub fn new(app: AppHandle) -> Self {
-		let (sender, _) = broadcast::channel(32);
-
 		let (tx, rx) = tokio::sync::mpsc::channel(32);
 
 		let mut recvr = debounced(ReceiverStream::new(rx), Duration::from_millis(200));
@@ -417,167 +555,307 @@ impl EditorConnection {
 		});
 
 		Self {
-			sender: RwLock::new(None).into(),
-			events: sender,
-			entity_tree_loaded: AtomicBool::new(false).into(),
+			games: RwLock::new(vec![]),
+			target: RwLock::new(None),
 			debounced_events: tx,
+			reconnect_signal: Notify::new(),
 			app
 		}
 	}
 
+	/// Match the games being connected to with the configured list, disconnecting from any which were removed.
+	async fn sync_games(&self, games: &[EditorConnectionGame]) {
+		let removed = {
+			let mut current = self.games.write().await;
+
+			if current.iter().map(|x| &x.game).eq(games.iter()) {
+				return;
+			}
+
+			let (kept, removed): (Vec<_>, Vec<_>) = current.drain(..).partition(|x| games.contains(&x.game));
+
+			*current = games
+				.iter()
+				.map(|game| {
+					kept.iter()
+						.find(|x| x.game == *game)
+						.cloned()
+						.unwrap_or_else(|| GameConnection::new(game.to_owned()).into())
+				})
+				.collect();
+
+			removed
+		};
+
+		{
+			let mut target = self.target.write().await;
+
+			if target.as_ref().is_some_and(|x| !games.iter().any(|y| y.name == *x)) {
+				*target = None;
+			}
+		}
+
+		for game in removed {
+			let _ = game.disconnect().await;
+		}
+
+		let _ = self.broadcast_state().await;
+	}
+
+	/// Tell every open entity editor, and the settings panel, about the state of the editor connection.
 	#[try_fn]
-	#[context("Couldn't connect to editor server")]
-	pub async fn connect(&self) -> Result<()> {
-		let mut sender_guard = self.sender.write().await;
+	#[context("Couldn't broadcast editor connection state")]
+	async fn broadcast_state(&self) -> Result<()> {
+		let connected = self.is_connected().await;
+
+		for editor in self.app.state::<AppState>().editor_states.iter() {
+			if let EditorData::QNEntity { .. } | EditorData::QNPatch { .. } = editor.data {
+				send_request(
+					&self.app,
+					Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+						EntityTreeRequest::SetEditorConnectionAvailable {
+							editor_id: editor.key().to_owned(),
+							editor_connection_available: connected
+						}
+					)))
+				)?;
+
+				send_request(
+					&self.app,
+					Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
+						EntityMonacoRequest::SetEditorConnected {
+							editor_id: editor.key().to_owned(),
+							connected
+						}
+					)))
+				)?;
+			}
+		}
 
-		if sender_guard.is_none() {
-			let (ws_stream, _) = connect_async("ws://localhost:46735")
-				.await
-				.context("Couldn't connect to WebSocket server")?;
+		send_request(
+			&self.app,
+			Request::Tool(ToolRequest::Settings(SettingsRequest::SetEditorConnectionState {
+				games: self.get_diagnostics().await,
+				target: self.target.read().await.to_owned()
+			}))
+		)?;
+	}
 
-			let (mut write, read) = ws_stream.split();
+	pub async fn get_diagnostics(&self) -> Vec<EditorConnectionDiagnostics> {
+		let mut diagnostics = vec![];
 
-			let app = self.app.clone();
-			let sender = self.sender.clone();
-			let events = self.events.clone();
+		for game in self.games.read().await.iter() {
+			diagnostics.push(game.diagnostics.read().await.to_owned());
+		}
 
-			self.entity_tree_loaded.store(false, Ordering::SeqCst);
+		diagnostics
+	}
 
-			spawn(async move {
-				read.for_each(|msg| async {
-					match msg {
-						Ok(msg) => {
-							if let Err::<_, Error>(e) = try {
-								match msg {
-									Message::Ping(_) => {}
-									Message::Pong(_) => {}
+	pub async fn get_target(&self) -> Option<String> {
+		self.target.read().await.to_owned()
+	}
 
-									Message::Close(_) => {
-										sender.write().await.take();
-
-										for editor in app.state::<AppState>().editor_states.iter() {
-											if let EditorData::QNEntity { .. } | EditorData::QNPatch { .. } =
-												editor.data
-											{
-												send_request(
-													&app,
-													Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
-														EntityTreeRequest::SetEditorConnectionAvailable {
-															editor_id: editor.key().to_owned(),
-															editor_connection_available: false
-														}
-													)))
-												)?;
-
-												send_request(
-													&app,
-													Request::Editor(EditorRequest::Entity(
-														EntityEditorRequest::Monaco(
-															EntityMonacoRequest::SetEditorConnected {
-																editor_id: editor.key().to_owned(),
-																connected: false
-															}
-														)
-													))
-												)?;
-											}
-										}
+	/// Choose which game commands are sent to, or None to send them to every connected game.
+	#[try_fn]
+	#[context("Couldn't set editor connection target")]
+	pub async fn set_target(&self, target: Option<String>) -> Result<()> {
+		*self.target.write().await = target;
+		self.broadcast_state().await?;
+	}
 
-										send_notification(
-											&app,
-											Notification {
-												kind: NotificationKind::Info,
-												title: "Disconnected from ZHMModSDK editor".into(),
-												subtitle: "Editor integration features will no longer be available."
-													.into()
-											}
-										)?;
-									}
+	/// Skip the wait before the next attempt to reconnect to each game.
+	pub async fn reconnect_now(&self) {
+		for game in self.games.read().await.iter() {
+			*game.backoff.write().await = (Instant::now(), MIN_RECONNECT_DELAY);
+		}
 
-									_ => {
-										let msg = msg.to_text().context("Couldn't convert message to text")?;
+		self.reconnect_signal.notify_one();
+	}
 
-										let msg: SDKEditorEvent = serde_json::from_str(msg).with_context(|| {
-											format!("Couldn't parse message {msg:?} as SDKEditorEvent")
-										})?;
+	#[try_fn]
+	pub async fn disconnect(&self) -> Result<()> {
+		for game in self.games.read().await.iter() {
+			game.disconnect().await?;
+		}
+	}
 
-										// It's ok if there are no listeners
-										let _ = events.send(msg);
-									}
-								}
-							} {
-								send_request(
-									&app,
-									Request::Global(GlobalRequest::ErrorReport {
-										error: format!("{:?}", e.context("Editor connection message handling error"))
-									})
-								)
-								.expect("Couldn't send error report to frontend");
-							}
-						}
+	/// Whether any of the games that commands are sent to are connected.
+	pub async fn is_connected(&self) -> bool {
+		!self.get_targets().await.is_empty()
+	}
 
-						Err(_) => {
-							sender.write().await.take();
+	/// The connected games that commands are sent to.
+	async fn get_targets(&self) -> Vec<Arc<GameConnection>> {
+		let target = self.target.read().await.to_owned();
 
-							for editor in app.state::<AppState>().editor_states.iter() {
-								if let EditorData::QNEntity { .. } | EditorData::QNPatch { .. } = editor.data {
-									send_request(
-										&app,
-										Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
-											EntityTreeRequest::SetEditorConnectionAvailable {
-												editor_id: editor.key().to_owned(),
-												editor_connection_available: false
-											}
-										)))
-									)
-									.expect("Couldn't send data to frontend");
+		let mut targets = vec![];
 
-									send_request(
-										&app,
-										Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
-											EntityMonacoRequest::SetEditorConnected {
-												editor_id: editor.key().to_owned(),
-												connected: false
-											}
-										)))
-									)
-									.expect("Couldn't send data to frontend");
-								}
-							}
+		for game in self.games.read().await.iter() {
+			if target.as_ref().is_none_or(|x| *x == game.game.name) && game.is_connected().await {
+				targets.push(game.to_owned());
+			}
+		}
 
-							send_notification(
-								&app,
-								Notification {
-									kind: NotificationKind::Info,
-									title: "Disconnected from ZHMModSDK editor".into(),
-									subtitle: "Editor integration features will no longer be available.".into()
-								}
-							)
-							.expect("Couldn't send data to frontend");
-						}
-					}
-				})
-				.await;
-			});
+		targets
+	}
+
+	#[try_fn]
+	pub async fn select_entity(&self, entity_id: &str, tblu: &str) -> Result<()> {
+		for game in self.get_targets().await {
+			game.select_entity(entity_id, tblu).await?;
+		}
+	}
+
+	#[try_fn]
+	pub async fn get_player_transform(&self) -> Result<QNTransform> {
+		self.get_targets()
+			.await
+			.first()
+			.context("Not connected")?
+			.get_player_transform()
+			.await?
+	}
+
+	#[try_fn]
+	pub async fn get_camera_transform(&self) -> Result<QNTransform> {
+		self.get_targets()
+			.await
+			.first()
+			.context("Not connected")?
+			.get_camera_transform()
+			.await?
+	}
+
+	#[try_fn]
+	pub async fn set_property(&self, entity_id: &str, tblu: &str, property: &str, value: PropertyValue) -> Result<()> {
+		for game in self.get_targets().await {
+			game.set_property(entity_id, tblu, property, value.to_owned()).await?;
+		}
+	}
+
+	#[try_fn]
+	pub async fn signal_pin(&self, entity_id: &str, tblu: &str, pin: &str, output: bool) -> Result<()> {
+		for game in self.get_targets().await {
+			game.signal_pin(entity_id, tblu, pin, output).await?;
+		}
+	}
+
+	/// Ask the game to spawn a new entity; the SDK doesn't handle this yet, so new entities will only appear once it
+	/// does or the scene is reloaded.
+	#[try_fn]
+	pub async fn spawn_entity(&self, template: &str, entity_id: &str, name: &str) -> Result<()> {
+		for game in self.get_targets().await {
+			game.spawn_entity(template, entity_id, name).await?;
+		}
+	}
+}
+
+impl GameConnection {
+	fn new(game: EditorConnectionGame) -> Self {
+		let (events, _) = broadcast::channel(32);
+
+		Self {
+			diagnostics: RwLock::new(EditorConnectionDiagnostics {
+				game: game.to_owned(),
+				status: EditorConnectionStatus::Disconnected,
+				sdk_version: None,
+				capabilities: None,
+				failed_attempts: 0,
+				next_attempt_in: None,
+				last_error: None
+			}),
+			game,
+			sender: RwLock::new(None),
+			events,
+			entity_tree_loaded: AtomicBool::new(false),
+			backoff: RwLock::new((Instant::now(), MIN_RECONNECT_DELAY))
+		}
+	}
+
+	/// Change the connection's diagnostics, letting the frontend know if anything changed.
+	#[try_fn]
+	async fn update_diagnostics(
+		&self,
+		app: &AppHandle,
+		update: impl FnOnce(&mut EditorConnectionDiagnostics)
+	) -> Result<()> {
+		let changed = {
+			let mut diagnostics = self.diagnostics.write().await;
+
+			let previous = diagnostics.to_owned();
+			update(&mut diagnostics);
+
+			*diagnostics != previous
+		};
+
+		if changed {
+			app.state::<AppState>().editor_connection.broadcast_state().await?;
+		}
+	}
+
+	/// Record that the connection was lost and let the frontend know.
+	#[try_fn]
+	#[context("Couldn't handle editor connection being lost")]
+	async fn handle_disconnection(&self, app: &AppHandle) -> Result<()> {
+		self.update_diagnostics(app, |x| {
+			x.status = EditorConnectionStatus::Disconnected;
+			x.sdk_version = None;
+			x.capabilities = None;
+		})
+		.await?;
+
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Info,
+				title: format!("Disconnected from ZHMModSDK editor ({})", self.game.name),
+				subtitle: "Editor integration features will be unavailable until GlacierKit reconnects.".into(),
+				link: None
+			}
+		)?;
+	}
+
+	#[try_fn]
+	#[context("Couldn't connect to editor server of {}", self.game.name)]
+	async fn connect(
+		self: &Arc<Self>,
+		app: &AppHandle,
+		debounced_events: &tokio::sync::mpsc::Sender<SDKEditorEvent>
+	) -> Result<()> {
+		let mut sender_guard = self.sender.write().await;
+
+		if sender_guard.is_none() {
+			let (ws_stream, _) = connect_async(&self.game.url)
+				.await
+				.context("Couldn't connect to WebSocket server")?;
+
+			let (mut write, read) = ws_stream.split();
+
+			self.entity_tree_loaded.store(false, Ordering::SeqCst);
 
 			let mut receiver = self.events.subscribe();
 
-			let entity_tree_loaded = self.entity_tree_loaded.clone();
+			// Subscribed before saying hello so that the game's welcome can't arrive before anything is listening for it
+			let mut welcome_receiver = self.events.subscribe();
 
-			let app = self.app.clone();
+			let game = self.clone();
 
-			let debounced_events = self.debounced_events.clone();
+			let _app = app.clone();
+
+			let debounced_events = debounced_events.clone();
+
+			let event_loop = spawn(async move {
+				let app = _app;
 
-			spawn(async move {
 				loop {
 					if let Ok(evt) = receiver.recv().await {
 						match evt {
 							SDKEditorEvent::EntityTreeRebuilt => {
-								entity_tree_loaded.store(true, Ordering::SeqCst);
+								game.entity_tree_loaded.store(true, Ordering::SeqCst);
 							}
 
 							SDKEditorEvent::SceneClearing { .. } | SDKEditorEvent::SceneLoading { .. } => {
-								entity_tree_loaded.store(false, Ordering::SeqCst);
+								game.entity_tree_loaded.store(false, Ordering::SeqCst);
 							}
 
 							SDKEditorEvent::EntitySelected {
@@ -602,7 +880,7 @@ impl EditorConnection {
 									send_request(
 										&app,
 										Request::Global(GlobalRequest::ErrorReport {
-											error: format!("SDK editor error: {:?}", message)
+											error: format!("SDK editor error ({}): {:?}", game.game.name, message)
 										})
 									)
 									.expect("Couldn't send error report to frontend");
@@ -615,6 +893,66 @@ impl EditorConnection {
 				}
 			});
 
+			let game = self.clone();
+
+			let _app = app.clone();
+
+			spawn(async move {
+				let app = _app;
+
+				read.for_each(|msg| async {
+					match msg {
+						Ok(msg) => {
+							if let Err::<_, Error>(e) = try {
+								match msg {
+									Message::Ping(_) => {}
+									Message::Pong(_) => {}
+
+									Message::Close(_) => {
+										// The connection may have been given up on before it was established
+										if game.sender.write().await.take().is_some() {
+											game.handle_disconnection(&app).await?;
+										}
+									}
+
+									_ => {
+										let msg = msg.to_text().context("Couldn't convert message to text")?;
+
+										let msg: SDKEditorEvent = serde_json::from_str(msg).with_context(|| {
+											format!("Couldn't parse message {msg:?} as SDKEditorEvent")
+										})?;
+
+										// It's ok if there are no listeners
+										let _ = game.events.send(msg);
+									}
+								}
+							} {
+								send_request(
+									&app,
+									Request::Global(GlobalRequest::ErrorReport {
+										error: format!("{:?}", e.context("Editor connection message handling error"))
+									})
+								)
+								.expect("Couldn't send error report to frontend");
+							}
+						}
+
+						Err(_) => {
+							// The socket may already have been closed properly
+							if game.sender.write().await.take().is_some() {
+								game.handle_disconnection(&app)
+									.await
+									.expect("Couldn't send data to frontend");
+							}
+						}
+					}
+				})
+				.await;
+
+				// Events from a previous connection shouldn't be handled again after reconnecting
+				event_loop.abort();
+			});
+
 			write
 				.send(Message::Text(
 					serde_json::to_string(&SDKEditorRequest::Hello {
@@ -626,48 +964,61 @@ impl EditorConnection {
 
 			*sender_guard = Some(write);
 
-			self.wait_for_event(|evt| matches!(evt, SDKEditorEvent::Welcome))
-				.await?;
+			// The state broadcast below checks whether each game is connected
+			drop(sender_guard);
 
-			for editor in self.app.state::<AppState>().editor_states.iter() {
-				if let EditorData::QNEntity { .. } | EditorData::QNPatch { .. } = editor.data {
-					send_request(
-						&self.app,
-						Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
-							EntityTreeRequest::SetEditorConnectionAvailable {
-								editor_id: editor.key().to_owned(),
-								editor_connection_available: true
-							}
-						)))
-					)?;
-
-					send_request(
-						&self.app,
-						Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
-							EntityMonacoRequest::SetEditorConnected {
-								editor_id: editor.key().to_owned(),
-								connected: true
-							}
-						)))
-					)?;
+			let welcome = timeout(WELCOME_TIMEOUT, async {
+				loop {
+					if let SDKEditorEvent::Welcome { version, capabilities } =
+						welcome_receiver.recv().await.context("Event channel closed")?
+					{
+						return Ok::<_, Error>((version, capabilities));
+					}
 				}
-			}
+			})
+			.await
+			.context("The game didn't respond to the connection in time")
+			.and_then(|x| x);
+
+			let (version, capabilities) = match welcome {
+				Ok(welcome) => welcome,
+
+				Err(e) => {
+					// The connection can't be used without the welcome, so it's closed to be tried again later
+					if let Some(mut sender) = self.sender.write().await.take() {
+						let _ = sender.send(Message::Close(None)).await;
+					}
+
+					return Err(e);
+				}
+			};
+
+			self.update_diagnostics(app, |x| {
+				x.status = EditorConnectionStatus::Connected;
+				x.sdk_version = version;
+				x.capabilities = capabilities;
+				x.failed_attempts = 0;
+				x.next_attempt_in = None;
+				x.last_error = None;
+			})
+			.await?;
 
 			send_notification(
-				&self.app,
+				app,
 				Notification {
 					kind: NotificationKind::Info,
-					title: "Connected to ZHMModSDK editor".into(),
+					title: format!("Connected to ZHMModSDK editor ({})", self.game.name),
 					subtitle: "Selection and property changes will be synced automatically, and the entity context \
 					           menu now has additional options."
-						.into()
+						.into(),
+					link: None
 				}
 			)?;
 		}
 	}
 
 	#[try_fn]
-	pub async fn disconnect(&self) -> Result<()> {
+	async fn disconnect(&self) -> Result<()> {
 		let mut sender_guard = self.sender.write().await;
 
 		if sender_guard.is_some() {
@@ -681,7 +1032,7 @@ impl EditorConnection {
 		}
 	}
 
-	pub async fn is_connected(&self) -> bool {
+	async fn is_connected(&self) -> bool {
 		self.sender.read().await.is_some()
 	}
 
@@ -723,9 +1074,20 @@ impl EditorConnection {
 		}
 	}
 
+	#[try_fn]
+	#[context("Couldn't spawn entity {:?}", entity_id)]
+	async fn spawn_entity(&self, template: &str, entity_id: &str, name: &str) -> Result<()> {
+		self.send_request(SDKEditorRequest::SpawnEntity {
+			templateId: template.to_owned(),
+			entityId: entity_id.to_owned(),
+			name: name.to_owned()
+		})
+		.await?;
+	}
+
 	#[try_fn]
 	#[context("Couldn't select entity {:?}", entity_id)]
-	pub async fn select_entity(&self, entity_id: &str, tblu: &str) -> Result<()> {
+	async fn select_entity(&self, entity_id: &str, tblu: &str) -> Result<()> {
 		self.send_request(SDKEditorRequest::SelectEntity {
 			entity: EntitySelector::Game {
 				id: entity_id.to_owned(),
@@ -737,7 +1099,7 @@ impl EditorConnection {
 
 	#[try_fn]
 	#[context("Couldn't get player transform")]
-	pub async fn get_player_transform(&self) -> Result<QNTransform> {
+	async fn get_player_transform(&self) -> Result<QNTransform> {
 		let msg_id: i64 = rng().random();
 		self.send_request(SDKEditorRequest::GetHitmanEntity { msgId: Some(msg_id) })
 			.await?;
@@ -776,7 +1138,7 @@ impl EditorConnection {
 
 	#[try_fn]
 	#[context("Couldn't get camera transform")]
-	pub async fn get_camera_transform(&self) -> Result<QNTransform> {
+	async fn get_camera_transform(&self) -> Result<QNTransform> {
 		let msg_id: i64 = rng().random();
 		self.send_request(SDKEditorRequest::GetCameraEntity { msgId: Some(msg_id) })
 			.await?;
@@ -815,7 +1177,7 @@ impl EditorConnection {
 
 	#[try_fn]
 	#[context("Couldn't set property {property} on {entity_id}")]
-	pub async fn set_property(&self, entity_id: &str, tblu: &str, property: &str, value: PropertyValue) -> Result<()> {
+	async fn set_property(&self, entity_id: &str, tblu: &str, property: &str, value: PropertyValue) -> Result<()> {
 		if value.property_type == "SEntityTemplateReference" {
 			self.send_request(SDKEditorRequest::SetEntityProperty {
 				entity: EntitySelector::Game {
@@ -933,7 +1295,7 @@ impl EditorConnection {
 
 	#[try_fn]
 	#[context("Couldn't signal pin {pin} on {entity_id}")]
-	pub async fn signal_pin(&self, entity_id: &str, tblu: &str, pin: &str, output: bool) -> Result<()> {
+	async fn signal_pin(&self, entity_id: &str, tblu: &str, pin: &str, output: bool) -> Result<()> {
 		self.send_request(SDKEditorRequest::SignalEntityPin {
 			entity: EntitySelector::Game {
 				id: entity_id.to_owned(),
@@ -2,7 +2,6 @@ use std::str::FromStr;
 
 use anyhow::{Context, Result};
 use anyhow::{anyhow, bail};
-use dashmap::DashMap;
 use fn_error_context::context;
 use hashbrown::HashMap;
 use hitman_commons::game::GameVersion;
@@ -11,7 +10,10 @@ use hitman_commons::metadata::{ResourceType, RuntimeID};
 use hitman_commons::rpkg_tool::RpkgResourceMeta;
 use indexmap::IndexMap;
 use itertools::Itertools;
-use quickentity_rs::qn_structs::{Entity, FullRef, Ref, RefMaybeConstantValue, RefWithConstantValue, SubEntity};
+use quickentity_rs::patch_structs::PatchOperation;
+use quickentity_rs::qn_structs::{
+	Entity, FullRef, Property, Ref, RefMaybeConstantValue, RefWithConstantValue, SubEntity
+};
 use rand::rng;
 use rand::seq::IndexedRandom;
 use rayon::iter::IntoParallelRefIterator;
@@ -27,9 +29,11 @@ use velcro::vec;
 use crate::languages::get_language_map;
 use crate::rpkg::extract_entity;
 use crate::{
+	entity_cache::EntityCache,
 	model::EditorValidity,
 	ores_repo::RepositoryItem,
-	rpkg::{extract_latest_metadata, extract_latest_resource}
+	rpkg::{extract_latest_metadata, extract_latest_resource},
+	unit_conversion::parse_hex_colour
 };
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
@@ -77,6 +81,35 @@ pub enum ReverseReferenceData {
 	}
 }
 
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum OverrideKind {
+	PropertyOverride,
+	OverrideDelete,
+	PinConnectionOverride,
+	PinConnectionOverrideDelete
+}
+
+/// An entity targeted by an override, which may be local or in an external scene.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideTarget {
+	pub entity_id: String,
+	pub external_scene: Option<String>,
+	pub name: Option<String>
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideListing {
+	pub kind: OverrideKind,
+
+	/// The overridden property names, or the pins of a pin connection override
+	pub detail: String,
+
+	pub targets: Vec<OverrideTarget>
+}
+
 /// Get the local reference contained within a Ref, or None if it's an external or null reference.
 pub fn get_local_reference(reference: &Ref) -> Option<String> {
 	match reference {
@@ -94,6 +127,30 @@ pub fn get_local_reference(reference: &Ref) -> Option<String> {
 	}
 }
 
+/// Get a readable name for a factory, like `zspatialentity` for `[modules:/zspatialentity.class].pc_entitytype`,
+/// falling back to the hash if it isn't in the hash list.
+pub fn get_factory_name(factory: &str, hash_list: Option<&HashList>) -> String {
+	let path = if factory.starts_with('[') {
+		Some(factory.to_owned())
+	} else {
+		RuntimeID::from_any(factory)
+			.ok()
+			.and_then(|hash| hash_list?.entries.get(&hash)?.path.to_owned())
+	};
+
+	path.and_then(|path| {
+		path.split(']')
+			.next()?
+			.rsplit(['/', '?'])
+			.next()?
+			.split('.')
+			.next()
+			.filter(|x| !x.is_empty())
+			.map(|x| x.to_owned())
+	})
+	.unwrap_or_else(|| factory.to_owned())
+}
+
 #[try_fn]
 #[context("Couldn't calculate reverse references")]
 pub fn calculate_reverse_references(entity: &Entity) -> Result<HashMap<String, Vec<ReverseReference>>> {
@@ -328,6 +385,25 @@ pub fn get_recursive_children(
 	children
 }
 
+/// Get the local ancestors of a sub-entity, starting from the root of the tree and ending with its parent.
+pub fn get_local_ancestors(entity: &Entity, target: &str) -> Vec<String> {
+	let mut ancestors = vec![];
+	let mut current = entity.entities.get(target);
+
+	while let Some(parent) = current.and_then(|x| get_local_reference(&x.parent)) {
+		// Guard against cyclic parent references, which would otherwise loop forever
+		if parent == target || ancestors.contains(&parent) {
+			break;
+		}
+
+		current = entity.entities.get(&parent);
+		ancestors.push(parent);
+	}
+
+	ancestors.reverse();
+	ancestors
+}
+
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct CopiedEntityData {
@@ -572,9 +648,69 @@ pub fn check_local_references_exist(sub_entity: &SubEntity, entity: &Entity) ->
 	EditorValidity::Valid
 }
 
+/// List all the overrides in an entity, resolving the names of the entities they target.
+pub fn get_override_listings(
+	game_files: &PartitionManager,
+	cached_entities: &EntityCache,
+	game_version: GameVersion,
+	hash_list: &HashList,
+	entity: &Entity
+) -> Vec<OverrideListing> {
+	let get_target = |reference: &Ref| {
+		let (entity_id, external_scene) = match reference {
+			Ref::Short(Some(ent)) => (ent.to_owned(), None),
+			Ref::Full(reference) => (reference.entity_ref.to_owned(), reference.external_scene.to_owned()),
+			Ref::Short(None) => return None
+		};
+
+		Some(OverrideTarget {
+			entity_id,
+			external_scene,
+			name: get_ref_decoration(game_files, cached_entities, game_version, hash_list, entity, reference)
+				.map(|(_, name)| name)
+		})
+	};
+
+	let property_overrides = entity.property_overrides.iter().map(|x| OverrideListing {
+		kind: OverrideKind::PropertyOverride,
+		detail: x.properties.keys().join(", "),
+		targets: x.entities.iter().filter_map(get_target).collect()
+	});
+
+	let override_deletes = entity.override_deletes.iter().map(|x| OverrideListing {
+		kind: OverrideKind::OverrideDelete,
+		detail: String::new(),
+		targets: get_target(x).into_iter().collect()
+	});
+
+	let pin_connection_overrides = entity.pin_connection_overrides.iter().map(|x| OverrideListing {
+		kind: OverrideKind::PinConnectionOverride,
+		detail: format!("{} -> {}", x.from_pin, x.to_pin),
+		targets: [&x.from_entity, &x.to_entity]
+			.into_iter()
+			.filter_map(get_target)
+			.collect()
+	});
+
+	let pin_connection_override_deletes = entity.pin_connection_override_deletes.iter().map(|x| OverrideListing {
+		kind: OverrideKind::PinConnectionOverrideDelete,
+		detail: format!("{} -> {}", x.from_pin, x.to_pin),
+		targets: [&x.from_entity, &x.to_entity]
+			.into_iter()
+			.filter_map(get_target)
+			.collect()
+	});
+
+	property_overrides
+		.chain(override_deletes)
+		.chain(pin_connection_overrides)
+		.chain(pin_connection_override_deletes)
+		.collect()
+}
+
 pub fn get_ref_decoration(
 	game_files: &PartitionManager,
-	cached_entities: &DashMap<RuntimeID, Entity>,
+	cached_entities: &EntityCache,
 	game_version: GameVersion,
 	hash_list: &HashList,
 	entity: &Entity,
@@ -606,6 +742,107 @@ pub fn get_ref_decoration(
 	}
 }
 
+/// Describe the entity a reference to an external scene points to, as Markdown for a hover. Returns None for local
+/// references, or if the external scene couldn't be extracted.
+pub fn get_external_ref_details(
+	game_files: &PartitionManager,
+	cached_entities: &EntityCache,
+	game_version: GameVersion,
+	hash_list: &HashList,
+	reference: &Ref
+) -> Option<(String, String)> {
+	let Ref::Full(FullRef {
+		entity_ref,
+		external_scene: Some(external_scene),
+		..
+	}) = reference
+	else {
+		return None;
+	};
+
+	let scene_id = RuntimeID::from_any(external_scene).ok()?;
+
+	let external_entity = extract_entity(game_files, cached_entities, game_version, hash_list, scene_id).ok()?;
+	let sub_entity = external_entity.entities.get(entity_ref)?;
+
+	let scene_path = hash_list
+		.entries
+		.get(&scene_id)
+		.and_then(|x| x.path.to_owned())
+		.unwrap_or_else(|| external_scene.to_owned());
+
+	let mut details = format!(
+		"**{}** (`{}`)\n\nFactory: `{}`\n\nScene: `{}`",
+		sub_entity.name, entity_ref, sub_entity.factory, scene_path
+	);
+
+	if let Some(transform) = sub_entity
+		.properties
+		.as_ref()
+		.and_then(|x| x.get("m_mTransform"))
+		.map(|x| &x.value)
+	{
+		let format_vector = |key: &str| {
+			let vector = transform.get(key)?;
+
+			Some(
+				["x", "y", "z"]
+					.iter()
+					.map(|axis| {
+						vector
+							.get(axis)
+							.and_then(|x| x.as_f64())
+							.unwrap_or_default()
+							.to_string()
+					})
+					.join(", ")
+			)
+		};
+
+		if let Some(position) = format_vector("position") {
+			details.push_str(&format!("\n\nPosition: {}", position));
+		}
+
+		if let Some(rotation) = format_vector("rotation") {
+			details.push_str(&format!("\n\nRotation: {}", rotation));
+		}
+	}
+
+	Some((entity_ref.to_owned(), details))
+}
+
+/// Get the hover details of every reference to an external scene in a sub-entity's parent and reference properties.
+pub fn get_external_ref_hovers(
+	game_files: &PartitionManager,
+	cached_entities: &EntityCache,
+	game_version: GameVersion,
+	hash_list: &HashList,
+	sub_entity: &SubEntity
+) -> Vec<(String, String)> {
+	let mut references = vec![sub_entity.parent.to_owned()];
+
+	for property_data in sub_entity.properties.as_ref().unwrap_or(&Default::default()).values() {
+		if property_data.property_type == "SEntityTemplateReference" {
+			references.extend(from_value::<Ref>(property_data.value.to_owned()).ok());
+		} else if property_data.property_type == "TArray<SEntityTemplateReference>" {
+			references.extend(
+				from_value::<Vec<Ref>>(property_data.value.to_owned())
+					.ok()
+					.into_iter()
+					.flatten()
+			);
+		}
+	}
+
+	references
+		.iter()
+		.filter_map(|reference| {
+			get_external_ref_details(game_files, cached_entities, game_version, hash_list, reference)
+		})
+		.unique_by(|(id, _)| id.to_owned())
+		.collect()
+}
+
 #[try_fn]
 #[context("Couldn't get decoration for LINE {}", line)]
 pub fn get_line_decoration(
@@ -691,7 +928,7 @@ pub fn get_line_decoration(
 #[context("Couldn't get decorations for sub-entity {}", sub_entity.name)]
 pub fn get_decorations(
 	game_files: &PartitionManager,
-	cached_entities: &DashMap<RuntimeID, Entity>,
+	cached_entities: &EntityCache,
 	repository: &[RepositoryItem],
 	hash_list: &HashList,
 	game_version: GameVersion,
@@ -829,6 +1066,21 @@ pub fn get_decorations(
 					}
 				}
 			}
+		} else if property_data.property_type == "SColorRGB" || property_data.property_type == "SColorRGBA" {
+			if let Some(hex) = property_data.value.as_str()
+				&& let Some([r, g, b, a]) = parse_hex_colour(hex)
+			{
+				decorations.push((
+					hex.to_owned(),
+					format!(
+						"rgba({}, {}, {}, {})",
+						(r * 255.0).round(),
+						(g * 255.0).round(),
+						(b * 255.0).round(),
+						a
+					)
+				));
+			}
 		}
 	}
 
@@ -1138,6 +1390,167 @@ pub fn get_decorations(
 	decorations.into_iter().unique().collect()
 }
 
+/// Check whether any GUID or repository ID property of the entity (including platform-specific properties and
+/// overrides) refers to the given GUID.
+pub fn entity_uses_guid(entity: &Entity, guid: &str) -> bool {
+	let value_uses_guid = |property_type: &str, value: &Value| match property_type {
+		"ZGuid" | "ZRepositoryID" => value.as_str().is_some_and(|x| x.eq_ignore_ascii_case(guid)),
+
+		"TArray<ZGuid>" | "TArray<ZRepositoryID>" => value.as_array().is_some_and(|x| {
+			x.iter()
+				.any(|x| x.as_str().is_some_and(|x| x.eq_ignore_ascii_case(guid)))
+		}),
+
+		_ => false
+	};
+
+	entity.entities.values().any(|sub_entity| {
+		sub_entity
+			.properties
+			.as_ref()
+			.is_some_and(|x| x.values().any(|x| value_uses_guid(&x.property_type, &x.value)))
+			|| sub_entity.platform_specific_properties.as_ref().is_some_and(|x| {
+				x.values()
+					.flat_map(|x| x.values())
+					.any(|x| value_uses_guid(&x.property_type, &x.value))
+			})
+	}) || entity.property_overrides.iter().any(|x| {
+		x.properties
+			.values()
+			.any(|x| value_uses_guid(&x.property_type, &x.value))
+	})
+}
+
+/// A term of a tree search query which searches inside the properties of sub-entities.
+#[derive(Clone, Debug)]
+pub enum PropertySearchTerm {
+	/// `property:` - the property's name contains the given text.
+	Name(String),
+
+	/// `propertytype:` - the property's type contains the given text.
+	Type(String),
+
+	/// `value:` - some part of the property's value matches the given comparison.
+	Value(ValueComparison)
+}
+
+#[derive(Clone, Debug)]
+pub enum ValueComparison {
+	/// A string value contains the given text.
+	Contains(String),
+	Bool(bool),
+	Equal(f64),
+	GreaterThan(f64),
+	GreaterThanOrEqual(f64),
+	LessThan(f64),
+	LessThanOrEqual(f64)
+}
+
+impl ValueComparison {
+	fn parse(query: &str) -> Self {
+		let comparisons: [(&str, fn(f64) -> Self); 5] = [
+			(">=", ValueComparison::GreaterThanOrEqual),
+			("<=", ValueComparison::LessThanOrEqual),
+			(">", ValueComparison::GreaterThan),
+			("<", ValueComparison::LessThan),
+			("", ValueComparison::Equal)
+		];
+
+		let numeric = comparisons.into_iter().find_map(|(prefix, comparison)| {
+			query
+				.strip_prefix(prefix)
+				.and_then(|x| x.parse::<f64>().ok())
+				.map(comparison)
+		});
+
+		if let Some(numeric) = numeric {
+			numeric
+		} else if let Ok(x) = query.parse::<bool>() {
+			ValueComparison::Bool(x)
+		} else {
+			ValueComparison::Contains(query.to_owned())
+		}
+	}
+
+	/// Check the comparison against a value, recursing into arrays and objects so that (for example) a single component of an SVector3 can match.
+	fn matches(&self, value: &Value) -> bool {
+		match value {
+			Value::Array(x) => x.iter().any(|x| self.matches(x)),
+			Value::Object(x) => x.values().any(|x| self.matches(x)),
+
+			Value::Number(x) => x.as_f64().is_some_and(|x| match *self {
+				ValueComparison::Equal(y) => (x - y).abs() < 1e-6,
+				ValueComparison::GreaterThan(y) => x > y,
+				ValueComparison::GreaterThanOrEqual(y) => x >= y,
+				ValueComparison::LessThan(y) => x < y,
+				ValueComparison::LessThanOrEqual(y) => x <= y,
+				_ => false
+			}),
+
+			Value::Bool(x) => matches!(self, ValueComparison::Bool(y) if x == y),
+
+			Value::String(x) => matches!(self, ValueComparison::Contains(y) if x.to_lowercase().contains(y)),
+
+			Value::Null => false
+		}
+	}
+}
+
+impl PropertySearchTerm {
+	fn matches(&self, name: &str, property: &Property) -> bool {
+		match self {
+			PropertySearchTerm::Name(x) => name.to_lowercase().contains(x),
+			PropertySearchTerm::Type(x) => property.property_type.to_lowercase().contains(x),
+			PropertySearchTerm::Value(x) => x.matches(&property.value)
+		}
+	}
+}
+
+/// Split a (lowercase) tree search query into plain text terms and property search terms.
+pub fn parse_search_query(query: &str) -> (Vec<String>, Vec<PropertySearchTerm>) {
+	let mut text_terms = vec![];
+	let mut property_terms = vec![];
+
+	for term in query.split(' ').filter(|x| !x.is_empty()) {
+		if let Some(x) = term.strip_prefix("propertytype:") {
+			property_terms.push(PropertySearchTerm::Type(x.to_owned()));
+		} else if let Some(x) = term.strip_prefix("property:") {
+			property_terms.push(PropertySearchTerm::Name(x.to_owned()));
+		} else if let Some(x) = term.strip_prefix("value:") {
+			property_terms.push(PropertySearchTerm::Value(ValueComparison::parse(x)));
+		} else {
+			text_terms.push(term.to_owned());
+		}
+	}
+
+	(text_terms, property_terms)
+}
+
+/// Get the paths of all properties of the sub-entity (including platform-specific properties) which match every one of the given terms.
+pub fn get_matching_property_paths(sub_entity: &SubEntity, terms: &[PropertySearchTerm]) -> Vec<String> {
+	let property_matches = |name: &str, property: &Property| terms.iter().all(|x| x.matches(name, property));
+
+	sub_entity
+		.properties
+		.iter()
+		.flatten()
+		.filter(|(name, property)| property_matches(name, property))
+		.map(|(name, _)| format!("properties.{}", name))
+		.chain(
+			sub_entity
+				.platform_specific_properties
+				.iter()
+				.flatten()
+				.flat_map(|(platform, properties)| {
+					properties
+						.iter()
+						.filter(|(name, property)| property_matches(name, property))
+						.map(move |(name, _)| format!("platformSpecificProperties.{}.{}", platform, name))
+				})
+		)
+		.collect()
+}
+
 pub fn is_valid_entity_factory(resource_type: ResourceType) -> bool {
 	resource_type == "TEMP"
 		|| resource_type == "CPPT"
@@ -1163,6 +1576,17 @@ pub fn is_valid_entity_blueprint(resource_type: ResourceType) -> bool {
 		|| resource_type == "WSGB"
 }
 
+/// Get the RGBA value of each SColorRGB and SColorRGBA property of a sub-entity, for the colour picker.
+pub fn get_colours(sub_entity: &SubEntity) -> Vec<(String, [f64; 4])> {
+	sub_entity
+		.properties
+		.iter()
+		.flatten()
+		.filter(|(_, x)| x.property_type == "SColorRGB" || x.property_type == "SColorRGBA")
+		.filter_map(|(name, x)| Some((name.to_owned(), parse_hex_colour(x.value.as_str()?)?)))
+		.collect()
+}
+
 /// New, modified, removed (ID, name, parent, factory, has reverse parent refs)
 pub fn get_diff_info(
 	original: &Entity,
@@ -1229,3 +1653,75 @@ pub fn get_diff_info(
 		removed
 	)
 }
+
+/// Sub-entity keys which the QN format allows to be left out, and so can be removed when empty.
+const OMITTABLE_SUB_ENTITY_KEYS: [&str; 9] = [
+	"properties",
+	"platformSpecificProperties",
+	"events",
+	"inputCopying",
+	"outputCopying",
+	"propertyAliases",
+	"exposedEntities",
+	"exposedInterfaces",
+	"subsets"
+];
+
+/// The sub-entity a patch operation applies to, or None if it applies to the entity itself.
+pub fn get_operation_sub_entity(operation: &PatchOperation) -> Option<String> {
+	match operation {
+		PatchOperation::AddEntity(id, _)
+		| PatchOperation::RemoveEntityByID(id)
+		| PatchOperation::SubEntityOperation(id, _) => Some(id.to_owned()),
+
+		_ => None
+	}
+}
+
+fn is_empty_container(value: &Value) -> bool {
+	match value {
+		Value::Object(x) => x.is_empty(),
+		Value::Array(x) => x.is_empty(),
+		_ => false
+	}
+}
+
+/// Tidy a serialised entity.json to keep diffs small: sub-entity keys which are empty (like `properties: {}`) are
+/// removed, event and copying triggers with no targets are removed, and properties are sorted by name. Property values
+/// themselves are left alone, as an empty array or object can be a meaningful value.
+pub fn tidy_entity_json(entity: &mut Value) {
+	let Some(entities) = entity.get_mut("entities").and_then(|x| x.as_object_mut()) else {
+		return;
+	};
+
+	for sub_entity in entities.values_mut().filter_map(|x| x.as_object_mut()) {
+		for key in ["events", "inputCopying", "outputCopying"] {
+			if let Some(events) = sub_entity.get_mut(key).and_then(|x| x.as_object_mut()) {
+				for triggers in events.values_mut().filter_map(|x| x.as_object_mut()) {
+					triggers.retain(|_, targets| !is_empty_container(targets));
+				}
+
+				events.retain(|_, triggers| !is_empty_container(triggers));
+			}
+		}
+
+		if let Some(properties) = sub_entity.get_mut("properties").and_then(|x| x.as_object_mut()) {
+			properties.sort_keys();
+		}
+
+		if let Some(platforms) = sub_entity
+			.get_mut("platformSpecificProperties")
+			.and_then(|x| x.as_object_mut())
+		{
+			for properties in platforms.values_mut().filter_map(|x| x.as_object_mut()) {
+				properties.sort_keys();
+			}
+
+			platforms.retain(|_, properties| !is_empty_container(properties));
+			platforms.sort_keys();
+		}
+
+		sub_entity
+			.retain(|key, value| !(OMITTABLE_SUB_ENTITY_KEYS.contains(&key.as_str()) && is_empty_container(value)));
+	}
+}
@@ -0,0 +1,166 @@
+use std::{
+	ops::Deref,
+	sync::atomic::{AtomicU64, AtomicUsize, Ordering}
+};
+
+use dashmap::{
+	DashMap,
+	mapref::{entry::Entry, one::Ref}
+};
+use hitman_commons::metadata::RuntimeID;
+use quickentity_rs::qn_structs::Entity;
+
+pub fn default_entity_cache_max_entries() -> u32 {
+	5000
+}
+
+/// In megabytes.
+pub fn default_entity_cache_max_memory() -> u32 {
+	4096
+}
+
+/// Entities extracted from the game files, which evicts the least recently used once there are more than the configured
+/// number of them or they take up more than the configured amount of memory.
+///
+/// Reading through `get` marks an entity as used; entities which are borrowed elsewhere are never evicted, so the limits
+/// can be briefly exceeded.
+pub struct EntityCache {
+	entities: DashMap<RuntimeID, Entity>,
+
+	/// Entity -> (When it was last used, approximate size in bytes)
+	usage: DashMap<RuntimeID, (u64, usize)>,
+
+	/// Incremented whenever an entity is used, to order them by how recently they were used
+	clock: AtomicU64,
+
+	/// Approximate total size of the cached entities in bytes
+	memory: AtomicUsize,
+
+	/// 0 for no limit
+	max_entries: AtomicUsize,
+
+	/// In bytes; 0 for no limit
+	max_memory: AtomicUsize
+}
+
+impl Deref for EntityCache {
+	type Target = DashMap<RuntimeID, Entity>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.entities
+	}
+}
+
+impl EntityCache {
+	/// Create an empty cache with the given limits; the memory limit is in megabytes, and 0 means no limit.
+	pub fn new(max_entries: u32, max_memory: u32) -> Self {
+		Self {
+			entities: DashMap::new(),
+			usage: DashMap::new(),
+			clock: AtomicU64::new(0),
+			memory: AtomicUsize::new(0),
+			max_entries: AtomicUsize::new(max_entries as usize),
+			max_memory: AtomicUsize::new(max_memory as usize * 1024 * 1024)
+		}
+	}
+
+	/// Change the limits of the cache, evicting entities if it's now over them. The memory limit is in megabytes, and 0
+	/// means no limit.
+	pub fn set_limits(&self, max_entries: u32, max_memory: u32) {
+		self.max_entries.store(max_entries as usize, Ordering::Relaxed);
+		self.max_memory
+			.store(max_memory as usize * 1024 * 1024, Ordering::Relaxed);
+
+		self.evict(None);
+	}
+
+	/// Get a cached entity, marking it as recently used.
+	pub fn get(&self, id: &RuntimeID) -> Option<Ref<'_, RuntimeID, Entity>> {
+		let entity = self.entities.get(id)?;
+
+		if let Some(mut usage) = self.usage.get_mut(id) {
+			usage.0 = self.clock.fetch_add(1, Ordering::Relaxed);
+		}
+
+		Some(entity)
+	}
+
+	/// Cache an entity, evicting the least recently used entities if the cache is now over its limits. The size is an
+	/// approximation of how much memory the entity takes up, like the size of the binary data it was converted from.
+	pub fn insert(&self, id: RuntimeID, entity: Entity, size: usize) {
+		self.entities.insert(id, entity);
+
+		if let Some((_, (_, old_size))) = self.usage.remove(&id) {
+			self.memory.fetch_sub(old_size, Ordering::Relaxed);
+		}
+
+		self.usage
+			.insert(id, (self.clock.fetch_add(1, Ordering::Relaxed), size));
+		self.memory.fetch_add(size, Ordering::Relaxed);
+
+		self.evict(Some(id));
+	}
+
+	/// Remove an entity from the cache.
+	pub fn remove(&self, id: &RuntimeID) -> Option<(RuntimeID, Entity)> {
+		if let Some((_, (_, size))) = self.usage.remove(id) {
+			self.memory.fetch_sub(size, Ordering::Relaxed);
+		}
+
+		self.entities.remove(id)
+	}
+
+	/// Approximate total size of the cached entities in bytes.
+	pub fn memory(&self) -> usize {
+		self.memory.load(Ordering::Relaxed)
+	}
+
+	/// Remove every entity from the cache, returning how many there were and roughly how much memory they took up.
+	pub fn clear(&self) -> (usize, usize) {
+		let cleared = (self.entities.len(), self.memory.swap(0, Ordering::Relaxed));
+
+		self.entities.clear();
+		self.usage.clear();
+
+		cleared
+	}
+
+	/// Evict the least recently used entities until the cache is within its limits, other than the given entity.
+	fn evict(&self, keep: Option<RuntimeID>) {
+		let max_entries = self.max_entries.load(Ordering::Relaxed);
+		let max_memory = self.max_memory.load(Ordering::Relaxed);
+
+		let over_limits = || {
+			(max_entries != 0 && self.usage.len() > max_entries)
+				|| (max_memory != 0 && self.memory.load(Ordering::Relaxed) > max_memory)
+		};
+
+		if !over_limits() {
+			return;
+		}
+
+		let mut candidates = self
+			.usage
+			.iter()
+			.filter(|x| Some(*x.key()) != keep)
+			.map(|x| (*x.key(), x.value().0))
+			.collect::<Vec<_>>();
+
+		candidates.sort_unstable_by_key(|(_, last_used)| *last_used);
+
+		for (id, _) in candidates {
+			if !over_limits() {
+				break;
+			}
+
+			// Entities borrowed elsewhere (possibly by this thread) are skipped rather than waited on
+			if let Some(Entry::Occupied(entry)) = self.entities.try_entry(id) {
+				entry.remove();
+
+				if let Some((_, (_, size))) = self.usage.remove(&id) {
+					self.memory.fetch_sub(size, Ordering::Relaxed);
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,61 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+
+/// Where the community-maintained documentation database is fetched from on startup.
+pub const ENTITY_DOCS_ENDPOINT: &str = "https://hitman-resources.netlify.app/glacierkit/entity-docs.json";
+
+/// Documentation for a property or pin, shown when hovering over it in the entity editor.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct DocumentationEntry {
+	pub description: String,
+
+	/// The unit the value is in, like `metres` or `seconds`
+	#[serde(default)]
+	pub unit: Option<String>,
+
+	#[serde(default)]
+	pub example: Option<Value>
+}
+
+/// Documentation for the properties and pins of entity classes. Bundled, and refreshed from the community-maintained
+/// database on startup.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct EntityDocs {
+	/// Class -> Property -> Documentation
+	#[serde(default)]
+	pub properties: IndexMap<String, IndexMap<String, DocumentationEntry>>,
+
+	/// Class -> Pin -> Documentation
+	#[serde(default)]
+	pub pins: IndexMap<String, IndexMap<String, DocumentationEntry>>
+}
+
+/// Find the documentation for a name, preferring that of the given class and otherwise using the first class which
+/// documents it, since properties and pins are usually inherited from another class. Classes are compared without case,
+/// so the names given by `get_factory_name` can be used directly.
+fn find_entry<'a>(
+	entries: &'a IndexMap<String, IndexMap<String, DocumentationEntry>>,
+	class: Option<&str>,
+	name: &str
+) -> Option<&'a DocumentationEntry> {
+	class
+		.and_then(|class| {
+			entries
+				.iter()
+				.find(|(x, _)| x.eq_ignore_ascii_case(class))
+				.and_then(|(_, x)| x.get(name))
+		})
+		.or_else(|| entries.values().find_map(|x| x.get(name)))
+}
+
+impl EntityDocs {
+	pub fn get_property(&self, class: Option<&str>, name: &str) -> Option<&DocumentationEntry> {
+		find_entry(&self.properties, class, name)
+	}
+
+	pub fn get_pin(&self, class: Option<&str>, name: &str) -> Option<&DocumentationEntry> {
+		find_entry(&self.pins, class, name)
+	}
+}
@@ -4,7 +4,7 @@ use anyhow::{Context, Result, anyhow};
 use arc_swap::ArcSwap;
 use fn_error_context::context;
 use hashbrown::{HashMap, HashSet};
-use hitman_commons::{game::GameVersion, metadata::RuntimeID, rpkg_tool::RpkgResourceMeta};
+use hitman_commons::{metadata::RuntimeID, rpkg_tool::RpkgResourceMeta};
 use hitman_formats::ores::{parse_hashes_ores, parse_json_ores};
 use itertools::Itertools;
 use quickentity_rs::convert_to_qn;
@@ -13,22 +13,19 @@ use regex::bytes::Regex;
 use rpkg_rs::resource::runtime_resource_id::RuntimeResourceID;
 use serde::Serialize;
 use serde_json::{to_string, to_vec};
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Manager, async_runtime::block_on};
 use tonytools::hmlanguages;
 use tryvial::try_fn;
 use uuid::Uuid;
 
 use crate::{
-	finish_task, get_loaded_game_version,
+	Notification, NotificationKind, finish_task, get_loaded_game_version,
 	languages::get_language_map,
 	model::{AppSettings, AppState, EditorData, EditorState, EditorType, GlobalRequest, Request},
-	resourcelib::{
-		convert_generic_str, h2_convert_binary_to_blueprint, h2_convert_binary_to_factory,
-		h3_convert_binary_to_blueprint, h3_convert_binary_to_factory, h2016_convert_binary_to_blueprint,
-		h2016_convert_binary_to_factory
-	},
-	rpkg::extract_latest_resource,
-	send_request, start_task
+	resourcelib::{convert_async, convert_generic_str},
+	rpkg::{convert_blueprint, convert_factory, extract_latest_resource},
+	send_notification, send_request,
+	tasks::{start_cancellable_task, update_task}
 };
 
 #[try_fn]
@@ -38,7 +35,8 @@ pub fn start_content_search(
 	query: String,
 	filetypes: Vec<String>,
 	use_qn_format: bool,
-	partitions_to_search: Vec<String>
+	partitions_to_search: Vec<String>,
+	convert_with_resourcelib: bool
 ) -> Result<()> {
 	let app_settings = app.state::<ArcSwap<AppSettings>>();
 	let app_state = app.state::<AppState>();
@@ -72,17 +70,23 @@ pub fn start_content_search(
 
 		let total_resources = resources.len();
 
-		let mut progress_task = start_task(app, format!("Searching game files for \"{query}\": 0%"))?;
+		let task = start_cancellable_task(app, format!("Searching game files for \"{query}\": 0%"))?;
 		let mut last_percent = 0;
 
 		let start_time = Instant::now();
 
+		// The search runs on the global pool, so that only the ResourceLib conversions themselves take up the (much
+		// smaller) conversion pool
 		for (progress, chunk) in resources.into_iter().chunks(1000).into_iter().enumerate() {
 			matching_ids.par_extend(
 				chunk
 					.collect_vec()
 					.into_par_iter()
 					.filter(|(resource_id, (partition, resource_info))| {
+						if task.is_cancelled() {
+							return false;
+						}
+
 						let filetype = resource_info.data_type();
 
 						if filetypes.contains(&filetype) {
@@ -95,15 +99,10 @@ pub fn start_content_search(
 												RpkgResourceMeta::from(*resource_info)
 											);
 
-											let factory = match game_version {
-												GameVersion::H1 => {
-													h2016_convert_binary_to_factory(&temp_data).ok()?.into_modern()
-												}
-
-												GameVersion::H2 => h2_convert_binary_to_factory(&temp_data).ok()?,
-
-												GameVersion::H3 => h3_convert_binary_to_factory(&temp_data).ok()?
-											};
+											let factory = block_on(convert_async(move || {
+												convert_factory(&temp_data, game_version)
+											}))
+											.ok()?;
 
 											let blueprint_hash = &temp_meta
 												.hash_reference_data
@@ -117,15 +116,10 @@ pub fn start_content_search(
 												RpkgResourceMeta::from(partition.get_resource_info(&tblu_rrid).ok()?)
 											);
 
-											let blueprint = match game_version {
-												GameVersion::H1 => {
-													h2016_convert_binary_to_blueprint(&tblu_data).ok()?.into_modern()
-												}
-
-												GameVersion::H2 => h2_convert_binary_to_blueprint(&tblu_data).ok()?,
-
-												GameVersion::H3 => h3_convert_binary_to_blueprint(&tblu_data).ok()?
-											};
+											let blueprint = block_on(convert_async(move || {
+												convert_blueprint(&tblu_data, game_version)
+											}))
+											.ok()?;
 
 											let entity =
 												convert_to_qn(&factory, &temp_meta, &blueprint, &tblu_meta, false)
@@ -135,15 +129,10 @@ pub fn start_content_search(
 										} else {
 											let temp_data = partition.read_resource(resource_id).ok()?;
 
-											let factory = match game_version {
-												GameVersion::H1 => {
-													h2016_convert_binary_to_factory(&temp_data).ok()?.into_modern()
-												}
-
-												GameVersion::H2 => h2_convert_binary_to_factory(&temp_data).ok()?,
-
-												GameVersion::H3 => h3_convert_binary_to_factory(&temp_data).ok()?
-											};
+											let factory = block_on(convert_async(move || {
+												convert_factory(&temp_data, game_version)
+											}))
+											.ok()?;
 
 											let (tblu_rrid, _) = &resource_info
 												.references()
@@ -151,15 +140,10 @@ pub fn start_content_search(
 
 											let tblu_data = partition.read_resource(tblu_rrid).ok()?;
 
-											let blueprint = match game_version {
-												GameVersion::H1 => {
-													h2016_convert_binary_to_blueprint(&tblu_data).ok()?.into_modern()
-												}
-
-												GameVersion::H2 => h2_convert_binary_to_blueprint(&tblu_data).ok()?,
-
-												GameVersion::H3 => h3_convert_binary_to_blueprint(&tblu_data).ok()?
-											};
+											let blueprint = block_on(convert_async(move || {
+												convert_blueprint(&tblu_data, game_version)
+											}))
+											.ok()?;
 
 											let mut s = to_vec(&factory).ok()?;
 											s.append(&mut to_vec(&blueprint).ok()?);
@@ -174,15 +158,17 @@ pub fn start_content_search(
 								"AIRG" | "ATMD" | "VIDB" | "UICB" | "CPPT" | "CRMD" | "DSWB" | "WSWB" | "GFXF"
 								| "GIDX" | "WSGB" | "ECPB" | "ENUM" => {
 									let s: Option<_> = try {
-										convert_generic_str(
-											&partition.read_resource(resource_id).ok()?,
-											game_version,
-											if filetype == "WSWB" {
-												"DSWB".try_into().ok()?
-											} else {
-												filetype.try_into().ok()?
-											}
-										)
+										let data = partition.read_resource(resource_id).ok()?;
+
+										let resource_type = if filetype == "WSWB" {
+											"DSWB".try_into().ok()?
+										} else {
+											filetype.try_into().ok()?
+										};
+
+										block_on(convert_async(move || {
+											convert_generic_str(&data, game_version, resource_type)
+										}))
 										.ok()?
 									};
 
@@ -527,7 +513,26 @@ pub fn start_content_search(
 									}
 								}
 
-								_ => false
+								// Types without dedicated handling are searched as raw data, or as ResourceLib JSON if
+								// requested so that property names and enum values which aren't stored literally are found
+								_ => {
+									let s: Option<_> = try {
+										let data = partition.read_resource(resource_id).ok()?;
+
+										if convert_with_resourcelib
+											&& let Ok(resource_type) = filetype.try_into()
+											&& let Ok(converted) = block_on(convert_async({
+												let data = data.to_owned();
+												move || convert_generic_str(&data, game_version, resource_type)
+											})) {
+											converted.into_bytes()
+										} else {
+											data
+										}
+									};
+
+									if let Some(s) = s { query.is_match(&s) } else { false }
+								}
 							}
 						} else {
 							false
@@ -536,17 +541,32 @@ pub fn start_content_search(
 					.map(|(x, _)| x.to_hex_string())
 			);
 
+			if task.is_cancelled() {
+				finish_task(app, task.id)?;
+
+				send_notification(
+					app,
+					Notification {
+						kind: NotificationKind::Info,
+						title: "Search cancelled".into(),
+						subtitle: format!("The search for \"{query}\" was cancelled."),
+						link: None
+					}
+				)?;
+
+				return Ok(());
+			}
+
 			let percent = ((((progress * 1000) as f32) / (total_resources as f32)) * 100.0).round() as u8;
 			if percent != last_percent {
 				last_percent = percent;
 
-				finish_task(app, progress_task)?;
-
 				let secs_remaining = ((Instant::now() - start_time).as_secs_f32() / ((progress * 1000) as f32)
 					* ((total_resources - (progress * 1000)) as f32)) as u64;
 
-				progress_task = start_task(
+				update_task(
 					app,
+					task.id,
 					format!(
 						"Searching game files for \"{}\": {}%, {}{} remaining",
 						query,
@@ -558,8 +578,7 @@ pub fn start_content_search(
 			}
 		}
 
-		finish_task(app, progress_task)?;
-		progress_task = start_task(app, format!("Preparing search results for \"{}\"", query))?;
+		update_task(app, task.id, format!("Preparing search results for \"{}\"", query))?;
 
 		let results = matching_ids
 			.into_iter()
@@ -598,6 +617,6 @@ pub fn start_content_search(
 			})
 		)?;
 
-		finish_task(app, progress_task)?;
+		finish_task(app, task.id)?;
 	}
 }
@@ -0,0 +1,476 @@
+use std::ops::Deref;
+
+use anyhow::{Context, Result, anyhow, bail};
+use arc_swap::ArcSwap;
+use fn_error_context::context;
+use serde_json::{Value, json};
+use tauri::{AppHandle, Manager};
+use tonytools::hmlanguages;
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::{
+	get_loaded_game_version,
+	languages::get_language_map,
+	model::{
+		AppSettings, AppState, DialogueContainer, DialogueContainerType, DialogueEditorEvent, DialogueEditorRequest,
+		DialogueIssue, EditorData, EditorRequest, GlobalRequest, Request
+	},
+	send_request
+};
+
+/// The property of a DLGE JSON file which holds the root container.
+const ROOT_CONTAINER: &str = "rootContainer";
+
+/// The types of container which can be children of a given type of container.
+fn allowed_children(container_type: DialogueContainerType) -> &'static [DialogueContainerType] {
+	match container_type {
+		DialogueContainerType::WavFile => &[],
+		DialogueContainerType::Random => &[DialogueContainerType::WavFile],
+		DialogueContainerType::Switch => &[DialogueContainerType::WavFile, DialogueContainerType::Random],
+		DialogueContainerType::Sequence => &[
+			DialogueContainerType::WavFile,
+			DialogueContainerType::Random,
+			DialogueContainerType::Switch
+		]
+	}
+}
+
+fn get_container_type(container: &Value) -> Option<DialogueContainerType> {
+	match container.get("type")?.as_str()? {
+		"WavFile" => Some(DialogueContainerType::WavFile),
+		"Random" => Some(DialogueContainerType::Random),
+		"Switch" => Some(DialogueContainerType::Switch),
+		"Sequence" => Some(DialogueContainerType::Sequence),
+		_ => None
+	}
+}
+
+/// Convert a container from the DLGE JSON into the tree shown in the editor.
+#[try_fn]
+#[context("Couldn't read dialogue container")]
+pub fn get_container_tree(container: &Value) -> Result<DialogueContainer> {
+	let container_type = get_container_type(container).context("Unknown container type")?;
+
+	let mut properties = container.as_object().context("Container is not an object")?.to_owned();
+	properties.remove("type");
+
+	let children = match properties.remove("containers") {
+		Some(Value::Array(children)) => children.iter().map(get_container_tree).collect::<Result<_>>()?,
+		Some(_) => bail!("Container children must be an array"),
+		None => vec![]
+	};
+
+	DialogueContainer {
+		container_type,
+		properties: Value::Object(properties),
+		children
+	}
+}
+
+/// Get the root container of a DLGE JSON file as a tree.
+#[try_fn]
+#[context("Couldn't read dialogue")]
+pub fn get_dialogue_tree(dialogue: &Value) -> Result<DialogueContainer> {
+	get_container_tree(dialogue.get(ROOT_CONTAINER).context("No root container")?)?
+}
+
+/// Check the structure of a dialogue tree, returning any problems which would prevent it from being rebuilt.
+pub fn validate_dialogue(root: &DialogueContainer) -> Vec<DialogueIssue> {
+	let mut issues = vec![];
+	validate_container(root, None, &mut vec![], &mut issues);
+	issues
+}
+
+fn validate_container(
+	container: &DialogueContainer,
+	parent: Option<DialogueContainerType>,
+	path: &mut Vec<u32>,
+	issues: &mut Vec<DialogueIssue>
+) {
+	let mut messages = vec![];
+
+	let has_string = |property: &str| {
+		container
+			.properties
+			.get(property)
+			.and_then(|x| x.as_str())
+			.is_some_and(|x| !x.is_empty())
+	};
+
+	if let Some(parent) = parent
+		&& !allowed_children(parent).contains(&container.container_type)
+	{
+		messages.push(format!(
+			"{:?} containers can't contain {:?} containers",
+			parent, container.container_type
+		));
+	}
+
+	match parent {
+		Some(DialogueContainerType::Random) if container.properties.get("weight").is_none() => {
+			messages.push("Containers in a Random container must have a weight".into());
+		}
+
+		Some(DialogueContainerType::Switch)
+			if !container
+				.properties
+				.get("cases")
+				.and_then(|x| x.as_array())
+				.is_some_and(|x| !x.is_empty()) =>
+		{
+			messages.push("Containers in a Switch container must have at least one case".into());
+		}
+
+		_ => {}
+	}
+
+	match container.container_type {
+		DialogueContainerType::WavFile => {
+			for property in ["wavName", "soundtag"] {
+				if !has_string(property) {
+					messages.push(format!("WavFile containers must have a {property}"));
+				}
+			}
+		}
+
+		DialogueContainerType::Switch => {
+			if !has_string("switchKey") {
+				messages.push("Switch containers must have a switchKey".into());
+			}
+		}
+
+		DialogueContainerType::Random | DialogueContainerType::Sequence => {}
+	}
+
+	if container.container_type != DialogueContainerType::WavFile && container.children.is_empty() {
+		messages.push(format!(
+			"{:?} containers must have at least one child",
+			container.container_type
+		));
+	}
+
+	issues.extend(messages.into_iter().map(|message| DialogueIssue {
+		path: path.to_owned(),
+		message
+	}));
+
+	for (index, child) in container.children.iter().enumerate() {
+		path.push(index as u32);
+		validate_container(child, Some(container.container_type), path, issues);
+		path.pop();
+	}
+}
+
+/// Get the container at a path of child indices from the root container.
+fn get_container_mut<'a>(dialogue: &'a mut Value, path: &[u32]) -> Option<&'a mut Value> {
+	let mut container = dialogue.get_mut(ROOT_CONTAINER)?;
+
+	for &index in path {
+		container = container.get_mut("containers")?.get_mut(index as usize)?;
+	}
+
+	Some(container)
+}
+
+/// Add or remove the properties which depend on a container's parent: weights in Random containers and cases in Switch
+/// containers.
+fn adapt_to_parent(container: &mut Value, parent: DialogueContainerType) {
+	let Some(container) = container.as_object_mut() else {
+		return;
+	};
+
+	if parent == DialogueContainerType::Random {
+		container.entry("weight").or_insert(json!(1.0));
+	} else {
+		container.remove("weight");
+	}
+
+	if parent == DialogueContainerType::Switch {
+		container.entry("cases").or_insert(json!([]));
+	} else {
+		container.remove("cases");
+	}
+}
+
+fn new_container(container_type: DialogueContainerType) -> Value {
+	match container_type {
+		DialogueContainerType::WavFile => json!({
+			"type": "WavFile",
+			"wavName": "",
+			"soundtag": "",
+			"languages": {}
+		}),
+
+		DialogueContainerType::Random => json!({
+			"type": "Random",
+			"containers": []
+		}),
+
+		DialogueContainerType::Switch => json!({
+			"type": "Switch",
+			"switchKey": "",
+			"default": "",
+			"containers": []
+		}),
+
+		DialogueContainerType::Sequence => json!({
+			"type": "Sequence",
+			"containers": []
+		})
+	}
+}
+
+/// Insert a container into the children of the container at the given path, checking that it can be contained there.
+#[try_fn]
+#[context("Couldn't insert dialogue container")]
+fn insert_container(dialogue: &mut Value, parent: &[u32], index: Option<usize>, mut container: Value) -> Result<()> {
+	let parent = get_container_mut(dialogue, parent).context("No such parent container")?;
+
+	let parent_type = get_container_type(parent).context("Unknown parent container type")?;
+	let container_type = get_container_type(&container).context("Unknown container type")?;
+
+	if !allowed_children(parent_type).contains(&container_type) {
+		bail!(
+			"{:?} containers can't contain {:?} containers",
+			parent_type,
+			container_type
+		);
+	}
+
+	adapt_to_parent(&mut container, parent_type);
+
+	let children = parent
+		.as_object_mut()
+		.context("Parent container is not an object")?
+		.entry("containers")
+		.or_insert(json!([]))
+		.as_array_mut()
+		.context("Container children must be an array")?;
+
+	children.insert(index.unwrap_or(children.len()).min(children.len()), container);
+}
+
+/// Remove the container at the given path, returning it.
+#[try_fn]
+#[context("Couldn't remove dialogue container")]
+fn remove_container(dialogue: &mut Value, path: &[u32]) -> Result<Value> {
+	let (&index, parent) = path.split_last().context("The root container can't be removed")?;
+
+	let children = get_container_mut(dialogue, parent)
+		.and_then(|x| x.get_mut("containers"))
+		.and_then(|x| x.as_array_mut())
+		.context("No such parent container")?;
+
+	if index as usize >= children.len() {
+		bail!("No such container");
+	}
+
+	children.remove(index as usize)
+}
+
+/// Send the dialogue tree of an editor to the frontend, along with any problems with its structure.
+#[try_fn]
+#[context("Couldn't update dialogue")]
+fn update_dialogue(app: &AppHandle, id: Uuid, dialogue: &Value) -> Result<()> {
+	let root = get_dialogue_tree(dialogue)?;
+	let issues = validate_dialogue(&root);
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Dialogue(DialogueEditorRequest::SetDialogue {
+			id,
+			root,
+			issues
+		}))
+	)?;
+}
+
+/// Check that a dialogue can be rebuilt into a DLGE with TonyTools, so that problems are found on save rather than when
+/// the mod is deployed.
+#[try_fn]
+#[context("Couldn't rebuild dialogue")]
+pub fn rebuild_dialogue(app: &AppHandle, dialogue: &Value) -> Result<()> {
+	let app_state = app.state::<AppState>();
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+
+	if let Some(issue) = validate_dialogue(&get_dialogue_tree(dialogue)?).into_iter().next() {
+		bail!("Invalid dialogue structure: {}", issue.message);
+	}
+
+	let game_version = get_loaded_game_version(
+		app,
+		app_settings.load().game_install.as_ref().context("No game selected")?
+	)?;
+
+	let hash_list = app_state
+		.tonytools_hash_list
+		.load()
+		.as_ref()
+		.context("No TonyTools hash list available")?
+		.deref()
+		.to_owned();
+
+	let json = serde_json::to_string(dialogue)?;
+
+	// Custom language maps are read from the JSON, otherwise each of the game's language maps is tried in turn
+	let mut first_error = None;
+	let mut iteration = 0;
+
+	while let Some((_, langmap)) = get_language_map(game_version, iteration) {
+		let mut dlge = hmlanguages::dlge::DLGE::new(hash_list.to_owned(), game_version.into(), langmap, None, false)
+			.map_err(|x| anyhow!("TonyTools error: {x:?}"))?;
+
+		match dlge.rebuild(json.to_owned()) {
+			Ok(_) => return Ok(()),
+			Err(e) => {
+				first_error.get_or_insert(e);
+			}
+		}
+
+		iteration += 1;
+	}
+
+	bail!(
+		"TonyTools error: {:?}",
+		first_error.context("No language maps available")?
+	);
+}
+
+#[try_fn]
+#[context("Couldn't handle dialogue editor event")]
+pub async fn handle_dialogue_event(app: &AppHandle, event: DialogueEditorEvent) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	match event {
+		DialogueEditorEvent::Initialise { id } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let dialogue = match editor_state.data {
+				EditorData::Dialogue { ref dialogue } => dialogue,
+
+				_ => {
+					bail!("Editor {} is not a dialogue editor", id);
+				}
+			};
+
+			update_dialogue(app, id, dialogue)?;
+		}
+
+		DialogueEditorEvent::AddContainer {
+			id,
+			parent,
+			container_type
+		} => {
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let dialogue = match editor_state.data {
+				EditorData::Dialogue { ref mut dialogue } => dialogue,
+
+				_ => {
+					bail!("Editor {} is not a dialogue editor", id);
+				}
+			};
+
+			insert_container(dialogue, &parent, None, new_container(container_type))?;
+
+			update_dialogue(app, id, dialogue)?;
+
+			send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+		}
+
+		DialogueEditorEvent::RemoveContainer { id, path } => {
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let dialogue = match editor_state.data {
+				EditorData::Dialogue { ref mut dialogue } => dialogue,
+
+				_ => {
+					bail!("Editor {} is not a dialogue editor", id);
+				}
+			};
+
+			remove_container(dialogue, &path)?;
+
+			update_dialogue(app, id, dialogue)?;
+
+			send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+		}
+
+		DialogueEditorEvent::MoveContainer {
+			id,
+			path,
+			mut new_parent,
+			index
+		} => {
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let dialogue = match editor_state.data {
+				EditorData::Dialogue { ref mut dialogue } => dialogue,
+
+				_ => {
+					bail!("Editor {} is not a dialogue editor", id);
+				}
+			};
+
+			if new_parent.starts_with(&path) {
+				bail!("A container can't be moved into itself");
+			}
+
+			let (&old_index, old_parent) = path.split_last().context("The root container can't be moved")?;
+
+			// Removing the container shifts its later siblings, which may include the new parent or one of its ancestors
+			if new_parent.len() > old_parent.len()
+				&& new_parent.starts_with(old_parent)
+				&& new_parent[old_parent.len()] > old_index
+			{
+				new_parent[old_parent.len()] -= 1;
+			}
+
+			// Restore the original tree if the container can't be inserted at its new location
+			let original = dialogue.to_owned();
+
+			let container = remove_container(dialogue, &path)?;
+
+			if let Err(e) = insert_container(dialogue, &new_parent, Some(index as usize), container) {
+				*dialogue = original;
+				return Err(e);
+			}
+
+			update_dialogue(app, id, dialogue)?;
+
+			send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+		}
+
+		DialogueEditorEvent::UpdateContainer { id, path, properties } => {
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let dialogue = match editor_state.data {
+				EditorData::Dialogue { ref mut dialogue } => dialogue,
+
+				_ => {
+					bail!("Editor {} is not a dialogue editor", id);
+				}
+			};
+
+			let properties = properties.as_object().context("Properties must be an object")?;
+
+			let container = get_container_mut(dialogue, &path)
+				.and_then(|x| x.as_object_mut())
+				.context("No such container")?;
+
+			container.retain(|key, _| key.as_str() == "type" || key.as_str() == "containers");
+
+			container.extend(
+				properties
+					.iter()
+					.filter(|(key, _)| key.as_str() != "type" && key.as_str() != "containers")
+					.map(|(key, value)| (key.to_owned(), value.to_owned()))
+			);
+
+			update_dialogue(app, id, dialogue)?;
+
+			send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+		}
+	}
+}
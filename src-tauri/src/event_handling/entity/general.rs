@@ -1,16 +1,47 @@
+use std::fs;
+
 use anyhow::{Context, Result, anyhow};
+use arc_swap::ArcSwap;
 use fn_error_context::context;
+use hitman_commons::metadata::RuntimeID;
+use quickentity_rs::{generate_patch, qn_structs::Entity};
+use serde_json::to_string;
 use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
 use tryvial::try_fn;
 
 use crate::{
-	model::{AppState, EditorData, EditorRequest, EntityEditorRequest, EntityGeneralEvent, EntityTreeRequest, Request},
-	send_request
+	Notification, NotificationKind,
+	biome::format_json,
+	entity::get_diff_info,
+	get_loaded_game_version,
+	model::{
+		AppSettings, AppState, EditorData, EditorRequest, EntityEditorRequest, EntityGeneralEvent, EntityTreeRequest,
+		GlobalRequest, Request
+	},
+	rpkg::extract_entity_async,
+	scene_outline::{OutlineFormat, render_scene_outline},
+	send_notification, send_request,
+	split_view::{get_tab_state, get_tab_state_mut}
 };
 
+/// Get the size of a patch as it would be saved, formatted unless it's too large to format quickly.
+#[try_fn]
+#[context("Couldn't get patch size")]
+fn get_saved_patch_size(base: &Entity, current: &Entity) -> Result<usize> {
+	let unformatted = to_string(&generate_patch(base, current).map_err(|x| anyhow!(x))?)?;
+
+	if unformatted.len() < 1024 * 1024 {
+		format_json(&unformatted)?.len()
+	} else {
+		unformatted.len()
+	}
+}
+
 #[try_fn]
 #[context("Couldn't handle update content event")]
 pub async fn handle(app: &AppHandle, event: EntityGeneralEvent) -> Result<()> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
 	let app_state = app.state::<AppState>();
 
 	match event {
@@ -18,7 +49,7 @@ pub async fn handle(app: &AppHandle, event: EntityGeneralEvent) -> Result<()> {
 			editor_id,
 			show_reverse_parent_refs
 		} => {
-			let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 			let settings = match editor_state.data {
 				EditorData::QNEntity { ref mut settings, .. } => settings,
@@ -37,7 +68,7 @@ pub async fn handle(app: &AppHandle, event: EntityGeneralEvent) -> Result<()> {
 			editor_id,
 			show_changes_from_original
 		} => {
-			let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 			let settings = match editor_state.data {
 				EditorData::QNEntity { ref mut settings, .. } => settings,
@@ -61,5 +92,152 @@ pub async fn handle(app: &AppHandle, event: EntityGeneralEvent) -> Result<()> {
 				)))
 			)?;
 		}
+
+		EntityGeneralEvent::MinimisePatch { editor_id } => {
+			let (factory_hash, previous_size) = {
+				let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+				let EditorData::QNPatch {
+					ref base, ref current, ..
+				} = editor_state.data
+				else {
+					Err(anyhow!("Editor {} is not a QN patch editor", editor_id))?;
+					panic!();
+				};
+
+				// Compare against the patch as it is on disk, since that's what has accumulated any cruft
+				let previous_size = match editor_state.file.as_ref().and_then(|x| fs::read(x).ok()) {
+					Some(data) => data.len(),
+					None => get_saved_patch_size(base, current)?
+				};
+
+				(current.factory_hash.to_owned(), previous_size)
+			};
+
+			let game_files = app_state.game_files.load();
+			let game_files = game_files.as_ref().context("Game files not loaded")?;
+
+			let hash_list = app_state.hash_list.load();
+			let hash_list = hash_list.as_ref().context("No hash list available")?;
+
+			let game_version = get_loaded_game_version(
+				app,
+				app_settings.load().game_install.as_ref().context("No game selected")?
+			)?;
+
+			let factory_hash = RuntimeID::from_any(&factory_hash)?;
+
+			// The cached copy may be from before the game files last changed
+			app_state.cached_entities.remove(&factory_hash);
+
+			let base = extract_entity_async(
+				game_files,
+				&app_state.cached_entities,
+				game_version,
+				hash_list,
+				factory_hash
+			)
+			.await?
+			.to_owned();
+
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+			let EditorData::QNPatch {
+				base: ref mut editor_base,
+				ref current,
+				..
+			} = editor_state.data
+			else {
+				Err(anyhow!("Editor {} is not a QN patch editor", editor_id))?;
+				panic!();
+			};
+
+			// Diffing only produces the operations needed to get from the base to the current entity, so no-op
+			// operations and repeated property sets are dropped
+			let minimised_size = get_saved_patch_size(&base, current)?;
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+					EntityTreeRequest::SetDiffInfo {
+						editor_id,
+						diff_info: get_diff_info(&base, current)
+					}
+				)))
+			)?;
+
+			*editor_base = Box::new(base);
+
+			send_request(
+				app,
+				Request::Global(GlobalRequest::SetTabUnsaved {
+					id: editor_id,
+					unsaved: true
+				})
+			)?;
+
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Success,
+					title: "Patch minimised".into(),
+					subtitle: format!(
+						"The patch will go from {:.1} KB to {:.1} KB ({:.0}% smaller) when saved.",
+						previous_size as f64 / 1024.0,
+						minimised_size as f64 / 1024.0,
+						previous_size.saturating_sub(minimised_size) as f64 / previous_size.max(1) as f64 * 100.0
+					),
+					link: None
+				}
+			)?;
+		}
+
+		EntityGeneralEvent::ExportOutline { editor_id, format } => {
+			let outline = {
+				let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+				let entity = match editor_state.data {
+					EditorData::QNEntity { ref entity, .. } => entity,
+					EditorData::QNPatch { ref current, .. } => current,
+
+					_ => {
+						Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+						panic!();
+					}
+				};
+
+				render_scene_outline(entity, app_state.hash_list.load().as_deref(), format)
+			};
+
+			let mut dialog = app.dialog().file().set_title("Export outline");
+
+			if let Some(project) = app_state.project.load().as_ref() {
+				dialog = dialog.set_directory(&project.path);
+			}
+
+			if let Some(path) = dialog
+				.set_file_name(&format!("outline.{}", format.extension()))
+				.add_filter(
+					match format {
+						OutlineFormat::Markdown => "Markdown document",
+						OutlineFormat::Html => "HTML document"
+					},
+					&[format.extension()]
+				)
+				.blocking_save_file()
+			{
+				fs::write(path.as_path().context("Invalid path")?, outline)?;
+
+				send_notification(
+					app,
+					Notification {
+						kind: NotificationKind::Success,
+						title: "Outline exported".into(),
+						subtitle: "The entity tree has been saved as a document.".into(),
+						link: None
+					}
+				)?;
+			}
+		}
 	}
 }
@@ -1,21 +1,246 @@
 use anyhow::{Context, Result, anyhow};
+use arc_swap::ArcSwap;
 use fn_error_context::context;
-use quickentity_rs::qn_structs::{CommentEntity, Ref};
+use quickentity_rs::qn_structs::{CommentEntity, Entity, ExposedEntity, PropertyAlias, Ref};
+use serde::Serialize;
 use tauri::{AppHandle, Manager};
 use tryvial::try_fn;
+use uuid::Uuid;
 
 use crate::{
-	entity::get_local_reference,
+	Notification, NotificationKind,
+	entity::{get_diff_info, get_local_reference, get_override_listings},
+	finish_task, get_loaded_game_version,
 	model::{
-		AppState, EditorData, EditorRequest, EntityEditorRequest, EntityMetaPaneEvent, EntityTreeRequest,
-		GlobalRequest, Request
+		AppSettings, AppState, EditorData, EditorRequest, EntityEditorRequest, EntityMetaPaneEvent,
+		EntityMetaPaneRequest, EntityMonacoRequest, EntityTreeRequest, GlobalRequest, Request
 	},
-	send_request
+	send_notification, send_request,
+	split_view::{get_tab_state, get_tab_state_mut},
+	start_task
 };
 
+use super::monaco::open_factory;
+
+/// Send the property aliases of an entity to the meta pane.
+#[try_fn]
+#[context("Couldn't send property aliases of {} to meta pane", entity_id)]
+pub fn send_property_aliases(app: &AppHandle, editor_id: Uuid, entity_id: &str, entity: &Entity) -> Result<()> {
+	let property_aliases = entity
+		.entities
+		.get(entity_id)
+		.context("No such entity")?
+		.property_aliases
+		.to_owned()
+		.unwrap_or_default()
+		.into_iter()
+		.collect::<Vec<_>>();
+
+	let entity_names = property_aliases
+		.iter()
+		.flat_map(|(_, aliases)| aliases)
+		.filter_map(|x| get_local_reference(&x.original_entity))
+		.filter_map(|x| Some((x.to_owned(), entity.entities.get(&x)?.name.to_owned())))
+		.collect();
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::MetaPane(
+			EntityMetaPaneRequest::SetPropertyAliases {
+				editor_id,
+				entity_id: entity_id.to_owned(),
+				property_aliases,
+				entity_names
+			}
+		)))
+	)?;
+}
+
+/// Check that a property alias refers to an entity which exists and a property which that entity has, returning why
+/// it's invalid if it isn't. Properties can only be checked for local entities, and only once intellisense is loaded.
+#[try_fn]
+#[context("Couldn't validate property alias")]
+fn validate_property_alias(
+	app: &AppHandle,
+	entity: &Entity,
+	aliased_name: &str,
+	alias: &PropertyAlias
+) -> Result<Option<String>> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+	let app_state = app.state::<AppState>();
+
+	if aliased_name.trim().is_empty() {
+		return Ok(Some("The alias must have a name".into()));
+	}
+
+	if alias.original_property.trim().is_empty() {
+		return Ok(Some("The aliased property must have a name".into()));
+	}
+
+	let Some(original_entity) = get_local_reference(&alias.original_entity) else {
+		return Ok(None);
+	};
+
+	if !entity.entities.contains_key(&original_entity) {
+		return Ok(Some(format!("There is no entity {} in this entity", original_entity)));
+	}
+
+	if let Some(intellisense) = app_state.intellisense.load().as_ref()
+		&& let Some(game_files) = app_state.game_files.load().as_ref()
+		&& let Some(hash_list) = app_state.hash_list.load().as_ref()
+		&& let Some(install) = app_settings.load().game_install.as_ref()
+	{
+		let properties = intellisense.get_properties(
+			game_files,
+			&app_state.cached_entities,
+			hash_list,
+			get_loaded_game_version(app, install)?,
+			entity,
+			&original_entity,
+			false
+		)?;
+
+		if !properties.iter().any(|(name, ..)| *name == alias.original_property) {
+			return Ok(Some(format!(
+				"{} doesn't have a property called {}",
+				entity.entities[&original_entity].name, alias.original_property
+			)));
+		}
+	}
+
+	None
+}
+
+/// Send the exposed entities and exposed interfaces of an entity to the meta pane.
+#[try_fn]
+#[context("Couldn't send exposed entities of {} to meta pane", entity_id)]
+pub fn send_exposed_entities(app: &AppHandle, editor_id: Uuid, entity_id: &str, entity: &Entity) -> Result<()> {
+	let sub_entity = entity.entities.get(entity_id).context("No such entity")?;
+
+	let exposed_entities = sub_entity
+		.exposed_entities
+		.to_owned()
+		.unwrap_or_default()
+		.into_iter()
+		.collect::<Vec<_>>();
+
+	let exposed_interfaces = sub_entity
+		.exposed_interfaces
+		.to_owned()
+		.unwrap_or_default()
+		.into_iter()
+		.collect::<Vec<_>>();
+
+	let entity_names = exposed_entities
+		.iter()
+		.flat_map(|(_, exposed)| exposed.refers_to.iter().filter_map(get_local_reference))
+		.chain(exposed_interfaces.iter().map(|(_, referenced)| referenced.to_owned()))
+		.filter_map(|x| Some((x.to_owned(), entity.entities.get(&x)?.name.to_owned())))
+		.collect();
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::MetaPane(
+			EntityMetaPaneRequest::SetExposedEntities {
+				editor_id,
+				entity_id: entity_id.to_owned(),
+				exposed_entities,
+				exposed_interfaces,
+				entity_names
+			}
+		)))
+	)?;
+}
+
+/// Resolve what was typed into a reference picker to a local entity, by ID or else by name, returning why it couldn't
+/// be resolved if it can't.
+pub fn resolve_local_entity(entity: &Entity, input: &str) -> Result<String, String> {
+	let input = input.trim();
+
+	if entity.entities.contains_key(input) {
+		return Ok(input.to_owned());
+	}
+
+	let matching = entity
+		.entities
+		.iter()
+		.filter(|(_, sub_entity)| sub_entity.name.eq_ignore_ascii_case(input))
+		.map(|(id, _)| id.to_owned())
+		.collect::<Vec<_>>();
+
+	match matching.len() {
+		0 => Err(format!("There is no entity with the ID or name {}", input)),
+		1 => Ok(matching.into_iter().next().unwrap()),
+		_ => Err(format!(
+			"There are multiple entities called {}; use an ID instead",
+			input
+		))
+	}
+}
+
+/// Let the frontend know that an entity's property aliases, exposed entities or exposed interfaces were changed.
+#[try_fn]
+#[context("Couldn't send entity change to frontend")]
+fn finish_meta_pane_change(app: &AppHandle, editor_id: Uuid, entity_id: &str, editor_data: &EditorData) -> Result<()> {
+	let entity = match editor_data {
+		EditorData::QNEntity { entity, .. } => entity,
+		EditorData::QNPatch { current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	send_request(
+		app,
+		Request::Global(GlobalRequest::SetTabUnsaved {
+			id: editor_id,
+			unsaved: true
+		})
+	)?;
+
+	let mut buf = Vec::new();
+	let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+	let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+	entity
+		.entities
+		.get(entity_id)
+		.context("No such entity")?
+		.serialize(&mut ser)?;
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
+			EntityMonacoRequest::ReplaceContentIfSameEntityID {
+				editor_id,
+				entity_id: entity_id.to_owned(),
+				content: String::from_utf8(buf)?
+			}
+		)))
+	)?;
+
+	send_property_aliases(app, editor_id, entity_id, entity)?;
+	send_exposed_entities(app, editor_id, entity_id, entity)?;
+
+	if let EditorData::QNPatch { base, current, .. } = editor_data {
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+				EntityTreeRequest::SetDiffInfo {
+					editor_id,
+					diff_info: get_diff_info(base, current)
+				}
+			)))
+		)?;
+	}
+}
+
 #[try_fn]
 #[context("Couldn't handle entity meta pane event")]
 pub async fn handle(app: &AppHandle, event: EntityMetaPaneEvent) -> Result<()> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
 	let app_state = app.state::<AppState>();
 
 	match event {
@@ -36,7 +261,7 @@ pub async fn handle(app: &AppHandle, event: EntityMetaPaneEvent) -> Result<()> {
 			entity_id,
 			notes
 		} => {
-			let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 			let entity = match editor_state.data {
 				EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -68,5 +293,414 @@ pub async fn handle(app: &AppHandle, event: EntityMetaPaneEvent) -> Result<()> {
 				})
 			)?;
 		}
+
+		EntityMetaPaneEvent::RequestOverrides { editor_id } => {
+			if let Some(game_files) = app_state.game_files.load().as_ref()
+				&& let Some(hash_list) = app_state.hash_list.load().as_ref()
+				&& let Some(install) = app_settings.load().game_install.as_ref()
+			{
+				let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+				let entity = match editor_state.data {
+					EditorData::QNEntity { ref entity, .. } => entity,
+					EditorData::QNPatch { ref current, .. } => current,
+
+					_ => {
+						Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+						panic!();
+					}
+				};
+
+				let task = start_task(app, "Resolving override targets")?;
+
+				let overrides = get_override_listings(
+					game_files,
+					&app_state.cached_entities,
+					get_loaded_game_version(app, install)?,
+					hash_list,
+					entity
+				);
+
+				send_request(
+					app,
+					Request::Editor(EditorRequest::Entity(EntityEditorRequest::MetaPane(
+						EntityMetaPaneRequest::SetOverrides { editor_id, overrides }
+					)))
+				)?;
+
+				finish_task(app, task)?;
+			}
+		}
+
+		EntityMetaPaneEvent::CreatePropertyAlias {
+			editor_id,
+			entity_id,
+			aliased_name,
+			alias
+		} => {
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref mut entity, .. } => entity,
+				EditorData::QNPatch { ref mut current, .. } => current,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+					panic!();
+				}
+			};
+
+			if let Some(problem) = validate_property_alias(app, entity, &aliased_name, &alias)? {
+				send_notification(
+					app,
+					Notification {
+						kind: NotificationKind::Error,
+						title: "Invalid property alias".into(),
+						subtitle: problem,
+						link: None
+					}
+				)?;
+
+				return Ok(());
+			}
+
+			entity
+				.entities
+				.get_mut(&entity_id)
+				.context("No such entity")?
+				.property_aliases
+				.get_or_insert_default()
+				.entry(aliased_name.trim().to_owned())
+				.or_default()
+				.push(alias);
+
+			finish_meta_pane_change(app, editor_id, &entity_id, &editor_state.data)?;
+		}
+
+		EntityMetaPaneEvent::EditPropertyAlias {
+			editor_id,
+			entity_id,
+			aliased_name,
+			index,
+			new_aliased_name,
+			alias
+		} => {
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref mut entity, .. } => entity,
+				EditorData::QNPatch { ref mut current, .. } => current,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+					panic!();
+				}
+			};
+
+			if let Some(problem) = validate_property_alias(app, entity, &new_aliased_name, &alias)? {
+				send_notification(
+					app,
+					Notification {
+						kind: NotificationKind::Error,
+						title: "Invalid property alias".into(),
+						subtitle: problem,
+						link: None
+					}
+				)?;
+
+				return Ok(());
+			}
+
+			let property_aliases = entity
+				.entities
+				.get_mut(&entity_id)
+				.context("No such entity")?
+				.property_aliases
+				.get_or_insert_default();
+
+			let new_aliased_name = new_aliased_name.trim().to_owned();
+
+			let index = index as usize;
+
+			if new_aliased_name == aliased_name {
+				*property_aliases
+					.get_mut(&aliased_name)
+					.and_then(|x| x.get_mut(index))
+					.context("No such property alias")? = alias;
+			} else {
+				let aliases = property_aliases
+					.get_mut(&aliased_name)
+					.context("No such property alias")?;
+
+				if index >= aliases.len() {
+					Err(anyhow!("No such property alias"))?;
+				}
+
+				aliases.remove(index);
+
+				if aliases.is_empty() {
+					property_aliases.shift_remove(&aliased_name);
+				}
+
+				property_aliases.entry(new_aliased_name).or_default().push(alias);
+			}
+
+			finish_meta_pane_change(app, editor_id, &entity_id, &editor_state.data)?;
+		}
+
+		EntityMetaPaneEvent::DeletePropertyAlias {
+			editor_id,
+			entity_id,
+			aliased_name,
+			index
+		} => {
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref mut entity, .. } => entity,
+				EditorData::QNPatch { ref mut current, .. } => current,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+					panic!();
+				}
+			};
+
+			let sub_entity = entity.entities.get_mut(&entity_id).context("No such entity")?;
+
+			let property_aliases = sub_entity.property_aliases.get_or_insert_default();
+
+			let aliases = property_aliases
+				.get_mut(&aliased_name)
+				.context("No such property alias")?;
+
+			let index = index as usize;
+
+			if index >= aliases.len() {
+				Err(anyhow!("No such property alias"))?;
+			}
+
+			aliases.remove(index);
+
+			if aliases.is_empty() {
+				property_aliases.shift_remove(&aliased_name);
+			}
+
+			if property_aliases.is_empty() {
+				sub_entity.property_aliases = None;
+			}
+
+			finish_meta_pane_change(app, editor_id, &entity_id, &editor_state.data)?;
+		}
+
+		EntityMetaPaneEvent::SetExposedEntity {
+			editor_id,
+			entity_id,
+			exposed_name,
+			is_array,
+			refers_to
+		} => {
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref mut entity, .. } => entity,
+				EditorData::QNPatch { ref mut current, .. } => current,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+					panic!();
+				}
+			};
+
+			let resolved = if exposed_name.trim().is_empty() {
+				Err("The exposed entity must have a name".into())
+			} else if refers_to.is_empty() {
+				Err("The exposed entity must refer to at least one entity".into())
+			} else if !is_array && refers_to.len() > 1 {
+				Err("Only array exposed entities can refer to multiple entities".into())
+			} else {
+				refers_to
+					.iter()
+					.map(|x| resolve_local_entity(entity, x))
+					.collect::<Result<Vec<_>, String>>()
+			};
+
+			let refers_to = match resolved {
+				Ok(refers_to) => refers_to,
+
+				Err(problem) => {
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Error,
+							title: "Invalid exposed entity".into(),
+							subtitle: problem,
+							link: None
+						}
+					)?;
+
+					return Ok(());
+				}
+			};
+
+			entity
+				.entities
+				.get_mut(&entity_id)
+				.context("No such entity")?
+				.exposed_entities
+				.get_or_insert_default()
+				.insert(
+					exposed_name.trim().to_owned(),
+					ExposedEntity {
+						is_array,
+						refers_to: refers_to.into_iter().map(|x| Ref::Short(Some(x))).collect()
+					}
+				);
+
+			finish_meta_pane_change(app, editor_id, &entity_id, &editor_state.data)?;
+		}
+
+		EntityMetaPaneEvent::RemoveExposedEntity {
+			editor_id,
+			entity_id,
+			exposed_name
+		} => {
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref mut entity, .. } => entity,
+				EditorData::QNPatch { ref mut current, .. } => current,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+					panic!();
+				}
+			};
+
+			let sub_entity = entity.entities.get_mut(&entity_id).context("No such entity")?;
+
+			let exposed_entities = sub_entity.exposed_entities.as_mut().context("No such exposed entity")?;
+
+			exposed_entities
+				.shift_remove(&exposed_name)
+				.context("No such exposed entity")?;
+
+			if exposed_entities.is_empty() {
+				sub_entity.exposed_entities = None;
+			}
+
+			finish_meta_pane_change(app, editor_id, &entity_id, &editor_state.data)?;
+		}
+
+		EntityMetaPaneEvent::SetExposedInterface {
+			editor_id,
+			entity_id,
+			interface,
+			refers_to
+		} => {
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref mut entity, .. } => entity,
+				EditorData::QNPatch { ref mut current, .. } => current,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+					panic!();
+				}
+			};
+
+			let resolved = if interface.trim().is_empty() {
+				Err("The exposed interface must have a name".into())
+			} else {
+				resolve_local_entity(entity, &refers_to)
+			};
+
+			let refers_to = match resolved {
+				Ok(refers_to) => refers_to,
+
+				Err(problem) => {
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Error,
+							title: "Invalid exposed interface".into(),
+							subtitle: problem,
+							link: None
+						}
+					)?;
+
+					return Ok(());
+				}
+			};
+
+			entity
+				.entities
+				.get_mut(&entity_id)
+				.context("No such entity")?
+				.exposed_interfaces
+				.get_or_insert_default()
+				.insert(interface.trim().to_owned(), refers_to);
+
+			finish_meta_pane_change(app, editor_id, &entity_id, &editor_state.data)?;
+		}
+
+		EntityMetaPaneEvent::RemoveExposedInterface {
+			editor_id,
+			entity_id,
+			interface
+		} => {
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref mut entity, .. } => entity,
+				EditorData::QNPatch { ref mut current, .. } => current,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+					panic!();
+				}
+			};
+
+			let sub_entity = entity.entities.get_mut(&entity_id).context("No such entity")?;
+
+			let exposed_interfaces = sub_entity
+				.exposed_interfaces
+				.as_mut()
+				.context("No such exposed interface")?;
+
+			exposed_interfaces
+				.shift_remove(&interface)
+				.context("No such exposed interface")?;
+
+			if exposed_interfaces.is_empty() {
+				sub_entity.exposed_interfaces = None;
+			}
+
+			finish_meta_pane_change(app, editor_id, &entity_id, &editor_state.data)?;
+		}
+
+		EntityMetaPaneEvent::JumpToOverrideTarget {
+			editor_id,
+			entity_id,
+			external_scene
+		} => {
+			if let Some(external_scene) = external_scene {
+				// The opened editor's tree isn't loaded yet, so the entity is selected once it is
+				if let Some(opened_editor) = open_factory(app, external_scene).await? {
+					app_state.pending_selections.insert(opened_editor, entity_id);
+				}
+			} else {
+				send_request(
+					app,
+					Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+						EntityTreeRequest::Select {
+							editor_id,
+							id: Some(entity_id)
+						}
+					)))
+				)?;
+			}
+		}
 	}
 }
@@ -2,21 +2,46 @@ use std::fs;
 
 use anyhow::{Context, Result, anyhow};
 use fn_error_context::context;
-use hitman_commons::metadata::RuntimeID;
+use hitman_commons::{hash_list::HashList, metadata::RuntimeID};
+use itertools::Itertools;
+use rpkg_rs::resource::partition_manager::PartitionManager;
 use serde_json::to_vec;
 use tauri::{AppHandle, Manager};
-use tauri_plugin_aptabase::EventTracker;
 use tryvial::try_fn;
 
 use crate::{
 	Notification, NotificationKind,
+	entity::is_valid_entity_blueprint,
 	model::{
 		AppState, EditorData, EditorRequest, EditorState, EntityEditorRequest, EntityMetadataEvent,
 		EntityMetadataRequest, GlobalRequest, Request, SettingsRequest, ToolRequest
 	},
-	send_notification, send_request
+	rpkg::extract_latest_metadata,
+	send_notification, send_request,
+	split_view::{get_tab_document, get_tab_state, get_tab_state_mut},
+	telemetry::track_event
 };
 
+/// The blueprint referenced by a factory in the game files, if the factory is in the game files and references exactly
+/// one blueprint.
+fn get_factory_blueprint(game_files: &PartitionManager, hash_list: &HashList, factory: RuntimeID) -> Option<RuntimeID> {
+	extract_latest_metadata(game_files, factory)
+		.ok()?
+		.core_info
+		.references
+		.iter()
+		.map(|x| x.resource.get_id())
+		.filter(|x| {
+			hash_list
+				.entries
+				.get(x)
+				.is_some_and(|x| is_valid_entity_blueprint(x.resource_type))
+		})
+		.unique()
+		.exactly_one()
+		.ok()
+}
+
 #[try_fn]
 #[context("Couldn't handle entity metadata event")]
 pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
@@ -24,7 +49,7 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 
 	match event {
 		EntityMetadataEvent::Initialise { editor_id } => {
-			let editor_state = app_state.editor_states.get(&editor_id).context("No such editor")?;
+			let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
 
 			let entity = match editor_state.data {
 				EditorData::QNEntity { ref entity, .. } => entity,
@@ -87,7 +112,7 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 					let mut settings = (*project.settings.load_full()).to_owned();
 					settings.custom_paths.push(factory_hash.to_owned());
 
-					app.track_event("Save custom path by factory input", None).unwrap();
+					track_event(app, "Save custom path by factory input", None);
 
 					send_request(
 						app,
@@ -114,7 +139,8 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 						Notification {
 							kind: NotificationKind::Info,
 							title: "Custom path saved".into(),
-							subtitle: "The entered path has been saved in your custom paths list.".into()
+							subtitle: "The entered path has been saved in your custom paths list.".into(),
+							link: None
 						}
 					)?;
 				}
@@ -133,7 +159,7 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 			}
 
 			{
-				let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+				let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 				let entity = match editor_state.data {
 					EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -149,15 +175,22 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 					}
 				};
 
-				entity.factory_hash = factory_hash;
+				entity.factory_hash = factory_hash.to_owned();
 			}
 
 			// If it was a patch editor, we should convert it into an entity editor since now we're working on a new entity
 			if is_patch_editor {
-				let (_, state) = app_state.editor_states.remove(&editor_id).context("No such editor")?;
+				let document = get_tab_document(&app_state, editor_id);
+
+				let (_, state) = app_state.editor_states.remove(&document).context("No such editor")?;
 
 				let EditorState {
-					data: EditorData::QNPatch { settings, current, .. },
+					data: EditorData::QNPatch {
+						settings,
+						current,
+						recycle_bin,
+						..
+					},
 					file: None
 				} = state
 				else {
@@ -165,11 +198,12 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 				};
 
 				app_state.editor_states.insert(
-					editor_id.to_owned(),
+					document,
 					EditorState {
 						data: EditorData::QNEntity {
 							settings,
-							entity: current
+							entity: current,
+							recycle_bin
 						},
 						file: None
 					}
@@ -183,6 +217,33 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 					unsaved: true
 				})
 			)?;
+
+			// A factory used with the wrong blueprint breaks the entity in-game, so the factory's own blueprint is
+			// suggested if it isn't the one already set
+			if let Some(game_files) = app_state.game_files.load().as_ref()
+				&& let Some(hash_list) = app_state.hash_list.load().as_ref()
+				&& let Some(blueprint_hash) =
+					get_factory_blueprint(game_files, hash_list, RuntimeID::from_any(&factory_hash)?)
+			{
+				let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+				let EditorData::QNEntity { ref entity, .. } = editor_state.data else {
+					Err(anyhow!("Editor {} is not a QN entity editor", editor_id))?;
+					panic!();
+				};
+
+				if RuntimeID::from_any(&entity.blueprint_hash).ok() != Some(blueprint_hash) {
+					send_request(
+						app,
+						Request::Editor(EditorRequest::Entity(EntityEditorRequest::Metadata(
+							EntityMetadataRequest::SuggestBlueprintHash {
+								editor_id,
+								blueprint_hash: blueprint_hash.to_string()
+							}
+						)))
+					)?;
+				}
+			}
 		}
 
 		EntityMetadataEvent::SetBlueprintHash {
@@ -196,7 +257,7 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 					let mut settings = (*project.settings.load_full()).to_owned();
 					settings.custom_paths.push(blueprint_hash.to_owned());
 
-					app.track_event("Save custom path by blueprint input", None).unwrap();
+					track_event(app, "Save custom path by blueprint input", None);
 
 					send_request(
 						app,
@@ -223,7 +284,8 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 						Notification {
 							kind: NotificationKind::Info,
 							title: "Custom path saved".into(),
-							subtitle: "The entered path has been saved in your custom paths list.".into()
+							subtitle: "The entered path has been saved in your custom paths list.".into(),
+							link: None
 						}
 					)?;
 				}
@@ -242,7 +304,7 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 			}
 
 			{
-				let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+				let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 				let entity = match editor_state.data {
 					EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -263,10 +325,17 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 
 			// If it was a patch editor, we should convert it into an entity editor since now we're working on a new entity
 			if is_patch_editor {
-				let (_, state) = app_state.editor_states.remove(&editor_id).context("No such editor")?;
+				let document = get_tab_document(&app_state, editor_id);
+
+				let (_, state) = app_state.editor_states.remove(&document).context("No such editor")?;
 
 				let EditorState {
-					data: EditorData::QNPatch { settings, current, .. },
+					data: EditorData::QNPatch {
+						settings,
+						current,
+						recycle_bin,
+						..
+					},
 					file: None
 				} = state
 				else {
@@ -274,11 +343,12 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 				};
 
 				app_state.editor_states.insert(
-					editor_id.to_owned(),
+					document,
 					EditorState {
 						data: EditorData::QNEntity {
 							settings,
-							entity: current
+							entity: current,
+							recycle_bin
 						},
 						file: None
 					}
@@ -295,7 +365,7 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 		}
 
 		EntityMetadataEvent::SetRootEntity { editor_id, root_entity } => {
-			let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 			let entity = match editor_state.data {
 				EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -319,7 +389,7 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 		}
 
 		EntityMetadataEvent::SetSubType { editor_id, sub_type } => {
-			let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 			let entity = match editor_state.data {
 				EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -346,7 +416,7 @@ pub async fn handle(app: &AppHandle, event: EntityMetadataEvent) -> Result<()> {
 			editor_id,
 			external_scenes
 		} => {
-			let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 			let entity = match editor_state.data {
 				EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -10,6 +10,9 @@ pub mod meta_pane;
 pub mod metadata;
 pub mod monaco;
 pub mod overrides;
+pub mod problems;
+pub mod stats;
+pub mod subsets;
 pub mod tree;
 
 #[try_fn]
@@ -39,5 +42,17 @@ pub async fn handle(app: &AppHandle, event: EntityEditorEvent) -> Result<()> {
 		EntityEditorEvent::Overrides(event) => {
 			overrides::handle(app, event).await?;
 		}
+
+		EntityEditorEvent::Stats(event) => {
+			stats::handle(app, event).await?;
+		}
+
+		EntityEditorEvent::Problems(event) => {
+			problems::handle(app, event).await?;
+		}
+
+		EntityEditorEvent::Subsets(event) => {
+			subsets::handle(app, event).await?;
+		}
 	}
 }
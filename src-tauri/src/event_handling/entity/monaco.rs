@@ -4,8 +4,9 @@ use fn_error_context::context;
 use hashbrown::HashSet;
 use hitman_commons::metadata::RuntimeID;
 use log::debug;
-use quickentity_rs::qn_structs::Ref;
+use quickentity_rs::qn_structs::{Property, Ref};
 
+use serde::Serialize;
 use serde_json::from_str;
 
 use tauri::{AppHandle, Manager};
@@ -16,8 +17,8 @@ use crate::{
 	Notification, NotificationKind,
 	editor_connection::PropertyValue,
 	entity::{
-		check_local_references_exist, get_decorations, get_diff_info, is_valid_entity_blueprint,
-		is_valid_entity_factory
+		check_local_references_exist, get_colours, get_decorations, get_diff_info, get_external_ref_hovers,
+		is_valid_entity_blueprint, is_valid_entity_factory
 	},
 	finish_task,
 	general::open_in_editor,
@@ -27,7 +28,10 @@ use crate::{
 		EntityMonacoEvent, EntityMonacoRequest, EntityTreeRequest, GlobalRequest, Request
 	},
 	rpkg::extract_latest_overview_info,
-	send_notification, send_request, start_task
+	send_notification, send_request,
+	split_view::{get_tab_state, get_tab_state_mut},
+	start_task,
+	unit_conversion::{convert_property, set_colour}
 };
 
 pub const SAFE_TO_SYNC: [&str; 43] = [
@@ -106,13 +110,41 @@ pub async fn handle(app: &AppHandle, event: EntityMonacoEvent) -> Result<()> {
 			open_factory(app, factory).await?;
 		}
 
+		EntityMonacoEvent::FollowResource { resource, .. } => {
+			open_factory(app, resource).await?;
+		}
+
+		EntityMonacoEvent::ConvertProperty {
+			editor_id,
+			entity_id,
+			property,
+			conversion
+		} => {
+			modify_property(app, editor_id, entity_id, property, "Couldn't convert property", |x| {
+				convert_property(x, conversion)
+			})
+			.await?;
+		}
+
+		EntityMonacoEvent::SetColour {
+			editor_id,
+			entity_id,
+			property,
+			colour
+		} => {
+			modify_property(app, editor_id, entity_id, property, "Couldn't set colour", |x| {
+				set_colour(x, colour)
+			})
+			.await?;
+		}
+
 		EntityMonacoEvent::SignalPin {
 			editor_id,
 			entity_id,
 			pin,
 			output
 		} => {
-			let editor_state = app_state.editor_states.get(&editor_id).context("No such editor")?;
+			let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
 
 			let entity = match editor_state.data {
 				EditorData::QNEntity { ref entity, .. } => entity,
@@ -141,7 +173,10 @@ pub async fn handle(app: &AppHandle, event: EntityMonacoEvent) -> Result<()> {
 						id.to_owned(),
 						EditorState {
 							file: None,
-							data: EditorData::ResourceOverview { hash: resource }
+							data: EditorData::ResourceOverview {
+								hash: resource,
+								generic_rl_json: None
+							}
 						}
 					);
 
@@ -159,7 +194,8 @@ pub async fn handle(app: &AppHandle, event: EntityMonacoEvent) -> Result<()> {
 						Notification {
 							kind: NotificationKind::Error,
 							title: "Not a vanilla resource".into(),
-							subtitle: "This factory doesn't exist in the base game files.".into()
+							subtitle: "This factory doesn't exist in the base game files.".into(),
+							link: None
 						}
 					)?;
 				}
@@ -169,7 +205,8 @@ pub async fn handle(app: &AppHandle, event: EntityMonacoEvent) -> Result<()> {
 					Notification {
 						kind: NotificationKind::Error,
 						title: "No game selected".into(),
-						subtitle: "You can't open game files without a copy of the game selected.".into()
+						subtitle: "You can't open game files without a copy of the game selected.".into(),
+						link: None
 					}
 				)?;
 			}
@@ -177,13 +214,96 @@ pub async fn handle(app: &AppHandle, event: EntityMonacoEvent) -> Result<()> {
 	}
 }
 
+/// Replace a property of a sub-entity with a modified copy, then update the sub-entity as if the change had been made in
+/// the editor, so that it's validated and synced to the game in the same way. If the property can't be modified, the
+/// reason is shown as a notification.
+#[try_fn]
+#[context("Couldn't modify property {}", property)]
+async fn modify_property(
+	app: &AppHandle,
+	editor_id: Uuid,
+	entity_id: String,
+	property: String,
+	failure_title: &str,
+	modify: impl FnOnce(&Property) -> Result<Property>
+) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let mut sub_entity = {
+		let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+		let entity = match editor_state.data {
+			EditorData::QNEntity { ref entity, .. } => entity,
+			EditorData::QNPatch { ref current, .. } => current,
+
+			_ => {
+				Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+				panic!();
+			}
+		};
+
+		entity
+			.entities
+			.get(&entity_id)
+			.context("No such sub-entity")?
+			.to_owned()
+	};
+
+	let value = sub_entity
+		.properties
+		.as_mut()
+		.and_then(|x| x.get_mut(&property))
+		.context("No such property")?;
+
+	match modify(value) {
+		Ok(modified) => {
+			*value = modified;
+		}
+
+		Err(err) => {
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Error,
+					title: failure_title.into(),
+					subtitle: format!("{:#}", err),
+					link: None
+				}
+			)?;
+
+			return Ok(());
+		}
+	}
+
+	let mut buf = Vec::new();
+	let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+	let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+	sub_entity.serialize(&mut ser)?;
+
+	let content = String::from_utf8(buf)?;
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
+			EntityMonacoRequest::ReplaceContentIfSameEntityID {
+				editor_id: editor_id.to_owned(),
+				entity_id: entity_id.to_owned(),
+				content: content.to_owned()
+			}
+		)))
+	)?;
+
+	update_content(app, editor_id, entity_id, content).await?;
+}
+
 #[try_fn]
 #[context("Couldn't handle update content event")]
 pub async fn update_content(app: &AppHandle, editor_id: Uuid, entity_id: String, content: String) -> Result<()> {
 	let app_settings = app.state::<ArcSwap<AppSettings>>();
 	let app_state = app.state::<AppState>();
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -303,12 +423,14 @@ pub async fn update_content(app: &AppHandle, editor_id: Uuid, entity_id: String,
 					{
 						let task = start_task(app, "Updating decorations")?;
 
+						let game_version = get_loaded_game_version(app, install)?;
+
 						let decorations = get_decorations(
 							game_files,
 							&app_state.cached_entities,
 							repository,
 							hash_list,
-							get_loaded_game_version(app, install)?,
+							game_version,
 							tonytools_hash_list,
 							entity.entities.get(&entity_id).context("No such entity")?,
 							entity
@@ -325,7 +447,15 @@ pub async fn update_content(app: &AppHandle, editor_id: Uuid, entity_id: String,
 										.filter(|(x, _)| entity.entities.contains_key(x))
 										.map(|(x, _)| x.to_owned())
 										.collect(),
-									decorations
+									decorations,
+									colours: get_colours(entity.entities.get(&entity_id).context("No such entity")?),
+									external_ref_hovers: get_external_ref_hovers(
+										game_files,
+										&app_state.cached_entities,
+										game_version,
+										hash_list,
+										entity.entities.get(&entity_id).context("No such entity")?
+									)
 								}
 							)))
 						)?;
@@ -489,9 +619,11 @@ pub async fn update_content(app: &AppHandle, editor_id: Uuid, entity_id: String,
 	}
 }
 
+/// Open a resource in the appropriate editor: the entity editor for TEMPs, or a resource overview otherwise. Returns the
+/// ID of the entity editor if one was opened.
 #[try_fn]
 #[context("Couldn't handle open factory event")]
-pub async fn open_factory(app: &AppHandle, factory: String) -> Result<()> {
+pub async fn open_factory(app: &AppHandle, factory: String) -> Result<Option<Uuid>> {
 	let app_settings = app.state::<ArcSwap<AppSettings>>();
 	let app_state = app.state::<AppState>();
 
@@ -503,7 +635,9 @@ pub async fn open_factory(app: &AppHandle, factory: String) -> Result<()> {
 
 		if let Ok((filetype, _, _)) = extract_latest_overview_info(game_files, factory) {
 			if filetype == "TEMP" {
-				open_in_editor(app, game_files, install, hash_list, factory).await?;
+				return Ok(Some(
+					open_in_editor(app, game_files, install, hash_list, factory).await?
+				));
 			} else {
 				let id = Uuid::new_v4();
 
@@ -512,7 +646,8 @@ pub async fn open_factory(app: &AppHandle, factory: String) -> Result<()> {
 					EditorState {
 						file: None,
 						data: EditorData::ResourceOverview {
-							hash: factory.to_owned()
+							hash: factory.to_owned(),
+							generic_rl_json: None
 						}
 					}
 				);
@@ -532,7 +667,8 @@ pub async fn open_factory(app: &AppHandle, factory: String) -> Result<()> {
 				Notification {
 					kind: NotificationKind::Error,
 					title: "Not a vanilla resource".into(),
-					subtitle: "This factory doesn't exist in the base game files.".into()
+					subtitle: "This resource doesn't exist in the base game files.".into(),
+					link: None
 				}
 			)?;
 		}
@@ -542,8 +678,11 @@ pub async fn open_factory(app: &AppHandle, factory: String) -> Result<()> {
 			Notification {
 				kind: NotificationKind::Error,
 				title: "No game selected".into(),
-				subtitle: "You can't open game files without a copy of the game selected.".into()
+				subtitle: "You can't open game files without a copy of the game selected.".into(),
+				link: None
 			}
 		)?;
 	}
+
+	None
 }
@@ -16,7 +16,9 @@ use crate::{
 		AppSettings, AppState, EditorData, EditorRequest, EntityEditorRequest, EntityOverridesEvent,
 		EntityOverridesRequest, GlobalRequest, Request
 	},
-	send_request, start_task
+	send_request,
+	split_view::{get_tab_state, get_tab_state_mut},
+	start_task
 };
 
 #[try_fn]
@@ -188,7 +190,7 @@ pub async fn handle(app: &AppHandle, event: EntityOverridesEvent) -> Result<()>
 
 	match event {
 		EntityOverridesEvent::Initialise { editor_id } => {
-			let editor_state = app_state.editor_states.get(&editor_id).context("No such editor")?;
+			let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
 
 			let entity = match editor_state.data {
 				EditorData::QNEntity { ref entity, .. } => entity,
@@ -249,7 +251,7 @@ pub async fn handle(app: &AppHandle, event: EntityOverridesEvent) -> Result<()>
 		}
 
 		EntityOverridesEvent::UpdatePropertyOverrides { editor_id, content } => {
-			let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 			let entity = match editor_state.data {
 				EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -279,7 +281,7 @@ pub async fn handle(app: &AppHandle, event: EntityOverridesEvent) -> Result<()>
 		}
 
 		EntityOverridesEvent::UpdateOverrideDeletes { editor_id, content } => {
-			let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 			let entity = match editor_state.data {
 				EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -309,7 +311,7 @@ pub async fn handle(app: &AppHandle, event: EntityOverridesEvent) -> Result<()>
 		}
 
 		EntityOverridesEvent::UpdatePinConnectionOverrides { editor_id, content } => {
-			let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 			let entity = match editor_state.data {
 				EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -339,7 +341,7 @@ pub async fn handle(app: &AppHandle, event: EntityOverridesEvent) -> Result<()>
 		}
 
 		EntityOverridesEvent::UpdatePinConnectionOverrideDeletes { editor_id, content } => {
-			let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 			let entity = match editor_state.data {
 				EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -0,0 +1,130 @@
+use anyhow::{Context, Result, anyhow};
+use fn_error_context::context;
+use hashbrown::HashMap;
+use itertools::Itertools;
+use quickentity_rs::qn_structs::Entity;
+use regex::Regex;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::{
+	entity::get_local_reference,
+	model::{
+		AppState, EditorData, EditorRequest, EntityEditorRequest, EntityProblem, EntityProblemsEvent,
+		EntityProblemsRequest, ProjectSettings, Request
+	},
+	send_request,
+	split_view::get_tab_state
+};
+
+/// Names which are left over from creating an entity and never renaming it; compared case-insensitively.
+const DEFAULT_ENTITY_NAMES: [&str; 6] = ["new entity", "new folder", "entity", "unnamed", "untitled", "name"];
+
+/// Check the names of an entity's sub-entities against the project's naming conventions, for duplicate names under the
+/// same parent and for default names.
+#[try_fn]
+#[context("Couldn't check entity {} for problems", entity.factory_hash)]
+pub fn find_entity_problems(entity: &Entity, settings: &ProjectSettings) -> Result<Vec<EntityProblem>> {
+	let patterns = settings
+		.entity_name_patterns
+		.iter()
+		.map(|pattern| Regex::new(pattern).with_context(|| format!("Invalid entity name pattern {}", pattern)))
+		.collect::<Result<Vec<_>>>()?;
+
+	let mut problems = vec![];
+	let mut names_by_parent: HashMap<(Option<String>, &str), Vec<&str>> = HashMap::new();
+
+	for (entity_id, sub_entity) in entity.entities.iter().sorted_by_key(|(id, _)| *id) {
+		let name = sub_entity.name.trim();
+
+		if name.is_empty() {
+			problems.push(EntityProblem {
+				entity_id: entity_id.to_owned(),
+				name: sub_entity.name.to_owned(),
+				message: "This entity has no name".into()
+			});
+		} else if DEFAULT_ENTITY_NAMES.contains(&name.to_lowercase().as_str()) {
+			problems.push(EntityProblem {
+				entity_id: entity_id.to_owned(),
+				name: sub_entity.name.to_owned(),
+				message: "This entity still has a default name".into()
+			});
+		} else if !patterns.is_empty() && !patterns.iter().any(|x| x.is_match(&sub_entity.name)) {
+			problems.push(EntityProblem {
+				entity_id: entity_id.to_owned(),
+				name: sub_entity.name.to_owned(),
+				message: "This entity's name doesn't match any of the project's naming conventions".into()
+			});
+		}
+
+		if !name.is_empty() {
+			names_by_parent
+				.entry((get_local_reference(&sub_entity.parent), name))
+				.or_default()
+				.push(entity_id);
+		}
+	}
+
+	for ((_, name), entity_ids) in names_by_parent.into_iter().sorted_by(|(a, _), (b, _)| a.cmp(b)) {
+		if entity_ids.len() > 1 {
+			for entity_id in &entity_ids {
+				problems.push(EntityProblem {
+					entity_id: (*entity_id).to_owned(),
+					name: name.to_owned(),
+					message: format!(
+						"{} other entit{} under the same parent also named {}",
+						entity_ids.len() - 1,
+						if entity_ids.len() == 2 { "y is" } else { "ies are" },
+						name
+					)
+				});
+			}
+		}
+	}
+
+	problems
+}
+
+#[try_fn]
+#[context("Couldn't handle entity problems event")]
+pub async fn handle(app: &AppHandle, event: EntityProblemsEvent) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	match event {
+		EntityProblemsEvent::Check { editor_id } => {
+			let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref entity, .. } => entity,
+				EditorData::QNPatch { ref current, .. } => current,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+					panic!();
+				}
+			};
+
+			let settings = app_state
+				.project
+				.load()
+				.as_ref()
+				.map(|x| (*x.settings.load_full()).to_owned());
+
+			let enabled = settings.as_ref().is_some_and(|x| x.lint_entity_names);
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::Entity(EntityEditorRequest::Problems(
+					EntityProblemsRequest::SetProblems {
+						editor_id,
+						enabled,
+						problems: match settings {
+							Some(settings) if enabled => find_entity_problems(entity, &settings)?,
+							_ => vec![]
+						}
+					}
+				)))
+			)?;
+		}
+	}
+}
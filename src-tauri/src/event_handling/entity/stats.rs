@@ -0,0 +1,224 @@
+use anyhow::{Context, Result, anyhow};
+use arc_swap::ArcSwap;
+use fn_error_context::context;
+use hashbrown::HashMap;
+use hitman_commons::game::GameVersion;
+use itertools::Itertools;
+use quickentity_rs::{convert_to_rt, qn_structs::Entity};
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::{
+	get_loaded_game_version,
+	model::{
+		AppSettings, AppState, EditorData, EditorRequest, EntityEditorRequest, EntityStats, EntityStatsEvent,
+		EntityStatsRequest, PatchSummary, Request
+	},
+	resourcelib::{
+		h2_convert_blueprint_to_binary, h2_convert_factory_to_binary, h3_convert_blueprint_to_binary,
+		h3_convert_factory_to_binary
+	},
+	send_request,
+	split_view::get_tab_state
+};
+
+#[try_fn]
+#[context("Couldn't calculate stats for entity {}", entity.factory_hash)]
+pub fn calculate_entity_stats(entity: &Entity, game_version: GameVersion) -> Result<EntityStats> {
+	let mut factory_counts: HashMap<&str, u32> = HashMap::new();
+	let mut property_count = 0;
+	let mut platform_specific_property_count = 0;
+	let mut pin_connection_count = 0;
+
+	for sub_entity in entity.entities.values() {
+		*factory_counts.entry(&sub_entity.factory).or_default() += 1;
+
+		property_count += sub_entity.properties.as_ref().map(|x| x.len()).unwrap_or(0);
+
+		platform_specific_property_count += sub_entity
+			.platform_specific_properties
+			.as_ref()
+			.map(|x| x.values().map(|x| x.len()).sum::<usize>())
+			.unwrap_or(0);
+
+		let pins = [
+			&sub_entity.events,
+			&sub_entity.input_copying,
+			&sub_entity.output_copying
+		];
+
+		pin_connection_count += pins
+			.into_iter()
+			.flatten()
+			.flat_map(|x| x.values())
+			.flat_map(|x| x.values())
+			.map(|x| x.len())
+			.sum::<usize>();
+	}
+
+	let (factory, factory_meta, blueprint, blueprint_meta) =
+		convert_to_rt(entity).map_err(|x| anyhow!("QuickEntity error: {:?}", x))?;
+
+	// H1 uses a different binary format which can't be generated from the modern structures
+	let (temp_size, tblu_size) = match game_version {
+		GameVersion::H1 => (None, None),
+
+		GameVersion::H2 => (
+			h2_convert_factory_to_binary(&factory).ok().map(|x| x.len() as u32),
+			h2_convert_blueprint_to_binary(&blueprint).ok().map(|x| x.len() as u32)
+		),
+
+		GameVersion::H3 => (
+			h3_convert_factory_to_binary(&factory).ok().map(|x| x.len() as u32),
+			h3_convert_blueprint_to_binary(&blueprint).ok().map(|x| x.len() as u32)
+		)
+	};
+
+	EntityStats {
+		entity_count: entity.entities.len() as u32,
+		factory_counts: factory_counts
+			.into_iter()
+			.sorted_by(|(a_factory, a_count), (b_factory, b_count)| {
+				b_count.cmp(a_count).then_with(|| a_factory.cmp(b_factory))
+			})
+			.map(|(factory, count)| (factory.to_owned(), count))
+			.collect(),
+		property_count: property_count as u32,
+		platform_specific_property_count: platform_specific_property_count as u32,
+		pin_connection_count: pin_connection_count as u32,
+		external_scene_count: entity.external_scenes.len() as u32,
+		dependency_count: (factory_meta.hash_reference_data.len() + blueprint_meta.hash_reference_data.len()) as u32,
+		temp_size,
+		tblu_size
+	}
+}
+
+/// Summarise what a patch changes: the sub-entities it adds, removes and renames, the properties it modifies by type and
+/// the external scenes it adds or removes.
+pub fn summarise_patch(base: &Entity, current: &Entity) -> PatchSummary {
+	let mut added_entities = vec![];
+	let mut renamed_entities = vec![];
+	let mut modified_entity_count = 0;
+	let mut property_counts: HashMap<&str, u32> = HashMap::new();
+
+	for (id, sub_entity) in &current.entities {
+		let Some(original) = base.entities.get(id) else {
+			added_entities.push((id.to_owned(), sub_entity.name.to_owned()));
+			continue;
+		};
+
+		if sub_entity == original {
+			continue;
+		}
+
+		modified_entity_count += 1;
+
+		if sub_entity.name != original.name {
+			renamed_entities.push((id.to_owned(), original.name.to_owned(), sub_entity.name.to_owned()));
+		}
+
+		let properties = sub_entity.properties.as_ref();
+		let original_properties = original.properties.as_ref();
+
+		for name in properties
+			.into_iter()
+			.flat_map(|x| x.keys())
+			.chain(original_properties.into_iter().flat_map(|x| x.keys()))
+			.unique()
+		{
+			let property = properties.and_then(|x| x.get(name));
+			let original_property = original_properties.and_then(|x| x.get(name));
+
+			if property != original_property
+				&& let Some(changed) = property.or(original_property)
+			{
+				*property_counts.entry(&changed.property_type).or_default() += 1;
+			}
+		}
+	}
+
+	let removed_entities = base
+		.entities
+		.iter()
+		.filter(|(id, _)| !current.entities.contains_key(*id))
+		.map(|(id, sub_entity)| (id.to_owned(), sub_entity.name.to_owned()))
+		.sorted_by(|a, b| a.1.cmp(&b.1))
+		.collect();
+
+	PatchSummary {
+		added_entities: added_entities.into_iter().sorted_by(|a, b| a.1.cmp(&b.1)).collect(),
+		removed_entities,
+		renamed_entities: renamed_entities.into_iter().sorted_by(|a, b| a.1.cmp(&b.1)).collect(),
+		modified_entity_count,
+		modified_properties: property_counts
+			.into_iter()
+			.sorted_by(|(a_type, a_count), (b_type, b_count)| b_count.cmp(a_count).then_with(|| a_type.cmp(b_type)))
+			.map(|(property_type, count)| (property_type.to_owned(), count))
+			.collect(),
+		added_external_scenes: current
+			.external_scenes
+			.iter()
+			.filter(|x| !base.external_scenes.contains(x))
+			.cloned()
+			.collect(),
+		removed_external_scenes: base
+			.external_scenes
+			.iter()
+			.filter(|x| !current.external_scenes.contains(x))
+			.cloned()
+			.collect()
+	}
+}
+
+#[try_fn]
+#[context("Couldn't handle entity stats event")]
+pub async fn handle(app: &AppHandle, event: EntityStatsEvent) -> Result<()> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+	let app_state = app.state::<AppState>();
+
+	match event {
+		EntityStatsEvent::Calculate { editor_id } => {
+			let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref entity, .. } => entity,
+				EditorData::QNPatch { ref current, .. } => current,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+					panic!();
+				}
+			};
+
+			let game_version = get_loaded_game_version(
+				app,
+				app_settings.load().game_install.as_ref().context("No game selected")?
+			)?;
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::Entity(EntityEditorRequest::Stats(
+					EntityStatsRequest::SetStats {
+						editor_id,
+						stats: calculate_entity_stats(entity, game_version)?
+					}
+				)))
+			)?;
+
+			if let EditorData::QNPatch {
+				ref base, ref current, ..
+			} = editor_state.data
+			{
+				send_request(
+					app,
+					Request::Editor(EditorRequest::Entity(EntityEditorRequest::Stats(
+						EntityStatsRequest::SetPatchSummary {
+							editor_id,
+							summary: summarise_patch(base, current)
+						}
+					)))
+				)?;
+			}
+		}
+	}
+}
@@ -0,0 +1,255 @@
+use anyhow::{Context, Result, anyhow};
+use fn_error_context::context;
+use hashbrown::HashMap;
+use itertools::Itertools;
+use quickentity_rs::qn_structs::Entity;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::{
+	Notification, NotificationKind,
+	entity::get_diff_info,
+	model::{
+		AppState, EditorData, EditorRequest, EntityEditorRequest, EntityMonacoRequest, EntitySubset,
+		EntitySubsetsEvent, EntitySubsetsRequest, EntityTreeRequest, GlobalRequest, Request
+	},
+	send_notification, send_request,
+	split_view::{get_tab_state, get_tab_state_mut}
+};
+
+use super::meta_pane::resolve_local_entity;
+
+/// Gather the subsets of an entity from the `subsets` of their members, sorted by owner name and then subset name.
+pub fn get_entity_subsets(entity: &Entity) -> Vec<EntitySubset> {
+	let mut subsets: HashMap<(&str, &str), Vec<(String, String)>> = HashMap::new();
+
+	for (entity_id, sub_entity) in &entity.entities {
+		for (subset, owners) in sub_entity.subsets.as_ref().unwrap_or(&Default::default()) {
+			for owner in owners {
+				subsets
+					.entry((owner, subset))
+					.or_default()
+					.push((entity_id.to_owned(), sub_entity.name.to_owned()));
+			}
+		}
+	}
+
+	subsets
+		.into_iter()
+		.map(|((owner, name), members)| EntitySubset {
+			owner: owner.to_owned(),
+			owner_name: entity
+				.entities
+				.get(owner)
+				.map(|x| x.name.to_owned())
+				.unwrap_or_else(|| owner.to_owned()),
+			name: name.to_owned(),
+			members: members
+				.into_iter()
+				.sorted_by(|(a_id, a_name), (b_id, b_name)| a_name.cmp(b_name).then_with(|| a_id.cmp(b_id)))
+				.collect()
+		})
+		.sorted_by(|a, b| {
+			a.owner_name
+				.cmp(&b.owner_name)
+				.then_with(|| a.owner.cmp(&b.owner))
+				.then_with(|| a.name.cmp(&b.name))
+		})
+		.collect()
+}
+
+/// Let the frontend know that a sub-entity's subset membership was changed.
+#[try_fn]
+#[context("Couldn't send subset change to frontend")]
+fn finish_subset_change(app: &AppHandle, editor_id: Uuid, entity_id: &str, editor_data: &EditorData) -> Result<()> {
+	let entity = match editor_data {
+		EditorData::QNEntity { entity, .. } => entity,
+		EditorData::QNPatch { current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	send_request(
+		app,
+		Request::Global(GlobalRequest::SetTabUnsaved {
+			id: editor_id,
+			unsaved: true
+		})
+	)?;
+
+	let mut buf = Vec::new();
+	let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+	let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+	entity
+		.entities
+		.get(entity_id)
+		.context("No such entity")?
+		.serialize(&mut ser)?;
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
+			EntityMonacoRequest::ReplaceContentIfSameEntityID {
+				editor_id,
+				entity_id: entity_id.to_owned(),
+				content: String::from_utf8(buf)?
+			}
+		)))
+	)?;
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Subsets(
+			EntitySubsetsRequest::SetSubsets {
+				editor_id,
+				subsets: get_entity_subsets(entity)
+			}
+		)))
+	)?;
+
+	if let EditorData::QNPatch { base, current, .. } = editor_data {
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+				EntityTreeRequest::SetDiffInfo {
+					editor_id,
+					diff_info: get_diff_info(base, current)
+				}
+			)))
+		)?;
+	}
+}
+
+#[try_fn]
+#[context("Couldn't handle entity subsets event")]
+pub async fn handle(app: &AppHandle, event: EntitySubsetsEvent) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	match event {
+		EntitySubsetsEvent::Initialise { editor_id } => {
+			let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref entity, .. } => entity,
+				EditorData::QNPatch { ref current, .. } => current,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+					panic!();
+				}
+			};
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::Entity(EntityEditorRequest::Subsets(
+					EntitySubsetsRequest::SetSubsets {
+						editor_id,
+						subsets: get_entity_subsets(entity)
+					}
+				)))
+			)?;
+		}
+
+		EntitySubsetsEvent::AddMember {
+			editor_id,
+			owner,
+			subset,
+			entity_id
+		} => {
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref mut entity, .. } => entity,
+				EditorData::QNPatch { ref mut current, .. } => current,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+					panic!();
+				}
+			};
+
+			let resolved = if subset.trim().is_empty() {
+				Err("The subset must have a name".into())
+			} else {
+				resolve_local_entity(entity, &owner)
+					.and_then(|owner| Ok((owner, resolve_local_entity(entity, &entity_id)?)))
+			};
+
+			let (owner, entity_id) = match resolved {
+				Ok(resolved) => resolved,
+
+				Err(problem) => {
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Error,
+							title: "Couldn't add subset member".into(),
+							subtitle: problem,
+							link: None
+						}
+					)?;
+
+					return Ok(());
+				}
+			};
+
+			let owners = entity
+				.entities
+				.get_mut(&entity_id)
+				.context("No such entity")?
+				.subsets
+				.get_or_insert_with(Default::default)
+				.entry(subset.trim().to_owned())
+				.or_default();
+
+			if !owners.contains(&owner) {
+				owners.push(owner);
+			}
+
+			finish_subset_change(app, editor_id, &entity_id, &editor_state.data)?;
+		}
+
+		EntitySubsetsEvent::RemoveMember {
+			editor_id,
+			owner,
+			subset,
+			entity_id
+		} => {
+			let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+			let entity = match editor_state.data {
+				EditorData::QNEntity { ref mut entity, .. } => entity,
+				EditorData::QNPatch { ref mut current, .. } => current,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+					panic!();
+				}
+			};
+
+			let sub_entity = entity.entities.get_mut(&entity_id).context("No such entity")?;
+
+			if let Some(subsets) = sub_entity.subsets.as_mut() {
+				if let Some(owners) = subsets.get_mut(&subset) {
+					owners.retain(|x| *x != owner);
+
+					if owners.is_empty() {
+						subsets.shift_remove(&subset);
+					}
+				}
+
+				if subsets.is_empty() {
+					sub_entity.subsets = None;
+				}
+			}
+
+			finish_subset_change(app, editor_id, &entity_id, &editor_state.data)?;
+		}
+	}
+}
@@ -1,6 +1,6 @@
-use std::ops::Deref;
+use std::{fs, ops::Deref, path::PathBuf};
 
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 use arboard::Clipboard;
 use arc_swap::ArcSwap;
 use fn_error_context::context;
@@ -10,43 +10,71 @@ use hitman_commons::{
 	metadata::{PathedID, RuntimeID}
 };
 use hitman_formats::wwev::WwiseEvent;
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use itertools::Itertools;
 use log::debug;
 use quickentity_rs::{
-	apply_patch,
+	apply_patch, generate_patch,
 	patch_structs::{Patch, PatchOperation, SubEntityOperation},
-	qn_structs::{FullRef, Property, Ref, RefMaybeConstantValue, RefWithConstantValue, SubEntity}
+	qn_structs::{
+		Entity, FullRef, Property, Ref, RefMaybeConstantValue, RefWithConstantValue, SimpleProperty, SubEntity, SubType
+	}
 };
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::Serialize;
-use serde_json::{Value, from_slice, from_str, from_value, json, to_string, to_value};
+use serde_json::{Value, from_slice, from_str, from_value, json, to_string, to_value, to_vec};
 use tauri::{AppHandle, Manager};
 use tryvial::try_fn;
 use uuid::Uuid;
 
 use crate::{
 	Notification, NotificationKind,
-	editor_connection::PropertyValue,
+	audio_event_stub::generate_audio_event_stub,
+	biome::format_json,
+	editor_connection::{PropertyValue, QNTransform, Vec3},
 	entity::{
 		CopiedEntityData, ReverseReferenceData, alter_ref_according_to_changelist, calculate_reverse_references,
-		change_reference_to_local, check_local_references_exist, get_decorations, get_diff_info, get_local_reference,
-		get_recursive_children, is_valid_entity_factory, random_entity_id
+		change_reference_to_local, check_local_references_exist, get_colours, get_decorations, get_diff_info,
+		get_external_ref_hovers, get_factory_name, get_local_ancestors, get_local_reference,
+		get_matching_property_paths, get_operation_sub_entity, get_recursive_children, is_valid_entity_factory,
+		parse_search_query, random_entity_id
 	},
+	entity_docs::DocumentationEntry,
 	finish_task, get_loaded_game_version,
 	model::{
-		AppSettings, AppState, EditorData, EditorRequest, EditorValidity, EntityEditorRequest, EntityGeneralRequest,
-		EntityMetaPaneRequest, EntityMonacoRequest, EntityTreeEvent, EntityTreeRequest, GlobalRequest, Request
+		AppSettings, AppState, ConstantValueConnection, DeletedEntities, EditorData, EditorRequest, EditorValidity,
+		EntityEditorRequest, EntityGeneralRequest, EntityMetaPaneRequest, EntityMetadataRequest, EntityMonacoRequest,
+		EntityTreeEvent, EntityTreeRequest, GlobalRequest, PastableTemplate, RecycleBinEntry, Request, SettingsRequest,
+		TemplateParameter, TemplateParameterKind, ToolRequest
 	},
+	navigation::record_navigation,
+	refuse_if_read_only,
 	resourcelib::{
-		h2_convert_binary_to_factory, h2_convert_cppt, h3_convert_binary_to_factory, h3_convert_cppt,
+		convert_async, h2_convert_binary_to_factory, h2_convert_cppt, h3_convert_binary_to_factory, h3_convert_cppt,
 		h2016_convert_binary_to_factory, h2016_convert_cppt
 	},
-	rpkg::{extract_entity, extract_latest_metadata, extract_latest_resource},
-	send_notification, send_request, start_task
+	rpkg::{extract_entity_async, extract_latest_metadata, extract_latest_resource},
+	send_notification, send_request,
+	split_view::{get_tab_state, get_tab_state_mut},
+	start_task,
+	telemetry::track_event,
+	transform::{ArrayPattern, align_to_surface, array_transforms, offset_transform, snap_to_grid, translate},
+	workspace_trust::refuse_if_untrusted
+};
+
+use super::{
+	meta_pane::{send_exposed_entities, send_property_aliases},
+	monaco::SAFE_TO_SYNC
 };
 
-use super::monaco::SAFE_TO_SYNC;
+/// Entities with more sub-entities than this are loaded lazily, with each level of the tree sent when it's expanded.
+const LAZY_TREE_THRESHOLD: usize = 5000;
+
+/// The most copies an array of entities can be generated with, so that a mistyped count doesn't flood the entity.
+const MAX_ARRAY_COPIES: u32 = 1000;
+
+/// The most deletions kept in an editor's recycle bin before the oldest are discarded.
+const MAX_RECYCLE_BIN_ENTRIES: usize = 50;
 
 #[try_fn]
 #[context("Couldn't handle tree event")]
@@ -98,6 +126,29 @@ pub async fn handle(app: &AppHandle, event: EntityTreeEvent) -> Result<()> {
 			.await?;
 		}
 
+		EntityTreeEvent::Duplicate {
+			editor_id,
+			id,
+			include_children,
+			offset
+		} => {
+			duplicate(app, editor_id, id, include_children, offset).await?;
+		}
+
+		EntityTreeEvent::GenerateArray { editor_id, id, pattern } => {
+			generate_array(app, editor_id, id, pattern).await?;
+		}
+
+		EntityTreeEvent::PasteAsInstance { editor_id, parent_id } => {
+			paste_as_instance(
+				app,
+				editor_id,
+				parent_id,
+				from_str::<CopiedEntityData>(&Clipboard::new()?.get_text()?)?
+			)
+			.await?;
+		}
+
 		EntityTreeEvent::Search { editor_id, query } => {
 			search(app, editor_id, query).await?;
 		}
@@ -109,9 +160,10 @@ pub async fn handle(app: &AppHandle, event: EntityTreeEvent) -> Result<()> {
 		EntityTreeEvent::UseTemplate {
 			editor_id,
 			parent_id,
-			template
+			template,
+			parameter_values
 		} => {
-			paste(app, editor_id, parent_id, template).await?;
+			use_template(app, editor_id, parent_id, template, parameter_values).await?;
 		}
 
 		EntityTreeEvent::AddGameBrowserItem {
@@ -122,6 +174,16 @@ pub async fn handle(app: &AppHandle, event: EntityTreeEvent) -> Result<()> {
 			add_game_browser_item(app, editor_id, parent_id, file).await?;
 		}
 
+		EntityTreeEvent::AddCustomSound {
+			editor_id,
+			parent_id,
+			name,
+			audio,
+			template
+		} => {
+			add_custom_sound(app, editor_id, parent_id, name, audio, template).await?;
+		}
+
 		EntityTreeEvent::SelectEntityInEditor { editor_id, entity_id } => {
 			select_entity_in_editor(app, editor_id, entity_id).await?;
 		}
@@ -145,6 +207,92 @@ pub async fn handle(app: &AppHandle, event: EntityTreeEvent) -> Result<()> {
 		EntityTreeEvent::RestoreToOriginal { editor_id, entity_id } => {
 			restore_to_original(app, editor_id, entity_id).await?;
 		}
+
+		EntityTreeEvent::RestoreDeleted { editor_id, index } => {
+			restore_deleted(app, editor_id, index as usize).await?;
+		}
+
+		EntityTreeEvent::ExtractFactoryToProject { editor_id, entity_id } => {
+			extract_factory_to_project(app, editor_id, entity_id).await?;
+		}
+
+		EntityTreeEvent::SnapEntityToGrid {
+			editor_id,
+			entity_id,
+			grid_size,
+			angle_snap
+		} => {
+			update_entity_transform(app, editor_id, entity_id, |transform| {
+				snap_to_grid(transform, grid_size, angle_snap)
+			})
+			.await?;
+		}
+
+		EntityTreeEvent::AlignEntityToSurface {
+			editor_id,
+			entity_id,
+			normal
+		} => {
+			update_entity_transform(app, editor_id, entity_id, |transform| {
+				align_to_surface(transform, &normal)
+			})
+			.await?;
+		}
+
+		EntityTreeEvent::OffsetEntityTransform {
+			editor_id,
+			entity_id,
+			position,
+			rotation,
+			local
+		} => {
+			update_entity_transform(app, editor_id, entity_id, |transform| {
+				offset_transform(transform, &position, &rotation, local)
+			})
+			.await?;
+		}
+
+		EntityTreeEvent::GetConnectionPins {
+			editor_id,
+			source_id,
+			target_id
+		} => {
+			get_connection_pins(app, editor_id, source_id, target_id).await?;
+		}
+
+		EntityTreeEvent::ConnectEntities {
+			editor_id,
+			source_id,
+			output_pin,
+			target_id,
+			input_pin,
+			value
+		} => {
+			connect_entities(app, editor_id, source_id, output_pin, target_id, input_pin, value).await?;
+		}
+
+		EntityTreeEvent::GetConstantValues { editor_id, entity_id } => {
+			get_constant_values(app, editor_id, entity_id).await?;
+		}
+
+		EntityTreeEvent::SetConstantValue {
+			editor_id,
+			entity_id,
+			output_pin,
+			input_pin,
+			index,
+			value
+		} => {
+			set_constant_value(app, editor_id, entity_id, output_pin, input_pin, index as usize, value).await?;
+		}
+
+		EntityTreeEvent::ExpandNode { editor_id, id } => {
+			expand_node(app, editor_id, id).await?;
+		}
+
+		EntityTreeEvent::RevealNode { editor_id, id } => {
+			reveal_node(app, editor_id, id).await?;
+		}
 	}
 }
 
@@ -153,7 +301,7 @@ pub async fn handle(app: &AppHandle, event: EntityTreeEvent) -> Result<()> {
 pub async fn initialise(app: &AppHandle, editor_id: Uuid) -> Result<()> {
 	let app_state = app.state::<AppState>();
 
-	let editor_state = app_state.editor_states.get(&editor_id).context("No such editor")?;
+	let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref entity, .. } => entity,
@@ -188,7 +336,14 @@ pub async fn initialise(app: &AppHandle, editor_id: Uuid) -> Result<()> {
 		}
 	}
 
+	let lazy = entity.entities.len() > LAZY_TREE_THRESHOLD;
+
 	for (entity_id, entity_data) in entity.entities.iter() {
+		// Lazily loaded trees only start with the root-level entities
+		if lazy && get_local_reference(&entity_data.parent).is_some() {
+			continue;
+		}
+
 		entities.push((
 			entity_id.to_owned(),
 			entity_data.parent.to_owned(),
@@ -213,7 +368,8 @@ pub async fn initialise(app: &AppHandle, editor_id: Uuid) -> Result<()> {
 		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
 			EntityTreeRequest::NewTree {
 				editor_id: editor_id.to_owned(),
-				entities
+				entities,
+				lazy
 			}
 		)))
 	)?;
@@ -250,6 +406,20 @@ pub async fn initialise(app: &AppHandle, editor_id: Uuid) -> Result<()> {
 		)))
 	)?;
 
+	if let EditorData::QNEntity { ref recycle_bin, .. } | EditorData::QNPatch { ref recycle_bin, .. } =
+		editor_state.data
+	{
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+				EntityTreeRequest::SetRecycleBin {
+					editor_id,
+					entries: get_recycle_bin_entries(recycle_bin)
+				}
+			)))
+		)?;
+	}
+
 	if let EditorData::QNPatch {
 		ref base, ref current, ..
 	} = editor_state.data
@@ -264,6 +434,97 @@ pub async fn initialise(app: &AppHandle, editor_id: Uuid) -> Result<()> {
 			)))
 		)?;
 	}
+
+	if let Some((_, id)) = app_state.pending_selections.remove(&editor_id) {
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+				EntityTreeRequest::Select {
+					editor_id,
+					id: Some(id)
+				}
+			)))
+		)?;
+	}
+}
+
+#[try_fn]
+#[context("Couldn't handle expand node event")]
+pub async fn expand_node(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+	let entity = match editor_state.data {
+		EditorData::QNEntity { ref entity, .. } => entity,
+		EditorData::QNPatch { ref current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	let parents: HashSet<String> = entity
+		.entities
+		.values()
+		.filter_map(|x| get_local_reference(&x.parent))
+		.collect();
+
+	let children = entity
+		.entities
+		.iter()
+		.filter(|(_, x)| get_local_reference(&x.parent).is_some_and(|x| x == id))
+		.map(|(entity_id, entity_data)| {
+			(
+				entity_id.to_owned(),
+				entity_data.parent.to_owned(),
+				entity_data.name.to_owned(),
+				entity_data.factory.to_owned(),
+				parents.contains(entity_id)
+			)
+		})
+		.collect();
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+			EntityTreeRequest::NodeChildren {
+				editor_id,
+				id,
+				children
+			}
+		)))
+	)?;
+}
+
+#[try_fn]
+#[context("Couldn't handle reveal node event")]
+pub async fn reveal_node(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+	let entity = match editor_state.data {
+		EditorData::QNEntity { ref entity, .. } => entity,
+		EditorData::QNPatch { ref current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+			EntityTreeRequest::RevealPath {
+				editor_id,
+				ancestors: get_local_ancestors(entity, &id),
+				id
+			}
+		)))
+	)?;
 }
 
 #[try_fn]
@@ -271,7 +532,7 @@ pub async fn initialise(app: &AppHandle, editor_id: Uuid) -> Result<()> {
 pub async fn create(app: &AppHandle, editor_id: Uuid, id: String, content: SubEntity) -> Result<()> {
 	let app_state = app.state::<AppState>();
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -314,7 +575,7 @@ pub async fn create(app: &AppHandle, editor_id: Uuid, id: String, content: SubEn
 pub async fn rename(app: &AppHandle, editor_id: Uuid, id: String, new_name: String) -> Result<()> {
 	let app_state = app.state::<AppState>();
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -379,7 +640,9 @@ pub async fn select(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()>
 	let app_settings = app.state::<ArcSwap<AppSettings>>();
 	let app_state = app.state::<AppState>();
 
-	let editor_state = app_state.editor_states.get(&editor_id).context("No such editor")?;
+	record_navigation(app, editor_id, Some(id.to_owned()));
+
+	let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref entity, .. } => entity,
@@ -473,6 +736,9 @@ pub async fn select(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()>
 		)))
 	)?;
 
+	send_property_aliases(app, editor_id.to_owned(), &id, entity)?;
+	send_exposed_entities(app, editor_id.to_owned(), &id, entity)?;
+
 	finish_task(app, task)?;
 
 	if let Some(intellisense) = app_state.intellisense.load().as_ref()
@@ -511,18 +777,81 @@ pub async fn select(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()>
 			}
 		);
 
+		let properties = properties?;
+		let pins = pins?;
+
+		let enums = properties
+			.iter()
+			.map(|(_, ty, _, _)| {
+				ty.strip_prefix("TArray<")
+					.and_then(|x| x.strip_suffix('>'))
+					.unwrap_or(ty)
+			})
+			.unique()
+			.filter_map(|ty| Some((ty.to_owned(), intellisense.enums.get(ty)?.to_owned())))
+			.collect();
+
+		let sub_entity = entity.entities.get(&id).context("No such entity")?;
+		let class = get_factory_name(&sub_entity.factory, Some(hash_list.as_ref()));
+		let entity_docs = app_state.entity_docs.load();
+
+		let property_docs = properties
+			.iter()
+			.filter_map(|(name, _, _, _)| {
+				Some((
+					name.to_owned(),
+					entity_docs.get_property(Some(&class), name)?.to_owned()
+				))
+			})
+			.collect();
+
+		// Pins which aren't in the documentation database fall back to the descriptions in the bundled CPPT pin list
+		let cppt_pins = RuntimeID::from_any(&sub_entity.factory)
+			.ok()
+			.and_then(|x| intellisense.cppt_pins.get(&x));
+
+		let pin_docs = pins
+			.0
+			.iter()
+			.chain(pins.1.iter())
+			.unique()
+			.filter_map(|pin| {
+				let docs = entity_docs.get_pin(Some(&class), pin).cloned().or_else(|| {
+					let cppt_pins = cppt_pins?;
+
+					cppt_pins
+						.inputs
+						.iter()
+						.chain(cppt_pins.outputs.iter())
+						.find(|x| x.name == *pin && !x.description.is_empty())
+						.map(|x| DocumentationEntry {
+							description: x.description.to_owned(),
+							unit: None,
+							example: None
+						})
+				})?;
+
+				Some((pin.to_owned(), docs))
+			})
+			.collect();
+
 		send_request(
 			app,
 			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
 				EntityMonacoRequest::UpdateIntellisense {
 					editor_id: editor_id.to_owned(),
 					entity_id: id.to_owned(),
-					properties: properties?,
-					pins: pins?
+					properties,
+					pins,
+					enums,
+					property_docs,
+					pin_docs
 				}
 			)))
 		)?;
 
+		intellisense.save_cache()?;
+
 		finish_task(app, task)?;
 
 		let task = start_task(app, format!("Computing decorations for {}", id))?;
@@ -549,7 +878,15 @@ pub async fn select(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()>
 						.filter(|(x, _)| entity.entities.contains_key(x))
 						.map(|(x, _)| x.to_owned())
 						.collect(),
-					decorations
+					decorations,
+					colours: get_colours(entity.entities.get(&id).context("No such entity")?),
+					external_ref_hovers: get_external_ref_hovers(
+						game_files,
+						&app_state.cached_entities,
+						game_version,
+						hash_list,
+						entity.entities.get(&id).context("No such entity")?
+					)
 				}
 			)))
 		)?;
@@ -574,7 +911,7 @@ pub async fn select(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()>
 pub async fn reparent(app: &AppHandle, editor_id: Uuid, id: String, new_parent: Ref) -> Result<()> {
 	let app_state = app.state::<AppState>();
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -640,11 +977,20 @@ pub async fn delete(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()>
 
 	let task = start_task(app, format!("Deleting entity {}", id))?;
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
-	let entity = match editor_state.data {
-		EditorData::QNEntity { ref mut entity, .. } => entity,
-		EditorData::QNPatch { ref mut current, .. } => current,
+	let (entity, recycle_bin) = match editor_state.data {
+		EditorData::QNEntity {
+			ref mut entity,
+			ref mut recycle_bin,
+			..
+		} => (entity, recycle_bin),
+
+		EditorData::QNPatch {
+			ref mut current,
+			ref mut recycle_bin,
+			..
+		} => (current, recycle_bin),
 
 		_ => {
 			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
@@ -658,6 +1004,32 @@ pub async fn delete(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()>
 		.into_iter()
 		.collect::<HashSet<_>>();
 
+	// The entities which refer to the deleted entities are compared before and after deletion, so that the references
+	// can be put back if the deleted entities are restored
+	let referencing_entities = entities_to_delete
+		.iter()
+		.filter_map(|x| reverse_refs.get(x))
+		.flatten()
+		.filter(|x| !matches!(x.data, ReverseReferenceData::Parent) && !entities_to_delete.contains(&x.from))
+		.map(|x| x.from.to_owned())
+		.collect::<HashSet<_>>();
+
+	let references_before = entity
+		.entities
+		.iter()
+		.filter(|(x, _)| referencing_entities.contains(*x))
+		.map(|(x, y)| (x.to_owned(), y.to_owned()))
+		.collect::<IndexMap<_, _>>();
+
+	let deleted_entities = entity
+		.entities
+		.iter()
+		.filter(|(x, _)| entities_to_delete.contains(*x))
+		.map(|(x, y)| (x.to_owned(), y.to_owned()))
+		.collect::<Vec<_>>();
+
+	let deleted_name = entity.entities.get(&id).context("No such entity")?.name.to_owned();
+
 	let mut patch = Patch {
 		factory_hash: String::new(),
 		blueprint_hash: String::new(),
@@ -934,6 +1306,43 @@ pub async fn delete(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()>
 
 	entity.entities.retain(|x, _| !entities_to_delete.contains(x));
 
+	let references_after = entity
+		.entities
+		.iter()
+		.filter(|(x, _)| referencing_entities.contains(*x))
+		.map(|(x, y)| (x.to_owned(), y.to_owned()))
+		.collect::<IndexMap<_, _>>();
+
+	// Only the sub-entities differ between the two, so the rest of the entity is cloned without them
+	let all_entities = std::mem::take(&mut entity.entities);
+	let mut before = (**entity).to_owned();
+	entity.entities = all_entities;
+
+	let mut after = before.to_owned();
+	before.entities = references_before;
+	after.entities = references_after;
+
+	recycle_bin.push(DeletedEntities {
+		id: id.to_owned(),
+		name: deleted_name,
+		entities: deleted_entities,
+		references: generate_patch(&after, &before).map_err(|x| anyhow!(x))?
+	});
+
+	if recycle_bin.len() > MAX_RECYCLE_BIN_ENTRIES {
+		recycle_bin.remove(0);
+	}
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+			EntityTreeRequest::SetRecycleBin {
+				editor_id,
+				entries: get_recycle_bin_entries(recycle_bin)
+			}
+		)))
+	)?;
+
 	finish_task(app, task)?;
 
 	send_notification(
@@ -946,10 +1355,12 @@ pub async fn delete(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()>
 				if entities_to_delete.len() == 1 { "y" } else { "ies" }
 			),
 			subtitle: format!(
-				"The entity, its children and {} reference{} have been deleted",
+				"The entity, its children and {} reference{} have been deleted; they can be restored from the recycle \
+				 bin until the tab is closed",
 				refs_deleted,
 				if refs_deleted == 1 { "" } else { "s" }
-			)
+			),
+			link: None
 		}
 	)?;
 
@@ -988,17 +1399,24 @@ pub async fn delete(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()>
 }
 
 #[try_fn]
-#[context("Couldn't handle copy event")]
-pub async fn copy(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()> {
+#[context("Couldn't handle restore deleted event")]
+pub async fn restore_deleted(app: &AppHandle, editor_id: Uuid, index: usize) -> Result<()> {
 	let app_state = app.state::<AppState>();
 
-	let task = start_task(app, format!("Copying entity {} and its children", id))?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
-	let editor_state = app_state.editor_states.get(&editor_id).context("No such editor")?;
+	let (entity, recycle_bin) = match editor_state.data {
+		EditorData::QNEntity {
+			ref mut entity,
+			ref mut recycle_bin,
+			..
+		} => (entity, recycle_bin),
 
-	let entity = match editor_state.data {
-		EditorData::QNEntity { ref entity, .. } => entity,
-		EditorData::QNPatch { ref current, .. } => current,
+		EditorData::QNPatch {
+			ref mut current,
+			ref mut recycle_bin,
+			..
+		} => (current, recycle_bin),
 
 		_ => {
 			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
@@ -1006,516 +1424,1123 @@ pub async fn copy(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()> {
 		}
 	};
 
-	let reverse_refs = calculate_reverse_references(entity)?;
+	let deleted = recycle_bin.get(index).context("No such recycle bin entry")?;
 
-	let entities_to_copy = get_recursive_children(entity, &id, &reverse_refs)?
-		.into_iter()
-		.collect::<HashSet<_>>();
+	if let Some((existing, _)) = deleted.entities.iter().find(|(x, _)| entity.entities.contains_key(x)) {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Error,
+				title: "Can't restore entities".into(),
+				subtitle: format!("An entity with the ID {} already exists.", existing),
+				link: None
+			}
+		)?;
 
-	let data_to_copy = CopiedEntityData {
-		root_entity: id.to_owned(),
-		data: entity
-			.entities
-			.iter()
-			.filter(|(x, _)| entities_to_copy.contains(*x))
-			.map(|(x, y)| (x.to_owned(), y.to_owned()))
-			.collect()
-	};
+		return Ok(());
+	}
 
-	Clipboard::new()?.set_text(to_string(&data_to_copy)?)?;
+	let root_parent = deleted
+		.entities
+		.iter()
+		.find(|(x, _)| *x == deleted.id)
+		.and_then(|(_, x)| get_local_reference(&x.parent));
 
-	finish_task(app, task)?;
-}
+	if let Some(ref parent) = root_parent
+		&& !entity.entities.contains_key(parent)
+	{
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Error,
+				title: "Can't restore entities".into(),
+				subtitle: format!(
+					"The entity {} was parented to no longer exists; restore or recreate it first.",
+					parent
+				),
+				link: None
+			}
+		)?;
 
-#[try_fn]
-#[context("Couldn't handle paste event")]
-pub async fn paste(
-	app: &AppHandle,
-	editor_id: Uuid,
-	parent_id: String,
-	mut paste_data: CopiedEntityData
-) -> Result<()> {
-	let app_state = app.state::<AppState>();
+		return Ok(());
+	}
 
-	let task = start_task(
-		app,
-		format!(
-			"Pasting entity {}",
-			paste_data
-				.data
-				.get(&paste_data.root_entity)
-				.context("No such root entity")?
-				.name
-		)
-	)?;
+	let task = start_task(app, format!("Restoring entity {}", deleted.id))?;
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let deleted = recycle_bin.remove(index);
 
-	let entity = match editor_state.data {
-		EditorData::QNEntity { ref mut entity, .. } => entity,
-		EditorData::QNPatch { ref mut current, .. } => current,
+	let restored_count = deleted.entities.len();
 
-		_ => {
-			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
-			panic!();
-		}
-	};
+	entity.entities.extend(deleted.entities.iter().cloned());
 
-	let mut changed_entity_ids = HashMap::new();
-	let mut added_external_scenes = 0;
+	// References from entities which have since been deleted themselves are dropped
+	let mut references = deleted.references;
 
-	// Randomise new entity IDs for all subentities contained in the paste data
-	for id in paste_data.data.keys() {
-		changed_entity_ids.insert(id.to_owned(), random_entity_id());
-	}
+	references
+		.patch
+		.retain(|x| get_operation_sub_entity(x).is_none_or(|x| entity.entities.contains_key(&x)));
 
-	// The IDs of all entities in the paste, in both changed and original forms.
-	let all_paste_contents = paste_data
-		.data
-		.keys()
-		.cloned()
-		.chain(changed_entity_ids.values().cloned())
-		.collect::<HashSet<_>>();
-
-	// Change all internal references so they match with the new randomised entity IDs, and also remove any local references that don't exist in the entity we're pasting into
-	for (sub_entity_id, sub_entity) in paste_data.data.iter_mut() {
-		if paste_data.root_entity != *sub_entity_id {
-			// Parent refs are all internal to the paste since the paste is created based on parent hierarchy
-			sub_entity.parent = change_reference_to_local(
-				&sub_entity.parent,
-				changed_entity_ids
-					.get(&get_local_reference(&sub_entity.parent).unwrap())
-					.unwrap()
-					.to_owned()
-			);
-		}
+	let reference_count = references.patch.len();
 
-		for property_data in sub_entity
-			.properties
-			.as_mut()
-			.unwrap_or(&mut Default::default())
-			.values_mut()
-		{
-			if property_data.property_type == "SEntityTemplateReference" {
-				let entity_ref = alter_ref_according_to_changelist(
-					&from_value::<Ref>(property_data.value.to_owned()).context("Invalid reference")?,
-					&changed_entity_ids
-				);
+	apply_patch(entity, references, true).map_err(|x| anyhow!(x))?;
 
-				property_data.value = to_value(&entity_ref)?;
+	let mut reverse_parent_refs: HashSet<String> = HashSet::new();
 
-				// If the ref is external, add the external scene
-				if let Ref::Full(FullRef {
-					external_scene: Some(ref scene),
-					..
-				}) = entity_ref
-				{
-					if !entity.external_scenes.contains(scene) {
-						entity.external_scenes.push(scene.to_owned());
-						added_external_scenes += 1;
-					}
-				}
+	for entity_data in entity.entities.values() {
+		if let Some(parent) = get_local_reference(&entity_data.parent) {
+			reverse_parent_refs.insert(parent);
+		}
+	}
 
-				// If the ref is local but to a sub-entity that doesn't exist in the entity we're pasting into (and isn't an internal reference within the paste), set the property to null
-				if get_local_reference(&entity_ref)
-					.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
-					.unwrap_or(false)
-				{
-					property_data.value = Value::Null;
-				}
-			} else if property_data.property_type == "TArray<SEntityTemplateReference>" {
-				property_data.value = to_value(
-					from_value::<Vec<Ref>>(property_data.value.to_owned())
-						.context("Invalid reference array")?
-						.into_iter()
-						.map(|entity_ref| {
-							if let Ref::Full(FullRef {
-								external_scene: Some(ref scene),
-								..
-							}) = entity_ref
-							{
-								if !entity.external_scenes.contains(scene) {
-									entity.external_scenes.push(scene.to_owned());
-									added_external_scenes += 1;
-								}
-							}
+	let mut new_entities = deleted
+		.entities
+		.into_iter()
+		.map(|(id, sub_entity)| {
+			let x = reverse_parent_refs.contains(&id);
+			(id, sub_entity.parent, sub_entity.name, sub_entity.factory, x)
+		})
+		.collect::<Vec<_>>();
+
+	// Make sure the entity being restored under is updated to be considered a folder (if it's a ZEntity)
+	if let Some(parent) = root_parent {
+		let parent_data = entity.entities.get(&parent).context("No such entity")?;
+
+		new_entities.push((
+			parent.to_owned(),
+			parent_data.parent.to_owned(),
+			parent_data.name.to_owned(),
+			parent_data.factory.to_owned(),
+			true
+		));
+	}
 
-							alter_ref_according_to_changelist(&entity_ref, &changed_entity_ids)
-						})
-						.filter(|entity_ref| {
-							!get_local_reference(entity_ref)
-								.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
-								.unwrap_or(false)
-						})
-						.collect_vec()
-				)?;
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+			EntityTreeRequest::NewItems {
+				editor_id,
+				new_entities
 			}
-		}
+		)))
+	)?;
 
-		for properties in sub_entity
-			.platform_specific_properties
-			.as_mut()
-			.unwrap_or(&mut Default::default())
-			.values_mut()
-		{
-			for property_data in properties.values_mut() {
-				if property_data.property_type == "SEntityTemplateReference" {
-					let entity_ref = alter_ref_according_to_changelist(
-						&from_value::<Ref>(property_data.value.to_owned()).context("Invalid reference")?,
-						&changed_entity_ids
-					);
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+			EntityTreeRequest::SetRecycleBin {
+				editor_id,
+				entries: get_recycle_bin_entries(recycle_bin)
+			}
+		)))
+	)?;
 
-					property_data.value = to_value(&entity_ref)?;
+	finish_task(app, task)?;
 
-					// If the ref is external, add the external scene
-					if let Ref::Full(FullRef {
-						external_scene: Some(ref scene),
-						..
-					}) = entity_ref
-					{
-						if !entity.external_scenes.contains(scene) {
-							entity.external_scenes.push(scene.to_owned());
-							added_external_scenes += 1;
-						}
-					}
+	send_notification(
+		app,
+		Notification {
+			kind: NotificationKind::Info,
+			title: format!(
+				"Restored {} entit{}",
+				restored_count,
+				if restored_count == 1 { "y" } else { "ies" }
+			),
+			subtitle: format!(
+				"{} and {} reference{} to it have been restored",
+				deleted.name,
+				reference_count,
+				if reference_count == 1 { "" } else { "s" }
+			),
+			link: None
+		}
+	)?;
 
-					// If the ref is local but to a sub-entity that doesn't exist in the entity we're pasting into (and isn't an internal reference within the paste), set the property to null
-					if get_local_reference(&entity_ref)
-						.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
-						.unwrap_or(false)
-					{
-						property_data.value = Value::Null;
-					}
-				} else if property_data.property_type == "TArray<SEntityTemplateReference>" {
-					property_data.value = to_value(
-						from_value::<Vec<Ref>>(property_data.value.to_owned())
-							.context("Invalid reference array")?
-							.into_iter()
-							.map(|entity_ref| {
-								if let Ref::Full(FullRef {
-									external_scene: Some(ref scene),
-									..
-								}) = entity_ref
-								{
-									if !entity.external_scenes.contains(scene) {
-										entity.external_scenes.push(scene.to_owned());
-										added_external_scenes += 1;
-									}
-								}
+	send_request(
+		app,
+		Request::Global(GlobalRequest::SetTabUnsaved {
+			id: editor_id,
+			unsaved: true
+		})
+	)?;
 
-								alter_ref_according_to_changelist(&entity_ref, &changed_entity_ids)
-							})
-							.filter(|entity_ref| {
-								!get_local_reference(entity_ref)
-									.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
-									.unwrap_or(false)
-							})
-							.collect_vec()
-					)?;
+	if let EditorData::QNPatch {
+		ref base, ref current, ..
+	} = editor_state.data
+	{
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+				EntityTreeRequest::SetDiffInfo {
+					editor_id,
+					diff_info: get_diff_info(base, current)
 				}
-			}
-		}
+			)))
+		)?;
+	}
+}
 
-		for values in sub_entity
-			.events
-			.as_mut()
-			.unwrap_or(&mut Default::default())
-			.values_mut()
-		{
-			for refs in values.values_mut() {
-				for reference in refs.iter_mut() {
-					let underlying_ref = match reference {
-						RefMaybeConstantValue::Ref(x) => x,
-						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => {
-							entity_ref
-						}
-					};
+/// Summarise the entries of an editor's recycle bin for the frontend, most recently deleted first.
+pub fn get_recycle_bin_entries(recycle_bin: &[DeletedEntities]) -> Vec<RecycleBinEntry> {
+	recycle_bin
+		.iter()
+		.enumerate()
+		.rev()
+		.map(|(index, deleted)| RecycleBinEntry {
+			index: index as u32,
+			id: deleted.id.to_owned(),
+			name: deleted.name.to_owned(),
+			entity_count: deleted.entities.len() as u32,
+			reference_count: deleted.references.patch.len() as u32
+		})
+		.collect()
+}
 
-					if let Ref::Full(FullRef {
-						external_scene: Some(scene),
-						..
-					}) = underlying_ref
-					{
-						if !entity.external_scenes.contains(scene) {
-							entity.external_scenes.push(scene.to_owned());
-							added_external_scenes += 1;
-						}
-					}
+#[try_fn]
+#[context("Couldn't handle copy event")]
+pub async fn copy(app: &AppHandle, editor_id: Uuid, id: String) -> Result<()> {
+	let app_state = app.state::<AppState>();
 
-					*reference = match reference {
-						RefMaybeConstantValue::Ref(x) => {
-							RefMaybeConstantValue::Ref(alter_ref_according_to_changelist(x, &changed_entity_ids))
-						}
-						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, value }) => {
-							RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue {
-								entity_ref: alter_ref_according_to_changelist(entity_ref, &changed_entity_ids),
-								value: value.to_owned()
-							})
-						}
-					};
-				}
+	let task = start_task(app, format!("Copying entity {} and its children", id))?;
 
-				refs.retain(|reference| {
-					let underlying_ref = match reference {
-						RefMaybeConstantValue::Ref(x) => x,
-						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => {
-							entity_ref
-						}
-					};
+	let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
 
-					!get_local_reference(underlying_ref)
-						.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
-						.unwrap_or(false)
-				});
-			}
+	let entity = match editor_state.data {
+		EditorData::QNEntity { ref entity, .. } => entity,
+		EditorData::QNPatch { ref current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
 		}
+	};
 
-		for values in sub_entity
-			.input_copying
-			.as_mut()
-			.unwrap_or(&mut Default::default())
-			.values_mut()
-		{
-			for refs in values.values_mut() {
-				for reference in refs.iter_mut() {
-					let underlying_ref = match reference {
-						RefMaybeConstantValue::Ref(x) => x,
-						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => {
-							entity_ref
-						}
-					};
+	let reverse_refs = calculate_reverse_references(entity)?;
 
-					if let Ref::Full(FullRef {
-						external_scene: Some(scene),
-						..
-					}) = underlying_ref
-					{
-						if !entity.external_scenes.contains(scene) {
-							entity.external_scenes.push(scene.to_owned());
-							added_external_scenes += 1;
-						}
-					}
+	let entities_to_copy = get_recursive_children(entity, &id, &reverse_refs)?
+		.into_iter()
+		.collect::<HashSet<_>>();
 
-					*reference = match reference {
-						RefMaybeConstantValue::Ref(x) => {
-							RefMaybeConstantValue::Ref(alter_ref_according_to_changelist(x, &changed_entity_ids))
-						}
-						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, value }) => {
-							RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue {
-								entity_ref: alter_ref_according_to_changelist(entity_ref, &changed_entity_ids),
-								value: value.to_owned()
-							})
-						}
-					};
-				}
+	let data_to_copy = CopiedEntityData {
+		root_entity: id.to_owned(),
+		data: entity
+			.entities
+			.iter()
+			.filter(|(x, _)| entities_to_copy.contains(*x))
+			.map(|(x, y)| (x.to_owned(), y.to_owned()))
+			.collect()
+	};
 
-				refs.retain(|reference| {
-					let underlying_ref = match reference {
-						RefMaybeConstantValue::Ref(x) => x,
-						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => {
-							entity_ref
-						}
-					};
+	Clipboard::new()?.set_text(to_string(&data_to_copy)?)?;
 
-					!get_local_reference(underlying_ref)
-						.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
-						.unwrap_or(false)
-				});
-			}
-		}
+	finish_task(app, task)?;
+}
 
-		for values in sub_entity
-			.output_copying
-			.as_mut()
-			.unwrap_or(&mut Default::default())
-			.values_mut()
-		{
-			for refs in values.values_mut() {
-				for reference in refs.iter_mut() {
-					let underlying_ref = match reference {
-						RefMaybeConstantValue::Ref(x) => x,
-						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => {
-							entity_ref
-						}
-					};
+/// Get the next name in a sequence by incrementing the number at its end, keeping any leading zeroes, or by adding a
+/// number if it doesn't have one; `Crate 09` becomes `Crate 10` and `Crate` becomes `Crate 2`.
+fn increment_name(name: &str) -> String {
+	let prefix = name.trim_end_matches(|x: char| x.is_ascii_digit());
+	let digits = &name[prefix.len()..];
 
-					if let Ref::Full(FullRef {
-						external_scene: Some(scene),
-						..
-					}) = underlying_ref
-					{
-						if !entity.external_scenes.contains(scene) {
-							entity.external_scenes.push(scene.to_owned());
-							added_external_scenes += 1;
-						}
-					}
+	match digits.parse::<u64>() {
+		Ok(number) => format!("{}{:0width$}", prefix, number + 1, width = digits.len()),
+		Err(_) => format!("{} 2", name)
+	}
+}
 
-					*reference = match reference {
-						RefMaybeConstantValue::Ref(x) => {
-							RefMaybeConstantValue::Ref(alter_ref_according_to_changelist(x, &changed_entity_ids))
-						}
-						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, value }) => {
-							RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue {
-								entity_ref: alter_ref_according_to_changelist(entity_ref, &changed_entity_ids),
-								value: value.to_owned()
-							})
-						}
-					};
-				}
+/// Duplicate an entity, and optionally its children, under the same parent with fresh IDs. The copy's name has its
+/// number incremented until no sibling has the same name, and its transform is moved by the offset, so that a row of
+/// props can be made by duplicating repeatedly.
+#[try_fn]
+#[context("Couldn't handle duplicate event")]
+pub async fn duplicate(
+	app: &AppHandle,
+	editor_id: Uuid,
+	id: String,
+	include_children: bool,
+	offset: Vec3
+) -> Result<()> {
+	let app_state = app.state::<AppState>();
 
-				refs.retain(|reference| {
-					let underlying_ref = match reference {
-						RefMaybeConstantValue::Ref(x) => x,
-						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => {
-							entity_ref
-						}
-					};
+	let (parent_id, paste_data) = {
+		let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
 
-					!get_local_reference(underlying_ref)
-						.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
-						.unwrap_or(false)
-				});
+		let entity = match editor_state.data {
+			EditorData::QNEntity { ref entity, .. } => entity,
+			EditorData::QNPatch { ref current, .. } => current,
+
+			_ => {
+				Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+				panic!();
 			}
-		}
+		};
 
-		for aliases in sub_entity
-			.property_aliases
-			.as_mut()
-			.unwrap_or(&mut Default::default())
-			.values_mut()
-		{
-			for alias_data in aliases.iter_mut() {
-				alias_data.original_entity =
-					alter_ref_according_to_changelist(&alias_data.original_entity, &changed_entity_ids);
+		let sub_entity = entity.entities.get(&id).context("No such entity")?;
 
-				if let Ref::Full(FullRef {
-					external_scene: Some(ref scene),
-					..
-				}) = alias_data.original_entity
-				{
-					if !entity.external_scenes.contains(scene) {
-						entity.external_scenes.push(scene.to_owned());
-						added_external_scenes += 1;
-					}
-				}
-			}
+		let parent_id = get_local_reference(&sub_entity.parent)
+			.context("Only entities with a parent in the same entity can be duplicated")?;
 
-			aliases.retain(|alias_data| {
-				!get_local_reference(&alias_data.original_entity)
-					.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
-					.unwrap_or(false)
-			});
+		let siblings = entity
+			.entities
+			.values()
+			.filter(|x| get_local_reference(&x.parent).as_ref() == Some(&parent_id))
+			.map(|x| x.name.as_str())
+			.collect::<HashSet<_>>();
+
+		let mut name = increment_name(&sub_entity.name);
+
+		while siblings.contains(name.as_str()) {
+			name = increment_name(&name);
 		}
 
-		for exposed_entity in sub_entity
-			.exposed_entities
-			.as_mut()
-			.unwrap_or(&mut Default::default())
-			.values_mut()
-		{
-			for reference in exposed_entity.refers_to.iter_mut() {
-				*reference = alter_ref_according_to_changelist(reference, &changed_entity_ids);
+		let entities_to_copy = if include_children {
+			get_recursive_children(entity, &id, &calculate_reverse_references(entity)?)?
+				.into_iter()
+				.collect::<HashSet<_>>()
+		} else {
+			HashSet::from([id.to_owned()])
+		};
 
-				if let Ref::Full(FullRef {
-					external_scene: Some(scene),
-					..
-				}) = reference
-				{
-					if !entity.external_scenes.contains(scene) {
-						entity.external_scenes.push(scene.to_owned());
-						added_external_scenes += 1;
-					}
-				}
-			}
+		let mut paste_data = CopiedEntityData {
+			root_entity: id.to_owned(),
+			data: entity
+				.entities
+				.iter()
+				.filter(|(x, _)| entities_to_copy.contains(*x))
+				.map(|(x, y)| (x.to_owned(), y.to_owned()))
+				.collect()
+		};
 
-			exposed_entity.refers_to.retain(|x| {
-				// Only retain those not meeting the criteria for deletion (local ref, not in entity we're pasting into or the paste itself)
-				!get_local_reference(x)
-					.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
-					.unwrap_or(false)
-			});
-		}
+		let root = paste_data.data.get_mut(&id).context("No such entity")?;
 
-		for referenced_entity in sub_entity
-			.exposed_interfaces
-			.as_mut()
-			.unwrap_or(&mut Default::default())
-			.values_mut()
-		{
-			*referenced_entity = changed_entity_ids
-				.get(referenced_entity)
-				.unwrap_or(referenced_entity)
-				.to_owned();
+		root.name = name;
+
+		if let Some(property) = root.properties.as_mut().and_then(|x| x.get_mut("m_mTransform")) {
+			let transform = from_value::<QNTransform>(property.value.to_owned()).context("Invalid transform")?;
+
+			property.value = to_value(translate(&transform, &offset))?;
 		}
 
-		sub_entity
-			.exposed_interfaces
-			.as_mut()
-			.unwrap_or(&mut Default::default())
-			.retain(|_, x| entity.entities.contains_key(x) || all_paste_contents.contains(x));
+		(parent_id, paste_data)
+	};
 
-		for member_of in sub_entity
-			.subsets
-			.as_mut()
-			.unwrap_or(&mut Default::default())
-			.values_mut()
-		{
-			for parental_entity in member_of.iter_mut() {
-				*parental_entity = changed_entity_ids
-					.get(parental_entity)
-					.unwrap_or(parental_entity)
-					.to_owned();
+	paste(app, editor_id, parent_id, paste_data).await?;
+}
+
+/// Clone an entity and its children into a pattern, parented to a new spatial entity placed where the original is. The
+/// copies are spawned in the game if it's connected.
+#[try_fn]
+#[context("Couldn't handle generate array event")]
+pub async fn generate_array(app: &AppHandle, editor_id: Uuid, id: String, pattern: ArrayPattern) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	if pattern.count() == 0 || pattern.count() > MAX_ARRAY_COPIES {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Error,
+				title: "Invalid number of copies".into(),
+				subtitle: format!("An array can have between 1 and {} copies.", MAX_ARRAY_COPIES),
+				link: None
 			}
+		)?;
 
-			member_of.retain(|x| entity.entities.contains_key(x) || all_paste_contents.contains(x));
-		}
+		return Ok(());
 	}
 
-	// Change the actual entity IDs in the paste data
-	paste_data.data = paste_data
-		.data
-		.into_iter()
-		.map(|(x, y)| (changed_entity_ids.get(&x).unwrap().to_owned(), y))
-		.collect();
-
-	paste_data
-		.data
-		.get_mut(changed_entity_ids.get(&paste_data.root_entity).unwrap())
-		.unwrap()
-		.parent = change_reference_to_local(
-		&paste_data
-			.data
-			.get_mut(changed_entity_ids.get(&paste_data.root_entity).unwrap())
-			.unwrap()
-			.parent,
-		parent_id.to_owned()
+	track_event(
+		app,
+		"Generate entity array",
+		Some(json!({
+			"copies": pattern.count()
+		}))
 	);
 
-	entity.entities.extend(paste_data.data.to_owned());
-
-	let mut new_entities = vec![];
-	let mut reverse_parent_refs: HashSet<String> = HashSet::new();
+	let (group_id, group, blueprint_hash, copies) = {
+		let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
-	for entity_data in entity.entities.values() {
-		match entity_data.parent {
-			Ref::Full(ref reference) if reference.external_scene.is_none() => {
-				reverse_parent_refs.insert(reference.entity_ref.to_owned());
-			}
+		let entity = match editor_state.data {
+			EditorData::QNEntity { ref mut entity, .. } => entity,
+			EditorData::QNPatch { ref mut current, .. } => current,
 
-			Ref::Short(Some(ref reference)) => {
-				reverse_parent_refs.insert(reference.to_owned());
+			_ => {
+				Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+				panic!();
 			}
+		};
 
-			_ => {}
-		}
-	}
+		let sub_entity = entity.entities.get(&id).context("No such entity")?;
 
-	for (entity_id, entity_data) in paste_data.data {
-		let x = reverse_parent_refs.contains(&entity_id);
-		new_entities.push((entity_id, entity_data.parent, entity_data.name, entity_data.factory, x));
-	}
+		let original_transform = sub_entity
+			.properties
+			.as_ref()
+			.and_then(|x| x.get("m_mTransform"))
+			.map(|x| from_value::<QNTransform>(x.value.to_owned()).context("Invalid transform"))
+			.transpose()?
+			.unwrap_or(QNTransform {
+				rotation: Vec3 { x: 0.0, y: 0.0, z: 0.0 },
+				position: Vec3 { x: 0.0, y: 0.0, z: 0.0 },
+				scale: None
+			});
 
-	// Make sure the entity being pasted under is updated to be considered a folder (if it's a ZEntity)
+		let group_id = random_entity_id();
+
+		let group = SubEntity {
+			parent: sub_entity.parent.to_owned(),
+			name: format!("{} array", sub_entity.name),
+			factory: "[modules:/zspatialentity.class].pc_entitytype".into(),
+			blueprint: "[modules:/zspatialentity.class].pc_entityblueprint".into(),
+			factory_flag: None,
+			editor_only: None,
+			properties: Some(
+				[(
+					"m_mTransform".into(),
+					Property {
+						property_type: "SMatrix43".into(),
+						value: to_value(QNTransform {
+							rotation: Vec3 { x: 0.0, y: 0.0, z: 0.0 },
+							position: original_transform.position.to_owned(),
+							scale: None
+						})?,
+						post_init: None
+					}
+				)]
+				.into_iter()
+				.collect()
+			),
+			platform_specific_properties: None,
+			events: None,
+			input_copying: None,
+			output_copying: None,
+			property_aliases: None,
+			exposed_entities: None,
+			exposed_interfaces: None,
+			subsets: None
+		};
+
+		let entities_to_copy = get_recursive_children(entity, &id, &calculate_reverse_references(entity)?)?
+			.into_iter()
+			.collect::<HashSet<_>>();
+
+		let copied = CopiedEntityData {
+			root_entity: id.to_owned(),
+			data: entity
+				.entities
+				.iter()
+				.filter(|(x, _)| entities_to_copy.contains(*x))
+				.map(|(x, y)| (x.to_owned(), y.to_owned()))
+				.collect()
+		};
+
+		let copies = array_transforms(&original_transform, &pattern)
+			.into_iter()
+			.enumerate()
+			.map(|(index, transform)| {
+				let mut copy = copied.to_owned();
+
+				let root = copy.data.get_mut(&id).context("No such entity")?;
+
+				root.name = format!("{} {}", root.name, index + 1);
+
+				root.properties.get_or_insert_default().insert(
+					"m_mTransform".into(),
+					Property {
+						property_type: "SMatrix43".into(),
+						value: to_value(transform)?,
+						post_init: None
+					}
+				);
+
+				Ok(copy)
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		entity.entities.insert(group_id.to_owned(), group.to_owned());
+
+		(group_id, group, entity.blueprint_hash.to_owned(), copies)
+	};
+
+	let task = start_task(app, format!("Generating {} copies of {}", copies.len(), id))?;
+
+	let editor_connected = app_state.editor_connection.is_connected().await;
+
+	if editor_connected {
+		app_state
+			.editor_connection
+			.spawn_entity(&group.factory, &group_id, &group.name)
+			.await?;
+	}
+
+	for copy in copies {
+		let root = copy.data.get(&copy.root_entity).context("No such entity")?.to_owned();
+
+		let copy_id = paste(app, editor_id, group_id.to_owned(), copy).await?;
+
+		if editor_connected {
+			app_state
+				.editor_connection
+				.spawn_entity(&root.factory, &copy_id, &root.name)
+				.await?;
+
+			if let Some(transform) = root.properties.as_ref().and_then(|x| x.get("m_mTransform")) {
+				app_state
+					.editor_connection
+					.set_property(
+						&copy_id,
+						&blueprint_hash,
+						"m_mTransform",
+						PropertyValue {
+							property_type: "SMatrix43".into(),
+							data: transform.value.to_owned()
+						}
+					)
+					.await?;
+			}
+		}
+	}
+
+	finish_task(app, task)?;
+}
+
+/// Parse the value entered for a template parameter into what's substituted into the template, returning why it's
+/// invalid if it is.
+fn parse_template_parameter(parameter: &TemplateParameter, value: &str) -> Result<Value, String> {
+	let value = value.trim();
+
+	match parameter.kind {
+		TemplateParameterKind::Text => Ok(Value::String(value.to_owned())),
+
+		TemplateParameterKind::Position => {
+			let components = value
+				.split([',', ' '])
+				.filter(|x| !x.is_empty())
+				.map(|x| x.parse::<f64>())
+				.collect::<Result<Vec<_>, _>>()
+				.ok()
+				.filter(|x| x.len() == 3)
+				.ok_or_else(|| format!("{} must be three numbers, like 1.5, 0, -2", parameter.name))?;
+
+			Ok(json!({
+				"x": components[0],
+				"y": components[1],
+				"z": components[2]
+			}))
+		}
+
+		TemplateParameterKind::RepositoryId => {
+			Uuid::parse_str(value)
+				.map(|x| Value::String(x.to_string()))
+				.map_err(|_| {
+					format!(
+						"{} must be a repository ID, like 7af86858-df9d-478b-b5d8-803379cd165f",
+						parameter.name
+					)
+				})
+		}
+	}
+}
+
+/// Replace the placeholders of a template's parameters in a JSON value.
+fn substitute_template_parameters(value: &mut Value, parameters: &[(TemplateParameter, Value)]) {
+	match value {
+		Value::String(string) => {
+			for (parameter, parameter_value) in parameters {
+				let placeholder = format!("{{{{{}}}}}", parameter.name);
+
+				if *string == placeholder {
+					*value = parameter_value.to_owned();
+					return;
+				}
+
+				if let Value::String(parameter_value) = parameter_value {
+					*string = string.replace(&placeholder, parameter_value);
+				}
+			}
+		}
+
+		Value::Array(values) => {
+			for value in values {
+				substitute_template_parameters(value, parameters);
+			}
+		}
+
+		Value::Object(values) => {
+			for value in values.values_mut() {
+				substitute_template_parameters(value, parameters);
+			}
+		}
+
+		_ => {}
+	}
+}
+
+/// Paste a built-in template, asking the frontend for its parameters first if it has any.
+#[try_fn]
+#[context("Couldn't handle use template event")]
+pub async fn use_template(
+	app: &AppHandle,
+	editor_id: Uuid,
+	parent_id: String,
+	template: PastableTemplate,
+	parameter_values: Option<std::collections::HashMap<String, String>>
+) -> Result<()> {
+	if template.parameters.is_empty() {
+		paste(app, editor_id, parent_id, template.paste_data).await?;
+		return Ok(());
+	}
+
+	let Some(parameter_values) = parameter_values else {
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+				EntityTreeRequest::PromptTemplateParameters {
+					editor_id,
+					parent_id,
+					template
+				}
+			)))
+		)?;
+
+		return Ok(());
+	};
+
+	let parameters = template
+		.parameters
+		.iter()
+		.map(|parameter| {
+			let value = parameter_values
+				.get(&parameter.name)
+				.or(parameter.default.as_ref())
+				.ok_or_else(|| format!("No value was given for {}", parameter.name))?;
+
+			Ok((parameter.to_owned(), parse_template_parameter(parameter, value)?))
+		})
+		.collect::<Result<Vec<_>, String>>();
+
+	let parameters = match parameters {
+		Ok(parameters) => parameters,
+
+		Err(problem) => {
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Error,
+					title: "Invalid template parameter".into(),
+					subtitle: problem,
+					link: None
+				}
+			)?;
+
+			return Ok(());
+		}
+	};
+
+	let mut paste_data = to_value(&template.paste_data)?;
+	substitute_template_parameters(&mut paste_data, &parameters);
+
+	paste(
+		app,
+		editor_id,
+		parent_id,
+		from_value(paste_data).context("Template isn't valid after substituting parameters")?
+	)
+	.await?;
+}
+
+/// Paste copied entities under a parent with fresh IDs, returning the ID the root entity was pasted as.
+#[try_fn]
+#[context("Couldn't handle paste event")]
+pub async fn paste(
+	app: &AppHandle,
+	editor_id: Uuid,
+	parent_id: String,
+	mut paste_data: CopiedEntityData
+) -> Result<String> {
+	let app_state = app.state::<AppState>();
+
+	let task = start_task(
+		app,
+		format!(
+			"Pasting entity {}",
+			paste_data
+				.data
+				.get(&paste_data.root_entity)
+				.context("No such root entity")?
+				.name
+		)
+	)?;
+
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+	let entity = match editor_state.data {
+		EditorData::QNEntity { ref mut entity, .. } => entity,
+		EditorData::QNPatch { ref mut current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	let mut changed_entity_ids = HashMap::new();
+	let mut added_external_scenes = 0;
+
+	// Randomise new entity IDs for all subentities contained in the paste data
+	for id in paste_data.data.keys() {
+		changed_entity_ids.insert(id.to_owned(), random_entity_id());
+	}
+
+	let new_root_id = changed_entity_ids
+		.get(&paste_data.root_entity)
+		.context("No such root entity")?
+		.to_owned();
+
+	// The IDs of all entities in the paste, in both changed and original forms.
+	let all_paste_contents = paste_data
+		.data
+		.keys()
+		.cloned()
+		.chain(changed_entity_ids.values().cloned())
+		.collect::<HashSet<_>>();
+
+	// Change all internal references so they match with the new randomised entity IDs, and also remove any local references that don't exist in the entity we're pasting into
+	for (sub_entity_id, sub_entity) in paste_data.data.iter_mut() {
+		if paste_data.root_entity != *sub_entity_id {
+			// Parent refs are all internal to the paste since the paste is created based on parent hierarchy
+			sub_entity.parent = change_reference_to_local(
+				&sub_entity.parent,
+				changed_entity_ids
+					.get(&get_local_reference(&sub_entity.parent).unwrap())
+					.unwrap()
+					.to_owned()
+			);
+		}
+
+		for property_data in sub_entity
+			.properties
+			.as_mut()
+			.unwrap_or(&mut Default::default())
+			.values_mut()
+		{
+			if property_data.property_type == "SEntityTemplateReference" {
+				let entity_ref = alter_ref_according_to_changelist(
+					&from_value::<Ref>(property_data.value.to_owned()).context("Invalid reference")?,
+					&changed_entity_ids
+				);
+
+				property_data.value = to_value(&entity_ref)?;
+
+				// If the ref is external, add the external scene
+				if let Ref::Full(FullRef {
+					external_scene: Some(ref scene),
+					..
+				}) = entity_ref
+				{
+					if !entity.external_scenes.contains(scene) {
+						entity.external_scenes.push(scene.to_owned());
+						added_external_scenes += 1;
+					}
+				}
+
+				// If the ref is local but to a sub-entity that doesn't exist in the entity we're pasting into (and isn't an internal reference within the paste), set the property to null
+				if get_local_reference(&entity_ref)
+					.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
+					.unwrap_or(false)
+				{
+					property_data.value = Value::Null;
+				}
+			} else if property_data.property_type == "TArray<SEntityTemplateReference>" {
+				property_data.value = to_value(
+					from_value::<Vec<Ref>>(property_data.value.to_owned())
+						.context("Invalid reference array")?
+						.into_iter()
+						.map(|entity_ref| {
+							if let Ref::Full(FullRef {
+								external_scene: Some(ref scene),
+								..
+							}) = entity_ref
+							{
+								if !entity.external_scenes.contains(scene) {
+									entity.external_scenes.push(scene.to_owned());
+									added_external_scenes += 1;
+								}
+							}
+
+							alter_ref_according_to_changelist(&entity_ref, &changed_entity_ids)
+						})
+						.filter(|entity_ref| {
+							!get_local_reference(entity_ref)
+								.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
+								.unwrap_or(false)
+						})
+						.collect_vec()
+				)?;
+			}
+		}
+
+		for properties in sub_entity
+			.platform_specific_properties
+			.as_mut()
+			.unwrap_or(&mut Default::default())
+			.values_mut()
+		{
+			for property_data in properties.values_mut() {
+				if property_data.property_type == "SEntityTemplateReference" {
+					let entity_ref = alter_ref_according_to_changelist(
+						&from_value::<Ref>(property_data.value.to_owned()).context("Invalid reference")?,
+						&changed_entity_ids
+					);
+
+					property_data.value = to_value(&entity_ref)?;
+
+					// If the ref is external, add the external scene
+					if let Ref::Full(FullRef {
+						external_scene: Some(ref scene),
+						..
+					}) = entity_ref
+					{
+						if !entity.external_scenes.contains(scene) {
+							entity.external_scenes.push(scene.to_owned());
+							added_external_scenes += 1;
+						}
+					}
+
+					// If the ref is local but to a sub-entity that doesn't exist in the entity we're pasting into (and isn't an internal reference within the paste), set the property to null
+					if get_local_reference(&entity_ref)
+						.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
+						.unwrap_or(false)
+					{
+						property_data.value = Value::Null;
+					}
+				} else if property_data.property_type == "TArray<SEntityTemplateReference>" {
+					property_data.value = to_value(
+						from_value::<Vec<Ref>>(property_data.value.to_owned())
+							.context("Invalid reference array")?
+							.into_iter()
+							.map(|entity_ref| {
+								if let Ref::Full(FullRef {
+									external_scene: Some(ref scene),
+									..
+								}) = entity_ref
+								{
+									if !entity.external_scenes.contains(scene) {
+										entity.external_scenes.push(scene.to_owned());
+										added_external_scenes += 1;
+									}
+								}
+
+								alter_ref_according_to_changelist(&entity_ref, &changed_entity_ids)
+							})
+							.filter(|entity_ref| {
+								!get_local_reference(entity_ref)
+									.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
+									.unwrap_or(false)
+							})
+							.collect_vec()
+					)?;
+				}
+			}
+		}
+
+		for values in sub_entity
+			.events
+			.as_mut()
+			.unwrap_or(&mut Default::default())
+			.values_mut()
+		{
+			for refs in values.values_mut() {
+				for reference in refs.iter_mut() {
+					let underlying_ref = match reference {
+						RefMaybeConstantValue::Ref(x) => x,
+						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => {
+							entity_ref
+						}
+					};
+
+					if let Ref::Full(FullRef {
+						external_scene: Some(scene),
+						..
+					}) = underlying_ref
+					{
+						if !entity.external_scenes.contains(scene) {
+							entity.external_scenes.push(scene.to_owned());
+							added_external_scenes += 1;
+						}
+					}
+
+					*reference = match reference {
+						RefMaybeConstantValue::Ref(x) => {
+							RefMaybeConstantValue::Ref(alter_ref_according_to_changelist(x, &changed_entity_ids))
+						}
+						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, value }) => {
+							RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue {
+								entity_ref: alter_ref_according_to_changelist(entity_ref, &changed_entity_ids),
+								value: value.to_owned()
+							})
+						}
+					};
+				}
+
+				refs.retain(|reference| {
+					let underlying_ref = match reference {
+						RefMaybeConstantValue::Ref(x) => x,
+						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => {
+							entity_ref
+						}
+					};
+
+					!get_local_reference(underlying_ref)
+						.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
+						.unwrap_or(false)
+				});
+			}
+		}
+
+		for values in sub_entity
+			.input_copying
+			.as_mut()
+			.unwrap_or(&mut Default::default())
+			.values_mut()
+		{
+			for refs in values.values_mut() {
+				for reference in refs.iter_mut() {
+					let underlying_ref = match reference {
+						RefMaybeConstantValue::Ref(x) => x,
+						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => {
+							entity_ref
+						}
+					};
+
+					if let Ref::Full(FullRef {
+						external_scene: Some(scene),
+						..
+					}) = underlying_ref
+					{
+						if !entity.external_scenes.contains(scene) {
+							entity.external_scenes.push(scene.to_owned());
+							added_external_scenes += 1;
+						}
+					}
+
+					*reference = match reference {
+						RefMaybeConstantValue::Ref(x) => {
+							RefMaybeConstantValue::Ref(alter_ref_according_to_changelist(x, &changed_entity_ids))
+						}
+						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, value }) => {
+							RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue {
+								entity_ref: alter_ref_according_to_changelist(entity_ref, &changed_entity_ids),
+								value: value.to_owned()
+							})
+						}
+					};
+				}
+
+				refs.retain(|reference| {
+					let underlying_ref = match reference {
+						RefMaybeConstantValue::Ref(x) => x,
+						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => {
+							entity_ref
+						}
+					};
+
+					!get_local_reference(underlying_ref)
+						.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
+						.unwrap_or(false)
+				});
+			}
+		}
+
+		for values in sub_entity
+			.output_copying
+			.as_mut()
+			.unwrap_or(&mut Default::default())
+			.values_mut()
+		{
+			for refs in values.values_mut() {
+				for reference in refs.iter_mut() {
+					let underlying_ref = match reference {
+						RefMaybeConstantValue::Ref(x) => x,
+						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => {
+							entity_ref
+						}
+					};
+
+					if let Ref::Full(FullRef {
+						external_scene: Some(scene),
+						..
+					}) = underlying_ref
+					{
+						if !entity.external_scenes.contains(scene) {
+							entity.external_scenes.push(scene.to_owned());
+							added_external_scenes += 1;
+						}
+					}
+
+					*reference = match reference {
+						RefMaybeConstantValue::Ref(x) => {
+							RefMaybeConstantValue::Ref(alter_ref_according_to_changelist(x, &changed_entity_ids))
+						}
+						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, value }) => {
+							RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue {
+								entity_ref: alter_ref_according_to_changelist(entity_ref, &changed_entity_ids),
+								value: value.to_owned()
+							})
+						}
+					};
+				}
+
+				refs.retain(|reference| {
+					let underlying_ref = match reference {
+						RefMaybeConstantValue::Ref(x) => x,
+						RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, .. }) => {
+							entity_ref
+						}
+					};
+
+					!get_local_reference(underlying_ref)
+						.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
+						.unwrap_or(false)
+				});
+			}
+		}
+
+		for aliases in sub_entity
+			.property_aliases
+			.as_mut()
+			.unwrap_or(&mut Default::default())
+			.values_mut()
+		{
+			for alias_data in aliases.iter_mut() {
+				alias_data.original_entity =
+					alter_ref_according_to_changelist(&alias_data.original_entity, &changed_entity_ids);
+
+				if let Ref::Full(FullRef {
+					external_scene: Some(ref scene),
+					..
+				}) = alias_data.original_entity
+				{
+					if !entity.external_scenes.contains(scene) {
+						entity.external_scenes.push(scene.to_owned());
+						added_external_scenes += 1;
+					}
+				}
+			}
+
+			aliases.retain(|alias_data| {
+				!get_local_reference(&alias_data.original_entity)
+					.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
+					.unwrap_or(false)
+			});
+		}
+
+		for exposed_entity in sub_entity
+			.exposed_entities
+			.as_mut()
+			.unwrap_or(&mut Default::default())
+			.values_mut()
+		{
+			for reference in exposed_entity.refers_to.iter_mut() {
+				*reference = alter_ref_according_to_changelist(reference, &changed_entity_ids);
+
+				if let Ref::Full(FullRef {
+					external_scene: Some(scene),
+					..
+				}) = reference
+				{
+					if !entity.external_scenes.contains(scene) {
+						entity.external_scenes.push(scene.to_owned());
+						added_external_scenes += 1;
+					}
+				}
+			}
+
+			exposed_entity.refers_to.retain(|x| {
+				// Only retain those not meeting the criteria for deletion (local ref, not in entity we're pasting into or the paste itself)
+				!get_local_reference(x)
+					.map(|x| !entity.entities.contains_key(&x) && !all_paste_contents.contains(&x))
+					.unwrap_or(false)
+			});
+		}
+
+		for referenced_entity in sub_entity
+			.exposed_interfaces
+			.as_mut()
+			.unwrap_or(&mut Default::default())
+			.values_mut()
+		{
+			*referenced_entity = changed_entity_ids
+				.get(referenced_entity)
+				.unwrap_or(referenced_entity)
+				.to_owned();
+		}
+
+		sub_entity
+			.exposed_interfaces
+			.as_mut()
+			.unwrap_or(&mut Default::default())
+			.retain(|_, x| entity.entities.contains_key(x) || all_paste_contents.contains(x));
+
+		for member_of in sub_entity
+			.subsets
+			.as_mut()
+			.unwrap_or(&mut Default::default())
+			.values_mut()
+		{
+			for parental_entity in member_of.iter_mut() {
+				*parental_entity = changed_entity_ids
+					.get(parental_entity)
+					.unwrap_or(parental_entity)
+					.to_owned();
+			}
+
+			member_of.retain(|x| entity.entities.contains_key(x) || all_paste_contents.contains(x));
+		}
+	}
+
+	// Change the actual entity IDs in the paste data
+	paste_data.data = paste_data
+		.data
+		.into_iter()
+		.map(|(x, y)| (changed_entity_ids.get(&x).unwrap().to_owned(), y))
+		.collect();
+
+	paste_data
+		.data
+		.get_mut(changed_entity_ids.get(&paste_data.root_entity).unwrap())
+		.unwrap()
+		.parent = change_reference_to_local(
+		&paste_data
+			.data
+			.get_mut(changed_entity_ids.get(&paste_data.root_entity).unwrap())
+			.unwrap()
+			.parent,
+		parent_id.to_owned()
+	);
+
+	entity.entities.extend(paste_data.data.to_owned());
+
+	let mut new_entities = vec![];
+	let mut reverse_parent_refs: HashSet<String> = HashSet::new();
+
+	for entity_data in entity.entities.values() {
+		match entity_data.parent {
+			Ref::Full(ref reference) if reference.external_scene.is_none() => {
+				reverse_parent_refs.insert(reference.entity_ref.to_owned());
+			}
+
+			Ref::Short(Some(ref reference)) => {
+				reverse_parent_refs.insert(reference.to_owned());
+			}
+
+			_ => {}
+		}
+	}
+
+	for (entity_id, entity_data) in paste_data.data {
+		let x = reverse_parent_refs.contains(&entity_id);
+		new_entities.push((entity_id, entity_data.parent, entity_data.name, entity_data.factory, x));
+	}
+
+	// Make sure the entity being pasted under is updated to be considered a folder (if it's a ZEntity)
 	new_entities.push((
 		parent_id.to_owned(),
 		entity
@@ -1542,30 +2567,868 @@ pub async fn paste(
 	send_request(
 		app,
 		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
-			EntityTreeRequest::NewItems {
+			EntityTreeRequest::NewItems {
+				editor_id,
+				new_entities
+			}
+		)))
+	)?;
+
+	finish_task(app, task)?;
+
+	if added_external_scenes > 0 {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Info,
+				title: "Added external scenes".into(),
+				subtitle: format!(
+					"{} external scene{} been added to the entity to ensure that pasted references work.",
+					added_external_scenes,
+					if added_external_scenes > 1 { "s have" } else { " has" }
+				),
+				link: None
+			}
+		)?;
+	}
+
+	send_request(
+		app,
+		Request::Global(GlobalRequest::SetTabUnsaved {
+			id: editor_id,
+			unsaved: true
+		})
+	)?;
+
+	if let EditorData::QNPatch {
+		ref base, ref current, ..
+	} = editor_state.data
+	{
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+				EntityTreeRequest::SetDiffInfo {
+					editor_id,
+					diff_info: get_diff_info(base, current)
+				}
+			)))
+		)?;
+	}
+
+	new_root_id
+}
+
+/// Paste copied entities as a single entity referencing them, rather than as a copy of all of them. An entity without
+/// children is already a reference to its factory so it's pasted as-is; otherwise, the entity and its children are
+/// saved to a new template in the project, which the pasted entity then uses as its factory.
+#[try_fn]
+#[context("Couldn't handle paste as instance event")]
+pub async fn paste_as_instance(
+	app: &AppHandle,
+	editor_id: Uuid,
+	parent_id: String,
+	paste_data: CopiedEntityData
+) -> Result<()> {
+	if paste_data.data.len() <= 1 {
+		paste(app, editor_id, parent_id, paste_data).await?;
+		return Ok(());
+	}
+
+	let app_state = app.state::<AppState>();
+
+	let Some(project) = app_state.project.load_full() else {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Error,
+				title: "No project loaded".into(),
+				subtitle: "Pasting as an instance saves a new template to the project, so a project must be open."
+					.into(),
+				link: None
+			}
+		)?;
+
+		return Ok(());
+	};
+
+	if refuse_if_read_only(app, "Pasting as an instance")? {
+		return Ok(());
+	}
+
+	let root = paste_data
+		.data
+		.get(&paste_data.root_entity)
+		.context("No such root entity")?
+		.to_owned();
+
+	let quick_entity_version = match get_tab_state(&app_state, editor_id).context("No such editor")?.data {
+		EditorData::QNEntity { ref entity, .. } => entity.quick_entity_version,
+		EditorData::QNPatch { ref current, .. } => current.quick_entity_version,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	let task = start_task(app, format!("Pasting entity {} as an instance", root.name))?;
+
+	let file_name = format!(
+		"{}_{}",
+		root.name
+			.chars()
+			.filter(|x| x.is_ascii_alphanumeric() || *x == ' ' || *x == '_')
+			.collect::<String>()
+			.trim()
+			.replace(' ', "_")
+			.to_lowercase(),
+		&random_entity_id()[4..]
+	);
+
+	let factory_path = format!("[assembly:/_pro/instances/{}.entitytemplate].pc_entitytype", file_name);
+	let blueprint_path = format!(
+		"[assembly:/_pro/instances/{}.entitytemplate].pc_entityblueprint",
+		file_name
+	);
+
+	let mut template = Entity {
+		factory_hash: RuntimeID::from_any(&factory_path)?.to_string(),
+		blueprint_hash: RuntimeID::from_any(&blueprint_path)?.to_string(),
+		root_entity: paste_data.root_entity.to_owned(),
+		entities: paste_data.data,
+		property_overrides: vec![],
+		override_deletes: vec![],
+		pin_connection_overrides: vec![],
+		pin_connection_override_deletes: vec![],
+		external_scenes: vec![],
+		sub_type: SubType::Template,
+		quick_entity_version,
+		extra_factory_dependencies: vec![],
+		extra_blueprint_dependencies: vec![],
+		comments: vec![]
+	};
+
+	// The instance takes the place of the root entity, so its parent and transform stay in the entity being pasted into
+	{
+		let template_root = template
+			.entities
+			.get_mut(&template.root_entity)
+			.context("No such root entity")?;
+
+		template_root.parent = Ref::Short(None);
+
+		if let Some(properties) = template_root.properties.as_mut() {
+			properties.shift_remove("m_mTransform");
+			properties.shift_remove("m_eidParent");
+		}
+	}
+
+	for sub_entity in template.entities.values() {
+		if let EditorValidity::Invalid(err) = check_local_references_exist(sub_entity, &template)? {
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Error,
+					title: "Template would be invalid".into(),
+					subtitle: format!(
+						"The copied entities reference entities that weren't copied, so they can't be used as a \
+						 template on their own. {}",
+						err
+					),
+					link: None
+				}
+			)?;
+
+			finish_task(app, task)?;
+			return Ok(());
+		}
+	}
+
+	let content_path = project.path.join("content").join("chunk0");
+
+	fs::write(
+		if content_path.is_dir() {
+			content_path
+		} else {
+			project.path.to_owned()
+		}
+		.join(format!("{}.entity.json", file_name)),
+		format_json(&to_string(&template)?)?
+	)?;
+
+	let mut settings = (*project.settings.load_full()).to_owned();
+	settings.custom_paths.push(factory_path);
+	settings.custom_paths.push(blueprint_path);
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Metadata(
+			EntityMetadataRequest::UpdateCustomPaths {
+				editor_id: editor_id.to_owned(),
+				custom_paths: settings.custom_paths.to_owned()
+			}
+		)))
+	)?;
+
+	send_request(
+		app,
+		Request::Tool(ToolRequest::Settings(SettingsRequest::ChangeProjectSettings(
+			settings.to_owned()
+		)))
+	)?;
+
+	fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+	project.settings.store(settings.into());
+
+	track_event(app, "Paste as instance", None);
+
+	let instance_id = random_entity_id();
+
+	paste(
+		app,
+		editor_id,
+		parent_id,
+		CopiedEntityData {
+			root_entity: instance_id.to_owned(),
+			data: [(
+				instance_id,
+				SubEntity {
+					parent: root.parent,
+					name: root.name,
+					factory: template.factory_hash,
+					blueprint: template.blueprint_hash,
+					factory_flag: None,
+					editor_only: root.editor_only,
+					properties: root.properties.map(|properties| {
+						properties
+							.into_iter()
+							.filter(|(name, _)| name == "m_mTransform" || name == "m_eidParent")
+							.collect()
+					}),
+					platform_specific_properties: None,
+					events: None,
+					input_copying: None,
+					output_copying: None,
+					property_aliases: None,
+					exposed_entities: None,
+					exposed_interfaces: None,
+					subsets: None
+				}
+			)]
+			.into_iter()
+			.collect()
+		}
+	)
+	.await?;
+
+	finish_task(app, task)?;
+}
+
+/// Extract the game template an entity uses as its factory into the project, so that it can be customised, and make the
+/// entity use the project's copy instead. The copy is given new factory and blueprint paths so it doesn't replace the
+/// original template everywhere else it's used.
+#[try_fn]
+#[context("Couldn't handle extract factory to project event")]
+pub async fn extract_factory_to_project(app: &AppHandle, editor_id: Uuid, entity_id: String) -> Result<()> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+	let app_state = app.state::<AppState>();
+
+	let Some(project) = app_state.project.load_full() else {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Error,
+				title: "No project loaded".into(),
+				subtitle: "Extracting a factory saves it to the project, so a project must be open.".into(),
+				link: None
+			}
+		)?;
+
+		return Ok(());
+	};
+
+	if refuse_if_read_only(app, "Extracting a factory")? {
+		return Ok(());
+	}
+
+	let game_files = app_state.game_files.load_full().context("Game files not loaded")?;
+	let hash_list = app_state.hash_list.load_full().context("Hash list not loaded")?;
+	let game_version = get_loaded_game_version(
+		app,
+		app_settings
+			.load()
+			.game_install
+			.as_ref()
+			.context("No game install selected")?
+	)?;
+
+	let sub_entity = {
+		let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+		let entity = match editor_state.data {
+			EditorData::QNEntity { ref entity, .. } => entity,
+			EditorData::QNPatch { ref current, .. } => current,
+
+			_ => {
+				Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+				panic!();
+			}
+		};
+
+		entity.entities.get(&entity_id).context("No such entity")?.to_owned()
+	};
+
+	let factory_id = RuntimeID::from_any(&sub_entity.factory)?;
+
+	let Some(factory_entry) = hash_list.entries.get(&factory_id).filter(|x| x.resource_type == "TEMP") else {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Error,
+				title: "Factory isn't a game template".into(),
+				subtitle: "Only entities whose factory is a template (TEMP) from the game files can have it extracted."
+					.into(),
+				link: None
+			}
+		)?;
+
+		return Ok(());
+	};
+
+	let task = start_task(app, format!("Extracting factory of {}", sub_entity.name))?;
+
+	// Named after the template's file in its path, like npcactor for [assembly:/.../npcactor.entitytemplate]
+	let original_name = factory_entry
+		.path
+		.as_deref()
+		.and_then(|x| x.split(']').next())
+		.and_then(|x| x.rsplit('/').next())
+		.and_then(|x| x.split('.').next())
+		.filter(|x| !x.is_empty())
+		.unwrap_or(&sub_entity.name);
+
+	let file_name = format!(
+		"{}_{}",
+		original_name
+			.chars()
+			.filter(|x| x.is_ascii_alphanumeric() || *x == ' ' || *x == '_')
+			.collect::<String>()
+			.trim()
+			.replace(' ', "_")
+			.to_lowercase(),
+		&random_entity_id()[4..]
+	);
+
+	let factory_path = format!("[assembly:/_pro/extracted/{}.entitytemplate].pc_entitytype", file_name);
+	let blueprint_path = format!(
+		"[assembly:/_pro/extracted/{}.entitytemplate].pc_entityblueprint",
+		file_name
+	);
+
+	let mut template = extract_entity_async(
+		&game_files,
+		&app_state.cached_entities,
+		game_version,
+		&hash_list,
+		factory_id
+	)
+	.await?
+	.to_owned();
+
+	template.factory_hash = RuntimeID::from_any(&factory_path)?.to_string();
+	template.blueprint_hash = RuntimeID::from_any(&blueprint_path)?.to_string();
+
+	let content_path = project.path.join("content").join("chunk0");
+
+	let template_path = if content_path.is_dir() {
+		content_path
+	} else {
+		project.path.to_owned()
+	}
+	.join(format!("{}.entity.json", file_name));
+
+	fs::write(&template_path, to_vec(&template)?)?;
+
+	let mut settings = (*project.settings.load_full()).to_owned();
+	settings.custom_paths.push(factory_path);
+	settings.custom_paths.push(blueprint_path);
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Metadata(
+			EntityMetadataRequest::UpdateCustomPaths {
+				editor_id: editor_id.to_owned(),
+				custom_paths: settings.custom_paths.to_owned()
+			}
+		)))
+	)?;
+
+	send_request(
+		app,
+		Request::Tool(ToolRequest::Settings(SettingsRequest::ChangeProjectSettings(
+			settings.to_owned()
+		)))
+	)?;
+
+	fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+	project.settings.store(settings.into());
+
+	track_event(app, "Extract factory to project", None);
+
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+	let entity = match editor_state.data {
+		EditorData::QNEntity { ref mut entity, .. } => entity,
+		EditorData::QNPatch { ref mut current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	let has_reverse_parent_refs = entity
+		.entities
+		.values()
+		.any(|x| get_local_reference(&x.parent).is_some_and(|x| x == entity_id));
+
+	let sub_entity = entity.entities.get_mut(&entity_id).context("No such entity")?;
+	sub_entity.factory = template.factory_hash;
+	sub_entity.blueprint = template.blueprint_hash;
+
+	let sub_entity = sub_entity.to_owned();
+
+	send_request(
+		app,
+		Request::Global(GlobalRequest::SetTabUnsaved {
+			id: editor_id,
+			unsaved: true
+		})
+	)?;
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+			EntityTreeRequest::NewItems {
+				editor_id,
+				new_entities: vec![(
+					entity_id.to_owned(),
+					sub_entity.parent.to_owned(),
+					sub_entity.name.to_owned(),
+					sub_entity.factory.to_owned(),
+					has_reverse_parent_refs
+				)]
+			}
+		)))
+	)?;
+
+	let mut buf = Vec::new();
+	let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+	let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+	sub_entity.serialize(&mut ser)?;
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
+			EntityMonacoRequest::ReplaceContentIfSameEntityID {
+				editor_id: editor_id.to_owned(),
+				entity_id: entity_id.to_owned(),
+				content: String::from_utf8(buf)?
+			}
+		)))
+	)?;
+
+	if let EditorData::QNPatch {
+		ref base, ref current, ..
+	} = editor_state.data
+	{
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+				EntityTreeRequest::SetDiffInfo {
+					editor_id,
+					diff_info: get_diff_info(base, current)
+				}
+			)))
+		)?;
+	}
+
+	finish_task(app, task)?;
+
+	send_notification(
+		app,
+		Notification {
+			kind: NotificationKind::Success,
+			title: "Factory extracted".into(),
+			subtitle: format!(
+				"{} now uses a copy of its factory, saved to {}.",
+				sub_entity.name,
+				template_path.display()
+			),
+			link: None
+		}
+	)?;
+}
+
+#[try_fn]
+#[context("Couldn't handle search event")]
+pub async fn search(app: &AppHandle, editor_id: Uuid, query: String) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let task = start_task(app, format!("Searching for {}", query))?;
+
+	let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+	let entity = match editor_state.data {
+		EditorData::QNEntity { ref entity, .. } => entity,
+		EditorData::QNPatch { ref current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	let (text_terms, property_terms) = parse_search_query(&query);
+
+	let results: Vec<(String, Vec<String>)> = entity
+		.entities
+		.par_iter()
+		.filter(|(id, ent)| {
+			let mut s = format!("{}{}", id, to_string(ent).unwrap());
+			s.make_ascii_lowercase();
+			text_terms.iter().all(|q| s.contains(q))
+		})
+		.filter_map(|(id, ent)| {
+			if property_terms.is_empty() {
+				Some((id.to_owned(), vec![]))
+			} else {
+				let paths = get_matching_property_paths(ent, &property_terms);
+				(!paths.is_empty()).then(|| (id.to_owned(), paths))
+			}
+		})
+		.collect();
+
+	// Results in lazily loaded trees can only be shown once their ancestors have been loaded
+	let ancestors = results
+		.iter()
+		.flat_map(|(id, _)| get_local_ancestors(entity, id))
+		.collect::<IndexSet<_>>()
+		.into_iter()
+		.collect();
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+			EntityTreeRequest::SearchResults {
+				editor_id,
+				results: results.iter().map(|(id, _)| id.to_owned()).collect(),
+				property_matches: results.into_iter().filter(|(_, paths)| !paths.is_empty()).collect(),
+				ancestors
+			}
+		)))
+	)?;
+
+	finish_task(app, task)?;
+}
+
+#[try_fn]
+#[context("Couldn't handle help menu event")]
+pub async fn help_menu(app: &AppHandle, editor_id: Uuid, entity_id: String) -> Result<()> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+	let app_state = app.state::<AppState>();
+
+	let task = start_task(app, format!("Showing help menu for {}", entity_id))?;
+
+	let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+	let entity = match editor_state.data {
+		EditorData::QNEntity { ref entity, .. } => entity,
+		EditorData::QNPatch { ref current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	let sub_entity = entity.entities.get(&entity_id).context("No such entity")?;
+
+	if let Some(intellisense) = app_state.intellisense.load().as_ref()
+		&& let Some(game_files) = app_state.game_files.load().as_ref()
+		&& let Some(hash_list) = app_state.hash_list.load().as_ref()
+		&& let Some(install) = app_settings.load().game_install.as_ref()
+	{
+		let game_version = get_loaded_game_version(app, install)?;
+
+		let (properties, pins) = if hash_list
+			.entries
+			.get(&RuntimeID::from_any(&sub_entity.factory)?)
+			.map(|entry| entry.resource_type == "TEMP")
+			.unwrap_or(false)
+		{
+			let underlying_entity = extract_entity_async(
+				game_files,
+				&app_state.cached_entities,
+				game_version,
+				hash_list,
+				RuntimeID::from_any(&sub_entity.factory)?
+			)
+			.await?;
+
+			(
+				intellisense.get_properties(
+					game_files,
+					&app_state.cached_entities,
+					hash_list,
+					game_version,
+					&underlying_entity,
+					&underlying_entity.root_entity,
+					false
+				)?,
+				intellisense.get_pins(
+					game_files,
+					&app_state.cached_entities,
+					hash_list,
+					game_version,
+					&underlying_entity,
+					&underlying_entity.root_entity,
+					false
+				)?
+			)
+		} else {
+			(
+				intellisense.get_properties(
+					game_files,
+					&app_state.cached_entities,
+					hash_list,
+					game_version,
+					entity,
+					&entity_id,
+					true
+				)?,
+				intellisense.get_pins(
+					game_files,
+					&app_state.cached_entities,
+					hash_list,
+					game_version,
+					entity,
+					&entity_id,
+					true
+				)?
+			)
+		};
+
+		let properties_data_str = {
+			let mut buf = Vec::new();
+			let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+			let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+			properties
+				.into_iter()
+				.map(|(name, ty, default_val, post_init)| {
+					(
+						name,
+						if post_init {
+							json!({
+								"type": ty,
+								"value": default_val,
+								"postInit": true
+							})
+						} else {
+							json!({
+								"type": ty,
+								"value": default_val
+							})
+						}
+					)
+				})
+				.collect::<HashMap<_, _>>()
+				.serialize(&mut ser)?;
+
+			String::from_utf8(buf)?
+		};
+
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+				EntityTreeRequest::ShowHelpMenu {
+					editor_id,
+					factory: sub_entity.factory.to_owned(),
+					input_pins: pins.0,
+					output_pins: pins.1,
+					default_properties_json: properties_data_str
+				}
+			)))
+		)?;
+	} else {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Error,
+				title: "Help menu unavailable".into(),
+				subtitle: "A copy of the game hasn't been selected, or the hash list is unavailable.".into(),
+				link: None
+			}
+		)?;
+	}
+
+	finish_task(app, task)?;
+}
+
+#[try_fn]
+#[context("Couldn't handle get connection pins event")]
+pub async fn get_connection_pins(app: &AppHandle, editor_id: Uuid, source_id: String, target_id: String) -> Result<()> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+	let app_state = app.state::<AppState>();
+
+	let task = start_task(app, format!("Getting pins to connect {} to {}", source_id, target_id))?;
+
+	let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
+
+	let entity = match editor_state.data {
+		EditorData::QNEntity { ref entity, .. } => entity,
+		EditorData::QNPatch { ref current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	let source = entity.entities.get(&source_id).context("No such entity")?;
+	let target = entity.entities.get(&target_id).context("No such entity")?;
+
+	// Without intellisense the pins can still be typed in by hand
+	let (output_pins, input_pins) = if let Some(intellisense) = app_state.intellisense.load().as_ref()
+		&& let Some(game_files) = app_state.game_files.load().as_ref()
+		&& let Some(hash_list) = app_state.hash_list.load().as_ref()
+		&& let Some(install) = app_settings.load().game_install.as_ref()
+	{
+		let game_version = get_loaded_game_version(app, install)?;
+
+		let (source_pins, target_pins) = rayon::join(
+			|| {
+				intellisense.get_pins(
+					game_files,
+					&app_state.cached_entities,
+					hash_list,
+					game_version,
+					entity,
+					&source_id,
+					false
+				)
+			},
+			|| {
+				intellisense.get_pins(
+					game_files,
+					&app_state.cached_entities,
+					hash_list,
+					game_version,
+					entity,
+					&target_id,
+					false
+				)
+			}
+		);
+
+		(source_pins?.1, target_pins?.0)
+	} else {
+		(vec![], vec![])
+	};
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+			EntityTreeRequest::ShowConnectEntities {
 				editor_id,
-				new_entities
+				source_id: source_id.to_owned(),
+				source_name: source.name.to_owned(),
+				target_id: target_id.to_owned(),
+				target_name: target.name.to_owned(),
+				output_pins: output_pins.into_iter().unique().sorted().collect(),
+				input_pins: input_pins.into_iter().unique().sorted().collect()
 			}
 		)))
 	)?;
 
 	finish_task(app, task)?;
+}
 
-	if added_external_scenes > 0 {
+#[try_fn]
+#[context("Couldn't handle connect entities event")]
+pub async fn connect_entities(
+	app: &AppHandle,
+	editor_id: Uuid,
+	source_id: String,
+	output_pin: String,
+	target_id: String,
+	input_pin: String,
+	value: Option<SimpleProperty>
+) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+	let entity = match editor_state.data {
+		EditorData::QNEntity { ref mut entity, .. } => entity,
+		EditorData::QNPatch { ref mut current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	if output_pin.trim().is_empty() || input_pin.trim().is_empty() {
+		bail!("Both pins must be given");
+	}
+
+	if !entity.entities.contains_key(&target_id) {
+		bail!("No such target entity {}", target_id);
+	}
+
+	let connections = entity
+		.entities
+		.get_mut(&source_id)
+		.context("No such entity")?
+		.events
+		.get_or_insert_default()
+		.entry(output_pin.to_owned())
+		.or_default()
+		.entry(input_pin.to_owned())
+		.or_default();
+
+	let already_connected = value.is_none()
+		&& connections.iter().any(|x| match x {
+			RefMaybeConstantValue::Ref(reference) => get_local_reference(reference).is_some_and(|x| x == target_id),
+			RefMaybeConstantValue::RefWithConstantValue(_) => false
+		});
+
+	if already_connected {
 		send_notification(
 			app,
 			Notification {
 				kind: NotificationKind::Info,
-				title: "Added external scenes".into(),
-				subtitle: format!(
-					"{} external scene{} been added to the entity to ensure that pasted references work.",
-					added_external_scenes,
-					if added_external_scenes > 1 { "s have" } else { " has" }
-				)
+				title: "Already connected".into(),
+				subtitle: format!("{} already triggers {} on this entity.", output_pin, input_pin),
+				link: None
 			}
 		)?;
+
+		return Ok(());
 	}
 
+	connections.push(match value {
+		Some(value) => RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue {
+			entity_ref: Ref::Short(Some(target_id)),
+			value
+		}),
+
+		None => RefMaybeConstantValue::Ref(Ref::Short(Some(target_id)))
+	});
+
 	send_request(
 		app,
 		Request::Global(GlobalRequest::SetTabUnsaved {
@@ -1574,6 +3437,27 @@ pub async fn paste(
 		})
 	)?;
 
+	let mut buf = Vec::new();
+	let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+	let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+	entity
+		.entities
+		.get(&source_id)
+		.context("No such entity")?
+		.serialize(&mut ser)?;
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
+			EntityMonacoRequest::ReplaceContentIfSameEntityID {
+				editor_id: editor_id.to_owned(),
+				entity_id: source_id,
+				content: String::from_utf8(buf)?
+			}
+		)))
+	)?;
+
 	if let EditorData::QNPatch {
 		ref base, ref current, ..
 	} = editor_state.data
@@ -1590,56 +3474,102 @@ pub async fn paste(
 	}
 }
 
+/// The type of the property an input pin sets, going by a property of the same name on the target, if the target is
+/// local and intellisense knows of one.
 #[try_fn]
-#[context("Couldn't handle search event")]
-pub async fn search(app: &AppHandle, editor_id: Uuid, query: String) -> Result<()> {
+#[context("Couldn't get type of input pin {}", input_pin)]
+fn get_input_pin_type(app: &AppHandle, entity: &Entity, target: &Ref, input_pin: &str) -> Result<Option<String>> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
 	let app_state = app.state::<AppState>();
 
-	let task = start_task(app, format!("Searching for {}", query))?;
+	if let Some(target_id) = get_local_reference(target)
+		&& entity.entities.contains_key(&target_id)
+		&& let Some(intellisense) = app_state.intellisense.load().as_ref()
+		&& let Some(game_files) = app_state.game_files.load().as_ref()
+		&& let Some(hash_list) = app_state.hash_list.load().as_ref()
+		&& let Some(install) = app_settings.load().game_install.as_ref()
+	{
+		intellisense
+			.get_specific_property(
+				game_files,
+				&app_state.cached_entities,
+				hash_list,
+				get_loaded_game_version(app, install)?,
+				entity,
+				&target_id,
+				input_pin
+			)?
+			.map(|(property_type, _, _)| property_type)
+	} else {
+		None
+	}
+}
 
-	let editor_state = app_state.editor_states.get(&editor_id).context("No such editor")?;
+/// Whether a constant value has the shape its type needs, for the types whose shape is simple enough to check.
+fn is_valid_constant_value(property_type: &str, value: &Value) -> bool {
+	match property_type {
+		"bool" => value.is_boolean(),
+		"int8" | "int16" | "int32" | "int64" => value.is_i64(),
+		"uint8" | "uint16" | "uint32" | "uint64" => value.is_u64(),
+		"float32" | "float64" => value.is_number(),
+		"ZString" | "ZRepositoryID" | "ZGuid" => value.is_string(),
+		"SVector2" => value.get("x").is_some_and(|x| x.is_number()) && value.get("y").is_some_and(|x| x.is_number()),
+		"SVector3" => ["x", "y", "z"]
+			.iter()
+			.all(|axis| value.get(axis).is_some_and(|x| x.is_number())),
+		_ => true
+	}
+}
 
-	let entity = match editor_state.data {
-		EditorData::QNEntity { ref entity, .. } => entity,
-		EditorData::QNPatch { ref current, .. } => current,
+#[try_fn]
+#[context("Couldn't send constant values of {}", entity_id)]
+fn send_constant_values(app: &AppHandle, editor_id: Uuid, entity: &Entity, entity_id: &str) -> Result<()> {
+	let sub_entity = entity.entities.get(entity_id).context("No such entity")?;
 
-		_ => {
-			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
-			panic!();
+	let mut connections = vec![];
+
+	for (output_pin, triggers) in sub_entity.events.as_ref().unwrap_or(&Default::default()) {
+		for (input_pin, targets) in triggers {
+			for (index, target) in targets.iter().enumerate() {
+				if let RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, value }) = target
+				{
+					connections.push(ConstantValueConnection {
+						output_pin: output_pin.to_owned(),
+						input_pin: input_pin.to_owned(),
+						index: index as u32,
+						target: entity_ref.to_owned(),
+						target_name: get_local_reference(entity_ref)
+							.and_then(|x| entity.entities.get(&x))
+							.map(|x| x.name.to_owned()),
+						value: value.to_owned(),
+						expected_type: get_input_pin_type(app, entity, entity_ref, input_pin)?
+					});
+				}
+			}
 		}
-	};
+	}
 
 	send_request(
 		app,
 		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
-			EntityTreeRequest::SearchResults {
+			EntityTreeRequest::ShowConstantValues {
 				editor_id,
-				results: entity
-					.entities
-					.par_iter()
-					.filter(|(id, ent)| {
-						let mut s = format!("{}{}", id, to_string(ent).unwrap());
-						s.make_ascii_lowercase();
-						query.split(' ').all(|q| s.contains(q))
-					})
-					.map(|(id, _)| id.to_owned())
-					.collect()
+				entity_id: entity_id.to_owned(),
+				entity_name: sub_entity.name.to_owned(),
+				connections
 			}
 		)))
 	)?;
-
-	finish_task(app, task)?;
 }
 
 #[try_fn]
-#[context("Couldn't handle help menu event")]
-pub async fn help_menu(app: &AppHandle, editor_id: Uuid, entity_id: String) -> Result<()> {
-	let app_settings = app.state::<ArcSwap<AppSettings>>();
+#[context("Couldn't handle get constant values event")]
+pub async fn get_constant_values(app: &AppHandle, editor_id: Uuid, entity_id: String) -> Result<()> {
 	let app_state = app.state::<AppState>();
 
-	let task = start_task(app, format!("Showing help menu for {}", entity_id))?;
+	let task = start_task(app, format!("Getting constant values sent by {}", entity_id))?;
 
-	let editor_state = app_state.editor_states.get(&editor_id).context("No such editor")?;
+	let editor_state = get_tab_state(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref entity, .. } => entity,
@@ -1651,126 +3581,141 @@ pub async fn help_menu(app: &AppHandle, editor_id: Uuid, entity_id: String) -> R
 		}
 	};
 
-	let sub_entity = entity.entities.get(&entity_id).context("No such entity")?;
+	send_constant_values(app, editor_id, entity, &entity_id)?;
 
-	if let Some(intellisense) = app_state.intellisense.load().as_ref()
-		&& let Some(game_files) = app_state.game_files.load().as_ref()
-		&& let Some(hash_list) = app_state.hash_list.load().as_ref()
-		&& let Some(install) = app_settings.load().game_install.as_ref()
+	finish_task(app, task)?;
+}
+
+#[try_fn]
+#[context("Couldn't handle set constant value event")]
+pub async fn set_constant_value(
+	app: &AppHandle,
+	editor_id: Uuid,
+	entity_id: String,
+	output_pin: String,
+	input_pin: String,
+	index: usize,
+	value: SimpleProperty
+) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+	let entity = match editor_state.data {
+		EditorData::QNEntity { ref mut entity, .. } => entity,
+		EditorData::QNPatch { ref mut current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	let Some(RefMaybeConstantValue::RefWithConstantValue(connection)) = entity
+		.entities
+		.get(&entity_id)
+		.context("No such entity")?
+		.events
+		.as_ref()
+		.and_then(|x| x.get(&output_pin))
+		.and_then(|x| x.get(&input_pin))
+		.and_then(|x| x.get(index))
+	else {
+		bail!(
+			"No constant value connection from {} to {} at {}",
+			output_pin,
+			input_pin,
+			index
+		);
+	};
+
+	let problem = if value.property_type.trim().is_empty() {
+		Some("The value must have a type.".to_owned())
+	} else if let Some(expected_type) = get_input_pin_type(app, entity, &connection.entity_ref, &input_pin)?
+		&& expected_type != value.property_type
 	{
-		let game_version = get_loaded_game_version(app, install)?;
+		Some(format!(
+			"{} takes a value of type {}, not {}.",
+			input_pin, expected_type, value.property_type
+		))
+	} else if !is_valid_constant_value(&value.property_type, &value.value) {
+		Some(format!("The value isn't a valid {}.", value.property_type))
+	} else {
+		None
+	};
 
-		let (properties, pins) = if hash_list
-			.entries
-			.get(&RuntimeID::from_any(&sub_entity.factory)?)
-			.map(|entry| entry.resource_type == "TEMP")
-			.unwrap_or(false)
-		{
-			let underlying_entity = extract_entity(
-				game_files,
-				&app_state.cached_entities,
-				game_version,
-				hash_list,
-				RuntimeID::from_any(&sub_entity.factory)?
-			)?;
+	if let Some(problem) = problem {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Error,
+				title: "Invalid constant value".into(),
+				subtitle: problem,
+				link: None
+			}
+		)?;
 
-			(
-				intellisense.get_properties(
-					game_files,
-					&app_state.cached_entities,
-					hash_list,
-					game_version,
-					&underlying_entity,
-					&underlying_entity.root_entity,
-					false
-				)?,
-				intellisense.get_pins(
-					game_files,
-					&app_state.cached_entities,
-					hash_list,
-					game_version,
-					&underlying_entity,
-					&underlying_entity.root_entity,
-					false
-				)?
-			)
-		} else {
-			(
-				intellisense.get_properties(
-					game_files,
-					&app_state.cached_entities,
-					hash_list,
-					game_version,
-					entity,
-					&entity_id,
-					true
-				)?,
-				intellisense.get_pins(
-					game_files,
-					&app_state.cached_entities,
-					hash_list,
-					game_version,
-					entity,
-					&entity_id,
-					true
-				)?
-			)
-		};
+		return Ok(());
+	}
 
-		let properties_data_str = {
-			let mut buf = Vec::new();
-			let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
-			let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+	if let Some(RefMaybeConstantValue::RefWithConstantValue(connection)) = entity
+		.entities
+		.get_mut(&entity_id)
+		.context("No such entity")?
+		.events
+		.as_mut()
+		.and_then(|x| x.get_mut(&output_pin))
+		.and_then(|x| x.get_mut(&input_pin))
+		.and_then(|x| x.get_mut(index))
+	{
+		connection.value = value;
+	}
 
-			properties
-				.into_iter()
-				.map(|(name, ty, default_val, post_init)| {
-					(
-						name,
-						if post_init {
-							json!({
-								"type": ty,
-								"value": default_val,
-								"postInit": true
-							})
-						} else {
-							json!({
-								"type": ty,
-								"value": default_val
-							})
-						}
-					)
-				})
-				.collect::<HashMap<_, _>>()
-				.serialize(&mut ser)?;
+	send_request(
+		app,
+		Request::Global(GlobalRequest::SetTabUnsaved {
+			id: editor_id,
+			unsaved: true
+		})
+	)?;
+
+	let mut buf = Vec::new();
+	let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+	let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+	entity
+		.entities
+		.get(&entity_id)
+		.context("No such entity")?
+		.serialize(&mut ser)?;
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
+			EntityMonacoRequest::ReplaceContentIfSameEntityID {
+				editor_id: editor_id.to_owned(),
+				entity_id: entity_id.to_owned(),
+				content: String::from_utf8(buf)?
+			}
+		)))
+	)?;
 
-			String::from_utf8(buf)?
-		};
+	send_constant_values(app, editor_id, entity, &entity_id)?;
 
+	if let EditorData::QNPatch {
+		ref base, ref current, ..
+	} = editor_state.data
+	{
 		send_request(
 			app,
 			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
-				EntityTreeRequest::ShowHelpMenu {
+				EntityTreeRequest::SetDiffInfo {
 					editor_id,
-					factory: sub_entity.factory.to_owned(),
-					input_pins: pins.0,
-					output_pins: pins.1,
-					default_properties_json: properties_data_str
+					diff_info: get_diff_info(base, current)
 				}
 			)))
 		)?;
-	} else {
-		send_notification(
-			app,
-			Notification {
-				kind: NotificationKind::Error,
-				title: "Help menu unavailable".into(),
-				subtitle: "A copy of the game hasn't been selected, or the hash list is unavailable.".into()
-			}
-		)?;
 	}
-
-	finish_task(app, task)?;
 }
 
 #[try_fn]
@@ -1781,7 +3726,7 @@ pub async fn add_game_browser_item(app: &AppHandle, editor_id: Uuid, parent_id:
 
 	let task = start_task(app, format!("Adding {}", file))?;
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -1818,17 +3763,20 @@ pub async fn add_game_browser_item(app: &AppHandle, editor_id: Uuid, parent_id:
 				"TEMP" => {
 					let (temp_meta, temp_data) = extract_latest_resource(game_files, file)?;
 
-					let factory = match game_version {
-						GameVersion::H1 => h2016_convert_binary_to_factory(&temp_data)
-							.context("Couldn't convert binary data to ResourceLib factory")?
-							.into_modern(),
+					let factory = convert_async(move || {
+						Ok(match game_version {
+							GameVersion::H1 => h2016_convert_binary_to_factory(&temp_data)
+								.context("Couldn't convert binary data to ResourceLib factory")?
+								.into_modern(),
 
-						GameVersion::H2 => h2_convert_binary_to_factory(&temp_data)
-							.context("Couldn't convert binary data to ResourceLib factory")?,
+							GameVersion::H2 => h2_convert_binary_to_factory(&temp_data)
+								.context("Couldn't convert binary data to ResourceLib factory")?,
 
-						GameVersion::H3 => h3_convert_binary_to_factory(&temp_data)
-							.context("Couldn't convert binary data to ResourceLib factory")?
-					};
+							GameVersion::H3 => h3_convert_binary_to_factory(&temp_data)
+								.context("Couldn't convert binary data to ResourceLib factory")?
+						})
+					})
+					.await?;
 
 					let blueprint_hash = &temp_meta
 						.core_info
@@ -1872,8 +3820,8 @@ pub async fn add_game_browser_item(app: &AppHandle, editor_id: Uuid, parent_id:
 				"CPPT" => {
 					let (cppt_meta, cppt_data) = extract_latest_resource(game_files, file)?;
 
-					let factory =
-						match game_version {
+					let factory = convert_async(move || {
+						Ok(match game_version {
 							GameVersion::H1 => h2016_convert_cppt(&cppt_data)
 								.context("Couldn't convert binary data to ResourceLib format")?,
 
@@ -1882,7 +3830,9 @@ pub async fn add_game_browser_item(app: &AppHandle, editor_id: Uuid, parent_id:
 
 							GameVersion::H3 => h3_convert_cppt(&cppt_data)
 								.context("Couldn't convert binary data to ResourceLib format")?
-						};
+						})
+					})
+					.await?;
 
 					let blueprint_hash = &cppt_meta
 						.core_info
@@ -2340,7 +4290,8 @@ pub async fn add_game_browser_item(app: &AppHandle, editor_id: Uuid, parent_id:
 				Notification {
 					kind: NotificationKind::Error,
 					title: "Not a valid template".into(),
-					subtitle: "Only entity templates can be dragged into the entity tree.".into()
+					subtitle: "Only entity templates can be dragged into the entity tree.".into(),
+					link: None
 				}
 			)?;
 		}
@@ -2350,13 +4301,207 @@ pub async fn add_game_browser_item(app: &AppHandle, editor_id: Uuid, parent_id:
 			Notification {
 				kind: NotificationKind::Error,
 				title: "Game data unavailable".into(),
-				subtitle: "A copy of the game hasn't been selected, or the hash list is unavailable.".into()
+				subtitle: "A copy of the game hasn't been selected, or the hash list is unavailable.".into(),
+				link: None
+			}
+		)?;
+	}
+
+	finish_task(app, task)?;
+
+	if let EditorData::QNPatch {
+		ref base, ref current, ..
+	} = editor_state.data
+	{
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+				EntityTreeRequest::SetDiffInfo {
+					editor_id,
+					diff_info: get_diff_info(base, current)
+				}
+			)))
+		)?;
+	}
+}
+
+#[try_fn]
+#[context("Couldn't handle add custom sound event")]
+pub async fn add_custom_sound(
+	app: &AppHandle,
+	editor_id: Uuid,
+	parent_id: String,
+	name: String,
+	audio: PathBuf,
+	template: String
+) -> Result<()> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+	let app_state = app.state::<AppState>();
+
+	let Some(project) = app_state.project.load_full() else {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Error,
+				title: "No project loaded".into(),
+				subtitle: "Adding a custom sound saves its audio to the project, so a project must be open.".into(),
+				link: None
 			}
 		)?;
+
+		return Ok(());
+	};
+
+	if refuse_if_read_only(app, "Adding a custom sound")? {
+		return Ok(());
+	}
+
+	let encoder = app_settings.load().wem_encoder.to_owned();
+
+	if audio
+		.extension()
+		.is_some_and(|x| x.to_string_lossy().eq_ignore_ascii_case("wav"))
+	{
+		if encoder.is_none() {
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Error,
+					title: "No WEM encoder".into(),
+					subtitle: "Set the program to convert WAV files to WEM with in the settings first, or use a WEM \
+					           file."
+						.into(),
+					link: None
+				}
+			)?;
+
+			return Ok(());
+		}
+
+		if refuse_if_untrusted(app, "Converting audio")? {
+			return Ok(());
+		}
 	}
 
+	let game_files = app_state.game_files.load_full().context("Game files not loaded")?;
+
+	let task = start_task(app, format!("Adding custom sound {}", name))?;
+
+	let stub = generate_audio_event_stub(
+		app,
+		encoder.as_ref(),
+		&game_files,
+		&project.path,
+		RuntimeID::from_any(template.trim()).context("Invalid template hash or path")?,
+		&audio,
+		&name
+	)
+	.await;
+
 	finish_task(app, task)?;
 
+	let stub = stub?;
+
+	let mut settings = (*project.settings.load_full()).to_owned();
+
+	for path in [&stub.wwev_path, &stub.wwem_path] {
+		if !settings.custom_paths.contains(path) {
+			settings.custom_paths.push(path.to_owned());
+		}
+	}
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Metadata(
+			EntityMetadataRequest::UpdateCustomPaths {
+				editor_id: editor_id.to_owned(),
+				custom_paths: settings.custom_paths.to_owned()
+			}
+		)))
+	)?;
+
+	send_request(
+		app,
+		Request::Tool(ToolRequest::Settings(SettingsRequest::ChangeProjectSettings(
+			settings.to_owned()
+		)))
+	)?;
+
+	fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+	project.settings.store(settings.into());
+
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+	let entity = match editor_state.data {
+		EditorData::QNEntity { ref mut entity, .. } => entity,
+		EditorData::QNPatch { ref mut current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	let entity_id = random_entity_id();
+
+	let sub_entity = SubEntity {
+		parent: Ref::Short((parent_id != "#").then_some(parent_id)),
+		name: stub.name.to_owned(),
+		factory: "[modules:/zaudioevententity.class].pc_entitytype".into(),
+		factory_flag: None,
+		blueprint: "[modules:/zaudioevententity.class].pc_entityblueprint".into(),
+		editor_only: None,
+		properties: Some({
+			let mut properties = IndexMap::new();
+			properties.insert(
+				"m_pMainEvent".into(),
+				Property {
+					property_type: "ZRuntimeResourceID".into(),
+					value: json!({
+						"resource": stub.wwev_path,
+						"flag": "5F"
+					}),
+					post_init: None
+				}
+			);
+			properties
+		}),
+		platform_specific_properties: None,
+		events: None,
+		input_copying: None,
+		output_copying: None,
+		property_aliases: None,
+		exposed_entities: None,
+		exposed_interfaces: None,
+		subsets: None
+	};
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+			EntityTreeRequest::NewItems {
+				editor_id: editor_id.to_owned(),
+				new_entities: vec![(
+					entity_id.to_owned(),
+					sub_entity.parent.to_owned(),
+					sub_entity.name.to_owned(),
+					sub_entity.factory.to_owned(),
+					false
+				)]
+			}
+		)))
+	)?;
+
+	entity.entities.insert(entity_id, sub_entity);
+
+	send_request(
+		app,
+		Request::Global(GlobalRequest::SetTabUnsaved {
+			id: editor_id,
+			unsaved: true
+		})
+	)?;
+
 	if let EditorData::QNPatch {
 		ref base, ref current, ..
 	} = editor_state.data
@@ -2371,6 +4516,21 @@ pub async fn add_game_browser_item(app: &AppHandle, editor_id: Uuid, parent_id:
 			)))
 		)?;
 	}
+
+	track_event(app, "Add custom sound", None);
+
+	send_notification(
+		app,
+		Notification {
+			kind: NotificationKind::Success,
+			title: "Custom sound added".into(),
+			subtitle: format!(
+				"{} and {} were added to the project, along with an audio event entity playing them.",
+				stub.wwev_path, stub.wwem_path
+			),
+			link: None
+		}
+	)?;
 }
 
 #[try_fn]
@@ -2380,7 +4540,7 @@ pub async fn select_entity_in_editor(app: &AppHandle, editor_id: Uuid, entity_id
 
 	let task = start_task(app, format!("Selecting {} in editor", entity_id))?;
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -2408,7 +4568,7 @@ pub async fn move_entity_to_player(app: &AppHandle, editor_id: Uuid, entity_id:
 
 	let task = start_task(app, format!("Moving {} to player position", entity_id))?;
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -2594,7 +4754,7 @@ pub async fn rotate_entity_as_player(app: &AppHandle, editor_id: Uuid, entity_id
 
 	let task = start_task(app, format!("Adjusting {} to player rotation", entity_id))?;
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -2780,7 +4940,7 @@ pub async fn move_entity_to_camera(app: &AppHandle, editor_id: Uuid, entity_id:
 
 	let task = start_task(app, format!("Moving {} to camera position", entity_id))?;
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -2966,7 +5126,7 @@ pub async fn rotate_entity_as_camera(app: &AppHandle, editor_id: Uuid, entity_id
 
 	let task = start_task(app, format!("Adjusting {} to camera rotation", entity_id))?;
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 	let entity = match editor_state.data {
 		EditorData::QNEntity { ref mut entity, .. } => entity,
@@ -3152,7 +5312,7 @@ pub async fn restore_to_original(app: &AppHandle, editor_id: Uuid, entity_id: St
 
 	let task = start_task(app, format!("Reverting {} to original state", entity_id))?;
 
-	let mut editor_state = app_state.editor_states.get_mut(&editor_id).context("No such editor")?;
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
 
 	let EditorData::QNPatch {
 		ref base,
@@ -3173,7 +5333,8 @@ pub async fn restore_to_original(app: &AppHandle, editor_id: Uuid, entity_id: St
 			Notification {
 				kind: NotificationKind::Error,
 				title: "Entity would be invalid".into(),
-				subtitle: err
+				subtitle: err,
+				link: None
 			}
 		)?;
 
@@ -3390,3 +5551,114 @@ pub async fn restore_to_original(app: &AppHandle, editor_id: Uuid, entity_id: St
 
 	finish_task(app, task)?;
 }
+
+/// Compute a new transform for an entity from its current one, updating both the entity and the connected editor.
+#[try_fn]
+#[context("Couldn't update transform of entity {}", entity_id)]
+pub async fn update_entity_transform(
+	app: &AppHandle,
+	editor_id: Uuid,
+	entity_id: String,
+	update: impl FnOnce(&QNTransform) -> QNTransform
+) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let mut editor_state = get_tab_state_mut(&app_state, editor_id).context("No such editor")?;
+
+	let entity = match editor_state.data {
+		EditorData::QNEntity { ref mut entity, .. } => entity,
+		EditorData::QNPatch { ref mut current, .. } => current,
+
+		_ => {
+			Err(anyhow!("Editor {} is not a QN editor", editor_id))?;
+			panic!();
+		}
+	};
+
+	let property = entity
+		.entities
+		.get_mut(&entity_id)
+		.context("No such entity")?
+		.properties
+		.get_or_insert_default()
+		.entry("m_mTransform".into())
+		.or_insert(Property {
+			property_type: "SMatrix43".into(),
+			value: json!({
+				"rotation": {
+					"x": 0,
+					"y": 0,
+					"z": 0
+				},
+				"position": {
+					"x": 0,
+					"y": 0,
+					"z": 0
+				}
+			}),
+			post_init: None
+		});
+
+	let transform = from_value::<QNTransform>(property.value.to_owned()).context("Invalid transform")?;
+
+	property.value = to_value(update(&transform))?;
+
+	if app_state.editor_connection.is_connected().await {
+		app_state
+			.editor_connection
+			.set_property(
+				&entity_id,
+				&entity.blueprint_hash,
+				"m_mTransform",
+				PropertyValue {
+					property_type: "SMatrix43".into(),
+					data: property.value.to_owned()
+				}
+			)
+			.await?;
+	}
+
+	send_request(
+		app,
+		Request::Global(GlobalRequest::SetTabUnsaved {
+			id: editor_id,
+			unsaved: true
+		})
+	)?;
+
+	let mut buf = Vec::new();
+	let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+	let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+	entity
+		.entities
+		.get(&entity_id)
+		.context("No such entity")?
+		.serialize(&mut ser)?;
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Entity(EntityEditorRequest::Monaco(
+			EntityMonacoRequest::ReplaceContentIfSameEntityID {
+				editor_id: editor_id.to_owned(),
+				entity_id,
+				content: String::from_utf8(buf)?
+			}
+		)))
+	)?;
+
+	if let EditorData::QNPatch {
+		ref base, ref current, ..
+	} = editor_state.data
+	{
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+				EntityTreeRequest::SetDiffInfo {
+					editor_id,
+					diff_info: get_diff_info(base, current)
+				}
+			)))
+		)?;
+	}
+}
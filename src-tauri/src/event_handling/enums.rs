@@ -0,0 +1,124 @@
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::{
+	model::{AppState, EditorData, EditorRequest, EnumEditorEvent, EnumEditorRequest, GlobalRequest, Request},
+	resourcelib::SEnumType,
+	send_request
+};
+
+/// Send the enums of an editor to the frontend and update the intellisense enum cache, so that property completions
+/// reflect any custom values immediately.
+#[try_fn]
+#[context("Couldn't update enums")]
+fn update_enums(app: &AppHandle, id: Uuid, enums: &[SEnumType]) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	if let Some(intellisense) = app_state.intellisense.load().as_ref() {
+		for enum_type in enums {
+			intellisense
+				.enums
+				.insert(enum_type.name.to_owned(), enum_type.items.to_owned());
+		}
+	}
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Enum(EnumEditorRequest::SetEnums {
+			id,
+			enums: enums.iter().map(|x| (x.name.to_owned(), x.items.to_owned())).collect()
+		}))
+	)?;
+}
+
+#[try_fn]
+#[context("Couldn't handle enum editor event")]
+pub async fn handle_enum_event(app: &AppHandle, event: EnumEditorEvent) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	match event {
+		EnumEditorEvent::Initialise { id } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let enums = match editor_state.data {
+				EditorData::Enum { ref enums } => enums,
+
+				_ => {
+					bail!("Editor {} is not an enum editor", id);
+				}
+			};
+
+			update_enums(app, id, enums)?;
+		}
+
+		EnumEditorEvent::AddEnum { id, name } => {
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let enums = match editor_state.data {
+				EditorData::Enum { ref mut enums } => enums,
+
+				_ => {
+					bail!("Editor {} is not an enum editor", id);
+				}
+			};
+
+			if enums.iter().any(|x| x.name == name) {
+				bail!("An enum named {} already exists", name);
+			}
+
+			enums.push(SEnumType { name, items: vec![] });
+
+			update_enums(app, id, enums)?;
+
+			send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+		}
+
+		EnumEditorEvent::AddValue { id, enum_name, value } => {
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let enums = match editor_state.data {
+				EditorData::Enum { ref mut enums } => enums,
+
+				_ => {
+					bail!("Editor {} is not an enum editor", id);
+				}
+			};
+
+			let enum_type = enums.iter_mut().find(|x| x.name == enum_name).context("No such enum")?;
+
+			if !enum_type.items.contains(&value) {
+				enum_type.items.push(value);
+			}
+
+			update_enums(app, id, enums)?;
+
+			send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+		}
+
+		EnumEditorEvent::RemoveValue { id, enum_name, value } => {
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let enums = match editor_state.data {
+				EditorData::Enum { ref mut enums } => enums,
+
+				_ => {
+					bail!("Editor {} is not an enum editor", id);
+				}
+			};
+
+			enums
+				.iter_mut()
+				.find(|x| x.name == enum_name)
+				.context("No such enum")?
+				.items
+				.retain(|x| *x != value);
+
+			update_enums(app, id, enums)?;
+
+			send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+		}
+	}
+}
@@ -0,0 +1,277 @@
+use std::{
+	fs,
+	path::{Path, PathBuf}
+};
+
+use anyhow::{Context, Result, anyhow};
+use fn_error_context::context;
+use git2::{DiffFormat, DiffOptions, Repository, Status, StatusOptions};
+use quickentity_rs::{generate_patch, qn_structs::Entity};
+use serde_json::{from_slice, to_string};
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::{
+	Notification, NotificationKind,
+	backups::BACKUP_FOLDER,
+	biome::format_json,
+	model::{AppState, GitChangeKind, GitEvent, GitFileStatus, GitRequest, Request, ToolRequest},
+	send_notification, send_request,
+	telemetry::track_event
+};
+
+/// Get the path of a file relative to the working directory of a repository.
+fn get_repository_path<'a>(repo: &Repository, path: &'a Path) -> Result<&'a Path> {
+	let workdir = repo.workdir().context("Repository has no working directory")?;
+
+	path.strip_prefix(workdir).context("File is not in the repository")
+}
+
+/// Get the status of every changed file in a project, or an error if the project is not in a Git repository.
+#[try_fn]
+#[context("Couldn't get Git status")]
+pub fn get_git_status(project_path: &Path) -> Result<Vec<GitFileStatus>> {
+	let repo = Repository::discover(project_path)?;
+	let workdir = repo
+		.workdir()
+		.context("Repository has no working directory")?
+		.to_owned();
+
+	let statuses = repo.statuses(Some(
+		StatusOptions::new()
+			.include_untracked(true)
+			.recurse_untracked_dirs(true)
+			.renames_head_to_index(true)
+	))?;
+
+	let mut files = vec![];
+
+	for entry in statuses.iter() {
+		let Some(path) = entry.path() else {
+			continue;
+		};
+
+		let path = workdir.join(path);
+
+		// The repository may contain more than just the project, and backups of project files aren't part of it
+		let Ok(relative_path) = path.strip_prefix(project_path) else {
+			continue;
+		};
+
+		if relative_path.starts_with(BACKUP_FOLDER) {
+			continue;
+		}
+
+		let status = entry.status();
+
+		let change = if status.is_conflicted() {
+			GitChangeKind::Conflicted
+		} else if status.intersects(Status::INDEX_NEW | Status::WT_NEW) {
+			GitChangeKind::New
+		} else if status.intersects(Status::INDEX_DELETED | Status::WT_DELETED) {
+			GitChangeKind::Deleted
+		} else if status.intersects(Status::INDEX_RENAMED | Status::WT_RENAMED) {
+			GitChangeKind::Renamed
+		} else if status.intersects(Status::INDEX_TYPECHANGE | Status::WT_TYPECHANGE) {
+			GitChangeKind::TypeChange
+		} else {
+			GitChangeKind::Modified
+		};
+
+		files.push(GitFileStatus {
+			relative_path: relative_path.to_string_lossy().replace('\\', "/"),
+			path,
+			change,
+			staged: status.intersects(
+				Status::INDEX_NEW
+					| Status::INDEX_MODIFIED
+					| Status::INDEX_DELETED
+					| Status::INDEX_RENAMED
+					| Status::INDEX_TYPECHANGE
+			) && !status.intersects(
+				Status::WT_NEW | Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_RENAMED | Status::WT_TYPECHANGE
+			)
+		});
+	}
+
+	files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+	files
+}
+
+/// Read the committed (HEAD) version of a file, if it exists.
+#[try_fn]
+#[context("Couldn't read committed version of file")]
+fn read_head_file(repo: &Repository, path: &Path) -> Result<Option<Vec<u8>>> {
+	let Ok(head) = repo.head() else {
+		return Ok(None);
+	};
+
+	match head.peel_to_tree()?.get_path(get_repository_path(repo, path)?) {
+		Ok(entry) => Some(entry.to_object(repo)?.peel_to_blob()?.content().to_owned()),
+		Err(_) => None
+	}
+}
+
+/// Get a textual diff of a file between HEAD and the working directory.
+#[try_fn]
+#[context("Couldn't get textual diff")]
+fn get_text_diff(repo: &Repository, path: &Path) -> Result<String> {
+	let head_tree = repo.head().ok().and_then(|x| x.peel_to_tree().ok());
+
+	let diff = repo.diff_tree_to_workdir_with_index(
+		head_tree.as_ref(),
+		Some(
+			DiffOptions::new()
+				.pathspec(get_repository_path(repo, path)?)
+				.include_untracked(true)
+				.recurse_untracked_dirs(true)
+				.show_untracked_content(true)
+		)
+	)?;
+
+	let mut text = String::new();
+
+	diff.print(DiffFormat::Patch, |_, _, line| {
+		if matches!(line.origin(), '+' | '-' | ' ') {
+			text.push(line.origin());
+		}
+
+		text.push_str(&String::from_utf8_lossy(line.content()));
+
+		true
+	})?;
+
+	text
+}
+
+/// Get a QuickEntity patch between the committed and current versions of an entity file.
+#[try_fn]
+#[context("Couldn't get entity diff")]
+fn get_entity_diff(repo: &Repository, path: &Path) -> Result<Option<String>> {
+	let Some(base) = read_head_file(repo, path)? else {
+		return Ok(None);
+	};
+
+	let Ok(current) = fs::read(path) else {
+		return Ok(None);
+	};
+
+	let base: Entity = from_slice(&base).context("Committed entity is invalid")?;
+	let current: Entity = from_slice(&current).context("Current entity is invalid")?;
+
+	Some(format_json(&to_string(
+		&generate_patch(&base, &current)
+			.map_err(|x| anyhow!(x))
+			.context("Couldn't generate patch")?
+	)?)?)
+}
+
+/// Stage the given files (including deletions) and commit them.
+#[try_fn]
+#[context("Couldn't commit files")]
+fn commit_files(project_path: &Path, files: &[PathBuf], message: &str) -> Result<()> {
+	let repo = Repository::discover(project_path)?;
+
+	let mut index = repo.index()?;
+
+	for file in files {
+		let repository_path = get_repository_path(&repo, file)?;
+
+		if file.exists() {
+			index.add_path(repository_path)?;
+		} else {
+			index.remove_path(repository_path)?;
+		}
+	}
+
+	index.write()?;
+
+	let tree = repo.find_tree(index.write_tree()?)?;
+	let signature = repo
+		.signature()
+		.context("No Git user is configured; set user.name and user.email")?;
+
+	let parent = repo.head().ok().and_then(|x| x.peel_to_commit().ok());
+
+	repo.commit(
+		Some("HEAD"),
+		&signature,
+		&signature,
+		message,
+		&tree,
+		&parent.iter().collect::<Vec<_>>()
+	)?;
+}
+
+#[try_fn]
+#[context("Couldn't handle Git event")]
+pub async fn handle_git_event(app: &AppHandle, event: GitEvent) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	match event {
+		GitEvent::Refresh => {
+			if let Some(project) = app_state.project.load().as_ref() {
+				send_request(
+					app,
+					Request::Tool(ToolRequest::Git(GitRequest::SetStatus(
+						get_git_status(&project.path).ok()
+					)))
+				)?;
+			}
+		}
+
+		GitEvent::Diff(path) => {
+			if let Some(project) = app_state.project.load().as_ref() {
+				let repo = Repository::discover(&project.path)?;
+
+				let entity_diff = if path.to_string_lossy().ends_with(".entity.json") {
+					get_entity_diff(&repo, &path)?
+				} else {
+					None
+				};
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::Git(GitRequest::SetDiff {
+						is_entity_patch: entity_diff.is_some(),
+						diff: match entity_diff {
+							Some(diff) => diff,
+							None => get_text_diff(&repo, &path)?
+						},
+						path
+					}))
+				)?;
+			}
+		}
+
+		GitEvent::Commit { files, message } => {
+			if let Some(project) = app_state.project.load().as_ref() {
+				track_event(app, "Commit project files", None);
+
+				commit_files(&project.path, &files, &message)?;
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::Git(GitRequest::SetStatus(
+						get_git_status(&project.path).ok()
+					)))
+				)?;
+
+				send_notification(
+					app,
+					Notification {
+						kind: NotificationKind::Success,
+						title: "Changes committed".into(),
+						subtitle: format!(
+							"{} file{} committed.",
+							files.len(),
+							if files.len() == 1 { "" } else { "s" }
+						),
+						link: None
+					}
+				)?;
+			}
+		}
+	}
+}
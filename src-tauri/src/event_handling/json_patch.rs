@@ -0,0 +1,162 @@
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use serde::Serialize;
+use serde_json::{Value, from_str, from_value};
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::{
+	json_schema::get_pointer_diagnostics,
+	model::{
+		AppState, EditorData, EditorRequest, GlobalRequest, JsonPatchBase, JsonPatchEditorEvent,
+		JsonPatchEditorRequest, Request, TextDiagnostic
+	},
+	patches::{PatchOperationOutcome, apply_json_patch_operations, get_json_patch_base},
+	send_request
+};
+
+/// Apply a JSON patch file to its target resource, one operation at a time so that every failed operation can be
+/// marked, and send the result to the editor. The target is extracted again only if the patch now targets a different
+/// resource.
+#[try_fn]
+#[context("Couldn't update JSON patch preview")]
+fn update_preview(app: &AppHandle, id: Uuid, content: &str, base: &mut Option<JsonPatchBase>) -> Result<()> {
+	let patch = match from_str::<Value>(content) {
+		Ok(patch) => patch,
+
+		Err(err) => {
+			send_request(
+				app,
+				Request::Editor(EditorRequest::JsonPatch(JsonPatchEditorRequest::SetPreview {
+					id,
+					preview: None,
+					diagnostics: vec![TextDiagnostic {
+						message: format!("Invalid JSON: {}", err),
+						start_line: err.line() as u32,
+						start_column: err.column() as u32,
+						end_line: err.line() as u32,
+						end_column: err.column() as u32 + 1
+					}]
+				}))
+			)?;
+
+			return Ok(());
+		}
+	};
+
+	let mut errors = vec![];
+
+	let file = patch.get("file").and_then(|x| x.as_str());
+	let patch_type = patch.get("type").and_then(|x| x.as_str()).unwrap_or("JSON");
+
+	if let Some(file) = file {
+		if !base
+			.as_ref()
+			.is_some_and(|x| x.file == file && x.patch_type == patch_type)
+		{
+			*base = match get_json_patch_base(app, file, patch_type) {
+				Ok(x) => Some(x),
+
+				Err(err) => {
+					errors.push(("/file".into(), format!("{:?}", err)));
+					None
+				}
+			};
+		}
+	} else {
+		*base = None;
+		errors.push(("".into(), "Patch has no file key".into()));
+	}
+
+	let mut preview = base.as_ref().map(|x| x.data.to_owned());
+
+	match patch.get("patch") {
+		Some(Value::Array(operations)) => {
+			if let Some(preview) = preview.as_mut() {
+				for (index, result) in apply_json_patch_operations(preview, operations).into_iter().enumerate() {
+					if let PatchOperationOutcome::Fails(err) = result.outcome {
+						errors.push((format!("/patch/{}", index), err));
+					}
+				}
+			} else {
+				for (index, operation) in operations.iter().enumerate() {
+					if let Err(err) = from_value::<json_patch::PatchOperation>(operation.to_owned()) {
+						errors.push((format!("/patch/{}", index), format!("Invalid operation: {}", err)));
+					}
+				}
+			}
+		}
+
+		Some(_) => errors.push(("/patch".into(), "Patch key must be an array of operations".into())),
+
+		None => errors.push(("".into(), "Patch has no patch key".into()))
+	}
+
+	let preview = preview
+		.map(|preview| {
+			let mut buf = Vec::new();
+			let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+			let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+			preview.serialize(&mut ser)?;
+
+			anyhow::Ok(String::from_utf8(buf)?)
+		})
+		.transpose()?;
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::JsonPatch(JsonPatchEditorRequest::SetPreview {
+			id,
+			preview,
+			diagnostics: get_pointer_diagnostics(content, errors)
+		}))
+	)?;
+}
+
+#[try_fn]
+#[context("Couldn't handle JSON patch editor event")]
+pub async fn handle_json_patch_event(app: &AppHandle, event: JsonPatchEditorEvent) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	match event {
+		JsonPatchEditorEvent::Initialise { id } => {
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let EditorData::JsonPatch { content, base } = &mut editor_state.data else {
+				bail!("Editor {} is not a JSON patch editor", id);
+			};
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::JsonPatch(JsonPatchEditorRequest::ReplaceContent {
+					id,
+					content: content.to_owned()
+				}))
+			)?;
+
+			update_preview(app, id, content, base)?;
+		}
+
+		JsonPatchEditorEvent::UpdateContent { id, content } => {
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let EditorData::JsonPatch {
+				content: old_content,
+				base
+			} = &mut editor_state.data
+			else {
+				bail!("Editor {} is not a JSON patch editor", id);
+			};
+
+			if content != *old_content {
+				update_preview(app, id, &content, base)?;
+
+				*old_content = content;
+
+				send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+			}
+		}
+	}
+}
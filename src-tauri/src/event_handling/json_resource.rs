@@ -0,0 +1,91 @@
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::{
+	model::{
+		AppState, EditorData, EditorRequest, GlobalRequest, JsonResourceEditorEvent, JsonResourceEditorRequest,
+		JsonResourceKind, Request
+	},
+	send_request
+};
+
+/// Keys found on every GameChanger definition.
+const GAMECHANGER_KEYS: [&str; 3] = ["Id", "Name", "Objectives"];
+
+/// Keys found on every challenge.
+const CHALLENGE_KEYS: [&str; 3] = ["Id", "Name", "Definition"];
+
+/// Work out which bundled schema applies to a JSON resource, if any. Challenge resources are an object with a `groups`
+/// array of challenge groups, and GameChanger resources are an object of GameChanger IDs to definitions.
+pub fn get_json_resource_kind(json: &Value) -> Option<JsonResourceKind> {
+	let json = json.as_object()?;
+
+	let challenges = json
+		.get("groups")
+		.and_then(|x| x.as_array())
+		.and_then(|x| x.first())
+		.and_then(|x| x.get("Challenges"))
+		.and_then(|x| x.as_array())
+		.and_then(|x| x.first());
+
+	if let Some(challenge) = challenges
+		&& CHALLENGE_KEYS.iter().all(|key| challenge.get(key).is_some())
+	{
+		return Some(JsonResourceKind::Challenges);
+	}
+
+	if !json.is_empty()
+		&& json
+			.values()
+			.all(|definition| GAMECHANGER_KEYS.iter().all(|key| definition.get(key).is_some()))
+	{
+		return Some(JsonResourceKind::GameChangers);
+	}
+
+	None
+}
+
+#[try_fn]
+#[context("Couldn't handle JSON resource editor event")]
+pub async fn handle_json_resource_event(app: &AppHandle, event: JsonResourceEditorEvent) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	match event {
+		JsonResourceEditorEvent::Initialise { id } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let EditorData::JsonResource { kind, content, .. } = &editor_state.data else {
+				bail!("Editor {} is not a JSON resource editor", id);
+			};
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::JsonResource(JsonResourceEditorRequest::ReplaceContent {
+					id,
+					kind: *kind,
+					content: content.to_owned()
+				}))
+			)?;
+		}
+
+		JsonResourceEditorEvent::UpdateContent { id, content } => {
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let EditorData::JsonResource {
+				content: old_content, ..
+			} = &mut editor_state.data
+			else {
+				bail!("Editor {} is not a JSON resource editor", id);
+			};
+
+			if content != *old_content {
+				*old_content = content;
+
+				send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+			}
+		}
+	}
+}
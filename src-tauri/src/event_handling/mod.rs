@@ -1,6 +1,13 @@
 pub mod content_search;
+pub mod dialogue;
 pub mod entity;
+pub mod enums;
+pub mod git;
+pub mod json_patch;
+pub mod json_resource;
+pub mod outfit_browser;
 pub mod repository_patch;
 pub mod resource_overview;
+pub mod text;
 pub mod tools;
 pub mod unlockables_patch;
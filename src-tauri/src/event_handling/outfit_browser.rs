@@ -0,0 +1,225 @@
+use std::{fs, io::Cursor};
+
+use anyhow::{Context, Result};
+use arc_swap::ArcSwap;
+use fn_error_context::context;
+use hashbrown::HashMap;
+use hitman_commons::{hash_list::HashList, metadata::RuntimeID};
+use image::ImageReader;
+use indexmap::IndexMap;
+use quickentity_rs::qn_structs::{Ref, SubEntity};
+use serde_json::{Value, from_value};
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::{
+	entity::get_local_reference,
+	finish_task,
+	general::open_in_editor,
+	get_loaded_game_version,
+	model::{
+		AppSettings, AppState, OutfitBrowserEntry, OutfitBrowserEntryKind, OutfitBrowserEvent, OutfitBrowserRequest,
+		Request, ToolRequest
+	},
+	rpkg::{extract_entity_async, extract_latest_resource},
+	send_request, start_task
+};
+
+const OUTFIT_KIT_FACTORY: &str = "[modules:/zglobaloutfitkit.class].pc_entitytype";
+
+/// The character templates and image of a global outfit kit.
+struct OutfitKitInfo {
+	character_templates: Vec<RuntimeID>,
+	image: Option<RuntimeID>
+}
+
+/// Get the GFXI referenced by a ZRuntimeResourceID property value, if any.
+fn get_image_resource(value: &Value, hash_list: &HashList) -> Option<RuntimeID> {
+	let res = match value {
+		Value::Object(obj) => obj.get("resource")?.as_str()?,
+		Value::String(res) => res.as_str(),
+		_ => None?
+	};
+
+	let res = RuntimeID::from_any(res).ok()?;
+
+	(hash_list.entries.get(&res)?.resource_type == "GFXI").then_some(res)
+}
+
+#[try_fn]
+#[context("Couldn't get outfit kit information")]
+fn get_outfit_kit_info(
+	sub_entity: &SubEntity,
+	entities: &IndexMap<String, SubEntity>,
+	hash_list: &HashList
+) -> Result<OutfitKitInfo> {
+	let properties = sub_entity.properties.as_ref();
+
+	let character_templates = properties
+		.and_then(|x| x.get("m_aCharSets"))
+		.map(|x| from_value::<Vec<Ref>>(x.value.to_owned()))
+		.transpose()
+		.context("Invalid m_aCharSets")?
+		.unwrap_or_default()
+		.iter()
+		.filter_map(get_local_reference)
+		.filter_map(|x| entities.get(&x))
+		.filter_map(|x| RuntimeID::from_any(&x.factory).ok())
+		.collect();
+
+	let image = properties.and_then(|x| {
+		x.values()
+			.filter(|x| x.property_type == "ZRuntimeResourceID")
+			.find_map(|x| get_image_resource(&x.value, hash_list))
+	});
+
+	OutfitKitInfo {
+		character_templates,
+		image
+	}
+}
+
+#[try_fn]
+#[context("Couldn't handle outfit browser event")]
+pub async fn handle_outfit_browser_event(app: &AppHandle, event: OutfitBrowserEvent) -> Result<()> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+	let app_state = app.state::<AppState>();
+
+	match event {
+		OutfitBrowserEvent::Initialise => {
+			if let Some(game_files) = app_state.game_files.load().as_ref()
+				&& let Some(resource_reverse_dependencies) = app_state.resource_reverse_dependencies.load().as_ref()
+				&& let Some(install) = app_settings.load().game_install.as_ref()
+				&& let Some(hash_list) = app_state.hash_list.load().as_ref()
+				&& let Some(repository) = app_state.repository.load().as_ref()
+			{
+				let task = start_task(app, "Finding outfit kits")?;
+
+				let game_version = get_loaded_game_version(app, install)?;
+
+				let outfit_kit_factory = RuntimeID::from_any(OUTFIT_KIT_FACTORY)?;
+
+				// Repository ID -> outfit kit (brick it was found in, kit info)
+				let mut outfit_kits = HashMap::new();
+
+				for brick in resource_reverse_dependencies
+					.get(&outfit_kit_factory)
+					.map(|x| x.as_slice())
+					.unwrap_or_default()
+					.iter()
+					.filter(|x| hash_list.entries.get(*x).is_some_and(|x| x.resource_type == "TEMP"))
+				{
+					let Ok(entity) =
+						extract_entity_async(game_files, &app_state.cached_entities, game_version, hash_list, *brick)
+							.await
+					else {
+						continue;
+					};
+
+					for sub_entity in entity.entities.values() {
+						if RuntimeID::from_any(&sub_entity.factory).ok() != Some(outfit_kit_factory) {
+							continue;
+						}
+
+						if let Some(repository_id) = sub_entity
+							.properties
+							.as_ref()
+							.and_then(|x| x.get("m_sId"))
+							.and_then(|x| x.value.as_str())
+							.and_then(|x| x.parse::<Uuid>().ok())
+						{
+							outfit_kits.insert(
+								repository_id,
+								(*brick, get_outfit_kit_info(sub_entity, &entity.entities, hash_list)?)
+							);
+						}
+					}
+				}
+
+				finish_task(app, task)?;
+
+				let task = start_task(app, "Building outfit list")?;
+
+				let mut entries = vec![];
+
+				for item in repository.iter() {
+					let (kind, name, outfit_id) = if item.data.contains_key("OutfitVariationIndex") {
+						(
+							OutfitBrowserEntryKind::NPC,
+							item.data.get("Name"),
+							item.data
+								.get("Outfit")
+								.and_then(|x| x.as_str())
+								.and_then(|x| x.parse::<Uuid>().ok())
+						)
+					} else if item.data.contains_key("IsHitmanSuit") {
+						(
+							OutfitBrowserEntryKind::Outfit,
+							item.data.get("CommonName"),
+							Some(item.id)
+						)
+					} else {
+						continue;
+					};
+
+					let kit = outfit_id.and_then(|x| outfit_kits.get(&x));
+
+					entries.push(OutfitBrowserEntry {
+						repository_id: item.id,
+						kind,
+						name: name.and_then(|x| x.as_str()).unwrap_or_default().into(),
+						outfit_kit_brick: kit.map(|(brick, _)| *brick),
+						character_templates: kit.map(|(_, x)| x.character_templates.to_owned()).unwrap_or_default(),
+						image: kit.and_then(|(_, x)| x.image)
+					});
+				}
+
+				entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::OutfitBrowser(OutfitBrowserRequest::SetEntries(entries)))
+				)?;
+
+				finish_task(app, task)?;
+			}
+		}
+
+		OutfitBrowserEvent::LoadImage(hash) => {
+			if let Some(game_files) = app_state.game_files.load().as_ref() {
+				let data_dir = app.path().app_data_dir().expect("Couldn't get data dir");
+
+				fs::create_dir_all(data_dir.join("temp"))?;
+
+				let image_path = data_dir.join("temp").join(format!("{}.png", hash));
+
+				if !image_path.exists() {
+					let (_, res_data) = extract_latest_resource(game_files, hash)?;
+
+					ImageReader::new(Cursor::new(res_data))
+						.with_guessed_format()?
+						.decode()?
+						.save(&image_path)?;
+				}
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::OutfitBrowser(OutfitBrowserRequest::SetImage {
+						hash,
+						image_path
+					}))
+				)?;
+			}
+		}
+
+		OutfitBrowserEvent::OpenEntity(hash) => {
+			if let Some(game_files) = app_state.game_files.load().as_ref()
+				&& let Some(install) = app_settings.load().game_install.as_ref()
+				&& let Some(hash_list) = app_state.hash_list.load().as_ref()
+			{
+				open_in_editor(app, game_files, install, hash_list, hash).await?;
+			}
+		}
+	}
+}
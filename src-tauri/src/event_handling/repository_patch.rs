@@ -1,24 +1,38 @@
+use std::cmp::Ordering;
+
 use anyhow::{Context, Result, bail};
+use arboard::Clipboard;
 use fn_error_context::context;
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use indexmap::IndexMap;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rayon::{
+	iter::{IntoParallelRefIterator, ParallelIterator},
+	slice::ParallelSliceMut
+};
 use serde::Serialize;
-use serde_json::{Value, from_str, from_value};
+use serde_json::{Number, Value, from_str, from_value, to_string};
 use tauri::{AppHandle, Manager};
 use tryvial::try_fn;
 use uuid::Uuid;
 
 use crate::{
+	Notification, NotificationKind,
+	entity::entity_uses_guid,
+	event_handling::unlockables_patch,
 	finish_task,
+	localisation::add_localisation,
 	model::{
-		AppState, EditorData, EditorRequest, GlobalRequest, RepositoryPatchEditorEvent, RepositoryPatchEditorRequest,
-		Request
+		AppState, EditorData, EditorRequest, EditorState, EditorType, GlobalRequest, RepositoryPatchEditorEvent,
+		RepositoryPatchEditorRequest, Request, UnlockablesPatchEditorRequest
 	},
-	ores_repo::{RepositoryItem, RepositoryItemInformation},
-	send_request, start_task
+	ores_repo::{RepositoryColumnType, RepositoryItem, RepositoryItemInformation, RepositoryTableRow},
+	refuse_if_read_only, send_notification, send_request, start_task,
+	table_import::read_import_table
 };
 
+/// How many rows of an import file are shown while mapping its columns.
+const IMPORT_PREVIEW_ROWS: usize = 5;
+
 #[try_fn]
 #[context("Couldn't get information of repository item {item:?}")]
 fn get_repository_item_information(item: &RepositoryItem) -> Result<RepositoryItemInformation> {
@@ -190,6 +204,160 @@ fn get_modified_items(base: &[RepositoryItem], current: &[RepositoryItem]) -> Ve
 		.collect()
 }
 
+/// The properties of the repository items with the kind of value they hold, most common first.
+fn get_table_columns(items: &[RepositoryItem]) -> Vec<(String, RepositoryColumnType)> {
+	let mut columns: IndexMap<&str, (usize, Option<RepositoryColumnType>)> = IndexMap::new();
+
+	for item in items {
+		for (key, value) in &item.data {
+			let (count, column_type) = columns.entry(key.as_str()).or_default();
+
+			*count += 1;
+
+			*column_type = match (*column_type, RepositoryColumnType::of(value)) {
+				(None, x) | (x, None) => x,
+				(Some(a), Some(b)) if a == b => Some(a),
+				_ => Some(RepositoryColumnType::Mixed)
+			};
+		}
+	}
+
+	columns.sort_by(|_, (a, _), _, (b, _)| b.cmp(a));
+
+	columns
+		.into_iter()
+		.map(|(key, (_, column_type))| (key.to_owned(), column_type.unwrap_or(RepositoryColumnType::Mixed)))
+		.collect()
+}
+
+/// The text a cell is filtered by; strings are matched without their quotes.
+fn get_cell_text(value: &Value) -> String {
+	match value {
+		Value::String(x) => x.to_lowercase(),
+		x => x.to_string().to_lowercase()
+	}
+}
+
+/// Order two cells, keeping items which don't have the property last regardless of the direction.
+fn compare_cells(a: Option<&Value>, b: Option<&Value>, descending: bool) -> Ordering {
+	let (a, b) = match (a, b) {
+		(Some(a), Some(b)) => (a, b),
+		(Some(_), None) => return Ordering::Less,
+		(None, Some(_)) => return Ordering::Greater,
+		(None, None) => return Ordering::Equal
+	};
+
+	let ordering = match (a, b) {
+		(Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()).unwrap_or(Ordering::Equal),
+		(Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+		(a, b) => get_cell_text(a).cmp(&get_cell_text(b))
+	};
+
+	if descending { ordering.reverse() } else { ordering }
+}
+
+/// Get the rows of the table view of a repository patch, filtered and sorted.
+fn get_table_rows(
+	base: &[RepositoryItem],
+	current: &[RepositoryItem],
+	columns: &[String],
+	sort_by: Option<&str>,
+	sort_descending: bool,
+	filter: &str
+) -> Vec<RepositoryTableRow> {
+	let terms = filter
+		.split_whitespace()
+		.map(|term| {
+			match term
+				.split_once(':')
+				.and_then(|(column, text)| Some((columns.iter().find(|x| x.eq_ignore_ascii_case(column))?, text)))
+			{
+				Some((column, text)) => (Some(column.as_str()), text.to_lowercase()),
+				None => (None, term.to_lowercase())
+			}
+		})
+		.collect::<Vec<_>>();
+
+	let mut items = current
+		.par_iter()
+		.filter(|item| {
+			terms.iter().all(|(column, text)| match column {
+				Some(column) => item.data.get(*column).is_some_and(|x| get_cell_text(x).contains(text)),
+
+				None => {
+					item.id.to_string().contains(text)
+						|| columns
+							.iter()
+							.filter_map(|column| item.data.get(column))
+							.any(|x| get_cell_text(x).contains(text))
+				}
+			})
+		})
+		.collect::<Vec<_>>();
+
+	if let Some(sort_by) = sort_by {
+		items.par_sort_by(|a, b| compare_cells(a.data.get(sort_by), b.data.get(sort_by), sort_descending));
+	}
+
+	let modified = get_modified_items(base, current).into_iter().collect::<HashSet<_>>();
+
+	items
+		.into_iter()
+		.map(|item| RepositoryTableRow {
+			item: item.id,
+			modified: modified.contains(&item.id),
+			cells: columns.iter().map(|column| item.data.get(column).cloned()).collect()
+		})
+		.collect()
+}
+
+/// Parse the text entered into a table cell according to the type of its column; None means the property should be
+/// removed.
+#[try_fn]
+#[context("Couldn't parse value for {column}")]
+fn parse_cell_value(value: &str, column: &str, column_type: Option<RepositoryColumnType>) -> Result<Option<Value>> {
+	if value.trim().is_empty() && column_type != Some(RepositoryColumnType::String) {
+		return Ok(None);
+	}
+
+	Some(match column_type {
+		Some(RepositoryColumnType::String) => Value::String(value.into()),
+
+		Some(RepositoryColumnType::Number) => {
+			Value::Number(from_str::<Number>(value.trim()).context("Value must be a number")?)
+		}
+
+		Some(RepositoryColumnType::Boolean) => {
+			Value::Bool(value.trim().parse::<bool>().context("Value must be true or false")?)
+		}
+
+		Some(RepositoryColumnType::Array) => {
+			let value = from_str::<Value>(value).context("Value must be a JSON array")?;
+
+			if !value.is_array() {
+				bail!("Value must be a JSON array");
+			}
+
+			value
+		}
+
+		Some(RepositoryColumnType::Object) => {
+			let value = from_str::<Value>(value).context("Value must be a JSON object")?;
+
+			if !value.is_object() {
+				bail!("Value must be a JSON object");
+			}
+
+			value
+		}
+
+		// Columns without a single type take JSON, falling back to a string
+		Some(RepositoryColumnType::Mixed) | None => {
+			from_str::<Value>(value).unwrap_or_else(|_| Value::String(value.into()))
+		}
+	})
+}
+
 #[try_fn]
 #[context("Couldn't handle repository patch event")]
 pub async fn handle_repository_patch_event(app: &AppHandle, event: RepositoryPatchEditorEvent) -> Result<()> {
@@ -285,6 +453,151 @@ pub async fn handle_repository_patch_event(app: &AppHandle, event: RepositoryPat
 			finish_task(app, task)?;
 		}
 
+		RepositoryPatchEditorEvent::CreateRepositoryItemFromTemplate {
+			id,
+			template,
+			name,
+			create_unlockable
+		} => {
+			let task = start_task(app, "Creating repository item")?;
+
+			let new_id = Uuid::new_v4();
+
+			{
+				let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+				let (base, repository) = match editor_state.data {
+					EditorData::RepositoryPatch {
+						ref base,
+						ref mut current,
+						..
+					} => (base, current),
+
+					_ => {
+						bail!("Editor {} is not a repository patch editor", id);
+					}
+				};
+
+				repository.push(RepositoryItem {
+					id: new_id.to_owned(),
+					data: template.create_item(&name)
+				});
+
+				send_request(
+					app,
+					Request::Editor(EditorRequest::RepositoryPatch(
+						RepositoryPatchEditorRequest::AddNewRepositoryItem {
+							id: id.to_owned(),
+							new_item: (
+								new_id.to_owned(),
+								get_repository_item_information(repository.last().unwrap())?
+							)
+						}
+					))
+				)?;
+
+				send_request(
+					app,
+					Request::Editor(EditorRequest::RepositoryPatch(
+						RepositoryPatchEditorRequest::SetModifiedRepositoryItems {
+							id,
+							modified: get_modified_items(base, repository)
+						}
+					))
+				)?;
+
+				send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+			}
+
+			if create_unlockable {
+				let unlockable = template.create_unlockable(new_id, &name);
+
+				// Add the unlockable to an open unlockables patch if there is one, otherwise copy it for the user to paste
+				let unlockables_editor = app_state
+					.editor_states
+					.iter()
+					.find(|x| matches!(x.data, EditorData::UnlockablesPatch { .. }))
+					.map(|x| x.key().to_owned());
+
+				if let Some(unlockables_editor) = unlockables_editor {
+					let mut editor_state = app_state
+						.editor_states
+						.get_mut(&unlockables_editor)
+						.context("No such editor")?;
+
+					let (base, unlockables) = match editor_state.data {
+						EditorData::UnlockablesPatch {
+							ref base,
+							ref mut current,
+							..
+						} => (base, current),
+
+						_ => unreachable!()
+					};
+
+					send_request(
+						app,
+						Request::Editor(EditorRequest::UnlockablesPatch(
+							UnlockablesPatchEditorRequest::AddNewUnlockable {
+								id: unlockables_editor,
+								new_unlockable: (
+									unlockable.id.to_owned(),
+									unlockables_patch::get_unlockable_information(&unlockable)?
+								)
+							}
+						))
+					)?;
+
+					unlockables.push(unlockable);
+
+					send_request(
+						app,
+						Request::Editor(EditorRequest::UnlockablesPatch(
+							UnlockablesPatchEditorRequest::SetModifiedUnlockables {
+								id: unlockables_editor,
+								modified: unlockables_patch::get_modified_items(base, unlockables)
+							}
+						))
+					)?;
+
+					send_request(
+						app,
+						Request::Global(GlobalRequest::SetTabUnsaved {
+							id: unlockables_editor,
+							unsaved: true
+						})
+					)?;
+
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Success,
+							title: "Unlockable created".into(),
+							subtitle: "An unlockable for the new item has been added to the open unlockables patch."
+								.into(),
+							link: None
+						}
+					)?;
+				} else {
+					Clipboard::new()?.set_text(to_string(&unlockable)?)?;
+
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Info,
+							title: "Unlockable copied".into(),
+							subtitle: "No unlockables patch is open, so the unlockable for the new item has been \
+							           copied to your clipboard."
+								.into(),
+							link: None
+						}
+					)?;
+				}
+			}
+
+			finish_task(app, task)?;
+		}
+
 		RepositoryPatchEditorEvent::ResetModifications { id, item } => {
 			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
 
@@ -451,5 +764,405 @@ pub async fn handle_repository_patch_event(app: &AppHandle, event: RepositoryPat
 
 			finish_task(app, task)?;
 		}
+
+		RepositoryPatchEditorEvent::GenerateLocalisation { id, item } => {
+			if refuse_if_read_only(app, "Generating localisation")? {
+				return Ok(());
+			}
+
+			let project = app_state.project.load();
+			let project = project.as_ref().context("No project loaded")?;
+
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let (base, repository) = match editor_state.data {
+				EditorData::RepositoryPatch {
+					ref base,
+					ref mut current,
+					..
+				} => (base, current),
+
+				_ => {
+					bail!("Editor {} is not a repository patch editor", id);
+				}
+			};
+
+			let itm = repository
+				.iter_mut()
+				.find(|x| x.id == item)
+				.context("No such repository item")?;
+
+			let name = itm
+				.data
+				.get("CommonName")
+				.or_else(|| itm.data.get("Name"))
+				.and_then(|x| x.as_str())
+				.filter(|x| !x.trim().is_empty())
+				.map(|x| x.to_owned())
+				.unwrap_or_else(|| item.to_string());
+
+			let keys = add_localisation(
+				&project.path,
+				&project.settings.load().localisation_languages,
+				&name,
+				&[("NAME", name.to_owned()), ("DESC", format!("Description of {}", name))]
+			)?;
+
+			itm.data.insert("Title".into(), Value::String(keys[0].to_owned()));
+			itm.data.insert("Description".into(), Value::String(keys[1].to_owned()));
+
+			let mut buf_orig = Vec::new();
+			let formatter_orig = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+			let mut ser_orig = serde_json::Serializer::with_formatter(&mut buf_orig, formatter_orig);
+
+			if let Some(orig_item) = base.iter().find(|x| x.id == item) {
+				orig_item.data.serialize(&mut ser_orig)?;
+			}
+
+			let mut buf = Vec::new();
+			let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+			let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+			itm.data.serialize(&mut ser)?;
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::RepositoryPatch(
+					RepositoryPatchEditorRequest::SetMonacoContent {
+						id,
+						item,
+						orig_data: String::from_utf8(buf_orig)?,
+						data: String::from_utf8(buf)?
+					}
+				))
+			)?;
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::RepositoryPatch(
+					RepositoryPatchEditorRequest::SetModifiedRepositoryItems {
+						id,
+						modified: get_modified_items(base, repository)
+					}
+				))
+			)?;
+
+			send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Success,
+					title: "Localisation generated".into(),
+					subtitle: format!(
+						"{} and {} have been added to the manifest; replace the placeholder strings with the actual \
+						 text.",
+						keys[0], keys[1]
+					),
+					link: None
+				}
+			)?;
+		}
+
+		RepositoryPatchEditorEvent::FindEntitiesUsingItem { item, .. } => {
+			let task = start_task(app, format!("Finding entities using {}", item))?;
+
+			let guid = item.to_string();
+
+			let results = app_state
+				.cached_entities
+				.iter()
+				.filter(|x| entity_uses_guid(x.value(), &guid))
+				.map(|x| {
+					let hash = x.key().to_owned();
+
+					let path = app_state
+						.hash_list
+						.load()
+						.as_ref()
+						.and_then(|hash_list| hash_list.entries.get(&hash))
+						.and_then(|x| x.path.as_ref().or(x.hint.as_ref()).cloned());
+
+					(hash.to_string(), "TEMP".into(), path)
+				})
+				.collect::<Vec<_>>();
+
+			if results.is_empty() {
+				send_notification(
+					app,
+					Notification {
+						kind: NotificationKind::Info,
+						title: "No entities found".into(),
+						subtitle: "None of the entities loaded so far use this repository item. Entities are only \
+						           searched once they have been opened or referenced."
+							.into(),
+						link: None
+					}
+				)?;
+			} else {
+				let id = Uuid::new_v4();
+
+				app_state.editor_states.insert(
+					id.to_owned(),
+					EditorState {
+						file: None,
+						data: EditorData::ContentSearchResults { results }
+					}
+				);
+
+				send_request(
+					app,
+					Request::Global(GlobalRequest::CreateTab {
+						id,
+						name: format!("Entities using {}", item),
+						editor_type: EditorType::ContentSearchResults
+					})
+				)?;
+			}
+
+			finish_task(app, task)?;
+		}
+
+		RepositoryPatchEditorEvent::GetTableColumns { id } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let EditorData::RepositoryPatch { current, .. } = &editor_state.data else {
+				bail!("Editor {} is not a repository patch editor", id);
+			};
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::RepositoryPatch(
+					RepositoryPatchEditorRequest::SetTableColumns {
+						id,
+						columns: get_table_columns(current)
+					}
+				))
+			)?;
+		}
+
+		RepositoryPatchEditorEvent::GetTable {
+			id,
+			columns,
+			sort_by,
+			sort_descending,
+			filter
+		} => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let EditorData::RepositoryPatch { base, current, .. } = &editor_state.data else {
+				bail!("Editor {} is not a repository patch editor", id);
+			};
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::RepositoryPatch(
+					RepositoryPatchEditorRequest::SetTableRows {
+						id,
+						rows: get_table_rows(base, current, &columns, sort_by.as_deref(), sort_descending, &filter)
+					}
+				))
+			)?;
+		}
+
+		RepositoryPatchEditorEvent::EditTableCell {
+			id,
+			item,
+			column,
+			value
+		} => {
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let EditorData::RepositoryPatch { base, current, .. } = &mut editor_state.data else {
+				bail!("Editor {} is not a repository patch editor", id);
+			};
+
+			let column_type = get_table_columns(current)
+				.into_iter()
+				.find(|(x, _)| *x == column)
+				.map(|(_, x)| x);
+
+			let value = parse_cell_value(&value, &column, column_type)?;
+
+			let itm = current
+				.iter_mut()
+				.find(|x| x.id == item)
+				.context("No such repository item")?;
+
+			if itm.data.get(&column) == value.as_ref() {
+				return Ok(());
+			}
+
+			if let Some(value) = value.to_owned() {
+				itm.data.insert(column.to_owned(), value);
+			} else {
+				itm.data.shift_remove(&column);
+			}
+
+			let info = get_repository_item_information(itm)?;
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::RepositoryPatch(
+					RepositoryPatchEditorRequest::SetTableCell {
+						id,
+						item,
+						column,
+						value
+					}
+				))
+			)?;
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::RepositoryPatch(
+					RepositoryPatchEditorRequest::SetModifiedRepositoryItems {
+						id,
+						modified: get_modified_items(base, current)
+					}
+				))
+			)?;
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::RepositoryPatch(
+					RepositoryPatchEditorRequest::ModifyItemInformation { id, item, info }
+				))
+			)?;
+
+			send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+		}
+
+		RepositoryPatchEditorEvent::ReadImportFile { id, path } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let EditorData::RepositoryPatch { current, .. } = &editor_state.data else {
+				bail!("Editor {} is not a repository patch editor", id);
+			};
+
+			let table = read_import_table(&path)?;
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::RepositoryPatch(
+					RepositoryPatchEditorRequest::ShowImportMapping {
+						id,
+						path,
+						preview: table
+							.rows
+							.iter()
+							.take(IMPORT_PREVIEW_ROWS)
+							.map(|row| {
+								table
+									.columns
+									.iter()
+									.map(|column| match row.get(column) {
+										Some(Value::String(x)) => x.to_owned(),
+										Some(x) => x.to_string(),
+										None => String::new()
+									})
+									.collect()
+							})
+							.collect(),
+						columns: table.columns,
+						row_count: table.rows.len() as u32,
+						properties: get_table_columns(current).into_iter().map(|(x, _)| x).collect()
+					}
+				))
+			)?;
+		}
+
+		RepositoryPatchEditorEvent::ImportItems { id, path, mapping } => {
+			let task = start_task(app, "Importing repository items")?;
+
+			let table = read_import_table(&path)?;
+
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let EditorData::RepositoryPatch { base, current, .. } = &mut editor_state.data else {
+				bail!("Editor {} is not a repository patch editor", id);
+			};
+
+			let mapping = mapping
+				.into_iter()
+				.map(|(column, property)| (column, property.trim().to_owned()))
+				.filter(|(_, property)| !property.is_empty())
+				.collect::<Vec<_>>();
+
+			if mapping.is_empty() {
+				bail!("No columns were mapped to properties");
+			}
+
+			let column_types = get_table_columns(current).into_iter().collect::<HashMap<_, _>>();
+
+			let mut new_items = vec![];
+
+			for (index, row) in table.rows.into_iter().enumerate() {
+				let mut data = IndexMap::new();
+
+				for (column, property) in &mapping {
+					let value = match row.get(column) {
+						Some(Value::String(x)) if x.trim().is_empty() => None,
+
+						Some(Value::String(x)) => parse_cell_value(x, property, column_types.get(property).copied())
+							.with_context(|| format!("Couldn't import row {}", index + 1))?,
+
+						Some(x) => Some(x.to_owned()),
+
+						None => None
+					};
+
+					if let Some(value) = value {
+						data.insert(property.to_owned(), value);
+					}
+				}
+
+				new_items.push(RepositoryItem {
+					id: Uuid::new_v4(),
+					data
+				});
+			}
+
+			let imported = new_items.len();
+
+			current.extend(new_items);
+
+			let items = current
+				.par_iter()
+				.map(|item| -> Result<_> { Ok((item.id.to_owned(), get_repository_item_information(item)?)) })
+				.collect::<Result<_>>()?;
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::RepositoryPatch(
+					RepositoryPatchEditorRequest::SetRepositoryItems { id, items }
+				))
+			)?;
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::RepositoryPatch(
+					RepositoryPatchEditorRequest::SetModifiedRepositoryItems {
+						id,
+						modified: get_modified_items(base, current)
+					}
+				))
+			)?;
+
+			send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+
+			finish_task(app, task)?;
+
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Success,
+					title: format!("Imported {} item{}", imported, if imported == 1 { "" } else { "s" }),
+					subtitle: "Each row of the file has been added as a new repository item with a generated ID."
+						.into(),
+					link: None
+				}
+			)?;
+		}
 	}
 }
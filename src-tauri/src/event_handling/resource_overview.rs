@@ -1,4 +1,11 @@
-use std::{fmt::Write, fs, io::Cursor, ops::Deref, sync::Arc};
+use std::{
+	fmt::Write,
+	fs,
+	io::Cursor,
+	ops::Deref,
+	path::{Path, PathBuf},
+	sync::Arc
+};
 
 use anyhow::{Context, Result, anyhow, bail};
 use arc_swap::ArcSwap;
@@ -9,47 +16,835 @@ use glacier_texture::{
 	texture_map::TextureMap
 };
 
-use hashbrown::HashMap;
-use hitman_commons::{game::GameVersion, hash_list::HashList, metadata::RuntimeID, rpkg_tool::RpkgResourceMeta};
+use hashbrown::{HashMap, HashSet};
+use hitman_commons::{
+	game::GameVersion,
+	hash_list::HashList,
+	metadata::{ExtendedResourceMetadata, ResourceType, RuntimeID},
+	rpkg_tool::RpkgResourceMeta
+};
 use hitman_formats::{
 	material::{MaterialEntity, MaterialInstance},
 	ores::{parse_hashes_ores, parse_json_ores},
 	sdef::SoundDefinitions,
 	wwev::WwiseEvent
 };
-use image::{ImageFormat, ImageReader};
+use image::{DynamicImage, ImageFormat, ImageReader, imageops::FilterType};
+use itertools::Itertools;
+use log::warn;
 use prim_rs::render_primitive::RenderPrimitive;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use rpkg_rs::{GlacierResource, resource::partition_manager::PartitionManager};
 use serde::Serialize;
-use serde_json::{Value, json, to_string, to_vec};
+use serde_json::{Value, from_str, json, to_string, to_vec};
 use tauri::{AppHandle, Manager, State};
-use tauri_plugin_aptabase::EventTracker;
 use tauri_plugin_dialog::DialogExt;
 use tauri_plugin_shell::ShellExt;
 use tonytools::hmlanguages;
 use tryvial::try_fn;
 use uuid::Uuid;
+use walkdir::WalkDir;
 
 use crate::{
 	Notification, NotificationKind, RunCommandExt,
+	aloc::{collision_to_obj, parse_aloc},
+	audio_names::{get_audio_names, wwise_hash},
+	behaviour_tree::BehaviourTreeNode,
 	biome::format_json,
+	borg::{Bone, parse_bone_rig},
+	collections::store_collections,
+	event_handling::json_resource::get_json_resource_kind,
+	external_tools::launch_external_tool,
+	extraction_presets::enqueue_preset_extraction,
 	finish_task,
 	general::open_in_editor,
 	get_loaded_game_version,
 	languages::get_language_map,
+	material_rebuild::rebuild_material_entity,
 	model::{
-		AppSettings, AppState, EditorData, EditorRequest, EditorState, EditorType, GlobalRequest, Request,
-		ResourceOverviewData, ResourceOverviewEvent, ResourceOverviewRequest
+		AnimationDependency, AppSettings, AppState, BlueprintEntityInfo, BlueprintPinForwarding, BlueprintSubsetInfo,
+		EditorData, EditorRequest, EditorState, EditorType, GlobalRequest, Request, ResourceOverviewData,
+		ResourceOverviewEvent, ResourceOverviewRequest, TextureMipComparison, WwiseSwitchGroup
 	},
+	packaging::DIST_FOLDER,
+	reference_flags::{
+		DecodedReferenceFlag, ReferenceKind, decode_reference_flag, find_project_meta, read_meta_dependencies,
+		set_meta_dependency_flag, write_meta_dependencies
+	},
+	refuse_if_read_only,
+	resource_capabilities::get_resource_type_capabilities,
 	resourcelib::{
-		convert_generic, h2_convert_binary_to_blueprint, h2_convert_binary_to_factory, h3_convert_binary_to_blueprint,
-		h3_convert_binary_to_factory, h2016_convert_binary_to_blueprint, h2016_convert_binary_to_factory
+		SwitchGroup, convert_async, convert_generic, convert_generic_async, h2_convert_binary_to_blueprint,
+		h2_convert_binary_to_factory, h3_convert_binary_to_blueprint, h3_convert_binary_to_factory,
+		h2016_convert_binary_to_blueprint, h2016_convert_binary_to_factory
+	},
+	rpkg::{
+		extract_changelog_versions, extract_entity_async, extract_latest_metadata, extract_latest_overview_info,
+		extract_latest_resource, extract_resource_changelog, get_resource_dlc
 	},
-	rpkg::{extract_entity, extract_latest_overview_info, extract_latest_resource, extract_resource_changelog},
-	send_notification, send_request, start_task
+	scaleform::{parse_gfxf, repack_gfxf},
+	send_notification, send_request,
+	soundbank::{get_switch_media, read_event_media},
+	start_task,
+	telemetry::track_event,
+	waveform::get_waveform,
+	workspace_trust::refuse_if_untrusted
 };
 
+/// The number of lines sent at a time when previewing converted resources too large to preview in full.
+const PREVIEW_PAGE_LINES: usize = 2000;
+
+/// The maximum number of matching lines returned when searching a converted resource.
+const PREVIEW_SEARCH_LIMIT: usize = 1000;
+
+/// Convert a ResourceLib resource to indented JSON on the ResourceLib conversion pool.
+#[try_fn]
+#[context("Couldn't convert resource to JSON")]
+pub async fn get_generic_rl_json(
+	game_files: &PartitionManager,
+	game_version: GameVersion,
+	hash: RuntimeID
+) -> Result<String> {
+	let (res_meta, res_data) = extract_latest_resource(game_files, hash)?;
+
+	let resource_type = if res_meta.core_info.resource_type == "WSWB" {
+		"DSWB".try_into()?
+	} else {
+		res_meta.core_info.resource_type
+	};
+
+	convert_async(move || {
+		let mut buf = Vec::new();
+		let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+		let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+		convert_generic::<Value>(&res_data, game_version, resource_type)?.serialize(&mut ser)?;
+
+		Ok(String::from_utf8(buf)?)
+	})
+	.await?
+}
+
+/// Keep the JSON of a resource previewed with ResourceLib in its editor, or clear it.
+fn cache_generic_rl_json(app_state: &AppState, id: Uuid, json: Option<Arc<String>>) {
+	if let Some(mut editor_state) = app_state.editor_states.get_mut(&id)
+		&& let EditorData::ResourceOverview {
+			ref mut generic_rl_json,
+			..
+		} = editor_state.data
+	{
+		*generic_rl_json = json;
+	}
+}
+
+/// Get the JSON of a resource previewed with ResourceLib, only converting it if the editor hasn't kept it already.
+#[try_fn]
+#[context("Couldn't get JSON of resource overview {}", id)]
+async fn get_cached_generic_rl_json(app: &AppHandle, id: Uuid) -> Result<Option<Arc<String>>> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+	let app_state = app.state::<AppState>();
+
+	let (hash, cached) = {
+		let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+		match editor_state.data {
+			EditorData::ResourceOverview {
+				hash,
+				ref generic_rl_json
+			} => (hash, generic_rl_json.to_owned()),
+
+			_ => {
+				Err(anyhow!("Editor {} is not a resource overview", id))?;
+				panic!();
+			}
+		}
+	};
+
+	if cached.is_some() {
+		return Ok(cached);
+	}
+
+	if let Some(game_files) = app_state.game_files.load().as_ref()
+		&& let Some(install) = app_settings.load().game_install.as_ref()
+	{
+		let json = Arc::new(get_generic_rl_json(game_files, get_loaded_game_version(app, install)?, hash).await?);
+
+		cache_generic_rl_json(&app_state, id, Some(json.to_owned()));
+
+		Some(json)
+	} else {
+		None
+	}
+}
+
+/// Convert a copy of a resource to indented JSON so that it can be compared with another copy. JSON resources are used
+/// as they are, ORES files are converted to their JSON form and anything else is converted with ResourceLib.
+#[try_fn]
+#[context("Couldn't convert copy of resource to JSON")]
+fn get_version_json(data: &[u8], game_version: GameVersion, resource_type: ResourceType) -> Result<String> {
+	let value = match resource_type.as_ref() {
+		"JSON" | "REPO" => serde_json::from_slice::<Value>(data)?,
+		"ORES" => serde_json::from_str::<Value>(&parse_json_ores(data)?)?,
+		"WSWB" => convert_generic::<Value>(data, game_version, "DSWB".try_into()?)?,
+		_ => convert_generic::<Value>(data, game_version, resource_type)?
+	};
+
+	let mut buf = Vec::new();
+	let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+	let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+	value.serialize(&mut ser)?;
+
+	String::from_utf8(buf)?
+}
+
+/// The mean difference per channel, out of 255, above which a texture's mips are taken to have different contents.
+const MIP_DIFFERENCE_THRESHOLD: f64 = 40.0;
+
+fn render_format_name(format: RenderFormat) -> &'static str {
+	match format {
+		RenderFormat::R16G16B16A16 => "R16G16B16A16",
+		RenderFormat::R8G8B8A8 => "R8G8B8A8",
+		RenderFormat::R8G8 => "R8G8",
+		RenderFormat::A8 => "A8",
+		RenderFormat::BC1 => "BC1",
+		RenderFormat::BC2 => "BC2",
+		RenderFormat::BC3 => "BC3",
+		RenderFormat::BC4 => "BC4",
+		RenderFormat::BC5 => "BC5",
+		RenderFormat::BC7 => "BC7"
+	}
+}
+
+#[try_fn]
+#[context("Couldn't convert texture to image")]
+fn texture_to_image(texture: &TextureMap) -> Result<DynamicImage> {
+	let tga_data = glacier_texture::convert::create_tga(texture).context("Couldn't convert texture to TGA")?;
+
+	let mut reader = ImageReader::new(Cursor::new(tga_data));
+	reader.set_format(ImageFormat::Tga);
+
+	reader.decode()?
+}
+
+/// Decode a TEXT, using the high mip from its TEXD if given, and save it as a PNG.
+#[try_fn]
+#[context("Couldn't render texture")]
+fn render_texture(
+	game_version: GameVersion,
+	text_data: Vec<u8>,
+	texd_data: Option<Vec<u8>>,
+	path: &Path
+) -> Result<TextureMap> {
+	let mut texture =
+		TextureMap::process_data(game_version.into(), text_data).context("Couldn't process texture data")?;
+
+	if let Some(texd_data) = texd_data {
+		let mipblock =
+			MipblockData::from_memory(&texd_data, game_version.into()).context("Couldn't process TEXD data")?;
+		texture.set_mipblock1(mipblock);
+	}
+
+	texture_to_image(&texture)?.save(path)?;
+
+	texture
+}
+
+/// Find a resource file in the project, named by its hash and type like `00123456789ABCDE.TEXT`.
+fn find_project_resource(project_path: &Path, hash: RuntimeID, resource_type: &str) -> Option<PathBuf> {
+	let name = format!("{}.{}", hash, resource_type).to_lowercase();
+
+	WalkDir::new(project_path)
+		.into_iter()
+		.filter_entry(|x| x.file_name() != ".git" && x.file_name() != DIST_FOLDER)
+		.filter_map(|x| x.ok())
+		.filter(|x| x.file_type().is_file())
+		.find(|x| x.file_name().to_string_lossy().to_lowercase() == name)
+		.map(|x| x.into_path())
+}
+
+/// Render the project's replacement of an image resource the same way as the original, so the two can be compared. A
+/// replacement TEXT uses the project's replacement of its TEXD if there is one, and the original TEXD otherwise.
+#[try_fn]
+#[context("Couldn't render project replacement of {}", hash)]
+fn render_project_replacement(
+	game_files: &PartitionManager,
+	game_version: GameVersion,
+	project_path: &Path,
+	hash: RuntimeID,
+	resource_type: &str,
+	texd: Option<RuntimeID>,
+	path: &Path
+) -> Result<Option<PathBuf>> {
+	let Some(replacement) = find_project_resource(project_path, hash, resource_type) else {
+		return Ok(None);
+	};
+
+	let data = fs::read(&replacement).context("Couldn't read replacement")?;
+
+	if resource_type == "TEXT" {
+		let texd_data = if let Some(texd) = texd {
+			if let Some(texd_replacement) = find_project_resource(project_path, texd, "TEXD") {
+				Some(fs::read(texd_replacement).context("Couldn't read TEXD replacement")?)
+			} else {
+				Some(extract_latest_resource(game_files, texd)?.1)
+			}
+		} else {
+			None
+		};
+
+		render_texture(game_version, data, texd_data, path)?;
+	} else {
+		ImageReader::new(Cursor::new(data))
+			.with_guessed_format()?
+			.decode()?
+			.save(path)?;
+	}
+
+	Some(path.to_owned())
+}
+
+/// Render the loaded project's replacement of an image resource into the temp folder, if it has one. A replacement which
+/// can't be rendered is warned about rather than stopping the original from being shown.
+#[try_fn]
+#[context("Couldn't preview project replacement of {}", hash)]
+fn preview_project_replacement(
+	app: &AppHandle,
+	game_files: &PartitionManager,
+	game_version: GameVersion,
+	hash: RuntimeID,
+	resource_type: &str,
+	texd: Option<RuntimeID>
+) -> Result<Option<PathBuf>> {
+	let app_state = app.state::<AppState>();
+
+	let Some(project) = app_state.project.load_full() else {
+		return Ok(None);
+	};
+
+	let path = app
+		.path()
+		.app_data_dir()
+		.context("Couldn't get data dir")?
+		.join("temp")
+		.join(format!("{}.png", Uuid::new_v4()));
+
+	match render_project_replacement(
+		game_files,
+		game_version,
+		&project.path,
+		hash,
+		resource_type,
+		texd,
+		&path
+	) {
+		Ok(path) => path,
+
+		Err(e) => {
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Warning,
+					title: "Couldn't preview replacement".into(),
+					subtitle: format!("The project's replacement for {} couldn't be rendered: {:?}", hash, e),
+					link: None
+				}
+			)?;
+
+			None
+		}
+	}
+}
+
+/// Render the low mip of a TEXT by itself and with the high mip from its TEXD, saving both to the given folder, and
+/// check whether they look like the same texture.
+#[try_fn]
+#[context("Couldn't compare texture mips of {}", hash)]
+pub fn compare_texture_mips(
+	game_files: &PartitionManager,
+	game_version: GameVersion,
+	hash: RuntimeID,
+	temp_dir: &Path
+) -> Result<TextureMipComparison> {
+	let (res_meta, res_data) = extract_latest_resource(game_files, hash)?;
+
+	let low_texture =
+		TextureMap::process_data(game_version.into(), res_data.to_owned()).context("Couldn't process texture data")?;
+
+	let format = render_format_name(low_texture.format());
+
+	let low_image = texture_to_image(&low_texture)?;
+	let low_image_path = temp_dir.join(format!("{}.png", Uuid::new_v4()));
+	low_image.save(&low_image_path)?;
+
+	let mut comparison = TextureMipComparison {
+		format: format.into(),
+		low_image_path,
+		low_resolution: (low_image.width(), low_image.height()),
+		texd: None,
+		high_image_path: None,
+		high_resolution: None,
+		warnings: vec![]
+	};
+
+	let Some(texd) = res_meta.core_info.references.first().map(|x| x.resource.get_id()) else {
+		return Ok(comparison);
+	};
+
+	comparison.texd = Some(texd.to_string());
+
+	let high_image: Result<DynamicImage> = try {
+		let (_, texd_data) = extract_latest_resource(game_files, texd)?;
+
+		let mut texture =
+			TextureMap::process_data(game_version.into(), res_data).context("Couldn't process texture data")?;
+
+		texture.set_mipblock1(
+			MipblockData::from_memory(&texd_data, game_version.into()).context("Couldn't process TEXD data")?
+		);
+
+		texture_to_image(&texture)?
+	};
+
+	let high_image = match high_image {
+		Ok(x) => x,
+
+		Err(err) => {
+			comparison.warnings.push(format!(
+				"The TEXD couldn't be read using the TEXT's format ({format}), so it was probably made for a \
+				 different format: {err:#}"
+			));
+
+			return Ok(comparison);
+		}
+	};
+
+	let high_image_path = temp_dir.join(format!("{}.png", Uuid::new_v4()));
+	high_image.save(&high_image_path)?;
+
+	comparison.high_image_path = Some(high_image_path);
+	comparison.high_resolution = Some((high_image.width(), high_image.height()));
+
+	if high_image.width() <= low_image.width() && high_image.height() <= low_image.height() {
+		comparison
+			.warnings
+			.push("The high mip isn't larger than the low mip.".into());
+	}
+
+	let low_ratio = low_image.width() as f64 / low_image.height().max(1) as f64;
+	let high_ratio = high_image.width() as f64 / high_image.height().max(1) as f64;
+
+	if (low_ratio - high_ratio).abs() > low_ratio * 0.01 {
+		comparison
+			.warnings
+			.push("The low and high mips have different aspect ratios.".into());
+	} else {
+		let low_pixels = low_image.to_rgba8();
+		let high_pixels = high_image
+			.resize_exact(low_image.width(), low_image.height(), FilterType::Triangle)
+			.to_rgba8();
+
+		let difference = low_pixels
+			.as_raw()
+			.iter()
+			.zip(high_pixels.as_raw())
+			.map(|(x, y)| x.abs_diff(*y) as u64)
+			.sum::<u64>() as f64
+			/ low_pixels.as_raw().len().max(1) as f64;
+
+		if difference > MIP_DIFFERENCE_THRESHOLD {
+			comparison.warnings.push(format!(
+				"The high mip looks very different from the low mip (a mean difference of {difference:.0} out of \
+				 255), which usually means the TEXD was made for a different format than {format}."
+			));
+		}
+	}
+
+	comparison
+}
+
+/// Get the switch groups a Wwise event depends on. If the event's data is given, each switch is resolved to the indices
+/// of the audio objects it plays through the switch containers for its group in the event's soundbanks.
+#[try_fn]
+#[context("Couldn't get switch groups of Wwise event")]
+pub fn get_wwev_switch_groups(
+	game_files: &PartitionManager,
+	hash_list: &HashList,
+	game_version: GameVersion,
+	references: &[RuntimeID],
+	event_data: Option<&[u8]>
+) -> Result<Vec<WwiseSwitchGroup>> {
+	let (soundbank, object_media) = match event_data {
+		Some(event_data) => read_event_media(event_data).context("Couldn't read WWEV")?,
+		None => (&[][..], vec![])
+	};
+
+	let mut banks = vec![soundbank.to_owned()];
+
+	if event_data.is_some() {
+		for reference in references {
+			if hash_list
+				.entries
+				.get(reference)
+				.is_some_and(|x| x.resource_type == "WBNK")
+			{
+				banks.push(extract_latest_resource(game_files, *reference)?.1);
+			}
+		}
+	}
+
+	let banks = banks.iter().map(|x| x.as_slice()).collect::<Vec<_>>();
+
+	let mut switch_groups = vec![];
+
+	for reference in references {
+		let Some(entry) = hash_list.entries.get(reference) else {
+			continue;
+		};
+
+		if entry.resource_type != "DSWB" && entry.resource_type != "WSWB" && entry.resource_type != "WSGB" {
+			continue;
+		}
+
+		let (res_meta, res_data) = extract_latest_resource(game_files, *reference)?;
+
+		let switch_group = convert_generic::<SwitchGroup>(
+			&res_data,
+			game_version,
+			if res_meta.core_info.resource_type == "WSWB" {
+				"DSWB".try_into()?
+			} else {
+				res_meta.core_info.resource_type
+			}
+		)?;
+
+		let path = hash_list.to_path(reference);
+
+		let name = path
+			.split('/')
+			.next_back()
+			.and_then(|x| x.split('.').next())
+			.map(|x| x.to_owned())
+			.unwrap_or(path.to_owned());
+
+		let switch_ids = switch_group
+			.m_aSwitches
+			.iter()
+			.map(|x| wwise_hash(x))
+			.collect::<Vec<_>>();
+		let switch_media = get_switch_media(&banks, wwise_hash(&name), &switch_ids);
+
+		switch_groups.push(WwiseSwitchGroup {
+			name,
+			switches: switch_group
+				.m_aSwitches
+				.into_iter()
+				.map(|switch| {
+					let objects = switch_media
+						.get(&wwise_hash(&switch))
+						.map(|media| {
+							object_media
+								.iter()
+								.enumerate()
+								.filter(|(_, media_id)| media.contains(*media_id))
+								.map(|(index, _)| index as u32)
+								.collect()
+						})
+						.unwrap_or_default();
+
+					(switch, objects)
+				})
+				.collect()
+		});
+	}
+
+	switch_groups
+}
+
+/// Convert an image to replace a GFXI with, resizing it to the original's dimensions and encoding it in the original's
+/// format.
+#[try_fn]
+#[context("Couldn't convert image to GFXI")]
+pub fn image_to_gfxi(original: &[u8], image: &[u8]) -> Result<Vec<u8>> {
+	let reader = ImageReader::new(Cursor::new(original)).with_guessed_format()?;
+	let format = reader.format().context("Couldn't get format of original image")?;
+	let original = reader.decode()?;
+
+	let mut image = ImageReader::new(Cursor::new(image)).with_guessed_format()?.decode()?;
+
+	if image.width() != original.width() || image.height() != original.height() {
+		image = image.resize_exact(original.width(), original.height(), FilterType::Lanczos3);
+	}
+
+	// JPEGs can't have an alpha channel, and PNGs shouldn't gain one that the original didn't have
+	let image = match format {
+		ImageFormat::Png if original.color().has_alpha() => DynamicImage::ImageRgba8(image.to_rgba8()),
+		ImageFormat::Png | ImageFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8()),
+		_ => bail!("Replacing {:?} images is not supported", format)
+	};
+
+	let mut data = Cursor::new(vec![]);
+	image.write_to(&mut data, format)?;
+
+	data.into_inner()
+}
+
+/// Convert a PRIM to an OBJ of its preferred LOD, returning the OBJ and its bounding box.
+#[try_fn]
+#[context("Couldn't convert PRIM to OBJ")]
+pub fn prim_to_obj(game_version: GameVersion, res_data: Vec<u8>) -> Result<(String, [f32; 6], Vec<Vec<u32>>)> {
+	let model =
+		RenderPrimitive::process_data(game_version.into(), res_data).context("Couldn't process texture data")?;
+
+	// Higher is less detail
+	let preferred_lod = 1;
+
+	// Get only the meshes and the bones which weighted meshes use
+	let meshes = model
+		.data
+		.objects
+		.iter()
+		.map(|mesh_obj| match mesh_obj {
+			prim_rs::render_primitive::MeshObject::Normal(mesh) => (mesh, None),
+			prim_rs::render_primitive::MeshObject::Weighted(mesh) => {
+				(&mesh.prim_mesh, Some(&mesh.bone_indices.elements))
+			}
+			prim_rs::render_primitive::MeshObject::Linked(mesh) => (&mesh.prim_mesh, None)
+		})
+		.collect::<Vec<_>>();
+
+	// Get only the meshes for the preferred LOD level
+	let meshes = meshes
+		.iter()
+		.filter(|(mesh, _)| mesh.prim_object.lod_mask & (1 << preferred_lod) == (1 << preferred_lod));
+
+	let mut previous_vertex_count: usize = 1;
+	let mut bounding_box: [f32; 6] = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+	let mut bone_influences = vec![];
+
+	let mut obj = String::new();
+
+	for (idx, (mesh, bone_indices)) in meshes.enumerate() {
+		bone_influences.push(
+			bone_indices
+				.map(|x| x.iter().map(|&x| x as u32).unique().sorted().collect())
+				.unwrap_or_default()
+		);
+
+		writeln!(obj, "o object.00{}", idx)?;
+
+		for position in &mesh.sub_mesh.buffers.position {
+			writeln!(obj, "v {} {} {}", position.x, position.y, position.z)?;
+		}
+
+		for vm in &mesh.sub_mesh.buffers.main {
+			writeln!(obj, "vn {} {} {}", vm.normal.x, vm.normal.y, vm.normal.z)?;
+		}
+
+		for idx in mesh.sub_mesh.indices.chunks(3) {
+			let [idx1, idx2, idx3] = [
+				idx[0] as usize + previous_vertex_count,
+				idx[1] as usize + previous_vertex_count,
+				idx[2] as usize + previous_vertex_count
+			];
+			writeln!(obj, "f {}//{} {}//{} {}//{}", idx1, idx1, idx2, idx2, idx3, idx3)?;
+		}
+
+		previous_vertex_count += mesh.sub_mesh.buffers.position.len();
+
+		let bb = mesh.sub_mesh.calc_bb();
+
+		bounding_box[0] = bounding_box[0].min(bb.min.x);
+		bounding_box[1] = bounding_box[1].min(bb.min.y);
+		bounding_box[2] = bounding_box[2].min(bb.min.z);
+
+		bounding_box[3] = bounding_box[3].max(bb.max.x);
+		bounding_box[4] = bounding_box[4].max(bb.max.y);
+		bounding_box[5] = bounding_box[5].max(bb.max.z);
+	}
+
+	(obj, bounding_box, bone_influences)
+}
+
+/// Get the skeleton of a weighted PRIM from the BORG it depends on, or an empty list if it doesn't depend on one.
+#[try_fn]
+#[context("Couldn't get skeleton of PRIM")]
+fn get_prim_skeleton(game_files: &PartitionManager, res_meta: &ExtendedResourceMetadata) -> Result<Vec<Bone>> {
+	let bone_rig = res_meta
+		.core_info
+		.references
+		.iter()
+		.map(|x| x.resource.get_id())
+		.find(|x| extract_latest_metadata(game_files, *x).is_ok_and(|x| x.core_info.resource_type == "BORG"));
+
+	if let Some(bone_rig) = bone_rig {
+		let (_, bone_rig_data) = extract_latest_resource(game_files, bone_rig)?;
+		parse_bone_rig(&bone_rig_data)?
+	} else {
+		vec![]
+	}
+}
+
+/// Resource types which make up animations: Morpheme networks, rigs and animation data, facial animation sets and bone
+/// rigs.
+const ANIMATION_RESOURCE_TYPES: [&str; 5] = ["MRTN", "MRTR", "MJBA", "FXAS", "BORG"];
+
+/// Find the animation resources and rigs an animation resource depends on, directly or through other animation
+/// resources.
+#[try_fn]
+#[context("Couldn't get animation dependencies of {}", hash)]
+fn get_animation_dependencies(
+	game_files: &PartitionManager,
+	hash_list: &HashList,
+	hash: RuntimeID
+) -> Result<Vec<AnimationDependency>> {
+	let mut dependencies = vec![];
+	let mut seen = HashSet::from([hash]);
+	let mut queue = vec![hash];
+
+	while let Some(current) = queue.pop() {
+		for reference in extract_latest_metadata(game_files, current)?.core_info.references {
+			let reference = reference.resource.get_id();
+
+			if !seen.insert(reference) {
+				continue;
+			}
+
+			let Ok(metadata) = extract_latest_metadata(game_files, reference) else {
+				continue;
+			};
+
+			let resource_type = metadata.core_info.resource_type.to_string();
+
+			if !ANIMATION_RESOURCE_TYPES.contains(&resource_type.as_str()) {
+				continue;
+			}
+
+			let bone_count = if resource_type == "BORG" {
+				extract_latest_resource(game_files, reference)
+					.and_then(|(_, data)| parse_bone_rig(&data))
+					.ok()
+					.map(|x| x.len() as u32)
+			} else {
+				None
+			};
+
+			dependencies.push(AnimationDependency {
+				hash: reference,
+				path: hash_list
+					.entries
+					.get(&reference)
+					.and_then(|x| x.path.as_ref().or(x.hint.as_ref()).cloned()),
+				resource_type,
+				bone_count
+			});
+
+			queue.push(reference);
+		}
+	}
+
+	dependencies.sort_by(|a, b| a.resource_type.cmp(&b.resource_type).then_with(|| a.path.cmp(&b.path)));
+
+	dependencies
+}
+
+/// Get the entity names, subsets and pin forwardings of a ResourceLib TBLU.
+#[try_fn]
+#[context("Couldn't get blueprint overview")]
+fn get_blueprint_overview(
+	blueprint: &Value,
+	deps: &[(RuntimeID, String)]
+) -> Result<(
+	Vec<BlueprintEntityInfo>,
+	Vec<BlueprintSubsetInfo>,
+	Vec<BlueprintPinForwarding>
+)> {
+	let sub_entities = blueprint
+		.get("subEntities")
+		.and_then(|x| x.as_array())
+		.context("Blueprint has no subEntities")?;
+
+	let entities = sub_entities
+		.iter()
+		.map(|sub_entity| BlueprintEntityInfo {
+			id: format!(
+				"{:0>16x}",
+				sub_entity.get("entityId").and_then(|x| x.as_u64()).unwrap_or_default()
+			),
+			name: sub_entity
+				.get("entityName")
+				.and_then(|x| x.as_str())
+				.unwrap_or_default()
+				.into(),
+			factory: sub_entity
+				.get("entityTypeResourceIndex")
+				.and_then(|x| x.as_u64())
+				.and_then(|x| deps.get(x as usize))
+				.map(|(hash, _)| *hash)
+		})
+		.collect::<Vec<_>>();
+
+	let entity_name = |index: Option<&Value>| {
+		index
+			.and_then(|x| x.as_i64())
+			.and_then(|x| entities.get(usize::try_from(x).ok()?))
+			.map(|x| format!("{} ({})", x.name, x.id))
+			.unwrap_or_else(|| "<none>".into())
+	};
+
+	let mut subsets = vec![];
+
+	for (sub_entity, info) in sub_entities.iter().zip(entities.iter()) {
+		// Subsets are stored as (name, subset) pairs
+		for subset in sub_entity
+			.get("entitySubsets")
+			.and_then(|x| x.as_array())
+			.map(|x| x.as_slice())
+			.unwrap_or_default()
+		{
+			subsets.push(BlueprintSubsetInfo {
+				owner: format!("{} ({})", info.name, info.id),
+				name: subset.get(0).and_then(|x| x.as_str()).unwrap_or_default().into(),
+				entities: subset
+					.get(1)
+					.and_then(|x| x.get("entities"))
+					.and_then(|x| x.as_array())
+					.map(|x| x.iter().map(|x| entity_name(Some(x))).collect())
+					.unwrap_or_default()
+			});
+		}
+	}
+
+	let mut pin_forwardings = vec![];
+
+	for (key, input) in [("inputPinForwardings", true), ("outputPinForwardings", false)] {
+		for forwarding in blueprint
+			.get(key)
+			.and_then(|x| x.as_array())
+			.map(|x| x.as_slice())
+			.unwrap_or_default()
+		{
+			pin_forwardings.push(BlueprintPinForwarding {
+				input,
+				from_entity: entity_name(forwarding.get("fromID")),
+				from_pin: forwarding
+					.get("fromPinName")
+					.and_then(|x| x.as_str())
+					.unwrap_or_default()
+					.into(),
+				to_entity: entity_name(forwarding.get("toID")),
+				to_pin: forwarding
+					.get("toPinName")
+					.and_then(|x| x.as_str())
+					.unwrap_or_default()
+					.into()
+			});
+		}
+	}
+
+	(entities, subsets, pin_forwardings)
+}
+
 #[try_fn]
 #[context("Couldn't initialise resource overview {id}")]
 pub async fn initialise_resource_overview(
@@ -64,6 +859,20 @@ pub async fn initialise_resource_overview(
 ) -> Result<()> {
 	let (filetype, chunk_patch, deps) = extract_latest_overview_info(game_files, hash)?;
 
+	// The editor may have been showing a different resource before
+	cache_generic_rl_json(app_state, id, None);
+
+	// The flags in the overview info are in whichever format the game uses, so they're decoded from the metadata's
+	// modern form instead
+	let decoded_flags = extract_latest_metadata(game_files, hash)?
+		.core_info
+		.references
+		.iter()
+		.map(|x| decode_reference_flag(x.flags.as_modern()).ok())
+		.collect::<Vec<_>>();
+
+	let capabilities = get_resource_type_capabilities(game_version, filetype.as_ref())?;
+
 	send_request(
 		app,
 		Request::Editor(EditorRequest::ResourceOverview(ResourceOverviewRequest::Initialise {
@@ -71,13 +880,15 @@ pub async fn initialise_resource_overview(
 			hash: hash.to_string(),
 			filetype: filetype.into(),
 			chunk_patch,
+			dlc: get_resource_dlc(game_files, hash),
 			path_or_hint: hash_list
 				.entries
 				.get(&hash)
 				.and_then(|x| x.path.as_ref().or(x.hint.as_ref()).cloned()),
 			dependencies: deps
 				.par_iter()
-				.map(|(hash, flag)| {
+				.enumerate()
+				.map(|(index, (hash, flag))| {
 					(
 						hash.to_string(),
 						hash_list
@@ -90,7 +901,8 @@ pub async fn initialise_resource_overview(
 							.get(hash)
 							.and_then(|x| x.path.as_ref().or(x.hint.as_ref()).cloned()),
 						flag.to_owned(),
-						resource_reverse_dependencies.contains_key(hash)
+						resource_reverse_dependencies.contains_key(hash),
+						decoded_flags.get(index).copied().flatten()
 					)
 				})
 				.collect(),
@@ -117,9 +929,29 @@ pub async fn initialise_resource_overview(
 				})
 				.unwrap_or_default(),
 			changelog: extract_resource_changelog(game_files, hash),
+			external_tools: app
+				.state::<ArcSwap<AppSettings>>()
+				.load()
+				.external_tools
+				.iter()
+				.map(|x| x.name.to_owned())
+				.collect(),
+			extraction_presets: app
+				.state::<ArcSwap<AppSettings>>()
+				.load()
+				.extraction_presets
+				.iter()
+				.map(|x| x.name.to_owned())
+				.collect(),
+			capabilities: capabilities.to_owned(),
 			data: match filetype.as_ref() {
+				// Types which can't be read in this game version are shown like any other binary data
+				_ if !capabilities.preview => ResourceOverviewData::Generic,
+
 				"TEMP" => {
-					let entity = extract_entity(game_files, &app_state.cached_entities, game_version, hash_list, hash)?;
+					let entity =
+						extract_entity_async(game_files, &app_state.cached_entities, game_version, hash_list, hash)
+							.await?;
 
 					ResourceOverviewData::Entity {
 						blueprint_hash: entity.blueprint_hash.to_owned(),
@@ -130,36 +962,73 @@ pub async fn initialise_resource_overview(
 					}
 				}
 
-				"AIRG" | "TBLU" | "ATMD" | "CPPT" | "VIDB" | "CBLU" | "CRMD" | "WSWB" | "DSWB" | "GFXF" | "GIDX"
-				| "WSGB" | "ECPB" | "UICB" | "ENUM" => {
-					let (res_meta, res_data) = extract_latest_resource(game_files, hash)?;
+				"TBLU" => {
+					let (_, res_data) = extract_latest_resource(game_files, hash)?;
 
-					ResourceOverviewData::GenericRL {
+					let blueprint = convert_generic_async::<Value>(res_data, game_version, "TBLU".try_into()?).await?;
+
+					let (entities, subsets, pin_forwardings) = get_blueprint_overview(&blueprint, &deps)?;
+
+					ResourceOverviewData::Blueprint {
 						json: {
 							let mut buf = Vec::new();
 							let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
 							let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
 
-							convert_generic::<Value>(
-								&res_data,
-								game_version,
-								if res_meta.core_info.resource_type == "WSWB" {
-									"DSWB".try_into()?
-								} else {
-									res_meta.core_info.resource_type
-								}
-							)?
-							.serialize(&mut ser)?;
+							blueprint.serialize(&mut ser)?;
 
 							if buf.len() < 1024 * 512 {
 								String::from_utf8(buf)?
 							} else {
 								"Too large to preview".into()
 							}
+						},
+						factories: resource_reverse_dependencies
+							.get(&hash)
+							.map(|x| x.as_slice())
+							.unwrap_or_default()
+							.iter()
+							.filter_map(|x| hash_list.entries.get(x).map(|entry| (x, entry)))
+							.filter(|(_, entry)| entry.resource_type == "TEMP")
+							.map(|(x, entry)| (*x, entry.path.as_ref().or(entry.hint.as_ref()).cloned()))
+							.collect(),
+						entities,
+						subsets,
+						pin_forwardings
+					}
+				}
+
+				"AIRG" | "ATMD" | "CPPT" | "VIDB" | "CBLU" | "CRMD" | "WSWB" | "DSWB" | "GIDX" | "WSGB" | "ECPB"
+				| "UICB" | "ENUM" => {
+					let json = Arc::new(get_generic_rl_json(game_files, game_version, hash).await?);
+
+					cache_generic_rl_json(app_state, id, Some(json.to_owned()));
+
+					if json.len() < 1024 * 512 {
+						ResourceOverviewData::GenericRL {
+							json: json.to_string(),
+							total_lines: None
+						}
+					} else {
+						ResourceOverviewData::GenericRL {
+							json: json.lines().take(PREVIEW_PAGE_LINES).collect::<Vec<_>>().join("\n"),
+							total_lines: Some(json.lines().count() as u32)
 						}
 					}
 				}
 
+				"AIBB" => ResourceOverviewData::BehaviourTree {
+					tree: BehaviourTreeNode::from_json(
+						hash.to_string(),
+						&convert_generic_async::<Value>(
+							extract_latest_resource(game_files, hash)?.1,
+							game_version,
+							"AIBB".try_into()?
+						)
+						.await?
+					)
+				},
+
 				"ORES" if hash == "0057C2C3941115CA".parse()? => ResourceOverviewData::Unlockables,
 
 				"ORES" => ResourceOverviewData::Ores {
@@ -192,75 +1061,84 @@ pub async fn initialise_resource_overview(
 
 					ResourceOverviewData::Image {
 						image_path: data_dir.join("temp").join(format!("{}.png", temp_file_id)),
-						dds_data: None
+						dds_data: None,
+						modded_image_path: preview_project_replacement(
+							app,
+							game_files,
+							game_version,
+							hash,
+							"GFXI",
+							None
+						)?
 					}
 				}
 
 				"PRIM" => {
-					let (_, res_data) = extract_latest_resource(game_files, hash)?;
-
-					let model = RenderPrimitive::process_data(game_version.into(), res_data)
-						.context("Couldn't process texture data")?;
-
-					// Higher is less detail
-					let preferred_lod = 1;
+					let (res_meta, res_data) = extract_latest_resource(game_files, hash)?;
 
-					// Get only the meshes, we don't need weight metadata for the preview
-					let meshes = model
-						.data
-						.objects
-						.iter()
-						.map(|mesh_obj| match mesh_obj {
-							prim_rs::render_primitive::MeshObject::Normal(mesh) => mesh,
-							prim_rs::render_primitive::MeshObject::Weighted(mesh) => &mesh.prim_mesh,
-							prim_rs::render_primitive::MeshObject::Linked(mesh) => &mesh.prim_mesh
-						})
-						.collect::<Vec<_>>();
+					let (obj, bounding_box, bone_influences) = prim_to_obj(game_version, res_data)?;
 
-					// Get only the meshes for the preferred LOD level
-					let meshes = meshes
-						.iter()
-						.filter(|mesh| mesh.prim_object.lod_mask & (1 << preferred_lod) == (1 << preferred_lod));
+					// The mesh can still be previewed without its skeleton
+					let bones = match get_prim_skeleton(game_files, &res_meta) {
+						Ok(bones) => bones,
 
-					let mut previous_vertex_count: usize = 1;
-					let mut bounding_box: [f32; 6] = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+						Err(e) => {
+							warn!("{:?}", e);
+							vec![]
+						}
+					};
 
-					let mut obj = String::new();
+					ResourceOverviewData::Mesh {
+						obj,
+						bounding_box,
+						bone_influences: if bones.is_empty() { vec![] } else { bone_influences },
+						bones
+					}
+				}
 
-					for (idx, mesh) in meshes.enumerate() {
-						writeln!(obj, "o object.00{}", idx)?;
+				"BORG" => {
+					let (_, res_data) = extract_latest_resource(game_files, hash)?;
 
-						for position in &mesh.sub_mesh.buffers.position {
-							writeln!(obj, "v {} {} {}", position.x, position.y, position.z)?;
-						}
+					ResourceOverviewData::Skeleton {
+						bones: parse_bone_rig(&res_data)?
+					}
+				}
 
-						for vm in &mesh.sub_mesh.buffers.main {
-							writeln!(obj, "vn {} {} {}", vm.normal.x, vm.normal.y, vm.normal.z)?;
-						}
+				"GFXF" => {
+					let (_, res_data) = extract_latest_resource(game_files, hash)?;
+					let resource = parse_gfxf(&res_data, game_version)?;
+
+					ResourceOverviewData::Scaleform {
+						swf_extension: resource.swf_extension().into(),
+						swf_size: resource.swf_data.len() as u32,
+						additional_files: resource
+							.additional_files()
+							.map(|(name, data)| (name.to_owned(), data.len() as u32))
+							.collect()
+					}
+				}
 
-						for idx in mesh.sub_mesh.indices.chunks(3) {
-							let [idx1, idx2, idx3] = [
-								idx[0] as usize + previous_vertex_count,
-								idx[1] as usize + previous_vertex_count,
-								idx[2] as usize + previous_vertex_count
-							];
-							writeln!(obj, "f {}//{} {}//{} {}//{}", idx1, idx1, idx2, idx2, idx3, idx3)?;
-						}
+				// Morpheme's formats can't be parsed, but the rigs they use can
+				"MRTN" | "MRTR" | "MJBA" | "FXAS" => {
+					let (_, res_data) = extract_latest_resource(game_files, hash)?;
 
-						previous_vertex_count += mesh.sub_mesh.buffers.position.len();
+					ResourceOverviewData::Animation {
+						size: res_data.len() as u32,
+						dependencies: get_animation_dependencies(game_files, hash_list, hash)?
+					}
+				}
 
-						let bb = mesh.sub_mesh.calc_bb();
+				"ALOC" => {
+					let (_, res_data) = extract_latest_resource(game_files, hash)?;
 
-						bounding_box[0] = bounding_box[0].min(bb.min.x);
-						bounding_box[1] = bounding_box[1].min(bb.min.y);
-						bounding_box[2] = bounding_box[2].min(bb.min.z);
+					let (obj, bounding_box) = collision_to_obj(&parse_aloc(&res_data)?)?;
 
-						bounding_box[3] = bounding_box[3].max(bb.max.x);
-						bounding_box[4] = bounding_box[4].max(bb.max.y);
-						bounding_box[5] = bounding_box[5].max(bb.max.z);
+					ResourceOverviewData::Mesh {
+						obj,
+						bounding_box,
+						bones: vec![],
+						bone_influences: vec![]
 					}
-
-					ResourceOverviewData::Mesh { obj, bounding_box }
 				}
 
 				"TEXT" => {
@@ -271,26 +1149,16 @@ pub async fn initialise_resource_overview(
 
 					let (res_meta, res_data) = extract_latest_resource(game_files, hash)?;
 
-					let mut texture = TextureMap::process_data(game_version.into(), res_data)
-						.context("Couldn't process texture data")?;
+					let texd = res_meta.core_info.references.first().map(|x| x.resource.get_id());
 
-					if let Some(texd_depend) = res_meta.core_info.references.first() {
-						let (_, texd_data) = extract_latest_resource(game_files, texd_depend.resource.get_id())?;
-						let mipblock = MipblockData::from_memory(&texd_data, game_version.into())
-							.context("Couldn't process TEXD data")?;
-						texture.set_mipblock1(mipblock);
-					}
-
-					let tga_data =
-						glacier_texture::convert::create_tga(&texture).context("Couldn't convert texture to TGA")?;
-
-					let mut reader = ImageReader::new(Cursor::new(tga_data.to_owned()));
-
-					reader.set_format(image::ImageFormat::Tga);
-
-					reader
-						.decode()?
-						.save(data_dir.join("temp").join(format!("{}.png", temp_file_id)))?;
+					let texture = render_texture(
+						game_version,
+						res_data,
+						texd.map(|texd| extract_latest_resource(game_files, texd))
+							.transpose()?
+							.map(|(_, texd_data)| texd_data),
+						&data_dir.join("temp").join(format!("{}.png", temp_file_id))
+					)?;
 
 					ResourceOverviewData::Image {
 						image_path: data_dir.join("temp").join(format!("{}.png", temp_file_id)),
@@ -307,20 +1175,16 @@ pub async fn initialise_resource_overview(
 								TextureType::UNKNOWN512 => "unknown"
 							}
 							.into(),
-							match texture.format() {
-								RenderFormat::R16G16B16A16 => "R16G16B16A16",
-								RenderFormat::R8G8B8A8 => "R8G8B8A8",
-								RenderFormat::R8G8 => "R8G8",
-								RenderFormat::A8 => "A8",
-								RenderFormat::BC1 => "BC1",
-								RenderFormat::BC2 => "BC2",
-								RenderFormat::BC3 => "BC3",
-								RenderFormat::BC4 => "BC4",
-								RenderFormat::BC5 => "BC5",
-								RenderFormat::BC7 => "BC7"
-							}
-							.into()
-						))
+							render_format_name(texture.format()).into()
+						)),
+						modded_image_path: preview_project_replacement(
+							app,
+							game_files,
+							game_version,
+							hash,
+							"TEXT",
+							texd
+						)?
 					}
 				}
 
@@ -357,7 +1221,7 @@ pub async fn initialise_resource_overview(
 						wav_paths.push((
 							"Embedded audio".into(),
 							data_dir.join("temp").join(format!("{}.wav", temp_file_id))
-						))
+						));
 					}
 
 					for object in wwev.streamed {
@@ -392,12 +1256,55 @@ pub async fn initialise_resource_overview(
 						wav_paths.push((
 							wwem_hash.to_string(),
 							data_dir.join("temp").join(format!("{}.wav", temp_file_id))
-						))
+						));
 					}
 
+					// The audio can still be previewed without knowing which of its objects each switch plays
+					let switch_groups = match get_wwev_switch_groups(
+						game_files,
+						hash_list,
+						game_version,
+						&res_meta
+							.core_info
+							.references
+							.iter()
+							.map(|x| x.resource.get_id())
+							.collect::<Vec<_>>(),
+						Some(&res_data)
+					) {
+						Ok(switch_groups) => switch_groups,
+
+						Err(e) => {
+							warn!("{:?}", e);
+							vec![]
+						}
+					};
+
+					// The audio can still be previewed without the names of the objects it uses
+					let wwise_names = match get_audio_names(
+						game_files,
+						hash_list,
+						game_version,
+						&app_state.local_hash_list.load(),
+						hash
+					) {
+						Ok(wwise_names) => wwise_names,
+
+						Err(e) => {
+							warn!("{:?}", e);
+							vec![]
+						}
+					};
+
 					ResourceOverviewData::MultiAudio {
 						name: wwev.name,
-						wav_paths
+						waveforms: wav_paths
+							.iter()
+							.map(|(_, path)| fs::read(path).ok().and_then(|x| get_waveform(&x).ok()))
+							.collect(),
+						wav_paths,
+						switch_groups,
+						wwise_names
 					}
 				}
 
@@ -426,15 +1333,24 @@ pub async fn initialise_resource_overview(
 						.context("VGMStream command failed")?;
 
 					ResourceOverviewData::Audio {
+						waveform: get_waveform(&fs::read(data_dir.join("temp").join(format!("{}.wav", temp_file_id)))?)
+							.ok(),
 						wav_path: data_dir.join("temp").join(format!("{}.wav", temp_file_id))
 					}
 				}
 
 				"REPO" => ResourceOverviewData::Repository,
 
-				"JSON" => ResourceOverviewData::Json {
-					json: format_json(&String::from_utf8(extract_latest_resource(game_files, hash)?.1)?)?
-				},
+				"ASET" => ResourceOverviewData::AssetSet,
+
+				"JSON" => {
+					let json = String::from_utf8(extract_latest_resource(game_files, hash)?.1)?;
+
+					ResourceOverviewData::Json {
+						kind: from_str::<Value>(&json).ok().as_ref().and_then(get_json_resource_kind),
+						json: format_json(&json)?
+					}
+				}
 
 				"CLNG" => ResourceOverviewData::HMLanguages {
 					json: {
@@ -801,6 +1717,20 @@ pub async fn initialise_resource_overview(
 				},
 
 				"SDEF" => ResourceOverviewData::SoundDefinitions {
+					wwise_names: match get_audio_names(
+						game_files,
+						hash_list,
+						game_version,
+						&app_state.local_hash_list.load(),
+						hash
+					) {
+						Ok(wwise_names) => wwise_names,
+
+						Err(e) => {
+							warn!("{:?}", e);
+							vec![]
+						}
+					},
 					json: {
 						let (res_meta, res_data) = extract_latest_resource(game_files, hash)?;
 
@@ -825,6 +1755,95 @@ pub async fn initialise_resource_overview(
 			}
 		}))
 	)?;
+
+	send_project_dependency_flags(app, id, hash)?;
+
+	if filetype == "ASET" {
+		send_asset_set_entries(app, id, hash)?;
+	}
+}
+
+/// Send the constituent resources of an ASET, from its JSON meta file in the project if it has one and otherwise from
+/// the game files.
+#[try_fn]
+#[context("Couldn't send ASET entries for {}", hash)]
+pub fn send_asset_set_entries(app: &AppHandle, id: Uuid, hash: RuntimeID) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let game_files = app_state.game_files.load();
+	let game_files = game_files.as_ref().context("Game files not loaded")?;
+
+	let hash_list = app_state.hash_list.load();
+	let hash_list = hash_list.as_ref().context("No hash list available")?;
+
+	let project_meta = app_state
+		.project
+		.load()
+		.as_ref()
+		.and_then(|project| find_project_meta(&project.path, hash));
+
+	let (hashes, editable) = match project_meta {
+		Some(meta_path) => (
+			read_meta_dependencies(&meta_path)?
+				.into_iter()
+				.map(|(hash, _)| hash)
+				.collect::<Vec<_>>(),
+			true
+		),
+
+		None => (
+			extract_latest_overview_info(game_files, hash)?
+				.2
+				.into_iter()
+				.map(|(hash, _)| hash.to_string())
+				.collect(),
+			false
+		)
+	};
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::ResourceOverview(
+			ResourceOverviewRequest::SetAssetSetEntries {
+				id,
+				entries: hashes
+					.into_iter()
+					.map(|hash| {
+						let entry = RuntimeID::from_any(&hash).ok().and_then(|x| hash_list.entries.get(&x));
+
+						(
+							hash,
+							entry.map(|x| x.resource_type.into()).unwrap_or_default(),
+							entry.and_then(|x| x.path.as_ref().or(x.hint.as_ref()).cloned())
+						)
+					})
+					.collect(),
+				editable
+			}
+		))
+	)?;
+}
+
+/// Send the dependencies of the resource's JSON meta file in the project, if there's a project and it has one.
+#[try_fn]
+#[context("Couldn't send project dependency flags for {}", hash)]
+pub fn send_project_dependency_flags(app: &AppHandle, id: Uuid, hash: RuntimeID) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	if let Some(project) = app_state.project.load().as_ref()
+		&& let Some(meta_path) = find_project_meta(&project.path, hash)
+	{
+		send_request(
+			app,
+			Request::Editor(EditorRequest::ResourceOverview(
+				ResourceOverviewRequest::SetProjectDependencyFlags {
+					id,
+					dependencies: read_meta_dependencies(&meta_path)?,
+					meta_path
+				}
+			))
+		)?;
+	}
 }
 
 #[try_fn]
@@ -835,14 +1854,16 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 
 	match event {
 		ResourceOverviewEvent::Initialise { id } => {
-			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+			let hash = {
+				let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
 
-			let hash = match editor_state.data {
-				EditorData::ResourceOverview { hash, .. } => hash,
+				match editor_state.data {
+					EditorData::ResourceOverview { hash, .. } => hash,
 
-				_ => {
-					Err(anyhow!("Editor {} is not a resource overview", id))?;
-					panic!();
+					_ => {
+						Err(anyhow!("Editor {} is not a resource overview", id))?;
+						panic!();
+					}
 				}
 			};
 
@@ -870,18 +1891,22 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 		}
 
 		ResourceOverviewEvent::FollowDependency { id, new_hash } => {
-			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+			let hash = {
+				let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
 
-			let hash = match editor_state.data {
-				EditorData::ResourceOverview { ref mut hash, .. } => hash,
+				let hash = match editor_state.data {
+					EditorData::ResourceOverview { ref mut hash, .. } => hash,
 
-				_ => {
-					Err(anyhow!("Editor {} is not a resource overview", id))?;
-					panic!();
-				}
-			};
+					_ => {
+						Err(anyhow!("Editor {} is not a resource overview", id))?;
+						panic!();
+					}
+				};
 
-			*hash = RuntimeID::from_any(&new_hash)?;
+				*hash = RuntimeID::from_any(&new_hash)?;
+
+				*hash
+			};
 
 			let task = start_task(app, format!("Loading resource overview for {}", hash))?;
 
@@ -894,7 +1919,7 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 					app,
 					&app_state,
 					id,
-					*hash,
+					hash,
 					game_files,
 					get_loaded_game_version(app, install)?,
 					resource_reverse_dependencies,
@@ -922,7 +1947,8 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				EditorState {
 					file: None,
 					data: EditorData::ResourceOverview {
-						hash: RuntimeID::from_any(&hash)?
+						hash: RuntimeID::from_any(&hash)?,
+						generic_rl_json: None
 					}
 				}
 			);
@@ -972,14 +1998,9 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 			};
 
-			if let Some(game_files) = app_state.game_files.load().as_ref()
+			if let Some(game_files) = app_state.game_files.load_full()
 				&& let Some(hash_list) = app_state.hash_list.load().as_ref()
 			{
-				let (metadata, data) = extract_latest_resource(game_files, hash)?;
-				let metadata_file = RpkgResourceMeta::from_resource_metadata(metadata, false)
-					.to_binary()
-					.context("Couldn't serialise meta file")?;
-
 				let file_type = hash_list
 					.entries
 					.get(&hash)
@@ -998,15 +2019,27 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 					.add_filter(format!("{} file", &file_type), &[file_type.as_ref()])
 					.blocking_save_file()
 				{
-					fs::write(path.as_path().context("Invalid path")?, data)?;
+					app_state.extraction_queue.enqueue(
+						format!("Extract {}.{}", hash, file_type),
+						move |_| async move {
+							let (metadata, data) = extract_latest_resource(&game_files, hash)?;
+							let metadata_file = RpkgResourceMeta::from_resource_metadata(metadata, false)
+								.to_binary()
+								.context("Couldn't serialise meta file")?;
 
-					fs::write(
-						path.as_path()
-							.context("Invalid path")?
-							.parent()
-							.unwrap()
-							.join(format!("{}.{}.meta", hash, file_type)),
-						metadata_file
+							fs::write(path.as_path().context("Invalid path")?, data)?;
+
+							fs::write(
+								path.as_path()
+									.context("Invalid path")?
+									.parent()
+									.unwrap()
+									.join(format!("{}.{}.meta", hash, file_type)),
+								metadata_file
+							)?;
+
+							anyhow::Ok(())
+						}
 					)?;
 				}
 			}
@@ -1024,17 +2057,12 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 			};
 
-			if let Some(game_files) = app_state.game_files.load().as_ref()
+			if let Some(game_files) = app_state.game_files.load_full()
 				&& let Some(install) = app_settings.load().game_install.as_ref()
-				&& let Some(hash_list) = app_state.hash_list.load().as_ref()
+				&& let Some(hash_list) = app_state.hash_list.load_full()
 			{
-				let entity_json = to_vec(&*extract_entity(
-					game_files,
-					&app_state.cached_entities,
-					get_loaded_game_version(app, install)?,
-					hash_list,
-					hash
-				)?)?;
+				let game_version = get_loaded_game_version(app, install)?;
+				let cached_entities = app_state.cached_entities.to_owned();
 
 				let mut dialog = app.dialog().file().set_title("Extract entity");
 
@@ -1046,7 +2074,19 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 					.add_filter("QuickEntity entity", &["entity.json"])
 					.blocking_save_file()
 				{
-					fs::write(path.as_path().context("Invalid path")?, entity_json)?;
+					app_state.extraction_queue.enqueue(
+						format!("Extract {} as QuickEntity", hash),
+						move |_| async move {
+							let entity_json = to_vec(
+								&*extract_entity_async(&game_files, &cached_entities, game_version, &hash_list, hash)
+									.await?
+							)?;
+
+							fs::write(path.as_path().context("Invalid path")?, entity_json)?;
+
+							anyhow::Ok(())
+						}
+					)?;
 				}
 			}
 		}
@@ -1063,28 +2103,10 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 			};
 
-			if let Some(game_files) = app_state.game_files.load().as_ref()
+			if let Some(game_files) = app_state.game_files.load_full()
 				&& let Some(install) = app_settings.load().game_install.as_ref()
 			{
-				let (metadata, data) = extract_latest_resource(game_files, hash)?;
-				let metadata_file = RpkgResourceMeta::from_resource_metadata(metadata, false);
-
-				let data = match get_loaded_game_version(app, install)? {
-					GameVersion::H1 => to_vec(
-						&h2016_convert_binary_to_factory(&data)
-							.context("Couldn't convert binary data to ResourceLib factory")?
-					)?,
-
-					GameVersion::H2 => to_vec(
-						&h2_convert_binary_to_factory(&data)
-							.context("Couldn't convert binary data to ResourceLib factory")?
-					)?,
-
-					GameVersion::H3 => to_vec(
-						&h3_convert_binary_to_factory(&data)
-							.context("Couldn't convert binary data to ResourceLib factory")?
-					)?
-				};
+				let game_version = get_loaded_game_version(app, install)?;
 
 				let mut dialog = app.dialog().file().set_title("Extract file");
 
@@ -1097,14 +2119,45 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 					.add_filter("TEMP.json file", &["TEMP.json"])
 					.blocking_save_file()
 				{
-					fs::write(path.as_path().context("Invalid path")?, data)?;
+					app_state
+						.extraction_queue
+						.enqueue(format!("Extract {}.TEMP.json", hash), move |_| async move {
+							let (metadata, data) = extract_latest_resource(&game_files, hash)?;
+							let metadata_file = RpkgResourceMeta::from_resource_metadata(metadata, false);
+
+							let data = convert_async(move || {
+								Ok(match game_version {
+									GameVersion::H1 => to_vec(
+										&h2016_convert_binary_to_factory(&data)
+											.context("Couldn't convert binary data to ResourceLib factory")?
+									)?,
+
+									GameVersion::H2 => to_vec(
+										&h2_convert_binary_to_factory(&data)
+											.context("Couldn't convert binary data to ResourceLib factory")?
+									)?,
+
+									GameVersion::H3 => to_vec(
+										&h3_convert_binary_to_factory(&data)
+											.context("Couldn't convert binary data to ResourceLib factory")?
+									)?
+								})
+							})
+							.await?;
 
-					fs::write(
-						path.as_path()
-							.context("Invalid path")?
-							.join(format!("{}.{}.meta.json", hash, metadata_file.hash_resource_type)),
-						to_string(&metadata_file).context("Couldn't serialise meta file")?
-					)?;
+							fs::write(path.as_path().context("Invalid path")?, data)?;
+
+							fs::write(
+								path.as_path()
+									.context("Invalid path")?
+									.parent()
+									.unwrap()
+									.join(format!("{}.{}.meta.json", hash, metadata_file.hash_resource_type)),
+								to_string(&metadata_file).context("Couldn't serialise meta file")?
+							)?;
+
+							anyhow::Ok(())
+						})?;
 				}
 			}
 		}
@@ -1121,28 +2174,22 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 			};
 
-			if let Some(game_files) = app_state.game_files.load().as_ref()
+			if let Some(game_files) = app_state.game_files.load_full()
 				&& let Some(install) = app_settings.load().game_install.as_ref()
 				&& let Some(hash_list) = app_state.hash_list.load().as_ref()
 			{
-				let (metadata, data) = extract_latest_resource(
-					game_files,
-					RuntimeID::from_any(
-						&extract_entity(
-							game_files,
-							&app_state.cached_entities,
-							get_loaded_game_version(app, install)?,
-							hash_list,
-							hash
-						)?
-						.blueprint_hash
-					)?
+				let blueprint_hash = RuntimeID::from_any(
+					&extract_entity_async(
+						&game_files,
+						&app_state.cached_entities,
+						get_loaded_game_version(app, install)?,
+						hash_list,
+						hash
+					)
+					.await?
+					.blueprint_hash
 				)?;
 
-				let metadata_file = RpkgResourceMeta::from_resource_metadata(metadata.to_owned(), false)
-					.to_binary()
-					.context("Couldn't serialise meta file")?;
-
 				let mut dialog = app.dialog().file().set_title("Extract file");
 
 				if let Some(project) = app_state.project.load().as_ref() {
@@ -1150,19 +2197,32 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 
 				if let Some(path) = dialog
-					.set_file_name(&format!("{}.TBLU", metadata.core_info.id))
+					.set_file_name(&format!("{}.TBLU", blueprint_hash))
 					.add_filter("TBLU file", &["TBLU"])
 					.blocking_save_file()
 				{
-					fs::write(path.as_path().context("Invalid path")?, data)?;
+					app_state.extraction_queue.enqueue(
+						format!("Extract {}.TBLU", blueprint_hash),
+						move |_| async move {
+							let (metadata, data) = extract_latest_resource(&game_files, blueprint_hash)?;
 
-					fs::write(
-						path.as_path()
-							.context("Invalid path")?
-							.parent()
-							.unwrap()
-							.join(format!("{}.{}.meta", hash, metadata.core_info.resource_type)),
-						metadata_file
+							let metadata_file = RpkgResourceMeta::from_resource_metadata(metadata.to_owned(), false)
+								.to_binary()
+								.context("Couldn't serialise meta file")?;
+
+							fs::write(path.as_path().context("Invalid path")?, data)?;
+
+							fs::write(
+								path.as_path()
+									.context("Invalid path")?
+									.parent()
+									.unwrap()
+									.join(format!("{}.{}.meta", hash, metadata.core_info.resource_type)),
+								metadata_file
+							)?;
+
+							anyhow::Ok(())
+						}
 					)?;
 				}
 			}
@@ -1180,45 +2240,18 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 			};
 
-			if let Some(game_files) = app_state.game_files.load().as_ref()
+			if let Some(game_files) = app_state.game_files.load_full()
 				&& let Some(install) = app_settings.load().game_install.as_ref()
 				&& let Some(hash_list) = app_state.hash_list.load().as_ref()
 			{
 				let game_version = get_loaded_game_version(app, install)?;
 
-				let (metadata, data) = extract_latest_resource(
-					game_files,
-					RuntimeID::from_any(
-						&extract_entity(
-							game_files,
-							&app_state.cached_entities,
-							get_loaded_game_version(app, install)?,
-							hash_list,
-							hash
-						)?
+				let blueprint_hash = RuntimeID::from_any(
+					&extract_entity_async(&game_files, &app_state.cached_entities, game_version, hash_list, hash)
+						.await?
 						.blueprint_hash
-					)?
 				)?;
 
-				let metadata_file = RpkgResourceMeta::from_resource_metadata(metadata.to_owned(), false);
-
-				let data = match game_version {
-					GameVersion::H1 => to_vec(
-						&h2016_convert_binary_to_blueprint(&data)
-							.context("Couldn't convert binary data to ResourceLib blueprint")?
-					)?,
-
-					GameVersion::H2 => to_vec(
-						&h2_convert_binary_to_blueprint(&data)
-							.context("Couldn't convert binary data to ResourceLib blueprint")?
-					)?,
-
-					GameVersion::H3 => to_vec(
-						&h3_convert_binary_to_blueprint(&data)
-							.context("Couldn't convert binary data to ResourceLib blueprint")?
-					)?
-				};
-
 				let mut dialog = app.dialog().file().set_title("Extract file");
 
 				if let Some(project) = app_state.project.load().as_ref() {
@@ -1226,19 +2259,50 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 
 				if let Some(path) = dialog
-					.set_file_name(&format!("{}.TBLU.json", metadata.core_info.id))
+					.set_file_name(&format!("{}.TBLU.json", blueprint_hash))
 					.add_filter("TBLU.json file", &["TBLU.json"])
 					.blocking_save_file()
 				{
-					fs::write(path.as_path().context("Invalid path")?, data)?;
+					app_state.extraction_queue.enqueue(
+						format!("Extract {}.TBLU.json", blueprint_hash),
+						move |_| async move {
+							let (metadata, data) = extract_latest_resource(&game_files, blueprint_hash)?;
+
+							let metadata_file = RpkgResourceMeta::from_resource_metadata(metadata, false);
+
+							let data = convert_async(move || {
+								Ok(match game_version {
+									GameVersion::H1 => to_vec(
+										&h2016_convert_binary_to_blueprint(&data)
+											.context("Couldn't convert binary data to ResourceLib blueprint")?
+									)?,
+
+									GameVersion::H2 => to_vec(
+										&h2_convert_binary_to_blueprint(&data)
+											.context("Couldn't convert binary data to ResourceLib blueprint")?
+									)?,
+
+									GameVersion::H3 => to_vec(
+										&h3_convert_binary_to_blueprint(&data)
+											.context("Couldn't convert binary data to ResourceLib blueprint")?
+									)?
+								})
+							})
+							.await?;
 
-					fs::write(
-						path.as_path()
-							.context("Invalid path")?
-							.parent()
-							.unwrap()
-							.join(format!("{}.{}.meta.json", hash, metadata_file.hash_resource_type)),
-						to_string(&metadata_file).context("Couldn't serialise meta file")?
+							fs::write(path.as_path().context("Invalid path")?, data)?;
+
+							fs::write(
+								path.as_path()
+									.context("Invalid path")?
+									.parent()
+									.unwrap()
+									.join(format!("{}.{}.meta.json", hash, metadata_file.hash_resource_type)),
+								to_string(&metadata_file).context("Couldn't serialise meta file")?
+							)?;
+
+							anyhow::Ok(())
+						}
 					)?;
 				}
 			}
@@ -1256,10 +2320,18 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 			};
 
-			if let Some(game_files) = app_state.game_files.load().as_ref()
+			if let Some(game_files) = app_state.game_files.load_full()
 				&& let Some(install) = app_settings.load().game_install.as_ref()
+				&& let Some(hash_list) = app_state.hash_list.load().as_ref()
 			{
-				let (res_meta, res_data) = extract_latest_resource(game_files, hash)?;
+				let game_version = get_loaded_game_version(app, install)?;
+
+				let file_type = hash_list
+					.entries
+					.get(&hash)
+					.expect("Can only open files from the hash list")
+					.resource_type
+					.to_owned();
 
 				let mut dialog = app.dialog().file().set_title("Extract file");
 
@@ -1268,30 +2340,39 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 
 				if let Some(path) = dialog
-					.set_file_name(&format!("{}.{}.json", hash, res_meta.core_info.resource_type))
-					.add_filter(
-						format!("{}.json file", res_meta.core_info.resource_type),
-						&[&format!("{}.json", res_meta.core_info.resource_type)]
-					)
+					.set_file_name(&format!("{}.{}.json", hash, file_type))
+					.add_filter(format!("{}.json file", file_type), &[&format!("{}.json", file_type)])
 					.blocking_save_file()
 				{
-					fs::write(
-						path.as_path().context("Invalid path")?,
-						to_vec(&convert_generic::<Value>(
-							&res_data,
-							get_loaded_game_version(app, install)?,
-							res_meta.core_info.resource_type
-						)?)?
-					)?;
+					app_state.extraction_queue.enqueue(
+						format!("Extract {}.{}.json", hash, file_type),
+						move |_| async move {
+							let (res_meta, res_data) = extract_latest_resource(&game_files, hash)?;
+
+							fs::write(
+								path.as_path().context("Invalid path")?,
+								to_vec(
+									&convert_generic_async::<Value>(
+										res_data,
+										game_version,
+										res_meta.core_info.resource_type
+									)
+									.await?
+								)?
+							)?;
 
-					fs::write(
-						path.as_path()
-							.context("Invalid path")?
-							.parent()
-							.unwrap()
-							.join(format!("{}.{}.meta.json", hash, res_meta.core_info.resource_type)),
-						to_string(&RpkgResourceMeta::from_resource_metadata(res_meta, false))
-							.context("Couldn't serialise meta file")?
+							fs::write(
+								path.as_path()
+									.context("Invalid path")?
+									.parent()
+									.unwrap()
+									.join(format!("{}.{}.meta.json", hash, res_meta.core_info.resource_type)),
+								to_string(&RpkgResourceMeta::from_resource_metadata(res_meta, false))
+									.context("Couldn't serialise meta file")?
+							)?;
+
+							anyhow::Ok(())
+						}
 					)?;
 				}
 			}
@@ -1309,48 +2390,39 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 			};
 
-			if let Some(game_files) = app_state.game_files.load().as_ref() {
-				if hash == "0057C2C3941115CA".parse()? {
-					let (_, res_data) = extract_latest_resource(game_files, hash)?;
-
-					let mut dialog = app.dialog().file().set_title("Extract file");
-
-					if let Some(project) = app_state.project.load().as_ref() {
-						dialog = dialog.set_directory(&project.path);
-					}
-
-					let res_data = parse_json_ores(&res_data)?;
-
-					if let Some(path) = dialog
-						.set_file_name(&format!("{}.json", hash))
-						.add_filter("JSON file", &["json"])
-						.blocking_save_file()
-					{
-						fs::write(path.as_path().context("Invalid path")?, res_data)?;
-					}
-				} else {
-					let (_, res_data) = extract_latest_resource(game_files, hash)?;
+			if let Some(game_files) = app_state.game_files.load_full() {
+				let mut dialog = app.dialog().file().set_title("Extract file");
 
-					let mut dialog = app.dialog().file().set_title("Extract file");
+				if let Some(project) = app_state.project.load().as_ref() {
+					dialog = dialog.set_directory(&project.path);
+				}
 
-					if let Some(project) = app_state.project.load().as_ref() {
-						dialog = dialog.set_directory(&project.path);
-					}
+				if let Some(path) = dialog
+					.set_file_name(&format!("{}.json", hash))
+					.add_filter("JSON file", &["json"])
+					.blocking_save_file()
+				{
+					app_state
+						.extraction_queue
+						.enqueue(format!("Extract {}.json", hash), move |_| async move {
+							let (_, res_data) = extract_latest_resource(&game_files, hash)?;
 
-					let res_data = parse_hashes_ores(&res_data)?;
+							if hash == "0057C2C3941115CA".parse()? {
+								fs::write(path.as_path().context("Invalid path")?, parse_json_ores(&res_data)?)?;
+							} else {
+								fs::write(
+									path.as_path().context("Invalid path")?,
+									to_vec(&parse_hashes_ores(&res_data)?)?
+								)?;
+							}
 
-					if let Some(path) = dialog
-						.set_file_name(&format!("{}.json", hash))
-						.add_filter("JSON file", &["json"])
-						.blocking_save_file()
-					{
-						fs::write(path.as_path().context("Invalid path")?, to_vec(&res_data)?)?;
-					}
+							anyhow::Ok(())
+						})?;
 				}
 			}
 		}
 
-		ResourceOverviewEvent::ExtractAsImage { id } => {
+		ResourceOverviewEvent::ExtractAsSkeletonJson { id } => {
 			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
 
 			let hash = match editor_state.data {
@@ -1362,11 +2434,7 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 			};
 
-			if let Some(game_files) = app_state.game_files.load().as_ref()
-				&& let Some(install) = app_settings.load().game_install.as_ref()
-			{
-				let (res_meta, res_data) = extract_latest_resource(game_files, hash)?;
-
+			if let Some(game_files) = app_state.game_files.load_full() {
 				let mut dialog = app.dialog().file().set_title("Extract file");
 
 				if let Some(project) = app_state.project.load().as_ref() {
@@ -1374,125 +2442,98 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 
 				if let Some(path) = dialog
-					.set_file_name(&format!("{}.png", hash))
-					.add_filter("PNG file", &["png"])
-					.add_filter("JPEG file", &["jpg"])
-					.add_filter("TGA file", &["tga"])
-					.add_filter("DDS file", &["dds"])
+					.set_file_name(&format!("{}.json", hash))
+					.add_filter("JSON file", &["json"])
 					.blocking_save_file()
 				{
-					app.track_event(
-						"Extract image file as image format",
-						Some(json!({
-							"format": path.as_path().context("Invalid path")?
-									.file_name()
-									.context("No file name")?
-									.to_str()
-									.context("Filename was invalid string")?
-									.split('.')
-									.next_back()
-									.unwrap_or("None")
-						}))
-					)
-					.unwrap();
-
-					match res_meta.core_info.resource_type.as_ref() {
-						"GFXI" => {
-							let reader = ImageReader::new(Cursor::new(res_data.to_owned())).with_guessed_format()?;
-
-							if path
-								.as_path()
-								.context("Invalid path")?
-								.file_name()
-								.context("No file name")?
-								.to_str()
-								.context("Filename was invalid string")?
-								.ends_with(".dds")
-							{
-								match reader.format().context("Couldn't get format")? {
-									ImageFormat::Dds => {
-										fs::write(path.as_path().context("Invalid path")?, res_data)?;
-									}
+					app_state
+						.extraction_queue
+						.enqueue(format!("Extract {}.json", hash), move |_| async move {
+							let (_, res_data) = extract_latest_resource(&game_files, hash)?;
+
+							fs::write(
+								path.as_path().context("Invalid path")?,
+								to_vec(&parse_bone_rig(&res_data)?)?
+							)?;
+
+							anyhow::Ok(())
+						})?;
+				}
+			}
+		}
 
-									_ => {
-										send_notification(
-											app,
-											Notification {
-												kind: NotificationKind::Error,
-												title: "DDS encoding not supported".into(),
-												subtitle: "The image is not natively in DDS format and cannot be \
-												           re-encoded as DDS. Please choose another format."
-													.into()
-											}
-										)?;
-									}
-								}
-							} else {
-								reader.decode()?.save(path.as_path().context("Invalid path")?)?;
-							}
-						}
+		ResourceOverviewEvent::ExtractAnimationWithDependencies { id } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if let Some(game_files) = app_state.game_files.load_full()
+				&& let Some(hash_list) = app_state.hash_list.load_full()
+			{
+				let mut dialog = app.dialog().file().set_title("Extract animation to folder");
 
-						"TEXT" => {
-							let mut texture =
-								TextureMap::process_data(get_loaded_game_version(app, install)?.into(), res_data)
-									.context("Couldn't process texture data")?;
+				if let Some(project) = app_state.project.load().as_ref() {
+					dialog = dialog.set_directory(&project.path);
+				}
 
-							if let Some(texd_depend) = res_meta.core_info.references.first() {
-								let (_, texd_data) =
-									extract_latest_resource(game_files, texd_depend.resource.get_id())?;
+				if let Some(path) = dialog.blocking_pick_folder() {
+					let path = path.into_path()?;
+					let app = app.clone();
 
-								let mip_block = MipblockData::from_memory(
-									&texd_data,
-									get_loaded_game_version(app, install)?.into()
+					app_state.extraction_queue.enqueue(
+						format!("Extract animation {} with dependencies", hash),
+						move |progress| async move {
+							let hashes = [hash]
+								.into_iter()
+								.chain(
+									get_animation_dependencies(&game_files, &hash_list, hash)?
+										.into_iter()
+										.map(|x| x.hash)
 								)
-								.context("Couldn't process TEXD data")?;
-								texture.set_mipblock1(mip_block);
-							}
+								.collect::<Vec<_>>();
 
-							if path
-								.as_path()
-								.context("Invalid path")?
-								.file_name()
-								.context("No file name")?
-								.to_str()
-								.context("Filename was invalid string")?
-								.ends_with(".dds")
-							{
-								let dds_data = glacier_texture::convert::create_dds(&texture)
-									.context("Couldn't convert texture to DDS")?;
-
-								fs::write(path.as_path().context("Invalid path")?, dds_data)?;
-							} else {
-								let tga_data = glacier_texture::convert::create_tga(&texture)
-									.context("Couldn't convert texture to TGA")?;
+							for (index, hash) in hashes.iter().enumerate() {
+								progress.check_cancelled()?;
+								progress.set_progress(index as u32, hashes.len() as u32)?;
 
-								let mut reader = ImageReader::new(Cursor::new(tga_data.to_owned()));
+								let (metadata, data) = extract_latest_resource(&game_files, *hash)?;
+								let resource_type = metadata.core_info.resource_type.to_string();
 
-								reader.set_format(image::ImageFormat::Tga);
+								fs::write(path.join(format!("{}.{}", hash, resource_type)), data)?;
 
-								if path
-									.as_path()
-									.context("Invalid path")?
-									.file_name()
-									.context("No file name")?
-									.to_str()
-									.context("Filename was invalid string")?
-									.ends_with(".tga")
-								{
-									fs::write(path.as_path().context("Invalid path")?, tga_data)?;
-								} else {
-									reader.decode()?.save(path.as_path().context("Invalid path")?)?;
-								}
+								fs::write(
+									path.join(format!("{}.{}.meta", hash, resource_type)),
+									RpkgResourceMeta::from_resource_metadata(metadata, false)
+										.to_binary()
+										.context("Couldn't serialise meta file")?
+								)?;
 							}
-						}
 
-						_ => bail!("Unsupported resource type")
-					}
+							send_notification(
+								&app,
+								Notification {
+									kind: NotificationKind::Success,
+									title: "Animation extracted".into(),
+									subtitle: format!("{} files have been extracted.", hashes.len()),
+									link: None
+								}
+							)?;
+
+							anyhow::Ok(())
+						}
+					)?;
 				}
 			}
 		}
 
-		ResourceOverviewEvent::ExtractAsWav { id } => {
+		ResourceOverviewEvent::ExtractScaleformMovie { id } => {
 			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
 
 			let hash = match editor_state.data {
@@ -1504,44 +2545,49 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 			};
 
-			if let Some(game_files) = app_state.game_files.load().as_ref() {
-				let mut dialog = app.dialog().file().set_title("Extract file");
+			if let Some(game_files) = app_state.game_files.load_full()
+				&& let Some(install) = app_settings.load().game_install.as_ref()
+			{
+				let game_version = get_loaded_game_version(app, install)?;
+
+				let mut dialog = app.dialog().file().set_title("Extract movie to folder");
 
 				if let Some(project) = app_state.project.load().as_ref() {
 					dialog = dialog.set_directory(&project.path);
 				}
 
-				if let Some(path) = dialog
-					.set_file_name(&format!("{}.wav", hash))
-					.add_filter("WAV file", &["wav"])
-					.blocking_save_file()
-				{
-					let (_, res_data) = extract_latest_resource(game_files, hash)?;
+				if let Some(path) = dialog.blocking_pick_folder() {
+					let path = path.into_path()?;
 
-					let data_dir = app.path().app_data_dir().expect("Couldn't get data dir");
+					app_state
+						.extraction_queue
+						.enqueue(format!("Extract movie of {}", hash), move |_| async move {
+							let (_, res_data) = extract_latest_resource(&game_files, hash)?;
+							let resource = parse_gfxf(&res_data, game_version)?;
 
-					let temp_file_id = Uuid::new_v4();
+							fs::write(
+								path.join(format!("{}.{}", hash, resource.swf_extension())),
+								&resource.swf_data
+							)?;
 
-					fs::write(data_dir.join("temp").join(format!("{}.wem", temp_file_id)), res_data)?;
+							for (name, data) in resource.additional_files() {
+								// Only the file name is kept, so names can't point outside the folder
+								let name = Path::new(name).file_name().context("Invalid additional file name")?;
 
-					app.shell()
-						.sidecar("vgmstream-cli")
-						.unwrap()
-						.current_dir(data_dir.join("temp"))
-						.args([
-							&format!("{}.wem", temp_file_id),
-							"-L",
-							"-o",
-							path.as_path().context("Invalid path")?.to_string_lossy().as_ref()
-						])
-						.run()
-						.await
-						.context("VGMStream command failed")?;
+								fs::write(path.join(name), data)?;
+							}
+
+							anyhow::Ok(())
+						})?;
 				}
 			}
 		}
 
-		ResourceOverviewEvent::ExtractMultiWav { id } => {
+		ResourceOverviewEvent::RepackScaleformMovie { id } => {
+			if refuse_if_read_only(app, "Repacking the movie")? {
+				return Ok(());
+			}
+
 			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
 
 			let hash = match editor_state.data {
@@ -1553,88 +2599,122 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 			};
 
-			if let Some(game_files) = app_state.game_files.load().as_ref() {
-				let mut dialog = app.dialog().file().set_title("Extract all WAVs to folder");
+			if let Some(game_files) = app_state.game_files.load_full()
+				&& let Some(install) = app_settings.load().game_install.as_ref()
+				&& let Some(movie_path) = app
+					.dialog()
+					.file()
+					.set_title("Select replacement movie")
+					.add_filter("SWF or GFx file", &["swf", "gfx"])
+					.blocking_pick_file()
+			{
+				let game_version = get_loaded_game_version(app, install)?;
+
+				let mut dialog = app.dialog().file().set_title("Save replacement GFXF");
 
 				if let Some(project) = app_state.project.load().as_ref() {
-					dialog = dialog.set_directory(&project.path);
-				}
+					// Replacements usually go in the project's base chunk content folder
+					let content_path = project.path.join("content").join("chunk0");
 
-				if let Some(path) = dialog.blocking_pick_folder() {
-					let data_dir = app.path().app_data_dir().expect("Couldn't get data dir");
+					dialog = dialog.set_directory(if content_path.is_dir() {
+						content_path
+					} else {
+						project.path.to_owned()
+					});
+				}
 
-					let (res_meta, res_data) = extract_latest_resource(game_files, hash)?;
+				if let Some(path) = dialog
+					.set_file_name(&format!("{}.GFXF", hash))
+					.add_filter("GFXF file", &["GFXF"])
+					.blocking_save_file()
+				{
+					track_event(app, "Repack GFXF with movie", None);
 
-					let wwev = WwiseEvent::parse(&res_data)?;
+					let (metadata, data) = extract_latest_resource(&game_files, hash)?;
 
-					let mut idx = 0;
+					let replacement = repack_gfxf(
+						&data,
+						fs::read(movie_path.as_path().context("Invalid path")?).context("Couldn't read movie")?,
+						game_version
+					)?;
 
-					for object in wwev.non_streamed {
-						let temp_file_id = Uuid::new_v4();
+					let metadata_file = RpkgResourceMeta::from_resource_metadata(metadata, false)
+						.to_binary()
+						.context("Couldn't serialise meta file")?;
 
-						fs::write(data_dir.join("temp").join(format!("{}.wem", temp_file_id)), object.data)?;
+					fs::write(path.as_path().context("Invalid path")?, replacement)?;
 
-						app.shell()
-							.sidecar("vgmstream-cli")
+					fs::write(
+						path.as_path()
+							.context("Invalid path")?
+							.parent()
 							.unwrap()
-							.current_dir(data_dir.join("temp"))
-							.args([
-								&format!("{}.wem", temp_file_id),
-								"-L",
-								"-o",
-								path.as_path()
-									.context("Invalid path")?
-									.join(format!("{}.wav", idx))
-									.to_string_lossy()
-									.as_ref()
-							])
-							.run()
-							.await
-							.context("VGMStream command failed")?;
+							.join(format!("{}.GFXF.meta", hash)),
+						metadata_file
+					)?;
 
-						idx += 1;
-					}
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Success,
+							title: "Movie repacked".into(),
+							subtitle: format!("The replacement for {} has been saved.", hash),
+							link: None
+						}
+					)?;
+				}
+			}
+		}
 
-					for object in wwev.streamed {
-						let temp_file_id = Uuid::new_v4();
+		ResourceOverviewEvent::ExportBehaviourTreeAsDot { id } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
 
-						let wwem_hash = res_meta
-							.core_info
-							.references
-							.get(object.dependency_index as usize)
-							.context("No such WWEM dependency")?
-							.resource
-							.get_id();
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
 
-						let (_, wem_data) = extract_latest_resource(game_files, wwem_hash)?;
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
 
-						fs::write(data_dir.join("temp").join(format!("{}.wem", temp_file_id)), wem_data)?;
+			if let Some(game_files) = app_state.game_files.load_full()
+				&& let Some(install) = app_settings.load().game_install.as_ref()
+			{
+				let game_version = get_loaded_game_version(app, install)?;
 
-						app.shell()
-							.sidecar("vgmstream-cli")
-							.unwrap()
-							.current_dir(data_dir.join("temp"))
-							.args([
-								&format!("{}.wem", temp_file_id),
-								"-L",
-								"-o",
-								path.as_path()
-									.context("Invalid path")?
-									.join(format!("{}.wav", idx))
-									.to_string_lossy()
-									.as_ref()
-							])
-							.run()
-							.await
-							.context("VGMStream command failed")?;
+				let mut dialog = app.dialog().file().set_title("Export behaviour tree");
 
-						idx += 1;
-					}
+				if let Some(project) = app_state.project.load().as_ref() {
+					dialog = dialog.set_directory(&project.path);
+				}
+
+				if let Some(path) = dialog
+					.set_file_name(&format!("{}.dot", hash))
+					.add_filter("Graphviz DOT file", &["dot", "gv"])
+					.blocking_save_file()
+				{
+					track_event(app, "Export behaviour tree as DOT", None);
+
+					app_state
+						.extraction_queue
+						.enqueue(format!("Export {}.dot", hash), move |_| async move {
+							let (_, res_data) = extract_latest_resource(&game_files, hash)?;
+
+							let tree = BehaviourTreeNode::from_json(
+								hash.to_string(),
+								&convert_generic_async::<Value>(res_data, game_version, "AIBB".try_into()?).await?
+							);
+
+							fs::write(path.as_path().context("Invalid path")?, tree.to_dot())?;
+
+							anyhow::Ok(())
+						})?;
 				}
 			}
 		}
 
-		ResourceOverviewEvent::ExtractSpecificMultiWav { id, index } => {
+		ResourceOverviewEvent::ExtractAsImage { id } => {
 			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
 
 			let hash = match editor_state.data {
@@ -1646,7 +2726,11 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 			};
 
-			if let Some(game_files) = app_state.game_files.load().as_ref() {
+			if let Some(game_files) = app_state.game_files.load_full()
+				&& let Some(install) = app_settings.load().game_install.as_ref()
+			{
+				let game_version = get_loaded_game_version(app, install)?;
+
 				let mut dialog = app.dialog().file().set_title("Extract file");
 
 				if let Some(project) = app_state.project.load().as_ref() {
@@ -1654,78 +2738,133 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 
 				if let Some(path) = dialog
-					.set_file_name(&format!("{}~{}.wav", hash, index))
-					.add_filter("WAV file", &["wav"])
+					.set_file_name(&format!("{}.png", hash))
+					.add_filter("PNG file", &["png"])
+					.add_filter("JPEG file", &["jpg"])
+					.add_filter("TGA file", &["tga"])
+					.add_filter("DDS file", &["dds"])
 					.blocking_save_file()
 				{
-					let data_dir = app.path().app_data_dir().expect("Couldn't get data dir");
-
-					let (res_meta, res_data) = extract_latest_resource(game_files, hash)?;
+					track_event(
+						app,
+						"Extract image file as image format",
+						Some(json!({
+							"format": path.as_path().context("Invalid path")?
+									.file_name()
+									.context("No file name")?
+									.to_str()
+									.context("Filename was invalid string")?
+									.split('.')
+									.next_back()
+									.unwrap_or("None")
+						}))
+					);
+
+					let app = app.clone();
+
+					app_state
+						.extraction_queue
+						.enqueue(format!("Extract {} as image", hash), move |_| async move {
+							let (res_meta, res_data) = extract_latest_resource(&game_files, hash)?;
+
+							match res_meta.core_info.resource_type.as_ref() {
+								"GFXI" => {
+									let reader =
+										ImageReader::new(Cursor::new(res_data.to_owned())).with_guessed_format()?;
+
+									if path
+										.as_path()
+										.context("Invalid path")?
+										.file_name()
+										.context("No file name")?
+										.to_str()
+										.context("Filename was invalid string")?
+										.ends_with(".dds")
+									{
+										match reader.format().context("Couldn't get format")? {
+											ImageFormat::Dds => {
+												fs::write(path.as_path().context("Invalid path")?, res_data)?;
+											}
 
-					let wwev = WwiseEvent::parse(&res_data)?;
+											_ => {
+												send_notification(
+													&app,
+													Notification {
+														kind: NotificationKind::Error,
+														title: "DDS encoding not supported".into(),
+														subtitle: "The image is not natively in DDS format and cannot \
+														           be re-encoded as DDS. Please choose another format."
+															.into(),
+														link: None
+													}
+												)?;
+											}
+										}
+									} else {
+										reader.decode()?.save(path.as_path().context("Invalid path")?)?;
+									}
+								}
 
-					let temp_file_id = Uuid::new_v4();
+								"TEXT" => {
+									let mut texture = TextureMap::process_data(game_version.into(), res_data)
+										.context("Couldn't process texture data")?;
 
-					if index < wwev.non_streamed.len() as u32 {
-						fs::write(
-							data_dir.join("temp").join(format!("{}.wem", temp_file_id)),
-							&wwev
-								.non_streamed
-								.get(index as usize)
-								.context("No such audio object")?
-								.data
-						)?;
+									if let Some(texd_depend) = res_meta.core_info.references.first() {
+										let (_, texd_data) =
+											extract_latest_resource(&game_files, texd_depend.resource.get_id())?;
 
-						app.shell()
-							.sidecar("vgmstream-cli")
-							.unwrap()
-							.current_dir(data_dir.join("temp"))
-							.args([
-								&format!("{}.wem", temp_file_id),
-								"-L",
-								"-o",
-								path.as_path().context("Invalid path")?.to_string_lossy().as_ref()
-							])
-							.run()
-							.await
-							.context("VGMStream command failed")?;
-					} else {
-						let wwem_hash = res_meta
-							.core_info
-							.references
-							.get(
-								wwev.streamed
-									.get(index as usize - wwev.non_streamed.len())
-									.context("No such audio object")?
-									.dependency_index as usize
-							)
-							.context("No such WWEM dependency")?
-							.resource
-							.get_id();
+										let mip_block = MipblockData::from_memory(&texd_data, game_version.into())
+											.context("Couldn't process TEXD data")?;
+										texture.set_mipblock1(mip_block);
+									}
 
-						let (_, wem_data) = extract_latest_resource(game_files, wwem_hash)?;
+									if path
+										.as_path()
+										.context("Invalid path")?
+										.file_name()
+										.context("No file name")?
+										.to_str()
+										.context("Filename was invalid string")?
+										.ends_with(".dds")
+									{
+										let dds_data = glacier_texture::convert::create_dds(&texture)
+											.context("Couldn't convert texture to DDS")?;
+
+										fs::write(path.as_path().context("Invalid path")?, dds_data)?;
+									} else {
+										let tga_data = glacier_texture::convert::create_tga(&texture)
+											.context("Couldn't convert texture to TGA")?;
+
+										let mut reader = ImageReader::new(Cursor::new(tga_data.to_owned()));
+
+										reader.set_format(image::ImageFormat::Tga);
+
+										if path
+											.as_path()
+											.context("Invalid path")?
+											.file_name()
+											.context("No file name")?
+											.to_str()
+											.context("Filename was invalid string")?
+											.ends_with(".tga")
+										{
+											fs::write(path.as_path().context("Invalid path")?, tga_data)?;
+										} else {
+											reader.decode()?.save(path.as_path().context("Invalid path")?)?;
+										}
+									}
+								}
 
-						fs::write(data_dir.join("temp").join(format!("{}.wem", temp_file_id)), wem_data)?;
+								_ => bail!("Unsupported resource type")
+							}
 
-						app.shell()
-							.sidecar("vgmstream-cli")
-							.unwrap()
-							.current_dir(data_dir.join("temp"))
-							.args([
-								&format!("{}.wem", temp_file_id),
-								"-L",
-								"-o",
-								path.as_path().context("Invalid path")?.to_string_lossy().as_ref()
-							])
-							.run()
-							.await
-							.context("VGMStream command failed")?;
-					}
+							anyhow::Ok(())
+						})?;
 				}
 			}
 		}
 
-		ResourceOverviewEvent::ExtractAsHMLanguages { id } => {
+		ResourceOverviewEvent::ExtractAsWav { id } => {
 			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
 
 			let hash = match editor_state.data {
@@ -1737,14 +2876,7 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 			};
 
-			if let Some(game_files) = app_state.game_files.load().as_ref()
-				&& let Some(install) = app_settings.load().game_install.as_ref()
-				&& let Some(hash_list) = app_state.hash_list.load().as_ref()
-			{
-				let game_version = get_loaded_game_version(app, install)?;
-
-				let (res_meta, res_data) = extract_latest_resource(game_files, hash)?;
-
+			if let Some(game_files) = app_state.game_files.load_full() {
 				let mut dialog = app.dialog().file().set_title("Extract file");
 
 				if let Some(project) = app_state.project.load().as_ref() {
@@ -1752,230 +2884,1038 @@ pub async fn handle_resource_overview_event(app: &AppHandle, event: ResourceOver
 				}
 
 				if let Some(path) = dialog
-					.set_file_name(&format!(
-						"{}.{}.json",
-						hash,
-						res_meta.core_info.resource_type.as_ref().to_lowercase()
-					))
-					.add_filter(
-						format!("{}.json file", res_meta.core_info.resource_type.as_ref().to_lowercase()),
-						&[&format!(
-							"{}.json",
-							res_meta.core_info.resource_type.as_ref().to_lowercase()
-						)]
-					)
+					.set_file_name(&format!("{}.wav", hash))
+					.add_filter("WAV file", &["wav"])
 					.blocking_save_file()
 				{
-					fs::write(
-						path.as_path().context("Invalid path")?,
-						match res_meta.core_info.resource_type.as_ref() {
-							"CLNG" => {
-								let clng = {
-									let mut iteration = 0;
-
-									loop {
-										if let Ok::<_, anyhow::Error>(x) = try {
-											let langmap = get_language_map(game_version, iteration)
-												.context("No more alternate language maps available")?;
-
-											let clng =
-												hmlanguages::clng::CLNG::new(game_version.into(), langmap.1.to_owned())
-													.map_err(|x| anyhow!("TonyTools error: {x:?}"))?;
+					let app = app.clone();
+
+					app_state
+						.extraction_queue
+						.enqueue(format!("Extract {}.wav", hash), move |_| async move {
+							let (_, res_data) = extract_latest_resource(&game_files, hash)?;
+
+							let data_dir = app.path().app_data_dir().expect("Couldn't get data dir");
+
+							let temp_file_id = Uuid::new_v4();
+
+							fs::write(data_dir.join("temp").join(format!("{}.wem", temp_file_id)), res_data)?;
+
+							app.shell()
+								.sidecar("vgmstream-cli")
+								.unwrap()
+								.current_dir(data_dir.join("temp"))
+								.args([
+									&format!("{}.wem", temp_file_id),
+									"-L",
+									"-o",
+									path.as_path().context("Invalid path")?.to_string_lossy().as_ref()
+								])
+								.run()
+								.await
+								.context("VGMStream command failed")?;
+
+							anyhow::Ok(())
+						})?;
+				}
+			}
+		}
 
-											clng.convert(
-												&res_data,
-												to_string(
-													&RpkgResourceMeta::from_resource_metadata(
-														res_meta.to_owned(),
-														false
-													)
-													.with_hash_list(&hash_list.entries)?
-												)?
-											)
-											.map_err(|x| anyhow!("TonyTools error: {x:?}"))?
-										} {
-											break x;
-										} else {
-											iteration += 1;
+		ResourceOverviewEvent::ExtractMultiWav { id } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
 
-											if get_language_map(game_version, iteration).is_none() {
-												bail!("No more alternate language maps available");
-											}
-										}
-									}
-								};
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
 
-								let mut buf = Vec::new();
-								let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
-								let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+			if let Some(game_files) = app_state.game_files.load_full() {
+				let mut dialog = app.dialog().file().set_title("Extract all WAVs to folder");
 
-								clng.serialize(&mut ser)?;
+				if let Some(project) = app_state.project.load().as_ref() {
+					dialog = dialog.set_directory(&project.path);
+				}
 
-								buf
-							}
+				if let Some(path) = dialog.blocking_pick_folder() {
+					let app = app.clone();
 
-							"DITL" => {
-								let ditl = hmlanguages::ditl::DITL::new(
-									app_state
-										.tonytools_hash_list
-										.load()
-										.as_ref()
-										.context("No TonyTools hash list available")?
-										.deref()
-										.to_owned()
-								)
-								.map_err(|x| anyhow!("TonyTools error: {x:?}"))?;
+					app_state.extraction_queue.enqueue(
+						format!("Extract all WAVs of {}", hash),
+						move |progress| async move {
+							let data_dir = app.path().app_data_dir().expect("Couldn't get data dir");
 
-								let mut buf = Vec::new();
-								let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
-								let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+							let (res_meta, res_data) = extract_latest_resource(&game_files, hash)?;
 
-								ditl.convert(
-									&res_data,
-									to_string(
-										&RpkgResourceMeta::from_resource_metadata(res_meta, false)
-											.with_hash_list(&hash_list.entries)?
-									)?
-								)
-								.map_err(|x| anyhow!("TonyTools error: {x:?}"))?
-								.serialize(&mut ser)?;
+							let wwev = WwiseEvent::parse(&res_data)?;
 
-								buf
-							}
+							let total = (wwev.non_streamed.len() + wwev.streamed.len()) as u32;
 
-							"DLGE" => {
-								let dlge = {
-									let mut iteration = 0;
-
-									loop {
-										if let Ok::<_, anyhow::Error>(x) = try {
-											let langmap = get_language_map(game_version, iteration)
-												.context("No more alternate language maps available")?;
-
-											let dlge = hmlanguages::dlge::DLGE::new(
-												app_state
-													.tonytools_hash_list
-													.load()
-													.as_ref()
-													.context("No TonyTools hash list available")?
-													.deref()
-													.to_owned(),
-												game_version.into(),
-												langmap.1.to_owned(),
-												None,
-												false
-											)
-											.map_err(|x| anyhow!("TonyTools error: {x:?}"))?;
+							let mut idx = 0;
 
-											dlge.convert(
-												&res_data,
-												to_string(
-													&RpkgResourceMeta::from_resource_metadata(
-														res_meta.to_owned(),
-														false
-													)
-													.with_hash_list(&hash_list.entries)?
-												)?
-											)
-											.map_err(|x| anyhow!("TonyTools error: {x:?}"))?
-										} {
-											break x;
-										} else {
-											iteration += 1;
+							for object in wwev.non_streamed {
+								progress.check_cancelled()?;
+								progress.set_progress(idx, total)?;
 
-											if get_language_map(game_version, iteration).is_none() {
-												bail!("No more alternate language maps available");
-											}
-										}
-									}
-								};
+								let temp_file_id = Uuid::new_v4();
 
-								let mut buf = Vec::new();
-								let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
-								let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+								fs::write(data_dir.join("temp").join(format!("{}.wem", temp_file_id)), object.data)?;
 
-								dlge.serialize(&mut ser)?;
+								app.shell()
+									.sidecar("vgmstream-cli")
+									.unwrap()
+									.current_dir(data_dir.join("temp"))
+									.args([
+										&format!("{}.wem", temp_file_id),
+										"-L",
+										"-o",
+										path.as_path()
+											.context("Invalid path")?
+											.join(format!("{}.wav", idx))
+											.to_string_lossy()
+											.as_ref()
+									])
+									.run()
+									.await
+									.context("VGMStream command failed")?;
 
-								buf
+								idx += 1;
 							}
 
-							"LOCR" => {
-								let locr = {
-									let mut iteration = 0;
-
-									loop {
-										if let Ok::<_, anyhow::Error>(x) = try {
-											let langmap = get_language_map(game_version, iteration)
-												.context("No more alternate language maps available")?;
-
-											let locr = hmlanguages::locr::LOCR::new(
-												app_state
-													.tonytools_hash_list
-													.load()
-													.as_ref()
-													.context("No TonyTools hash list available")?
-													.deref()
-													.to_owned(),
-												game_version.into(),
-												langmap.1.to_owned(),
-												langmap.0
-											)
-											.map_err(|x| anyhow!("TonyTools error: {x:?}"))?;
+							for object in wwev.streamed {
+								progress.check_cancelled()?;
+								progress.set_progress(idx, total)?;
+
+								let temp_file_id = Uuid::new_v4();
+
+								let wwem_hash = res_meta
+									.core_info
+									.references
+									.get(object.dependency_index as usize)
+									.context("No such WWEM dependency")?
+									.resource
+									.get_id();
+
+								let (_, wem_data) = extract_latest_resource(&game_files, wwem_hash)?;
+
+								fs::write(data_dir.join("temp").join(format!("{}.wem", temp_file_id)), wem_data)?;
+
+								app.shell()
+									.sidecar("vgmstream-cli")
+									.unwrap()
+									.current_dir(data_dir.join("temp"))
+									.args([
+										&format!("{}.wem", temp_file_id),
+										"-L",
+										"-o",
+										path.as_path()
+											.context("Invalid path")?
+											.join(format!("{}.wav", idx))
+											.to_string_lossy()
+											.as_ref()
+									])
+									.run()
+									.await
+									.context("VGMStream command failed")?;
 
-											locr.convert(
-												&res_data,
-												to_string(
-													&RpkgResourceMeta::from_resource_metadata(
-														res_meta.to_owned(),
-														false
+								idx += 1;
+							}
+
+							anyhow::Ok(())
+						}
+					)?;
+				}
+			}
+		}
+
+		ResourceOverviewEvent::ExtractSpecificMultiWav { id, index } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if let Some(game_files) = app_state.game_files.load_full() {
+				let mut dialog = app.dialog().file().set_title("Extract file");
+
+				if let Some(project) = app_state.project.load().as_ref() {
+					dialog = dialog.set_directory(&project.path);
+				}
+
+				if let Some(path) = dialog
+					.set_file_name(&format!("{}~{}.wav", hash, index))
+					.add_filter("WAV file", &["wav"])
+					.blocking_save_file()
+				{
+					let app = app.clone();
+
+					app_state.extraction_queue.enqueue(
+						format!("Extract {}~{}.wav", hash, index),
+						move |_| async move {
+							let data_dir = app.path().app_data_dir().expect("Couldn't get data dir");
+
+							let (res_meta, res_data) = extract_latest_resource(&game_files, hash)?;
+
+							let wwev = WwiseEvent::parse(&res_data)?;
+
+							let temp_file_id = Uuid::new_v4();
+
+							if index < wwev.non_streamed.len() as u32 {
+								fs::write(
+									data_dir.join("temp").join(format!("{}.wem", temp_file_id)),
+									&wwev
+										.non_streamed
+										.get(index as usize)
+										.context("No such audio object")?
+										.data
+								)?;
+
+								app.shell()
+									.sidecar("vgmstream-cli")
+									.unwrap()
+									.current_dir(data_dir.join("temp"))
+									.args([
+										&format!("{}.wem", temp_file_id),
+										"-L",
+										"-o",
+										path.as_path().context("Invalid path")?.to_string_lossy().as_ref()
+									])
+									.run()
+									.await
+									.context("VGMStream command failed")?;
+							} else {
+								let wwem_hash = res_meta
+									.core_info
+									.references
+									.get(
+										wwev.streamed
+											.get(index as usize - wwev.non_streamed.len())
+											.context("No such audio object")?
+											.dependency_index as usize
+									)
+									.context("No such WWEM dependency")?
+									.resource
+									.get_id();
+
+								let (_, wem_data) = extract_latest_resource(&game_files, wwem_hash)?;
+
+								fs::write(data_dir.join("temp").join(format!("{}.wem", temp_file_id)), wem_data)?;
+
+								app.shell()
+									.sidecar("vgmstream-cli")
+									.unwrap()
+									.current_dir(data_dir.join("temp"))
+									.args([
+										&format!("{}.wem", temp_file_id),
+										"-L",
+										"-o",
+										path.as_path().context("Invalid path")?.to_string_lossy().as_ref()
+									])
+									.run()
+									.await
+									.context("VGMStream command failed")?;
+							}
+
+							anyhow::Ok(())
+						}
+					)?;
+				}
+			}
+		}
+
+		ResourceOverviewEvent::ExtractAsHMLanguages { id } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if let Some(game_files) = app_state.game_files.load_full()
+				&& let Some(install) = app_settings.load().game_install.as_ref()
+				&& let Some(hash_list) = app_state.hash_list.load_full()
+			{
+				let game_version = get_loaded_game_version(app, install)?;
+				let tonytools_hash_list = app_state.tonytools_hash_list.load_full();
+
+				let file_type = hash_list
+					.entries
+					.get(&hash)
+					.expect("Can only open files from the hash list")
+					.resource_type
+					.as_ref()
+					.to_lowercase();
+
+				let mut dialog = app.dialog().file().set_title("Extract file");
+
+				if let Some(project) = app_state.project.load().as_ref() {
+					dialog = dialog.set_directory(&project.path);
+				}
+
+				if let Some(path) = dialog
+					.set_file_name(&format!("{}.{}.json", hash, file_type))
+					.add_filter(format!("{}.json file", file_type), &[&format!("{}.json", file_type)])
+					.blocking_save_file()
+				{
+					app_state.extraction_queue.enqueue(
+						format!("Extract {}.{}.json", hash, file_type),
+						move |_| async move {
+							let (res_meta, res_data) = extract_latest_resource(&game_files, hash)?;
+
+							fs::write(
+								path.as_path().context("Invalid path")?,
+								match res_meta.core_info.resource_type.as_ref() {
+									"CLNG" => {
+										let clng = {
+											let mut iteration = 0;
+
+											loop {
+												if let Ok::<_, anyhow::Error>(x) = try {
+													let langmap = get_language_map(game_version, iteration)
+														.context("No more alternate language maps available")?;
+
+													let clng = hmlanguages::clng::CLNG::new(
+														game_version.into(),
+														langmap.1.to_owned()
+													)
+													.map_err(|x| anyhow!("TonyTools error: {x:?}"))?;
+
+													clng.convert(
+														&res_data,
+														to_string(
+															&RpkgResourceMeta::from_resource_metadata(
+																res_meta.to_owned(),
+																false
+															)
+															.with_hash_list(&hash_list.entries)?
+														)?
 													)
+													.map_err(|x| anyhow!("TonyTools error: {x:?}"))?
+												} {
+													break x;
+												} else {
+													iteration += 1;
+
+													if get_language_map(game_version, iteration).is_none() {
+														bail!("No more alternate language maps available");
+													}
+												}
+											}
+										};
+
+										let mut buf = Vec::new();
+										let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+										let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+										clng.serialize(&mut ser)?;
+
+										buf
+									}
+
+									"DITL" => {
+										let ditl = hmlanguages::ditl::DITL::new(
+											tonytools_hash_list
+												.as_ref()
+												.context("No TonyTools hash list available")?
+												.deref()
+												.to_owned()
+										)
+										.map_err(|x| anyhow!("TonyTools error: {x:?}"))?;
+
+										let mut buf = Vec::new();
+										let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+										let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+										ditl.convert(
+											&res_data,
+											to_string(
+												&RpkgResourceMeta::from_resource_metadata(res_meta, false)
 													.with_hash_list(&hash_list.entries)?
+											)?
+										)
+										.map_err(|x| anyhow!("TonyTools error: {x:?}"))?
+										.serialize(&mut ser)?;
+
+										buf
+									}
+
+									"DLGE" => {
+										let dlge = {
+											let mut iteration = 0;
+
+											loop {
+												if let Ok::<_, anyhow::Error>(x) = try {
+													let langmap = get_language_map(game_version, iteration)
+														.context("No more alternate language maps available")?;
+
+													let dlge = hmlanguages::dlge::DLGE::new(
+														tonytools_hash_list
+															.as_ref()
+															.context("No TonyTools hash list available")?
+															.deref()
+															.to_owned(),
+														game_version.into(),
+														langmap.1.to_owned(),
+														None,
+														false
+													)
+													.map_err(|x| anyhow!("TonyTools error: {x:?}"))?;
+
+													dlge.convert(
+														&res_data,
+														to_string(
+															&RpkgResourceMeta::from_resource_metadata(
+																res_meta.to_owned(),
+																false
+															)
+															.with_hash_list(&hash_list.entries)?
+														)?
+													)
+													.map_err(|x| anyhow!("TonyTools error: {x:?}"))?
+												} {
+													break x;
+												} else {
+													iteration += 1;
+
+													if get_language_map(game_version, iteration).is_none() {
+														bail!("No more alternate language maps available");
+													}
+												}
+											}
+										};
+
+										let mut buf = Vec::new();
+										let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+										let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+										dlge.serialize(&mut ser)?;
+
+										buf
+									}
+
+									"LOCR" => {
+										let locr = {
+											let mut iteration = 0;
+
+											loop {
+												if let Ok::<_, anyhow::Error>(x) = try {
+													let langmap = get_language_map(game_version, iteration)
+														.context("No more alternate language maps available")?;
+
+													let locr = hmlanguages::locr::LOCR::new(
+														tonytools_hash_list
+															.as_ref()
+															.context("No TonyTools hash list available")?
+															.deref()
+															.to_owned(),
+														game_version.into(),
+														langmap.1.to_owned(),
+														langmap.0
+													)
+													.map_err(|x| anyhow!("TonyTools error: {x:?}"))?;
+
+													locr.convert(
+														&res_data,
+														to_string(
+															&RpkgResourceMeta::from_resource_metadata(
+																res_meta.to_owned(),
+																false
+															)
+															.with_hash_list(&hash_list.entries)?
+														)?
+													)
+													.map_err(|x| anyhow!("TonyTools error: {x:?}"))?
+												} {
+													break x;
+												} else {
+													iteration += 1;
+
+													if get_language_map(game_version, iteration).is_none() {
+														bail!("No more alternate language maps available");
+													}
+												}
+											}
+										};
+
+										let mut buf = Vec::new();
+										let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+										let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+										locr.serialize(&mut ser)?;
+
+										buf
+									}
+
+									"RTLV" => {
+										let rtlv = hmlanguages::rtlv::RTLV::new(game_version.into(), None)
+											.map_err(|x| anyhow!("TonyTools error: {x:?}"))?
+											.convert(
+												&res_data,
+												to_string(
+													&RpkgResourceMeta::from_resource_metadata(res_meta, false)
+														.with_hash_list(&hash_list.entries)?
 												)?
 											)
-											.map_err(|x| anyhow!("TonyTools error: {x:?}"))?
-										} {
-											break x;
-										} else {
-											iteration += 1;
+											.map_err(|x| anyhow!("TonyTools error: {x:?}"))?;
 
-											if get_language_map(game_version, iteration).is_none() {
-												bail!("No more alternate language maps available");
-											}
-										}
+										let mut buf = Vec::new();
+										let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+										let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+										rtlv.serialize(&mut ser)?;
+
+										buf
 									}
-								};
 
-								let mut buf = Vec::new();
-								let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
-								let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+									_ => bail!("Not a valid HMLanguages resource type")
+								}
+							)?;
 
-								locr.serialize(&mut ser)?;
+							anyhow::Ok(())
+						}
+					)?;
+				}
+			}
+		}
 
-								buf
-							}
+		ResourceOverviewEvent::GetGenericRLPage { id, start_line } => {
+			if let Some(json) = get_cached_generic_rl_json(app, id).await? {
+				send_request(
+					app,
+					Request::Editor(EditorRequest::ResourceOverview(
+						ResourceOverviewRequest::SetGenericRLPage {
+							id,
+							start_line,
+							json: json
+								.lines()
+								.skip(start_line as usize)
+								.take(PREVIEW_PAGE_LINES)
+								.collect::<Vec<_>>()
+								.join("\n")
+						}
+					))
+				)?;
+			}
+		}
 
-							"RTLV" => {
-								let rtlv = hmlanguages::rtlv::RTLV::new(game_version.into(), None)
-									.map_err(|x| anyhow!("TonyTools error: {x:?}"))?
-									.convert(
-										&res_data,
-										to_string(
-											&RpkgResourceMeta::from_resource_metadata(res_meta, false)
-												.with_hash_list(&hash_list.entries)?
-										)?
-									)
-									.map_err(|x| anyhow!("TonyTools error: {x:?}"))?;
+		ResourceOverviewEvent::SearchGenericRL { id, query } => {
+			if let Some(json) = get_cached_generic_rl_json(app, id).await? {
+				let query = query.to_lowercase();
 
-								let mut buf = Vec::new();
-								let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
-								let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+				send_request(
+					app,
+					Request::Editor(EditorRequest::ResourceOverview(
+						ResourceOverviewRequest::SetGenericRLSearchResults {
+							id,
+							results: json
+								.lines()
+								.enumerate()
+								.filter(|(_, line)| line.to_lowercase().contains(&query))
+								.take(PREVIEW_SEARCH_LIMIT)
+								.map(|(index, line)| (index as u32, line.trim().to_owned()))
+								.collect()
+						}
+					))
+				)?;
+			}
+		}
 
-								rtlv.serialize(&mut ser)?;
+		ResourceOverviewEvent::AddToCollection { id, collection } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
 
-								buf
-							}
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			let mut collections = (*app_state.collections.load_full()).to_owned();
+
+			let hashes = collections.entry(collection.to_owned()).or_default();
+
+			if !hashes.contains(&hash) {
+				hashes.push(hash);
+			}
+
+			store_collections(app, collections)?;
+
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Success,
+					title: "Added to collection".into(),
+					subtitle: format!("{} has been added to the collection {}.", hash, collection),
+					link: None
+				}
+			)?;
+		}
+
+		ResourceOverviewEvent::OpenWithExternalTool { id, tool } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if refuse_if_untrusted(app, "Opening a resource with an external tool")? {
+				return Ok(());
+			}
+
+			let settings = app_settings.load();
+
+			let tool = settings
+				.external_tools
+				.iter()
+				.find(|x| x.name == tool)
+				.context("No such external tool")?;
+
+			let path = app_state
+				.hash_list
+				.load()
+				.as_ref()
+				.and_then(|x| x.entries.get(&hash)?.path.to_owned());
+
+			track_event(app, "Open resource with external tool", None);
+
+			launch_external_tool(app, tool, Some(&hash.to_string()), path.as_deref())?;
+		}
+
+		ResourceOverviewEvent::ExtractWithPreset { id, preset } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			track_event(app, "Extract resource with preset", None);
+
+			enqueue_preset_extraction(app, hash, &preset)?;
+		}
+
+		ResourceOverviewEvent::ExtractAsObj { id } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if let Some(game_files) = app_state.game_files.load_full()
+				&& let Some(install) = app_settings.load().game_install.as_ref()
+			{
+				let game_version = get_loaded_game_version(app, install)?;
+
+				let mut dialog = app.dialog().file().set_title("Extract file");
+
+				if let Some(project) = app_state.project.load().as_ref() {
+					dialog = dialog.set_directory(&project.path);
+				}
+
+				if let Some(path) = dialog
+					.set_file_name(&format!("{}.obj", hash))
+					.add_filter("OBJ file", &["obj"])
+					.blocking_save_file()
+				{
+					app_state
+						.extraction_queue
+						.enqueue(format!("Extract {}.obj", hash), move |_| async move {
+							let (res_meta, res_data) = extract_latest_resource(&game_files, hash)?;
+
+							let (obj, _) = match res_meta.core_info.resource_type.as_ref() {
+								"PRIM" => {
+									let (obj, bounding_box, _) = prim_to_obj(game_version, res_data)?;
+									(obj, bounding_box)
+								}
+								"ALOC" => collision_to_obj(&parse_aloc(&res_data)?)?,
+								_ => bail!("Not a mesh resource")
+							};
+
+							fs::write(path.as_path().context("Invalid path")?, obj)?;
+
+							anyhow::Ok(())
+						})?;
+				}
+			}
+		}
+
+		ResourceOverviewEvent::CompareTextureMips { id } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if let Some(game_files) = app_state.game_files.load().as_ref()
+				&& let Some(install) = app_settings.load().game_install.as_ref()
+			{
+				track_event(app, "Compare texture mips", None);
+
+				let temp_dir = app.path().app_data_dir().context("Couldn't get data dir")?.join("temp");
+				fs::create_dir_all(&temp_dir)?;
+
+				let comparison =
+					compare_texture_mips(game_files, get_loaded_game_version(app, install)?, hash, &temp_dir)?;
+
+				send_request(
+					app,
+					Request::Editor(EditorRequest::ResourceOverview(
+						ResourceOverviewRequest::SetTextureMipComparison { id, comparison }
+					))
+				)?;
+			}
+		}
+
+		ResourceOverviewEvent::GetChangelogDiff { id, index } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if let Some(game_files) = app_state.game_files.load().as_ref()
+				&& let Some(install) = app_settings.load().game_install.as_ref()
+			{
+				let game_version = get_loaded_game_version(app, install)?;
+
+				let (resource_type, original, modified) = extract_changelog_versions(game_files, hash, index as usize)?;
+
+				send_request(
+					app,
+					Request::Editor(EditorRequest::ResourceOverview(
+						ResourceOverviewRequest::SetChangelogDiff {
+							id,
+							index,
+							original: original
+								.map(|x| get_version_json(&x, game_version, resource_type))
+								.transpose()?,
+							modified: modified
+								.map(|x| get_version_json(&x, game_version, resource_type))
+								.transpose()?
+						}
+					))
+				)?;
+			}
+		}
+
+		ResourceOverviewEvent::SetProjectDependencyFlag { id, index, flag } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if refuse_if_read_only(app, "Changing dependency flags")? {
+				return Ok(());
+			}
+
+			let project = app_state.project.load_full().context("No project loaded")?;
+			let meta_path = find_project_meta(&project.path, hash).context("No meta file for resource in project")?;
+
+			track_event(app, "Change dependency flag in resource overview", None);
+
+			set_meta_dependency_flag(&meta_path, index as usize, &flag)?;
+
+			send_project_dependency_flags(app, id, hash)?;
+		}
+
+		ResourceOverviewEvent::MoveAssetSetEntry { id, from, to } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if refuse_if_read_only(app, "Editing ASETs")? {
+				return Ok(());
+			}
+
+			let project = app_state.project.load_full().context("No project loaded")?;
+			let meta_path = find_project_meta(&project.path, hash).context("No meta file for resource in project")?;
+
+			track_event(app, "Move entry in ASET", None);
+
+			let mut dependencies = read_meta_dependencies(&meta_path)?;
+
+			let (from, to) = (from as usize, to as usize);
+
+			if from >= dependencies.len() || to >= dependencies.len() {
+				bail!("No such ASET entry");
+			}
+
+			let dependency = dependencies.remove(from);
+			dependencies.insert(to, dependency);
+
+			write_meta_dependencies(&meta_path, &dependencies)?;
+
+			send_asset_set_entries(app, id, hash)?;
+			send_project_dependency_flags(app, id, hash)?;
+		}
+
+		ResourceOverviewEvent::AddAssetSetEntry { id, resource } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if refuse_if_read_only(app, "Editing ASETs")? {
+				return Ok(());
+			}
+
+			let project = app_state.project.load_full().context("No project loaded")?;
+			let meta_path = find_project_meta(&project.path, hash).context("No meta file for resource in project")?;
+
+			let resource = RuntimeID::from_any(resource.trim()).context("Invalid resource hash or path")?;
+
+			track_event(app, "Add entry to ASET", None);
+
+			let mut dependencies = read_meta_dependencies(&meta_path)?;
+
+			// Constituent resources are referenced at install time, without being acquired, for every language
+			dependencies.push((
+				resource.to_string(),
+				DecodedReferenceFlag {
+					kind: ReferenceKind::Install,
+					acquired: false,
+					language_code: None
+				}
+			));
+
+			write_meta_dependencies(&meta_path, &dependencies)?;
+
+			send_asset_set_entries(app, id, hash)?;
+			send_project_dependency_flags(app, id, hash)?;
+		}
+
+		ResourceOverviewEvent::RemoveAssetSetEntry { id, index } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if refuse_if_read_only(app, "Editing ASETs")? {
+				return Ok(());
+			}
+
+			let project = app_state.project.load_full().context("No project loaded")?;
+			let meta_path = find_project_meta(&project.path, hash).context("No meta file for resource in project")?;
+
+			track_event(app, "Remove entry from ASET", None);
+
+			let mut dependencies = read_meta_dependencies(&meta_path)?;
+
+			let index = index as usize;
+
+			if index >= dependencies.len() {
+				bail!("No such ASET entry");
+			}
+
+			dependencies.remove(index);
+
+			write_meta_dependencies(&meta_path, &dependencies)?;
+
+			send_asset_set_entries(app, id, hash)?;
+			send_project_dependency_flags(app, id, hash)?;
+		}
+
+		ResourceOverviewEvent::SaveMaterialEntity { id, json } => {
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if refuse_if_read_only(app, "Saving materials")? {
+				return Ok(());
+			}
+
+			let project = app_state.project.load_full().context("No project loaded")?;
+
+			let game_files = app_state.game_files.load();
+			let game_files = game_files.as_ref().context("Game files not loaded")?;
+
+			let hash_list = app_state.hash_list.load();
+			let hash_list = hash_list.as_ref().context("No hash list available")?;
+
+			track_event(app, "Save material entity to project", None);
+
+			let task = start_task(app, format!("Saving material {}", hash))?;
+
+			let rebuilt = rebuild_material_entity(game_files, hash_list, &project.path, hash, &json)?;
+
+			finish_task(app, task)?;
+
+			send_notification(
+				app,
+				if rebuilt.missing_references.is_empty() {
+					Notification {
+						kind: NotificationKind::Success,
+						title: "Material saved".into(),
+						subtitle: format!(
+							"The MATT and MATB were written to {}.",
+							rebuilt
+								.files
+								.iter()
+								.filter_map(|x| x.strip_prefix(&project.path).ok())
+								.map(|x| x.to_string_lossy())
+								.collect::<Vec<_>>()
+								.join(" and ")
+						),
+						link: None
+					}
+				} else {
+					Notification {
+						kind: NotificationKind::Warning,
+						title: "Material saved with missing resources".into(),
+						subtitle: format!(
+							"The material refers to resources which aren't in the game files or the project, so the \
+							 game may crash when loading it: {}",
+							rebuilt.missing_references.join(", ")
+						),
+						link: None
+					}
+				}
+			)?;
+		}
+
+		ResourceOverviewEvent::ReplaceWithImage { id } => {
+			if refuse_if_read_only(app, "Replacing textures")? {
+				return Ok(());
+			}
+
+			let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+			let hash = match editor_state.data {
+				EditorData::ResourceOverview { hash, .. } => hash,
+
+				_ => {
+					Err(anyhow!("Editor {} is not a resource overview", id))?;
+					panic!();
+				}
+			};
+
+			if let Some(game_files) = app_state.game_files.load_full()
+				&& let Some(image_path) = app
+					.dialog()
+					.file()
+					.set_title("Select replacement image")
+					.add_filter("Image file", &["png", "jpg", "jpeg"])
+					.blocking_pick_file()
+			{
+				let mut dialog = app.dialog().file().set_title("Save replacement GFXI");
+
+				if let Some(project) = app_state.project.load().as_ref() {
+					// Replacements usually go in the project's base chunk content folder
+					let content_path = project.path.join("content").join("chunk0");
+
+					dialog = dialog.set_directory(if content_path.is_dir() {
+						content_path
+					} else {
+						project.path.to_owned()
+					});
+				}
+
+				if let Some(path) = dialog
+					.set_file_name(&format!("{}.GFXI", hash))
+					.add_filter("GFXI file", &["GFXI"])
+					.blocking_save_file()
+				{
+					track_event(app, "Replace GFXI with image", None);
+
+					let (metadata, data) = extract_latest_resource(&game_files, hash)?;
+
+					let replacement = image_to_gfxi(
+						&data,
+						&fs::read(image_path.as_path().context("Invalid path")?).context("Couldn't read image")?
+					)?;
+
+					let metadata_file = RpkgResourceMeta::from_resource_metadata(metadata, false)
+						.to_binary()
+						.context("Couldn't serialise meta file")?;
+
+					fs::write(path.as_path().context("Invalid path")?, replacement)?;
+
+					fs::write(
+						path.as_path()
+							.context("Invalid path")?
+							.parent()
+							.unwrap()
+							.join(format!("{}.GFXI.meta", hash)),
+						metadata_file
+					)?;
 
-							_ => bail!("Not a valid HMLanguages resource type")
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Success,
+							title: "Image replaced".into(),
+							subtitle: format!("The replacement for {} has been saved.", hash),
+							link: None
 						}
 					)?;
 				}
@@ -0,0 +1,319 @@
+use anyhow::{Context, Result, anyhow};
+use fn_error_context::context;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::{
+	Notification, NotificationKind, finish_task,
+	json_schema::get_diagnostics,
+	model::{AppState, EditorData, EditorRequest, GlobalRequest, Request, TextEditorRequest, TextFileType},
+	send_notification, send_request, start_task
+};
+
+/// How many matches are sent to the frontend at once.
+const SEARCH_CHUNK_SIZE: usize = 500;
+
+/// The most matches listed for a search, so that searching a huge file for something common stays responsive.
+const MAX_SEARCH_MATCHES: usize = 10000;
+
+/// The most characters of a matching line shown around the match.
+const PREVIEW_LENGTH: usize = 120;
+
+/// A match of a search in a text editor.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct TextSearchMatch {
+	/// Byte offset of the match in the content, which identifies it when replacing it
+	pub offset: u32,
+
+	/// 1-based, like Monaco's
+	pub line: u32,
+
+	/// 1-based, in characters
+	pub column: u32,
+
+	/// In characters
+	pub length: u32,
+
+	/// The part of the line around the match
+	pub preview: String
+}
+
+/// Build the pattern for a search; plain text is escaped so that it's matched literally.
+#[try_fn]
+#[context("Couldn't build search pattern")]
+fn build_pattern(query: &str, regex: bool, match_case: bool) -> Result<Regex> {
+	RegexBuilder::new(&if regex { query.to_owned() } else { regex::escape(query) })
+		.case_insensitive(!match_case)
+		.multi_line(true)
+		.build()?
+}
+
+fn get_preview(line: &str, column: usize, length: usize) -> String {
+	let chars = line.chars().collect::<Vec<_>>();
+
+	let start = column.saturating_sub(PREVIEW_LENGTH / 3);
+	let end = (column + length.max(PREVIEW_LENGTH - (column - start))).min(chars.len());
+
+	format!(
+		"{}{}{}",
+		if start > 0 { "…" } else { "" },
+		chars[start..end].iter().collect::<String>().trim_end(),
+		if end < chars.len() { "…" } else { "" }
+	)
+}
+
+/// Search the stored content of a text editor, rather than the copy in Monaco, which struggles with large files. Matches
+/// are sent in chunks as they're found, tagged with the search's ID so that results of an outdated search can be
+/// ignored.
+#[try_fn]
+#[context("Couldn't search text editor {}", id)]
+pub async fn find_in_text(
+	app: &AppHandle,
+	id: Uuid,
+	search_id: Uuid,
+	query: String,
+	regex: bool,
+	match_case: bool
+) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
+
+	let EditorData::Text { ref content, .. } = editor_state.data else {
+		Err(anyhow!("Editor {} is not a text editor", id))?;
+		panic!();
+	};
+
+	if query.is_empty() {
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Text(TextEditorRequest::AddSearchResults {
+				id,
+				search_id,
+				matches: vec![],
+				done: true,
+				truncated: false
+			}))
+		)?;
+
+		return Ok(());
+	}
+
+	let pattern = match build_pattern(&query, regex, match_case) {
+		Ok(pattern) => pattern,
+
+		Err(_) => {
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Error,
+					title: "Invalid regular expression".into(),
+					subtitle: format!("{} isn't a valid regular expression.", query),
+					link: None
+				}
+			)?;
+
+			return Ok(());
+		}
+	};
+
+	let task = start_task(app, "Searching text")?;
+
+	let mut chunk = vec![];
+	let mut found = 0;
+	let mut truncated = false;
+
+	// Matches are found in order, so the line and column can be worked out by walking forward through the content
+	let mut line: u32 = 1;
+	let mut line_start = 0;
+
+	for result in pattern.find_iter(content) {
+		// Empty matches (like those of ^) aren't useful to list
+		if result.is_empty() {
+			continue;
+		}
+
+		if found == MAX_SEARCH_MATCHES {
+			truncated = true;
+			break;
+		}
+
+		let before = &content[line_start..result.start()];
+
+		line += before.matches('\n').count() as u32;
+
+		if let Some(last_line_break) = before.rfind('\n') {
+			line_start += last_line_break + 1;
+		}
+
+		let line_end = content[line_start..]
+			.find('\n')
+			.map(|x| line_start + x)
+			.unwrap_or(content.len());
+
+		let column = content[line_start..result.start()].chars().count();
+		let length = result.as_str().chars().count();
+
+		chunk.push(TextSearchMatch {
+			offset: result.start() as u32,
+			line,
+			column: column as u32 + 1,
+			length: length as u32,
+			preview: get_preview(content[line_start..line_end].trim_end_matches('\r'), column, length)
+		});
+
+		found += 1;
+
+		if chunk.len() == SEARCH_CHUNK_SIZE {
+			send_request(
+				app,
+				Request::Editor(EditorRequest::Text(TextEditorRequest::AddSearchResults {
+					id,
+					search_id,
+					matches: std::mem::take(&mut chunk),
+					done: false,
+					truncated: false
+				}))
+			)?;
+		}
+	}
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::Text(TextEditorRequest::AddSearchResults {
+			id,
+			search_id,
+			matches: chunk,
+			done: true,
+			truncated
+		}))
+	)?;
+
+	finish_task(app, task)?;
+}
+
+/// Replace matches of a search in the stored content of a text editor, either every match or only the one at the given
+/// byte offset. Replacements may refer to groups of a regular expression with `$1` or `${name}`.
+#[try_fn]
+#[context("Couldn't replace in text editor {}", id)]
+pub async fn replace_in_text(
+	app: &AppHandle,
+	id: Uuid,
+	query: String,
+	regex: bool,
+	match_case: bool,
+	replacement: String,
+	offset: Option<u32>
+) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	if query.is_empty() {
+		return Ok(());
+	}
+
+	let pattern = build_pattern(&query, regex, match_case)?;
+
+	// Plain text replacements are inserted as they are
+	let replacement = if regex {
+		replacement
+	} else {
+		replacement.replace('$', "$$")
+	};
+
+	let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+	let EditorData::Text {
+		ref mut content,
+		ref file_type
+	} = editor_state.data
+	else {
+		Err(anyhow!("Editor {} is not a text editor", id))?;
+		panic!();
+	};
+
+	let task = start_task(app, "Replacing text")?;
+
+	let (new_content, replaced) = match offset.map(|x| x as usize) {
+		Some(offset) => {
+			let Some(captures) = content
+				.is_char_boundary(offset)
+				.then(|| pattern.captures_at(content, offset))
+				.flatten()
+				.filter(|x| x.get(0).is_some_and(|x| x.start() == offset))
+			else {
+				finish_task(app, task)?;
+
+				send_notification(
+					app,
+					Notification {
+						kind: NotificationKind::Info,
+						title: "Match no longer exists".into(),
+						subtitle: "The text has changed since it was searched; search again to replace it.".into(),
+						link: None
+					}
+				)?;
+
+				return Ok(());
+			};
+
+			let whole = captures.get(0).context("No match")?;
+
+			let mut expanded = String::new();
+			captures.expand(&replacement, &mut expanded);
+
+			(
+				format!("{}{}{}", &content[..whole.start()], expanded, &content[whole.end()..]),
+				1
+			)
+		}
+
+		None => {
+			let replaced = pattern.find_iter(content).count();
+
+			(
+				pattern.replace_all(content, replacement.as_str()).into_owned(),
+				replaced
+			)
+		}
+	};
+
+	if replaced > 0 && new_content != *content {
+		*content = new_content;
+
+		send_request(
+			app,
+			Request::Editor(EditorRequest::Text(TextEditorRequest::ReplaceContent {
+				id,
+				content: content.to_owned()
+			}))
+		)?;
+
+		if let TextFileType::ManifestJson = file_type {
+			send_request(
+				app,
+				Request::Editor(EditorRequest::Text(TextEditorRequest::SetDiagnostics {
+					id,
+					diagnostics: get_diagnostics(&app_state.manifest_schema.load(), content)
+				}))
+			)?;
+		}
+
+		send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+	}
+
+	finish_task(app, task)?;
+
+	send_notification(
+		app,
+		Notification {
+			kind: NotificationKind::Info,
+			title: format!("Replaced {} match{}", replaced, if replaced == 1 { "" } else { "es" }),
+			subtitle: "Search again to see the matches which remain.".into(),
+			link: None
+		}
+	)?;
+}
@@ -1,46 +1,77 @@
-use std::{fs, ops::Deref, time::Duration};
+use std::{fs, ops::Deref};
 
 use anyhow::{Context, Result, anyhow};
 use arc_swap::ArcSwap;
 use fn_error_context::context;
 use hitman_commons::metadata::RuntimeID;
 use hitman_commons::{game::GameVersion, rpkg_tool::RpkgResourceMeta};
-use hitman_formats::ores::parse_json_ores;
-use indexmap::IndexMap;
 use itertools::Itertools;
 use quickentity_rs::{
 	apply_patch, convert_to_qn, convert_to_rt, generate_patch,
 	patch_structs::Patch,
-	qn_structs::{CommentEntity, Entity, Ref, SubEntity, SubType}
+	qn_structs::{CommentEntity, Entity}
 };
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use rpkg_rs::resource::runtime_resource_id::RuntimeResourceID;
-use serde_json::{Value, from_slice, from_str, from_value, json, to_string, to_value, to_vec};
+use serde_json::{Value, from_slice, json, to_string, to_vec};
 use tauri::{AppHandle, Manager, async_runtime};
-use tauri_plugin_aptabase::EventTracker;
-use tokio::net::TcpStream;
+use tauri_plugin_dialog::DialogExt;
 use tryvial::try_fn;
 use uuid::Uuid;
 use velcro::vec;
 
-use crate::ores_repo::UnlockableItem;
+use crate::audio_import::{find_wav_files, import_wav_files};
+use crate::audio_names::{brute_force_names, gather_audio_ids, send_audio_names, store_audio_names};
+use crate::backups::{list_backups, restore_backup};
+use crate::chunk_assignment::{record_chunk_assignment, suggest_chunk_assignment};
+use crate::collaboration::{connect_collaboration, host_collaboration, stop_collaboration};
+use crate::collections::store_collections;
+use crate::conflicts::UNLOCKABLES_HASH;
+use crate::converters::convert;
+use crate::crash_analysis::send_crash_analysis;
+use crate::diagnostics::get_session_diagnostics;
+use crate::editor_connection::auto_reconnect;
+use crate::entity_docs::{ENTITY_DOCS_ENDPOINT, EntityDocs};
+use crate::event_handling::git::handle_git_event;
+use crate::event_handling::outfit_browser::handle_outfit_browser_event;
+use crate::external_tools::launch_external_tool;
+use crate::extraction_presets::enqueue_preset_extraction;
+use crate::file_operations::{batch_rename, create_from_template, duplicate_path, empty_entity, get_batch_renames};
+use crate::hash_remapping::{remap_custom_paths, remap_hashes};
+use crate::hash_resolution::resolve_hashes;
+use crate::mod_conflicts::find_mod_conflicts;
+use crate::orphaned_files::{archive_orphaned_files, find_orphaned_files};
+use crate::packaging::package_mod;
+use crate::patches::{
+	extract_unlockables, parse_json_patch, repository_to_patchable, test_patch, unlockables_to_patchable
+};
 use crate::resourcelib::{
-	h2_convert_binary_to_blueprint, h2_convert_binary_to_factory, h3_convert_binary_to_blueprint,
+	convert_async, h2_convert_binary_to_blueprint, h2_convert_binary_to_factory, h3_convert_binary_to_blueprint,
 	h3_convert_binary_to_factory, h2016_convert_binary_to_blueprint, h2016_convert_binary_to_factory
 };
-use crate::rpkg::extract_latest_resource;
-use crate::{Notification, NotificationKind, send_notification};
+use crate::rpkg::{extract_latest_resource, get_partition_dlc};
+use crate::saved_searches::{delete_saved_search, run_saved_search, save_search};
+use crate::split_view::close_tab;
+use crate::tasks::start_cancellable_task;
+use crate::telemetry::{is_offline, set_offline_mode, track_event};
+use crate::watch_list::snapshot_new_watched_resources;
+use crate::workspace_trust::{is_project_trusted, refuse_if_untrusted, set_project_trusted};
+use crate::{MANIFEST_SCHEMA_ENDPOINT, Notification, NotificationKind, refuse_if_read_only, send_notification};
 use crate::{
 	convert_json_patch_to_merge_patch,
 	model::{
-		AppSettings, AppState, ContentSearchEvent, EditorData, EditorState, EditorType, FileBrowserEvent,
-		GameBrowserEntry, GameBrowserEvent, GameBrowserRequest, GlobalRequest, Request, SearchFilter, SettingsEvent,
-		SettingsRequest, ToolEvent, ToolRequest
+		AppSettings, AppState, AudioImportEvent, AudioImportRequest, AudioNamesEvent, AudioNamesRequest,
+		ContentSearchEvent, ConvertersEvent, ConvertersRequest, CrashAnalysisEvent, EditorData, EditorRequest,
+		EditorState, EditorType, EntityEditorRequest, EntityMetadataRequest, ExtractionQueueEvent,
+		ExtractionQueueRequest, FileBrowserEvent, FileBrowserRequest, GameBrowserEntry, GameBrowserEvent,
+		GameBrowserRequest, GlobalRequest, HashRemappingEvent, HashRemappingRequest, HashResolutionEvent,
+		HashResolutionRequest, PartitionFilter, Request, SearchFilter, SettingsEvent, SettingsRequest, ToolEvent,
+		ToolRequest
 	}
 };
 use crate::{event_handling::content_search::start_content_search, send_request};
 use crate::{finish_task, start_task};
-use crate::{general::open_in_editor, rpkg::extract_entity};
+use crate::{general::open_in_editor, rpkg::extract_entity_async};
 use crate::{
 	general::{load_game_files, open_file},
 	get_loaded_game_version
@@ -60,6 +91,22 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 				}
 			}
 
+			FileBrowserEvent::Create { .. }
+			| FileBrowserEvent::Delete(_)
+			| FileBrowserEvent::Rename { .. }
+			| FileBrowserEvent::NormaliseQNFile { .. }
+			| FileBrowserEvent::ConvertEntityToPatch { .. }
+			| FileBrowserEvent::ConvertPatchToEntity { .. }
+			| FileBrowserEvent::ConvertRepoPatchToMergePatch { .. }
+			| FileBrowserEvent::ConvertRepoPatchToJsonPatch { .. }
+			| FileBrowserEvent::ConvertUnlockablesPatchToMergePatch { .. }
+			| FileBrowserEvent::ConvertUnlockablesPatchToJsonPatch { .. }
+			| FileBrowserEvent::Duplicate { .. }
+			| FileBrowserEvent::BatchRename { .. }
+			| FileBrowserEvent::CreateFromTemplate { .. }
+			| FileBrowserEvent::AssignChunk { .. }
+				if refuse_if_read_only(app, "Changing project files")? => {}
+
 			FileBrowserEvent::Create { path, is_folder } => {
 				let task = start_task(
 					app,
@@ -84,45 +131,7 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 
 					match extension.as_ref() {
 						"entity.json" => {
-							fs::write(
-								path,
-								to_string(&Entity {
-									factory_hash: String::new(),
-									blueprint_hash: String::new(),
-									root_entity: "fffffffffffffffe".into(),
-									entities: velcro::map_iter! {
-										"fffffffffffffffe": SubEntity {
-											parent: Ref::Short(None),
-											name: "Scene".into(),
-											factory: "[modules:/zspatialentity.class].pc_entitytype".into(),
-											blueprint: "[modules:/zspatialentity.class].pc_entityblueprint".into(),
-											factory_flag: None,
-											editor_only: None,
-											properties: None,
-											platform_specific_properties: None,
-											events: None,
-											input_copying: None,
-											output_copying: None,
-											property_aliases: None,
-											exposed_entities: None,
-											exposed_interfaces: None,
-											subsets: None
-										}
-									}
-									.map(|(x, y)| (x.to_owned(), y))
-									.collect(),
-									property_overrides: vec![],
-									override_deletes: vec![],
-									pin_connection_overrides: vec![],
-									pin_connection_override_deletes: vec![],
-									external_scenes: vec![],
-									sub_type: SubType::Scene,
-									quick_entity_version: 3.1,
-									extra_factory_dependencies: vec![],
-									extra_blueprint_dependencies: vec![],
-									comments: vec![]
-								})?
-							)?;
+							fs::write(path, to_string(&empty_entity())?)?;
 						}
 
 						"repository.json" => {
@@ -166,6 +175,154 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 				finish_task(app, task)?;
 			}
 
+			FileBrowserEvent::Duplicate { path } => {
+				let task = start_task(
+					app,
+					format!("Duplicating {}", path.file_name().unwrap().to_string_lossy())
+				)?;
+
+				duplicate_path(&path)?;
+
+				finish_task(app, task)?;
+			}
+
+			FileBrowserEvent::PreviewBatchRename {
+				folder,
+				pattern,
+				replacement
+			} => {
+				let (renames, error) = match get_batch_renames(&folder, &pattern, &replacement) {
+					Ok(renames) => (renames, None),
+					Err(e) => (vec![], Some(format!("{:#}", e)))
+				};
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::FileBrowser(FileBrowserRequest::ShowBatchRenamePreview {
+						folder,
+						renames,
+						error
+					}))
+				)?;
+			}
+
+			FileBrowserEvent::BatchRename {
+				folder,
+				pattern,
+				replacement
+			} => {
+				let task = start_task(
+					app,
+					format!("Renaming files in {}", folder.file_name().unwrap().to_string_lossy())
+				)?;
+
+				track_event(app, "Batch rename files", None);
+
+				let renamed = batch_rename(&folder, &pattern, &replacement)?;
+
+				finish_task(app, task)?;
+
+				send_notification(
+					app,
+					Notification {
+						kind: NotificationKind::Success,
+						title: "Files renamed".into(),
+						subtitle: format!(
+							"{} file{} renamed.",
+							renamed,
+							if renamed == 1 { " was" } else { "s were" }
+						),
+						link: None
+					}
+				)?;
+			}
+
+			FileBrowserEvent::CreateFromTemplate { folder, name, template } => {
+				track_event(
+					app,
+					"Create file from template",
+					Some(json!({
+						"template": template
+					}))
+				);
+
+				let path = create_from_template(&folder, &name, template)?;
+
+				open_file(app, path).await?;
+			}
+
+			FileBrowserEvent::SuggestChunkAssignment { path, scenario } => {
+				let project = app_state.project.load_full().context("No project loaded")?;
+				let game_files = app_state.game_files.load_full().context("Game files not loaded")?;
+
+				let (assignment, error) = match suggest_chunk_assignment(
+					&game_files,
+					&project.path,
+					&project.settings.load().custom_paths,
+					&path,
+					&scenario
+				) {
+					Ok(assignment) => (Some(assignment), None),
+					Err(e) => (None, Some(format!("{:#}", e)))
+				};
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::FileBrowser(FileBrowserRequest::ShowChunkAssignment {
+						path,
+						assignment,
+						error
+					}))
+				)?;
+			}
+
+			FileBrowserEvent::TestPatch { path } => {
+				let task = start_task(
+					app,
+					format!(
+						"Testing {}",
+						path.file_name().context("No file name")?.to_string_lossy()
+					)
+				)?;
+
+				let (test, error) = match test_patch(app, &path).await {
+					Ok(test) => (Some(test), None),
+					Err(e) => (None, Some(format!("{:#}", e)))
+				};
+
+				finish_task(app, task)?;
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::FileBrowser(FileBrowserRequest::ShowPatchTest {
+						path,
+						test,
+						error
+					}))
+				)?;
+			}
+
+			FileBrowserEvent::AssignChunk { assignment } => {
+				let project = app_state.project.load_full().context("No project loaded")?;
+
+				track_event(app, "Assign entity to partition", None);
+
+				record_chunk_assignment(&project.path, &assignment)?;
+
+				send_notification(
+					app,
+					Notification {
+						kind: NotificationKind::Success,
+						title: "Partition assigned".into(),
+						subtitle: format!(
+							"{} will be loaded in {} (chunk{}).",
+							assignment.entity_path, assignment.partition, assignment.chunk
+						),
+						link: None
+					}
+				)?;
+			}
+
 			FileBrowserEvent::NormaliseQNFile { path } => {
 				let task = start_task(
 					app,
@@ -216,7 +373,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 							Notification {
 								kind: NotificationKind::Success,
 								title: "File normalised".into(),
-								subtitle: "The entity file has been re-saved in canonical format.".into()
+								subtitle: "The entity file has been re-saved in canonical format.".into(),
+								link: None
 							}
 						)?;
 					}
@@ -229,13 +387,14 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 							&& let Some(install) = app_settings.load().game_install.as_ref()
 							&& let Some(hash_list) = app_state.hash_list.load().as_ref()
 						{
-							let mut entity = extract_entity(
+							let mut entity = extract_entity_async(
 								game_files,
 								&app_state.cached_entities,
 								get_loaded_game_version(app, install)?,
 								hash_list,
 								RuntimeID::from_any(&patch.factory_hash)?
-							)?
+							)
+							.await?
 							.to_owned();
 
 							let base = entity.to_owned();
@@ -278,7 +437,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 								Notification {
 									kind: NotificationKind::Success,
 									title: "File normalised".into(),
-									subtitle: "The patch file has been re-saved in canonical format.".into()
+									subtitle: "The patch file has been re-saved in canonical format.".into(),
+									link: None
 								}
 							)?;
 						} else {
@@ -288,7 +448,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 									kind: NotificationKind::Error,
 									title: "No game selected".into(),
 									subtitle: "You can't normalise patch files without a copy of the game selected."
-										.into()
+										.into(),
+									link: None
 								}
 							)?;
 						}
@@ -332,17 +493,20 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 					let (temp_meta, temp_data) =
 						extract_latest_resource(game_files, RuntimeID::from_any(&entity.factory_hash)?)?;
 
-					let factory = match game_version {
-						GameVersion::H1 => h2016_convert_binary_to_factory(&temp_data)
-							.context("Couldn't convert binary data to ResourceLib factory")?
-							.into_modern(),
+					let factory = convert_async(move || {
+						Ok(match game_version {
+							GameVersion::H1 => h2016_convert_binary_to_factory(&temp_data)
+								.context("Couldn't convert binary data to ResourceLib factory")?
+								.into_modern(),
 
-						GameVersion::H2 => h2_convert_binary_to_factory(&temp_data)
-							.context("Couldn't convert binary data to ResourceLib factory")?,
+							GameVersion::H2 => h2_convert_binary_to_factory(&temp_data)
+								.context("Couldn't convert binary data to ResourceLib factory")?,
 
-						GameVersion::H3 => h3_convert_binary_to_factory(&temp_data)
-							.context("Couldn't convert binary data to ResourceLib factory")?
-					};
+							GameVersion::H3 => h3_convert_binary_to_factory(&temp_data)
+								.context("Couldn't convert binary data to ResourceLib factory")?
+						})
+					})
+					.await?;
 
 					let blueprint_hash = temp_meta
 						.core_info
@@ -354,17 +518,20 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 
 					let (tblu_meta, tblu_data) = extract_latest_resource(game_files, blueprint_hash)?;
 
-					let blueprint = match game_version {
-						GameVersion::H1 => h2016_convert_binary_to_blueprint(&tblu_data)
-							.context("Couldn't convert binary data to ResourceLib blueprint")?
-							.into_modern(),
+					let blueprint = convert_async(move || {
+						Ok(match game_version {
+							GameVersion::H1 => h2016_convert_binary_to_blueprint(&tblu_data)
+								.context("Couldn't convert binary data to ResourceLib blueprint")?
+								.into_modern(),
 
-						GameVersion::H2 => h2_convert_binary_to_blueprint(&tblu_data)
-							.context("Couldn't convert binary data to ResourceLib blueprint")?,
+							GameVersion::H2 => h2_convert_binary_to_blueprint(&tblu_data)
+								.context("Couldn't convert binary data to ResourceLib blueprint")?,
 
-						GameVersion::H3 => h3_convert_binary_to_blueprint(&tblu_data)
-							.context("Couldn't convert binary data to ResourceLib blueprint")?
-					};
+							GameVersion::H3 => h3_convert_binary_to_blueprint(&tblu_data)
+								.context("Couldn't convert binary data to ResourceLib blueprint")?
+						})
+					})
+					.await?;
 
 					let base = convert_to_qn(
 						&factory,
@@ -399,7 +566,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 						Notification {
 							kind: NotificationKind::Success,
 							title: "File converted to patch".into(),
-							subtitle: "The entity.json file has been converted into a patch file.".into()
+							subtitle: "The entity.json file has been converted into a patch file.".into(),
+							link: None
 						}
 					)?;
 				} else {
@@ -409,7 +577,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 							kind: NotificationKind::Error,
 							title: "No game selected".into(),
 							subtitle: "You can't convert between entity and patch without a copy of the game selected."
-								.into()
+								.into(),
+							link: None
 						}
 					)?;
 				}
@@ -423,13 +592,14 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 					&& let Some(install) = app_settings.load().game_install.as_ref()
 					&& let Some(hash_list) = app_state.hash_list.load().as_ref()
 				{
-					let mut entity = extract_entity(
+					let mut entity = extract_entity_async(
 						game_files,
 						&app_state.cached_entities,
 						get_loaded_game_version(app, install)?,
 						hash_list,
 						RuntimeID::from_any(&patch.factory_hash)?
-					)?
+					)
+					.await?
 					.to_owned();
 
 					apply_patch(&mut entity, patch, true).map_err(|x| anyhow!("QuickEntity error: {:?}", x))?;
@@ -471,7 +641,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 						Notification {
 							kind: NotificationKind::Success,
 							title: "File converted to entity.json".into(),
-							subtitle: "The patch file has been converted into an entity.json file.".into()
+							subtitle: "The patch file has been converted into an entity.json file.".into(),
+							link: None
 						}
 					)?;
 				} else {
@@ -481,7 +652,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 							kind: NotificationKind::Error,
 							title: "No game selected".into(),
 							subtitle: "You can't convert between entity and patch without a copy of the game selected."
-								.into()
+								.into(),
+							link: None
 						}
 					)?;
 				}
@@ -497,24 +669,14 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 					== "REPO"
 				{
 					if let Some(cached_repository) = app_state.repository.load().as_ref() {
-						let mut current = to_value(
-							cached_repository
-								.iter()
-								.cloned()
-								.map(|x| (x.id, x.data))
-								.collect::<IndexMap<Uuid, IndexMap<String, Value>>>()
-						)?;
+						let mut current = repository_to_patchable(cached_repository)?;
 
 						let base = current.to_owned();
 
-						let patch: Vec<json_patch::PatchOperation> = from_value(
-							from_slice::<Value>(&fs::read(&path).context("Couldn't read file")?)
+						let patch = parse_json_patch(
+							&from_slice::<Value>(&fs::read(&path).context("Couldn't read file")?)
 								.context("Invalid JSON")?
-								.get("patch")
-								.context("No patch key")?
-								.to_owned()
-						)
-						.context("Invalid JSON patch")?;
+						)?;
 
 						json_patch::patch(&mut current, &patch)?;
 
@@ -542,7 +704,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 							Notification {
 								kind: NotificationKind::Success,
 								title: "File converted to repository.json".into(),
-								subtitle: "The patch file has been converted into a repository.json file.".into()
+								subtitle: "The patch file has been converted into a repository.json file.".into(),
+								link: None
 							}
 						)?;
 					} else {
@@ -553,7 +716,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 								title: "No game selected".into(),
 								subtitle: "You can't convert between patch formats without a copy of the game \
 								           selected."
-									.into()
+									.into(),
+								link: None
 							}
 						)?;
 					}
@@ -565,7 +729,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 							title: "Not a repository patch".into(),
 							subtitle: "This patch is for a different type of file, so it can't be converted to a \
 							           repository.json file."
-								.into()
+								.into(),
+							link: None
 						}
 					)?;
 				}
@@ -573,13 +738,7 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 
 			FileBrowserEvent::ConvertRepoPatchToJsonPatch { path } => {
 				if let Some(cached_repository) = app_state.repository.load().as_ref() {
-					let mut current = to_value(
-						cached_repository
-							.iter()
-							.cloned()
-							.map(|x| (x.id, x.data))
-							.collect::<IndexMap<Uuid, IndexMap<String, Value>>>()
-					)?;
+					let mut current = repository_to_patchable(cached_repository)?;
 
 					let base = current.to_owned();
 
@@ -615,7 +774,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 						Notification {
 							kind: NotificationKind::Success,
 							title: "File converted to JSON.patch.json".into(),
-							subtitle: "The patch file has been converted into a JSON.patch.json file.".into()
+							subtitle: "The patch file has been converted into a JSON.patch.json file.".into(),
+							link: None
 						}
 					)?;
 				} else {
@@ -625,7 +785,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 							kind: NotificationKind::Error,
 							title: "No game selected".into(),
 							subtitle: "You can't convert between patch formats without a copy of the game selected."
-								.into()
+								.into(),
+							link: None
 						}
 					)?;
 				}
@@ -638,43 +799,17 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 					.context("Patch had no file key")?
 					.as_str()
 					.context("File key was not string")?
-					== "0057C2C3941115CA"
+					== UNLOCKABLES_HASH
 				{
 					if let Some(game_files) = app_state.game_files.load().as_ref() {
-						let mut current = to_value(
-							from_str::<Vec<UnlockableItem>>(&parse_json_ores(
-								&extract_latest_resource(game_files, "0057C2C3941115CA".parse::<RuntimeID>()?)?.1
-							)?)?
-							.into_iter()
-							.map(|x| {
-								(
-									x.data
-										.get("Id")
-										.expect("Unlockable did not have Id")
-										.as_str()
-										.expect("Id was not string")
-										.to_owned(),
-									{
-										let mut y = IndexMap::new();
-										y.insert("Guid".into(), to_value(x.id).unwrap());
-										y.extend(x.data.into_iter().filter(|(key, _)| key != "Id"));
-										y
-									}
-								)
-							})
-							.collect::<IndexMap<String, IndexMap<String, Value>>>()
-						)?;
+						let mut current = unlockables_to_patchable(&extract_unlockables(game_files)?)?;
 
 						let base = current.to_owned();
 
-						let patch: Vec<json_patch::PatchOperation> = from_value(
-							from_slice::<Value>(&fs::read(&path).context("Couldn't read file")?)
+						let patch = parse_json_patch(
+							&from_slice::<Value>(&fs::read(&path).context("Couldn't read file")?)
 								.context("Invalid JSON")?
-								.get("patch")
-								.context("No patch key")?
-								.to_owned()
-						)
-						.context("Invalid JSON patch")?;
+						)?;
 
 						json_patch::patch(&mut current, &patch)?;
 
@@ -702,7 +837,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 							Notification {
 								kind: NotificationKind::Success,
 								title: "File converted to unlockables.json".into(),
-								subtitle: "The patch file has been converted into a unlockables.json file.".into()
+								subtitle: "The patch file has been converted into a unlockables.json file.".into(),
+								link: None
 							}
 						)?;
 					} else {
@@ -713,7 +849,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 								title: "No game selected".into(),
 								subtitle: "You can't convert between patch formats without a copy of the game \
 								           selected."
-									.into()
+									.into(),
+								link: None
 							}
 						)?;
 					}
@@ -725,7 +862,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 							title: "Not an unlockables patch".into(),
 							subtitle: "This patch is for a different type of file, so it can't be converted to a \
 							           unlockables.json file."
-								.into()
+								.into(),
+							link: None
 						}
 					)?;
 				}
@@ -733,29 +871,7 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 
 			FileBrowserEvent::ConvertUnlockablesPatchToJsonPatch { path } => {
 				if let Some(game_files) = app_state.game_files.load().as_ref() {
-					let mut current = to_value(
-						from_str::<Vec<UnlockableItem>>(&parse_json_ores(
-							&extract_latest_resource(game_files, "0057C2C3941115CA".parse::<RuntimeID>()?)?.1
-						)?)?
-						.into_iter()
-						.map(|x| {
-							(
-								x.data
-									.get("Id")
-									.expect("Unlockable did not have Id")
-									.as_str()
-									.expect("Id was not string")
-									.to_owned(),
-								{
-									let mut y = IndexMap::new();
-									y.insert("Guid".into(), to_value(x.id).unwrap());
-									y.extend(x.data.into_iter().filter(|(key, _)| key != "Id"));
-									y
-								}
-							)
-						})
-						.collect::<IndexMap<String, IndexMap<String, Value>>>()
-					)?;
+					let mut current = unlockables_to_patchable(&extract_unlockables(game_files)?)?;
 
 					let base = current.to_owned();
 
@@ -791,7 +907,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 						Notification {
 							kind: NotificationKind::Success,
 							title: "File converted to JSON.patch.json".into(),
-							subtitle: "The patch file has been converted into a JSON.patch.json file.".into()
+							subtitle: "The patch file has been converted into a JSON.patch.json file.".into(),
+							link: None
 						}
 					)?;
 				} else {
@@ -801,7 +918,63 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 							kind: NotificationKind::Error,
 							title: "No game selected".into(),
 							subtitle: "You can't convert between patch formats without a copy of the game selected."
-								.into()
+								.into(),
+							link: None
+						}
+					)?;
+				}
+			}
+
+			FileBrowserEvent::OpenWithExternalTool { path, tool } => {
+				if refuse_if_untrusted(app, "Opening a file with an external tool")? {
+					return Ok(());
+				}
+
+				let settings = app_settings.load();
+
+				let tool = settings
+					.external_tools
+					.iter()
+					.find(|x| x.name == tool)
+					.context("No such external tool")?;
+
+				track_event(app, "Open file with external tool", None);
+
+				launch_external_tool(app, tool, None, Some(&path.to_string_lossy()))?;
+			}
+
+			FileBrowserEvent::RequestBackups { path } => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					send_request(
+						app,
+						Request::Tool(ToolRequest::FileBrowser(FileBrowserRequest::ShowBackups {
+							backups: list_backups(&project.path, &path)?,
+							path
+						}))
+					)?;
+				}
+			}
+
+			FileBrowserEvent::RestoreBackup { path, timestamp } => {
+				if refuse_if_read_only(app, "Restoring a previous version")? {
+					return Ok(());
+				}
+
+				if let Some(project) = app_state.project.load().as_ref() {
+					track_event(app, "Restore previous version of file", None);
+
+					restore_backup(&project.path, &path, timestamp, project.settings.load().backup_count)?;
+
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Success,
+							title: "Previous version restored".into(),
+							subtitle: "The file's contents before restoring have been kept as a backup, so this can \
+							           be undone from the same menu. Reopen the file to see the restored version if \
+							           it's already open."
+								.into(),
+							link: None
 						}
 					)?;
 				}
@@ -816,7 +989,10 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 					id.to_owned(),
 					EditorState {
 						file: None,
-						data: EditorData::ResourceOverview { hash: hash.to_owned() }
+						data: EditorData::ResourceOverview {
+							hash: hash.to_owned(),
+							generic_rl_json: None
+						}
 					}
 				);
 
@@ -830,7 +1006,7 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 				)?;
 			}
 
-			GameBrowserEvent::Search(query, filter) => {
+			GameBrowserEvent::Search(query, filter, partition_filter) => {
 				let task = start_task(app, format!("Searching game files for {}", query))?;
 
 				if let Some(install) = app_settings.load().game_install.as_ref()
@@ -856,6 +1032,31 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 
 					let query_terms = query.split(' ').collect_vec();
 
+					// The partition and DLC of a resource, or None if its partition is excluded by the filter
+					let get_partition = |hash: RuntimeID| {
+						let rrid = RuntimeResourceID::from(hash);
+
+						let partition = game_files.partitions.iter().find(|x| x.contains(&rrid)).unwrap();
+
+						let dlc = get_partition_dlc(partition);
+
+						let included = match &partition_filter {
+							PartitionFilter::All => true,
+							PartitionFilter::BaseGame => dlc.is_none(),
+							PartitionFilter::Partition(id) => partition.partition_info().id.to_string() == *id
+						};
+
+						included.then(|| {
+							(
+								(
+									partition.partition_info().id.to_string(),
+									partition.partition_info().name.to_owned().unwrap_or("<unnamed>".into())
+								),
+								dlc
+							)
+						})
+					};
+
 					if let Some(hash_list) = app_state.hash_list.load().deref() {
 						send_request(
 							app,
@@ -890,29 +1091,17 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 													s.contains(y)
 												})
 											})
-											.map(|(&hash, entry)| GameBrowserEntry {
-												hash,
-												path: entry.path.to_owned(),
-												hint: entry.hint.to_owned(),
-												filetype: entry.resource_type,
-												partition: {
-													let rrid = RuntimeResourceID::from(hash);
-
-													let partition = game_files
-														.partitions
-														.iter()
-														.find(|x| x.contains(&rrid))
-														.unwrap();
-
-													(
-														partition.partition_info().id.to_string(),
-														partition
-															.partition_info()
-															.name
-															.to_owned()
-															.unwrap_or("<unnamed>".into())
-													)
-												}
+											.filter_map(|(&hash, entry)| {
+												let (partition, dlc) = get_partition(hash)?;
+
+												Some(GameBrowserEntry {
+													hash,
+													path: entry.path.to_owned(),
+													hint: entry.hint.to_owned(),
+													filetype: entry.resource_type,
+													partition,
+													dlc
+												})
 											})
 											.collect()
 									} else {
@@ -938,29 +1127,17 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 													s.contains(y)
 												})
 											})
-											.map(|(&hash, entry)| GameBrowserEntry {
-												hash,
-												path: entry.path.to_owned(),
-												hint: entry.hint.to_owned(),
-												filetype: entry.resource_type,
-												partition: {
-													let rrid = RuntimeResourceID::from(hash);
-
-													let partition = game_files
-														.partitions
-														.iter()
-														.find(|x| x.contains(&rrid))
-														.unwrap();
-
-													(
-														partition.partition_info().id.to_string(),
-														partition
-															.partition_info()
-															.name
-															.to_owned()
-															.unwrap_or("<unnamed>".into())
-													)
-												}
+											.filter_map(|(&hash, entry)| {
+												let (partition, dlc) = get_partition(hash)?;
+
+												Some(GameBrowserEntry {
+													hash,
+													path: entry.path.to_owned(),
+													hint: entry.hint.to_owned(),
+													filetype: entry.resource_type,
+													partition,
+													dlc
+												})
 											})
 											.collect()
 									}
@@ -981,11 +1158,97 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 					open_in_editor(app, game_files, install, hash_list, hash).await?;
 				}
 			}
+
+			GameBrowserEvent::RemoveFromCollection { collection, hash } => {
+				let mut collections = (*app_state.collections.load_full()).to_owned();
+
+				if let Some(hashes) = collections.get_mut(&collection) {
+					hashes.retain(|x| *x != hash);
+				}
+
+				store_collections(app, collections)?;
+			}
+
+			GameBrowserEvent::DeleteCollection(collection) => {
+				let mut collections = (*app_state.collections.load_full()).to_owned();
+
+				collections.shift_remove(&collection);
+
+				store_collections(app, collections)?;
+			}
+
+			GameBrowserEvent::ExtractCollection(collection) => {
+				if let Some(game_files) = app_state.game_files.load_full()
+					&& let Some(hash_list) = app_state.hash_list.load_full()
+					&& let Some(hashes) = app_state.collections.load().get(&collection)
+				{
+					let hashes = hashes.to_owned();
+
+					let mut dialog = app
+						.dialog()
+						.file()
+						.set_title(format!("Extract {} to folder", collection));
+
+					if let Some(project) = app_state.project.load().as_ref() {
+						dialog = dialog.set_directory(&project.path);
+					}
+
+					if let Some(path) = dialog.blocking_pick_folder() {
+						let path = path.into_path()?;
+						let app = app.clone();
+
+						app_state.extraction_queue.enqueue(
+							format!("Extract collection {}", collection),
+							move |progress| async move {
+								for (index, hash) in hashes.iter().enumerate() {
+									progress.check_cancelled()?;
+									progress.set_progress(index as u32, hashes.len() as u32)?;
+
+									let Some(entry) = hash_list.entries.get(hash) else {
+										continue;
+									};
+
+									let (metadata, data) = extract_latest_resource(&game_files, *hash)?;
+
+									fs::write(path.join(format!("{}.{}", hash, entry.resource_type)), data)?;
+
+									fs::write(
+										path.join(format!("{}.{}.meta", hash, entry.resource_type)),
+										RpkgResourceMeta::from_resource_metadata(metadata, false)
+											.to_binary()
+											.context("Couldn't serialise meta file")?
+									)?;
+								}
+
+								send_notification(
+									&app,
+									Notification {
+										kind: NotificationKind::Success,
+										title: "Collection extracted".into(),
+										subtitle: format!("{} files have been extracted.", hashes.len()),
+										link: None
+									}
+								)?;
+
+								anyhow::Ok(())
+							}
+						)?;
+					}
+				}
+			}
+
+			GameBrowserEvent::ExtractWithPreset { hash, preset } => {
+				track_event(app, "Extract resource with preset from game tree", None);
+
+				enqueue_preset_extraction(app, hash, &preset)?;
+			}
 		},
 
 		ToolEvent::Settings(event) => match event {
 			SettingsEvent::Initialise => {
-				if let Ok(req) = reqwest::get("https://hitman-resources.netlify.app/glacierkit/dynamics.json").await {
+				if !is_offline()
+					&& let Ok(req) = reqwest::get("https://hitman-resources.netlify.app/glacierkit/dynamics.json").await
+				{
 					send_request(
 						app,
 						Request::Global(GlobalRequest::InitialiseDynamics {
@@ -995,6 +1258,38 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 					)?;
 				}
 
+				if !is_offline()
+					&& let Ok(req) = reqwest::get(MANIFEST_SCHEMA_ENDPOINT).await
+					&& let Ok(data) = req.bytes().await
+					&& let Ok(schema) = from_slice::<Value>(&data)
+				{
+					fs::write(
+						app.path()
+							.app_data_dir()
+							.context("Couldn't get app data dir")?
+							.join("manifest_schema.json"),
+						data
+					)?;
+
+					app_state.manifest_schema.store(schema.into());
+				}
+
+				if !is_offline()
+					&& let Ok(req) = reqwest::get(ENTITY_DOCS_ENDPOINT).await
+					&& let Ok(data) = req.bytes().await
+					&& let Ok(docs) = from_slice::<EntityDocs>(&data)
+				{
+					fs::write(
+						app.path()
+							.app_data_dir()
+							.context("Couldn't get app data dir")?
+							.join("entity_docs.json"),
+						data
+					)?;
+
+					app_state.entity_docs.store(docs.into());
+				}
+
 				let selected_install_info = app_settings
 					.load()
 					.game_install
@@ -1009,7 +1304,8 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 					})
 					.unwrap_or("None".into());
 
-				app.track_event(
+				track_event(
+					app,
 					"App initialised",
 					Some(json!({
 						"game_installs": app_state.game_installs.len(),
@@ -1018,8 +1314,7 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 						"editor_connection": app_settings.load().editor_connection,
 						"selected_install": selected_install_info
 					}))
-				)
-				.unwrap();
+				);
 
 				send_request(
 					app,
@@ -1029,6 +1324,45 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 					}))
 				)?;
 
+				send_request(
+					app,
+					Request::Tool(ToolRequest::Settings(SettingsRequest::SetEditorConnectionState {
+						games: app_state.editor_connection.get_diagnostics().await,
+						target: app_state.editor_connection.get_target().await
+					}))
+				)?;
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::Settings(SettingsRequest::SetCollaborationStatus(
+						app_state.collaboration.status()
+					)))
+				)?;
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::FileBrowser(FileBrowserRequest::SetExternalTools(
+						app_settings
+							.load()
+							.external_tools
+							.iter()
+							.map(|x| x.name.to_owned())
+							.collect()
+					)))
+				)?;
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::GameBrowser(GameBrowserRequest::SetExtractionPresets(
+						app_settings
+							.load()
+							.extraction_presets
+							.iter()
+							.map(|x| x.name.to_owned())
+							.collect()
+					)))
+				)?;
+
 				if app
 					.path()
 					.app_log_dir()
@@ -1042,23 +1376,7 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 
 				load_game_files(app).await?;
 
-				let app = app.clone();
-
-				async_runtime::spawn(async move {
-					let mut interval = tokio::time::interval(Duration::from_secs(10));
-
-					loop {
-						interval.tick().await;
-
-						// Attempt to connect every 10 seconds
-						if app.state::<ArcSwap<AppSettings>>().load().editor_connection
-							&& !app.state::<AppState>().editor_connection.is_connected().await
-							&& TcpStream::connect("localhost:46735").await.is_ok()
-						{
-							let _ = app.state::<AppState>().editor_connection.connect().await;
-						}
-					}
-				});
+				async_runtime::spawn(auto_reconnect(app.clone()));
 			}
 
 			SettingsEvent::ChangeGameInstall(path) => {
@@ -1079,6 +1397,53 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 				}
 			}
 
+			SettingsEvent::ReconnectEditorConnection => {
+				track_event(app, "Reconnect editor connection manually", None);
+
+				app_state.editor_connection.reconnect_now().await;
+			}
+
+			SettingsEvent::ChangeEditorConnectionGames(value) => {
+				let mut settings = (*app_settings.load_full()).to_owned();
+				settings.editor_connection_games = value;
+				fs::write(
+					app.path()
+						.app_data_dir()
+						.context("Couldn't get app data dir")?
+						.join("settings.json"),
+					to_vec(&settings)?
+				)?;
+				app_settings.store(settings.into());
+
+				// The auto-reconnect loop picks up the new list of games
+				app_state.editor_connection.reconnect_now().await;
+			}
+
+			SettingsEvent::SetEditorConnectionTarget(target) => {
+				app_state.editor_connection.set_target(target).await?;
+			}
+
+			SettingsEvent::HostCollaboration {
+				name,
+				secret,
+				interface,
+				port
+			} => {
+				track_event(app, "Host collaboration", None);
+
+				host_collaboration(app, name, secret, interface, port)?;
+			}
+
+			SettingsEvent::ConnectCollaboration { name, secret, address } => {
+				track_event(app, "Connect to collaboration", None);
+
+				connect_collaboration(app, name, secret, address)?;
+			}
+
+			SettingsEvent::StopCollaboration => {
+				stop_collaboration(app)?;
+			}
+
 			SettingsEvent::ChangeExtractModdedFiles(value) => {
 				let mut settings = (*app_settings.load_full()).to_owned();
 				settings.extract_modded_files = value;
@@ -1105,14 +1470,24 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 				app_settings.store(settings.into());
 			}
 
+			SettingsEvent::ChangeOfflineMode(value) => {
+				let mut settings = (*app_settings.load_full()).to_owned();
+				settings.offline_mode = value;
+				fs::write(
+					app.path()
+						.app_data_dir()
+						.context("Couldn't get app data dir")?
+						.join("settings.json"),
+					to_vec(&settings)?
+				)?;
+				app_settings.store(settings.into());
+				set_offline_mode(value);
+			}
+
 			SettingsEvent::ChangeEditorConnection(value) => {
 				let mut settings = (*app_settings.load_full()).to_owned();
 				settings.editor_connection = value;
 
-				if !value && app_state.editor_connection.is_connected().await {
-					app_state.editor_connection.disconnect().await?;
-				}
-
 				fs::write(
 					app.path()
 						.app_data_dir()
@@ -1121,11 +1496,181 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 					to_vec(&settings)?
 				)?;
 				app_settings.store(settings.into());
+
+				// Every game is disconnected, not just those that commands are sent to
+				if !value {
+					app_state.editor_connection.disconnect().await?;
+				}
+			}
+
+			SettingsEvent::ChangeExternalTools(value) => {
+				let mut settings = (*app_settings.load_full()).to_owned();
+				settings.external_tools = value;
+				fs::write(
+					app.path()
+						.app_data_dir()
+						.context("Couldn't get app data dir")?
+						.join("settings.json"),
+					to_vec(&settings)?
+				)?;
+				app_settings.store(settings.into());
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::FileBrowser(FileBrowserRequest::SetExternalTools(
+						app_settings
+							.load()
+							.external_tools
+							.iter()
+							.map(|x| x.name.to_owned())
+							.collect()
+					)))
+				)?;
+			}
+
+			SettingsEvent::ChangeExtractionPresets(value) => {
+				let mut settings = (*app_settings.load_full()).to_owned();
+				settings.extraction_presets = value;
+				fs::write(
+					app.path()
+						.app_data_dir()
+						.context("Couldn't get app data dir")?
+						.join("settings.json"),
+					to_vec(&settings)?
+				)?;
+				app_settings.store(settings.into());
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::GameBrowser(GameBrowserRequest::SetExtractionPresets(
+						app_settings
+							.load()
+							.extraction_presets
+							.iter()
+							.map(|x| x.name.to_owned())
+							.collect()
+					)))
+				)?;
+			}
+
+			SettingsEvent::ChangeWemEncoder(value) => {
+				let mut settings = (*app_settings.load_full()).to_owned();
+				settings.wem_encoder = value;
+				fs::write(
+					app.path()
+						.app_data_dir()
+						.context("Couldn't get app data dir")?
+						.join("settings.json"),
+					to_vec(&settings)?
+				)?;
+				app_settings.store(settings.into());
+			}
+
+			SettingsEvent::ChangeEntityCacheMaxEntries(value) => {
+				let mut settings = (*app_settings.load_full()).to_owned();
+				settings.entity_cache_max_entries = value;
+				fs::write(
+					app.path()
+						.app_data_dir()
+						.context("Couldn't get app data dir")?
+						.join("settings.json"),
+					to_vec(&settings)?
+				)?;
+				app_settings.store(settings.into());
+
+				app_state
+					.cached_entities
+					.set_limits(value, app_settings.load().entity_cache_max_memory);
+			}
+
+			SettingsEvent::ChangeEntityCacheMaxMemory(value) => {
+				let mut settings = (*app_settings.load_full()).to_owned();
+				settings.entity_cache_max_memory = value;
+				fs::write(
+					app.path()
+						.app_data_dir()
+						.context("Couldn't get app data dir")?
+						.join("settings.json"),
+					to_vec(&settings)?
+				)?;
+				app_settings.store(settings.into());
+
+				app_state
+					.cached_entities
+					.set_limits(app_settings.load().entity_cache_max_entries, value);
+			}
+
+			SettingsEvent::ChangeWatchedResources(value) => {
+				let mut settings = (*app_settings.load_full()).to_owned();
+				settings.watched_resources = value
+					.iter()
+					.filter(|x| !x.trim().is_empty())
+					.map(|x| {
+						RuntimeID::from_any(x.trim()).with_context(|| format!("{} is not a valid hash or path", x))
+					})
+					.collect::<Result<_>>()?;
+				fs::write(
+					app.path()
+						.app_data_dir()
+						.context("Couldn't get app data dir")?
+						.join("settings.json"),
+					to_vec(&settings)?
+				)?;
+				app_settings.store(settings.into());
+
+				if let Some(install) = app_settings.load().game_install.as_ref() {
+					snapshot_new_watched_resources(app, get_loaded_game_version(app, install)?)?;
+				}
+			}
+
+			SettingsEvent::ClearCaches => {
+				let (entities, memory) = app_state.cached_entities.clear();
+
+				if let Some(intellisense) = app_state.intellisense.load().as_ref() {
+					intellisense.cppt_properties.clear();
+					intellisense.matt_properties.clear();
+				}
+
+				track_event(
+					app,
+					"Clear caches",
+					Some(json!({
+						"entities": entities,
+						"memory": memory
+					}))
+				);
+
+				send_notification(
+					app,
+					Notification {
+						kind: NotificationKind::Success,
+						title: "Caches cleared".into(),
+						subtitle: format!(
+							"{} cached entities were cleared, freeing roughly {:.1} MB.",
+							entities,
+							memory as f64 / 1024.0 / 1024.0
+						),
+						link: None
+					}
+				)?;
+			}
+
+			SettingsEvent::GetDiagnostics => {
+				let task = start_task(app, "Measuring memory usage")?;
+
+				let diagnostics = get_session_diagnostics(&app_state);
+
+				finish_task(app, task)?;
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::Settings(SettingsRequest::ShowDiagnostics(diagnostics)))
+				)?;
 			}
 
 			SettingsEvent::ChangeCustomPaths(value) => {
 				if let Some(project) = app_state.project.load().as_ref() {
-					app.track_event("Edit custom paths list manually", None).unwrap();
+					track_event(app, "Edit custom paths list manually", None);
 
 					let mut settings = (*project.settings.load_full()).to_owned();
 					settings.custom_paths = value;
@@ -1133,11 +1678,707 @@ pub async fn handle_tool_event(app: &AppHandle, event: ToolEvent) -> Result<()>
 					project.settings.store(settings.into());
 				}
 			}
+
+			SettingsEvent::ChangePackageIgnore(value) => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					let mut settings = (*project.settings.load_full()).to_owned();
+					settings.package_ignore = value;
+					fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+					project.settings.store(settings.into());
+				}
+			}
+
+			SettingsEvent::ChangeAudioNamePrefixes(value) => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					let mut settings = (*project.settings.load_full()).to_owned();
+					settings.audio_name_prefixes = value;
+					fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+					project.settings.store(settings.into());
+				}
+			}
+
+			SettingsEvent::ChangeLintEntityNames(value) => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					let mut settings = (*project.settings.load_full()).to_owned();
+					settings.lint_entity_names = value;
+					fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+					project.settings.store(settings.into());
+				}
+			}
+
+			SettingsEvent::ChangeEntityNamePatterns(value) => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					let mut settings = (*project.settings.load_full()).to_owned();
+					settings.entity_name_patterns = value;
+					fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+					project.settings.store(settings.into());
+				}
+			}
+
+			SettingsEvent::ChangeTidyEntitiesOnSave(value) => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					let mut settings = (*project.settings.load_full()).to_owned();
+					settings.tidy_entities_on_save = value;
+					fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+					project.settings.store(settings.into());
+				}
+			}
+
+			SettingsEvent::ChangeLocalisationLanguages(value) => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					let mut settings = (*project.settings.load_full()).to_owned();
+					settings.localisation_languages = value;
+					fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+					project.settings.store(settings.into());
+				}
+			}
+
+			SettingsEvent::ChangeReadOnly(value) => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					track_event(
+						app,
+						"Change project read-only mode",
+						Some(json!({
+							"read_only": value
+						}))
+					);
+
+					let mut settings = (*project.settings.load_full()).to_owned();
+					settings.read_only = value;
+					fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+					project.settings.store(settings.into());
+				}
+			}
+
+			SettingsEvent::ChangeProjectTrusted(value) => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					track_event(
+						app,
+						"Change project trust",
+						Some(json!({
+							"trusted": value
+						}))
+					);
+
+					set_project_trusted(app, project.path.to_owned(), value)?;
+				}
+			}
+
+			SettingsEvent::ChangeBackupCount(value) => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					let mut settings = (*project.settings.load_full()).to_owned();
+					settings.backup_count = value;
+					fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+					project.settings.store(settings.into());
+				}
+			}
+
+			SettingsEvent::ChangePatchSplitMaxOperations(value) => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					let mut settings = (*project.settings.load_full()).to_owned();
+					settings.patch_split_max_operations = value;
+					fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+					project.settings.store(settings.into());
+				}
+			}
+
+			SettingsEvent::ChangePatchSplitMaxSize(value) => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					let mut settings = (*project.settings.load_full()).to_owned();
+					settings.patch_split_max_size = value;
+					fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+					project.settings.store(settings.into());
+				}
+			}
+
+			SettingsEvent::PackageMod(bump) => {
+				if refuse_if_read_only(app, "Packaging the mod")? {
+					return Ok(());
+				}
+
+				if let Some(project) = app_state.project.load().as_ref() {
+					track_event(app, "Package mod", None);
+
+					let task = start_task(app, "Packaging mod")?;
+
+					// The changelog is generated by running Git in the project, which its config could hijack
+					let archive_path = package_mod(
+						&project.path,
+						&project.settings.load().package_ignore,
+						bump,
+						is_project_trusted(app)
+					)?;
+
+					finish_task(app, task)?;
+
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Success,
+							title: "Mod packaged".into(),
+							subtitle: format!("The mod has been packaged to {}.", archive_path.display()),
+							link: None
+						}
+					)?;
+				}
+			}
+
+			SettingsEvent::FindOrphanedFiles => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					track_event(app, "Find orphaned files", None);
+
+					let task = start_task(app, "Finding orphaned files")?;
+
+					let files = find_orphaned_files(&project.path, &project.settings.load().package_ignore)?;
+
+					finish_task(app, task)?;
+
+					send_request(
+						app,
+						Request::Tool(ToolRequest::Settings(SettingsRequest::SetOrphanedFiles(files)))
+					)?;
+				}
+			}
+
+			SettingsEvent::ArchiveOrphanedFiles(files) => {
+				if refuse_if_read_only(app, "Archiving orphaned files")? {
+					return Ok(());
+				}
+
+				if let Some(project) = app_state.project.load().as_ref() {
+					track_event(
+						app,
+						"Archive orphaned files",
+						Some(json!({
+							"files": files.len()
+						}))
+					);
+
+					let archive_path = archive_orphaned_files(&project.path, &files)?;
+
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Success,
+							title: "Orphaned files archived".into(),
+							subtitle: format!(
+								"{} file{} moved to {}.",
+								files.len(),
+								if files.len() == 1 { " was" } else { "s were" },
+								archive_path.display()
+							),
+							link: None
+						}
+					)?;
+				}
+			}
+
+			SettingsEvent::FindModConflicts => {
+				if let Some(project) = app_state.project.load().as_ref() {
+					track_event(app, "Find mod conflicts", None);
+
+					let task = start_task(app, "Finding conflicts with installed mods")?;
+
+					let conflicts = find_mod_conflicts(
+						&project.path,
+						app_settings.load().game_install.as_ref().context("No game selected")?,
+						app_state.hash_list.load().as_deref()
+					)?;
+
+					finish_task(app, task)?;
+
+					send_request(
+						app,
+						Request::Tool(ToolRequest::Settings(SettingsRequest::SetModConflicts(conflicts)))
+					)?;
+				}
+			}
 		},
 
 		ToolEvent::ContentSearch(event) => match event {
-			ContentSearchEvent::Search(query, filetypes, use_qn_format, partitions_to_search) => {
-				start_content_search(app, query, filetypes, use_qn_format, partitions_to_search)?;
+			ContentSearchEvent::Search(
+				query,
+				filetypes,
+				use_qn_format,
+				partitions_to_search,
+				convert_with_resourcelib
+			) => {
+				start_content_search(
+					app,
+					query,
+					filetypes,
+					use_qn_format,
+					partitions_to_search,
+					convert_with_resourcelib
+				)?;
+			}
+
+			ContentSearchEvent::SaveSearch(search) => {
+				save_search(app, search)?;
+			}
+
+			ContentSearchEvent::DeleteSavedSearch(name) => {
+				delete_saved_search(app, &name)?;
+			}
+
+			ContentSearchEvent::RunSavedSearch(name) => {
+				run_saved_search(app, &name)?;
+			}
+		},
+
+		ToolEvent::OutfitBrowser(event) => {
+			handle_outfit_browser_event(app, event).await?;
+		}
+
+		ToolEvent::Git(event) => {
+			handle_git_event(app, event).await?;
+		}
+
+		ToolEvent::ExtractionQueue(event) => match event {
+			ExtractionQueueEvent::Initialise => {
+				send_request(
+					app,
+					Request::Tool(ToolRequest::ExtractionQueue(ExtractionQueueRequest::SetItems(
+						app_state.extraction_queue.items()?
+					)))
+				)?;
+			}
+
+			ExtractionQueueEvent::Cancel(id) => {
+				app_state.extraction_queue.cancel(id)?;
+			}
+
+			ExtractionQueueEvent::ClearFinished => {
+				app_state.extraction_queue.clear_finished()?;
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::ExtractionQueue(ExtractionQueueRequest::SetItems(
+						app_state.extraction_queue.items()?
+					)))
+				)?;
+			}
+		},
+
+		ToolEvent::AudioNames(event) => match event {
+			AudioNamesEvent::Initialise => {
+				send_audio_names(app)?;
+			}
+
+			AudioNamesEvent::GatherTargets(hash) => {
+				let game_files = app_state.game_files.load();
+				let game_files = game_files.as_ref().context("Game files not available")?;
+
+				let hash_list = app_state.hash_list.load();
+				let hash_list = hash_list.as_ref().context("No hash list available")?;
+
+				let game_version = get_loaded_game_version(
+					app,
+					app_settings.load().game_install.as_ref().context("No game selected")?
+				)?;
+
+				let (ids, names) = gather_audio_ids(
+					game_files,
+					hash_list,
+					game_version,
+					&app_state.local_hash_list.load(),
+					hash
+				)?;
+
+				// Names found in the resources themselves don't need to be brute-forced
+				if !names.is_empty() {
+					let mut wwise_names = app_state.local_hash_list.load().wwise_names.to_owned();
+					wwise_names.extend(names);
+					store_audio_names(app, wwise_names)?;
+				}
+
+				if ids.is_empty() {
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Info,
+							title: "No unknown IDs found".into(),
+							subtitle: format!("{hash} doesn't contain any IDs without a known name."),
+							link: None
+						}
+					)?;
+				} else {
+					send_request(
+						app,
+						Request::Tool(ToolRequest::AudioNames(AudioNamesRequest::AddTargets(ids)))
+					)?;
+				}
+			}
+
+			AudioNamesEvent::BruteForce {
+				targets,
+				mut words,
+				wordlist
+			} => {
+				track_event(app, "Brute-force audio names", None);
+
+				let task = start_task(app, "Brute-forcing audio names")?;
+
+				if let Some(wordlist) = wordlist {
+					words.extend(
+						fs::read_to_string(wordlist)
+							.context("Couldn't read wordlist")?
+							.lines()
+							.map(|x| x.trim())
+							.filter(|x| !x.is_empty())
+							.map(|x| x.to_owned())
+					);
+				}
+
+				let prefixes = app_state
+					.project
+					.load()
+					.as_ref()
+					.map(|x| x.settings.load().audio_name_prefixes.to_owned())
+					.unwrap_or_default();
+
+				let results = brute_force_names(&targets.into_iter().collect(), &words, &prefixes);
+
+				let mut names = app_state.local_hash_list.load().wwise_names.to_owned();
+				names.extend(results.iter().cloned());
+				store_audio_names(app, names)?;
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::AudioNames(AudioNamesRequest::SetResults(results)))
+				)?;
+
+				finish_task(app, task)?;
+			}
+
+			AudioNamesEvent::RemoveName(id) => {
+				let mut names = app_state.local_hash_list.load().wwise_names.to_owned();
+				names.shift_remove(&id);
+				store_audio_names(app, names)?;
+			}
+		},
+
+		ToolEvent::Converters(event) => match event {
+			ConvertersEvent::Convert(input) => {
+				let conversions = convert(&input, app_state.hash_list.load().as_deref());
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::Converters(ConvertersRequest::SetConversions {
+						input,
+						conversions
+					}))
+				)?;
+			}
+		},
+
+		ToolEvent::HashRemapping(event) => match event {
+			HashRemappingEvent::Remap { mapping, dry_run } => {
+				if !dry_run && refuse_if_read_only(app, "Remapping hashes")? {
+					return Ok(());
+				}
+
+				let project = app_state.project.load_full().context("No project loaded")?;
+
+				track_event(
+					app,
+					"Remap hashes",
+					Some(json!({
+						"dry_run": dry_run,
+						"mappings": mapping.len()
+					}))
+				);
+
+				let task = start_task(
+					app,
+					if dry_run {
+						"Finding hashes to remap"
+					} else {
+						"Remapping hashes"
+					}
+				)?;
+
+				let files = remap_hashes(&project.path, &mapping, dry_run)?;
+
+				if !dry_run {
+					let mut settings = (*project.settings.load_full()).to_owned();
+
+					if let Some(custom_paths) = remap_custom_paths(&settings.custom_paths, &mapping)? {
+						settings.custom_paths = custom_paths;
+
+						send_request(
+							app,
+							Request::Tool(ToolRequest::Settings(SettingsRequest::ChangeProjectSettings(
+								settings.to_owned()
+							)))
+						)?;
+
+						fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+						project.settings.store(settings.to_owned().into());
+					}
+
+					// Open tabs for the remapped files are reopened so that they can't save the old hashes back
+					let remapped_tabs = app_state
+						.editor_states
+						.iter()
+						.filter_map(|x| {
+							let path = x.file.as_ref()?;
+
+							files
+								.iter()
+								.any(|file| file.path == *path)
+								.then(|| (x.key().to_owned(), path.to_owned()))
+						})
+						.collect::<Vec<_>>();
+
+					for (tab, path) in remapped_tabs {
+						if let Some(split_tabs) = close_tab(&app_state, tab) {
+							app_state.editor_states.remove(&tab);
+
+							for split_tab in split_tabs {
+								send_request(app, Request::Global(GlobalRequest::RemoveTab(split_tab)))?;
+							}
+						}
+
+						send_request(app, Request::Global(GlobalRequest::RemoveTab(tab)))?;
+
+						open_file(app, path).await?;
+					}
+
+					let qn_editors = app_state
+						.editor_states
+						.iter()
+						.filter(|x| matches!(x.data, EditorData::QNEntity { .. } | EditorData::QNPatch { .. }))
+						.map(|x| x.key().to_owned())
+						.collect::<Vec<_>>();
+
+					for editor_id in qn_editors {
+						send_request(
+							app,
+							Request::Editor(EditorRequest::Entity(EntityEditorRequest::Metadata(
+								EntityMetadataRequest::UpdateCustomPaths {
+									editor_id,
+									custom_paths: settings.custom_paths.to_owned()
+								}
+							)))
+						)?;
+					}
+
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Success,
+							title: "Hashes remapped".into(),
+							subtitle: format!(
+								"{} file{} changed. Open tabs for these files have been reopened to show the new \
+								 hashes.",
+								files.len(),
+								if files.len() == 1 { " was" } else { "s were" }
+							),
+							link: None
+						}
+					)?;
+				}
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::HashRemapping(HashRemappingRequest::SetReport {
+						dry_run,
+						files
+					}))
+				)?;
+
+				finish_task(app, task)?;
+			}
+		},
+
+		ToolEvent::HashResolution(event) => match event {
+			HashResolutionEvent::Resolve(input) => {
+				let task = start_task(app, "Resolving hashes")?;
+
+				let results = resolve_hashes(
+					&input,
+					app_state.hash_list.load().as_deref(),
+					app_state.game_files.load().as_deref(),
+					app_state.resource_reverse_dependencies.load().as_deref()
+				);
+
+				track_event(
+					app,
+					"Resolve hashes",
+					Some(json!({
+						"results": results.len()
+					}))
+				);
+
+				send_request(
+					app,
+					Request::Tool(ToolRequest::HashResolution(HashResolutionRequest::SetResults(results)))
+				)?;
+
+				finish_task(app, task)?;
+			}
+
+			HashResolutionEvent::OpenResourceOverview(hash) => {
+				let id = Uuid::new_v4();
+
+				app_state.editor_states.insert(
+					id.to_owned(),
+					EditorState {
+						file: None,
+						data: EditorData::ResourceOverview {
+							hash,
+							generic_rl_json: None
+						}
+					}
+				);
+
+				send_request(
+					app,
+					Request::Global(GlobalRequest::CreateTab {
+						id,
+						name: format!("Resource overview ({hash})"),
+						editor_type: EditorType::ResourceOverview
+					})
+				)?;
+			}
+		},
+
+		ToolEvent::AudioImport(event) => match event {
+			AudioImportEvent::SelectFolder => {
+				if let Some(folder) = app
+					.dialog()
+					.file()
+					.set_title("Select folder of WAV files")
+					.blocking_pick_folder()
+				{
+					let folder = folder.into_path()?;
+
+					send_request(
+						app,
+						Request::Tool(ToolRequest::AudioImport(AudioImportRequest::SetFiles {
+							files: find_wav_files(&folder)?,
+							folder
+						}))
+					)?;
+				}
+			}
+
+			AudioImportEvent::Import { folder, mapping } => {
+				if refuse_if_read_only(app, "Importing audio")? {
+					return Ok(());
+				}
+
+				if refuse_if_untrusted(app, "Importing audio")? {
+					return Ok(());
+				}
+
+				let Some(encoder) = app_settings.load().wem_encoder.to_owned() else {
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Error,
+							title: "No WEM encoder".into(),
+							subtitle: "Set the program to convert WAV files to WEM with in the settings first.".into(),
+							link: None
+						}
+					)?;
+
+					return Ok(());
+				};
+
+				let project = app_state.project.load_full().context("No project loaded")?;
+				let game_files = app_state.game_files.load_full().context("Game files not loaded")?;
+
+				track_event(
+					app,
+					"Import audio",
+					Some(json!({
+						"files": mapping.len()
+					}))
+				);
+
+				let task = start_cancellable_task(app, "Importing audio")?;
+
+				let imported =
+					import_wav_files(app, &task, &encoder, &game_files, &project.path, &folder, &mapping).await;
+
+				finish_task(app, task.id)?;
+
+				let imported = imported?;
+
+				send_notification(
+					app,
+					Notification {
+						kind: if imported == mapping.len() {
+							NotificationKind::Success
+						} else {
+							NotificationKind::Info
+						},
+						title: if imported == mapping.len() {
+							"Audio imported".into()
+						} else {
+							"Audio import cancelled".into()
+						},
+						subtitle: format!(
+							"{} of {} file{} converted and added to the project.",
+							imported,
+							mapping.len(),
+							if mapping.len() == 1 { " was" } else { "s were" }
+						),
+						link: None
+					}
+				)?;
+			}
+		},
+
+		ToolEvent::CrashAnalysis(event) => match event {
+			CrashAnalysisEvent::SelectFile => {
+				if let Some(path) = app
+					.dialog()
+					.file()
+					.set_title("Select crash log or minidump")
+					.add_filter("Crash log or minidump", &["txt", "log", "dmp"])
+					.blocking_pick_file()
+				{
+					track_event(app, "Analyse crash", None);
+
+					send_crash_analysis(app, &path.into_path()?)?;
+				}
+			}
+
+			CrashAnalysisEvent::Analyse(path) => {
+				track_event(app, "Analyse crash", None);
+
+				send_crash_analysis(app, &path)?;
+			}
+
+			CrashAnalysisEvent::OpenFile(path) => {
+				open_file(app, path).await?;
+			}
+
+			CrashAnalysisEvent::OpenResourceOverview(hash) => {
+				let id = Uuid::new_v4();
+
+				app_state.editor_states.insert(
+					id.to_owned(),
+					EditorState {
+						file: None,
+						data: EditorData::ResourceOverview {
+							hash,
+							generic_rl_json: None
+						}
+					}
+				);
+
+				send_request(
+					app,
+					Request::Global(GlobalRequest::CreateTab {
+						id,
+						name: format!("Resource overview ({hash})"),
+						editor_type: EditorType::ResourceOverview
+					})
+				)?;
 			}
 		}
 	}
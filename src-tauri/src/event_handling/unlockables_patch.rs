@@ -1,28 +1,38 @@
-use std::collections::HashMap;
+use std::{
+	collections::HashMap,
+	fs,
+	io::Cursor,
+	path::{Path, PathBuf}
+};
 
 use anyhow::{Context, Result, bail};
 use fn_error_context::context;
+use hitman_commons::metadata::RuntimeID;
+use image::ImageReader;
 use indexmap::IndexMap;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::Serialize;
-use serde_json::{Value, from_str};
+use serde_json::{Value, from_slice, from_str};
 use tauri::{AppHandle, Manager};
 use tryvial::try_fn;
 use uuid::Uuid;
 
 use crate::{
-	finish_task,
+	Notification, NotificationKind, finish_task,
+	localisation::add_localisation,
 	model::{
 		AppState, EditorData, EditorRequest, GlobalRequest, Request, UnlockablesPatchEditorEvent,
 		UnlockablesPatchEditorRequest
 	},
-	ores_repo::{UnlockableInformation, UnlockableItem},
-	send_request, start_task
+	ores_repo::{RepositoryItem, UnlockableDecoration, UnlockableInformation, UnlockableItem},
+	refuse_if_read_only,
+	rpkg::extract_latest_resource,
+	send_notification, send_request, start_task
 };
 
 #[try_fn]
 #[context("Couldn't get information of unlockable {item:?}")]
-fn get_unlockable_information(item: &UnlockableItem) -> Result<UnlockableInformation> {
+pub fn get_unlockable_information(item: &UnlockableItem) -> Result<UnlockableInformation> {
 	if let Some(ty) = item.data.get("Type") {
 		match ty.as_str().context("Type was not string")? {
 			"access" => UnlockableInformation::Access {
@@ -72,7 +82,159 @@ fn get_unlockable_information(item: &UnlockableItem) -> Result<UnlockableInforma
 	}
 }
 
-fn get_modified_items(base: &[UnlockableItem], current: &[UnlockableItem]) -> Vec<Uuid> {
+/// Get a string value, treating empty strings as missing.
+fn get_non_empty_string(value: Option<&Value>) -> Option<String> {
+	value
+		.and_then(|x| x.as_str())
+		.filter(|x| !x.trim().is_empty())
+		.map(|x| x.to_owned())
+}
+
+/// The folders of the project's mod which image paths can refer to files in.
+fn get_blobs_folders(project_path: &Path) -> Vec<PathBuf> {
+	let Some(manifest) = fs::read(project_path.join("manifest.json"))
+		.ok()
+		.and_then(|x| from_slice::<Value>(&x).ok())
+	else {
+		return vec![];
+	};
+
+	manifest
+		.get("options")
+		.and_then(|x| x.as_array())
+		.into_iter()
+		.flatten()
+		.chain([&manifest])
+		.filter_map(|x| x.get("blobsFolders").and_then(|x| x.as_array()))
+		.flatten()
+		.filter_map(|x| x.as_str())
+		.map(|x| project_path.join(x))
+		.collect()
+}
+
+/// Get a previewable version of an image, which can be an image resource (extracted to the temp folder) or a path
+/// within one of the given blobs folders.
+#[try_fn]
+#[context("Couldn't get preview of image {}", image)]
+fn get_image_preview(app: &AppHandle, image: &str, blobs_folders: &[PathBuf]) -> Result<Option<PathBuf>> {
+	let app_state = app.state::<AppState>();
+
+	if let Ok(hash) = RuntimeID::from_any(image)
+		&& let Some(game_files) = app_state.game_files.load().as_ref()
+		&& let Some(hash_list) = app_state.hash_list.load().as_ref()
+		&& hash_list.entries.get(&hash).is_some_and(|x| x.resource_type == "GFXI")
+	{
+		let data_dir = app.path().app_data_dir().expect("Couldn't get data dir");
+
+		fs::create_dir_all(data_dir.join("temp"))?;
+
+		let image_path = data_dir.join("temp").join(format!("{}.png", hash));
+
+		if !image_path.exists() {
+			let (_, res_data) = extract_latest_resource(game_files, hash)?;
+
+			ImageReader::new(Cursor::new(res_data))
+				.with_guessed_format()?
+				.decode()?
+				.save(&image_path)?;
+		}
+
+		Some(image_path)
+	} else {
+		blobs_folders
+			.iter()
+			.map(|x| x.join(image.trim_start_matches('/')))
+			.find(|x| x.is_file())
+	}
+}
+
+/// Get the name, subtitle and image preview of an unlockable, preferring those of the repository item it unlocks.
+#[try_fn]
+#[context("Couldn't get decoration of unlockable {}", item.id)]
+pub fn get_unlockable_decoration(
+	app: &AppHandle,
+	item: &UnlockableItem,
+	repository: &HashMap<Uuid, &RepositoryItem>,
+	blobs_folders: &[PathBuf]
+) -> Result<UnlockableDecoration> {
+	let properties = item.data.get("Properties");
+
+	let repository_item = properties
+		.and_then(|x| x.get("RepositoryId"))
+		.and_then(|x| x.as_str())
+		.and_then(|x| x.parse::<Uuid>().ok())
+		.and_then(|x| repository.get(&x));
+
+	let name = repository_item
+		.and_then(|x| get_non_empty_string(x.data.get("CommonName")).or(get_non_empty_string(x.data.get("Name"))))
+		.or(get_non_empty_string(properties.and_then(|x| x.get("Name"))))
+		.or(get_non_empty_string(item.data.get("DisplayNameLocKey")));
+
+	let subtitle = repository_item
+		.and_then(|x| get_non_empty_string(x.data.get("Title")))
+		.or(get_non_empty_string(properties.and_then(|x| x.get("Description"))));
+
+	let mut image_path = None;
+
+	for image in [
+		repository_item.and_then(|x| x.data.get("Image")),
+		properties.and_then(|x| x.get("LargeScreenPath"))
+	]
+	.into_iter()
+	.filter_map(get_non_empty_string)
+	{
+		if let Some(path) = get_image_preview(app, &image, blobs_folders)? {
+			image_path = Some(path);
+			break;
+		}
+	}
+
+	UnlockableDecoration {
+		name,
+		subtitle,
+		image_path
+	}
+}
+
+/// Resolve the decorations of the given unlockables and send them to the editor.
+#[try_fn]
+#[context("Couldn't update unlockable decorations")]
+fn update_decorations(app: &AppHandle, id: Uuid, unlockables: &[UnlockableItem]) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let repository = app_state.repository.load();
+
+	let repository = repository
+		.as_ref()
+		.map(|x| x.iter().map(|x| (x.id, x)).collect::<HashMap<_, _>>())
+		.unwrap_or_default();
+
+	let blobs_folders = app_state
+		.project
+		.load()
+		.as_ref()
+		.map(|x| get_blobs_folders(&x.path))
+		.unwrap_or_default();
+
+	let decorations = unlockables
+		.par_iter()
+		.map(|item| -> Result<_> {
+			Ok((
+				item.id.to_owned(),
+				get_unlockable_decoration(app, item, &repository, &blobs_folders)?
+			))
+		})
+		.collect::<Result<_>>()?;
+
+	send_request(
+		app,
+		Request::Editor(EditorRequest::UnlockablesPatch(
+			UnlockablesPatchEditorRequest::SetDecorations { id, decorations }
+		))
+	)?;
+}
+
+pub fn get_modified_items(base: &[UnlockableItem], current: &[UnlockableItem]) -> Vec<Uuid> {
 	let base_items = base.iter().map(|x| (&x.id, &x.data)).collect::<HashMap<_, _>>();
 
 	current
@@ -131,6 +293,8 @@ pub async fn handle_unlockables_patch_event(app: &AppHandle, event: UnlockablesP
 				))
 			)?;
 
+			update_decorations(app, id, unlockables)?;
+
 			finish_task(app, task)?;
 		}
 
@@ -219,6 +383,8 @@ pub async fn handle_unlockables_patch_event(app: &AppHandle, event: UnlockablesP
 					.iter_mut()
 					.find(|x| x.id == unlockable)
 					.context("No such item in unlockables")? = base_unlockable.to_owned();
+
+				update_decorations(app, id, std::slice::from_ref(base_unlockable))?;
 			} else {
 				unlockables.retain(|x| x.id != unlockable);
 
@@ -312,6 +478,12 @@ pub async fn handle_unlockables_patch_event(app: &AppHandle, event: UnlockablesP
 					))
 				)?;
 
+				update_decorations(
+					app,
+					id,
+					std::slice::from_ref(unlockables.iter().find(|x| x.id == unlockable).unwrap())
+				)?;
+
 				send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
 			}
 
@@ -366,5 +538,104 @@ pub async fn handle_unlockables_patch_event(app: &AppHandle, event: UnlockablesP
 
 			finish_task(app, task)?;
 		}
+
+		UnlockablesPatchEditorEvent::GenerateLocalisation { id, unlockable } => {
+			if refuse_if_read_only(app, "Generating localisation")? {
+				return Ok(());
+			}
+
+			let project = app_state.project.load();
+			let project = project.as_ref().context("No project loaded")?;
+
+			let mut editor_state = app_state.editor_states.get_mut(&id).context("No such editor")?;
+
+			let (base, unlockables) = match editor_state.data {
+				EditorData::UnlockablesPatch {
+					ref base,
+					ref mut current,
+					..
+				} => (base, current),
+
+				_ => {
+					bail!("Editor {} is not a unlockables patch editor", id);
+				}
+			};
+
+			let itm = unlockables
+				.iter_mut()
+				.find(|x| x.id == unlockable)
+				.context("No such unlockable")?;
+
+			let name = get_non_empty_string(itm.data.get("Properties").and_then(|x| x.get("Name")))
+				.or_else(|| get_non_empty_string(itm.data.get("Id")))
+				.unwrap_or_else(|| unlockable.to_string());
+
+			let keys = add_localisation(
+				&project.path,
+				&project.settings.load().localisation_languages,
+				&name,
+				&[("NAME", name.to_owned())]
+			)?;
+
+			itm.data
+				.insert("DisplayNameLocKey".into(), Value::String(keys[0].to_owned()));
+
+			let mut buf_orig = Vec::new();
+			let formatter_orig = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+			let mut ser_orig = serde_json::Serializer::with_formatter(&mut buf_orig, formatter_orig);
+
+			if let Some(orig_unlockable) = base.iter().find(|x| x.id == unlockable) {
+				orig_unlockable.data.serialize(&mut ser_orig)?;
+			}
+
+			let mut buf = Vec::new();
+			let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+			let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+			itm.data.serialize(&mut ser)?;
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::UnlockablesPatch(
+					UnlockablesPatchEditorRequest::SetMonacoContent {
+						id,
+						unlockable,
+						orig_data: String::from_utf8(buf_orig)?,
+						data: String::from_utf8(buf)?
+					}
+				))
+			)?;
+
+			send_request(
+				app,
+				Request::Editor(EditorRequest::UnlockablesPatch(
+					UnlockablesPatchEditorRequest::SetModifiedUnlockables {
+						id,
+						modified: get_modified_items(base, unlockables)
+					}
+				))
+			)?;
+
+			update_decorations(
+				app,
+				id,
+				std::slice::from_ref(unlockables.iter().find(|x| x.id == unlockable).unwrap())
+			)?;
+
+			send_request(app, Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }))?;
+
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Success,
+					title: "Localisation generated".into(),
+					subtitle: format!(
+						"{} has been added to the manifest; replace the placeholder strings with the actual text.",
+						keys[0]
+					),
+					link: None
+				}
+			)?;
+		}
 	}
 }
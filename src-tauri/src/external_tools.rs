@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+use tryvial::try_fn;
+
+/// A user-configured external program (e.g. RPKG Tool) that resources and files can be opened with.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalTool {
+	pub name: String,
+	pub path: PathBuf,
+
+	/// The arguments to pass to the tool; `{hash}` and `{path}` are replaced with the hash and path of the target.
+	/// Arguments are separated by spaces, and can be wrapped in double quotes to include spaces.
+	pub arguments: String
+}
+
+/// Split an argument template into individual arguments, respecting double quotes.
+pub fn split_arguments(template: &str) -> Vec<String> {
+	let mut args = vec![];
+	let mut current = String::new();
+	let mut in_quotes = false;
+	let mut has_arg = false;
+
+	for char in template.chars() {
+		match char {
+			'"' => {
+				in_quotes = !in_quotes;
+				has_arg = true;
+			}
+
+			x if x.is_whitespace() && !in_quotes => {
+				if has_arg {
+					args.push(std::mem::take(&mut current));
+					has_arg = false;
+				}
+			}
+
+			x => {
+				current.push(x);
+				has_arg = true;
+			}
+		}
+	}
+
+	if has_arg {
+		args.push(current);
+	}
+
+	args
+}
+
+/// Launch an external tool, substituting the given hash and path into its argument template.
+///
+/// The tool runs in the background; this returns as soon as it has started.
+#[try_fn]
+#[context("Couldn't launch external tool {}", tool.name)]
+pub fn launch_external_tool(
+	app: &AppHandle,
+	tool: &ExternalTool,
+	hash: Option<&str>,
+	path: Option<&str>
+) -> Result<()> {
+	if tool.arguments.contains("{hash}") && hash.is_none() {
+		bail!("{} requires a hash, which isn't available here", tool.name);
+	}
+
+	if tool.arguments.contains("{path}") && path.is_none() {
+		bail!("{} requires a path, which isn't available here", tool.name);
+	}
+
+	let args = split_arguments(&tool.arguments)
+		.into_iter()
+		.map(|x| {
+			x.replace("{hash}", hash.unwrap_or_default())
+				.replace("{path}", path.unwrap_or_default())
+		})
+		.collect::<Vec<_>>();
+
+	let mut command = app.shell().command(&tool.path).args(args);
+
+	if let Some(folder) = tool.path.parent().filter(|x| x.is_dir()) {
+		command = command.current_dir(folder);
+	}
+
+	command.spawn().context("Couldn't start process")?;
+}
@@ -0,0 +1,275 @@
+use std::{
+	fs,
+	io::Cursor,
+	path::{Path, PathBuf}
+};
+
+use anyhow::{Context, Result, bail};
+use arc_swap::ArcSwap;
+use fn_error_context::context;
+use glacier_texture::{mipblock::MipblockData, texture_map::TextureMap};
+use hitman_commons::{game::GameVersion, hash_list::HashList, metadata::RuntimeID, rpkg_tool::RpkgResourceMeta};
+use image::{ImageFormat, ImageReader};
+use rpkg_rs::resource::partition_manager::PartitionManager;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, to_string, to_vec};
+use specta::Type;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::{
+	Notification, NotificationKind, get_loaded_game_version,
+	model::{AppSettings, AppState},
+	resourcelib::convert_generic_async,
+	rpkg::extract_latest_resource,
+	send_notification
+};
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtractionFormat {
+	/// The resource as it's stored in the game files, along with a binary meta file
+	Raw,
+
+	/// The resource converted to JSON with ResourceLib, along with a JSON meta file
+	ResourceLibJson,
+
+	/// Textures and images converted to PNG
+	Png
+}
+
+/// A named set of extraction options, which lets resources be extracted without choosing where to each time.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionPreset {
+	pub name: String,
+	pub format: ExtractionFormat,
+
+	/// The name of the extracted file, without its extension; `{hash}`, `{name}` and `{type}` are replaced with the
+	/// resource's hash, its name from the hash list (or hash, if it has none) and its resource type.
+	pub naming_scheme: String,
+
+	/// The folder to extract to; `{project}`, `{folder}` and `{type}` are replaced with the project folder, the folder
+	/// of the resource's path in the hash list and its resource type.
+	pub destination: String
+}
+
+/// The name and folder of a resource from its path, like `agent47_default` and
+/// `_pro/characters/templates/hero/agent47` for
+/// `[assembly:/_pro/characters/templates/hero/agent47/agent47.template?/agent47_default.entitytemplate].pc_entitytype`.
+fn split_resource_path(path: &str) -> (String, String) {
+	let inner = path.trim_start_matches('[').split(']').next().unwrap_or_default();
+
+	let inner = inner.split_once(":/").map(|(_, x)| x).unwrap_or(inner);
+
+	let (folder, file) = match inner.split_once('?') {
+		Some((before, after)) => (
+			before.rsplit_once('/').map(|(x, _)| x).unwrap_or_default(),
+			after.rsplit('/').next().unwrap_or_default()
+		),
+
+		None => inner.rsplit_once('/').unwrap_or(("", inner))
+	};
+
+	let name = file.split_once('.').map(|(x, _)| x).unwrap_or(file);
+
+	(name.to_owned(), folder.trim_matches('/').to_owned())
+}
+
+/// Replace characters which can't be used in file names.
+fn sanitise_file_name(name: &str) -> String {
+	name.replace(['<', '>', ':', '"', '|', '?', '*', '/', '\\'], "_")
+}
+
+/// Work out where a resource would be extracted to with the given preset, without the extension.
+#[try_fn]
+#[context("Couldn't get extraction path for {} with preset {}", hash, preset.name)]
+fn get_preset_path(
+	preset: &ExtractionPreset,
+	hash_list: &HashList,
+	project_path: Option<&Path>,
+	hash: RuntimeID
+) -> Result<PathBuf> {
+	let entry = hash_list.entries.get(&hash).context("Resource not in hash list")?;
+
+	let (name, folder) = entry
+		.path
+		.as_deref()
+		.map(split_resource_path)
+		.unwrap_or_else(|| (entry.hint.to_owned().unwrap_or_else(|| hash.to_string()), String::new()));
+
+	let name = if name.is_empty() { hash.to_string() } else { name };
+
+	if preset.destination.contains("{project}") && project_path.is_none() {
+		bail!("The preset extracts into the project, but no project is loaded");
+	}
+
+	let destination = preset
+		.destination
+		.replace("{project}", &project_path.unwrap_or(Path::new("")).to_string_lossy())
+		.replace("{folder}", &folder)
+		.replace("{type}", entry.resource_type.as_ref());
+
+	if !Path::new(&destination).is_absolute() {
+		bail!("The preset's destination {} isn't an absolute folder", destination);
+	}
+
+	let file_name = preset
+		.naming_scheme
+		.replace("{hash}", &hash.to_string())
+		.replace("{name}", &name)
+		.replace("{type}", entry.resource_type.as_ref());
+
+	PathBuf::from(destination).join(sanitise_file_name(&file_name))
+}
+
+/// Extract a resource using an extraction preset, returning the path of the extracted file.
+#[try_fn]
+#[context("Couldn't extract {} with preset {}", hash, preset.name)]
+pub async fn extract_with_preset(
+	game_files: &PartitionManager,
+	hash_list: &HashList,
+	game_version: GameVersion,
+	project_path: Option<&Path>,
+	preset: &ExtractionPreset,
+	hash: RuntimeID
+) -> Result<PathBuf> {
+	let path = get_preset_path(preset, hash_list, project_path, hash)?;
+
+	fs::create_dir_all(path.parent().context("No parent")?)?;
+
+	let (res_meta, res_data) = extract_latest_resource(game_files, hash)?;
+	let resource_type = res_meta.core_info.resource_type;
+
+	let file_name = path.file_name().context("No file name")?.to_string_lossy();
+
+	match preset.format {
+		ExtractionFormat::Raw => {
+			let extracted_path = path.with_file_name(format!("{}.{}", file_name, resource_type));
+
+			fs::write(&extracted_path, res_data)?;
+
+			fs::write(
+				path.with_file_name(format!("{}.{}.meta", file_name, resource_type)),
+				RpkgResourceMeta::from_resource_metadata(res_meta, false)
+					.to_binary()
+					.context("Couldn't serialise meta file")?
+			)?;
+
+			extracted_path
+		}
+
+		ExtractionFormat::ResourceLibJson => {
+			let extracted_path = path.with_file_name(format!("{}.{}.json", file_name, resource_type));
+
+			fs::write(
+				&extracted_path,
+				to_vec(&convert_generic_async::<Value>(res_data, game_version, resource_type).await?)?
+			)?;
+
+			fs::write(
+				path.with_file_name(format!("{}.{}.meta.json", file_name, resource_type)),
+				to_string(&RpkgResourceMeta::from_resource_metadata(res_meta, false))
+					.context("Couldn't serialise meta file")?
+			)?;
+
+			extracted_path
+		}
+
+		ExtractionFormat::Png => {
+			let extracted_path = path.with_file_name(format!("{}.png", file_name));
+
+			match resource_type.as_ref() {
+				"GFXI" => {
+					ImageReader::new(Cursor::new(res_data))
+						.with_guessed_format()?
+						.decode()?
+						.save(&extracted_path)?;
+				}
+
+				"TEXT" => {
+					let mut texture = TextureMap::process_data(game_version.into(), res_data)
+						.context("Couldn't process texture data")?;
+
+					if let Some(texd_depend) = res_meta.core_info.references.first() {
+						let (_, texd_data) = extract_latest_resource(game_files, texd_depend.resource.get_id())?;
+
+						let mip_block = MipblockData::from_memory(&texd_data, game_version.into())
+							.context("Couldn't process TEXD data")?;
+						texture.set_mipblock1(mip_block);
+					}
+
+					let tga_data =
+						glacier_texture::convert::create_tga(&texture).context("Couldn't convert texture to TGA")?;
+
+					let mut reader = ImageReader::new(Cursor::new(tga_data));
+					reader.set_format(ImageFormat::Tga);
+					reader.decode()?.save(&extracted_path)?;
+				}
+
+				_ => bail!("Only textures and images can be extracted as PNG")
+			}
+
+			extracted_path
+		}
+	}
+}
+
+/// Queue a resource to be extracted with the extraction preset of the given name, showing a notification with where it
+/// was extracted to once it's done.
+#[try_fn]
+#[context("Couldn't queue extraction of {} with preset {}", hash, preset)]
+pub fn enqueue_preset_extraction(app: &AppHandle, hash: RuntimeID, preset: &str) -> Result<()> {
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+	let app_state = app.state::<AppState>();
+
+	let preset = app_settings
+		.load()
+		.extraction_presets
+		.iter()
+		.find(|x| x.name == preset)
+		.context("No such extraction preset")?
+		.to_owned();
+
+	let game_files = app_state.game_files.load_full().context("Game files not loaded")?;
+	let hash_list = app_state.hash_list.load_full().context("Hash list not loaded")?;
+
+	let game_version = get_loaded_game_version(
+		app,
+		app_settings
+			.load()
+			.game_install
+			.as_ref()
+			.context("No game install selected")?
+	)?;
+
+	let project_path = app_state.project.load().as_ref().map(|x| x.path.to_owned());
+
+	let app = app.clone();
+
+	app_state.extraction_queue.enqueue(
+		format!("Extract {} with preset {}", hash, preset.name),
+		move |_| async move {
+			let path = extract_with_preset(
+				&game_files,
+				&hash_list,
+				game_version,
+				project_path.as_deref(),
+				&preset,
+				hash
+			)
+			.await?;
+
+			send_notification(
+				&app,
+				Notification {
+					kind: NotificationKind::Success,
+					title: "Resource extracted".into(),
+					subtitle: format!("{} has been extracted to {}.", hash, path.display()),
+					link: None
+				}
+			)?;
+
+			anyhow::Ok(())
+		}
+	)?;
+}
@@ -0,0 +1,216 @@
+use std::{
+	fs,
+	path::{Path, PathBuf}
+};
+
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use hashbrown::HashSet;
+use quickentity_rs::qn_structs::{Entity, Ref, SubEntity, SubType};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::to_string;
+use specta::Type;
+use tryvial::try_fn;
+use walkdir::WalkDir;
+
+/// A kind of file that can be created with some starting content from the file browser.
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum FileTemplate {
+	/// An entity with just a root spatial entity
+	Entity,
+
+	/// An empty repository.json merge patch
+	RepositoryPatch,
+
+	/// An empty unlockables.json merge patch
+	UnlockablesPatch
+}
+
+impl FileTemplate {
+	pub fn extension(&self) -> &'static str {
+		match self {
+			FileTemplate::Entity => "entity.json",
+			FileTemplate::RepositoryPatch => "repository.json",
+			FileTemplate::UnlockablesPatch => "unlockables.json"
+		}
+	}
+
+	pub fn contents(&self) -> Result<String> {
+		Ok(match self {
+			FileTemplate::Entity => to_string(&empty_entity())?,
+			FileTemplate::RepositoryPatch | FileTemplate::UnlockablesPatch => "{}".into()
+		})
+	}
+}
+
+/// An entity with nothing in it but a root spatial entity called Scene.
+pub fn empty_entity() -> Entity {
+	Entity {
+		factory_hash: String::new(),
+		blueprint_hash: String::new(),
+		root_entity: "fffffffffffffffe".into(),
+		entities: velcro::map_iter! {
+			"fffffffffffffffe": SubEntity {
+				parent: Ref::Short(None),
+				name: "Scene".into(),
+				factory: "[modules:/zspatialentity.class].pc_entitytype".into(),
+				blueprint: "[modules:/zspatialentity.class].pc_entityblueprint".into(),
+				factory_flag: None,
+				editor_only: None,
+				properties: None,
+				platform_specific_properties: None,
+				events: None,
+				input_copying: None,
+				output_copying: None,
+				property_aliases: None,
+				exposed_entities: None,
+				exposed_interfaces: None,
+				subsets: None
+			}
+		}
+		.map(|(x, y)| (x.to_owned(), y))
+		.collect(),
+		property_overrides: vec![],
+		override_deletes: vec![],
+		pin_connection_overrides: vec![],
+		pin_connection_override_deletes: vec![],
+		external_scenes: vec![],
+		sub_type: SubType::Scene,
+		quick_entity_version: 3.1,
+		extra_factory_dependencies: vec![],
+		extra_blueprint_dependencies: vec![],
+		comments: vec![]
+	}
+}
+
+/// Create a file from a template in the given folder, adding the template's extension to the name if it isn't there
+/// already. Returns the path of the new file.
+#[try_fn]
+#[context("Couldn't create {} from template", name)]
+pub fn create_from_template(folder: &Path, name: &str, template: FileTemplate) -> Result<PathBuf> {
+	let name = name.trim();
+
+	if name.is_empty() || name.contains(['/', '\\']) {
+		bail!("Invalid file name");
+	}
+
+	let path = if name.ends_with(&format!(".{}", template.extension())) {
+		folder.join(name)
+	} else {
+		folder.join(format!("{}.{}", name, template.extension()))
+	};
+
+	if path.exists() {
+		bail!("{} already exists", path.display());
+	}
+
+	fs::write(&path, template.contents()?)?;
+
+	path
+}
+
+/// Copy a file or folder next to itself, named like `file copy.entity.json` or `file copy 2.entity.json` if that's
+/// taken. Returns the path of the copy.
+#[try_fn]
+#[context("Couldn't duplicate {}", path.display())]
+pub fn duplicate_path(path: &Path) -> Result<PathBuf> {
+	let name = path.file_name().context("No file name")?.to_string_lossy().to_string();
+
+	// Everything after the first dot is kept as the extension, so entity.json and the like stay intact
+	let (stem, extension) = if path.is_dir() {
+		(name.as_str(), String::new())
+	} else {
+		name.split_once('.')
+			.map(|(stem, extension)| (stem, format!(".{}", extension)))
+			.unwrap_or((name.as_str(), String::new()))
+	};
+
+	let target = (1..)
+		.map(|x| {
+			path.with_file_name(if x == 1 {
+				format!("{} copy{}", stem, extension)
+			} else {
+				format!("{} copy {}{}", stem, x, extension)
+			})
+		})
+		.find(|x| !x.exists())
+		.context("No free name")?;
+
+	if path.is_dir() {
+		for entry in WalkDir::new(path).sort_by_file_name() {
+			let entry = entry?;
+			let destination = target.join(entry.path().strip_prefix(path)?);
+
+			if entry.file_type().is_dir() {
+				fs::create_dir_all(destination)?;
+			} else {
+				fs::copy(entry.path(), destination)?;
+			}
+		}
+	} else {
+		fs::copy(path, &target)?;
+	}
+
+	target
+}
+
+/// Work out the renames of the files and folders within a folder (at any depth) whose names match a regex, replacing
+/// the matches with the replacement (which can refer to capture groups like `$1`). Contents come before their folders,
+/// so the renames can be carried out in order.
+#[try_fn]
+#[context("Couldn't work out batch rename")]
+pub fn get_batch_renames(folder: &Path, pattern: &str, replacement: &str) -> Result<Vec<(PathBuf, PathBuf)>> {
+	let regex = Regex::new(pattern).context("Invalid pattern")?;
+
+	let mut renames = vec![];
+	let mut targets = HashSet::new();
+
+	for entry in WalkDir::new(folder)
+		.min_depth(1)
+		.contents_first(true)
+		.sort_by_file_name()
+		.into_iter()
+		.filter_entry(|x| x.file_name() != ".git")
+	{
+		let entry = entry?;
+
+		let name = entry.file_name().to_string_lossy();
+		let new_name = regex.replace_all(&name, replacement);
+
+		if new_name == name {
+			continue;
+		}
+
+		if new_name.trim().is_empty() || new_name.contains(['/', '\\']) {
+			bail!("{} would be renamed to an invalid name", entry.path().display());
+		}
+
+		let target = entry.path().with_file_name(new_name.as_ref());
+
+		if target.exists() || !targets.insert(target.to_owned()) {
+			bail!(
+				"{} would be renamed to {}, which is taken",
+				entry.path().display(),
+				new_name
+			);
+		}
+
+		renames.push((entry.path().to_owned(), target));
+	}
+
+	renames
+}
+
+/// Rename the files and folders within a folder whose names match a regex. Returns the number of renames.
+#[try_fn]
+#[context("Couldn't batch rename files in {}", folder.display())]
+pub fn batch_rename(folder: &Path, pattern: &str, replacement: &str) -> Result<usize> {
+	let renames = get_batch_renames(folder, pattern, replacement)?;
+
+	for (old_path, new_path) in &renames {
+		fs::rename(old_path, new_path)?;
+	}
+
+	renames.len()
+}
@@ -9,8 +9,6 @@ use dashmap::DashMap;
 use fn_error_context::context;
 use hashbrown::HashMap;
 use hitman_commons::{game::GameVersion, hash_list::HashList, metadata::RuntimeID};
-use hitman_formats::ores::parse_json_ores;
-use indexmap::IndexMap;
 use itertools::Itertools;
 use quickentity_rs::{
 	apply_patch,
@@ -24,26 +22,40 @@ use rpkg_rs::{
 	misc::ini_file_system::IniFileSystem, resource::partition_manager::PartitionManager,
 	resource::pdefs::PackageDefinitionSource
 };
-use serde_json::{Value, from_slice, from_str, from_value, to_value};
+use serde_json::{Value, from_slice, from_str};
 use tauri::{AppHandle, Manager};
 use tryvial::try_fn;
 use uuid::Uuid;
 use velcro::vec;
 
+use crate::intellisense::{Intellisense, get_game_files_fingerprint};
 use crate::ores_repo::RepositoryItem;
+use crate::patches::{
+	extract_unlockables, parse_json_patch, patchable_to_repository, patchable_to_unlockables, repository_to_patchable,
+	unlockables_to_patchable
+};
+use crate::resourcelib::{SEnumType, convert_generic};
 use crate::rpkg::extract_latest_resource;
 use crate::{
 	HASH_LIST_ENDPOINT, HASH_LIST_VERSION_ENDPOINT, Notification, NotificationKind, TONYTOOLS_HASH_LIST_ENDPOINT,
-	TONYTOOLS_HASH_LIST_VERSION_ENDPOINT, finish_task, send_notification, send_request, start_task
+	TONYTOOLS_HASH_LIST_VERSION_ENDPOINT, collections::send_collections, finish_task, send_notification, send_request,
+	start_task, tasks::start_cancellable_task, telemetry::is_offline
+};
+use crate::{
+	biome::format_json,
+	event_handling::{
+		dialogue::get_dialogue_tree, json_resource::get_json_resource_kind,
+		resource_overview::initialise_resource_overview
+	},
+	get_loaded_game_version
 };
-use crate::{event_handling::resource_overview::initialise_resource_overview, get_loaded_game_version};
-use crate::{intellisense::Intellisense, ores_repo::UnlockableItem};
 use crate::{
 	model::{
 		AppSettings, AppState, ContentSearchRequest, EditorData, EditorState, EditorType, FileBrowserRequest,
-		GameBrowserRequest, GlobalRequest, JsonPatchType, Request, TextFileType, ToolRequest
+		GameBrowserRequest, GlobalRequest, JsonPatchType, OutfitBrowserRequest, Request, TextFileType, ToolRequest
 	},
-	rpkg::extract_entity
+	rpkg::extract_entity_async,
+	watch_list::check_watched_resources
 };
 
 #[try_fn]
@@ -110,7 +122,8 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 						file: Some(path.to_owned()),
 						data: EditorData::QNEntity {
 							entity: Box::new(entity),
-							settings: Default::default()
+							settings: Default::default(),
+							recycle_bin: vec![]
 						}
 					}
 				);
@@ -135,13 +148,14 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 					let patch: Patch =
 						from_slice(&fs::read(path).context("Couldn't read file")?).context("Invalid entity")?;
 
-					let mut entity = extract_entity(
+					let mut entity = extract_entity_async(
 						game_files,
 						&app_state.cached_entities,
 						get_loaded_game_version(app, install)?,
 						hash_list,
 						RuntimeID::from_any(&patch.factory_hash)?
-					)?
+					)
+					.await?
 					.to_owned();
 
 					let base = entity.to_owned();
@@ -170,7 +184,8 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 							data: EditorData::QNPatch {
 								base: Box::new(base),
 								current: Box::new(entity),
-								settings: Default::default()
+								settings: Default::default(),
+								recycle_bin: vec![]
 							}
 						}
 					);
@@ -194,7 +209,8 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 						Notification {
 							kind: NotificationKind::Error,
 							title: "No game selected".into(),
-							subtitle: "You can't open patch files without a copy of the game selected.".into()
+							subtitle: "You can't open patch files without a copy of the game selected.".into(),
+							link: None
 						}
 					)?;
 				}
@@ -292,33 +308,20 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 				let id = Uuid::new_v4();
 
 				if let Some(cached_repository) = app_state.repository.load().as_ref() {
-					let mut repository = to_value(
-						cached_repository
-							.iter()
-							.cloned()
-							.map(|x| (x.id, x.data))
-							.collect::<IndexMap<Uuid, IndexMap<String, Value>>>()
-					)?;
-
-					let base = to_value(cached_repository)?;
+					let mut repository = repository_to_patchable(cached_repository)?;
 
 					let patch: Value =
 						from_slice(&fs::read(path).context("Couldn't read file")?).context("Invalid JSON")?;
 
 					json_patch::merge(&mut repository, &patch);
 
-					let repository = from_value::<IndexMap<Uuid, IndexMap<String, Value>>>(repository)?
-						.into_iter()
-						.map(|(id, data)| RepositoryItem { id, data })
-						.collect();
-
 					app_state.editor_states.insert(
 						id.to_owned(),
 						EditorState {
 							file: Some(path.to_owned()),
 							data: EditorData::RepositoryPatch {
-								base: from_value(base)?,
-								current: repository,
+								base: cached_repository.to_vec(),
+								current: patchable_to_repository(repository)?,
 								patch_type: JsonPatchType::MergePatch
 							}
 						}
@@ -345,7 +348,8 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 						Notification {
 							kind: NotificationKind::Error,
 							title: "No game selected".into(),
-							subtitle: "You can't open patch files without a copy of the game selected.".into()
+							subtitle: "You can't open patch files without a copy of the game selected.".into(),
+							link: None
 						}
 					)?;
 				}
@@ -355,65 +359,22 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 				let id = Uuid::new_v4();
 
 				if let Some(game_files) = app_state.game_files.load().as_ref() {
-					let mut unlockables = to_value(
-						from_str::<Vec<UnlockableItem>>(&parse_json_ores(
-							&extract_latest_resource(game_files, "0057C2C3941115CA".parse::<RuntimeID>()?)?.1
-						)?)?
-						.into_iter()
-						.map(|x| {
-							(
-								x.data
-									.get("Id")
-									.expect("Unlockable did not have Id")
-									.as_str()
-									.expect("Id was not string")
-									.to_owned(),
-								{
-									let mut y = IndexMap::new();
-									y.insert("Guid".into(), to_value(x.id).unwrap());
-									y.extend(x.data.into_iter().filter(|(key, _)| key != "Id"));
-									y
-								}
-							)
-						})
-						.collect::<IndexMap<String, IndexMap<String, Value>>>()
-					)?;
+					let base = extract_unlockables(game_files)?;
 
-					let base = from_str::<Value>(&parse_json_ores(
-						&extract_latest_resource(game_files, "0057C2C3941115CA".parse::<RuntimeID>()?)?.1
-					)?)?;
+					let mut unlockables = unlockables_to_patchable(&base)?;
 
 					let patch: Value =
 						from_slice(&fs::read(path).context("Couldn't read file")?).context("Invalid JSON")?;
 
 					json_patch::merge(&mut unlockables, &patch);
 
-					let unlockables = from_value::<IndexMap<String, IndexMap<String, Value>>>(unlockables)?
-						.into_iter()
-						.map(|(id, data)| UnlockableItem {
-							id: data
-								.get("Guid")
-								.expect("No Guid on unlockable item")
-								.as_str()
-								.expect("Guid was not string")
-								.try_into()
-								.expect("Guid was not valid UUID"),
-							data: {
-								let mut y = IndexMap::new();
-								y.insert("Id".into(), Value::String(id));
-								y.extend(data.into_iter().filter(|(key, _)| key != "Guid"));
-								y
-							}
-						})
-						.collect();
-
 					app_state.editor_states.insert(
 						id.to_owned(),
 						EditorState {
 							file: Some(path.to_owned()),
 							data: EditorData::UnlockablesPatch {
-								base: from_value(base)?,
-								current: unlockables,
+								base,
+								current: patchable_to_unlockables(unlockables)?,
 								patch_type: JsonPatchType::MergePatch
 							}
 						}
@@ -440,7 +401,8 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 						Notification {
 							kind: NotificationKind::Error,
 							title: "No game selected".into(),
-							subtitle: "You can't open patch files without a copy of the game selected.".into()
+							subtitle: "You can't open patch files without a copy of the game selected.".into(),
+							link: None
 						}
 					)?;
 				}
@@ -460,39 +422,17 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 				{
 					"REPO" => {
 						if let Some(cached_repository) = app_state.repository.load().as_ref() {
-							let mut repository = to_value(
-								cached_repository
-									.iter()
-									.cloned()
-									.map(|x| (x.id, x.data))
-									.collect::<IndexMap<Uuid, IndexMap<String, Value>>>()
-							)?;
-
-							let base = to_value(cached_repository)?;
+							let mut repository = repository_to_patchable(cached_repository)?;
 
-							let patch = from_slice::<Value>(&fs::read(path).context("Couldn't read file")?)
-								.context("Invalid JSON")?;
-
-							let patch = patch.get("patch").context("Patch had no patch key")?;
-
-							json_patch::patch(
-								&mut repository,
-								&from_value::<Vec<json_patch::PatchOperation>>(patch.to_owned())
-									.context("Invalid JSON patch")?
-							)?;
-
-							let repository = from_value::<IndexMap<Uuid, IndexMap<String, Value>>>(repository)?
-								.into_iter()
-								.map(|(id, data)| RepositoryItem { id, data })
-								.collect();
+							json_patch::patch(&mut repository, &parse_json_patch(&file)?)?;
 
 							app_state.editor_states.insert(
 								id.to_owned(),
 								EditorState {
 									file: Some(path.to_owned()),
 									data: EditorData::RepositoryPatch {
-										base: from_value(base)?,
-										current: repository,
+										base: cached_repository.to_vec(),
+										current: patchable_to_repository(repository)?,
 										patch_type: JsonPatchType::JsonPatch
 									}
 								}
@@ -519,7 +459,8 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 								Notification {
 									kind: NotificationKind::Error,
 									title: "No game selected".into(),
-									subtitle: "You can't open patch files without a copy of the game selected.".into()
+									subtitle: "You can't open patch files without a copy of the game selected.".into(),
+									link: None
 								}
 							)?;
 						}
@@ -536,71 +477,19 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 						let id = Uuid::new_v4();
 
 						if let Some(game_files) = app_state.game_files.load().as_ref() {
-							let mut unlockables = to_value(
-								from_str::<Vec<UnlockableItem>>(&parse_json_ores(
-									&extract_latest_resource(game_files, "0057C2C3941115CA".parse::<RuntimeID>()?)?.1
-								)?)?
-								.into_iter()
-								.map(|x| {
-									(
-										x.data
-											.get("Id")
-											.expect("Unlockable did not have Id")
-											.as_str()
-											.expect("Id was not string")
-											.to_owned(),
-										{
-											let mut y = IndexMap::new();
-											y.insert("Guid".into(), to_value(x.id).unwrap());
-											y.extend(x.data.into_iter().filter(|(key, _)| key != "Id"));
-											y
-										}
-									)
-								})
-								.collect::<IndexMap<String, IndexMap<String, Value>>>()
-							)?;
-
-							let base = from_str::<Value>(&parse_json_ores(
-								&extract_latest_resource(game_files, "0057C2C3941115CA".parse::<RuntimeID>()?)?.1
-							)?)?;
-
-							let patch = from_slice::<Value>(&fs::read(path).context("Couldn't read file")?)
-								.context("Invalid JSON")?;
+							let base = extract_unlockables(game_files)?;
 
-							let patch = patch.get("patch").context("Patch had no patch key")?;
+							let mut unlockables = unlockables_to_patchable(&base)?;
 
-							json_patch::patch(
-								&mut unlockables,
-								&from_value::<Vec<json_patch::PatchOperation>>(patch.to_owned())
-									.context("Invalid JSON patch")?
-							)?;
-
-							let unlockables = from_value::<IndexMap<String, IndexMap<String, Value>>>(unlockables)?
-								.into_iter()
-								.map(|(id, data)| UnlockableItem {
-									id: data
-										.get("Guid")
-										.expect("No Guid on unlockable item")
-										.as_str()
-										.expect("Guid was not string")
-										.try_into()
-										.expect("Guid was not valid UUID"),
-									data: {
-										let mut y = IndexMap::new();
-										y.insert("Id".into(), Value::String(id));
-										y.extend(data.into_iter().filter(|(key, _)| key != "Guid"));
-										y
-									}
-								})
-								.collect();
+							json_patch::patch(&mut unlockables, &parse_json_patch(&file)?)?;
 
 							app_state.editor_states.insert(
 								id.to_owned(),
 								EditorState {
 									file: Some(path.to_owned()),
 									data: EditorData::UnlockablesPatch {
-										base: from_value(base)?,
-										current: unlockables,
+										base,
+										current: patchable_to_unlockables(unlockables)?,
 										patch_type: JsonPatchType::JsonPatch
 									}
 								}
@@ -627,7 +516,8 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 								Notification {
 									kind: NotificationKind::Error,
 									title: "No game selected".into(),
-									subtitle: "You can't open patch files without a copy of the game selected.".into()
+									subtitle: "You can't open patch files without a copy of the game selected.".into(),
+									link: None
 								}
 							)?;
 						}
@@ -638,11 +528,11 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 							id.to_owned(),
 							EditorState {
 								file: Some(path.to_owned()),
-								data: EditorData::Text {
+								data: EditorData::JsonPatch {
 									content: fs::read_to_string(path)
 										.context("Couldn't read file")?
 										.replace("\r\n", "\n"),
-									file_type: TextFileType::Json
+									base: None
 								}
 							}
 						);
@@ -652,41 +542,108 @@ pub async fn open_file(app: &AppHandle, path: impl AsRef<Path>) -> Result<()> {
 							Request::Global(GlobalRequest::CreateTab {
 								id,
 								name: path.file_name().context("No file name")?.to_string_lossy().into(),
-								editor_type: EditorType::Text {
-									file_type: TextFileType::Json
-								}
+								editor_type: EditorType::JsonPatch
 							})
 						)?;
 					}
 				}
 			}
 
+			"ENUM" => {
+				if let Some(install) = app_settings.load().game_install.as_ref() {
+					let id = Uuid::new_v4();
+
+					let enums: Vec<SEnumType> = convert_generic(
+						&fs::read(path).context("Couldn't read file")?,
+						get_loaded_game_version(app, install)?,
+						"ENUM".try_into()?
+					)?;
+
+					app_state.editor_states.insert(
+						id.to_owned(),
+						EditorState {
+							file: Some(path.to_owned()),
+							data: EditorData::Enum { enums }
+						}
+					);
+
+					send_request(
+						app,
+						Request::Global(GlobalRequest::CreateTab {
+							id,
+							name: path.file_name().context("No file name")?.to_string_lossy().into(),
+							editor_type: EditorType::Enum
+						})
+					)?;
+				} else {
+					send_request(
+						app,
+						Request::Tool(ToolRequest::FileBrowser(FileBrowserRequest::Select(None)))
+					)?;
+
+					send_notification(
+						app,
+						Notification {
+							kind: NotificationKind::Error,
+							title: "No game selected".into(),
+							subtitle: "You can't open enum files without a copy of the game selected.".into(),
+							link: None
+						}
+					)?;
+				}
+			}
+
 			"dlge.json" | "locr.json" | "rtlv.json" | "clng.json" | "ditl.json" | "material.json" | "contract.json" => {
 				let id = Uuid::new_v4();
 
-				app_state.editor_states.insert(
-					id.to_owned(),
-					EditorState {
-						file: Some(path.to_owned()),
-						data: EditorData::Text {
-							content: fs::read_to_string(path)
-								.context("Couldn't read file")?
-								.replace("\r\n", "\n"),
-							file_type: TextFileType::Json
+				let content = fs::read_to_string(path)
+					.context("Couldn't read file")?
+					.replace("\r\n", "\n");
+
+				// Dialogue with a structure the visual editor can't display is opened as plain JSON instead
+				if extension == "dlge.json"
+					&& let Ok(dialogue) = from_str::<Value>(&content)
+					&& get_dialogue_tree(&dialogue).is_ok()
+				{
+					app_state.editor_states.insert(
+						id.to_owned(),
+						EditorState {
+							file: Some(path.to_owned()),
+							data: EditorData::Dialogue { dialogue }
 						}
-					}
-				);
+					);
 
-				send_request(
-					app,
-					Request::Global(GlobalRequest::CreateTab {
-						id,
-						name: path.file_name().context("No file name")?.to_string_lossy().into(),
-						editor_type: EditorType::Text {
-							file_type: TextFileType::Json
+					send_request(
+						app,
+						Request::Global(GlobalRequest::CreateTab {
+							id,
+							name: path.file_name().context("No file name")?.to_string_lossy().into(),
+							editor_type: EditorType::Dialogue
+						})
+					)?;
+				} else {
+					app_state.editor_states.insert(
+						id.to_owned(),
+						EditorState {
+							file: Some(path.to_owned()),
+							data: EditorData::Text {
+								content,
+								file_type: TextFileType::Json
+							}
 						}
-					})
-				)?;
+					);
+
+					send_request(
+						app,
+						Request::Global(GlobalRequest::CreateTab {
+							id,
+							name: path.file_name().context("No file name")?.to_string_lossy().into(),
+							editor_type: EditorType::Text {
+								file_type: TextFileType::Json
+							}
+						})
+					)?;
+				}
 			}
 
 			_ => {
@@ -729,6 +686,8 @@ pub async fn load_game_files(app: &AppHandle) -> Result<()> {
 	app_state.repository.store(None);
 	app_state.cached_entities.clear();
 
+	let mut intellisense_cache_location = None;
+
 	if let Some(path) = app_settings.load().game_install.as_ref() {
 		let task = start_task(app, "Loading game files")?;
 
@@ -761,6 +720,19 @@ pub async fn load_game_files(app: &AppHandle) -> Result<()> {
 			.find(|joined_path| joined_path.exists())
 			.context("Couldn't find valid runtime folder")?;
 
+		let fingerprint = get_game_files_fingerprint(&runtime_path, app_settings.load().extract_modded_files)?;
+
+		intellisense_cache_location = Some((
+			app.path()
+				.app_data_dir()
+				.context("Couldn't get app data dir")?
+				.join(format!(
+					"intellisense_cache_{}.sml",
+					format!("{:?}", get_loaded_game_version(app, path)?).to_lowercase()
+				)),
+			fingerprint.to_owned()
+		));
+
 		let mut partitions = match get_loaded_game_version(app, path)? {
 			GameVersion::H1 => PackageDefinitionSource::HM2016(fs::read(runtime_path.join("packagedefinition.txt"))?)
 				.read()
@@ -821,7 +793,7 @@ pub async fn load_game_files(app: &AppHandle) -> Result<()> {
 			.context("Couldn't mount partitions")?;
 
 		finish_task(app, loading_task)?;
-		let task = start_task(app, "Caching reverse references")?;
+		let task = start_cancellable_task(app, "Caching reverse references")?;
 
 		let mut reverse_dependencies: DashMap<RuntimeID, Vec<RuntimeID>> = DashMap::new();
 
@@ -857,50 +829,83 @@ pub async fn load_game_files(app: &AppHandle) -> Result<()> {
 				})
 			})
 			.for_each(|(key, value)| {
+				if task.is_cancelled() {
+					return;
+				}
+
 				if let Some(mut x) = reverse_dependencies.get_mut(&key) {
 					x.push(value);
 				}
 			});
 
-		send_request(
-			app,
-			Request::Tool(ToolRequest::ContentSearch(ContentSearchRequest::SetPartitions(
-				partition_manager
-					.partitions
-					.iter()
-					.map(|x| {
-						(
-							x.partition_info().name.as_deref().unwrap_or("<unnamed>").to_owned(),
-							x.partition_info().id.to_string()
-						)
-					})
-					.collect()
-			)))
-		)?;
-
-		app_state.game_files.store(Some(partition_manager.into()));
-
-		app_state.resource_reverse_dependencies.store(Some(
-			reverse_dependencies
-				.into_par_iter()
-				.map(|(x, mut y)| {
-					(x, {
-						y.sort_unstable();
-						y.into_iter().dedup().collect()
-					})
+		// The game files are left unloaded if cancelled, since most features need the reverse references
+		if task.is_cancelled() {
+			finish_task(app, task.id)?;
+
+			send_notification(
+				app,
+				Notification {
+					kind: NotificationKind::Info,
+					title: "Game files not loaded".into(),
+					subtitle: "Loading the game files was cancelled. Restart GlacierKit or select a game install to \
+					           load them."
+						.into(),
+					link: None
+				}
+			)?;
+		} else {
+			let partitions = partition_manager
+				.partitions
+				.iter()
+				.map(|x| {
+					(
+						x.partition_info().name.as_deref().unwrap_or("<unnamed>").to_owned(),
+						x.partition_info().id.to_string()
+					)
 				})
-				.collect::<HashMap<_, _>>()
-				.into()
-		));
+				.collect_vec();
 
-		finish_task(app, task)?;
+			send_request(
+				app,
+				Request::Tool(ToolRequest::ContentSearch(ContentSearchRequest::SetPartitions(
+					partitions.to_owned()
+				)))
+			)?;
+
+			send_request(
+				app,
+				Request::Tool(ToolRequest::GameBrowser(GameBrowserRequest::SetPartitions(partitions)))
+			)?;
+
+			app_state.game_files.store(Some(partition_manager.into()));
+
+			app_state.resource_reverse_dependencies.store(Some(
+				reverse_dependencies
+					.into_par_iter()
+					.map(|(x, mut y)| {
+						(x, {
+							y.sort_unstable();
+							y.into_iter().dedup().collect()
+						})
+					})
+					.collect::<HashMap<_, _>>()
+					.into()
+			));
+
+			finish_task(app, task.id)?;
+
+			check_watched_resources(app, get_loaded_game_version(app, path)?, &fingerprint)?;
+		}
 	}
 
 	let task = start_task(app, "Acquiring latest hash list")?;
 
 	let current_version = app_state.hash_list.load().as_ref().map(|x| x.version).unwrap_or(0);
 
-	if let Ok(data) = reqwest::get(HASH_LIST_VERSION_ENDPOINT).await {
+	// Offline mode uses whichever hash lists were last downloaded
+	if !is_offline()
+		&& let Ok(data) = reqwest::get(HASH_LIST_VERSION_ENDPOINT).await
+	{
 		if let Ok(data) = data.text().await {
 			let new_version = data
 				.trim()
@@ -934,7 +939,9 @@ pub async fn load_game_files(app: &AppHandle) -> Result<()> {
 		.map(|x| x.version)
 		.unwrap_or(0);
 
-	if let Ok(data) = reqwest::get(TONYTOOLS_HASH_LIST_VERSION_ENDPOINT).await {
+	if !is_offline()
+		&& let Ok(data) = reqwest::get(TONYTOOLS_HASH_LIST_VERSION_ENDPOINT).await
+	{
 		if let Ok(data) = data.text().await {
 			let new_version = from_str::<Value>(&data)
 				.context("Couldn't parse online version data as JSON")?
@@ -971,6 +978,8 @@ pub async fn load_game_files(app: &AppHandle) -> Result<()> {
 		)))
 	)?;
 
+	send_collections(app)?;
+
 	send_request(
 		app,
 		Request::Tool(ToolRequest::ContentSearch(ContentSearchRequest::SetEnabled(
@@ -985,19 +994,25 @@ pub async fn load_game_files(app: &AppHandle) -> Result<()> {
 	{
 		let task = start_task(app, "Setting up intellisense")?;
 
-		app_state.intellisense.store(Some(
-			Intellisense {
-				cppt_properties: DashMap::new().into(),
-				cppt_pins: from_slice(include_bytes!("../assets/pins.json")).unwrap(),
-				uicb_prop_types: from_slice(include_bytes!("../assets/uicbPropTypes.json")).unwrap(),
-				matt_properties: DashMap::new().into(),
-				file_types: resource_reverse_dependencies
-					.par_iter()
-					.filter_map(|(x, _)| Some((x.to_owned(), hash_list.entries.get(x)?.resource_type.to_owned())))
-					.collect()
-			}
-			.into()
-		));
+		let intellisense = Intellisense {
+			cppt_properties: DashMap::new().into(),
+			factory_properties: DashMap::new().into(),
+			factory_pins: DashMap::new().into(),
+			cache_location: intellisense_cache_location,
+			cache_changed: Default::default(),
+			cppt_pins: from_slice(include_bytes!("../assets/pins.json")).unwrap(),
+			uicb_prop_types: from_slice(include_bytes!("../assets/uicbPropTypes.json")).unwrap(),
+			matt_properties: DashMap::new().into(),
+			enums: DashMap::new().into(),
+			file_types: resource_reverse_dependencies
+				.par_iter()
+				.filter_map(|(x, _)| Some((x.to_owned(), hash_list.entries.get(x)?.resource_type.to_owned())))
+				.collect()
+		};
+
+		intellisense.load_cache()?;
+
+		app_state.intellisense.store(Some(intellisense.into()));
 
 		finish_task(app, task)?
 	};
@@ -1015,6 +1030,13 @@ pub async fn load_game_files(app: &AppHandle) -> Result<()> {
 		finish_task(app, task)?;
 	}
 
+	send_request(
+		app,
+		Request::Tool(ToolRequest::OutfitBrowser(OutfitBrowserRequest::SetEnabled(
+			app_state.repository.load().is_some() && app_state.hash_list.load().is_some()
+		)))
+	)?;
+
 	if let Some(game_files) = app_state.game_files.load().as_ref()
 		&& let Some(resource_reverse_dependencies) = app_state.resource_reverse_dependencies.load().as_ref()
 		&& let Some(install) = app_settings.load().game_install.as_ref()
@@ -1023,7 +1045,7 @@ pub async fn load_game_files(app: &AppHandle) -> Result<()> {
 		let task = start_task(app, "Refreshing editors")?;
 
 		for editor in app_state.editor_states.iter_mut() {
-			if let EditorData::ResourceOverview { hash } = editor.data {
+			if let EditorData::ResourceOverview { hash, .. } = editor.data {
 				let task = start_task(app, format!("Refreshing resource overview for {}", hash))?;
 
 				initialise_resource_overview(
@@ -1046,7 +1068,7 @@ pub async fn load_game_files(app: &AppHandle) -> Result<()> {
 	}
 }
 
-/// Only available for entities, the repository and unlockables currently
+/// Only available for entities, the repository and unlockables currently. Returns the ID of the opened editor.
 #[try_fn]
 #[context("Couldn't open {hash} in editor")]
 pub async fn open_in_editor(
@@ -1055,7 +1077,7 @@ pub async fn open_in_editor(
 	install: &PathBuf,
 	hash_list: &HashList,
 	hash: RuntimeID
-) -> Result<()> {
+) -> Result<Uuid> {
 	let app_state = app.state::<AppState>();
 
 	match hash_list
@@ -1068,13 +1090,14 @@ pub async fn open_in_editor(
 		"TEMP" => {
 			let task = start_task(app, format!("Loading entity {}", hash))?;
 
-			let entity = extract_entity(
+			let entity = extract_entity_async(
 				game_files,
 				&app_state.cached_entities,
 				get_loaded_game_version(app, install)?,
 				hash_list,
 				hash
-			)?
+			)
+			.await?
 			.to_owned();
 
 			let default_tab_name = format!(
@@ -1113,7 +1136,8 @@ pub async fn open_in_editor(
 					data: EditorData::QNPatch {
 						base: Box::new(entity.to_owned()),
 						current: Box::new(entity),
-						settings: Default::default()
+						settings: Default::default(),
+						recycle_bin: vec![]
 					}
 				}
 			);
@@ -1128,6 +1152,8 @@ pub async fn open_in_editor(
 			)?;
 
 			finish_task(app, task)?;
+
+			id
 		}
 
 		"REPO" => {
@@ -1165,6 +1191,8 @@ pub async fn open_in_editor(
 			)?;
 
 			finish_task(app, task)?;
+
+			id
 		}
 
 		"ORES" if hash == "0057C2C3941115CA".parse()? => {
@@ -1172,9 +1200,7 @@ pub async fn open_in_editor(
 
 			let id = Uuid::new_v4();
 
-			let unlockables: Vec<UnlockableItem> = from_str(&parse_json_ores(
-				&extract_latest_resource(game_files, "0057C2C3941115CA".parse::<RuntimeID>()?)?.1
-			)?)?;
+			let unlockables = extract_unlockables(game_files)?;
 
 			app_state.editor_states.insert(
 				id.to_owned(),
@@ -1200,6 +1226,79 @@ pub async fn open_in_editor(
 			)?;
 
 			finish_task(app, task)?;
+
+			id
+		}
+
+		"ENUM" => {
+			let task = start_task(app, format!("Loading enums {}", hash))?;
+
+			let id = Uuid::new_v4();
+
+			let enums: Vec<SEnumType> = convert_generic(
+				&extract_latest_resource(game_files, hash)?.1,
+				get_loaded_game_version(app, install)?,
+				"ENUM".try_into()?
+			)?;
+
+			app_state.editor_states.insert(
+				id.to_owned(),
+				EditorState {
+					file: None,
+					data: EditorData::Enum { enums }
+				}
+			);
+
+			send_request(
+				app,
+				Request::Global(GlobalRequest::CreateTab {
+					id,
+					name: format!("{}.ENUM", hash),
+					editor_type: EditorType::Enum
+				})
+			)?;
+
+			finish_task(app, task)?;
+
+			id
+		}
+
+		"JSON" => {
+			let task = start_task(app, format!("Loading JSON resource {}", hash))?;
+
+			let id = Uuid::new_v4();
+
+			let content = String::from_utf8(extract_latest_resource(game_files, hash)?.1)?;
+
+			let base: Value = from_str(&content).context("Resource isn't valid JSON")?;
+
+			let kind = get_json_resource_kind(&base).context("There's no schema for this JSON resource")?;
+
+			app_state.editor_states.insert(
+				id.to_owned(),
+				EditorState {
+					file: None,
+					data: EditorData::JsonResource {
+						hash,
+						kind,
+						base,
+						content: format_json(&content)?
+					}
+				}
+			);
+
+			send_request(
+				app,
+				Request::Global(GlobalRequest::CreateTab {
+					id,
+					name: format!("{}.JSON", hash),
+					editor_type: EditorType::JsonResource { kind }
+				})
+			)?;
+
+			finish_task(app, task)?;
+
+			id
 		}
 
 		x => panic!("Opening {x} files in editor is not supported")
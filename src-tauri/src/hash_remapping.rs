@@ -0,0 +1,153 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	sync::LazyLock
+};
+
+use anyhow::{Context, Result};
+use fn_error_context::context;
+use hashbrown::HashMap;
+use hitman_commons::metadata::RuntimeID;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tryvial::try_fn;
+use walkdir::WalkDir;
+
+use crate::{backups::BACKUP_FOLDER, packaging::DIST_FOLDER};
+
+/// Project files which can refer to resources by hash.
+const REMAPPABLE_EXTENSIONS: [&str; 5] = [
+	".entity.json",
+	".entity.patch.json",
+	".repository.json",
+	".unlockables.json",
+	".json.patch.json"
+];
+
+/// A JSON string which is either a hash or a resource path.
+static HASH_STRING: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r#""([0-9A-Fa-f]{16}|\[[^"\\]+\]\.pc_[a-z]+)""#).expect("Regex is valid"));
+
+/// A project file containing hashes which have been (or would be) remapped.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct RemappedFile {
+	pub path: PathBuf,
+
+	/// Old hash or path as written, new hash or path, number of occurrences
+	pub replacements: Vec<(String, String, u32)>
+}
+
+/// Parse a mapping of old hashes to new hashes, keeping the new path of each hash if it was given as one.
+#[try_fn]
+#[context("Couldn't parse hash mapping")]
+fn parse_mapping(mapping: &[(String, String)]) -> Result<HashMap<RuntimeID, (String, Option<String>)>> {
+	mapping
+		.iter()
+		.map(|(old, new)| {
+			let new = new.trim();
+
+			Ok((
+				RuntimeID::from_any(old.trim()).with_context(|| format!("Invalid hash {}", old))?,
+				(
+					RuntimeID::from_any(new)
+						.with_context(|| format!("Invalid hash {}", new))?
+						.to_string(),
+					new.starts_with('[').then(|| new.to_owned())
+				)
+			))
+		})
+		.collect::<Result<HashMap<_, _>>>()?
+}
+
+/// Replace every occurrence of the old hashes in the project's entities, patches, repository and unlockables files and
+/// manifest with the new hashes. Occurrences can be written as hashes or as paths, and are replaced with the new hash in
+/// the form it was given. Nothing is written if it's a dry run.
+#[try_fn]
+#[context("Couldn't remap hashes in {}", project_path.display())]
+pub fn remap_hashes(project_path: &Path, mapping: &[(String, String)], dry_run: bool) -> Result<Vec<RemappedFile>> {
+	let mapping = parse_mapping(mapping)?;
+
+	let mut remapped = vec![];
+
+	for entry in WalkDir::new(project_path)
+		.into_iter()
+		.filter_entry(|x| x.file_name() != ".git" && x.file_name() != DIST_FOLDER && x.file_name() != BACKUP_FOLDER)
+		.filter_map(|x| x.ok())
+		.filter(|x| x.file_type().is_file())
+	{
+		let file_name = entry.file_name().to_string_lossy();
+
+		if file_name != "manifest.json" && !REMAPPABLE_EXTENSIONS.iter().any(|x| file_name.ends_with(x)) {
+			continue;
+		}
+
+		let contents = fs::read_to_string(entry.path()).with_context(|| format!("Couldn't read {}", file_name))?;
+
+		let mut replacements: Vec<(String, String, u32)> = vec![];
+
+		let remapped_contents = HASH_STRING.replace_all(&contents, |captures: &Captures| {
+			if let Ok(hash) = RuntimeID::from_any(&captures[1])
+				&& let Some((new_hash, new_path)) = mapping.get(&hash)
+			{
+				let old = captures[1].to_owned();
+
+				let new = new_path.as_ref().unwrap_or(new_hash);
+
+				if let Some(replacement) = replacements.iter_mut().find(|(x, _, _)| *x == old) {
+					replacement.2 += 1;
+				} else {
+					replacements.push((old, new.to_owned(), 1));
+				}
+
+				format!("\"{}\"", new)
+			} else {
+				captures[0].to_owned()
+			}
+		});
+
+		if !replacements.is_empty() {
+			if !dry_run {
+				fs::write(entry.path(), remapped_contents.as_bytes())
+					.with_context(|| format!("Couldn't write {}", file_name))?;
+			}
+
+			remapped.push(RemappedFile {
+				path: entry.path().to_owned(),
+				replacements
+			});
+		}
+	}
+
+	remapped
+}
+
+/// Get the project's custom paths after remapping hashes, without the paths of old hashes and with the new paths that
+/// were given. Returns None if the custom paths don't need to change.
+#[try_fn]
+#[context("Couldn't remap custom paths")]
+pub fn remap_custom_paths(custom_paths: &[String], mapping: &[(String, String)]) -> Result<Option<Vec<String>>> {
+	let mapping = parse_mapping(mapping)?;
+
+	let mut remapped = custom_paths
+		.iter()
+		.filter(|x| {
+			RuntimeID::from_any(x)
+				.map(|x| !mapping.contains_key(&x))
+				.unwrap_or(true)
+		})
+		.cloned()
+		.collect::<Vec<_>>();
+
+	for new_path in mapping.values().filter_map(|(_, new_path)| new_path.as_ref()) {
+		if !remapped.contains(new_path) {
+			remapped.push(new_path.to_owned());
+		}
+	}
+
+	if remapped != custom_paths {
+		return Ok(Some(remapped));
+	}
+
+	None
+}
@@ -0,0 +1,71 @@
+use std::sync::LazyLock;
+
+use hashbrown::{HashMap, HashSet};
+use hitman_commons::{hash_list::HashList, metadata::RuntimeID};
+use regex::Regex;
+use rpkg_rs::resource::partition_manager::PartitionManager;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::rpkg::extract_latest_overview_info;
+
+/// A hash or resource path, possibly surrounded by other text like in a crash log.
+pub static HASH_OR_PATH: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r"\[[^\]\r\n]+\]\.pc_[a-z]+|\b[0-9A-Fa-f]{16}\b").expect("Regex is valid"));
+
+/// A hash or path from the input, resolved against the hash list and game files.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct ResolvedResource {
+	/// The hash or path as it appeared in the input
+	pub input: String,
+
+	pub hash: String,
+	pub resource_type: Option<String>,
+	pub path: Option<String>,
+	pub hint: Option<String>,
+
+	/// The chunk and patch containing the latest copy of the resource, or None if it isn't in the game files
+	pub chunk: Option<String>,
+
+	/// How many resources in the game files depend on this one, or None if the game files aren't loaded
+	pub reverse_references: Option<u32>
+}
+
+/// Find every hash and resource path in the input and resolve them, in the order they first appear. Anything which isn't
+/// in the hash list or game files is still included, with only what can be worked out from the input.
+pub fn resolve_hashes(
+	input: &str,
+	hash_list: Option<&HashList>,
+	game_files: Option<&PartitionManager>,
+	reverse_dependencies: Option<&HashMap<RuntimeID, Vec<RuntimeID>>>
+) -> Vec<ResolvedResource> {
+	let mut seen = HashSet::new();
+
+	HASH_OR_PATH
+		.find_iter(input)
+		.filter_map(|x| Some((x.as_str(), RuntimeID::from_any(x.as_str()).ok()?)))
+		.filter(|(_, hash)| seen.insert(*hash))
+		.map(|(input, hash)| {
+			let entry = hash_list.and_then(|x| x.entries.get(&hash));
+
+			let overview_info = game_files.and_then(|x| extract_latest_overview_info(x, hash).ok());
+
+			ResolvedResource {
+				input: input.to_owned(),
+				hash: hash.to_string(),
+				resource_type: entry.map(|x| x.resource_type.to_string()).or_else(|| {
+					overview_info
+						.as_ref()
+						.map(|(resource_type, _, _)| resource_type.to_string())
+				}),
+				path: entry
+					.and_then(|x| x.path.to_owned())
+					.or_else(|| input.starts_with('[').then(|| input.to_owned())),
+				hint: entry.and_then(|x| x.hint.to_owned()),
+				chunk: overview_info.map(|(_, chunk, _)| chunk),
+				reverse_references: reverse_dependencies
+					.map(|x| x.get(&hash).map(|x| x.len() as u32).unwrap_or_default())
+			}
+		})
+		.collect()
+}
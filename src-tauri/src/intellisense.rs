@@ -1,4 +1,12 @@
-use std::{str::FromStr, sync::Arc};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	str::FromStr,
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering}
+	}
+};
 
 use anyhow::{Context, Result, bail};
 use dashmap::DashMap;
@@ -26,6 +34,7 @@ use tryvial::try_fn;
 
 use crate::{
 	entity::get_local_reference,
+	entity_cache::EntityCache,
 	resourcelib::{
 		EAttributeKind, EExtendedPropertyType, convert_uicb, h2_convert_cppt, h2_convert_dswb, h2_convert_ecpb,
 		h2_convert_wsgb, h3_convert_cppt, h3_convert_dswb, h3_convert_ecpb, h3_convert_wsgb, h2016_convert_cppt,
@@ -51,10 +60,64 @@ pub struct CPPTPinInfo {
 	pub description: String
 }
 
+/// The properties and pins of game factories, saved between sessions so they don't need to be worked out again.
+#[derive(Serialize, Deserialize, Default)]
+struct IntellisenseCache {
+	/// Identifies the game files the cache was built from; the cache is discarded if they've changed
+	fingerprint: String,
+
+	properties: HashMap<RuntimeID, Vec<(String, String, Value, bool)>>,
+	pins: HashMap<RuntimeID, (Vec<String>, Vec<String>)>
+}
+
+/// Identify the state of the game files by the names, sizes and modification times of the RPKGs, which change whenever
+/// the game is patched. Whether modded files are extracted is included, since that changes which patches are read.
+#[try_fn]
+#[context("Couldn't get fingerprint of game files in {}", runtime_path.display())]
+pub fn get_game_files_fingerprint(runtime_path: &Path, extract_modded_files: bool) -> Result<String> {
+	let mut rpkgs = fs::read_dir(runtime_path)?
+		.filter_map(|x| x.ok())
+		.filter(|x| x.path().extension().is_some_and(|x| x.eq_ignore_ascii_case("rpkg")))
+		.map(|x| {
+			let metadata = x.metadata()?;
+
+			anyhow::Ok(format!(
+				"{}:{}:{}",
+				x.file_name().to_string_lossy(),
+				metadata.len(),
+				metadata
+					.modified()?
+					.duration_since(std::time::UNIX_EPOCH)
+					.unwrap_or_default()
+					.as_secs()
+			))
+		})
+		.collect::<Result<Vec<_>>>()?;
+
+	rpkgs.sort();
+
+	format!(
+		"{:x}",
+		md5::compute(format!("{}|{}", extract_modded_files, rpkgs.join("|")))
+	)
+}
+
 pub struct Intellisense {
 	/// CPPT -> Property -> (Type, Value)
 	pub cppt_properties: Arc<DashMap<RuntimeID, HashMap<String, (String, Value)>>>,
 
+	/// Factory -> Properties it gives sub-entities, for game factories; persisted in the intellisense cache
+	pub factory_properties: Arc<DashMap<RuntimeID, Vec<(String, String, Value, bool)>>>,
+
+	/// Factory -> (Input, Output) pins it gives sub-entities, for game factories; persisted in the intellisense cache
+	pub factory_pins: Arc<DashMap<RuntimeID, (Vec<String>, Vec<String>)>>,
+
+	/// Where the intellisense cache is saved and the fingerprint of the game files it's for
+	pub cache_location: Option<(PathBuf, String)>,
+
+	/// Whether anything has been added to the cache since it was loaded or last saved
+	pub cache_changed: Arc<AtomicBool>,
+
 	pub cppt_pins: HashMap<RuntimeID, CPPTPinsInfo>,
 
 	/// Property type as enum -> String version
@@ -62,10 +125,68 @@ pub struct Intellisense {
 
 	pub matt_properties: Arc<DashMap<RuntimeID, IndexMap<String, MaterialOverride>>>,
 
+	/// Enum type -> Values, for enums loaded in the enum editor
+	pub enums: Arc<DashMap<String, Vec<String>>>,
+
 	pub file_types: HashMap<RuntimeID, ResourceType>
 }
 
 impl Intellisense {
+	/// Load the properties and pins of game factories cached in a previous session, if they were for the same game files.
+	#[try_fn]
+	#[context("Couldn't load intellisense cache")]
+	pub fn load_cache(&self) -> Result<()> {
+		let Some((path, fingerprint)) = self.cache_location.as_ref() else {
+			return Ok(());
+		};
+
+		let Ok(data) = fs::read(path) else {
+			return Ok(());
+		};
+
+		// An unreadable cache is treated the same as an outdated one
+		let Ok(cache) = serde_smile::from_slice::<IntellisenseCache>(&data) else {
+			return Ok(());
+		};
+
+		if cache.fingerprint != *fingerprint {
+			return Ok(());
+		}
+
+		self.factory_properties.extend(cache.properties);
+		self.factory_pins.extend(cache.pins);
+	}
+
+	/// Save the properties and pins of game factories to the intellisense cache, if any have been found since it was
+	/// loaded or last saved.
+	#[try_fn]
+	#[context("Couldn't save intellisense cache")]
+	pub fn save_cache(&self) -> Result<()> {
+		let Some((path, fingerprint)) = self.cache_location.as_ref() else {
+			return Ok(());
+		};
+
+		if !self.cache_changed.swap(false, Ordering::Relaxed) {
+			return Ok(());
+		}
+
+		let cache = IntellisenseCache {
+			fingerprint: fingerprint.to_owned(),
+			properties: self
+				.factory_properties
+				.iter()
+				.map(|x| (x.key().to_owned(), x.value().to_owned()))
+				.collect(),
+			pins: self
+				.factory_pins
+				.iter()
+				.map(|x| (x.key().to_owned(), x.value().to_owned()))
+				.collect()
+		};
+
+		fs::write(path, serde_smile::to_vec(&cache)?)?;
+	}
+
 	#[try_fn]
 	#[context("Couldn't get properties for CPPT {}", cppt)]
 	fn get_cppt_properties(
@@ -386,7 +507,7 @@ impl Intellisense {
 	pub fn get_properties(
 		&self,
 		game_files: &PartitionManager,
-		cached_entities: &DashMap<RuntimeID, Entity>,
+		cached_entities: &EntityCache,
 		hash_list: &HashList,
 		game_version: GameVersion,
 		entity: &Entity,
@@ -468,7 +589,11 @@ impl Intellisense {
 					}
 					.into_par_iter()
 					.map(|factory| {
-						Ok({
+						if let Some(cached) = self.factory_properties.get(&factory.get_id()) {
+							return Ok(cached.to_owned());
+						}
+
+						let found = {
 							let mut found = vec![];
 
 							if let Some(ty) = self.file_types.get(&factory.get_id()) {
@@ -784,7 +909,12 @@ impl Intellisense {
 							}
 
 							found
-						})
+						};
+
+						self.factory_properties.insert(factory.get_id(), found.to_owned());
+						self.cache_changed.store(true, Ordering::Relaxed);
+
+						Ok(found)
 					})
 					.collect::<Result<Vec<_>>>()?
 					.into_iter()
@@ -809,7 +939,7 @@ impl Intellisense {
 	pub fn get_specific_property(
 		&self,
 		game_files: &PartitionManager,
-		cached_entities: &DashMap<RuntimeID, Entity>,
+		cached_entities: &EntityCache,
 		hash_list: &HashList,
 		game_version: GameVersion,
 		entity: &Entity,
@@ -1180,7 +1310,7 @@ impl Intellisense {
 	pub fn get_pins(
 		&self,
 		game_files: &PartitionManager,
-		cached_entities: &DashMap<RuntimeID, Entity>,
+		cached_entities: &EntityCache,
 		hash_list: &HashList,
 		game_version: GameVersion,
 		entity: &Entity,
@@ -1289,7 +1419,11 @@ impl Intellisense {
 		}
 		.into_par_iter()
 		.map(|factory| {
-			Ok({
+			if let Some(cached) = self.factory_pins.get(&factory.get_id()) {
+				return Ok(cached.to_owned());
+			}
+
+			let found = {
 				let mut input = vec![];
 				let mut output = vec![];
 
@@ -1482,7 +1616,12 @@ impl Intellisense {
 				}
 
 				(input, output)
-			})
+			};
+
+			self.factory_pins.insert(factory.get_id(), found.to_owned());
+			self.cache_changed.store(true, Ordering::Relaxed);
+
+			Ok(found)
 		})
 		.collect::<Result<Vec<_>>>()?
 		.into_iter()
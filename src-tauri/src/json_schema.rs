@@ -0,0 +1,349 @@
+use hashbrown::HashMap;
+use regex::Regex;
+use serde_json::Value;
+
+use crate::model::TextDiagnostic;
+
+/// Validate a value against a JSON schema, returning a list of (JSON pointer, error message) pairs.
+///
+/// This supports the subset of JSON Schema used by the manifest schema: `type`, `enum`, `const`, `required`,
+/// `properties`, `additionalProperties`, `items`, `anyOf`/`oneOf`, `pattern` and local `$ref`s.
+pub fn validate(schema: &Value, value: &Value) -> Vec<(String, String)> {
+	let mut errors = vec![];
+
+	validate_inner(schema, schema, value, "", &mut errors);
+
+	errors
+}
+
+fn validate_inner(root: &Value, schema: &Value, value: &Value, pointer: &str, errors: &mut Vec<(String, String)>) {
+	let Some(schema) = schema.as_object() else {
+		return;
+	};
+
+	if let Some(reference) = schema.get("$ref").and_then(|x| x.as_str()) {
+		if let Some(target) = reference.strip_prefix('#').and_then(|x| root.pointer(x)) {
+			validate_inner(root, target, value, pointer, errors);
+		}
+
+		return;
+	}
+
+	if let Some(ty) = schema.get("type") {
+		let allowed = match ty {
+			Value::String(x) => vec![x.as_str()],
+			Value::Array(x) => x.iter().filter_map(|x| x.as_str()).collect(),
+			_ => vec![]
+		};
+
+		if !allowed.is_empty() && !allowed.iter().any(|ty| value_is_type(value, ty)) {
+			errors.push((
+				pointer.into(),
+				format!("Expected {}, found {}", allowed.join(" or "), type_name(value))
+			));
+
+			return;
+		}
+	}
+
+	if let Some(allowed) = schema.get("enum").and_then(|x| x.as_array())
+		&& !allowed.contains(value)
+	{
+		errors.push((
+			pointer.into(),
+			format!(
+				"Value must be one of {}",
+				allowed.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", ")
+			)
+		));
+	}
+
+	if let Some(expected) = schema.get("const")
+		&& expected != value
+	{
+		errors.push((pointer.into(), format!("Value must be {}", expected)));
+	}
+
+	if let Some(variants) = schema.get("anyOf").or(schema.get("oneOf")).and_then(|x| x.as_array())
+		&& !variants.iter().any(|variant| {
+			let mut variant_errors = vec![];
+			validate_inner(root, variant, value, pointer, &mut variant_errors);
+			variant_errors.is_empty()
+		}) {
+		errors.push((pointer.into(), "Value did not match any of the allowed forms".into()));
+	}
+
+	if let Some(pattern) = schema.get("pattern").and_then(|x| x.as_str())
+		&& let Some(value) = value.as_str()
+		&& let Ok(regex) = Regex::new(pattern)
+		&& !regex.is_match(value)
+	{
+		errors.push((pointer.into(), format!("Value must match the pattern {}", pattern)));
+	}
+
+	if let Some(object) = value.as_object() {
+		if let Some(required) = schema.get("required").and_then(|x| x.as_array()) {
+			for key in required.iter().filter_map(|x| x.as_str()) {
+				if !object.contains_key(key) {
+					errors.push((pointer.into(), format!("Missing required property {}", key)));
+				}
+			}
+		}
+
+		let properties = schema.get("properties").and_then(|x| x.as_object());
+
+		for (key, property_value) in object {
+			let property_pointer = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+
+			if let Some(property_schema) = properties.and_then(|x| x.get(key)) {
+				validate_inner(root, property_schema, property_value, &property_pointer, errors);
+			} else {
+				match schema.get("additionalProperties") {
+					Some(Value::Bool(false)) => {
+						errors.push((property_pointer, format!("Unknown property {}", key)));
+					}
+
+					Some(additional_schema @ Value::Object(_)) => {
+						validate_inner(root, additional_schema, property_value, &property_pointer, errors);
+					}
+
+					_ => {}
+				}
+			}
+		}
+	}
+
+	if let Some(array) = value.as_array()
+		&& let Some(items) = schema.get("items")
+	{
+		for (index, item) in array.iter().enumerate() {
+			validate_inner(root, items, item, &format!("{}/{}", pointer, index), errors);
+		}
+	}
+}
+
+fn value_is_type(value: &Value, ty: &str) -> bool {
+	match ty {
+		"object" => value.is_object(),
+		"array" => value.is_array(),
+		"string" => value.is_string(),
+		"number" => value.is_number(),
+		"integer" => value.is_i64() || value.is_u64(),
+		"boolean" => value.is_boolean(),
+		"null" => value.is_null(),
+		_ => true
+	}
+}
+
+fn type_name(value: &Value) -> &'static str {
+	match value {
+		Value::Null => "null",
+		Value::Bool(_) => "boolean",
+		Value::Number(_) => "number",
+		Value::String(_) => "string",
+		Value::Array(_) => "array",
+		Value::Object(_) => "object"
+	}
+}
+
+/// Find the character range of every value in a JSON document, keyed by JSON pointer.
+///
+/// Object members are located by their key rather than their value, so that errors on large nested values don't
+/// highlight the entire value.
+fn get_spans(text: &str) -> HashMap<String, (usize, usize)> {
+	let chars = text.chars().collect::<Vec<_>>();
+	let mut spans = HashMap::new();
+
+	let mut pos = 0;
+	scan_value(&chars, &mut pos, "", None, &mut spans);
+
+	spans
+}
+
+fn skip_whitespace(chars: &[char], pos: &mut usize) {
+	while *pos < chars.len() && chars[*pos].is_whitespace() {
+		*pos += 1;
+	}
+}
+
+fn scan_string(chars: &[char], pos: &mut usize) -> String {
+	let mut value = String::new();
+
+	// Opening quote
+	*pos += 1;
+
+	while *pos < chars.len() && chars[*pos] != '"' {
+		if chars[*pos] == '\\' {
+			*pos += 1;
+		}
+
+		if let Some(&char) = chars.get(*pos) {
+			value.push(char);
+		}
+
+		*pos += 1;
+	}
+
+	// Closing quote
+	*pos += 1;
+
+	value
+}
+
+fn scan_value(
+	chars: &[char],
+	pos: &mut usize,
+	pointer: &str,
+	key_span: Option<(usize, usize)>,
+	spans: &mut HashMap<String, (usize, usize)>
+) {
+	skip_whitespace(chars, pos);
+
+	let start = *pos;
+
+	match chars.get(*pos) {
+		Some('{') => {
+			*pos += 1;
+
+			loop {
+				skip_whitespace(chars, pos);
+
+				match chars.get(*pos) {
+					Some('"') => {
+						let key_start = *pos;
+						let key = scan_string(chars, pos);
+						let key_end = *pos;
+
+						skip_whitespace(chars, pos);
+
+						// Colon
+						*pos += 1;
+
+						scan_value(
+							chars,
+							pos,
+							&format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1")),
+							Some((key_start, key_end)),
+							spans
+						);
+					}
+
+					Some(',') => *pos += 1,
+
+					Some('}') => {
+						*pos += 1;
+						break;
+					}
+
+					_ => break
+				}
+			}
+		}
+
+		Some('[') => {
+			*pos += 1;
+
+			let mut index = 0;
+
+			loop {
+				skip_whitespace(chars, pos);
+
+				match chars.get(*pos) {
+					Some(',') => *pos += 1,
+
+					Some(']') => {
+						*pos += 1;
+						break;
+					}
+
+					Some(_) => {
+						scan_value(chars, pos, &format!("{}/{}", pointer, index), None, spans);
+						index += 1;
+					}
+
+					None => break
+				}
+			}
+		}
+
+		Some('"') => {
+			scan_string(chars, pos);
+		}
+
+		Some(_) => {
+			while *pos < chars.len() && !matches!(chars[*pos], ',' | '}' | ']') && !chars[*pos].is_whitespace() {
+				*pos += 1;
+			}
+		}
+
+		None => {}
+	}
+
+	let (start, end) = match (chars.get(start), key_span) {
+		// Only highlight the opening bracket of objects and arrays
+		(Some('{' | '['), None) => (start, start + 1),
+		(_, Some(key_span)) => key_span,
+		_ => (start, *pos)
+	};
+
+	spans.insert(pointer.into(), (start, end));
+}
+
+/// Convert a character offset to a 1-indexed (line, column) pair.
+fn offset_to_position(text: &str, offset: usize) -> (u32, u32) {
+	let mut line = 1;
+	let mut column = 1;
+
+	for char in text.chars().take(offset) {
+		if char == '\n' {
+			line += 1;
+			column = 1;
+		} else {
+			column += 1;
+		}
+	}
+
+	(line, column)
+}
+
+/// Validate JSON text against a schema, producing diagnostics for any syntax or schema errors.
+pub fn get_diagnostics(schema: &Value, text: &str) -> Vec<TextDiagnostic> {
+	let value = match serde_json::from_str::<Value>(text) {
+		Ok(value) => value,
+
+		Err(err) => {
+			return vec![TextDiagnostic {
+				message: format!("Invalid JSON: {}", err),
+				start_line: err.line() as u32,
+				start_column: err.column() as u32,
+				end_line: err.line() as u32,
+				end_column: err.column() as u32 + 1
+			}];
+		}
+	};
+
+	get_pointer_diagnostics(text, validate(schema, &value))
+}
+
+/// Locate a list of (JSON pointer, error message) pairs in JSON text, producing diagnostics for them.
+pub fn get_pointer_diagnostics(text: &str, errors: Vec<(String, String)>) -> Vec<TextDiagnostic> {
+	let spans = get_spans(text);
+
+	errors
+		.into_iter()
+		.map(|(pointer, message)| {
+			let (start, end) = spans.get(&pointer).copied().unwrap_or((0, 1));
+
+			let (start_line, start_column) = offset_to_position(text, start);
+			let (end_line, end_column) = offset_to_position(text, end);
+
+			TextDiagnostic {
+				message,
+				start_line,
+				start_column,
+				end_line,
+				end_column
+			}
+		})
+		.collect()
+}
@@ -0,0 +1,116 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use serde_json::{Map, Value, from_slice, to_string};
+use tryvial::try_fn;
+
+use crate::biome::format_json;
+
+/// The languages the framework accepts in the localisation section of a manifest.
+pub const FRAMEWORK_LANGUAGES: [&str; 9] = [
+	"english",
+	"french",
+	"italian",
+	"german",
+	"spanish",
+	"russian",
+	"chineseSimplified",
+	"chineseTraditional",
+	"japanese"
+];
+
+pub fn default_localisation_languages() -> Vec<String> {
+	FRAMEWORK_LANGUAGES.into_iter().map(|x| x.into()).collect()
+}
+
+/// Convert text to the uppercase, underscore-separated form used in localisation keys, like `MY_NEW_ITEM`.
+fn to_key_part(text: &str) -> String {
+	text.chars()
+		.map(|x| {
+			if x.is_ascii_alphanumeric() {
+				x.to_ascii_uppercase()
+			} else {
+				'_'
+			}
+		})
+		.collect::<String>()
+		.split('_')
+		.filter(|x| !x.is_empty())
+		.collect::<Vec<_>>()
+		.join("_")
+}
+
+/// Generate localisation keys for an entry and add them to the localisation section of the project's manifest, with the
+/// given placeholder strings for each of the given languages. Each key is made from the mod ID, the entry's name and the
+/// suffix given with the placeholder, like `UI_MYMOD_MY_NEW_ITEM_NAME`; a number is added if the key is already taken.
+/// The framework turns the keys into LINE hashes and LOCR entries when the mod is deployed, so the returned keys can be
+/// used directly in the repository and unlockables.
+#[try_fn]
+#[context("Couldn't generate localisation for {}", name)]
+pub fn add_localisation(
+	project_path: &Path,
+	languages: &[String],
+	name: &str,
+	strings: &[(&str, String)]
+) -> Result<Vec<String>> {
+	if languages.is_empty() {
+		bail!("No localisation languages are set for the project");
+	}
+
+	let manifest_path = project_path.join("manifest.json");
+
+	let mut manifest: Value =
+		from_slice(&fs::read(&manifest_path).context("Couldn't read manifest.json")?).context("Invalid manifest")?;
+
+	let manifest = manifest.as_object_mut().context("Manifest is not an object")?;
+
+	let mod_id = to_key_part(
+		manifest
+			.get("id")
+			.and_then(|x| x.as_str())
+			.context("Manifest has no ID")?
+	);
+
+	let localisation = manifest
+		.entry("localisation")
+		.or_insert_with(|| Value::Object(Map::new()))
+		.as_object_mut()
+		.context("localisation is not an object")?;
+
+	let mut keys = vec![];
+
+	for (suffix, placeholder) in strings {
+		let base_key = format!("UI_{}_{}_{}", mod_id, to_key_part(name), to_key_part(suffix));
+
+		let key = (1..)
+			.map(|x| {
+				if x == 1 {
+					base_key.to_owned()
+				} else {
+					format!("{}_{}", base_key, x)
+				}
+			})
+			.find(|key| {
+				!localisation
+					.values()
+					.any(|strings| strings.as_object().is_some_and(|x| x.contains_key(key)))
+			})
+			.unwrap();
+
+		for language in languages {
+			localisation
+				.entry(language.to_owned())
+				.or_insert_with(|| Value::Object(Map::new()))
+				.as_object_mut()
+				.with_context(|| format!("Localisation for {} is not an object", language))?
+				.insert(key.to_owned(), Value::String(placeholder.to_owned()));
+		}
+
+		keys.push(key);
+	}
+
+	fs::write(&manifest_path, format_json(&to_string(&manifest)?)?)?;
+
+	keys
+}
@@ -6,18 +6,66 @@
 #![feature(try_find)]
 #![allow(clippy::type_complexity)]
 
+pub mod aloc;
+pub mod audio_event_stub;
+pub mod audio_import;
+pub mod audio_names;
+pub mod backups;
+pub mod behaviour_tree;
 pub mod biome;
+pub mod borg;
+pub mod chunk_assignment;
+pub mod collaboration;
+pub mod collections;
+pub mod compatibility;
+pub mod conflicts;
+pub mod converters;
+pub mod crash_analysis;
+pub mod diagnostics;
+pub mod dropped_files;
 pub mod editor_connection;
 pub mod entity;
+pub mod entity_cache;
+pub mod entity_docs;
 pub mod event_handling;
+pub mod external_tools;
+pub mod extraction_presets;
+pub mod file_operations;
 pub mod general;
+pub mod hash_remapping;
+pub mod hash_resolution;
 pub mod intellisense;
+pub mod json_schema;
 pub mod languages;
+pub mod localisation;
+pub mod material_rebuild;
+pub mod mod_conflicts;
 pub mod model;
+pub mod navigation;
 pub mod ores_repo;
+pub mod orphaned_files;
+pub mod packaging;
+pub mod patch_splitting;
+pub mod patches;
+pub mod reference_flags;
+pub mod resource_capabilities;
 pub mod resourcelib;
 pub mod rpkg;
+pub mod saved_searches;
+pub mod scaleform;
+pub mod scene_outline;
 pub mod show_in_folder;
+pub mod smf;
+pub mod soundbank;
+pub mod split_view;
+pub mod table_import;
+pub mod tasks;
+pub mod telemetry;
+pub mod transform;
+pub mod unit_conversion;
+pub mod watch_list;
+pub mod waveform;
+pub mod workspace_trust;
 
 use std::{
 	backtrace::{Backtrace, BacktraceStatus},
@@ -31,38 +79,62 @@ use std::{
 
 use anyhow::{Context, Error, Result, anyhow, bail};
 use arc_swap::ArcSwap;
+use backups::{BACKUP_FOLDER, back_up_project_file};
 use biome::format_json;
+use compatibility::warn_about_incompatibilities;
+use conflicts::find_patch_conflicts;
 use dashmap::DashMap;
+use dropped_files::{DroppedFileKind, detect_dropped_files, import_dropped_file};
 use editor_connection::EditorConnection;
-use entity::get_diff_info;
+use entity::{get_diff_info, tidy_entity_json};
+use entity_cache::EntityCache;
 use event_handling::{
-	repository_patch::handle_repository_patch_event, resource_overview::handle_resource_overview_event,
-	tools::handle_tool_event, unlockables_patch::handle_unlockables_patch_event
+	dialogue::{handle_dialogue_event, rebuild_dialogue},
+	enums::handle_enum_event,
+	git::get_git_status,
+	json_patch::handle_json_patch_event,
+	json_resource::handle_json_resource_event,
+	repository_patch::handle_repository_patch_event,
+	resource_overview::handle_resource_overview_event,
+	text::{find_in_text, replace_in_text},
+	tools::handle_tool_event,
+	unlockables_patch::handle_unlockables_patch_event
 };
 use fn_error_context::context;
 use general::open_file;
 use hashbrown::HashMap;
 use hitman_commons::game::GameVersion;
 use hitman_commons::game_detection::detect_installs;
-use indexmap::IndexMap;
 use json_patch::Patch;
+use json_schema::get_diagnostics;
 use log::{LevelFilter, info, trace};
 use model::{
 	AppSettings, AppState, ContentSearchResultsEvent, ContentSearchResultsRequest, EditorConnectionEvent, EditorData,
 	EditorEvent, EditorRequest, EditorState, EditorType, EntityEditorRequest, EntityMetadataRequest,
-	EntityMonacoRequest, EntityTreeRequest, Event, FileBrowserRequest, GlobalEvent, GlobalRequest, JsonPatchType,
-	Project, ProjectSettings, Request, SettingsRequest, TextEditorEvent, TextEditorRequest, TextFileType, ToolRequest
+	EntityMonacoRequest, EntityTreeRequest, Event, FileBrowserRequest, GitRequest, GlobalEvent, GlobalRequest,
+	JsonPatchType, Project, ProjectSettings, Request, SettingsRequest, TextEditorEvent, TextEditorRequest,
+	TextFileType, ToolRequest
 };
+use navigation::{navigate, record_navigation};
 use notify::RecursiveMode;
 use notify_debouncer_full::FileIdMap;
+use patch_splitting::{exceeds_split_thresholds, split_patch, write_split_patch};
+use patches::unlockables_to_patchable;
 use quickentity_rs::{generate_patch, qn_structs::Property};
 use rand::{Rng, rng};
+use resource_capabilities::get_loaded_resource_capabilities;
+use resourcelib::convert_generic_to_binary;
+use rpkg::extraction_queue::ExtractionQueue;
+use saved_searches::{rerun_saved_searches_on_change, send_saved_searches};
 use serde::{Deserialize, Serialize};
-use serde_json::{Value, from_slice, json, to_value, to_vec};
+use serde_json::{Value, from_slice, from_str, json, to_value, to_vec};
 use show_in_folder::show_in_folder;
+use smf::notify_dependency_issues;
+use split_view::{close_tab, get_tab_document, split_tab};
 use tauri::{AppHandle, Emitter, Manager, async_runtime};
-use tauri_plugin_aptabase::{EventTracker, InitOptions};
+use tauri_plugin_aptabase::InitOptions;
 use tauri_plugin_dialog::DialogExt;
+use telemetry::{flush_events_blocking, is_offline, is_offline_mode, set_offline_mode, track_event};
 use tryvial::try_fn;
 use uuid::Uuid;
 use velcro::vec;
@@ -82,6 +154,9 @@ pub const TONYTOOLS_HASH_LIST_ENDPOINT: &str =
 
 pub const UPLOAD_LOG_ENDPOINT: &str = "https://hitman-resources.netlify.app/.netlify/functions/upload-gk-log";
 
+pub const MANIFEST_SCHEMA_ENDPOINT: &str =
+	"https://raw.githubusercontent.com/atampy25/simple-mod-framework/main/Mod%20Manager/src/lib/manifest-schema.json";
+
 thread_local!(static IS_MAIN_THREAD: Cell<bool> = const { Cell::new(false) });
 thread_local!(static LOG_DIR: Cell<PathBuf> = Cell::new(Default::default()));
 
@@ -114,8 +189,12 @@ async fn main() {
 
 	tauri::async_runtime::set(tokio::runtime::Handle::current());
 
-	let specta =
-		tauri_specta::Builder::<tauri::Wry>::new().commands(tauri_specta::collect_commands![event, show_in_folder]);
+	let specta = tauri_specta::Builder::<tauri::Wry>::new().commands(tauri_specta::collect_commands![
+		event,
+		show_in_folder,
+		is_offline_mode,
+		get_loaded_resource_capabilities
+	]);
 
 	#[cfg(debug_assertions)]
 	if Path::new("../src/lib").is_dir() {
@@ -151,14 +230,16 @@ async fn main() {
 							.map(|loc| format!("{}:{}:{}", loc.file(), loc.line(), loc.column()))
 							.unwrap_or_default();
 
-						client
-							.track_event(
-								"Panic",
-								Some(json!({
-								  "info": format!("{} - {}", location, msg),
-								}))
-							)
-							.unwrap();
+						if !is_offline() {
+							client
+								.track_event(
+									"Panic",
+									Some(json!({
+									  "info": format!("{} - {}", location, msg),
+									}))
+								)
+								.unwrap();
+						}
 
 						let mut panic_report = String::new();
 
@@ -221,8 +302,6 @@ async fn main() {
 		.setup(|app| {
 			LOG_DIR.set(app.path().app_log_dir().expect("Couldn't get log dir"));
 
-			app.track_event("App started", None).unwrap();
-
 			info!("Starting app");
 
 			let app_data_path = app.path().app_data_dir().expect("Couldn't get data dir");
@@ -264,6 +343,10 @@ async fn main() {
 				app.manage(ArcSwap::new(settings.into()));
 			}
 
+			set_offline_mode(app.state::<ArcSwap<AppSettings>>().load().offline_mode);
+
+			track_event(app.handle(), "App started", None);
+
 			info!("Loaded settings");
 
 			if app_data_path.join("temp").exists() {
@@ -287,10 +370,45 @@ async fn main() {
 				editor_states: DashMap::new().into(),
 				game_files: None.into(),
 				resource_reverse_dependencies: None.into(),
-				cached_entities: DashMap::new().into(),
+				cached_entities: EntityCache::new(
+					app.state::<ArcSwap<AppSettings>>().load().entity_cache_max_entries,
+					app.state::<ArcSwap<AppSettings>>().load().entity_cache_max_memory
+				)
+				.into(),
 				repository: None.into(),
 				intellisense: None.into(),
-				editor_connection: EditorConnection::new(app.handle().clone())
+				manifest_schema: fs::read(app_data_path.join("manifest_schema.json"))
+					.ok()
+					.and_then(|x| from_slice(&x).ok())
+					.unwrap_or_else(|| from_slice(include_bytes!("../assets/manifest-schema.json")).unwrap())
+					.into(),
+				entity_docs: fs::read(app_data_path.join("entity_docs.json"))
+					.ok()
+					.and_then(|x| from_slice(&x).ok())
+					.unwrap_or_else(|| from_slice(include_bytes!("../assets/entity-docs.json")).unwrap())
+					.into(),
+				collections: fs::read(app_data_path.join("collections.json"))
+					.ok()
+					.and_then(|x| from_slice(&x).ok())
+					.unwrap_or_default()
+					.into(),
+				local_hash_list: fs::read(app_data_path.join("local_hash_list.json"))
+					.ok()
+					.and_then(|x| from_slice(&x).ok())
+					.unwrap_or_default()
+					.into(),
+				trusted_projects: fs::read(app_data_path.join("trusted_projects.json"))
+					.ok()
+					.and_then(|x| from_slice(&x).ok())
+					.unwrap_or_default()
+					.into(),
+				editor_connection: EditorConnection::new(app.handle().clone()),
+				collaboration: Default::default(),
+				extraction_queue: ExtractionQueue::new(app.handle().clone()),
+				navigation_history: Default::default(),
+				split_views: DashMap::new(),
+				pending_selections: DashMap::new(),
+				tasks: Default::default()
 			});
 
 			info!("Managed state");
@@ -301,8 +419,8 @@ async fn main() {
 		.expect("error while building tauri application")
 		.run(|handler, event| {
 			if let tauri::RunEvent::Exit = event {
-				handler.track_event("App exited", None).unwrap();
-				handler.flush_events_blocking();
+				track_event(handler, "App exited", None);
+				flush_events_blocking(handler);
 			}
 		});
 }
@@ -339,6 +457,12 @@ fn event(app: AppHandle, event: Event) {
 									panic!();
 								};
 
+								let diagnostics = if let TextFileType::ManifestJson = file_type {
+									Some(get_diagnostics(&app_state.manifest_schema.load(), &content))
+								} else {
+									None
+								};
+
 								send_request(
 									&app,
 									Request::Editor(EditorRequest::Text(TextEditorRequest::ReplaceContent {
@@ -354,6 +478,16 @@ fn event(app: AppHandle, event: Event) {
 										file_type
 									}))
 								)?;
+
+								if let Some(diagnostics) = diagnostics {
+									send_request(
+										&app,
+										Request::Editor(EditorRequest::Text(TextEditorRequest::SetDiagnostics {
+											id: id.to_owned(),
+											diagnostics
+										}))
+									)?;
+								}
 							}
 
 							TextEditorEvent::UpdateContent { id, content } => {
@@ -370,6 +504,19 @@ fn event(app: AppHandle, event: Event) {
 								};
 
 								if content != old_content {
+									if let TextFileType::ManifestJson = file_type {
+										send_request(
+											&app,
+											Request::Editor(EditorRequest::Text(TextEditorRequest::SetDiagnostics {
+												id: id.to_owned(),
+												diagnostics: get_diagnostics(
+													&app_state.manifest_schema.load(),
+													&content
+												)
+											}))
+										)?;
+									}
+
 									editor_state.data = EditorData::Text { content, file_type };
 
 									send_request(
@@ -378,6 +525,27 @@ fn event(app: AppHandle, event: Event) {
 									)?;
 								}
 							}
+
+							TextEditorEvent::Find {
+								id,
+								search_id,
+								query,
+								regex,
+								match_case
+							} => {
+								find_in_text(&app, id, search_id, query, regex, match_case).await?;
+							}
+
+							TextEditorEvent::Replace {
+								id,
+								query,
+								regex,
+								match_case,
+								replacement,
+								offset
+							} => {
+								replace_in_text(&app, id, query, regex, match_case, replacement, offset).await?;
+							}
 						},
 
 						EditorEvent::Entity(event) => {
@@ -396,6 +564,22 @@ fn event(app: AppHandle, event: Event) {
 							handle_unlockables_patch_event(&app, event).await?;
 						}
 
+						EditorEvent::Enum(event) => {
+							handle_enum_event(&app, event).await?;
+						}
+
+						EditorEvent::Dialogue(event) => {
+							handle_dialogue_event(&app, event).await?;
+						}
+
+						EditorEvent::JsonPatch(event) => {
+							handle_json_patch_event(&app, event).await?;
+						}
+
+						EditorEvent::JsonResource(event) => {
+							handle_json_resource_event(&app, event).await?;
+						}
+
 						EditorEvent::ContentSearchResults(event) => match event {
 							ContentSearchResultsEvent::Initialise { id } => {
 								let editor_state = app_state.editor_states.get(&id).context("No such editor")?;
@@ -427,7 +611,10 @@ fn event(app: AppHandle, event: Event) {
 									id.to_owned(),
 									EditorState {
 										file: None,
-										data: EditorData::ResourceOverview { hash }
+										data: EditorData::ResourceOverview {
+											hash,
+											generic_rl_json: None
+										}
 									}
 								);
 
@@ -470,7 +657,7 @@ fn event(app: AppHandle, event: Event) {
 						}
 
 						GlobalEvent::LoadWorkspace(path) => {
-							app.track_event("Workspace loaded", None).unwrap();
+							track_event(&app, "Workspace loaded", None);
 							let task = start_task(&app, format!("Loading project {}", path.display()))?;
 
 							let mut files = vec![];
@@ -478,6 +665,7 @@ fn event(app: AppHandle, event: Event) {
 							for entry in WalkDir::new(&path)
 								.sort_by_file_name()
 								.into_iter()
+								.filter_entry(|x| x.file_name() != BACKUP_FOLDER)
 								.filter_map(|x| x.ok())
 							{
 								files.push((
@@ -535,6 +723,15 @@ fn event(app: AppHandle, event: Event) {
 								)))
 							)?;
 
+							send_saved_searches(&app)?;
+
+							send_request(
+								&app,
+								Request::Tool(ToolRequest::Settings(SettingsRequest::SetProjectTrusted(
+									app_state.trusted_projects.load().contains(&path)
+								)))
+							)?;
+
 							send_request(
 								&app,
 								Request::Tool(ToolRequest::FileBrowser(FileBrowserRequest::NewTree {
@@ -543,6 +740,11 @@ fn event(app: AppHandle, event: Event) {
 								}))
 							)?;
 
+							send_request(
+								&app,
+								Request::Tool(ToolRequest::Git(GitRequest::SetStatus(get_git_status(&path).ok())))
+							)?;
+
 							let notify_path = path.to_owned();
 							let notify_app = app.to_owned();
 
@@ -553,7 +755,23 @@ fn event(app: AppHandle, event: Event) {
 									move |evts: notify_debouncer_full::DebounceEventResult| {
 										if let Err::<_, Error>(e) = try {
 											if let Ok(evts) = evts {
+												rerun_saved_searches_on_change(
+													&notify_app,
+													&notify_path,
+													evts.iter().flat_map(|evt| evt.paths.iter().map(|x| x.as_path()))
+												)?;
+
 												for evt in evts {
+													// Backups aren't shown in the file browser
+													if !evt.paths.is_empty()
+														&& evt
+															.paths
+															.iter()
+															.all(|x| x.starts_with(notify_path.join(BACKUP_FOLDER)))
+													{
+														continue;
+													}
+
 													if evt.need_rescan() {
 														// Refresh the whole tree
 
@@ -562,6 +780,7 @@ fn event(app: AppHandle, event: Event) {
 														for entry in WalkDir::new(&notify_path)
 															.sort_by_file_name()
 															.into_iter()
+															.filter_entry(|x| x.file_name() != BACKUP_FOLDER)
 															.filter_map(|x| x.ok())
 														{
 															files.push((
@@ -744,13 +963,18 @@ fn event(app: AppHandle, event: Event) {
 							}));
 
 							finish_task(&app, task)?;
+
+							let game_install = app_settings.load().game_install.to_owned();
+							notify_dependency_issues(&app, &path, game_install.as_deref()).await?;
 						}
 
 						GlobalEvent::SelectTab(tab) => {
 							if let Some(tab) = tab {
+								record_navigation(&app, tab, None);
+
 								if let Some(file) = app_state
 									.editor_states
-									.get(&tab)
+									.get(&get_tab_document(&app_state, tab))
 									.context("No such editor")?
 									.file
 									.as_ref()
@@ -771,19 +995,41 @@ fn event(app: AppHandle, event: Event) {
 						}
 
 						GlobalEvent::RemoveTab(tab) => {
-							let (_, old) = app_state.editor_states.remove(&tab).context("No such editor")?;
+							// Closing a split view leaves the editor state to the tab it was split from
+							if let Some(split_tabs) = close_tab(&app_state, tab) {
+								let (_, old) = app_state.editor_states.remove(&tab).context("No such editor")?;
 
-							if old.file.is_some() {
-								send_request(
-									&app,
-									Request::Tool(ToolRequest::FileBrowser(FileBrowserRequest::Select(None)))
-								)?;
+								if old.file.is_some() {
+									send_request(
+										&app,
+										Request::Tool(ToolRequest::FileBrowser(FileBrowserRequest::Select(None)))
+									)?;
+								}
+
+								for split_tab in split_tabs {
+									send_request(&app, Request::Global(GlobalRequest::RemoveTab(split_tab)))?;
+								}
 							}
 
 							send_request(&app, Request::Global(GlobalRequest::RemoveTab(tab)))?;
 						}
 
+						GlobalEvent::SplitTab(tab) => {
+							track_event(&app, "Split tab", None);
+
+							split_tab(&app, tab)?;
+						}
+
 						GlobalEvent::SaveTab(tab) => {
+							if refuse_if_read_only(&app, "Saving")? {
+								return;
+							}
+
+							let tab = get_tab_document(&app_state, tab);
+
+							// Must be done before getting the editor mutably as this reads the other editors
+							let conflicts = find_patch_conflicts(&app_state, tab)?;
+
 							let mut editor = app_state.editor_states.get_mut(&tab).context("No such editor")?;
 
 							let task = start_task(
@@ -799,6 +1045,9 @@ fn event(app: AppHandle, event: Event) {
 								)
 							)?;
 
+							// The operation count and size of a saved entity patch, to offer splitting it if it's too large
+							let mut patch_stats = None;
+
 							let data_to_save = match &editor.data {
 								EditorData::Nil => {
 									Err(anyhow!("Editor is a nil editor"))?;
@@ -815,29 +1064,156 @@ fn event(app: AppHandle, event: Event) {
 									panic!();
 								}
 
+								EditorData::Enum { enums } => {
+									track_event(
+										&app,
+										"Editor saved",
+										Some(json!({
+											"file_type": "Enum"
+										}))
+									);
+
+									convert_generic_to_binary(
+										enums,
+										get_loaded_game_version(
+											&app,
+											app_settings.load().game_install.as_ref().context("No game selected")?
+										)?,
+										"ENUM".try_into()?
+									)?
+								}
+
+								EditorData::Dialogue { dialogue } => {
+									track_event(
+										&app,
+										"Editor saved",
+										Some(json!({
+											"file_type": "Dialogue"
+										}))
+									);
+
+									rebuild_dialogue(&app, dialogue)?;
+
+									let mut buf = Vec::new();
+									let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+									let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+									dialogue.serialize(&mut ser)?;
+
+									buf
+								}
+
+								EditorData::JsonPatch { content, .. } => {
+									track_event(
+										&app,
+										"Editor saved",
+										Some(json!({
+											"file_type": "JsonPatch"
+										}))
+									);
+
+									content.as_bytes().to_owned()
+								}
+
+								EditorData::JsonResource {
+									hash,
+									kind,
+									base,
+									content
+								} => {
+									track_event(
+										&app,
+										"Editor saved",
+										Some(json!({
+											"file_type": "JsonResource",
+											"kind": kind
+										}))
+									);
+
+									let current = from_str::<Value>(content).context("Invalid JSON")?;
+
+									let save_path = if let Some(file) = editor.file.as_ref() {
+										Some(file.to_owned())
+									} else {
+										let mut dialog = app.dialog().file().set_title("Save file");
+
+										if let Some(project) = app_state.project.load().as_ref() {
+											dialog = dialog.set_directory(&project.path);
+										}
+
+										match dialog
+											.add_filter("JSON patch", &["JSON.patch.json"])
+											.blocking_save_file()
+										{
+											Some(path) => Some(path.as_path().context("Invalid path")?.to_owned()),
+											None => None
+										}
+									};
+
+									if let Some(save_path) = save_path {
+										back_up_project_file(&app, &save_path)?;
+
+										send_request(
+											&app,
+											Request::Global(GlobalRequest::ComputeJSONPatchAndSave {
+												base: base.to_owned(),
+												current,
+												save_path: save_path.to_owned(),
+												file_and_type: (hash.to_string(), "JSON".into())
+											})
+										)?;
+
+										editor.file = Some(save_path);
+
+										send_request(
+											&app,
+											Request::Global(GlobalRequest::SetTabUnsaved {
+												id: tab,
+												unsaved: false
+											})
+										)?;
+									}
+
+									finish_task(&app, task)?;
+
+									return;
+								}
+
 								EditorData::Text { content, file_type } => {
-									app.track_event(
+									track_event(
+										&app,
 										"Editor saved",
 										Some(json!({
 											"file_type": file_type
 										}))
-									)
-									.unwrap();
+									);
 
 									content.as_bytes().to_owned()
 								}
 
-								EditorData::QNEntity { entity, settings } => {
-									app.track_event(
+								EditorData::QNEntity { entity, settings, .. } => {
+									track_event(
+										&app,
 										"Editor saved",
 										Some(json!({
 											"file_type": "QNEntity",
 											"show_reverse_parent_refs": settings.show_reverse_parent_refs
 										}))
-									)
-									.unwrap();
+									);
+
+									let tidy = app_state
+										.project
+										.load()
+										.as_ref()
+										.is_some_and(|x| x.settings.load().tidy_entities_on_save);
 
-									let unformatted = serde_json::to_string(&entity).context("Entity is invalid")?;
+									let unformatted = if tidy {
+										let mut entity = to_value(entity).context("Entity is invalid")?;
+										tidy_entity_json(&mut entity);
+										serde_json::to_string(&entity)?
+									} else {
+										serde_json::to_string(&entity).context("Entity is invalid")?
+									};
 
 									if unformatted.len() < 1024 * 1024 {
 										format_json(&unformatted)?.into_bytes()
@@ -849,16 +1225,17 @@ fn event(app: AppHandle, event: Event) {
 								EditorData::QNPatch {
 									base,
 									current,
-									settings
+									settings,
+									..
 								} => {
-									app.track_event(
+									track_event(
+										&app,
 										"Editor saved",
 										Some(json!({
 											"file_type": "QNPatch",
 											"show_reverse_parent_refs": settings.show_reverse_parent_refs
 										}))
-									)
-									.unwrap();
+									);
 
 									// Once a patch has been saved you can no longer modify the hashes without manually converting to entity.json
 									send_request(
@@ -871,12 +1248,13 @@ fn event(app: AppHandle, event: Event) {
 										)))
 									)?;
 
-									let unformatted = serde_json::to_string(
-										&generate_patch(base, current)
-											.map_err(|x| anyhow!(x))
-											.context("Couldn't generate patch")?
-									)
-									.context("Entity is invalid")?;
+									let patch = generate_patch(base, current)
+										.map_err(|x| anyhow!(x))
+										.context("Couldn't generate patch")?;
+
+									let unformatted = serde_json::to_string(&patch).context("Entity is invalid")?;
+
+									patch_stats = Some((patch.patch.len(), unformatted.len()));
 
 									if unformatted.len() < 1024 * 1024 {
 										format_json(&unformatted)?.into_bytes()
@@ -890,14 +1268,14 @@ fn event(app: AppHandle, event: Event) {
 									current,
 									patch_type
 								} => {
-									app.track_event(
+									track_event(
+										&app,
 										"Editor saved",
 										Some(json!({
 											"file_type": "RepositoryPatch",
 											"json_patch_type": patch_type
 										}))
-									)
-									.unwrap();
+									);
 
 									match patch_type {
 										JsonPatchType::MergePatch => {
@@ -934,6 +1312,8 @@ fn event(app: AppHandle, event: Event) {
 											)?;
 
 											if let Some(file) = editor.file.as_ref() {
+												back_up_project_file(&app, file)?;
+
 												send_request(
 													&app,
 													Request::Global(GlobalRequest::ComputeJSONPatchAndSave {
@@ -965,6 +1345,11 @@ fn event(app: AppHandle, event: Event) {
 													editor.file =
 														Some(path.as_path().context("Invalid path")?.to_owned());
 
+													back_up_project_file(
+														&app,
+														path.as_path().context("Invalid path")?
+													)?;
+
 													send_request(
 														&app,
 														Request::Global(GlobalRequest::ComputeJSONPatchAndSave {
@@ -990,6 +1375,16 @@ fn event(app: AppHandle, event: Event) {
 
 											finish_task(&app, task)?;
 
+											if !conflicts.is_empty() {
+												send_request(
+													&app,
+													Request::Global(GlobalRequest::ShowPatchConflicts {
+														id: tab,
+														conflicts
+													})
+												)?;
+											}
+
 											return;
 										}
 									}
@@ -1000,69 +1395,20 @@ fn event(app: AppHandle, event: Event) {
 									current,
 									patch_type
 								} => {
-									app.track_event(
+									track_event(
+										&app,
 										"Editor saved",
 										Some(json!({
 											"file_type": "UnlockablesPatch",
 											"json_patch_type": patch_type
 										}))
-									)
-									.unwrap();
+									);
 
 									match patch_type {
 										JsonPatchType::MergePatch => {
-											let base = to_value(
-												base.iter()
-													.map(|x| {
-														(
-															x.data
-																.get("Id")
-																.expect("Unlockable did not have Id")
-																.as_str()
-																.expect("Id was not string")
-																.to_owned(),
-															{
-																let mut y = IndexMap::new();
-																y.insert("Guid".into(), to_value(x.id).unwrap());
-																y.extend(
-																	x.data
-																		.iter()
-																		.filter(|(key, _)| *key != "Id")
-																		.map(|(x, y)| (x.to_owned(), y.to_owned()))
-																);
-																y
-															}
-														)
-													})
-													.collect::<IndexMap<String, IndexMap<String, Value>>>()
-											)?;
+											let base = unlockables_to_patchable(base)?;
 
-											let current = to_value(
-												current
-													.iter()
-													.map(|x| {
-														(
-															x.data
-																.get("Id")
-																.expect("Unlockable did not have Id")
-																.as_str()
-																.expect("Id was not string")
-																.to_owned(),
-															{
-																let mut y = IndexMap::new();
-																y.insert("Guid".into(), to_value(x.id).unwrap());
-																y.extend(
-																	x.data
-																		.iter()
-																		.filter(|(key, _)| *key != "Id")
-																		.map(|(x, y)| (x.to_owned(), y.to_owned()))
-																);
-																y
-															}
-														)
-													})
-													.collect::<IndexMap<String, IndexMap<String, Value>>>()
-											)?;
+											let current = unlockables_to_patchable(current)?;
 
 											let patch = json_patch::diff(&base, &current);
 
@@ -1070,60 +1416,13 @@ fn event(app: AppHandle, event: Event) {
 										}
 
 										JsonPatchType::JsonPatch => {
-											let base = to_value(
-												base.iter()
-													.map(|x| {
-														(
-															x.data
-																.get("Id")
-																.expect("Unlockable did not have Id")
-																.as_str()
-																.expect("Id was not string")
-																.to_owned(),
-															{
-																let mut y = IndexMap::new();
-																y.insert("Guid".into(), to_value(x.id).unwrap());
-																y.extend(
-																	x.data
-																		.iter()
-																		.filter(|(key, _)| *key != "Id")
-																		.map(|(x, y)| (x.to_owned(), y.to_owned()))
-																);
-																y
-															}
-														)
-													})
-													.collect::<IndexMap<String, IndexMap<String, Value>>>()
-											)?;
+											let base = unlockables_to_patchable(base)?;
 
-											let current = to_value(
-												current
-													.iter()
-													.map(|x| {
-														(
-															x.data
-																.get("Id")
-																.expect("Unlockable did not have Id")
-																.as_str()
-																.expect("Id was not string")
-																.to_owned(),
-															{
-																let mut y = IndexMap::new();
-																y.insert("Guid".into(), to_value(x.id).unwrap());
-																y.extend(
-																	x.data
-																		.iter()
-																		.filter(|(key, _)| *key != "Id")
-																		.map(|(x, y)| (x.to_owned(), y.to_owned()))
-																);
-																y
-															}
-														)
-													})
-													.collect::<IndexMap<String, IndexMap<String, Value>>>()
-											)?;
+											let current = unlockables_to_patchable(current)?;
 
 											if let Some(file) = editor.file.as_ref() {
+												back_up_project_file(&app, file)?;
+
 												send_request(
 													&app,
 													Request::Global(GlobalRequest::ComputeJSONPatchAndSave {
@@ -1155,6 +1454,11 @@ fn event(app: AppHandle, event: Event) {
 													editor.file =
 														Some(path.as_path().context("Invalid path")?.to_owned());
 
+													back_up_project_file(
+														&app,
+														path.as_path().context("Invalid path")?
+													)?;
+
 													send_request(
 														&app,
 														Request::Global(GlobalRequest::ComputeJSONPatchAndSave {
@@ -1180,6 +1484,16 @@ fn event(app: AppHandle, event: Event) {
 
 											finish_task(&app, task)?;
 
+											if !conflicts.is_empty() {
+												send_request(
+													&app,
+													Request::Global(GlobalRequest::ShowPatchConflicts {
+														id: tab,
+														conflicts
+													})
+												)?;
+											}
+
 											return;
 										}
 									}
@@ -1187,6 +1501,8 @@ fn event(app: AppHandle, event: Event) {
 							};
 
 							if let Some(file) = editor.file.as_ref() {
+								back_up_project_file(&app, file)?;
+
 								fs::write(file, data_to_save).context("Couldn't write file")?;
 
 								send_request(
@@ -1238,6 +1554,14 @@ fn event(app: AppHandle, event: Event) {
 
 											EditorData::QNEntity { .. } => "QuickEntity entity",
 
+											EditorData::Enum { .. } => "ENUM file",
+
+											EditorData::Dialogue { .. } => "Dialogue file",
+
+											EditorData::JsonPatch { .. } => "JSON patch",
+
+											EditorData::JsonResource { .. } => "JSON patch",
+
 											EditorData::QNPatch { .. } => "QuickEntity patch",
 
 											EditorData::RepositoryPatch { patch_type, .. } => match patch_type {
@@ -1283,6 +1607,14 @@ fn event(app: AppHandle, event: Event) {
 
 											EditorData::QNEntity { .. } => "entity.json",
 
+											EditorData::Enum { .. } => "ENUM",
+
+											EditorData::Dialogue { .. } => "dlge.json",
+
+											EditorData::JsonPatch { .. } => "JSON.patch.json",
+
+											EditorData::JsonResource { .. } => "JSON.patch.json",
+
 											EditorData::QNPatch { .. } => "entity.patch.json",
 
 											EditorData::RepositoryPatch { patch_type, .. } => match patch_type {
@@ -1300,6 +1632,8 @@ fn event(app: AppHandle, event: Event) {
 								{
 									editor.file = Some(path.as_path().context("Invalid path")?.to_owned());
 
+									back_up_project_file(&app, path.as_path().context("Invalid path")?)?;
+
 									fs::write(&path.as_path().context("Invalid path")?, data_to_save)
 										.context("Couldn't write file")?;
 
@@ -1314,6 +1648,88 @@ fn event(app: AppHandle, event: Event) {
 							}
 
 							finish_task(&app, task)?;
+
+							if !conflicts.is_empty() {
+								send_request(
+									&app,
+									Request::Global(GlobalRequest::ShowPatchConflicts { id: tab, conflicts })
+								)?;
+							}
+
+							// Entities made for HITMAN 3 can use things which don't exist in older games, which would
+							// otherwise only show up as the entity not working in-game
+							if let EditorData::QNEntity { entity, .. } | EditorData::QNPatch { current: entity, .. } =
+								&editor.data
+							{
+								warn_about_incompatibilities(&app, entity)?;
+							}
+
+							if let Some((operations, size)) = patch_stats
+								&& editor.file.is_some() && let Some(project) = app_state.project.load().as_ref()
+								&& exceeds_split_thresholds(&project.settings.load(), operations, size)
+							{
+								send_request(
+									&app,
+									Request::Global(GlobalRequest::OfferPatchSplit {
+										id: tab,
+										operations: operations as u32,
+										size: size as u32
+									})
+								)?;
+							}
+						}
+
+						GlobalEvent::SplitPatch(tab) => {
+							if refuse_if_read_only(&app, "Splitting patches")? {
+								return;
+							}
+
+							if let Some(project) = app_state.project.load().as_ref() {
+								let tab = get_tab_document(&app_state, tab);
+
+								let mut editor = app_state.editor_states.get_mut(&tab).context("No such editor")?;
+
+								let EditorData::QNPatch { base, current, .. } = &editor.data else {
+									Err(anyhow!("Editor {} is not a QN patch editor", tab))?;
+									panic!();
+								};
+
+								let file = editor.file.to_owned().context("Patch has not been saved")?;
+
+								let task = start_task(&app, "Splitting patch")?;
+
+								let parts = split_patch(
+									&generate_patch(base, current)
+										.map_err(|x| anyhow!(x))
+										.context("Couldn't generate patch")?,
+									base,
+									current,
+									&project.settings.load()
+								)?;
+
+								back_up_project_file(&app, &file)?;
+
+								let paths = write_split_patch(&project.path, &file, &parts)?;
+
+								// The original file no longer exists, so saving again shouldn't recreate it alongside the parts
+								editor.file = None;
+
+								track_event(&app, "Split patch", Some(json!({ "parts": paths.len() })));
+
+								finish_task(&app, task)?;
+
+								send_notification(
+									&app,
+									Notification {
+										kind: NotificationKind::Success,
+										title: format!("Patch split into {} files", paths.len()),
+										subtitle: "This tab is no longer linked to a file; open the parts to edit \
+										           them separately."
+											.into(),
+										link: None
+									}
+								)?;
+							}
 						}
 
 						GlobalEvent::UploadLogAndReport(error) => {
@@ -1325,7 +1741,17 @@ fn event(app: AppHandle, event: Event) {
 							)
 							.context("Couldn't read log file")?;
 
-							if let Ok(res) = reqwest::Client::new()
+							if is_offline() {
+								send_notification(
+									&app,
+									Notification {
+										kind: NotificationKind::Info,
+										title: "Log not uploaded".into(),
+										subtitle: "Logs can't be uploaded while offline mode is enabled.".into(),
+										link: None
+									}
+								)?;
+							} else if let Ok(res) = reqwest::Client::new()
 								.post(UPLOAD_LOG_ENDPOINT)
 								.json(&json!({
 									"content": log_contents
@@ -1335,8 +1761,7 @@ fn event(app: AppHandle, event: Event) {
 								.and_then(|x| x.error_for_status())
 							{
 								let log_url = res.text().await.context("Couldn't decode log upload response")?;
-								app.track_event("Error with log", Some(json!({ "error": error, "log": log_url })))
-									.unwrap();
+								track_event(&app, "Error with log", Some(json!({ "error": error, "log": log_url })));
 							} else {
 								send_request(&app, Request::Global(GlobalRequest::LogUploadRejected))?;
 							}
@@ -1352,7 +1777,18 @@ fn event(app: AppHandle, event: Event) {
 							)
 							.context("Couldn't read panic report")?;
 
-							if let Ok(res) = reqwest::Client::new()
+							if is_offline() {
+								send_notification(
+									&app,
+									Notification {
+										kind: NotificationKind::Info,
+										title: "Crash report not uploaded".into(),
+										subtitle: "Crash reports can't be uploaded while offline mode is enabled."
+											.into(),
+										link: None
+									}
+								)?;
+							} else if let Ok(res) = reqwest::Client::new()
 								.post(UPLOAD_LOG_ENDPOINT)
 								.json(&json!({
 									"content": last_panic
@@ -1362,8 +1798,7 @@ fn event(app: AppHandle, event: Event) {
 								.and_then(|x| x.error_for_status())
 							{
 								let report_url = res.text().await.context("Couldn't decode report upload response")?;
-								app.track_event("Panic report", Some(json!({ "report": report_url })))
-									.unwrap();
+								track_event(&app, "Panic report", Some(json!({ "report": report_url })));
 							} else {
 								send_request(&app, Request::Global(GlobalRequest::LogUploadRejected))?;
 							}
@@ -1382,6 +1817,14 @@ fn event(app: AppHandle, event: Event) {
 							)?;
 						}
 
+						GlobalEvent::NavigateBack => {
+							navigate(&app, true).await?;
+						}
+
+						GlobalEvent::NavigateForward => {
+							navigate(&app, false).await?;
+						}
+
 						GlobalEvent::ClearLastPanic => {
 							fs::rename(
 								app.path()
@@ -1396,6 +1839,77 @@ fn event(app: AppHandle, event: Event) {
 									.join(format!("panic_{}.txt", rng().random::<u32>()))
 							)?;
 						}
+
+						GlobalEvent::DetectDroppedFiles(paths) => {
+							if let Some(project) = app_state.project.load().as_ref() {
+								send_request(
+									&app,
+									Request::Global(GlobalRequest::ShowDroppedFileImport(detect_dropped_files(
+										&project.path,
+										&paths
+									)?))
+								)?;
+							} else {
+								for path in paths {
+									open_file(&app, path).await?;
+								}
+							}
+						}
+
+						GlobalEvent::ImportDroppedFiles(files) => {
+							if refuse_if_read_only(&app, "Importing files")? {
+								return;
+							}
+
+							let project = app_state.project.load();
+							let project = project.as_ref().context("No project loaded")?;
+
+							track_event(
+								&app,
+								"Import dropped files",
+								Some(json!({
+									"files": files.len()
+								}))
+							);
+
+							let (unsupported, files): (Vec<_>, Vec<_>) =
+								files.into_iter().partition(|x| x.kind == DroppedFileKind::Unsupported);
+
+							// Files which can't be imported are opened instead
+							for file in unsupported {
+								open_file(&app, file.path).await?;
+							}
+
+							if !files.is_empty() {
+								let task = start_task(&app, "Importing files")?;
+
+								for file in &files {
+									import_dropped_file(&project.path, file)?;
+								}
+
+								finish_task(&app, task)?;
+
+								send_notification(
+									&app,
+									Notification {
+										kind: NotificationKind::Success,
+										title: "Files imported".into(),
+										subtitle: format!(
+											"{} file{} imported into the project.",
+											files.len(),
+											if files.len() == 1 { " was" } else { "s were" }
+										),
+										link: None
+									}
+								)?;
+							}
+						}
+
+						GlobalEvent::CancelTask(task) => {
+							if app_state.tasks.cancel(task) {
+								trace!("Cancelling task {}", task);
+							}
+						}
 					},
 
 					Event::EditorConnection(event) => match event {
@@ -1848,6 +2362,7 @@ pub fn convert_json_patch_to_merge_patch(new: &Value, patch: &Patch) -> Result<V
 pub fn start_task(app: &AppHandle, name: impl AsRef<str>) -> Result<Uuid> {
 	let task_id = Uuid::new_v4();
 	trace!("Starting task {}: {}", task_id, name.as_ref());
+	app.state::<AppState>().tasks.record_start(task_id, name.as_ref());
 	app.emit("start-task", (&task_id, name.as_ref()))?;
 	task_id
 }
@@ -1856,6 +2371,7 @@ pub fn start_task(app: &AppHandle, name: impl AsRef<str>) -> Result<Uuid> {
 #[context("Couldn't send task finish event for {:?} to frontend", task)]
 pub fn finish_task(app: &AppHandle, task: Uuid) -> Result<()> {
 	trace!("Ending task {}", task);
+	app.state::<AppState>().tasks.remove(task);
 	app.emit("finish-task", &task)?;
 }
 
@@ -1872,7 +2388,10 @@ pub enum NotificationKind {
 pub struct Notification {
 	pub kind: NotificationKind,
 	pub title: String,
-	pub subtitle: String
+	pub subtitle: String,
+
+	/// A URL which can be opened from the notification
+	pub link: Option<String>
 }
 
 #[try_fn]
@@ -1882,9 +2401,48 @@ pub fn send_notification(app: &AppHandle, notification: Notification) -> Result<
 	app.emit("send-notification", (Uuid::new_v4(), &notification))?;
 }
 
+/// Whether the loaded project is read-only, in which case a notification is shown explaining that the given action
+/// can't be done; the caller should then stop without writing anything.
+#[try_fn]
+#[context("Couldn't check whether project is read-only")]
+pub fn refuse_if_read_only(app: &AppHandle, action: &str) -> Result<bool> {
+	let read_only = app
+		.state::<AppState>()
+		.project
+		.load()
+		.as_ref()
+		.is_some_and(|x| x.settings.load().read_only);
+
+	if read_only {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Warning,
+				title: "Project is read-only".into(),
+				subtitle: format!(
+					"{} isn't possible while the project is read-only. You can turn off read-only mode in the project \
+					 settings.",
+					action
+				),
+				link: None
+			}
+		)?;
+	}
+
+	read_only
+}
+
 #[try_fn]
 #[context("Couldn't send request {:?} to frontend", request)]
 pub fn send_request(app: &AppHandle, request: Request) -> Result<()> {
 	trace!("Sending request: {:?}", request);
+
+	// Every edit marks its tab as unsaved, so this is where collaboration learns which editors to check for changes
+	if let Request::Global(GlobalRequest::SetTabUnsaved { id, unsaved: true }) = request
+		&& let Some(app_state) = app.try_state::<AppState>()
+	{
+		app_state.collaboration.mark_changed(get_tab_document(&app_state, id));
+	}
+
 	app.emit("request", &request)?;
 }
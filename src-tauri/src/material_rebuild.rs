@@ -0,0 +1,135 @@
+use std::{
+	fs,
+	path::{Component, Path, PathBuf}
+};
+
+use anyhow::{Context, Result};
+use fn_error_context::context;
+use hashbrown::HashSet;
+use hitman_commons::{hash_list::HashList, metadata::RuntimeID, rpkg_tool::RpkgResourceMeta};
+use hitman_formats::material::MaterialEntity;
+use rpkg_rs::resource::partition_manager::PartitionManager;
+use serde_json::from_str;
+use tryvial::try_fn;
+use walkdir::WalkDir;
+
+use crate::{
+	dropped_files::{get_content_chunk_folder, register_content_folder},
+	rpkg::extract_latest_metadata
+};
+
+/// The files written by rebuilding a material entity, and the resources it refers to which couldn't be found.
+pub struct RebuiltMaterial {
+	pub files: Vec<PathBuf>,
+
+	/// Hash, type and path (where known) of each missing resource, like `00123456789ABCDE (TEXT, [assembly:/...])`
+	pub missing_references: Vec<String>
+}
+
+/// Get the resources defined by files in the project named after their hash, like `00123456789ABCDE.TEXT`.
+fn get_project_resources(project_path: &Path) -> HashSet<RuntimeID> {
+	WalkDir::new(project_path)
+		.into_iter()
+		.filter_entry(|x| x.file_name() != ".git")
+		.filter_map(|x| x.ok())
+		.filter(|x| x.file_type().is_file())
+		.filter_map(|x| {
+			let (stem, _) = x.file_name().to_str()?.split_once('.')?;
+
+			if stem.len() == 16 {
+				RuntimeID::from_any(stem).ok()
+			} else {
+				None
+			}
+		})
+		.collect()
+}
+
+/// Convert edited material entity JSON back to MATT and MATB binaries replacing those of the given MATT, and write them
+/// with their meta files to the project's content folder, registering it with the manifest. Every resource the
+/// material refers to, like its textures, is checked to exist in either the game files or the project.
+#[try_fn]
+#[context("Couldn't rebuild material entity {}", matt)]
+pub fn rebuild_material_entity(
+	game_files: &PartitionManager,
+	hash_list: &HashList,
+	project_path: &Path,
+	matt: RuntimeID,
+	json: &str
+) -> Result<RebuiltMaterial> {
+	let material: MaterialEntity = from_str(json).context("Invalid material entity")?;
+
+	let mut original_matt_meta = extract_latest_metadata(game_files, matt)?;
+
+	let matb = original_matt_meta
+		.core_info
+		.references
+		.get(1)
+		.context("No MATB dependency")?
+		.resource
+		.get_id();
+
+	let mut original_matb_meta = extract_latest_metadata(game_files, matb)?;
+
+	let ((matt_data, matt_meta), (matb_data, matb_meta)) =
+		material.generate().context("Couldn't generate MATT and MATB")?;
+
+	let project_resources = get_project_resources(project_path);
+
+	let missing_references = matt_meta
+		.references
+		.iter()
+		.map(|x| x.resource.get_id())
+		.filter(|&x| x != matb)
+		.filter(|&x| !project_resources.contains(&x) && extract_latest_metadata(game_files, x).is_err())
+		.map(|x| match hash_list.entries.get(&x) {
+			Some(entry) => format!(
+				"{} ({}{})",
+				x,
+				entry.resource_type,
+				entry.path.as_ref().map(|x| format!(", {}", x)).unwrap_or_default()
+			),
+
+			None => x.to_string()
+		})
+		.collect();
+
+	original_matt_meta.core_info = matt_meta;
+	original_matb_meta.core_info = matb_meta;
+
+	let content_folder = get_content_chunk_folder(project_path)?;
+	fs::create_dir_all(project_path.join(&content_folder))?;
+
+	let mut files = vec![];
+
+	for (hash, resource_type, data, meta) in [
+		(matt, "MATT", matt_data, original_matt_meta),
+		(matb, "MATB", matb_data, original_matb_meta)
+	] {
+		let path = project_path
+			.join(&content_folder)
+			.join(format!("{}.{}", hash, resource_type));
+
+		fs::write(&path, data)?;
+
+		fs::write(
+			project_path
+				.join(&content_folder)
+				.join(format!("{}.{}.meta", hash, resource_type)),
+			RpkgResourceMeta::from_resource_metadata(meta, false)
+				.to_binary()
+				.context("Couldn't serialise meta file")?
+		)?;
+
+		files.push(path);
+	}
+
+	if let Some(Component::Normal(root)) = content_folder.components().next() {
+		register_content_folder(project_path, &root.to_string_lossy())?;
+	}
+
+	RebuiltMaterial {
+		files,
+		missing_references
+	}
+}
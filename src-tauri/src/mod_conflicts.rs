@@ -0,0 +1,242 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use fn_error_context::context;
+use hitman_commons::{hash_list::HashList, metadata::RuntimeID};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, from_slice};
+use specta::Type;
+use tryvial::try_fn;
+use walkdir::WalkDir;
+
+use crate::{
+	conflicts::{REPOSITORY_HASH, UNLOCKABLES_HASH},
+	smf::{InstalledMod, ModManifest, find_smf_path, get_enabled_mods}
+};
+
+/// How a file changes the resource or blob it targets.
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModChangeKind {
+	/// The file takes the place of the original entirely, like a raw resource or a full entity
+	Replace,
+
+	/// The file modifies the original, like an entity patch or a JSON patch
+	Patch
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ModConflictSeverity {
+	/// Both the project and the mod replace the resource, so whichever is deployed last wins outright
+	High,
+
+	/// One replaces the resource and the other patches it, so the patch is either lost or applied to something it
+	/// wasn't made for
+	Medium,
+
+	/// Both patch the resource, which usually merges cleanly unless they change the same data
+	Low
+}
+
+/// A resource or blob changed by both the project and a mod enabled in the framework.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct ModConflict {
+	pub severity: ModConflictSeverity,
+
+	/// The hash of the resource, or the path of the blob
+	pub target: String,
+
+	/// The path of the resource in the hash list, if it has one
+	pub target_path: Option<String>,
+
+	pub mod_id: String,
+	pub mod_name: String,
+
+	/// Forward-slash separated paths relative to the project
+	pub project_files: Vec<(String, ModChangeKind)>,
+
+	/// Forward-slash separated paths relative to the mod's folder
+	pub mod_files: Vec<(String, ModChangeKind)>
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+enum ModTarget {
+	Resource(RuntimeID),
+	Blob(String)
+}
+
+/// The files a mod deploys, grouped by what they change.
+type ModChanges = IndexMap<ModTarget, Vec<(String, ModChangeKind)>>;
+
+/// Get the resource a file in a content folder changes, and how, if it's a file that changes one.
+fn get_content_file_change(path: &Path) -> Option<(RuntimeID, ModChangeKind)> {
+	let name = path.file_name()?.to_string_lossy().to_lowercase();
+
+	let read_json = || from_slice::<Value>(&fs::read(path).ok()?).ok();
+
+	if name.ends_with(".entity.json") {
+		Some((
+			RuntimeID::from_any(read_json()?.get("tempHash")?.as_str()?).ok()?,
+			ModChangeKind::Replace
+		))
+	} else if name.ends_with(".entity.patch.json") {
+		Some((
+			RuntimeID::from_any(read_json()?.get("tempHash")?.as_str()?).ok()?,
+			ModChangeKind::Patch
+		))
+	} else if name.ends_with(".repository.json") {
+		Some((REPOSITORY_HASH.parse().ok()?, ModChangeKind::Patch))
+	} else if name.ends_with(".unlockables.json") {
+		Some((UNLOCKABLES_HASH.parse().ok()?, ModChangeKind::Patch))
+	} else if name.ends_with(".json.patch.json") {
+		Some((
+			RuntimeID::from_any(read_json()?.get("file")?.as_str()?).ok()?,
+			ModChangeKind::Patch
+		))
+	} else {
+		// Raw resources are named by their hash and type, like 00123456789ABCDE.TEMP; anything else (meta files,
+		// contracts, localisation and so on) either accompanies another file or adds new content
+		let (hash, _) = name.split_once('.')?;
+
+		Some((hash.to_uppercase().parse().ok()?, ModChangeKind::Replace))
+	}
+}
+
+/// Get the changes made by the files in a mod's content and blobs folders.
+fn get_mod_changes(mod_path: &Path, content_folders: &[String], blobs_folders: &[String]) -> ModChanges {
+	let mut changes = ModChanges::new();
+
+	let relative_path = |path: &Path| {
+		path.strip_prefix(mod_path)
+			.unwrap_or(path)
+			.to_string_lossy()
+			.replace('\\', "/")
+	};
+
+	for folder in content_folders {
+		for entry in WalkDir::new(mod_path.join(folder))
+			.into_iter()
+			.filter_map(|x| x.ok())
+			.filter(|x| x.file_type().is_file())
+		{
+			if let Some((hash, kind)) = get_content_file_change(entry.path()) {
+				changes
+					.entry(ModTarget::Resource(hash))
+					.or_default()
+					.push((relative_path(entry.path()), kind));
+			}
+		}
+	}
+
+	for folder in blobs_folders {
+		let folder = mod_path.join(folder);
+
+		for entry in WalkDir::new(&folder)
+			.into_iter()
+			.filter_map(|x| x.ok())
+			.filter(|x| x.file_type().is_file())
+		{
+			let blob = entry
+				.path()
+				.strip_prefix(&folder)
+				.unwrap_or(entry.path())
+				.to_string_lossy()
+				.replace('\\', "/")
+				.to_lowercase();
+
+			changes
+				.entry(ModTarget::Blob(blob))
+				.or_default()
+				.push((relative_path(entry.path()), ModChangeKind::Replace));
+		}
+	}
+
+	changes
+}
+
+/// Find the resources and blobs which both the project and the mods enabled in the framework change, sorted by severity
+/// and then by load order. All of the project's options are considered, as any of them could be enabled when it's
+/// deployed; installed mods only have their enabled options considered. The project itself is skipped if it's
+/// installed in the framework.
+#[try_fn]
+#[context("Couldn't find conflicts with installed mods")]
+pub fn find_mod_conflicts(
+	project_path: &Path,
+	game_install: &Path,
+	hash_list: Option<&HashList>
+) -> Result<Vec<ModConflict>> {
+	let manifest: ModManifest =
+		from_slice(&fs::read(project_path.join("manifest.json")).context("Couldn't read manifest.json")?)
+			.context("Invalid manifest")?;
+
+	let smf_path = find_smf_path(game_install).context("The framework isn't installed for the selected game")?;
+
+	let project = InstalledMod {
+		path: project_path.to_owned(),
+		manifest
+	};
+
+	let (content_folders, blobs_folders) = project.enabled_folders(
+		&project
+			.manifest
+			.options
+			.iter()
+			.map(|x| x.name.to_owned())
+			.collect::<Vec<_>>()
+	);
+
+	let project_changes = get_mod_changes(project_path, &content_folders, &blobs_folders);
+
+	let mut conflicts = vec![];
+
+	for (installed, enabled_options) in get_enabled_mods(&smf_path)? {
+		if installed.manifest.id == project.manifest.id {
+			continue;
+		}
+
+		let (content_folders, blobs_folders) = installed.enabled_folders(&enabled_options);
+
+		for (target, mod_files) in get_mod_changes(&installed.path, &content_folders, &blobs_folders) {
+			let Some(project_files) = project_changes.get(&target) else {
+				continue;
+			};
+
+			let project_replaces = project_files.iter().any(|(_, kind)| *kind == ModChangeKind::Replace);
+			let mod_replaces = mod_files.iter().any(|(_, kind)| *kind == ModChangeKind::Replace);
+
+			let (target, target_path) = match target {
+				ModTarget::Resource(hash) => (
+					hash.to_string(),
+					hash_list
+						.and_then(|x| x.entries.get(&hash))
+						.and_then(|x| x.path.to_owned())
+				),
+
+				ModTarget::Blob(path) => (path, None)
+			};
+
+			conflicts.push(ModConflict {
+				severity: match (project_replaces, mod_replaces) {
+					(true, true) => ModConflictSeverity::High,
+					(true, false) | (false, true) => ModConflictSeverity::Medium,
+					(false, false) => ModConflictSeverity::Low
+				},
+				target,
+				target_path,
+				mod_id: installed.manifest.id.to_owned(),
+				mod_name: installed
+					.manifest
+					.name
+					.to_owned()
+					.unwrap_or_else(|| installed.manifest.id.to_owned()),
+				project_files: project_files.to_owned(),
+				mod_files
+			});
+		}
+	}
+
+	// Sorting is stable, so conflicts of the same severity stay in load order
+	conflicts.sort_by_key(|x| x.severity);
+
+	conflicts
+}
@@ -10,7 +10,10 @@ use hitman_commons::{
 };
 use notify::RecommendedWatcher;
 use notify_debouncer_full::FileIdMap;
-use quickentity_rs::qn_structs::{Entity, Ref, SubEntity, SubType};
+use quickentity_rs::{
+	patch_structs::Patch,
+	qn_structs::{Entity, ExposedEntity, PropertyAlias, Ref, SimpleProperty, SubEntity, SubType}
+};
 use rpkg_rs::resource::partition_manager::PartitionManager;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -19,10 +22,53 @@ use structstruck::strike;
 use uuid::Uuid;
 
 use crate::{
-	editor_connection::{EditorConnection, QNTransform},
-	entity::{CopiedEntityData, ReverseReference},
+	audio_import::{WavFile, WemEncoder},
+	audio_names::LocalHashList,
+	backups::{FileBackup, default_backup_count},
+	behaviour_tree::BehaviourTreeNode,
+	borg::Bone,
+	chunk_assignment::ChunkAssignment,
+	collaboration::{Collaboration, CollaborationStatus},
+	collections::Collections,
+	converters::Conversion,
+	crash_analysis::CrashAnalysis,
+	diagnostics::SessionDiagnostics,
+	dropped_files::DroppedFile,
+	editor_connection::{
+		EditorConnection, EditorConnectionDiagnostics, EditorConnectionGame, QNTransform, Vec3,
+		default_editor_connection_games
+	},
+	entity::{CopiedEntityData, OverrideListing, ReverseReference},
+	entity_cache::{EntityCache, default_entity_cache_max_entries, default_entity_cache_max_memory},
+	entity_docs::{DocumentationEntry, EntityDocs},
+	event_handling::text::TextSearchMatch,
+	external_tools::ExternalTool,
+	extraction_presets::ExtractionPreset,
+	file_operations::FileTemplate,
+	hash_remapping::RemappedFile,
+	hash_resolution::ResolvedResource,
 	intellisense::Intellisense,
-	ores_repo::{RepositoryItem, RepositoryItemInformation, UnlockableInformation, UnlockableItem}
+	localisation::default_localisation_languages,
+	mod_conflicts::ModConflict,
+	navigation::NavigationHistory,
+	ores_repo::{
+		RepositoryColumnType, RepositoryItem, RepositoryItemInformation, RepositoryItemTemplate, RepositoryTableRow,
+		UnlockableDecoration, UnlockableInformation, UnlockableItem
+	},
+	packaging::default_package_ignore,
+	patch_splitting::{default_patch_split_max_operations, default_patch_split_max_size},
+	patches::PatchTest,
+	reference_flags::DecodedReferenceFlag,
+	resource_capabilities::ResourceTypeCapabilities,
+	resourcelib::SEnumType,
+	rpkg::extraction_queue::{ExtractionItem, ExtractionQueue},
+	saved_searches::SavedSearch,
+	scene_outline::OutlineFormat,
+	tasks::TaskRegistry,
+	transform::ArrayPattern,
+	unit_conversion::UnitConversion,
+	waveform::Waveform,
+	workspace_trust::TrustedProjects
 };
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
@@ -32,7 +78,42 @@ pub struct AppSettings {
 	pub game_install: Option<PathBuf>,
 	pub colourblind_mode: bool,
 	pub editor_connection: bool,
-	pub seen_announcements: Vec<String>
+	pub seen_announcements: Vec<String>,
+
+	/// Programs that resources and files can be opened with from the resource overview and file browser
+	#[serde(default)]
+	pub external_tools: Vec<ExternalTool>,
+
+	/// Disables telemetry and all network requests, for use without an internet connection
+	#[serde(default)]
+	pub offline_mode: bool,
+
+	/// Games to connect to the SDK editor servers of, which can be more than one to test with several game instances
+	#[serde(default = "default_editor_connection_games")]
+	pub editor_connection_games: Vec<EditorConnectionGame>,
+
+	/// Named sets of extraction options, which resources can be quickly extracted with from the resource overview and
+	/// game browser
+	#[serde(default)]
+	pub extraction_presets: Vec<ExtractionPreset>,
+
+	/// The most entities extracted from the game files to keep in memory before the least recently used are evicted, or
+	/// 0 for no limit
+	#[serde(default = "default_entity_cache_max_entries")]
+	pub entity_cache_max_entries: u32,
+
+	/// The most memory in megabytes that entities extracted from the game files can take up before the least recently
+	/// used are evicted, or 0 for no limit
+	#[serde(default = "default_entity_cache_max_memory")]
+	pub entity_cache_max_memory: u32,
+
+	/// The program WAV files are converted to WEM with when importing audio
+	#[serde(default)]
+	pub wem_encoder: Option<WemEncoder>,
+
+	/// Resources to check for changes whenever the game files are updated
+	#[serde(default)]
+	pub watched_resources: Vec<RuntimeID>
 }
 
 impl Default for AppSettings {
@@ -42,7 +123,15 @@ impl Default for AppSettings {
 			game_install: None,
 			colourblind_mode: false,
 			editor_connection: true,
-			seen_announcements: vec![]
+			seen_announcements: vec![],
+			external_tools: vec![],
+			offline_mode: false,
+			editor_connection_games: default_editor_connection_games(),
+			extraction_presets: vec![],
+			entity_cache_max_entries: default_entity_cache_max_entries(),
+			entity_cache_max_memory: default_entity_cache_max_memory(),
+			wem_encoder: None,
+			watched_resources: vec![]
 		}
 	}
 }
@@ -59,11 +148,46 @@ pub struct AppState {
 	/// Resource -> Resources which depend on it
 	pub resource_reverse_dependencies: ArcSwapOption<HashMap<RuntimeID, Vec<RuntimeID>>>,
 
-	pub cached_entities: Arc<DashMap<RuntimeID, Entity>>,
+	pub cached_entities: Arc<EntityCache>,
 	pub repository: ArcSwapOption<Vec<RepositoryItem>>,
 	pub intellisense: ArcSwapOption<Intellisense>,
 
-	pub editor_connection: EditorConnection
+	/// The JSON schema for SMF manifests; bundled, and refreshed from the SMF repository on startup
+	pub manifest_schema: ArcSwap<Value>,
+
+	/// Documentation for the properties and pins of entity classes; bundled, and refreshed from the community database
+	/// on startup
+	pub entity_docs: ArcSwap<EntityDocs>,
+
+	/// User-defined collections of resources, shown in the game browser
+	pub collections: ArcSwap<Collections>,
+
+	/// Names resolved locally, like Wwise object names found by brute-forcing, layered over the hash lists
+	pub local_hash_list: ArcSwap<LocalHashList>,
+
+	/// Projects allowed to run external programs
+	pub trusted_projects: ArcSwap<TrustedProjects>,
+
+	pub editor_connection: EditorConnection,
+
+	/// Opt-in syncing of entity changes with another GlacierKit instance
+	pub collaboration: Collaboration,
+
+	/// Worker pool that extractions are queued on
+	pub extraction_queue: ExtractionQueue,
+
+	/// Previously focused tabs and selected entities, for navigating back and forward
+	pub navigation_history: ArcSwap<NavigationHistory>,
+
+	/// Split views, mapped to the ID of the tab they were split from, whose editor state they show. Split views are
+	/// closed along with that tab.
+	pub split_views: DashMap<Uuid, Uuid>,
+
+	/// Entities to select in entity editors once their tree has loaded, for editors which were opened to show an entity
+	pub pending_selections: DashMap<Uuid, String>,
+
+	/// Running tasks which can be cancelled from the task bar
+	pub tasks: TaskRegistry
 }
 
 #[derive(Debug)]
@@ -76,7 +200,11 @@ pub struct EditorState {
 pub enum EditorData {
 	Nil,
 	ResourceOverview {
-		hash: RuntimeID
+		hash: RuntimeID,
+
+		/// The resource as JSON if it's previewed with ResourceLib, kept so that pages and searches of the preview don't
+		/// convert it again
+		generic_rl_json: Option<Arc<String>>
 	},
 	Text {
 		content: String,
@@ -84,12 +212,18 @@ pub enum EditorData {
 	},
 	QNEntity {
 		settings: EphemeralQNSettings,
-		entity: Box<Entity>
+		entity: Box<Entity>,
+
+		/// Most recently deleted last
+		recycle_bin: Vec<DeletedEntities>
 	},
 	QNPatch {
 		settings: EphemeralQNSettings,
 		base: Box<Entity>,
-		current: Box<Entity>
+		current: Box<Entity>,
+
+		/// Most recently deleted last
+		recycle_bin: Vec<DeletedEntities>
 	},
 	RepositoryPatch {
 		base: Vec<RepositoryItem>,
@@ -103,9 +237,38 @@ pub enum EditorData {
 	},
 	ContentSearchResults {
 		results: Vec<(String, String, Option<String>)>
+	},
+	Enum {
+		enums: Vec<SEnumType>
+	},
+	Dialogue {
+		dialogue: Value
+	},
+	JsonPatch {
+		content: String,
+
+		/// The resource the patch was last applied to, kept so it's only extracted again if the patch's target changes
+		base: Option<JsonPatchBase>
+	},
+	JsonResource {
+		hash: RuntimeID,
+		kind: JsonResourceKind,
+
+		/// The resource as it is in the game files, which saved patches are made against
+		base: Value,
+
+		content: String
 	}
 }
 
+/// The resource targeted by a JSON.patch.json file, as extracted from the game files.
+#[derive(Debug, Clone)]
+pub struct JsonPatchBase {
+	pub file: String,
+	pub patch_type: String,
+	pub data: Value
+}
+
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct EphemeralQNSettings {
@@ -122,6 +285,54 @@ impl Default for EphemeralQNSettings {
 	}
 }
 
+/// An entity deleted from an entity editor along with its children, kept so that it can be restored until the editor
+/// is closed.
+#[derive(Clone, Debug)]
+pub struct DeletedEntities {
+	/// The entity which was deleted; the others are its children
+	pub id: String,
+	pub name: String,
+	pub entities: Vec<(String, SubEntity)>,
+
+	/// Operations which add back the references other entities had to the deleted entities
+	pub references: Patch
+}
+
+/// An event connection which sends a constant value, as shown in the constant value editor.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct ConstantValueConnection {
+	pub output_pin: String,
+	pub input_pin: String,
+
+	/// The position of the connection among those from the output pin to the input pin
+	pub index: u32,
+
+	pub target: Ref,
+
+	/// The name of the target, if it's a local entity
+	pub target_name: Option<String>,
+
+	pub value: SimpleProperty,
+
+	/// The type of the target's property with the same name as the input pin, if intellisense knows of one
+	pub expected_type: Option<String>
+}
+
+/// An entry in an entity editor's recycle bin, as shown in the tree.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct RecycleBinEntry {
+	/// The position of the entry in the recycle bin, for restoring it
+	pub index: u32,
+
+	pub id: String,
+	pub name: String,
+
+	/// Including the entity itself
+	pub entity_count: u32,
+
+	pub reference_count: u32
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Project {
@@ -132,15 +343,80 @@ pub struct Project {
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectSettings {
-	pub custom_paths: Vec<String>
+	pub custom_paths: Vec<String>,
+
+	/// Glob-style patterns for files to leave out when packaging the mod
+	#[serde(default = "default_package_ignore")]
+	pub package_ignore: Vec<String>,
+
+	/// Prefixes tried before each word when brute-forcing audio names, like `play_`
+	#[serde(default)]
+	pub audio_name_prefixes: Vec<String>,
+
+	/// Whether to check sub-entity names for problems like duplicates and leftover default names
+	#[serde(default)]
+	pub lint_entity_names: bool,
+
+	/// Regexes which sub-entity names are expected to match at least one of, if there are any
+	#[serde(default)]
+	pub entity_name_patterns: Vec<String>,
+
+	/// Whether to refuse to write to the project, so that it can be browsed without risking accidental changes
+	#[serde(default)]
+	pub read_only: bool,
+
+	/// How many previous versions of each file to keep in the backup folder when saving over it; 0 turns backups off
+	#[serde(default = "default_backup_count")]
+	pub backup_count: u32,
+
+	/// Content search and entity tree queries saved to be run again
+	#[serde(default)]
+	pub saved_searches: Vec<SavedSearch>,
+
+	/// The framework languages that placeholder strings are added for when generating localisation keys
+	#[serde(default = "default_localisation_languages")]
+	pub localisation_languages: Vec<String>,
+
+	/// Whether to remove empty sub-entity keys and sort properties by name when saving entity.json files
+	#[serde(default)]
+	pub tidy_entities_on_save: bool,
+
+	/// Saved entity patches with more operations than this are offered to be split into several files; 0 for no limit
+	#[serde(default = "default_patch_split_max_operations")]
+	pub patch_split_max_operations: u32,
+
+	/// Saved entity patches larger than this many megabytes are offered to be split into several files; 0 for no limit
+	#[serde(default = "default_patch_split_max_size")]
+	pub patch_split_max_size: u32
 }
 
 impl Default for ProjectSettings {
 	fn default() -> Self {
-		Self { custom_paths: vec![] }
+		Self {
+			custom_paths: vec![],
+			package_ignore: default_package_ignore(),
+			audio_name_prefixes: vec![],
+			lint_entity_names: false,
+			entity_name_patterns: vec![],
+			read_only: false,
+			backup_count: default_backup_count(),
+			saved_searches: vec![],
+			localisation_languages: default_localisation_languages(),
+			tidy_entities_on_save: false,
+			patch_split_max_operations: default_patch_split_max_operations(),
+			patch_split_max_size: default_patch_split_max_size()
+		}
 	}
 }
 
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum VersionBump {
+	None,
+	Patch,
+	Minor,
+	Major
+}
+
 #[derive(Type, Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct GameBrowserEntry {
@@ -148,7 +424,88 @@ pub struct GameBrowserEntry {
 	pub path: Option<String>,
 	pub hint: Option<String>,
 	pub filetype: ResourceType,
-	pub partition: (String, String)
+	pub partition: (String, String),
+
+	/// The DLC the resource belongs to, or None if it's part of the base game
+	pub dlc: Option<String>
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct ResourceCollection {
+	pub name: String,
+	pub entries: Vec<GameBrowserEntry>
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct OutfitBrowserEntry {
+	pub repository_id: Uuid,
+	pub kind: OutfitBrowserEntryKind,
+	pub name: String,
+
+	/// The brick containing the outfit kit for this outfit, if one was found
+	pub outfit_kit_brick: Option<RuntimeID>,
+
+	pub character_templates: Vec<RuntimeID>,
+	pub image: Option<RuntimeID>
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub enum OutfitBrowserEntryKind {
+	Outfit,
+	NPC
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct GitFileStatus {
+	pub path: PathBuf,
+
+	/// The path relative to the project folder, with forward slashes
+	pub relative_path: String,
+
+	pub change: GitChangeKind,
+
+	/// Whether the change has been staged
+	pub staged: bool
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum GitChangeKind {
+	New,
+	Modified,
+	Deleted,
+	Renamed,
+	TypeChange,
+	Conflicted
+}
+
+/// Another patch which modifies the same resource as a patch being saved.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct PatchConflict {
+	/// The file containing the other patch, if it has been saved
+	pub file: Option<PathBuf>,
+
+	/// The editor containing the other patch, if it is open
+	pub editor: Option<Uuid>,
+
+	pub operations: Vec<PatchConflictOperation>
+}
+
+/// A piece of data which two patches both modify, and the operations they apply to it.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct PatchConflictOperation {
+	pub target: String,
+	pub ours: Value,
+	pub theirs: Value
+}
+
+/// A problem in a text file, with 1-indexed line and column positions.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct TextDiagnostic {
+	pub message: String,
+	pub start_line: u32,
+	pub start_column: u32,
+	pub end_line: u32,
+	pub end_column: u32
 }
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
@@ -169,7 +526,11 @@ pub enum EditorType {
 	QNPatch,
 	RepositoryPatch { patch_type: JsonPatchType },
 	UnlockablesPatch { patch_type: JsonPatchType },
-	ContentSearchResults
+	ContentSearchResults,
+	Enum,
+	Dialogue,
+	JsonPatch,
+	JsonResource { kind: JsonResourceKind }
 }
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
@@ -178,6 +539,43 @@ pub enum JsonPatchType {
 	JsonPatch
 }
 
+/// A kind of JSON resource with a bundled schema, which can be edited and saved as a JSON patch.
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonResourceKind {
+	/// A location's challenges, in groups
+	Challenges,
+
+	/// GameChanger definitions, by ID
+	GameChangers
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DialogueContainerType {
+	WavFile,
+	Random,
+	Switch,
+	Sequence
+}
+
+/// A container in a DLGE dialogue tree, with its children separated from its other properties.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct DialogueContainer {
+	pub container_type: DialogueContainerType,
+
+	/// Every property of the container other than its type and children
+	pub properties: Value,
+
+	pub children: Vec<DialogueContainer>
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct DialogueIssue {
+	/// The indices of the children to follow from the root container to reach the container with the issue
+	pub path: Vec<u32>,
+
+	pub message: String
+}
+
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
 #[serde(tag = "type", content = "data")]
 pub enum EditorValidity {
@@ -190,7 +588,33 @@ pub enum EditorValidity {
 pub struct PastableTemplate {
 	pub name: String,
 	pub icon: String,
-	pub paste_data: CopiedEntityData
+	pub paste_data: CopiedEntityData,
+
+	/// Values the user is asked for when pasting the template
+	#[serde(default)]
+	pub parameters: Vec<TemplateParameter>
+}
+
+/// A value substituted into a template when it's pasted. Any string in the paste data which is exactly `{{name}}` is
+/// replaced with the value, and text and repository ID values also replace `{{name}}` within longer strings.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateParameter {
+	pub name: String,
+	pub kind: TemplateParameterKind,
+	pub default: Option<String>
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TemplateParameterKind {
+	Text,
+
+	/// Three numbers separated by commas or spaces, substituted as an object with x, y and z
+	Position,
+
+	/// A repository GUID
+	RepositoryId
 }
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
@@ -201,6 +625,136 @@ pub struct PastableTemplateCategory {
 	pub templates: Vec<PastableTemplate>
 }
 
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct BlueprintEntityInfo {
+	pub id: String,
+	pub name: String,
+
+	/// The hash of the resource that this entity is created from
+	pub factory: Option<RuntimeID>
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct BlueprintSubsetInfo {
+	/// The name of the entity which owns the subset
+	pub owner: String,
+	pub name: String,
+	pub entities: Vec<String>
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct BlueprintPinForwarding {
+	/// Whether this is an input pin forwarding (as opposed to an output pin forwarding)
+	pub input: bool,
+	pub from_entity: String,
+	pub from_pin: String,
+	pub to_entity: String,
+	pub to_pin: String
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct EntityStats {
+	pub entity_count: u32,
+
+	/// Factory, number of sub-entities using it; most used first
+	pub factory_counts: Vec<(String, u32)>,
+
+	pub property_count: u32,
+	pub platform_specific_property_count: u32,
+	pub pin_connection_count: u32,
+	pub external_scene_count: u32,
+
+	/// The number of resources the converted TEMP and TBLU depend on
+	pub dependency_count: u32,
+
+	/// The size in bytes of the binary TEMP and TBLU the entity converts to, if they could be generated
+	pub temp_size: Option<u32>,
+	pub tblu_size: Option<u32>
+}
+
+/// What a patch changes in the entity it's based on.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct PatchSummary {
+	/// ID, name
+	pub added_entities: Vec<(String, String)>,
+
+	/// ID, name
+	pub removed_entities: Vec<(String, String)>,
+
+	/// ID, original name, new name
+	pub renamed_entities: Vec<(String, String, String)>,
+
+	/// The number of sub-entities which exist in both and were changed in any way
+	pub modified_entity_count: u32,
+
+	/// Property type, number of properties of that type which were added, changed or removed; most first
+	pub modified_properties: Vec<(String, u32)>,
+
+	pub added_external_scenes: Vec<String>,
+	pub removed_external_scenes: Vec<String>
+}
+
+/// A problem with the name of a sub-entity, found by the entity name lint.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct EntityProblem {
+	pub entity_id: String,
+	pub name: String,
+	pub message: String
+}
+
+/// A resource which an animation resource depends on, like the rig it animates or the animation data it plays.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct AnimationDependency {
+	pub hash: RuntimeID,
+	pub path: Option<String>,
+	pub resource_type: String,
+
+	/// The number of bones in a BORG, and so the number of bone tracks animations of it have, if it could be parsed
+	pub bone_count: Option<u32>
+}
+
+/// A subset owned by a sub-entity, with the sub-entities which are members of it.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct EntitySubset {
+	pub owner: String,
+	pub owner_name: String,
+	pub name: String,
+
+	/// Entity ID, name; sorted by name
+	pub members: Vec<(String, String)>
+}
+
+/// A TEXT's low mip rendered separately from the high mip in its TEXD, with any signs that they don't match.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct TextureMipComparison {
+	pub format: String,
+	pub low_image_path: PathBuf,
+
+	/// Width, height
+	pub low_resolution: (u32, u32),
+
+	/// The TEXD's hash, if the TEXT has one
+	pub texd: Option<String>,
+
+	/// None if there is no TEXD or it couldn't be read
+	pub high_image_path: Option<PathBuf>,
+
+	/// Width, height
+	pub high_resolution: Option<(u32, u32)>,
+
+	pub warnings: Vec<String>
+}
+
+/// A switch (or state) group used by a Wwise event. Audio objects which aren't listed under any of the group's switches
+/// play regardless of which switch is set.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct WwiseSwitchGroup {
+	pub name: String,
+
+	/// Switch name, indices of the audio objects which play for it
+	pub switches: Vec<(String, Vec<u32>)>
+}
+
 #[derive(Type, Serialize, Deserialize, Clone, derive_more::Debug)]
 #[serde(tag = "type", content = "data")]
 pub enum ResourceOverviewData {
@@ -210,29 +764,88 @@ pub enum ResourceOverviewData {
 		blueprint_path_or_hint: Option<String>
 	},
 	GenericRL {
-		json: String
+		json: String,
+
+		// Set if the converted JSON is too large to preview in full, in which case only the first page is sent
+		total_lines: Option<u32>
+	},
+	Blueprint {
+		json: String,
+
+		// The TEMP(s) which use this blueprint
+		factories: Vec<(RuntimeID, Option<String>)>,
+
+		#[debug(skip)]
+		entities: Vec<BlueprintEntityInfo>,
+
+		subsets: Vec<BlueprintSubsetInfo>,
+		pin_forwardings: Vec<BlueprintPinForwarding>
 	},
 	Json {
-		json: String
+		json: String,
+
+		/// The kind of resource, if it can be opened in the JSON resource editor
+		kind: Option<JsonResourceKind>
 	},
 	Ores {
 		json: String
 	},
 	Image {
 		image_path: PathBuf,
-		dds_data: Option<(String, String)>
+		dds_data: Option<(String, String)>,
+
+		/// The loaded project's replacement for the resource, rendered the same way, if it has one
+		modded_image_path: Option<PathBuf>
 	},
 	Audio {
-		wav_path: PathBuf
+		wav_path: PathBuf,
+
+		/// None if the converted audio couldn't be read, in which case the frontend decodes it itself
+		waveform: Option<Waveform>
 	},
 	Mesh {
 		#[debug(skip)]
 		obj: String,
-		bounding_box: [f32; 6]
+		bounding_box: [f32; 6],
+
+		/// The skeleton of a weighted PRIM from the BORG it depends on; empty for other meshes
+		bones: Vec<Bone>,
+
+		/// The indices of the bones used by each object in the OBJ, in the same order; empty if there's no skeleton
+		bone_influences: Vec<Vec<u32>>
+	},
+	Skeleton {
+		bones: Vec<Bone>
+	},
+	Scaleform {
+		/// The extension of the embedded movie, either swf or gfx
+		swf_extension: String,
+
+		/// In bytes
+		swf_size: u32,
+
+		/// The names and sizes (in bytes) of the files the movie loads, such as its images
+		additional_files: Vec<(String, u32)>
+	},
+	Animation {
+		/// In bytes
+		size: u32,
+
+		/// The animation resources and rigs this depends on, directly or through other animation resources
+		dependencies: Vec<AnimationDependency>
 	},
 	MultiAudio {
 		name: String,
-		wav_paths: Vec<(String, PathBuf)>
+		wav_paths: Vec<(String, PathBuf)>,
+
+		/// The waveform of each of the audio objects, in the same order
+		waveforms: Vec<Option<Waveform>>,
+
+		/// The switch groups the event depends on, for choosing which of its audio objects would play
+		switch_groups: Vec<WwiseSwitchGroup>,
+
+		/// The IDs of the named Wwise objects the event's soundbanks use, with their names if known
+		wwise_names: Vec<(u32, Option<String>)>
 	},
 	Repository,
 	Unlockables,
@@ -249,8 +862,18 @@ pub enum ResourceOverviewData {
 		json: String
 	},
 	SoundDefinitions {
-		json: String
-	}
+		json: String,
+
+		/// The IDs of the named Wwise objects the definitions' events use, with their names if known
+		wwise_names: Vec<(u32, Option<String>)>
+	},
+	BehaviourTree {
+		#[debug(skip)]
+		tree: BehaviourTreeNode
+	},
+
+	/// The constituent resources are sent separately, since they can be edited for ASETs in the project
+	AssetSet
 }
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
@@ -279,6 +902,15 @@ pub enum SearchFilter {
 	Sound
 }
 
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub enum PartitionFilter {
+	All,
+	BaseGame,
+
+	/// Only resources in the partition with the given ID, like chunk27
+	Partition(String)
+}
+
 #[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Dynamics {
@@ -351,13 +983,78 @@ strike! {
 
 				ConvertUnlockablesPatchToJsonPatch {
 					path: PathBuf
+				},
+
+				OpenWithExternalTool {
+					path: PathBuf,
+					tool: String
+				},
+
+				RequestBackups {
+					path: PathBuf
+				},
+
+				RestoreBackup {
+					path: PathBuf,
+					timestamp: u32
+				},
+
+				Duplicate {
+					path: PathBuf
+				},
+
+				PreviewBatchRename {
+					folder: PathBuf,
+					pattern: String,
+					replacement: String
+				},
+
+				BatchRename {
+					folder: PathBuf,
+					pattern: String,
+					replacement: String
+				},
+
+				CreateFromTemplate {
+					folder: PathBuf,
+					name: String,
+					template: FileTemplate
+				},
+
+				/// Suggest the partition an entity file should be loaded in, from the scenario it's used in
+				SuggestChunkAssignment {
+					path: PathBuf,
+					scenario: String
+				},
+
+				/// Load an entity in a partition by adding it to the manifest's packagedefinition changes
+				AssignChunk {
+					assignment: ChunkAssignment
+				},
+
+				/// Apply a patch file to the current game files without saving anything, to see which operations apply
+				TestPatch {
+					path: PathBuf
 				}
 			}),
 
 			GameBrowser(pub enum GameBrowserEvent {
 				Select(RuntimeID),
-				Search(String, SearchFilter),
-				OpenInEditor(RuntimeID)
+				Search(String, SearchFilter, PartitionFilter),
+				OpenInEditor(RuntimeID),
+
+				RemoveFromCollection {
+					collection: String,
+					hash: RuntimeID
+				},
+
+				DeleteCollection(String),
+				ExtractCollection(String),
+
+				ExtractWithPreset {
+					hash: RuntimeID,
+					preset: String
+				}
 			}),
 
 			Settings(pub enum SettingsEvent {
@@ -367,24 +1064,208 @@ strike! {
 				ChangeExtractModdedFiles(bool),
 				ChangeColourblind(bool),
 				ChangeEditorConnection(bool),
+				ChangeEditorConnectionGames(Vec<EditorConnectionGame>),
 
-				ChangeCustomPaths(Vec<String>)
-			}),
+				/// Choose which game entity selections and property changes are sent to, or None for every connected game
+				SetEditorConnectionTarget(Option<String>),
 
-			ContentSearch(pub enum ContentSearchEvent {
-				Search(String, Vec<String>, bool, Vec<String>)
-			})
-		}),
+				/// Wait for another instance with the same pairing code to connect and sync entity changes with it
+				HostCollaboration {
+					/// Shown to the other instance's user
+					name: String,
 
-		Editor(pub enum EditorEvent {
-			Text(pub enum TextEditorEvent {
-				Initialise {
-					id: Uuid
+					/// Pairing code which the other instance has to give to connect
+					secret: String,
+
+					/// Address of the network interface to listen on, or `0.0.0.0` for all of them
+					interface: String,
+
+					port: u16
+				},
+
+				/// Connect to another instance which is hosting, at an address like `192.168.1.2:46735`
+				ConnectCollaboration {
+					/// Shown to the other instance's user
+					name: String,
+
+					/// Pairing code which the host was given
+					secret: String,
+
+					address: String
+				},
+
+				StopCollaboration,
+
+				ReconnectEditorConnection,
+				ChangeOfflineMode(bool),
+				ChangeExternalTools(Vec<ExternalTool>),
+				ChangeExtractionPresets(Vec<ExtractionPreset>),
+				ChangeWemEncoder(Option<WemEncoder>),
+				ChangeEntityCacheMaxEntries(u32),
+
+				/// In megabytes
+				ChangeEntityCacheMaxMemory(u32),
+
+				/// Hashes or paths of resources to check for changes whenever the game files are updated
+				ChangeWatchedResources(Vec<String>),
+
+				/// Clear the cache of entities extracted from the game files and the in-memory intellisense caches
+				ClearCaches,
+
+				/// Measure the memory taken up by each editor and cache, and get the timings of recent tasks
+				GetDiagnostics,
+
+				ChangeCustomPaths(Vec<String>),
+				ChangePackageIgnore(Vec<String>),
+				ChangeAudioNamePrefixes(Vec<String>),
+				ChangeLintEntityNames(bool),
+				ChangeEntityNamePatterns(Vec<String>),
+				ChangeTidyEntitiesOnSave(bool),
+				ChangeLocalisationLanguages(Vec<String>),
+				ChangeReadOnly(bool),
+				ChangeBackupCount(u32),
+				ChangePatchSplitMaxOperations(u32),
+				ChangePatchSplitMaxSize(u32),
+
+				/// Whether the loaded project is allowed to run external programs
+				ChangeProjectTrusted(bool),
+
+				PackageMod(VersionBump),
+
+				FindOrphanedFiles,
+
+				/// Paths relative to the project
+				ArchiveOrphanedFiles(Vec<PathBuf>),
+
+				/// Find resources and blobs changed by both the project and mods enabled in the framework
+				FindModConflicts
+			}),
+
+			ContentSearch(pub enum ContentSearchEvent {
+				/// Query, resource types, whether to search entities in QuickEntity format, partitions, and whether to
+				/// convert types without dedicated handling to ResourceLib JSON before matching rather than searching
+				/// their raw data
+				Search(String, Vec<String>, bool, Vec<String>, bool),
+
+				/// Save a search to the project, replacing any saved search with the same name
+				SaveSearch(SavedSearch),
+
+				DeleteSavedSearch(String),
+				RunSavedSearch(String)
+			}),
+
+			OutfitBrowser(pub enum OutfitBrowserEvent {
+				Initialise,
+				LoadImage(RuntimeID),
+				OpenEntity(RuntimeID)
+			}),
+
+			Git(pub enum GitEvent {
+				Refresh,
+				Diff(PathBuf),
+
+				Commit {
+					files: Vec<PathBuf>,
+					message: String
+				}
+			}),
+
+			ExtractionQueue(pub enum ExtractionQueueEvent {
+				Initialise,
+				Cancel(Uuid),
+				ClearFinished
+			}),
+
+			AudioNames(pub enum AudioNamesEvent {
+				Initialise,
+
+				/// Add the IDs of unnamed Wwise objects in a WWEV or SDEF resource's soundbanks to the targets
+				GatherTargets(RuntimeID),
+
+				/// Hash every word, alone and after each of the project's prefixes, keeping any names which match a target
+				BruteForce {
+					targets: Vec<u32>,
+					words: Vec<String>,
+
+					/// A file with one word per line, tried in addition to the given words
+					wordlist: Option<PathBuf>
+				},
+
+				RemoveName(u32)
+			}),
+
+			Converters(pub enum ConvertersEvent {
+				Convert(String)
+			}),
+
+			HashRemapping(pub enum HashRemappingEvent {
+				/// Replace each old hash with its new hash throughout the project, or only report what would be replaced
+				Remap {
+					/// Old hash or path, new hash or path
+					mapping: Vec<(String, String)>,
+					dry_run: bool
+				}
+			}),
+
+			HashResolution(pub enum HashResolutionEvent {
+				/// Resolve every hash and path in some text, like a crash log
+				Resolve(String),
+
+				OpenResourceOverview(RuntimeID)
+			}),
+
+			AudioImport(pub enum AudioImportEvent {
+				/// Pick a folder and list the WAV files in it
+				SelectFolder,
+
+				/// Convert WAV files in a folder to WEM and add them to the project as replacements
+				Import {
+					folder: PathBuf,
+
+					/// File name, hash or path of the WWEM it replaces
+					mapping: Vec<(String, String)>
+				}
+			}),
+
+			CrashAnalysis(pub enum CrashAnalysisEvent {
+				/// Pick a crash log or minidump and analyse it
+				SelectFile,
+
+				Analyse(PathBuf),
+				OpenFile(PathBuf),
+				OpenResourceOverview(RuntimeID)
+			})
+		}),
+
+		Editor(pub enum EditorEvent {
+			Text(pub enum TextEditorEvent {
+				Initialise {
+					id: Uuid
 				},
 
 				UpdateContent {
 					id: Uuid,
 					content: String
+				},
+
+				/// Search the content held by the backend, for files too large for Monaco's own search; the ID is
+				/// sent back with the results
+				Find {
+					id: Uuid,
+					search_id: Uuid,
+					query: String,
+					regex: bool,
+					match_case: bool
+				},
+
+				/// Replace every match of a search, or only the one at the given byte offset
+				Replace {
+					id: Uuid,
+					query: String,
+					regex: bool,
+					match_case: bool,
+					replacement: String,
+					offset: Option<u32>
 				}
 			}),
 
@@ -398,6 +1279,19 @@ strike! {
 					SetShowChangesFromOriginal {
 						editor_id: Uuid,
 						show_changes_from_original: bool
+					},
+
+					/// Re-diff a patch against a freshly extracted copy of the original entity, so that saving it only
+					/// writes the operations it actually needs
+					MinimisePatch {
+						editor_id: Uuid
+					},
+
+					/// Save an outline of the entity tree, with names, factories and simple property values, as a
+					/// Markdown or HTML document
+					ExportOutline {
+						editor_id: Uuid,
+						format: OutlineFormat
 					}
 				}),
 
@@ -444,6 +1338,29 @@ strike! {
 						parent_id: String
 					},
 
+					/// Duplicate an entity beside itself under the same parent, incrementing the number at the end of
+					/// its name and moving it by an offset
+					Duplicate {
+						editor_id: Uuid,
+						id: String,
+						include_children: bool,
+						offset: Vec3
+					},
+
+					/// Clone an entity and its children into a line, grid or circle, parented to a new group entity
+					GenerateArray {
+						editor_id: Uuid,
+						id: String,
+						pattern: ArrayPattern
+					},
+
+					/// Paste the copied entities as a single entity using them as its factory, saving them to a new
+					/// template in the project if needed
+					PasteAsInstance {
+						editor_id: Uuid,
+						parent_id: String
+					},
+
 					Search {
 						editor_id: Uuid,
 						query: String
@@ -454,10 +1371,15 @@ strike! {
 						entity_id: String
 					},
 
+					/// Paste a built-in template; if it has parameters and no values are given, the frontend is asked for
+					/// them first
 					UseTemplate {
 						editor_id: Uuid,
 						parent_id: String,
-						template: CopiedEntityData
+						template: PastableTemplate,
+
+						/// Parameter name, value as entered
+						parameter_values: Option<std::collections::HashMap<String, String>>
 					},
 
 					AddGameBrowserItem {
@@ -466,6 +1388,19 @@ strike! {
 						file: RuntimeID
 					},
 
+					/// Generate a WWEM and WWEV in the project for a new sound, and add a zaudioevententity playing it
+					AddCustomSound {
+						editor_id: Uuid,
+						parent_id: String,
+						name: String,
+
+						/// A WAV or WEM file
+						audio: PathBuf,
+
+						/// Hash or path of a vanilla WWEV playing a single streamed sound, whose soundbank is reused
+						template: String
+					},
+
 					SelectEntityInEditor {
 						editor_id: Uuid,
 						entity_id: String
@@ -494,6 +1429,93 @@ strike! {
 					RestoreToOriginal {
 						editor_id: Uuid,
 						entity_id: String
+					},
+
+					/// Put back an entry of the recycle bin, along with the references to it which can still be restored
+					RestoreDeleted {
+						editor_id: Uuid,
+						index: u32
+					},
+
+					/// Extract the game template an entity uses as its factory into the project as an editable entity,
+					/// and use that copy as the entity's factory instead
+					ExtractFactoryToProject {
+						editor_id: Uuid,
+						entity_id: String
+					},
+
+					/// Round an entity's position to a grid and its rotation to multiples of an angle (in degrees); either
+					/// can be zero to leave it unchanged
+					SnapEntityToGrid {
+						editor_id: Uuid,
+						entity_id: String,
+						grid_size: f64,
+						angle_snap: f64
+					},
+
+					/// Rotate an entity as little as possible so that its up axis points along a surface normal
+					AlignEntityToSurface {
+						editor_id: Uuid,
+						entity_id: String,
+						normal: Vec3
+					},
+
+					/// Move an entity and rotate it (in degrees), relative to its own axes if local or otherwise to its
+					/// parent's
+					OffsetEntityTransform {
+						editor_id: Uuid,
+						entity_id: String,
+						position: Vec3,
+						rotation: Vec3,
+						local: bool
+					},
+
+					/// Request the output pins of one entity and the input pins of another, to choose an event
+					/// connection between them
+					GetConnectionPins {
+						editor_id: Uuid,
+						source_id: String,
+						target_id: String
+					},
+
+					/// Connect an output pin of one entity to an input pin of another, optionally sending a constant
+					/// value instead of the output's own
+					ConnectEntities {
+						editor_id: Uuid,
+						source_id: String,
+						output_pin: String,
+						target_id: String,
+						input_pin: String,
+						value: Option<SimpleProperty>
+					},
+
+					/// Request the connections of an entity which send constant values, to edit them
+					GetConstantValues {
+						editor_id: Uuid,
+						entity_id: String
+					},
+
+					/// Change the constant value sent by an event connection; the value is checked against the type of
+					/// the target pin where intellisense knows it
+					SetConstantValue {
+						editor_id: Uuid,
+						entity_id: String,
+						output_pin: String,
+						input_pin: String,
+						index: u32,
+						value: SimpleProperty
+					},
+
+					/// Request the children of a node in a lazily loaded tree
+					ExpandNode {
+						editor_id: Uuid,
+						id: String
+					},
+
+					/// Request the ancestors of a node in a lazily loaded tree, so that it can be loaded and selected
+					RevealNode {
+						editor_id: Uuid,
+						id: String
 					}
 				}),
 
@@ -524,6 +1546,31 @@ strike! {
 					OpenResourceOverview {
 						editor_id: Uuid,
 						resource: String
+					},
+
+					/// Open the resource referenced by a ZRuntimeResourceID property; TEMPs are opened in the entity editor
+					/// and other resources in a resource overview
+					FollowResource {
+						editor_id: Uuid,
+						resource: String
+					},
+
+					/// Convert a property of a sub-entity to another unit or representation, like degrees to radians
+					ConvertProperty {
+						editor_id: Uuid,
+						entity_id: String,
+						property: String,
+						conversion: UnitConversion
+					},
+
+					/// Set an SColorRGB or SColorRGBA property to a colour chosen in the colour picker
+					SetColour {
+						editor_id: Uuid,
+						entity_id: String,
+						property: String,
+
+						/// RGBA channels from 0 to 1
+						colour: [f64; 4]
 					}
 				}),
 
@@ -537,6 +1584,73 @@ strike! {
 						editor_id: Uuid,
 						entity_id: String,
 						notes: String
+					},
+
+					/// List the overrides in the entity, resolving the names of their targets
+					RequestOverrides {
+						editor_id: Uuid
+					},
+
+					/// Select a local override target in the tree, or open the external scene containing it
+					JumpToOverrideTarget {
+						editor_id: Uuid,
+						entity_id: String,
+						external_scene: Option<String>
+					},
+
+					CreatePropertyAlias {
+						editor_id: Uuid,
+						entity_id: String,
+						aliased_name: String,
+						alias: PropertyAlias
+					},
+
+					/// Replace the alias at the given index among those with the given name, which may be renamed
+					EditPropertyAlias {
+						editor_id: Uuid,
+						entity_id: String,
+						aliased_name: String,
+						index: u32,
+						new_aliased_name: String,
+						alias: PropertyAlias
+					},
+
+					DeletePropertyAlias {
+						editor_id: Uuid,
+						entity_id: String,
+						aliased_name: String,
+						index: u32
+					},
+
+					/// Add an exposed entity, or replace the one with the same name; the entities it refers to are given
+					/// as local entity IDs or names
+					SetExposedEntity {
+						editor_id: Uuid,
+						entity_id: String,
+						exposed_name: String,
+						is_array: bool,
+						refers_to: Vec<String>
+					},
+
+					RemoveExposedEntity {
+						editor_id: Uuid,
+						entity_id: String,
+						exposed_name: String
+					},
+
+					/// Add an exposed interface, or replace the one with the same name; the entity implementing it is
+					/// given as a local entity ID or name
+					SetExposedInterface {
+						editor_id: Uuid,
+						entity_id: String,
+						interface: String,
+						refers_to: String
+					},
+
+					RemoveExposedInterface {
+						editor_id: Uuid,
+						entity_id: String,
+						interface: String
 					}
 				}),
 
@@ -595,6 +1709,39 @@ strike! {
 						editor_id: Uuid,
 						content: String
 					}
+				}),
+
+				Stats(pub enum EntityStatsEvent {
+					Calculate {
+						editor_id: Uuid
+					}
+				}),
+
+				Problems(pub enum EntityProblemsEvent {
+					Check {
+						editor_id: Uuid
+					}
+				}),
+
+				Subsets(pub enum EntitySubsetsEvent {
+					Initialise {
+						editor_id: Uuid
+					},
+
+					/// Make an entity a member of a subset, creating the subset if the owner doesn't have it yet.
+					AddMember {
+						editor_id: Uuid,
+						owner: String,
+						subset: String,
+						entity_id: String
+					},
+
+					RemoveMember {
+						editor_id: Uuid,
+						owner: String,
+						subset: String,
+						entity_id: String
+					}
 				})
 			}),
 
@@ -664,6 +1811,109 @@ strike! {
 
 				ExtractAsHMLanguages {
 					id: Uuid
+				},
+
+				ExtractAsObj {
+					id: Uuid
+				},
+
+				/// Extract a BORG's bones as JSON
+				ExtractAsSkeletonJson {
+					id: Uuid
+				},
+
+				/// Extract an animation resource and the animation resources and rigs it depends on, with their meta
+				/// files, to a folder
+				ExtractAnimationWithDependencies {
+					id: Uuid
+				},
+
+				/// Extract the movie embedded in a GFXF, and the files it loads, to a folder
+				ExtractScaleformMovie {
+					id: Uuid
+				},
+
+				/// Replace the movie embedded in a GFXF with a SWF or GFx file, saving the repacked GFXF to the project
+				RepackScaleformMovie {
+					id: Uuid
+				},
+
+				/// Export a behaviour tree as a Graphviz DOT graph
+				ExportBehaviourTreeAsDot {
+					id: Uuid
+				},
+
+				AddToCollection {
+					id: Uuid,
+					collection: String
+				},
+
+				GetGenericRLPage {
+					id: Uuid,
+					start_line: u32
+				},
+
+				SearchGenericRL {
+					id: Uuid,
+					query: String
+				},
+
+				OpenWithExternalTool {
+					id: Uuid,
+					tool: String
+				},
+
+				ExtractWithPreset {
+					id: Uuid,
+					preset: String
+				},
+
+				/// Replace a GFXI with an image, resized and encoded to match the original
+				ReplaceWithImage {
+					id: Uuid
+				},
+
+				/// Render a TEXT's low mip separately from its TEXD's high mip, to check that they match
+				CompareTextureMips {
+					id: Uuid
+				},
+
+				/// Compare the resource at an entry of its changelog with the copy it replaced, as converted JSON
+				GetChangelogDiff {
+					id: Uuid,
+					index: u32
+				},
+
+				/// Change the flag of a dependency in the resource's JSON meta file in the project
+				SetProjectDependencyFlag {
+					id: Uuid,
+					index: u32,
+					flag: DecodedReferenceFlag
+				},
+
+				/// Move a constituent resource of an ASET in the project to a different position
+				MoveAssetSetEntry {
+					id: Uuid,
+					from: u32,
+					to: u32
+				},
+
+				/// Add a constituent resource to the end of an ASET in the project, by hash or path
+				AddAssetSetEntry {
+					id: Uuid,
+					resource: String
+				},
+
+				RemoveAssetSetEntry {
+					id: Uuid,
+					index: u32
+				},
+
+				/// Convert edited material entity JSON back to a MATT and MATB replacing those being viewed, and write
+				/// them to the project
+				SaveMaterialEntity {
+					id: Uuid,
+					json: String
 				}
 			}),
 
@@ -676,6 +1926,13 @@ strike! {
 					id: Uuid
 				},
 
+				CreateRepositoryItemFromTemplate {
+					id: Uuid,
+					template: RepositoryItemTemplate,
+					name: String,
+					create_unlockable: bool
+				},
+
 				ResetModifications {
 					id: Uuid,
 					item: Uuid
@@ -690,6 +1947,59 @@ strike! {
 				SelectItem {
 					id: Uuid,
 					item: Uuid
+				},
+
+				/// Generate localisation keys for the item's Title and Description, add placeholder strings for them to
+				/// the manifest and set them on the item
+				GenerateLocalisation {
+					id: Uuid,
+					item: Uuid
+				},
+
+				FindEntitiesUsingItem {
+					id: Uuid,
+					item: Uuid
+				},
+
+				/// List the properties of the repository for the table view
+				GetTableColumns {
+					id: Uuid
+				},
+
+				/// Get the rows of the table view; the filter is made of space-separated terms which all need to
+				/// match, either `Column:text` to match a column or plain text to match the ID or any shown column
+				GetTable {
+					id: Uuid,
+					columns: Vec<String>,
+					sort_by: Option<String>,
+					sort_descending: bool,
+					filter: String
+				},
+
+				/// Set a property of an item from the table view; the value is parsed according to the type of the
+				/// column, and an empty value removes properties which aren't strings
+				EditTableCell {
+					id: Uuid,
+					item: Uuid,
+					column: String,
+					value: String
+				},
+
+				/// Read a CSV file (with a header row) or a JSON array of objects to import new items from, and show
+				/// its columns to map them to repository properties
+				ReadImportFile {
+					id: Uuid,
+					path: PathBuf
+				},
+
+				/// Add a new item with a generated ID for each row of the file; cells are parsed like those edited in
+				/// the table view, and empty cells are left out
+				ImportItems {
+					id: Uuid,
+					path: PathBuf,
+
+					/// Column of the file, repository property; columns mapped to an empty property are skipped
+					mapping: Vec<(String, String)>
 				}
 			}),
 
@@ -716,6 +2026,13 @@ strike! {
 				SelectUnlockable {
 					id: Uuid,
 					unlockable: Uuid
+				},
+
+				/// Generate a localisation key for the unlockable's display name, add placeholder strings for it to the
+				/// manifest and set it on the unlockable
+				GenerateLocalisation {
+					id: Uuid,
+					unlockable: Uuid
 				}
 			}),
 
@@ -728,6 +2045,84 @@ strike! {
 					id: Uuid,
 					hash: RuntimeID
 				}
+			}),
+
+			Enum(pub enum EnumEditorEvent {
+				Initialise {
+					id: Uuid
+				},
+
+				AddEnum {
+					id: Uuid,
+					name: String
+				},
+
+				AddValue {
+					id: Uuid,
+					enum_name: String,
+					value: String
+				},
+
+				RemoveValue {
+					id: Uuid,
+					enum_name: String,
+					value: String
+				}
+			}),
+
+			Dialogue(pub enum DialogueEditorEvent {
+				Initialise {
+					id: Uuid
+				},
+
+				/// Add a new container as the last child of the container at the given path
+				AddContainer {
+					id: Uuid,
+					parent: Vec<u32>,
+					container_type: DialogueContainerType
+				},
+
+				RemoveContainer {
+					id: Uuid,
+					path: Vec<u32>
+				},
+
+				/// Move a container so that it's at the given index among the children of the new parent
+				MoveContainer {
+					id: Uuid,
+					path: Vec<u32>,
+					new_parent: Vec<u32>,
+					index: u32
+				},
+
+				/// Replace the properties of a container, leaving its type and children untouched
+				UpdateContainer {
+					id: Uuid,
+					path: Vec<u32>,
+					properties: Value
+				}
+			}),
+
+			JsonPatch(pub enum JsonPatchEditorEvent {
+				Initialise {
+					id: Uuid
+				},
+
+				UpdateContent {
+					id: Uuid,
+					content: String
+				}
+			}),
+
+			JsonResource(pub enum JsonResourceEditorEvent {
+				Initialise {
+					id: Uuid
+				},
+
+				UpdateContent {
+					id: Uuid,
+					content: String
+				}
 			})
 		}),
 
@@ -738,9 +2133,30 @@ strike! {
 			SelectTab(Option<Uuid>),
 			RemoveTab(Uuid),
 			SaveTab(Uuid),
+
+			/// Open another tab showing the same entity editor, sharing its state
+			SplitTab(Uuid),
+
 			UploadLogAndReport(String),
 			UploadLastPanic,
-			ClearLastPanic
+			ClearLastPanic,
+
+			/// Return to the previously focused tab or selected entity, reopening its tab if it was closed
+			NavigateBack,
+
+			/// Undo a previous NavigateBack
+			NavigateForward,
+
+			/// Files dropped into the window, which are offered to be imported into the project
+			DetectDroppedFiles(Vec<PathBuf>),
+
+			ImportDroppedFiles(Vec<DroppedFile>),
+
+			/// Ask a running task to stop; only tasks started as cancellable can be cancelled
+			CancelTask(Uuid),
+
+			/// Split the saved entity patch of a tab into several patch files by subtree
+			SplitPatch(Uuid)
 		}),
 
 		EditorConnection(pub enum EditorConnectionEvent {
@@ -790,6 +2206,41 @@ strike! {
 					/// Relative path, is folder
 					#[debug(skip)]
 					files: Vec<(PathBuf, bool)>
+				},
+
+				/// Names of the configured external tools files can be opened with
+				SetExternalTools(Vec<String>),
+
+				/// The previous versions of a file, newest first
+				ShowBackups {
+					path: PathBuf,
+					backups: Vec<FileBackup>
+				},
+
+				ShowBatchRenamePreview {
+					folder: PathBuf,
+
+					/// Old path, new path of each file or folder that would be renamed
+					renames: Vec<(PathBuf, PathBuf)>,
+
+					/// Why the rename can't be done, if it can't
+					error: Option<String>
+				},
+
+				ShowChunkAssignment {
+					path: PathBuf,
+					assignment: Option<ChunkAssignment>,
+
+					/// Why a partition can't be suggested, if it can't
+					error: Option<String>
+				},
+
+				ShowPatchTest {
+					path: PathBuf,
+					test: Option<PatchTest>,
+
+					/// Why the patch couldn't be tested, if it couldn't
+					error: Option<String>
 				}
 			}),
 
@@ -801,7 +2252,15 @@ strike! {
 
 					#[debug(skip)]
 					entries: Vec<GameBrowserEntry>
-				}
+				},
+
+				SetCollections(#[debug(skip)] Vec<ResourceCollection>),
+
+				/// Name, ID of each partition in the game files
+				SetPartitions(Vec<(String, String)>),
+
+				/// Names of the configured extraction presets
+				SetExtractionPresets(Vec<String>)
 			}),
 
 			Settings(pub enum SettingsRequest {
@@ -809,12 +2268,107 @@ strike! {
 					game_installs: Vec<GameInstall>,
 					settings: AppSettings
 				},
-				ChangeProjectSettings(ProjectSettings)
+				ChangeProjectSettings(ProjectSettings),
+
+				/// Whether the loaded project is allowed to run external programs
+				SetProjectTrusted(bool),
+
+				SetEditorConnectionState {
+					games: Vec<EditorConnectionDiagnostics>,
+
+					/// The game entity selections and property changes are sent to, or None for every connected game
+					target: Option<String>
+				},
+
+				/// Forward-slash separated paths relative to the project of files which aren't referenced by the manifest
+				SetOrphanedFiles(Vec<String>),
+
+				SetCollaborationStatus(CollaborationStatus),
+
+				/// Conflicts with mods enabled in the framework, most severe first
+				SetModConflicts(Vec<ModConflict>),
+
+				ShowDiagnostics(SessionDiagnostics)
 			}),
 
 			ContentSearch(pub enum ContentSearchRequest {
 				SetEnabled(bool),
-				SetPartitions(Vec<(String, String)>)
+				SetPartitions(Vec<(String, String)>),
+				SetSavedSearches(Vec<SavedSearch>)
+			}),
+
+			OutfitBrowser(pub enum OutfitBrowserRequest {
+				SetEnabled(bool),
+
+				SetEntries(#[debug(skip)] Vec<OutfitBrowserEntry>),
+
+				SetImage {
+					hash: RuntimeID,
+					image_path: PathBuf
+				}
+			}),
+
+			Git(pub enum GitRequest {
+				// None if the project is not in a Git repository
+				SetStatus(Option<Vec<GitFileStatus>>),
+
+				SetDiff {
+					path: PathBuf,
+
+					/// Whether the diff is a QuickEntity patch rather than a textual diff
+					is_entity_patch: bool,
+
+					#[debug(skip)]
+					diff: String
+				}
+			}),
+
+			ExtractionQueue(pub enum ExtractionQueueRequest {
+				SetItems(Vec<ExtractionItem>),
+				UpdateItem(ExtractionItem)
+			}),
+
+			AudioNames(pub enum AudioNamesRequest {
+				/// ID, name
+				SetNames(#[debug(skip)] Vec<(u32, String)>),
+
+				AddTargets(Vec<u32>),
+
+				/// The names found by the last brute-force; ID, name
+				SetResults(Vec<(u32, String)>)
+			}),
+
+			Converters(pub enum ConvertersRequest {
+				SetConversions {
+					/// The input that was converted, so outdated results can be ignored
+					input: String,
+
+					conversions: Vec<Conversion>
+				}
+			}),
+
+			HashRemapping(pub enum HashRemappingRequest {
+				SetReport {
+					/// Whether the files were left unchanged
+					dry_run: bool,
+
+					files: Vec<RemappedFile>
+				}
+			}),
+
+			HashResolution(pub enum HashResolutionRequest {
+				SetResults(Vec<ResolvedResource>)
+			}),
+
+			AudioImport(pub enum AudioImportRequest {
+				SetFiles {
+					folder: PathBuf,
+					files: Vec<WavFile>
+				}
+			}),
+
+			CrashAnalysis(pub enum CrashAnalysisRequest {
+				SetAnalysis(CrashAnalysis)
 			})
 		}),
 
@@ -829,6 +2383,25 @@ strike! {
 					id: Uuid,
 					file_type: TextFileType
 				},
+
+				SetDiagnostics {
+					id: Uuid,
+					diagnostics: Vec<TextDiagnostic>
+				},
+
+				/// A chunk of the matches of a search; more follow until one is marked as done
+				AddSearchResults {
+					id: Uuid,
+					search_id: Uuid,
+
+					#[debug(skip)]
+					matches: Vec<TextSearchMatch>,
+
+					done: bool,
+
+					/// Whether there were too many matches to list them all
+					truncated: bool
+				}
 			}),
 
 			Entity(pub enum EntityEditorRequest {
@@ -851,7 +2424,27 @@ strike! {
 
 						/// ID, parent, name, factory, has reverse parent refs
 						#[debug(skip)]
-						entities: Vec<(String, Ref, String, String, bool)>
+						entities: Vec<(String, Ref, String, String, bool)>,
+
+						/// Whether only the root-level entities have been sent, with deeper levels sent as they're expanded
+						lazy: bool
+					},
+
+					/// The children of a node in a lazily loaded tree
+					NodeChildren {
+						editor_id: Uuid,
+						id: String,
+
+						/// ID, parent, name, factory, has reverse parent refs
+						#[debug(skip)]
+						children: Vec<(String, Ref, String, String, bool)>
+					},
+
+					/// The ancestors of a node in a lazily loaded tree, starting from the root
+					RevealPath {
+						editor_id: Uuid,
+						id: String,
+						ancestors: Vec<String>
 					},
 
 					/// Instructs the frontend to take the list of new entities, add any new ones and update any ones that already exist (by ID) with the new information.
@@ -864,12 +2457,33 @@ strike! {
 						new_entities: Vec<(String, Ref, String, String, bool)>
 					},
 
+					SetRecycleBin {
+						editor_id: Uuid,
+
+						/// Most recently deleted first
+						entries: Vec<RecycleBinEntry>
+					},
+
+					/// Fill the search box with a query and run it
+					SetSearchQuery {
+						editor_id: Uuid,
+						query: String
+					},
+
 					SearchResults {
 						editor_id: Uuid,
 
 						/// The IDs of the entities matching the query
 						#[debug(skip)]
-						results: Vec<String>
+						results: Vec<String>,
+
+						/// Entity ID, paths of the properties matching any property search terms in the query
+						#[debug(skip)]
+						property_matches: Vec<(String, Vec<String>)>,
+
+						/// The ancestors of the results, parents before children; these must be loaded in lazily loaded trees
+						#[debug(skip)]
+						ancestors: Vec<String>
 					},
 
 					ShowHelpMenu {
@@ -880,11 +2494,35 @@ strike! {
 						default_properties_json: String
 					},
 
+					ShowConnectEntities {
+						editor_id: Uuid,
+						source_id: String,
+						source_name: String,
+						target_id: String,
+						target_name: String,
+						output_pins: Vec<String>,
+						input_pins: Vec<String>
+					},
+
+					ShowConstantValues {
+						editor_id: Uuid,
+						entity_id: String,
+						entity_name: String,
+						connections: Vec<ConstantValueConnection>
+					},
+
 					SetTemplates {
 						editor_id: Uuid,
 						templates: Vec<PastableTemplateCategory>
 					},
 
+					/// Ask the user for the values of a template's parameters, then send UseTemplate again with them
+					PromptTemplateParameters {
+						editor_id: Uuid,
+						parent_id: String,
+						template: PastableTemplate
+					},
+
 					SetEditorConnectionAvailable {
 						editor_id: Uuid,
 						editor_connection_available: bool
@@ -923,14 +2561,29 @@ strike! {
 						editor_id: Uuid,
 						entity_id: String,
 						properties: Vec<(String, String, Value, bool)>,
-						pins: (Vec<String>, Vec<String>)
+						pins: (Vec<String>, Vec<String>),
+
+						/// Enum name, values for any properties using enums loaded in the enum editor
+						enums: Vec<(String, Vec<String>)>,
+
+						/// Property name, documentation for any documented properties
+						property_docs: Vec<(String, DocumentationEntry)>,
+
+						/// Pin name, documentation for any documented pins
+						pin_docs: Vec<(String, DocumentationEntry)>
 					},
 
 					UpdateDecorationsAndMonacoInfo {
 						editor_id: Uuid,
 						entity_id: String,
 						decorations: Vec<(String, String)>,
-						local_ref_entity_ids: Vec<String>
+						local_ref_entity_ids: Vec<String>,
+
+						/// Property name, RGBA channels from 0 to 1 for each colour property, for the colour picker
+						colours: Vec<(String, [f64; 4])>,
+
+						/// Entity ID, Markdown describing the entity for each reference to an external scene
+						external_ref_hovers: Vec<(String, String)>
 					},
 
 					UpdateValidity {
@@ -955,6 +2608,34 @@ strike! {
 						editor_id: Uuid,
 						entity_id: String,
 						notes: String
+					},
+
+					SetOverrides {
+						editor_id: Uuid,
+						overrides: Vec<OverrideListing>
+					},
+
+					SetPropertyAliases {
+						editor_id: Uuid,
+						entity_id: String,
+
+						/// Aliased name, aliases with that name
+						property_aliases: Vec<(String, Vec<PropertyAlias>)>,
+
+						/// Names of the local entities the aliases refer to
+						entity_names: std::collections::HashMap<String, String>
+					},
+
+					SetExposedEntities {
+						editor_id: Uuid,
+						entity_id: String,
+						exposed_entities: Vec<(String, ExposedEntity)>,
+
+						/// Interface, ID of the entity implementing it
+						exposed_interfaces: Vec<(String, String)>,
+
+						/// Names of the local entities the exposed entities and interfaces refer to
+						entity_names: std::collections::HashMap<String, String>
 					}
 				}),
 
@@ -983,6 +2664,12 @@ strike! {
 						blueprint_hash: String
 					},
 
+					/// Offer to change the blueprint hash to the one used by the new factory in the game files
+					SuggestBlueprintHash {
+						editor_id: Uuid,
+						blueprint_hash: String
+					},
+
 					UpdateCustomPaths {
 						editor_id: Uuid,
 						custom_paths: Vec<String>
@@ -1002,6 +2689,36 @@ strike! {
 						editor_id: Uuid,
 						decorations: Vec<(String, String)>,
 					}
+				}),
+
+				Stats(pub enum EntityStatsRequest {
+					SetStats {
+						editor_id: Uuid,
+						stats: EntityStats
+					},
+
+					/// Sent along with the stats for patch editors
+					SetPatchSummary {
+						editor_id: Uuid,
+						summary: PatchSummary
+					}
+				}),
+
+				Problems(pub enum EntityProblemsRequest {
+					SetProblems {
+						editor_id: Uuid,
+
+						/// Whether entity name linting is turned on in the project settings
+						enabled: bool,
+						problems: Vec<EntityProblem>
+					}
+				}),
+
+				Subsets(pub enum EntitySubsetsRequest {
+					SetSubsets {
+						editor_id: Uuid,
+						subsets: Vec<EntitySubset>
+					}
 				})
 			}),
 
@@ -1011,11 +2728,15 @@ strike! {
 					hash: String,
 					filetype: String,
 					chunk_patch: String,
+
+					/// The DLC the resource belongs to, or None if it's part of the base game
+					dlc: Option<String>,
+
 					path_or_hint: Option<String>,
 
-					/// Hash, type, path/hint, flag, is actually in current game version
+					/// Hash, type, path/hint, flag, is actually in current game version, decoded flag
 					#[debug(skip)]
-					dependencies: Vec<(String, String, Option<String>, String, bool)>,
+					dependencies: Vec<(String, String, Option<String>, String, bool, Option<DecodedReferenceFlag>)>,
 
 					/// Hash, type, path/hint
 					#[debug(skip)]
@@ -1023,7 +2744,72 @@ strike! {
 
 					changelog: Vec<ResourceChangelogEntry>,
 
+					/// Names of the configured external tools the resource can be opened with
+					external_tools: Vec<String>,
+
+					/// Names of the configured extraction presets
+					extraction_presets: Vec<String>,
+
+					/// What can be done with the resource's type in the loaded game version
+					capabilities: ResourceTypeCapabilities,
+
 					data: ResourceOverviewData
+				},
+
+				SetGenericRLPage {
+					id: Uuid,
+
+					/// Zero-indexed
+					start_line: u32,
+
+					#[debug(skip)]
+					json: String
+				},
+
+				SetGenericRLSearchResults {
+					id: Uuid,
+
+					/// Zero-indexed line number, trimmed line
+					#[debug(skip)]
+					results: Vec<(u32, String)>
+				},
+
+				SetTextureMipComparison {
+					id: Uuid,
+					comparison: TextureMipComparison
+				},
+
+				/// The converted JSON of the copy replaced at a changelog entry and the copy it was replaced with; either
+				/// is None if the resource wasn't present
+				SetChangelogDiff {
+					id: Uuid,
+					index: u32,
+
+					#[debug(skip)]
+					original: Option<String>,
+
+					#[debug(skip)]
+					modified: Option<String>
+				},
+
+				/// The dependencies of the resource's JSON meta file in the project, if it has one
+				SetProjectDependencyFlags {
+					id: Uuid,
+					meta_path: PathBuf,
+
+					/// Hash, decoded flag
+					dependencies: Vec<(String, DecodedReferenceFlag)>
+				},
+
+				/// The constituent resources of an ASET, from its meta file in the project if it has one and otherwise
+				/// from the game files; only those in the project are editable
+				SetAssetSetEntries {
+					id: Uuid,
+
+					/// Hash, type, path or hint
+					entries: Vec<(String, String, Option<String>)>,
+
+					editable: bool
 				}
 			}),
 
@@ -1065,6 +2851,39 @@ strike! {
 					id: Uuid,
 					item: Uuid,
 					info: RepositoryItemInformation
+				},
+
+				/// Property names and types, most common first
+				SetTableColumns {
+					id: Uuid,
+					columns: Vec<(String, RepositoryColumnType)>
+				},
+
+				SetTableRows {
+					id: Uuid,
+
+					#[debug(skip)]
+					rows: Vec<RepositoryTableRow>
+				},
+
+				SetTableCell {
+					id: Uuid,
+					item: Uuid,
+					column: String,
+					value: Option<Value>
+				},
+
+				ShowImportMapping {
+					id: Uuid,
+					path: PathBuf,
+					columns: Vec<String>,
+					row_count: u32,
+
+					/// The first few rows, as text
+					preview: Vec<Vec<String>>,
+
+					/// Properties already used by items in the repository, to map the columns to
+					properties: Vec<String>
 				}
 			}),
 
@@ -1106,6 +2925,14 @@ strike! {
 					id: Uuid,
 					unlockable: Uuid,
 					info: UnlockableInformation
+				},
+
+				/// Merged with the existing decorations
+				SetDecorations {
+					id: Uuid,
+
+					#[debug(skip)]
+					decorations: Vec<(Uuid, UnlockableDecoration)>
 				}
 			}),
 
@@ -1117,6 +2944,56 @@ strike! {
 					#[debug(skip)]
 					results: Vec<(String, String, Option<String>)>
 				}
+			}),
+
+			Enum(pub enum EnumEditorRequest {
+				SetEnums {
+					id: Uuid,
+
+					/// Enum name, values
+					#[debug(skip)]
+					enums: Vec<(String, Vec<String>)>
+				}
+			}),
+
+			Dialogue(pub enum DialogueEditorRequest {
+				SetDialogue {
+					id: Uuid,
+
+					#[debug(skip)]
+					root: DialogueContainer,
+
+					issues: Vec<DialogueIssue>
+				}
+			}),
+
+			JsonPatch(pub enum JsonPatchEditorRequest {
+				ReplaceContent {
+					id: Uuid,
+					content: String
+				},
+
+				/// Show the target resource with the patch applied, and mark the parts of the patch that couldn't be
+				/// applied
+				SetPreview {
+					id: Uuid,
+
+					/// None if the target resource couldn't be loaded
+					#[debug(skip)]
+					preview: Option<String>,
+
+					diagnostics: Vec<TextDiagnostic>
+				}
+			}),
+
+			JsonResource(pub enum JsonResourceEditorRequest {
+				ReplaceContent {
+					id: Uuid,
+					kind: JsonResourceKind,
+
+					#[debug(skip)]
+					content: String
+				}
 			})
 		}),
 
@@ -1129,6 +3006,15 @@ strike! {
 				name: String,
 				editor_type: EditorType
 			},
+
+			/// Create a tab for a split view of another tab's editor state; requests for that editor state should be
+			/// handled by both tabs
+			CreateSplitTab {
+				id: Uuid,
+				document: Uuid,
+				editor_type: EditorType
+			},
+
 			RenameTab {
 				id: Uuid,
 				new_name: String
@@ -1146,7 +3032,21 @@ strike! {
 				file_and_type: (String, String)
 			},
 			RequestLastPanicUpload,
-			LogUploadRejected
+			LogUploadRejected,
+			ShowPatchConflicts {
+				id: Uuid,
+				conflicts: Vec<PatchConflict>
+			},
+			ShowDroppedFileImport(Vec<DroppedFile>),
+
+			/// Offer to split an entity patch which was saved over the project's thresholds
+			OfferPatchSplit {
+				id: Uuid,
+				operations: u32,
+
+				/// In bytes
+				size: u32
+			}
 		})
 	}
 }
@@ -0,0 +1,252 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use fn_error_context::context;
+use hitman_commons::metadata::RuntimeID;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::{
+	general::open_file,
+	model::{
+		AppState, EditorData, EditorRequest, EditorState, EditorType, EntityEditorRequest, EntityTreeRequest,
+		GlobalRequest, Request
+	},
+	send_request,
+	split_view::get_tab_state
+};
+
+/// The maximum number of locations kept in each direction of the navigation history.
+const MAX_HISTORY_LENGTH: usize = 100;
+
+/// How a tab can be reopened if it has been closed since it was navigated to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NavigationSource {
+	File(PathBuf),
+	ResourceOverview(RuntimeID),
+
+	/// The tab can't be reopened once closed
+	None
+}
+
+/// A tab, and the entity selected in it if it's an entity editor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NavigationLocation {
+	pub tab: Uuid,
+	pub source: NavigationSource,
+	pub entity: Option<String>
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct NavigationHistory {
+	pub back: Vec<NavigationLocation>,
+	pub current: Option<NavigationLocation>,
+	pub forward: Vec<NavigationLocation>
+}
+
+impl NavigationHistory {
+	/// Update the tab of every location after a closed tab is reopened with a different ID. Locations which become
+	/// identical to the one next to them are merged, as the reopened tab may have been recorded before it was replaced.
+	fn replace_tab(&mut self, old: Uuid, new: Uuid) {
+		for location in self
+			.back
+			.iter_mut()
+			.chain(self.current.iter_mut())
+			.chain(self.forward.iter_mut())
+		{
+			if location.tab == old {
+				location.tab = new;
+			}
+		}
+
+		self.back.dedup();
+		self.forward.dedup();
+
+		if self.current.is_some() {
+			while self.back.last() == self.current.as_ref() {
+				self.back.pop();
+			}
+
+			while self.forward.last() == self.current.as_ref() {
+				self.forward.pop();
+			}
+		}
+	}
+}
+
+/// Record that a tab was navigated to, optionally selecting an entity within it. Navigating forward is no longer
+/// possible after a new location is recorded.
+pub fn record_navigation(app: &AppHandle, tab: Uuid, entity: Option<String>) {
+	let app_state = app.state::<AppState>();
+
+	let Some(editor_state) = get_tab_state(&app_state, tab) else {
+		return;
+	};
+
+	let location = NavigationLocation {
+		tab,
+		source: if let Some(file) = editor_state.file.as_ref() {
+			NavigationSource::File(file.to_owned())
+		} else if let EditorData::ResourceOverview { hash, .. } = editor_state.data {
+			NavigationSource::ResourceOverview(hash)
+		} else {
+			NavigationSource::None
+		},
+		entity
+	};
+
+	app_state.navigation_history.rcu(|history| {
+		if let Some(current) = history.current.as_ref() {
+			// Selecting an already focused tab, or navigation reselecting the current location, doesn't add to the
+			// history
+			if *current == location || (current.tab == location.tab && location.entity.is_none()) {
+				return history.to_owned();
+			}
+		}
+
+		let mut history = (**history).to_owned();
+
+		if let Some(current) = history.current.replace(location.to_owned()) {
+			history.back.push(current);
+
+			if history.back.len() > MAX_HISTORY_LENGTH {
+				history.back.remove(0);
+			}
+		}
+
+		history.forward.clear();
+
+		history.into()
+	});
+}
+
+/// Navigate to the previous location in the history, or the next one if `back` is false. Locations in closed tabs are
+/// reopened if possible, and skipped otherwise.
+#[try_fn]
+#[context("Couldn't navigate through history")]
+pub async fn navigate(app: &AppHandle, back: bool) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	loop {
+		let mut target = None;
+
+		app_state.navigation_history.rcu(|history| {
+			let mut history = (**history).to_owned();
+
+			target = if back {
+				history.back.pop()
+			} else {
+				history.forward.pop()
+			};
+
+			if let Some(target) = target.as_ref()
+				&& let Some(current) = history.current.replace(target.to_owned())
+			{
+				if back {
+					history.forward.push(current);
+				} else {
+					history.back.push(current);
+				}
+			}
+
+			history
+		});
+
+		let Some(target) = target else {
+			break;
+		};
+
+		if restore_location(app, target).await? {
+			break;
+		}
+
+		// The location couldn't be restored, so it's dropped from the history
+		app_state.navigation_history.rcu(|history| NavigationHistory {
+			current: None,
+			..(**history).to_owned()
+		});
+	}
+}
+
+/// Focus the tab of a location and select its entity, or reopen the tab if it was closed. Returns whether the location
+/// could be restored.
+#[try_fn]
+#[context("Couldn't restore navigation location")]
+async fn restore_location(app: &AppHandle, location: NavigationLocation) -> Result<bool> {
+	let app_state = app.state::<AppState>();
+
+	if get_tab_state(&app_state, location.tab).is_some() {
+		send_request(app, Request::Global(GlobalRequest::SelectTab(location.tab)))?;
+
+		if let Some(entity) = location.entity {
+			send_request(
+				app,
+				Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+					EntityTreeRequest::Select {
+						editor_id: location.tab,
+						id: Some(entity)
+					}
+				)))
+			)?;
+		}
+	} else {
+		// A reopened tab's tree isn't loaded yet, so its entity can't be selected
+		let tab = match location.source {
+			NavigationSource::File(ref path) => {
+				if !path.is_file() {
+					return Ok(false);
+				}
+
+				open_file(app, path).await?;
+
+				let Some(tab) = app_state
+					.editor_states
+					.iter()
+					.find(|x| x.file.as_ref() == Some(path))
+					.map(|x| x.key().to_owned())
+				else {
+					return Ok(false);
+				};
+
+				tab
+			}
+
+			NavigationSource::ResourceOverview(hash) => {
+				let tab = Uuid::new_v4();
+
+				app_state.editor_states.insert(
+					tab,
+					EditorState {
+						file: None,
+						data: EditorData::ResourceOverview {
+							hash,
+							generic_rl_json: None
+						}
+					}
+				);
+
+				send_request(
+					app,
+					Request::Global(GlobalRequest::CreateTab {
+						id: tab,
+						name: format!("Resource overview ({hash})"),
+						editor_type: EditorType::ResourceOverview
+					})
+				)?;
+
+				tab
+			}
+
+			NavigationSource::None => return Ok(false)
+		};
+
+		app_state.navigation_history.rcu(|history| {
+			let mut history = (**history).to_owned();
+			history.replace_tab(location.tab, tab);
+			history
+		});
+	}
+
+	true
+}
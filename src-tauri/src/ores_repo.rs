@@ -1,6 +1,8 @@
+use std::path::PathBuf;
+
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Value, json};
 use specta::Type;
 use uuid::Uuid;
 
@@ -35,6 +37,138 @@ pub enum RepositoryItemInformation {
 	Unknown
 }
 
+/// The kind of value a repository property holds, judging by the items which have it.
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RepositoryColumnType {
+	String,
+	Number,
+	Boolean,
+	Array,
+	Object,
+
+	/// Different items have different kinds of value for the property
+	Mixed
+}
+
+impl RepositoryColumnType {
+	pub fn of(value: &Value) -> Option<Self> {
+		match value {
+			Value::Null => None,
+			Value::String(_) => Some(Self::String),
+			Value::Number(_) => Some(Self::Number),
+			Value::Bool(_) => Some(Self::Boolean),
+			Value::Array(_) => Some(Self::Array),
+			Value::Object(_) => Some(Self::Object)
+		}
+	}
+}
+
+/// A repository item in the table view of a repository patch.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct RepositoryTableRow {
+	pub item: Uuid,
+	pub modified: bool,
+
+	/// The value of each shown column, or None if the item doesn't have the property
+	pub cells: Vec<Option<Value>>
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RepositoryItemTemplate {
+	Weapon,
+	Outfit,
+	Prop
+}
+
+impl RepositoryItemTemplate {
+	/// Get the data for a new repository item of this kind, with the required keys pre-filled.
+	pub fn create_item(self, name: &str) -> IndexMap<String, Value> {
+		let data = match self {
+			Self::Weapon => json!({
+				"CommonName": name,
+				"Title": "",
+				"Description": "",
+				"Image": "",
+				"ItemType": "eItemWeapon",
+				"ItemSize": "ITEMSIZE_MEDIUM",
+				"InventoryCategoryIcon": "pistol",
+				"Rarity": "common",
+				"PrimaryConfiguration": "",
+				"OnlineTraits": [],
+				"Modifiers": []
+			}),
+
+			Self::Outfit => json!({
+				"CommonName": name,
+				"Title": "",
+				"Description": "",
+				"Image": "",
+				"IsHitmanSuit": true,
+				"OnlineTraits": []
+			}),
+
+			Self::Prop => json!({
+				"CommonName": name,
+				"Title": "",
+				"Description": "",
+				"Image": "",
+				"ItemType": "eOther",
+				"ItemSize": "ITEMSIZE_SMALL",
+				"InventoryCategoryIcon": "gadget",
+				"Rarity": "common",
+				"OnlineTraits": [],
+				"Modifiers": []
+			})
+		};
+
+		match data {
+			Value::Object(x) => x.into_iter().collect(),
+			_ => unreachable!()
+		}
+	}
+
+	/// Create an unlockable which unlocks the given repository item.
+	pub fn create_unlockable(self, repository_id: Uuid, name: &str) -> UnlockableItem {
+		let (ty, slot) = match self {
+			Self::Weapon => ("weapon", "carriedweapon"),
+			Self::Outfit => ("disguise", "disguise"),
+			Self::Prop => ("gear", "gear")
+		};
+
+		let id = Uuid::new_v4();
+
+		let data = json!({
+			"Id": format!("{}_{}", ty.to_uppercase(), id.to_string().to_uppercase().replace('-', "_")),
+			"DisplayNameLocKey": "",
+			"GameAsset": null,
+			"Type": ty,
+			"Subtype": null,
+			"ImageId": null,
+			"RMTPrice": -1,
+			"GamePrice": -1,
+			"IsPurchasable": false,
+			"IsPublished": true,
+			"IsDroppable": false,
+			"Capabilities": [],
+			"Qualities": {},
+			"Properties": {
+				"Name": name,
+				"RepositoryId": repository_id,
+				"LoadoutSlot": slot,
+				"Rarity": "common"
+			}
+		});
+
+		UnlockableItem {
+			id,
+			data: match data {
+				Value::Object(x) => x.into_iter().collect(),
+				_ => unreachable!()
+			}
+		}
+	}
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct UnlockableItem {
 	#[serde(rename = "Guid")]
@@ -58,3 +192,13 @@ pub enum UnlockableInformation {
 	LoadoutUnlock { id: Option<String> },
 	Unknown { id: Option<String> }
 }
+
+/// Details shown alongside an unlockable, taken from the repository item it unlocks where possible.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct UnlockableDecoration {
+	pub name: Option<String>,
+	pub subtitle: Option<String>,
+
+	/// A preview of the unlockable's image, if it's an image resource or a file in one of the project's blobs folders
+	pub image_path: Option<PathBuf>
+}
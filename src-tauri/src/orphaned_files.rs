@@ -0,0 +1,175 @@
+use std::{
+	fs,
+	path::{Component, Path, PathBuf}
+};
+
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use serde_json::{Value, from_slice};
+use tryvial::try_fn;
+use walkdir::WalkDir;
+
+use crate::backups::BACKUP_FOLDER;
+use crate::packaging::{DIST_FOLDER, ignore_pattern_to_regex};
+
+/// The folder, relative to the project, that orphaned files are archived to.
+pub const ORPHANED_FILES_FOLDER: &str = "unused";
+
+/// Files at the root of the project which are expected to not be referenced by the manifest; compared
+/// case-insensitively against the file name without its extension.
+const ROOT_DOCUMENTATION_FILES: [&str; 4] = ["readme", "license", "licence", "changelog"];
+
+/// The folders and files referenced by a manifest (or one of its options), as forward-slash separated relative paths.
+fn collect_manifest_references(manifest: &Value, folders: &mut Vec<String>, files: &mut Vec<String>) {
+	let normalise = |path: &str| path.replace('\\', "/").trim_matches('/').to_owned();
+
+	for key in ["contentFolders", "blobsFolders"] {
+		folders.extend(
+			manifest
+				.get(key)
+				.and_then(|x| x.as_array())
+				.into_iter()
+				.flatten()
+				.filter_map(|x| x.as_str())
+				.map(normalise)
+		);
+	}
+
+	for key in ["scripts", "peacockPlugins"] {
+		files.extend(
+			manifest
+				.get(key)
+				.and_then(|x| x.as_array())
+				.into_iter()
+				.flatten()
+				.filter_map(|x| x.as_str())
+				.map(normalise)
+		);
+	}
+
+	files.extend(
+		manifest
+			.get("runtimePackages")
+			.and_then(|x| x.as_array())
+			.into_iter()
+			.flatten()
+			.filter_map(|x| x.get("path").and_then(|x| x.as_str()))
+			.map(normalise)
+	);
+
+	if let Some(image) = manifest.get("image").and_then(|x| x.as_str()) {
+		files.push(normalise(image));
+	}
+
+	for option in manifest.get("options").and_then(|x| x.as_array()).into_iter().flatten() {
+		collect_manifest_references(option, folders, files);
+	}
+}
+
+/// Find files in the project which aren't in any of the manifest's content or blobs folders and aren't referenced by it
+/// directly (as a script, Peacock plugin, runtime package or option image); these are likely leftovers. Files the
+/// project ignores when packaging, the dist folder and the folder orphaned files are archived to are skipped. Returns
+/// forward-slash separated paths relative to the project.
+#[try_fn]
+#[context("Couldn't find orphaned files in {}", project_path.display())]
+pub fn find_orphaned_files(project_path: &Path, ignore: &[String]) -> Result<Vec<String>> {
+	let manifest: Value =
+		from_slice(&fs::read(project_path.join("manifest.json")).context("Couldn't read manifest.json")?)
+			.context("Invalid manifest")?;
+
+	let mut folders = vec![];
+	let mut files = vec!["manifest.json".to_owned()];
+	collect_manifest_references(&manifest, &mut folders, &mut files);
+
+	let ignore = ignore
+		.iter()
+		.chain(
+			[
+				format!("{}/**", DIST_FOLDER),
+				format!("{}/**", ORPHANED_FILES_FOLDER),
+				format!("{}/**", BACKUP_FOLDER)
+			]
+			.iter()
+		)
+		.filter(|x| !x.trim().is_empty())
+		.map(|x| ignore_pattern_to_regex(x))
+		.collect::<Result<Vec<_>>>()?;
+
+	let mut orphaned = vec![];
+
+	for entry in WalkDir::new(project_path)
+		.sort_by_file_name()
+		.into_iter()
+		.filter_entry(|x| x.file_name() != ".git")
+		.filter_map(|x| x.ok())
+		.filter(|x| x.file_type().is_file())
+	{
+		let relative_path = entry
+			.path()
+			.strip_prefix(project_path)?
+			.to_string_lossy()
+			.replace('\\', "/");
+
+		if ignore.iter().any(|x| x.is_match(&relative_path)) || files.contains(&relative_path) {
+			continue;
+		}
+
+		if folders
+			.iter()
+			.any(|folder| folder.is_empty() || relative_path.starts_with(&format!("{}/", folder)))
+		{
+			continue;
+		}
+
+		if !relative_path.contains('/')
+			&& ROOT_DOCUMENTATION_FILES.iter().any(|x| {
+				entry
+					.path()
+					.file_stem()
+					.is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(x))
+			}) {
+			continue;
+		}
+
+		orphaned.push(relative_path);
+	}
+
+	orphaned
+}
+
+/// Move the given files (relative to the project) into the orphaned files folder, keeping their folder structure, and
+/// remove any folders left empty. Returns the path of the orphaned files folder.
+#[try_fn]
+#[context("Couldn't archive orphaned files")]
+pub fn archive_orphaned_files(project_path: &Path, files: &[PathBuf]) -> Result<PathBuf> {
+	let archive_path = project_path.join(ORPHANED_FILES_FOLDER);
+
+	for file in files {
+		if file.is_absolute() || file.components().any(|x| matches!(x, Component::ParentDir)) {
+			bail!("{} is not inside the project", file.display());
+		}
+
+		let source = project_path.join(file);
+		let destination = archive_path.join(file);
+
+		if destination.exists() {
+			bail!("{} has already been archived", file.display());
+		}
+
+		fs::create_dir_all(destination.parent().context("No parent")?)?;
+
+		fs::rename(&source, &destination).with_context(|| format!("Couldn't move {}", file.display()))?;
+
+		let mut folder = source.parent();
+
+		while let Some(path) = folder
+			&& path != project_path
+			&& fs::read_dir(path).is_ok_and(|mut x| x.next().is_none())
+		{
+			fs::remove_dir(path)?;
+			folder = path.parent();
+		}
+	}
+
+	archive_path
+}
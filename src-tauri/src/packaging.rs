@@ -0,0 +1,227 @@
+use std::{
+	fs::{self, File},
+	io::Write,
+	path::{Path, PathBuf},
+	process::Command
+};
+
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use regex::Regex;
+use serde_json::{Value, from_slice, to_string};
+use tryvial::try_fn;
+use walkdir::WalkDir;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::{backups::BACKUP_FOLDER, biome::format_json, model::VersionBump, orphaned_files::ORPHANED_FILES_FOLDER};
+
+/// The folder, relative to the project, that packaged mods are written to.
+pub const DIST_FOLDER: &str = "dist";
+
+pub fn default_package_ignore() -> Vec<String> {
+	vec![
+		".git/**".into(),
+		".vscode/**".into(),
+		"project.json".into(),
+		"**/.DS_Store".into(),
+		"**/Thumbs.db".into(),
+		"**/desktop.ini".into(),
+		"**/*.bak".into(),
+		"**/*.tmp".into(),
+	]
+}
+
+/// Convert a glob-style ignore pattern into a regex matching forward-slash separated relative paths.
+///
+/// `**` matches any number of path segments, `*` matches within a single segment and `?` matches a single character.
+pub fn ignore_pattern_to_regex(pattern: &str) -> Result<Regex> {
+	let mut regex = String::from("^");
+	let mut chars = pattern.trim().trim_start_matches('/').chars().peekable();
+
+	while let Some(char) = chars.next() {
+		match char {
+			'*' if chars.peek() == Some(&'*') => {
+				chars.next();
+
+				// Allow "**/" to also match no folders at all
+				if chars.peek() == Some(&'/') {
+					chars.next();
+					regex.push_str("(?:.*/)?");
+				} else {
+					regex.push_str(".*");
+				}
+			}
+
+			'*' => regex.push_str("[^/]*"),
+			'?' => regex.push_str("[^/]"),
+			_ => regex.push_str(&regex::escape(&char.to_string()))
+		}
+	}
+
+	// Patterns without a trailing glob should also ignore the contents of matching folders
+	regex.push_str("(?:/.*)?$");
+
+	Ok(Regex::new(&regex)?)
+}
+
+/// Bump a semver-style version string.
+fn bump_version(version: &str, bump: VersionBump) -> Result<String> {
+	let mut parts = version
+		.split(['-', '+'])
+		.next()
+		.unwrap_or_default()
+		.split('.')
+		.map(|x| x.parse::<u32>().context("Version is not in the format x.y.z"))
+		.collect::<Result<Vec<_>>>()?;
+
+	if parts.len() != 3 {
+		bail!("Version is not in the format x.y.z");
+	}
+
+	match bump {
+		VersionBump::None => {}
+
+		VersionBump::Patch => {
+			parts[2] += 1;
+		}
+
+		VersionBump::Minor => {
+			parts[1] += 1;
+			parts[2] = 0;
+		}
+
+		VersionBump::Major => {
+			parts[0] += 1;
+			parts[1] = 0;
+			parts[2] = 0;
+		}
+	}
+
+	Ok(format!("{}.{}.{}", parts[0], parts[1], parts[2]))
+}
+
+/// Generate a changelog stub from the commits since the last tag, if the project is a Git repository.
+fn generate_changelog(project_path: &Path, version: &str) -> Option<String> {
+	if !project_path.join(".git").exists() {
+		return None;
+	}
+
+	let last_tag = Command::new("git")
+		.current_dir(project_path)
+		.args(["describe", "--tags", "--abbrev=0"])
+		.output()
+		.ok()
+		.filter(|x| x.status.success())
+		.map(|x| String::from_utf8_lossy(&x.stdout).trim().to_owned());
+
+	let mut args = vec![
+		"log".to_owned(),
+		"--pretty=format:- %s".to_owned(),
+		"--no-merges".to_owned(),
+	];
+
+	if let Some(last_tag) = last_tag {
+		args.push(format!("{}..HEAD", last_tag));
+	}
+
+	let output = Command::new("git").current_dir(project_path).args(args).output().ok()?;
+
+	if !output.status.success() {
+		return None;
+	}
+
+	Some(format!(
+		"# {}\n\n{}\n",
+		version,
+		String::from_utf8_lossy(&output.stdout).trim()
+	))
+}
+
+/// Package the mod in the given project, bumping its version and writing a zip (and changelog stub, if the project
+/// is a Git repository and one is wanted) to the dist folder. Returns the path of the created archive.
+#[try_fn]
+#[context("Couldn't package mod")]
+pub fn package_mod(project_path: &Path, ignore: &[String], bump: VersionBump, with_changelog: bool) -> Result<PathBuf> {
+	let manifest_path = project_path.join("manifest.json");
+
+	let mut manifest: Value =
+		from_slice(&fs::read(&manifest_path).context("Couldn't read manifest.json")?).context("Invalid manifest")?;
+
+	let id = manifest
+		.get("id")
+		.and_then(|x| x.as_str())
+		.context("Manifest has no ID")?
+		.to_owned();
+
+	let version = bump_version(
+		manifest
+			.get("version")
+			.and_then(|x| x.as_str())
+			.context("Manifest has no version")?,
+		bump
+	)?;
+
+	manifest["version"] = Value::String(version.to_owned());
+
+	// The bumped manifest is only written to the project once the archive has been built successfully
+	let manifest_contents = format_json(&to_string(&manifest)?)?;
+
+	let ignore = ignore
+		.iter()
+		.chain(
+			[
+				format!("{}/**", DIST_FOLDER),
+				format!("{}/**", BACKUP_FOLDER),
+				format!("{}/**", ORPHANED_FILES_FOLDER)
+			]
+			.iter()
+		)
+		.filter(|x| !x.trim().is_empty())
+		.map(|x| ignore_pattern_to_regex(x))
+		.collect::<Result<Vec<_>>>()?;
+
+	let dist_path = project_path.join(DIST_FOLDER);
+
+	fs::create_dir_all(&dist_path)?;
+
+	let archive_path = dist_path.join(format!("{}-{}.zip", id, version));
+
+	let mut zip = ZipWriter::new(File::create(&archive_path).context("Couldn't create archive")?);
+	let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+	for entry in WalkDir::new(project_path)
+		.sort_by_file_name()
+		.into_iter()
+		.filter_map(|x| x.ok())
+		.filter(|x| x.file_type().is_file())
+	{
+		let relative_path = entry
+			.path()
+			.strip_prefix(project_path)?
+			.to_string_lossy()
+			.replace('\\', "/");
+
+		if ignore.iter().any(|x| x.is_match(&relative_path)) {
+			continue;
+		}
+
+		// Mods are distributed in a folder named after their ID
+		zip.start_file(format!("{}/{}", id, relative_path), options)?;
+
+		if entry.path() == manifest_path {
+			zip.write_all(manifest_contents.as_bytes())?;
+		} else {
+			zip.write_all(&fs::read(entry.path())?)?;
+		}
+	}
+
+	zip.finish()?;
+
+	fs::write(&manifest_path, manifest_contents)?;
+
+	if with_changelog && let Some(changelog) = generate_changelog(project_path, &version) {
+		fs::write(dist_path.join(format!("{}-{}-changelog.md", id, version)), changelog)?;
+	}
+
+	archive_path
+}
@@ -0,0 +1,294 @@
+use std::{
+	fs,
+	path::{Path, PathBuf}
+};
+
+use anyhow::{Context, Result};
+use fn_error_context::context;
+use hashbrown::HashMap;
+use quickentity_rs::{
+	patch_structs::{Patch, PatchOperation},
+	qn_structs::Entity
+};
+use serde_json::to_string;
+use tryvial::try_fn;
+
+use crate::{
+	biome::format_json,
+	dropped_files::register_content_folder,
+	entity::{get_local_reference, get_operation_sub_entity},
+	model::ProjectSettings
+};
+
+pub fn default_patch_split_max_operations() -> u32 {
+	2000
+}
+
+pub fn default_patch_split_max_size() -> u32 {
+	2
+}
+
+/// Whether a saved patch is large enough that splitting it should be offered, going by the project's thresholds.
+pub fn exceeds_split_thresholds(settings: &ProjectSettings, operations: usize, size: usize) -> bool {
+	(settings.patch_split_max_operations != 0 && operations > settings.patch_split_max_operations as usize)
+		|| (settings.patch_split_max_size != 0 && size > settings.patch_split_max_size as usize * 1024 * 1024)
+}
+
+/// Group the operations of a patch by subtree, starting from the sub-entities parented to nothing in this entity and
+/// breaking up any subtree too large to fit in one part into its root and each of its children's subtrees. Groups are
+/// returned in depth-first order as indices into the patch's operations; operations on the entity itself come first.
+fn group_operations(
+	patch: &Patch,
+	base: &Entity,
+	current: &Entity,
+	sizes: &[usize],
+	max_operations: usize,
+	max_size: usize
+) -> Vec<Vec<usize>> {
+	let mut entity_operations: HashMap<String, Vec<usize>> = HashMap::new();
+	let mut entity_level = vec![];
+
+	for (index, operation) in patch.patch.iter().enumerate() {
+		match get_operation_sub_entity(operation) {
+			Some(id) => entity_operations.entry(id).or_default().push(index),
+			None => entity_level.push(index)
+		}
+	}
+
+	// Removed sub-entities are placed where they were in the base entity
+	let parent_of = |id: &str| {
+		current
+			.entities
+			.get(id)
+			.or_else(|| base.entities.get(id))
+			.and_then(|x| get_local_reference(&x.parent))
+			.filter(|parent| {
+				parent != id && (current.entities.contains_key(parent) || base.entities.contains_key(parent))
+			})
+	};
+
+	let mut children: HashMap<Option<String>, Vec<String>> = HashMap::new();
+
+	for id in current
+		.entities
+		.keys()
+		.chain(base.entities.keys().filter(|x| !current.entities.contains_key(*x)))
+	{
+		children.entry(parent_of(id)).or_default().push(id.to_owned());
+	}
+
+	// Sub-entities which only appear in the patch go at the top level
+	for id in entity_operations.keys() {
+		if !current.entities.contains_key(id) && !base.entities.contains_key(id) {
+			children.entry(None).or_default().push(id.to_owned());
+		}
+	}
+
+	fn subtree_operations(
+		id: &str,
+		children: &HashMap<Option<String>, Vec<String>>,
+		entity_operations: &HashMap<String, Vec<usize>>,
+		operations: &mut Vec<usize>,
+		depth: usize
+	) {
+		operations.extend(entity_operations.get(id).into_iter().flatten());
+
+		// Guard against parent cycles, which would otherwise recurse forever
+		if depth < 512 {
+			for child in children.get(&Some(id.to_owned())).into_iter().flatten() {
+				subtree_operations(child, children, entity_operations, operations, depth + 1);
+			}
+		}
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn collect_groups(
+		id: &str,
+		children: &HashMap<Option<String>, Vec<String>>,
+		entity_operations: &HashMap<String, Vec<usize>>,
+		sizes: &[usize],
+		max_operations: usize,
+		max_size: usize,
+		groups: &mut Vec<Vec<usize>>,
+		depth: usize
+	) {
+		let mut operations = vec![];
+		subtree_operations(id, children, entity_operations, &mut operations, depth);
+
+		if operations.is_empty() {
+			return;
+		}
+
+		let size = operations.iter().map(|x| sizes[*x]).sum::<usize>();
+
+		if depth >= 512 || (operations.len() <= max_operations && size <= max_size) {
+			groups.push(operations);
+		} else {
+			groups.push(entity_operations.get(id).cloned().unwrap_or_default());
+
+			for child in children.get(&Some(id.to_owned())).into_iter().flatten() {
+				collect_groups(
+					child,
+					children,
+					entity_operations,
+					sizes,
+					max_operations,
+					max_size,
+					groups,
+					depth + 1
+				);
+			}
+		}
+	}
+
+	let mut groups = vec![entity_level];
+
+	let mut roots = children.get(&None).cloned().unwrap_or_default();
+	roots.sort();
+	roots.dedup();
+
+	for root in roots {
+		collect_groups(
+			&root,
+			&children,
+			&entity_operations,
+			sizes,
+			max_operations,
+			max_size,
+			&mut groups,
+			0
+		);
+	}
+
+	groups.retain(|x| !x.is_empty());
+
+	// Anything left over (sub-entities whose parents form a cycle) goes in a group of its own
+	let mut grouped = vec![false; patch.patch.len()];
+
+	for index in groups.iter().flatten() {
+		grouped[*index] = true;
+	}
+
+	let ungrouped = (0..patch.patch.len()).filter(|x| !grouped[*x]).collect::<Vec<_>>();
+
+	if !ungrouped.is_empty() {
+		groups.push(ungrouped);
+	}
+
+	groups
+}
+
+/// Split a patch into several patches by subtree, each within the project's thresholds where possible. Applying the
+/// parts one after another has the same result as applying the original patch; operations keep their original order
+/// within each part.
+#[try_fn]
+#[context("Couldn't split patch")]
+pub fn split_patch(patch: &Patch, base: &Entity, current: &Entity, settings: &ProjectSettings) -> Result<Vec<Patch>> {
+	let max_operations = match settings.patch_split_max_operations {
+		0 => usize::MAX,
+		x => x as usize
+	};
+
+	let max_size = match settings.patch_split_max_size {
+		0 => usize::MAX,
+		x => x as usize * 1024 * 1024
+	};
+
+	let sizes = patch
+		.patch
+		.iter()
+		.map(|x| to_string(x).map(|x| x.len()))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut parts: Vec<Vec<usize>> = vec![];
+	let mut part_size = 0;
+
+	for group in group_operations(patch, base, current, &sizes, max_operations, max_size) {
+		let group_size = group.iter().map(|x| sizes[*x]).sum::<usize>();
+
+		match parts.last_mut() {
+			Some(part) if part.len() + group.len() <= max_operations && part_size + group_size <= max_size => {
+				part.extend(group);
+				part_size += group_size;
+			}
+
+			_ => {
+				parts.push(group);
+				part_size = group_size;
+			}
+		}
+	}
+
+	parts
+		.into_iter()
+		.map(|mut part| {
+			part.sort_unstable();
+
+			Patch {
+				factory_hash: patch.factory_hash.to_owned(),
+				blueprint_hash: patch.blueprint_hash.to_owned(),
+				patch: part
+					.into_iter()
+					.map(|x| patch.patch[x].to_owned())
+					.collect::<Vec<PatchOperation>>(),
+				patch_version: patch.patch_version
+			}
+		})
+		.collect()
+}
+
+/// The path of a part of a split patch, like `example.part002.entity.patch.json` for `example.entity.patch.json`. Part
+/// numbers are zero-padded so that the parts sort in the order they have to be applied, as later parts can depend on
+/// sub-entities added by earlier ones.
+fn get_part_path(path: &Path, part: usize) -> Result<PathBuf> {
+	let name = path.file_name().context("No file name")?.to_string_lossy();
+
+	let stem = name
+		.strip_suffix(".entity.patch.json")
+		.or_else(|| name.strip_suffix(".json"))
+		.unwrap_or(&name);
+
+	Ok(path.with_file_name(format!("{}.part{:03}.entity.patch.json", stem, part)))
+}
+
+/// Write the parts of a split patch next to the original patch file, which is removed. Parts left over from splitting
+/// the patch into more files before are removed too. If the file is in a chunk folder that isn't part of a content
+/// folder in the manifest, the content folder is added to the manifest so that the parts are deployed. Returns the paths
+/// of the parts.
+#[try_fn]
+#[context("Couldn't write split patch")]
+pub fn write_split_patch(project_path: &Path, path: &Path, parts: &[Patch]) -> Result<Vec<PathBuf>> {
+	let mut paths = vec![];
+
+	for (index, part) in parts.iter().enumerate() {
+		let part_path = get_part_path(path, index + 1)?;
+
+		fs::write(&part_path, format_json(&to_string(part)?)?)?;
+
+		paths.push(part_path);
+	}
+
+	let mut stale = parts.len() + 1;
+
+	while get_part_path(path, stale)?.is_file() {
+		fs::remove_file(get_part_path(path, stale)?)?;
+		stale += 1;
+	}
+
+	if path.is_file() {
+		fs::remove_file(path)?;
+	}
+
+	if let Some(chunk_folder) = path.parent()
+		&& chunk_folder
+			.file_name()
+			.is_some_and(|x| x.to_string_lossy().to_lowercase().starts_with("chunk"))
+		&& let Some(content_folder) = chunk_folder.parent()
+		&& let Ok(relative) = content_folder.strip_prefix(project_path)
+		&& !relative.as_os_str().is_empty()
+	{
+		register_content_folder(project_path, &relative.to_string_lossy().replace('\\', "/"))?;
+	}
+
+	paths
+}
@@ -0,0 +1,428 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result, anyhow, bail};
+use arc_swap::ArcSwap;
+use fn_error_context::context;
+use hitman_commons::metadata::RuntimeID;
+use hitman_formats::ores::parse_json_ores;
+use indexmap::IndexMap;
+use quickentity_rs::{apply_patch, patch_structs::Patch, qn_structs::Entity};
+use rpkg_rs::resource::partition_manager::PartitionManager;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, from_slice, from_str, from_value, to_value};
+use specta::Type;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::{
+	conflicts::{REPOSITORY_HASH, UNLOCKABLES_HASH},
+	entity::get_operation_sub_entity,
+	get_loaded_game_version,
+	model::{AppSettings, AppState, JsonPatchBase},
+	ores_repo::{RepositoryItem, UnlockableItem},
+	resourcelib::convert_generic,
+	rpkg::{extract_entity_async, extract_latest_resource}
+};
+
+/// What applying a single patch operation does to its target.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type", content = "data")]
+pub enum PatchOperationOutcome {
+	/// The operation changes the target
+	Applies,
+
+	/// The operation succeeds but leaves the target as it was, like setting a value to what it already is
+	NoOp,
+
+	/// The operation can't be applied, and why
+	Fails(String)
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct PatchOperationResult {
+	/// A short description of the operation, like `replace /Items/3/Name` or `SubEntityOperation abcdef0123456789`
+	pub operation: String,
+
+	pub outcome: PatchOperationOutcome
+}
+
+/// The result of applying a patch file to the current game files without writing anything.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct PatchTest {
+	/// The hash or path of the resource the patch targets
+	pub target: String,
+
+	pub operations: Vec<PatchOperationResult>
+}
+
+/// Convert the repository to the form its patches are written against: an object of item ID to item data.
+#[try_fn]
+#[context("Couldn't convert repository to patchable form")]
+pub fn repository_to_patchable(repository: &[RepositoryItem]) -> Result<Value> {
+	to_value(
+		repository
+			.iter()
+			.cloned()
+			.map(|x| (x.id, x.data))
+			.collect::<IndexMap<Uuid, IndexMap<String, Value>>>()
+	)?
+}
+
+#[try_fn]
+#[context("Couldn't convert patched repository to items")]
+pub fn patchable_to_repository(repository: Value) -> Result<Vec<RepositoryItem>> {
+	from_value::<IndexMap<Uuid, IndexMap<String, Value>>>(repository)?
+		.into_iter()
+		.map(|(id, data)| RepositoryItem { id, data })
+		.collect()
+}
+
+/// Extract the unlockables ORES from the game files.
+#[try_fn]
+#[context("Couldn't extract unlockables")]
+pub fn extract_unlockables(game_files: &PartitionManager) -> Result<Vec<UnlockableItem>> {
+	from_str(&parse_json_ores(
+		&extract_latest_resource(game_files, UNLOCKABLES_HASH.parse::<RuntimeID>()?)?.1
+	)?)?
+}
+
+/// Convert unlockables to the form their patches are written against: an object of unlockable ID to unlockable data,
+/// with the GUID moved into the data.
+#[try_fn]
+#[context("Couldn't convert unlockables to patchable form")]
+pub fn unlockables_to_patchable(unlockables: &[UnlockableItem]) -> Result<Value> {
+	let mut patchable = IndexMap::new();
+
+	for unlockable in unlockables {
+		let mut data = IndexMap::new();
+		data.insert("Guid".into(), to_value(unlockable.id)?);
+		data.extend(
+			unlockable
+				.data
+				.iter()
+				.filter(|(key, _)| *key != "Id")
+				.map(|(key, value)| (key.to_owned(), value.to_owned()))
+		);
+
+		patchable.insert(
+			unlockable
+				.data
+				.get("Id")
+				.context("Unlockable did not have Id")?
+				.as_str()
+				.context("Id was not string")?
+				.to_owned(),
+			data
+		);
+	}
+
+	to_value(patchable)?
+}
+
+#[try_fn]
+#[context("Couldn't convert patched unlockables to items")]
+pub fn patchable_to_unlockables(unlockables: Value) -> Result<Vec<UnlockableItem>> {
+	let mut items = vec![];
+
+	for (id, data) in from_value::<IndexMap<String, IndexMap<String, Value>>>(unlockables)? {
+		items.push(UnlockableItem {
+			id: data
+				.get("Guid")
+				.context("No Guid on unlockable item")?
+				.as_str()
+				.context("Guid was not string")?
+				.try_into()
+				.context("Guid was not valid UUID")?,
+			data: {
+				let mut y = IndexMap::new();
+				y.insert("Id".into(), Value::String(id));
+				y.extend(data.into_iter().filter(|(key, _)| key != "Guid"));
+				y
+			}
+		});
+	}
+
+	items
+}
+
+/// Get the operations of a JSON.patch.json file.
+#[try_fn]
+#[context("Couldn't read JSON patch operations")]
+pub fn parse_json_patch(file: &Value) -> Result<Vec<json_patch::PatchOperation>> {
+	from_value(file.get("patch").context("Patch had no patch key")?.to_owned()).context("Invalid JSON patch")?
+}
+
+/// Extract the resource a JSON.patch.json file targets as JSON. JSON resources are used as they are, ORES files are
+/// converted the same way as their JSON patches expect, and anything else is converted with ResourceLib.
+#[try_fn]
+#[context("Couldn't get target of JSON patch")]
+pub fn get_json_patch_base(app: &AppHandle, file: &str, patch_type: &str) -> Result<JsonPatchBase> {
+	let app_state = app.state::<AppState>();
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+
+	let game_files = app_state.game_files.load_full().context("No game loaded")?;
+
+	let (_, res_data) = extract_latest_resource(&game_files, file.parse::<RuntimeID>().context("Invalid file hash")?)?;
+
+	let data = match patch_type {
+		"JSON" => from_slice::<Value>(&res_data).context("Target isn't valid JSON")?,
+
+		"ORES" => from_str::<Value>(&parse_json_ores(&res_data)?)?,
+
+		_ => convert_generic::<Value>(
+			&res_data,
+			get_loaded_game_version(
+				app,
+				app_settings.load().game_install.as_ref().context("No game selected")?
+			)?,
+			patch_type.try_into().map_err(|_| anyhow!("Invalid patch type"))?
+		)?
+	};
+
+	JsonPatchBase {
+		file: file.into(),
+		patch_type: patch_type.into(),
+		data
+	}
+}
+
+/// Whether a JSON patch operation would leave the target as it was. This is checked without applying the operation, as
+/// the target can be as large as the whole repository.
+fn is_json_patch_no_op(target: &Value, operation: &json_patch::PatchOperation) -> bool {
+	match operation {
+		json_patch::PatchOperation::Replace(json_patch::ReplaceOperation { path, value }) => {
+			target.pointer(&path.to_string()) == Some(value)
+		}
+
+		// Adding to an array inserts rather than replaces, so only adding to an object can be a no-op
+		json_patch::PatchOperation::Add(json_patch::AddOperation { path, value }) => {
+			let path = path.to_string();
+
+			path.rsplit_once('/')
+				.is_some_and(|(parent, _)| target.pointer(parent).is_some_and(|x| x.is_object()))
+				&& target.pointer(&path) == Some(value)
+		}
+
+		json_patch::PatchOperation::Move(json_patch::MoveOperation { from, path }) => from == path,
+
+		_ => false
+	}
+}
+
+/// Apply JSON patch operations one at a time, so that a failing operation doesn't stop the rest from being applied, and
+/// report what each one did.
+pub fn apply_json_patch_operations(target: &mut Value, operations: &[Value]) -> Vec<PatchOperationResult> {
+	operations
+		.iter()
+		.map(|operation| PatchOperationResult {
+			operation: match (
+				operation.get("op").and_then(|x| x.as_str()),
+				operation.get("path").and_then(|x| x.as_str())
+			) {
+				(Some(op), Some(path)) => format!("{} {}", op, path),
+				_ => operation.to_string()
+			},
+
+			outcome: match from_value::<json_patch::PatchOperation>(operation.to_owned()) {
+				Ok(operation) if is_json_patch_no_op(target, &operation) => PatchOperationOutcome::NoOp,
+
+				Ok(operation) => match json_patch::patch(target, &[operation]) {
+					Ok(()) => PatchOperationOutcome::Applies,
+					Err(err) => PatchOperationOutcome::Fails(format!("Operation failed: {}", err))
+				},
+
+				Err(err) => PatchOperationOutcome::Fails(format!("Invalid operation: {}", err))
+			}
+		})
+		.collect()
+}
+
+/// Apply a merge patch one top-level key (one repository item or unlockable) at a time, and report what each one did.
+/// Merge patches can't fail, but they can leave items as they were.
+pub fn apply_merge_patch(target: &mut Value, patch: &Value) -> Vec<PatchOperationResult> {
+	let Some(items) = patch.as_object() else {
+		let outcome = if target == patch {
+			PatchOperationOutcome::NoOp
+		} else {
+			PatchOperationOutcome::Applies
+		};
+
+		json_patch::merge(target, patch);
+
+		return vec![PatchOperationResult {
+			operation: "Replace everything".into(),
+			outcome
+		}];
+	};
+
+	items
+		.iter()
+		.map(|(key, value)| {
+			let before = target.get(key).cloned();
+
+			json_patch::merge(
+				target,
+				&Value::Object(Map::from_iter([(key.to_owned(), value.to_owned())]))
+			);
+
+			PatchOperationResult {
+				operation: if value.is_null() {
+					format!("Remove {}", key)
+				} else if before.is_none() {
+					format!("Add {}", key)
+				} else {
+					format!("Modify {}", key)
+				},
+
+				outcome: if target.get(key) == before.as_ref() {
+					PatchOperationOutcome::NoOp
+				} else {
+					PatchOperationOutcome::Applies
+				}
+			}
+		})
+		.collect()
+}
+
+/// Apply a QuickEntity patch one operation at a time, and report what each one did. Unlike opening a patch, this isn't
+/// permissive, so operations which QuickEntity would otherwise skip with a warning are reported as failing.
+pub fn apply_entity_patch(entity: &mut Entity, patch: &Patch) -> Vec<PatchOperationResult> {
+	patch
+		.patch
+		.iter()
+		.map(|operation| {
+			let description = match to_value(operation).ok().as_ref().and_then(|x| x.as_object()) {
+				Some(operation) => match operation.iter().next() {
+					Some((kind, Value::Array(data))) => match data.first().and_then(|x| x.as_str()) {
+						Some(id) => format!("{} {}", kind, id),
+						None => kind.to_owned()
+					},
+
+					Some((kind, Value::String(id))) => format!("{} {}", kind, id),
+
+					Some((kind, _)) => kind.to_owned(),
+
+					None => "Unknown operation".into()
+				},
+
+				None => "Unknown operation".into()
+			};
+
+			// Only the affected sub-entity needs comparing, as serialising the whole entity for every operation is slow
+			let sub_entity = get_operation_sub_entity(operation);
+
+			let snapshot = |entity: &Entity| match sub_entity.as_ref() {
+				Some(id) => entity.entities.get(id).and_then(|x| to_value(x).ok()),
+				None => to_value(entity).ok()
+			};
+
+			let before = snapshot(entity);
+
+			let outcome = match apply_patch(
+				entity,
+				Patch {
+					factory_hash: patch.factory_hash.to_owned(),
+					blueprint_hash: patch.blueprint_hash.to_owned(),
+					patch: vec![operation.to_owned()],
+					patch_version: patch.patch_version
+				},
+				false
+			) {
+				Ok(()) if snapshot(entity) == before => PatchOperationOutcome::NoOp,
+				Ok(()) => PatchOperationOutcome::Applies,
+				Err(err) => PatchOperationOutcome::Fails(format!("{:#}", anyhow!(err)))
+			};
+
+			PatchOperationResult {
+				operation: description,
+				outcome
+			}
+		})
+		.collect()
+}
+
+/// Apply a patch file in the project (an entity.patch.json, repository.json, unlockables.json or JSON.patch.json) to
+/// the current game files without saving anything, and report which operations apply, fail or do nothing.
+#[try_fn]
+#[context("Couldn't test patch {}", path.display())]
+pub async fn test_patch(app: &AppHandle, path: &Path) -> Result<PatchTest> {
+	let app_state = app.state::<AppState>();
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+
+	let game_files = app_state.game_files.load_full().context("No game loaded")?;
+
+	let name = path.file_name().context("No file name")?.to_string_lossy();
+
+	let file: Value = from_slice(&fs::read(path).context("Couldn't read file")?).context("Invalid JSON")?;
+
+	if name.ends_with(".entity.patch.json") {
+		let patch: Patch = from_value(file).context("Invalid entity patch")?;
+
+		let hash_list = app_state.hash_list.load_full().context("No hash list available")?;
+
+		let mut entity = extract_entity_async(
+			&game_files,
+			&app_state.cached_entities,
+			get_loaded_game_version(
+				app,
+				app_settings.load().game_install.as_ref().context("No game selected")?
+			)?,
+			&hash_list,
+			RuntimeID::from_any(&patch.factory_hash)?
+		)
+		.await?
+		.to_owned();
+
+		PatchTest {
+			target: patch.factory_hash.to_owned(),
+			operations: apply_entity_patch(&mut entity, &patch)
+		}
+	} else if name.ends_with(".repository.json") {
+		let mut repository =
+			repository_to_patchable(app_state.repository.load().as_deref().context("No repository loaded")?)?;
+
+		PatchTest {
+			target: REPOSITORY_HASH.into(),
+			operations: apply_merge_patch(&mut repository, &file)
+		}
+	} else if name.ends_with(".unlockables.json") {
+		let mut unlockables = unlockables_to_patchable(&extract_unlockables(&game_files)?)?;
+
+		PatchTest {
+			target: UNLOCKABLES_HASH.into(),
+			operations: apply_merge_patch(&mut unlockables, &file)
+		}
+	} else if name.ends_with(".JSON.patch.json") {
+		let target = file
+			.get("file")
+			.context("Patch had no file key")?
+			.as_str()
+			.context("File key was not string")?;
+
+		let patch_type = file.get("type").and_then(|x| x.as_str()).unwrap_or("JSON");
+
+		let mut base = match (target, patch_type) {
+			(REPOSITORY_HASH, "REPO") => {
+				repository_to_patchable(app_state.repository.load().as_deref().context("No repository loaded")?)?
+			}
+
+			(UNLOCKABLES_HASH, "ORES") => unlockables_to_patchable(&extract_unlockables(&game_files)?)?,
+
+			_ => get_json_patch_base(app, target, patch_type)?.data
+		};
+
+		PatchTest {
+			target: target.into(),
+			operations: apply_json_patch_operations(
+				&mut base,
+				file.get("patch")
+					.context("Patch had no patch key")?
+					.as_array()
+					.context("Patch key must be an array of operations")?
+			)
+		}
+	} else {
+		bail!("Only entity.patch.json, repository.json, unlockables.json and JSON.patch.json files can be tested");
+	}
+}
@@ -0,0 +1,175 @@
+use std::{
+	fs,
+	path::{Path, PathBuf}
+};
+
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use hitman_commons::metadata::RuntimeID;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, from_slice, json, to_string};
+use specta::Type;
+use tryvial::try_fn;
+use walkdir::WalkDir;
+
+use crate::{biome::format_json, packaging::DIST_FOLDER};
+
+/// The language code of references which apply to every language.
+const NO_LANGUAGE: u8 = 0x1F;
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReferenceKind {
+	/// Loaded at install time, along with the resource itself
+	Install,
+
+	/// A strong runtime reference, which keeps the referenced resource loaded
+	Normal,
+
+	/// A weak runtime reference, which doesn't load the referenced resource by itself
+	Weak
+}
+
+/// A dependency flag, decoded from its modern (HITMAN 3) form.
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedReferenceFlag {
+	pub kind: ReferenceKind,
+
+	/// Whether the referenced resource is acquired by the game at runtime
+	pub acquired: bool,
+
+	/// The language the reference is for, or None if it applies to every language
+	pub language_code: Option<u8>
+}
+
+/// Decode a dependency flag in its modern form: the language code in the low five bits, then whether it's acquired,
+/// then the kind of reference in the top two bits.
+#[try_fn]
+#[context("Couldn't decode dependency flag {:02X}", flag)]
+pub fn decode_reference_flag(flag: u8) -> Result<DecodedReferenceFlag> {
+	let language_code = flag & 0x1F;
+
+	DecodedReferenceFlag {
+		kind: match flag >> 6 {
+			0 => ReferenceKind::Install,
+			1 => ReferenceKind::Normal,
+			2 => ReferenceKind::Weak,
+			_ => bail!("Unknown reference kind")
+		},
+		acquired: flag & 0x20 != 0,
+		language_code: (language_code != NO_LANGUAGE).then_some(language_code)
+	}
+}
+
+/// Encode a dependency flag in its modern form.
+pub fn encode_reference_flag(flag: &DecodedReferenceFlag) -> u8 {
+	let kind: u8 = match flag.kind {
+		ReferenceKind::Install => 0,
+		ReferenceKind::Normal => 1,
+		ReferenceKind::Weak => 2
+	};
+
+	let acquired: u8 = if flag.acquired { 0x20 } else { 0 };
+
+	(kind << 6) | acquired | (flag.language_code.unwrap_or(NO_LANGUAGE) & 0x1F)
+}
+
+/// Find the JSON meta file for a resource in the project, named after its hash like extracted meta files are.
+pub fn find_project_meta(project_path: &Path, hash: RuntimeID) -> Option<PathBuf> {
+	let prefix = format!("{}.", hash).to_lowercase();
+
+	WalkDir::new(project_path)
+		.into_iter()
+		.filter_entry(|x| x.file_name() != ".git" && x.file_name() != DIST_FOLDER)
+		.filter_map(|x| x.ok())
+		.filter(|x| x.file_type().is_file())
+		.find(|x| {
+			let name = x.file_name().to_string_lossy().to_lowercase();
+			name.starts_with(&prefix) && name.ends_with(".meta.json")
+		})
+		.map(|x| x.into_path())
+}
+
+/// Read the dependencies of a JSON meta file, with their decoded flags.
+#[try_fn]
+#[context("Couldn't read dependencies of {}", path.display())]
+pub fn read_meta_dependencies(path: &Path) -> Result<Vec<(String, DecodedReferenceFlag)>> {
+	let meta: Value = from_slice(&fs::read(path)?).context("Invalid meta JSON")?;
+
+	meta.get("hash_reference_data")
+		.and_then(|x| x.as_array())
+		.context("No dependencies in meta JSON")?
+		.iter()
+		.map(|dependency| {
+			let hash = dependency
+				.get("hash")
+				.and_then(|x| x.as_str())
+				.context("Dependency has no hash")?;
+
+			let flag = dependency
+				.get("flag")
+				.and_then(|x| x.as_str())
+				.context("Dependency has no flag")?;
+
+			Ok((
+				hash.to_owned(),
+				decode_reference_flag(u8::from_str_radix(flag, 16).context("Invalid flag")?)?
+			))
+		})
+		.collect::<Result<_>>()?
+}
+
+/// Change the flag of the dependency at the given index in a JSON meta file, rewriting the file.
+#[try_fn]
+#[context("Couldn't set flag of dependency {} in {}", index, path.display())]
+pub fn set_meta_dependency_flag(path: &Path, index: usize, flag: &DecodedReferenceFlag) -> Result<()> {
+	let mut meta: Value = from_slice(&fs::read(path)?).context("Invalid meta JSON")?;
+
+	*meta
+		.get_mut("hash_reference_data")
+		.and_then(|x| x.as_array_mut())
+		.context("No dependencies in meta JSON")?
+		.get_mut(index)
+		.context("No such dependency")?
+		.get_mut("flag")
+		.context("Dependency has no flag")? = Value::String(format!("{:02X}", encode_reference_flag(flag)));
+
+	fs::write(path, format_json(&to_string(&meta)?)?)?;
+}
+
+/// Replace the dependencies of a JSON meta file, rewriting the file. The size of the reference table is recalculated to
+/// match: a count, then a flag byte and an eight-byte hash for each dependency, or nothing at all if there are none.
+#[try_fn]
+#[context("Couldn't write dependencies of {}", path.display())]
+pub fn write_meta_dependencies(path: &Path, dependencies: &[(String, DecodedReferenceFlag)]) -> Result<()> {
+	let mut meta: Value = from_slice(&fs::read(path)?).context("Invalid meta JSON")?;
+
+	let meta_object = meta.as_object_mut().context("Meta JSON is not an object")?;
+
+	meta_object.insert(
+		"hash_reference_data".into(),
+		Value::Array(
+			dependencies
+				.iter()
+				.map(|(hash, flag)| {
+					json!({
+						"hash": hash,
+						"flag": format!("{:02X}", encode_reference_flag(flag))
+					})
+				})
+				.collect()
+		)
+	);
+
+	meta_object.insert(
+		"hash_reference_table_size".into(),
+		if dependencies.is_empty() {
+			0
+		} else {
+			4 + dependencies.len() * 9
+		}
+		.into()
+	);
+
+	fs::write(path, format_json(&to_string(&meta)?)?)?;
+}
@@ -0,0 +1,137 @@
+use anyhow::Result;
+use arc_swap::ArcSwap;
+use fn_error_context::context;
+use hitman_commons::game::GameVersion;
+use itertools::Itertools;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::{
+	get_loaded_game_version,
+	model::{AppSettings, AppState},
+	resourcelib::{get_supported_resource_types, has_generator}
+};
+
+/// What GlacierKit can do with a resource type in a particular game version.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct ResourceTypeCapabilities {
+	pub resource_type: String,
+
+	/// Whether the resource overview can show the resource's contents
+	pub preview: bool,
+
+	/// Whether the resource can be extracted in a more useful format than its binary data
+	pub convert: bool,
+
+	/// Whether the resource can be rebuilt from a converted or edited form
+	pub rebuild: bool
+}
+
+/// Types which the resource overview previews by converting them with ResourceLib.
+const RESOURCELIB_PREVIEWS: &[&str] = &[
+	"TEMP", "TBLU", "AIBB", "GFXF", "AIRG", "ATMD", "CPPT", "VIDB", "CBLU", "CRMD", "WSWB", "DSWB", "GIDX", "WSGB",
+	"ECPB", "UICB", "ENUM"
+];
+
+/// Types which are handled by GlacierKit's own parsers and the format crates rather than ResourceLib, as resource
+/// type, preview, convert and rebuild.
+const FORMAT_CAPABILITIES: &[(&str, bool, bool, bool)] = &[
+	("GFXI", true, true, true),
+	("TEXT", true, true, false),
+	("PRIM", true, true, false),
+	("ALOC", true, false, false),
+	("BORG", true, true, false),
+	("MRTN", true, true, false),
+	("MRTR", true, true, false),
+	("MJBA", true, true, false),
+	("FXAS", true, true, false),
+	("WWEV", true, true, false),
+	("WWES", true, true, false),
+	("WWEM", true, true, false),
+	("ORES", true, true, false),
+	("REPO", true, false, false),
+	("ASET", true, false, false),
+	("JSON", true, false, false),
+	("CLNG", true, true, false),
+	("DITL", true, true, false),
+	("DLGE", true, true, true),
+	("LOCR", true, true, false),
+	("RTLV", true, true, false),
+	("LINE", true, false, false),
+	("MATI", true, false, false),
+	("MATT", true, false, true),
+	("SDEF", true, false, false)
+];
+
+/// Get what GlacierKit can do with every resource type it supports beyond extracting binary data, for the given game
+/// version. Types which aren't listed can only be extracted as they are.
+#[try_fn]
+#[context("Couldn't get resource capabilities for {:?}", game_version)]
+pub fn get_resource_capabilities(game_version: GameVersion) -> Result<Vec<ResourceTypeCapabilities>> {
+	let mut capabilities = FORMAT_CAPABILITIES
+		.iter()
+		.map(|&(resource_type, preview, convert, rebuild)| ResourceTypeCapabilities {
+			resource_type: resource_type.into(),
+			preview,
+			convert,
+			rebuild
+		})
+		.collect_vec();
+
+	for resource_type in get_supported_resource_types(game_version)? {
+		if capabilities.iter().any(|x| x.resource_type == resource_type) {
+			continue;
+		}
+
+		capabilities.push(ResourceTypeCapabilities {
+			preview: RESOURCELIB_PREVIEWS.contains(&resource_type.as_str()),
+			convert: true,
+			rebuild: has_generator(game_version, &resource_type)?,
+			resource_type
+		});
+	}
+
+	capabilities.sort_by(|a, b| a.resource_type.cmp(&b.resource_type));
+
+	capabilities
+}
+
+/// Get what GlacierKit can do with a specific resource type in the given game version.
+#[try_fn]
+#[context("Couldn't get capabilities of resource type {}", resource_type)]
+pub fn get_resource_type_capabilities(
+	game_version: GameVersion,
+	resource_type: &str
+) -> Result<ResourceTypeCapabilities> {
+	get_resource_capabilities(game_version)?
+		.into_iter()
+		.find(|x| x.resource_type == resource_type)
+		.unwrap_or_else(|| ResourceTypeCapabilities {
+			resource_type: resource_type.into(),
+			preview: false,
+			convert: false,
+			rebuild: false
+		})
+}
+
+/// Get what GlacierKit can do with each resource type in the loaded game version, or nothing if no game is loaded.
+#[tauri::command]
+#[specta::specta]
+pub fn get_loaded_resource_capabilities(app: AppHandle) -> Option<Vec<ResourceTypeCapabilities>> {
+	app.state::<AppState>().game_files.load().as_ref()?;
+
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+	let install = app_settings.load().game_install.clone()?;
+
+	match get_loaded_game_version(&app, &install).and_then(get_resource_capabilities) {
+		Ok(capabilities) => Some(capabilities),
+
+		Err(e) => {
+			warn!("{:?}", e);
+			None
+		}
+	}
+}
@@ -1,26 +1,73 @@
-use std::ffi::{CStr, CString};
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::LazyLock;
+use std::thread::available_parallelism;
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, anyhow, bail};
 use fn_error_context::context;
 use hitman_commons::game::GameVersion;
 use hitman_commons::metadata::ResourceType;
 use hitman_commons::resourcelib::{
 	EntityBlueprint, EntityBlueprintLegacy, EntityFactory, EntityFactoryLegacy, Property
 };
+use rayon::{ThreadPool, ThreadPoolBuilder};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
 use tryvial::try_fn;
 
 mod bindings_2;
 mod bindings_2016;
 mod bindings_3;
 
-use self::bindings_2::{HM2_GetConverterForResource, HM2_GetGeneratorForResource, JsonString as JsonString2};
-use self::bindings_3::{HM3_GetConverterForResource, HM3_GetGeneratorForResource, JsonString as JsonString3};
+use self::bindings_2::{
+	HM2_FreeSupportedResourceTypes, HM2_GetConverterForResource, HM2_GetGeneratorForResource,
+	HM2_GetSupportedResourceTypes, JsonString as JsonString2
+};
+use self::bindings_3::{
+	HM3_FreeSupportedResourceTypes, HM3_GetConverterForResource, HM3_GetGeneratorForResource,
+	HM3_GetSupportedResourceTypes, JsonString as JsonString3
+};
 use self::bindings_2016::{
-	HM2016_GetConverterForResource, HM2016_GetGeneratorForResource, JsonString as JsonString2016
+	HM2016_FreeSupportedResourceTypes, HM2016_GetConverterForResource, HM2016_GetGeneratorForResource,
+	HM2016_GetSupportedResourceTypes, JsonString as JsonString2016
 };
 
+/// Conversions of large resources can take seconds, so they run on their own threads rather than holding up those which
+/// handle events.
+static CONVERSION_POOL: LazyLock<ThreadPool> = LazyLock::new(|| {
+	ThreadPoolBuilder::new()
+		.num_threads(available_parallelism().map(|x| x.get() / 2).unwrap_or(1).clamp(1, 4))
+		.thread_name(|index| format!("resourcelib-{index}"))
+		.build()
+		.expect("Couldn't build ResourceLib conversion pool")
+});
+
+/// Run a conversion job on the ResourceLib conversion pool, waiting for its result without blocking the async runtime.
+#[try_fn]
+#[context("Couldn't run ResourceLib conversion job")]
+pub async fn convert_async<T: Send + 'static>(job: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+	let (tx, rx) = oneshot::channel();
+
+	CONVERSION_POOL.spawn(move || {
+		let _ =
+			tx.send(catch_unwind(AssertUnwindSafe(job)).unwrap_or_else(|_| Err(anyhow!("Conversion job panicked"))));
+	});
+
+	rx.await.context("Conversion job was dropped")??
+}
+
+/// Convert binary data with ResourceLib on the conversion pool.
+#[try_fn]
+#[context("Couldn't convert binary data to ResourceLib format")]
+pub async fn convert_generic_async<T: DeserializeOwned + Send + 'static>(
+	data: Vec<u8>,
+	game: GameVersion,
+	resource_type: ResourceType
+) -> Result<T> {
+	convert_async(move || convert_generic::<T>(&data, game, resource_type)).await?
+}
+
 #[try_fn]
 #[context("Couldn't convert binary data to ResourceLib TEMP")]
 pub fn h3_convert_binary_to_factory(data: &[u8]) -> Result<EntityFactory> {
@@ -907,6 +954,15 @@ pub fn h2016_convert_wsgb(data: &[u8]) -> Result<SwitchGroup> {
 	}
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SEnumType {
+	#[serde(rename = "Name")]
+	pub name: String,
+
+	#[serde(rename = "Items")]
+	pub items: Vec<String>
+}
+
 #[try_fn]
 #[context("Couldn't convert binary data to ResourceLib format")]
 pub fn convert_generic<T: DeserializeOwned>(data: &[u8], game: GameVersion, resource_type: ResourceType) -> Result<T> {
@@ -1090,3 +1146,180 @@ pub fn convert_generic_str(data: &[u8], game: GameVersion, resource_type: Resour
 		}
 	}
 }
+
+#[try_fn]
+#[context("Couldn't convert ResourceLib format to binary data")]
+pub fn convert_generic_to_binary<T: Serialize>(
+	data: &T,
+	game: GameVersion,
+	resource_type: ResourceType
+) -> Result<Vec<u8>> {
+	unsafe {
+		match game {
+			GameVersion::H1 => {
+				let generator = HM2016_GetGeneratorForResource(CString::new(resource_type)?.as_ptr());
+
+				if generator.is_null() {
+					bail!("Couldn't get ResourceLib generator")
+				}
+
+				let json_string = CString::new(serde_json::to_string(data)?)?;
+				let json_string = JsonString2016 {
+					JsonData: json_string.as_ptr(),
+					StrSize: json_string.as_bytes().len()
+				};
+
+				let resource_mem =
+					(*generator).FromJsonStringToResourceMem.unwrap()(json_string.JsonData, json_string.StrSize, false);
+
+				if resource_mem.is_null() {
+					bail!("Couldn't convert data to ResourceMem")
+				}
+
+				let res = std::slice::from_raw_parts((*resource_mem).ResourceData.cast(), (*resource_mem).DataSize)
+					.to_owned();
+
+				(*generator).FreeResourceMem.unwrap()(resource_mem);
+
+				res
+			}
+
+			GameVersion::H2 => {
+				let generator = HM2_GetGeneratorForResource(CString::new(resource_type)?.as_ptr());
+
+				if generator.is_null() {
+					bail!("Couldn't get ResourceLib generator")
+				}
+
+				let json_string = CString::new(serde_json::to_string(data)?)?;
+				let json_string = JsonString2 {
+					JsonData: json_string.as_ptr(),
+					StrSize: json_string.as_bytes().len()
+				};
+
+				let resource_mem =
+					(*generator).FromJsonStringToResourceMem.unwrap()(json_string.JsonData, json_string.StrSize, false);
+
+				if resource_mem.is_null() {
+					bail!("Couldn't convert data to ResourceMem")
+				}
+
+				let res = std::slice::from_raw_parts((*resource_mem).ResourceData.cast(), (*resource_mem).DataSize)
+					.to_owned();
+
+				(*generator).FreeResourceMem.unwrap()(resource_mem);
+
+				res
+			}
+
+			GameVersion::H3 => {
+				let generator = HM3_GetGeneratorForResource(CString::new(resource_type)?.as_ptr());
+
+				if generator.is_null() {
+					bail!("Couldn't get ResourceLib generator")
+				}
+
+				let json_string = CString::new(serde_json::to_string(data)?)?;
+				let json_string = JsonString3 {
+					JsonData: json_string.as_ptr(),
+					StrSize: json_string.as_bytes().len()
+				};
+
+				let resource_mem =
+					(*generator).FromJsonStringToResourceMem.unwrap()(json_string.JsonData, json_string.StrSize, false);
+
+				if resource_mem.is_null() {
+					bail!("Couldn't convert data to ResourceMem")
+				}
+
+				let res = std::slice::from_raw_parts((*resource_mem).ResourceData.cast(), (*resource_mem).DataSize)
+					.to_owned();
+
+				(*generator).FreeResourceMem.unwrap()(resource_mem);
+
+				res
+			}
+		}
+	}
+}
+
+/// Read the types out of an array returned by ResourceLib's GetSupportedResourceTypes.
+unsafe fn read_resource_types(types: *mut *const c_char, count: usize) -> Result<Vec<String>> {
+	unsafe {
+		std::slice::from_raw_parts(types, count)
+			.iter()
+			.map(|x| {
+				Ok(CStr::from_ptr(*x)
+					.to_str()
+					.context("Couldn't convert CStr to str")?
+					.to_owned())
+			})
+			.collect()
+	}
+}
+
+/// The resource types which ResourceLib can convert for the given game version.
+#[try_fn]
+#[context("Couldn't get ResourceLib supported resource types for {:?}", game)]
+pub fn get_supported_resource_types(game: GameVersion) -> Result<Vec<String>> {
+	unsafe {
+		match game {
+			GameVersion::H1 => {
+				let types = HM2016_GetSupportedResourceTypes();
+
+				if types.is_null() {
+					bail!("Couldn't get ResourceLib supported resource types")
+				}
+
+				let res = read_resource_types((*types).Types, (*types).TypeCount);
+
+				HM2016_FreeSupportedResourceTypes(types);
+
+				res?
+			}
+
+			GameVersion::H2 => {
+				let types = HM2_GetSupportedResourceTypes();
+
+				if types.is_null() {
+					bail!("Couldn't get ResourceLib supported resource types")
+				}
+
+				let res = read_resource_types((*types).Types, (*types).TypeCount);
+
+				HM2_FreeSupportedResourceTypes(types);
+
+				res?
+			}
+
+			GameVersion::H3 => {
+				let types = HM3_GetSupportedResourceTypes();
+
+				if types.is_null() {
+					bail!("Couldn't get ResourceLib supported resource types")
+				}
+
+				let res = read_resource_types((*types).Types, (*types).TypeCount);
+
+				HM3_FreeSupportedResourceTypes(types);
+
+				res?
+			}
+		}
+	}
+}
+
+/// Whether ResourceLib can generate binary data of the given resource type for the given game version.
+#[try_fn]
+#[context("Couldn't check for ResourceLib generator")]
+pub fn has_generator(game: GameVersion, resource_type: &str) -> Result<bool> {
+	let resource_type = CString::new(resource_type)?;
+
+	unsafe {
+		match game {
+			GameVersion::H1 => !HM2016_GetGeneratorForResource(resource_type.as_ptr()).is_null(),
+			GameVersion::H2 => !HM2_GetGeneratorForResource(resource_type.as_ptr()).is_null(),
+			GameVersion::H3 => !HM3_GetGeneratorForResource(resource_type.as_ptr()).is_null()
+		}
+	}
+}
@@ -0,0 +1,261 @@
+use std::{
+	future::Future,
+	panic::{AssertUnwindSafe, catch_unwind},
+	pin::Pin,
+	sync::{
+		Arc, Mutex, MutexGuard,
+		atomic::{AtomicBool, Ordering},
+		mpsc::{Receiver, Sender, channel}
+	},
+	thread
+};
+
+use anyhow::{Result, anyhow, bail};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, async_runtime};
+use uuid::Uuid;
+
+use crate::{
+	Notification, NotificationKind,
+	model::{ExtractionQueueRequest, Request, ToolRequest},
+	send_notification, send_request
+};
+
+type ExtractionJob = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "type", content = "data")]
+pub enum ExtractionStatus {
+	Queued,
+	Running { completed: u32, total: u32 },
+	Finished,
+	Failed(String),
+	Cancelled
+}
+
+impl ExtractionStatus {
+	pub fn is_done(&self) -> bool {
+		matches!(
+			self,
+			ExtractionStatus::Finished | ExtractionStatus::Failed(_) | ExtractionStatus::Cancelled
+		)
+	}
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionItem {
+	pub id: Uuid,
+	pub name: String,
+	pub status: ExtractionStatus
+}
+
+struct QueuedExtraction {
+	id: Uuid,
+	job: ExtractionJob
+}
+
+struct QueueState {
+	app: AppHandle,
+
+	/// Items in the order they were queued, along with their cancellation flags
+	items: Mutex<IndexMap<Uuid, (ExtractionItem, Arc<AtomicBool>)>>
+}
+
+impl QueueState {
+	fn lock_items(&self) -> Result<MutexGuard<'_, IndexMap<Uuid, (ExtractionItem, Arc<AtomicBool>)>>> {
+		self.items.lock().map_err(|_| anyhow!("Extraction queue lock poisoned"))
+	}
+
+	fn set_status(&self, id: Uuid, status: ExtractionStatus) -> Result<()> {
+		let item = {
+			let mut items = self.lock_items()?;
+
+			let Some((item, _)) = items.get_mut(&id) else {
+				return Ok(());
+			};
+
+			item.status = status;
+			item.to_owned()
+		};
+
+		send_request(
+			&self.app,
+			Request::Tool(ToolRequest::ExtractionQueue(ExtractionQueueRequest::UpdateItem(item)))
+		)
+	}
+
+	fn is_cancelled(&self, id: Uuid) -> bool {
+		self.lock_items()
+			.ok()
+			.and_then(|x| x.get(&id).map(|(_, cancelled)| cancelled.load(Ordering::SeqCst)))
+			.unwrap_or(false)
+	}
+}
+
+/// A handle given to a running extraction, used to report progress and check for cancellation.
+#[derive(Clone)]
+pub struct ExtractionProgress {
+	id: Uuid,
+	cancelled: Arc<AtomicBool>,
+	state: Arc<QueueState>
+}
+
+impl ExtractionProgress {
+	pub fn set_progress(&self, completed: u32, total: u32) -> Result<()> {
+		self.state
+			.set_status(self.id, ExtractionStatus::Running { completed, total })
+	}
+
+	/// Returns an error if the extraction has been cancelled; call this between steps of long-running extractions.
+	pub fn check_cancelled(&self) -> Result<()> {
+		if self.cancelled.load(Ordering::SeqCst) {
+			bail!("Extraction cancelled");
+		}
+
+		Ok(())
+	}
+}
+
+/// Runs extractions on a pool of worker threads so that large extracts don't block event handling or each other.
+pub struct ExtractionQueue {
+	state: Arc<QueueState>,
+	sender: Sender<QueuedExtraction>
+}
+
+impl ExtractionQueue {
+	pub fn new(app: AppHandle) -> Self {
+		let state = Arc::new(QueueState {
+			app,
+			items: Mutex::new(IndexMap::new())
+		});
+
+		let (sender, receiver) = channel::<QueuedExtraction>();
+		let receiver = Arc::new(Mutex::new(receiver));
+
+		let workers = thread::available_parallelism().map(|x| x.get() / 2).unwrap_or(2).max(2);
+
+		for _ in 0..workers {
+			let state = state.clone();
+			let receiver = receiver.clone();
+
+			thread::spawn(move || run_worker(state, receiver));
+		}
+
+		Self { state, sender }
+	}
+
+	/// Add an extraction to the queue. The job is given an [`ExtractionProgress`] to report its progress with.
+	pub fn enqueue<F, Fut>(&self, name: impl Into<String>, job: F) -> Result<Uuid>
+	where
+		F: FnOnce(ExtractionProgress) -> Fut,
+		Fut: Future<Output = Result<()>> + Send + 'static
+	{
+		let id = Uuid::new_v4();
+		let cancelled = Arc::new(AtomicBool::new(false));
+
+		let item = ExtractionItem {
+			id,
+			name: name.into(),
+			status: ExtractionStatus::Queued
+		};
+
+		self.state
+			.lock_items()?
+			.insert(id, (item.to_owned(), cancelled.to_owned()));
+
+		send_request(
+			&self.state.app,
+			Request::Tool(ToolRequest::ExtractionQueue(ExtractionQueueRequest::UpdateItem(item)))
+		)?;
+
+		let job = job(ExtractionProgress {
+			id,
+			cancelled,
+			state: self.state.to_owned()
+		});
+
+		self.sender
+			.send(QueuedExtraction { id, job: Box::pin(job) })
+			.map_err(|_| anyhow!("Extraction workers have stopped"))?;
+
+		Ok(id)
+	}
+
+	/// Cancel an extraction. Queued extractions are skipped; running extractions stop at their next checkpoint.
+	pub fn cancel(&self, id: Uuid) -> Result<()> {
+		let was_queued = {
+			let items = self.state.lock_items()?;
+
+			let Some((item, cancelled)) = items.get(&id) else {
+				return Ok(());
+			};
+
+			cancelled.store(true, Ordering::SeqCst);
+			item.status == ExtractionStatus::Queued
+		};
+
+		if was_queued {
+			self.state.set_status(id, ExtractionStatus::Cancelled)?;
+		}
+
+		Ok(())
+	}
+
+	/// Remove all finished, failed and cancelled extractions from the queue.
+	pub fn clear_finished(&self) -> Result<()> {
+		self.state.lock_items()?.retain(|_, (item, _)| !item.status.is_done());
+
+		Ok(())
+	}
+
+	pub fn items(&self) -> Result<Vec<ExtractionItem>> {
+		Ok(self
+			.state
+			.lock_items()?
+			.values()
+			.map(|(item, _)| item.to_owned())
+			.collect())
+	}
+}
+
+fn run_worker(state: Arc<QueueState>, receiver: Arc<Mutex<Receiver<QueuedExtraction>>>) {
+	loop {
+		let received = receiver.lock().expect("Extraction receiver lock poisoned").recv();
+
+		let Ok(QueuedExtraction { id, job }) = received else {
+			break;
+		};
+
+		if state.is_cancelled(id) {
+			continue;
+		}
+
+		let _ = state.set_status(id, ExtractionStatus::Running { completed: 0, total: 1 });
+
+		let result = catch_unwind(AssertUnwindSafe(|| async_runtime::block_on(job)))
+			.unwrap_or_else(|_| Err(anyhow!("Extraction panicked")));
+
+		let status = match result {
+			Ok(()) => ExtractionStatus::Finished,
+			Err(_) if state.is_cancelled(id) => ExtractionStatus::Cancelled,
+			Err(e) => {
+				let _ = send_notification(
+					&state.app,
+					Notification {
+						kind: NotificationKind::Error,
+						title: "Extraction failed".into(),
+						subtitle: format!("{e:#}"),
+						link: None
+					}
+				);
+
+				ExtractionStatus::Failed(format!("{e:?}"))
+			}
+		};
+
+		let _ = state.set_status(id, status);
+	}
+}
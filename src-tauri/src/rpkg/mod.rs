@@ -0,0 +1,446 @@
+use anyhow::{Context, Result, anyhow, bail};
+use dashmap::mapref::one::Ref;
+use fn_error_context::context;
+use hitman_commons::{
+	game::GameVersion,
+	hash_list::HashList,
+	metadata::{ExtendedResourceMetadata, ResourceType, RuntimeID},
+	resourcelib::{EntityBlueprint, EntityFactory},
+	rpkg_tool::RpkgResourceMeta
+};
+use itertools::Itertools;
+use quickentity_rs::{convert_to_qn, qn_structs::Entity};
+use rpkg_rs::resource::{
+	partition_manager::PartitionManager,
+	resource_info::ResourceInfo,
+	resource_package::ResourceReferenceFlags,
+	resource_partition::{PatchId, ResourcePartition},
+	runtime_resource_id::RuntimeResourceID
+};
+use tryvial::try_fn;
+
+use crate::{
+	entity_cache::EntityCache,
+	model::{ResourceChangelogEntry, ResourceChangelogOperation},
+	resourcelib::{
+		convert_async, h2_convert_binary_to_blueprint, h2_convert_binary_to_factory, h3_convert_binary_to_blueprint,
+		h3_convert_binary_to_factory, h2016_convert_binary_to_blueprint, h2016_convert_binary_to_factory
+	}
+};
+
+pub mod extraction_queue;
+
+/// Partitions which make up the base game, rather than containing DLC or other separately purchased content.
+const BASE_GAME_PARTITIONS: [&str; 2] = ["super", "base"];
+
+/// Get the DLC a partition belongs to, or None if it's part of the base game.
+pub fn get_partition_dlc(partition: &ResourcePartition) -> Option<String> {
+	let info = partition.partition_info();
+
+	match &info.name {
+		Some(name) if BASE_GAME_PARTITIONS.contains(&name.as_str()) => None,
+		Some(name) => Some(name.to_owned()),
+
+		// Older games don't name their partitions, but DLC partitions are still identified by their type
+		None => {
+			let id = info.id.to_string();
+			id.starts_with("dlc").then_some(id)
+		}
+	}
+}
+
+/// Get the DLC the partition containing a resource belongs to, or None if it's part of the base game.
+pub fn get_resource_dlc(game_files: &PartitionManager, resource: impl Into<RuntimeID>) -> Option<String> {
+	let resource_id = RuntimeResourceID::from(resource.into());
+
+	game_files
+		.partitions
+		.iter()
+		.find(|x| x.contains(&resource_id))
+		.and_then(get_partition_dlc)
+}
+
+/// Extract the latest copy of a resource.
+pub fn extract_latest_resource(
+	game_files: &PartitionManager,
+	resource: impl Into<RuntimeID>
+) -> Result<(ExtendedResourceMetadata, Vec<u8>)> {
+	let runtime_id: RuntimeID = resource.into();
+
+	let resource_id = RuntimeResourceID::from(runtime_id);
+	for partition in &game_files.partitions {
+		if let Some((info, _)) = partition
+			.latest_resources()
+			.into_iter()
+			.find(|(x, _)| *x.rrid() == resource_id)
+		{
+			return Ok((
+				info.try_into()
+					.with_context(|| format!("Couldn't extract resource {runtime_id}"))?,
+				partition
+					.read_resource(&resource_id)
+					.with_context(|| format!("Couldn't extract {runtime_id} using rpkg-rs"))?
+			));
+		}
+	}
+
+	bail!("Couldn't find {runtime_id} in any partition when extracting resource");
+}
+
+/// Get the metadata of the latest copy of a resource. Faster than fully extracting the resource.
+pub fn extract_latest_metadata(
+	game_files: &PartitionManager,
+	resource: impl Into<RuntimeID>
+) -> Result<ExtendedResourceMetadata> {
+	let resource_id = RuntimeResourceID::from(resource.into());
+
+	for partition in &game_files.partitions {
+		if let Some((info, _)) = partition
+			.latest_resources()
+			.into_iter()
+			.find(|(x, _)| *x.rrid() == resource_id)
+		{
+			return info
+				.try_into()
+				.with_context(|| format!("Couldn't extract metadata for resource {resource_id}"));
+		}
+	}
+
+	bail!("Couldn't find {resource_id} in any partition when extracting metadata");
+}
+
+/// Get miscellaneous information (filetype, chunk and patch, dependencies with hash and flag) for the latest copy of a resource.
+pub fn extract_latest_overview_info(
+	game_files: &PartitionManager,
+	resource: impl Into<RuntimeID>
+) -> Result<(ResourceType, String, Vec<(RuntimeID, String)>)> {
+	let resource_id = RuntimeResourceID::from(resource.into());
+
+	for partition in &game_files.partitions {
+		if let Some((info, patchlevel)) = partition
+			.latest_resources()
+			.into_iter()
+			.find(|(x, _)| *x.rrid() == resource_id)
+		{
+			let package_name = match patchlevel {
+				PatchId::Base => partition.partition_info().id.to_string(),
+				PatchId::Patch(level) => format!("{}patch{}", partition.partition_info().id, level)
+			};
+
+			return Ok((
+				info.data_type()
+					.try_into()
+					.with_context(|| format!("Couldn't extract overview info for resource {resource_id}"))?,
+				match &partition.partition_info().name {
+					Some(name) => format!("{} ({})", name, package_name),
+					None => package_name
+				},
+				info.references()
+					.iter()
+					.map(|(res_id, flag)| {
+						Ok((
+							(*res_id).try_into()?,
+							format!(
+								"{:02X}",
+								match flag {
+									ResourceReferenceFlags::Legacy(x) => x.into_bits(),
+									ResourceReferenceFlags::Standard(x) => x.into_bits()
+								}
+							)
+						))
+					})
+					.collect::<Result<_>>()
+					.with_context(|| format!("Couldn't extract overview info for resource {resource_id}"))?
+			));
+		}
+	}
+
+	bail!("Couldn't find {resource_id} in any RPKG when extracting overview info");
+}
+
+/// Convert a TEMP to a ResourceLib factory, in the modern format whatever the game version.
+#[try_fn]
+#[context("Couldn't convert binary data to ResourceLib factory")]
+pub fn convert_factory(data: &[u8], game_version: GameVersion) -> Result<EntityFactory> {
+	match game_version {
+		GameVersion::H1 => h2016_convert_binary_to_factory(data)?.into_modern(),
+		GameVersion::H2 => h2_convert_binary_to_factory(data)?,
+		GameVersion::H3 => h3_convert_binary_to_factory(data)?
+	}
+}
+
+/// Convert a TBLU to a ResourceLib blueprint, in the modern format whatever the game version.
+#[try_fn]
+#[context("Couldn't convert binary data to ResourceLib blueprint")]
+pub fn convert_blueprint(data: &[u8], game_version: GameVersion) -> Result<EntityBlueprint> {
+	match game_version {
+		GameVersion::H1 => h2016_convert_binary_to_blueprint(data)?.into_modern(),
+		GameVersion::H2 => h2_convert_binary_to_blueprint(data)?,
+		GameVersion::H3 => h3_convert_binary_to_blueprint(data)?
+	}
+}
+
+/// Extract an entity by its factory and put it in the cache. Returns early if the entity is already cached.
+///
+/// This converts the entity on the calling thread; anything which can await should use `extract_entity_async`.
+#[try_fn]
+pub fn extract_entity<'a>(
+	resource_packages: &PartitionManager,
+	cached_entities: &'a EntityCache,
+	game_version: GameVersion,
+	hash_list: &HashList,
+	factory_id: impl Into<RuntimeID>
+) -> Result<Ref<'a, RuntimeID, Entity>> {
+	let runtime_id = factory_id.into();
+
+	{
+		if let Some(x) = cached_entities.get(&runtime_id) {
+			return Ok(x);
+		}
+	}
+
+	let x: Result<_> = try {
+		let (temp_meta, temp_data) =
+			extract_latest_resource(resource_packages, runtime_id).context("Couldn't extract TEMP")?;
+
+		if temp_meta.core_info.resource_type != "TEMP" {
+			bail!("Given factory was not a TEMP");
+		}
+
+		let factory = convert_factory(&temp_data, game_version)?;
+
+		let blueprint_id = &temp_meta
+			.core_info
+			.references
+			.get(factory.blueprint_index_in_resource_header as usize)
+			.context("Blueprint referenced in factory does not exist in dependencies")?
+			.resource;
+
+		let (tblu_meta, tblu_data) =
+			extract_latest_resource(resource_packages, blueprint_id.get_id()).context("Couldn't extract TBLU")?;
+
+		let blueprint = convert_blueprint(&tblu_data, game_version)?;
+
+		let entity = convert_to_qn(
+			&factory,
+			&RpkgResourceMeta::from_resource_metadata(temp_meta, false).with_hash_list(&hash_list.entries)?,
+			&blueprint,
+			&RpkgResourceMeta::from_resource_metadata(tblu_meta, false).with_hash_list(&hash_list.entries)?,
+			false
+		)
+		.map_err(|x| anyhow!("QuickEntity error: {:?}", x))?;
+
+		cached_entities.insert(runtime_id, entity, temp_data.len() + tblu_data.len());
+	};
+
+	x.with_context(|| format!("Couldn't extract and cache entity {runtime_id}"))?;
+
+	cached_entities
+		.get(&runtime_id)
+		.context("Entity was evicted from the cache as soon as it was added")?
+}
+
+/// Extract an entity by its factory and put it in the cache, converting it on the ResourceLib conversion pool so that
+/// the event isn't held up. Returns early if the entity is already cached.
+#[try_fn]
+pub async fn extract_entity_async<'a>(
+	resource_packages: &PartitionManager,
+	cached_entities: &'a EntityCache,
+	game_version: GameVersion,
+	hash_list: &HashList,
+	factory_id: impl Into<RuntimeID>
+) -> Result<Ref<'a, RuntimeID, Entity>> {
+	let runtime_id = factory_id.into();
+
+	{
+		if let Some(x) = cached_entities.get(&runtime_id) {
+			return Ok(x);
+		}
+	}
+
+	let x: Result<_> = try {
+		let (temp_meta, temp_data) =
+			extract_latest_resource(resource_packages, runtime_id).context("Couldn't extract TEMP")?;
+
+		if temp_meta.core_info.resource_type != "TEMP" {
+			bail!("Given factory was not a TEMP");
+		}
+
+		let temp_size = temp_data.len();
+		let factory = convert_async(move || convert_factory(&temp_data, game_version)).await?;
+
+		let blueprint_id = &temp_meta
+			.core_info
+			.references
+			.get(factory.blueprint_index_in_resource_header as usize)
+			.context("Blueprint referenced in factory does not exist in dependencies")?
+			.resource;
+
+		let (tblu_meta, tblu_data) =
+			extract_latest_resource(resource_packages, blueprint_id.get_id()).context("Couldn't extract TBLU")?;
+
+		let tblu_size = tblu_data.len();
+
+		let temp_meta =
+			RpkgResourceMeta::from_resource_metadata(temp_meta, false).with_hash_list(&hash_list.entries)?;
+		let tblu_meta =
+			RpkgResourceMeta::from_resource_metadata(tblu_meta, false).with_hash_list(&hash_list.entries)?;
+
+		let entity = convert_async(move || {
+			let blueprint = convert_blueprint(&tblu_data, game_version)?;
+
+			convert_to_qn(&factory, &temp_meta, &blueprint, &tblu_meta, false)
+				.map_err(|x| anyhow!("QuickEntity error: {:?}", x))
+		})
+		.await?;
+
+		cached_entities.insert(runtime_id, entity, temp_size + tblu_size);
+	};
+
+	x.with_context(|| format!("Couldn't extract and cache entity {runtime_id}"))?;
+
+	cached_entities
+		.get(&runtime_id)
+		.context("Entity was evicted from the cache as soon as it was added")?
+}
+
+/// Get the events in the history of a resource within each partition, sorted by patch level and then by operation.
+fn get_changelog_events<'a>(
+	game_files: &'a PartitionManager,
+	resource_id: &RuntimeResourceID
+) -> Vec<(ResourceChangelogOperation, &'a ResourcePartition, PatchId, String)> {
+	let mut events = vec![];
+
+	for partition in &game_files.partitions {
+		let mut last_occurence: Option<&ResourceInfo> = None;
+
+		let changes = partition.resource_patch_indices(resource_id);
+		let deletions = partition.resource_removal_indices(resource_id);
+
+		let occurrences = changes
+			.clone()
+			.into_iter()
+			.chain(deletions.clone().into_iter())
+			.collect::<Vec<PatchId>>();
+
+		for occurence in occurrences.iter().sorted() {
+			let op_desc = match occurence {
+				x if deletions.contains(x) => Some((
+					ResourceChangelogOperation::Delete,
+					"Removed resource from partition".into()
+				)),
+
+				x if changes.contains(x) => match partition.resource_info_from(resource_id, *x) {
+					Ok(info) => {
+						let op_desc = match last_occurence {
+							Some(last_info) => match info.size() as isize - last_info.size() as isize {
+								0 => (ResourceChangelogOperation::Edit, "Updated resource".into()),
+								size_diff => (
+									ResourceChangelogOperation::Edit,
+									format!("Updated resource: {:>+0.2} kB", size_diff as f32 / 1024.0)
+								)
+							},
+							None => (ResourceChangelogOperation::Init, "Added resource to partition".into())
+						};
+
+						last_occurence = Some(info);
+
+						Some(op_desc)
+					}
+
+					Err(_) => None
+				},
+
+				_ => None
+			};
+
+			if let Some((operation, description)) = op_desc {
+				events.push((operation, partition, *occurence, description));
+			}
+		}
+	}
+
+	events
+		.into_iter()
+		.sorted_by(|(op1, _, patch1, _), (op2, _, patch2, _)| patch1.cmp(patch2).then(op1.cmp(op2)))
+		.collect()
+}
+
+/// Get the history of the file, a changelog of events within the partitions. Will return an empty vector if the resource is not found in any partition.
+pub fn extract_resource_changelog(
+	game_files: &PartitionManager,
+	resource: impl Into<RuntimeID>
+) -> Vec<ResourceChangelogEntry> {
+	let resource_id = RuntimeResourceID::from(resource.into());
+
+	get_changelog_events(game_files, &resource_id)
+		.into_iter()
+		.map(|(operation, partition, patch, description)| ResourceChangelogEntry {
+			operation,
+			partition: match &partition.partition_info().name {
+				Some(name) => format!("{} ({})", name, partition.partition_info().id),
+				None => partition.partition_info().id.to_string()
+			},
+			patch: match patch {
+				PatchId::Base => "Base".into(),
+				PatchId::Patch(n) => {
+					format!("Patch {}", n)
+				}
+			},
+			description
+		})
+		.collect::<Vec<_>>()
+}
+
+/// Extract the copy of a resource at one entry of its changelog, along with the copy it replaced in the same partition,
+/// so that the two can be compared. Either copy is None if the resource wasn't present at that point, like when the entry
+/// is the resource being added or removed. The index is that of the entry returned by `extract_resource_changelog`.
+#[try_fn]
+#[context("Couldn't extract versions of resource for changelog entry {}", index)]
+pub fn extract_changelog_versions(
+	game_files: &PartitionManager,
+	resource: impl Into<RuntimeID>,
+	index: usize
+) -> Result<(ResourceType, Option<Vec<u8>>, Option<Vec<u8>>)> {
+	let resource_id = RuntimeResourceID::from(resource.into());
+
+	let events = get_changelog_events(game_files, &resource_id);
+
+	let (operation, partition, patch, _) = events.get(index).context("No such changelog entry")?;
+
+	let previous = events[..index]
+		.iter()
+		.rev()
+		.find(|(_, x, _, _)| x.partition_info().id == partition.partition_info().id);
+
+	let read_version = |operation: &ResourceChangelogOperation, patch: PatchId| -> Result<Option<Vec<u8>>> {
+		if *operation == ResourceChangelogOperation::Delete {
+			Ok(None)
+		} else {
+			Ok(Some(partition.read_resource_from(&resource_id, patch).with_context(
+				|| format!("Couldn't extract {resource_id} using rpkg-rs")
+			)?))
+		}
+	};
+
+	let resource_type: ResourceType = events[..=index]
+		.iter()
+		.rev()
+		.filter(|(operation, x, _, _)| {
+			*operation != ResourceChangelogOperation::Delete && x.partition_info().id == partition.partition_info().id
+		})
+		.find_map(|(_, x, patch, _)| x.resource_info_from(&resource_id, *patch).ok())
+		.context("Resource has no copy in the partition")?
+		.data_type()
+		.try_into()
+		.with_context(|| format!("Couldn't get type of resource {resource_id}"))?;
+
+	(
+		resource_type,
+		match previous {
+			Some((operation, _, patch, _)) => read_version(operation, *patch)?,
+			None => None
+		},
+		read_version(operation, *patch)?
+	)
+}
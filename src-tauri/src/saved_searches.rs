@@ -0,0 +1,262 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use serde::{Deserialize, Serialize};
+use serde_json::to_vec;
+use specta::Type;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::{
+	Notification, NotificationKind,
+	backups::BACKUP_FOLDER,
+	event_handling::content_search::start_content_search,
+	model::{
+		AppState, ContentSearchRequest, EditorData, EditorRequest, EntityEditorRequest, EntityTreeRequest,
+		GlobalRequest, Request, ToolRequest
+	},
+	packaging::DIST_FOLDER,
+	refuse_if_read_only, send_notification, send_request,
+	split_view::get_tab_document
+};
+
+/// A named query saved to the project, so that it can be run again without re-entering it.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedSearch {
+	pub name: String,
+	pub query: SavedSearchQuery,
+
+	/// Whether to run the search again whenever files in the project change
+	pub rerun_on_change: bool
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase", tag = "type", content = "data")]
+pub enum SavedSearchQuery {
+	/// A search of the game files, with the same options as the content search tool
+	#[serde(rename_all = "camelCase")]
+	ContentSearch {
+		query: String,
+		filetypes: Vec<String>,
+		use_qn_format: bool,
+		partitions: Vec<String>,
+		convert_with_resourcelib: bool
+	},
+
+	/// A search of the entity tree of whichever entity editor is focused
+	EntityTree { query: String }
+}
+
+/// Send the saved searches of the current project, if any, to the frontend.
+#[try_fn]
+#[context("Couldn't send saved searches")]
+pub fn send_saved_searches(app: &AppHandle) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	send_request(
+		app,
+		Request::Tool(ToolRequest::ContentSearch(ContentSearchRequest::SetSavedSearches(
+			app_state
+				.project
+				.load()
+				.as_ref()
+				.map(|project| project.settings.load().saved_searches.to_owned())
+				.unwrap_or_default()
+		)))
+	)?;
+}
+
+/// Apply a change to the saved searches of the current project and save it to the project file.
+#[try_fn]
+#[context("Couldn't update saved searches")]
+fn update_saved_searches(app: &AppHandle, update: impl FnOnce(&mut Vec<SavedSearch>)) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	if refuse_if_read_only(app, "Saving searches")? {
+		return Ok(());
+	}
+
+	let project = app_state.project.load();
+	let project = project.as_ref().context("No project loaded")?;
+
+	let mut settings = (*project.settings.load_full()).to_owned();
+	update(&mut settings.saved_searches);
+	fs::write(project.path.join("project.json"), to_vec(&settings)?)?;
+	project.settings.store(settings.into());
+
+	send_saved_searches(app)?;
+}
+
+/// Save a search to the project, replacing any existing search with the same name.
+#[try_fn]
+#[context("Couldn't save search {}", search.name)]
+pub fn save_search(app: &AppHandle, search: SavedSearch) -> Result<()> {
+	if search.name.trim().is_empty() {
+		bail!("Saved searches must have a name");
+	}
+
+	update_saved_searches(app, |searches| {
+		if let Some(existing) = searches.iter_mut().find(|x| x.name == search.name) {
+			*existing = search;
+		} else {
+			searches.push(search);
+		}
+	})?;
+}
+
+#[try_fn]
+#[context("Couldn't delete saved search {}", name)]
+pub fn delete_saved_search(app: &AppHandle, name: &str) -> Result<()> {
+	update_saved_searches(app, |searches| searches.retain(|x| x.name != name))?;
+}
+
+#[try_fn]
+#[context("Couldn't run saved search {}", name)]
+pub fn run_saved_search(app: &AppHandle, name: &str) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let search = app_state
+		.project
+		.load()
+		.as_ref()
+		.and_then(|project| {
+			project
+				.settings
+				.load()
+				.saved_searches
+				.iter()
+				.find(|x| x.name == name)
+				.cloned()
+		})
+		.context("No such saved search")?;
+
+	run_search(app, search.query)?;
+}
+
+#[try_fn]
+fn run_search(app: &AppHandle, query: SavedSearchQuery) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	match query {
+		SavedSearchQuery::ContentSearch {
+			query,
+			filetypes,
+			use_qn_format,
+			partitions,
+			convert_with_resourcelib
+		} => {
+			start_content_search(
+				app,
+				query,
+				filetypes,
+				use_qn_format,
+				partitions,
+				convert_with_resourcelib
+			)?;
+		}
+
+		SavedSearchQuery::EntityTree { query } => {
+			// The tree runs the search itself once its search box is filled, so that it shows the query being run
+			let editor_id = app_state
+				.navigation_history
+				.load()
+				.current
+				.as_ref()
+				.map(|x| get_tab_document(&app_state, x.tab))
+				.filter(|x| {
+					app_state
+						.editor_states
+						.get(x)
+						.is_some_and(|x| matches!(x.data, EditorData::QNEntity { .. } | EditorData::QNPatch { .. }))
+				});
+
+			if let Some(editor_id) = editor_id {
+				send_request(
+					app,
+					Request::Editor(EditorRequest::Entity(EntityEditorRequest::Tree(
+						EntityTreeRequest::SetSearchQuery { editor_id, query }
+					)))
+				)?;
+			} else {
+				send_notification(
+					app,
+					Notification {
+						kind: NotificationKind::Info,
+						title: "No entity editor focused".into(),
+						subtitle: "Focus an entity editor to run entity tree searches in it.".into(),
+						link: None
+					}
+				)?;
+			}
+		}
+	}
+}
+
+/// Whether a change to a file should cause saved searches to run again; changes to the project file and to generated
+/// folders don't.
+fn is_relevant_change(project_path: &Path, path: &Path) -> bool {
+	match path.strip_prefix(project_path) {
+		Ok(relative) => {
+			!(relative == Path::new("project.json")
+				|| relative.starts_with(BACKUP_FOLDER)
+				|| relative.starts_with(DIST_FOLDER)
+				|| relative.starts_with(".git"))
+		}
+
+		Err(_) => false
+	}
+}
+
+/// Run the saved searches marked to run again on changes, if any of the changed files are relevant. The searches are run
+/// on another thread, as content searches can take a while.
+#[try_fn]
+#[context("Couldn't run saved searches after project change")]
+pub fn rerun_saved_searches_on_change<'a>(
+	app: &AppHandle,
+	project_path: &Path,
+	changed: impl IntoIterator<Item = &'a Path>
+) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	if !changed.into_iter().any(|path| is_relevant_change(project_path, path)) {
+		return Ok(());
+	}
+
+	let searches = app_state
+		.project
+		.load()
+		.as_ref()
+		.map(|project| {
+			project
+				.settings
+				.load()
+				.saved_searches
+				.iter()
+				.filter(|x| x.rerun_on_change)
+				.cloned()
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default();
+
+	if searches.is_empty() {
+		return Ok(());
+	}
+
+	let app = app.to_owned();
+
+	std::thread::spawn(move || {
+		for search in searches {
+			if let Err(e) = run_search(&app, search.query) {
+				send_request(
+					&app,
+					Request::Global(GlobalRequest::ErrorReport {
+						error: format!("{:?}", e.context(format!("Couldn't run saved search {}", search.name)))
+					})
+				)
+				.expect("Couldn't send error report to frontend");
+			}
+		}
+	});
+}
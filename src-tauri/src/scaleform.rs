@@ -0,0 +1,58 @@
+use anyhow::{Result, bail};
+use fn_error_context::context;
+use hitman_commons::game::GameVersion;
+use serde::{Deserialize, Serialize};
+use tryvial::try_fn;
+
+use crate::resourcelib::{convert_generic, convert_generic_to_binary};
+
+/// A Scaleform GFx resource (GFXF): a SWF movie, plus any files it loads at runtime such as its images.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScaleformResource {
+	#[serde(rename = "m_pSwfData")]
+	pub swf_data: Vec<u8>,
+
+	#[serde(rename = "m_pAdditionalFileNames")]
+	pub additional_file_names: Vec<String>,
+
+	#[serde(rename = "m_pAdditionalFileData")]
+	pub additional_file_data: Vec<Vec<u8>>
+}
+
+impl ScaleformResource {
+	/// The extension of the embedded movie; GFx-processed movies have their own signature and extension.
+	pub fn swf_extension(&self) -> &'static str {
+		match self.swf_data.get(0..3) {
+			Some(b"GFX" | b"CFX") => "gfx",
+			_ => "swf"
+		}
+	}
+
+	/// The additional files with their names, in order.
+	pub fn additional_files(&self) -> impl Iterator<Item = (&str, &[u8])> {
+		self.additional_file_names
+			.iter()
+			.map(|x| x.as_str())
+			.zip(self.additional_file_data.iter().map(|x| x.as_slice()))
+	}
+}
+
+#[try_fn]
+#[context("Couldn't parse GFXF")]
+pub fn parse_gfxf(data: &[u8], game_version: GameVersion) -> Result<ScaleformResource> {
+	convert_generic::<ScaleformResource>(data, game_version, "GFXF".try_into()?)?
+}
+
+/// Replace the movie of a GFXF, keeping its additional files, and convert it back to binary.
+#[try_fn]
+#[context("Couldn't repack GFXF")]
+pub fn repack_gfxf(original: &[u8], swf: Vec<u8>, game_version: GameVersion) -> Result<Vec<u8>> {
+	if !matches!(swf.get(0..3), Some(b"FWS" | b"CWS" | b"ZWS" | b"GFX" | b"CFX")) {
+		bail!("The file isn't a SWF or GFx movie");
+	}
+
+	let mut resource = parse_gfxf(original, game_version)?;
+	resource.swf_data = swf;
+
+	convert_generic_to_binary(&resource, game_version, "GFXF".try_into()?)?
+}
@@ -0,0 +1,257 @@
+use std::fmt::Write;
+
+use hashbrown::HashMap;
+use hitman_commons::{hash_list::HashList, metadata::RuntimeID};
+use quickentity_rs::qn_structs::{Entity, Property, SubEntity};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+
+use crate::entity::{get_factory_name, get_local_reference};
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutlineFormat {
+	Markdown,
+	Html
+}
+
+impl OutlineFormat {
+	pub fn extension(self) -> &'static str {
+		match self {
+			Self::Markdown => "md",
+			Self::Html => "html"
+		}
+	}
+}
+
+/// The most properties listed for each sub-entity, so that the outline stays readable.
+const MAX_LISTED_PROPERTIES: usize = 6;
+
+/// Property types whose values don't mean much when written out, like transforms and references.
+const UNLISTED_PROPERTY_TYPES: [&str; 4] = ["SMatrix43", "SEntityTemplateReference", "ZGuid", "ZRuntimeResourceID"];
+
+/// Get the properties worth listing for a sub-entity: simple values like booleans, numbers, strings and enums.
+fn get_notable_properties(sub_entity: &SubEntity) -> Vec<(String, String)> {
+	sub_entity
+		.properties
+		.iter()
+		.flatten()
+		.filter(|(_, property)| {
+			!UNLISTED_PROPERTY_TYPES.contains(&property.property_type.as_str())
+				&& !property.property_type.starts_with("TArray")
+		})
+		.filter_map(|(name, property)| Some((name.to_owned(), get_property_text(property)?)))
+		.take(MAX_LISTED_PROPERTIES)
+		.collect()
+}
+
+fn get_property_text(property: &Property) -> Option<String> {
+	match &property.value {
+		Value::Bool(x) => Some(x.to_string()),
+		Value::Number(x) => Some(x.to_string()),
+		Value::String(x) if !x.is_empty() => Some(x.to_owned()),
+		_ => None
+	}
+}
+
+fn escape_html(text: &str) -> String {
+	text.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+}
+
+fn escape_markdown(text: &str) -> String {
+	text.chars().fold(String::new(), |mut acc, x| {
+		if "\\`*_[]<>#|".contains(x) {
+			acc.push('\\');
+		}
+
+		acc.push(x);
+		acc
+	})
+}
+
+struct OutlineWriter<'a> {
+	entity: &'a Entity,
+	hash_list: Option<&'a HashList>,
+	children: HashMap<&'a str, Vec<&'a str>>,
+	format: OutlineFormat,
+	output: String
+}
+
+impl OutlineWriter<'_> {
+	fn write_sub_entity(&mut self, id: &str, depth: usize) {
+		let Some(sub_entity) = self.entity.entities.get(id) else {
+			return;
+		};
+
+		let factory = get_factory_name(&sub_entity.factory, self.hash_list);
+		let properties = get_notable_properties(sub_entity);
+
+		match self.format {
+			OutlineFormat::Markdown => {
+				let _ = write!(
+					self.output,
+					"{}- **{}** ({})",
+					"  ".repeat(depth),
+					escape_markdown(&sub_entity.name),
+					escape_markdown(&factory)
+				);
+
+				if !properties.is_empty() {
+					let _ = write!(
+						self.output,
+						" — {}",
+						properties
+							.iter()
+							.map(|(name, value)| format!("{}: `{}`", escape_markdown(name), value.replace('`', "'")))
+							.collect::<Vec<_>>()
+							.join(", ")
+					);
+				}
+
+				self.output.push('\n');
+			}
+
+			OutlineFormat::Html => {
+				let _ = write!(
+					self.output,
+					"<li><strong>{}</strong> <span class=\"factory\">{}</span>",
+					escape_html(&sub_entity.name),
+					escape_html(&factory)
+				);
+
+				if !properties.is_empty() {
+					let _ = write!(
+						self.output,
+						"<ul class=\"properties\">{}</ul>",
+						properties
+							.iter()
+							.map(|(name, value)| format!(
+								"<li>{}: <code>{}</code></li>",
+								escape_html(name),
+								escape_html(value)
+							))
+							.collect::<String>()
+					);
+				}
+			}
+		}
+
+		let children = self.children.get(id).cloned().unwrap_or_default();
+		let nested_list = self.format == OutlineFormat::Html && !children.is_empty();
+
+		if nested_list {
+			self.output.push_str("<ul>\n");
+		}
+
+		for child in children {
+			self.write_sub_entity(child, depth + 1);
+		}
+
+		if nested_list {
+			self.output.push_str("</ul>");
+		}
+
+		if self.format == OutlineFormat::Html {
+			self.output.push_str("</li>\n");
+		}
+	}
+}
+
+/// Render the tree of an entity, with each sub-entity's name, factory and simple property values, as a Markdown or
+/// standalone HTML document. Sub-entities whose parent isn't in the entity are shown at the top level alongside the
+/// root, and siblings are sorted by name.
+pub fn render_scene_outline(entity: &Entity, hash_list: Option<&HashList>, format: OutlineFormat) -> String {
+	let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+	let mut top_level = vec![];
+
+	for (id, sub_entity) in &entity.entities {
+		match get_local_reference(&sub_entity.parent).filter(|x| x != id && entity.entities.contains_key(x)) {
+			Some(parent) => children
+				.entry(entity.entities.get_key_value(&parent).unwrap().0.as_str())
+				.or_default()
+				.push(id.as_str()),
+
+			None => top_level.push(id.as_str())
+		}
+	}
+
+	let sort_key = |id: &&str| {
+		(
+			*id != entity.root_entity,
+			entity
+				.entities
+				.get(*id)
+				.map(|x| x.name.to_lowercase())
+				.unwrap_or_default()
+		)
+	};
+
+	for siblings in children.values_mut() {
+		siblings.sort_by_key(sort_key);
+	}
+
+	top_level.sort_by_key(sort_key);
+
+	let title = entity
+		.entities
+		.get(&entity.root_entity)
+		.map(|x| x.name.to_owned())
+		.unwrap_or_else(|| entity.factory_hash.to_owned());
+
+	let factory_path = RuntimeID::from_any(&entity.factory_hash)
+		.ok()
+		.and_then(|hash| hash_list?.entries.get(&hash)?.path.to_owned());
+
+	let mut writer = OutlineWriter {
+		entity,
+		hash_list,
+		children,
+		format,
+		output: String::new()
+	};
+
+	match format {
+		OutlineFormat::Markdown => {
+			let _ = writeln!(writer.output, "# {}\n", escape_markdown(&title));
+			let _ = writeln!(
+				writer.output,
+				"Factory `{}`{}, {} sub-entities\n",
+				entity.factory_hash,
+				factory_path
+					.map(|x| format!(" ({})", escape_markdown(&x)))
+					.unwrap_or_default(),
+				entity.entities.len()
+			);
+		}
+
+		OutlineFormat::Html => {
+			let _ = write!(
+				writer.output,
+				"<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n<style>body {{ \
+				 font-family: sans-serif; }} ul {{ list-style: none; padding-left: 1.5em; }} .factory {{ color: #777; \
+				 }} .properties {{ font-size: 0.875em; color: #555; \
+				 }}</style>\n</head>\n<body>\n<h1>{0}</h1>\n<p>Factory <code>{1}</code>{2}, {3} \
+				 sub-entities</p>\n<ul>\n",
+				escape_html(&title),
+				entity.factory_hash,
+				factory_path
+					.map(|x| format!(" ({})", escape_html(&x)))
+					.unwrap_or_default(),
+				entity.entities.len()
+			);
+		}
+	}
+
+	for id in top_level {
+		writer.write_sub_entity(id, 0);
+	}
+
+	if format == OutlineFormat::Html {
+		writer.output.push_str("</ul>\n</body>\n</html>\n");
+	}
+
+	writer.output
+}
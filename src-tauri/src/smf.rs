@@ -0,0 +1,355 @@
+use std::{
+	fs,
+	path::{Path, PathBuf}
+};
+
+use anyhow::{Context, Result};
+use fn_error_context::context;
+use hashbrown::HashMap;
+use log::debug;
+use serde::Deserialize;
+use serde_json::from_slice;
+use tauri::AppHandle;
+use tryvial::try_fn;
+
+use crate::{Notification, NotificationKind, send_notification, telemetry::is_offline};
+
+/// Redirects to the page of the latest framework release, whose URL ends with the release's tag.
+pub const SMF_LATEST_RELEASE_ENDPOINT: &str = "https://github.com/atampy25/simple-mod-framework/releases/latest";
+
+/// The name of the folder the framework is installed to, next to the game's Retail folder.
+const SMF_FOLDER: &str = "Simple Mod Framework";
+
+/// The parts of a framework mod manifest needed to resolve dependencies and find the files the mod deploys.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ModManifest {
+	pub id: String,
+
+	#[serde(default)]
+	pub name: Option<String>,
+
+	#[serde(default)]
+	pub version: Option<String>,
+
+	#[serde(default)]
+	pub framework_version: Option<String>,
+
+	/// IDs of mods which must be enabled for this mod to be deployed
+	#[serde(default)]
+	pub requirements: Vec<String>,
+
+	/// A URL to a JSON file used to check for updates to the mod
+	#[serde(default)]
+	pub update_check: Option<String>,
+
+	#[serde(default)]
+	pub content_folders: Vec<String>,
+
+	#[serde(default)]
+	pub blobs_folders: Vec<String>,
+
+	#[serde(default)]
+	pub options: Vec<ModOption>
+}
+
+/// An optional part of a mod, which can add content and blobs folders when enabled.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ModOption {
+	pub name: String,
+
+	/// The group of mutually exclusive options the option belongs to, if any
+	#[serde(default)]
+	pub group: Option<String>,
+
+	#[serde(default)]
+	pub content_folders: Vec<String>,
+
+	#[serde(default)]
+	pub blobs_folders: Vec<String>
+}
+
+/// The parts of the framework's config needed to know which mods are enabled.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct SmfConfig {
+	/// IDs of the enabled mods, in the order they're deployed
+	#[serde(default)]
+	load_order: Vec<String>,
+
+	/// The enabled options of each mod; grouped options are given as `group:name`
+	#[serde(default)]
+	mod_options: HashMap<String, Vec<String>>
+}
+
+/// A mod installed in the framework, with the folder it's installed to.
+#[derive(Clone, Debug)]
+pub struct InstalledMod {
+	pub path: PathBuf,
+	pub manifest: ModManifest
+}
+
+impl InstalledMod {
+	/// The content and blobs folders the mod deploys, relative to its folder, given its enabled options.
+	pub fn enabled_folders(&self, enabled_options: &[String]) -> (Vec<String>, Vec<String>) {
+		let mut content_folders = self.manifest.content_folders.to_owned();
+		let mut blobs_folders = self.manifest.blobs_folders.to_owned();
+
+		for option in self.manifest.options.iter().filter(|option| {
+			enabled_options.iter().any(|x| {
+				*x == option.name
+					|| option
+						.group
+						.as_ref()
+						.is_some_and(|group| *x == format!("{}:{}", group, option.name))
+			})
+		}) {
+			content_folders.extend(option.content_folders.iter().cloned());
+			blobs_folders.extend(option.blobs_folders.iter().cloned());
+		}
+
+		(content_folders, blobs_folders)
+	}
+}
+
+/// The JSON file at a mod's update check URL.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ModUpdateInfo {
+	pub version: String,
+
+	#[serde(default)]
+	pub url: Option<String>
+}
+
+/// A problem with the dependencies of a mod.
+#[derive(Clone, Debug)]
+pub enum DependencyIssue {
+	/// A required mod isn't installed in the framework
+	Missing { id: String },
+
+	/// A required mod is installed, but a newer version is available
+	Outdated {
+		id: String,
+		name: String,
+		installed_version: String,
+		latest_version: String,
+		link: Option<String>
+	},
+
+	/// The mod was made for an older version of the framework than the latest release
+	FrameworkOutdated {
+		project_version: String,
+		latest_version: String
+	}
+}
+
+impl DependencyIssue {
+	pub fn into_notification(self) -> Notification {
+		match self {
+			DependencyIssue::Missing { id } => Notification {
+				kind: NotificationKind::Warning,
+				title: "Missing dependency".into(),
+				subtitle: format!("This mod requires {id}, which isn't installed in the framework."),
+				link: Some(format!("https://www.nexusmods.com/hitman3/search/?gsearch={id}"))
+			},
+
+			DependencyIssue::Outdated {
+				name,
+				installed_version,
+				latest_version,
+				link,
+				..
+			} => Notification {
+				kind: NotificationKind::Info,
+				title: "Outdated dependency".into(),
+				subtitle: format!("{name} {installed_version} is installed, but {latest_version} is available."),
+				link
+			},
+
+			DependencyIssue::FrameworkOutdated {
+				project_version,
+				latest_version
+			} => Notification {
+				kind: NotificationKind::Info,
+				title: "Newer framework available".into(),
+				subtitle: format!(
+					"This mod targets framework version {project_version}, but {latest_version} is the latest release."
+				),
+				link: Some(SMF_LATEST_RELEASE_ENDPOINT.into())
+			}
+		}
+	}
+}
+
+/// Parse a version like `1.2.3` or `v1.2.3-beta` into its numeric parts, for comparison.
+pub fn parse_version(version: &str) -> Option<Vec<u32>> {
+	version
+		.trim()
+		.trim_start_matches('v')
+		.split(['-', '+'])
+		.next()?
+		.split('.')
+		.map(|x| x.parse::<u32>().ok())
+		.collect()
+}
+
+/// Whether `latest` is a newer version than `current`. Versions that can't be parsed are never considered outdated.
+pub fn is_newer_version(current: &str, latest: &str) -> bool {
+	match (parse_version(current), parse_version(latest)) {
+		(Some(current), Some(latest)) => latest > current,
+		_ => false
+	}
+}
+
+/// Find the framework's folder for a game install, if the framework is installed.
+pub fn find_smf_path(game_install: &Path) -> Option<PathBuf> {
+	[Some(game_install), game_install.parent()]
+		.into_iter()
+		.flatten()
+		.map(|x| x.join(SMF_FOLDER))
+		.find(|x| x.is_dir())
+}
+
+/// Read the manifests of all mods installed in the framework. Mods with invalid manifests are skipped.
+#[try_fn]
+#[context("Couldn't get installed framework mods")]
+pub fn get_installed_mods(smf_path: &Path) -> Result<Vec<InstalledMod>> {
+	let mut mods = vec![];
+
+	for entry in fs::read_dir(smf_path.join("Mods")).context("Couldn't read Mods folder")? {
+		let path = entry?.path();
+		let manifest_path = path.join("manifest.json");
+
+		if !manifest_path.is_file() {
+			continue;
+		}
+
+		match from_slice::<ModManifest>(&fs::read(&manifest_path)?) {
+			Ok(manifest) => mods.push(InstalledMod { path, manifest }),
+			Err(e) => debug!("Skipping mod with invalid manifest {}: {e}", manifest_path.display())
+		}
+	}
+
+	mods
+}
+
+/// Get the mods enabled in the framework, in load order, with the names of their enabled options. Mods without a
+/// manifest, like raw RPKG mods, are skipped.
+#[try_fn]
+#[context("Couldn't get enabled framework mods")]
+pub fn get_enabled_mods(smf_path: &Path) -> Result<Vec<(InstalledMod, Vec<String>)>> {
+	let config: SmfConfig =
+		from_slice(&fs::read(smf_path.join("config.json")).context("Couldn't read framework config")?)
+			.context("Invalid framework config")?;
+
+	let mut installed = get_installed_mods(smf_path)?;
+
+	config
+		.load_order
+		.iter()
+		.filter_map(|id| {
+			let index = installed.iter().position(|x| x.manifest.id == *id)?;
+
+			Some((
+				installed.swap_remove(index),
+				config.mod_options.get(id).cloned().unwrap_or_default()
+			))
+		})
+		.collect()
+}
+
+/// Get the latest version of a mod from its update check URL.
+pub async fn get_latest_mod_version(update_check: &str) -> Option<ModUpdateInfo> {
+	if is_offline() {
+		return None;
+	}
+
+	reqwest::get(update_check).await.ok()?.json().await.ok()
+}
+
+/// Get the version of the latest framework release.
+pub async fn get_latest_framework_version() -> Option<String> {
+	if is_offline() {
+		return None;
+	}
+
+	let res = reqwest::get(SMF_LATEST_RELEASE_ENDPOINT).await.ok()?;
+
+	Some(
+		res.url()
+			.path_segments()?
+			.next_back()?
+			.trim_start_matches('v')
+			.to_owned()
+	)
+}
+
+/// Check the project's manifest against the mods installed in the framework, returning any missing or outdated
+/// dependencies. Projects without a valid manifest have no issues.
+#[try_fn]
+#[context("Couldn't check mod dependencies")]
+pub async fn check_dependencies(project_path: &Path, game_install: Option<&Path>) -> Result<Vec<DependencyIssue>> {
+	let Ok(manifest) = fs::read(project_path.join("manifest.json")) else {
+		return Ok(vec![]);
+	};
+
+	let Ok(manifest) = from_slice::<ModManifest>(&manifest) else {
+		return Ok(vec![]);
+	};
+
+	let mut issues = vec![];
+
+	if let Some(project_version) = manifest.framework_version
+		&& let Some(latest_version) = get_latest_framework_version().await
+		&& is_newer_version(&project_version, &latest_version)
+	{
+		issues.push(DependencyIssue::FrameworkOutdated {
+			project_version,
+			latest_version
+		});
+	}
+
+	if manifest.requirements.is_empty() {
+		return Ok(issues);
+	}
+
+	// Without the framework there's nothing to check the requirements against
+	let Some(smf_path) = game_install.and_then(find_smf_path) else {
+		return Ok(issues);
+	};
+
+	let installed = get_installed_mods(&smf_path)?;
+
+	for requirement in manifest.requirements {
+		let Some(installed_mod) = installed.iter().map(|x| &x.manifest).find(|x| x.id == requirement) else {
+			issues.push(DependencyIssue::Missing { id: requirement });
+			continue;
+		};
+
+		if let Some(installed_version) = installed_mod.version.as_ref()
+			&& let Some(update_check) = installed_mod.update_check.as_ref()
+			&& let Some(latest) = get_latest_mod_version(update_check).await
+			&& is_newer_version(installed_version, &latest.version)
+		{
+			issues.push(DependencyIssue::Outdated {
+				name: installed_mod.name.to_owned().unwrap_or_else(|| requirement.to_owned()),
+				id: requirement,
+				installed_version: installed_version.to_owned(),
+				latest_version: latest.version,
+				link: latest.url
+			});
+		}
+	}
+
+	issues
+}
+
+/// Check the project's dependencies and show a notification for each issue found.
+#[try_fn]
+#[context("Couldn't notify about mod dependencies")]
+pub async fn notify_dependency_issues(app: &AppHandle, project_path: &Path, game_install: Option<&Path>) -> Result<()> {
+	for issue in check_dependencies(project_path, game_install).await? {
+		send_notification(app, issue.into_notification())?;
+	}
+}
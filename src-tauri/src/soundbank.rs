@@ -0,0 +1,279 @@
+use hashbrown::{HashMap, HashSet};
+
+pub const HIRC_SOUND: u8 = 2;
+pub const HIRC_ACTION: u8 = 3;
+pub const HIRC_EVENT: u8 = 4;
+pub const HIRC_RANDOM_SEQUENCE_CONTAINER: u8 = 5;
+pub const HIRC_SWITCH_CONTAINER: u8 = 6;
+pub const HIRC_ACTOR_MIXER: u8 = 7;
+pub const HIRC_LAYER_CONTAINER: u8 = 9;
+
+/// Sources whose plugin ID has this type have their parameters stored along with them.
+const PLUGIN_TYPE_SOURCE: u32 = 2;
+
+/// A switch container's group, and the child nodes it plays for each of the group's switches.
+struct SwitchContainer {
+	group_id: u32,
+	packages: Vec<(u32, Vec<u32>)>
+}
+
+pub fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+	Some(u16::from_le_bytes(data.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+pub fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+	Some(u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+/// Get the objects in the hierarchy of a Wwise soundbank, as their type and data. Anything after a malformed object is
+/// skipped.
+pub fn get_hierarchy_objects(bank: &[u8]) -> Vec<(u8, &[u8])> {
+	let mut objects = vec![];
+	let mut offset = 0;
+
+	// Soundbanks are a sequence of chunks, each starting with a tag and its size
+	while let Some(tag) = bank.get(offset..offset + 4)
+		&& let Some(size) = read_u32(bank, offset + 4)
+	{
+		let Some(chunk) = bank.get(offset + 8..offset + 8 + size as usize) else {
+			break;
+		};
+
+		if tag == b"HIRC" {
+			read_hierarchy_objects(chunk, &mut objects);
+		}
+
+		offset += 8 + size as usize;
+	}
+
+	objects
+}
+
+fn read_hierarchy_objects<'a>(chunk: &'a [u8], objects: &mut Vec<(u8, &'a [u8])>) -> Option<()> {
+	let object_count = read_u32(chunk, 0)?;
+	let mut offset = 4;
+
+	for _ in 0..object_count {
+		let object_type = *chunk.get(offset)?;
+		let size = read_u32(chunk, offset + 1)? as usize;
+
+		objects.push((object_type, chunk.get(offset + 5..offset + 5 + size)?));
+
+		offset += 5 + size;
+	}
+
+	Some(())
+}
+
+/// Read the soundbank of a WWEV and the WEM IDs of its audio objects, in the order the WWEV lists them. WWEVs are laid
+/// out as the event's name, its soundbank, the maximum attenuation radius, the non-streamed objects (each a WEM ID and
+/// the WEM's data) and, if there are none, the streamed objects (each a dependency index, WEM ID and prefetch data).
+pub fn read_event_media(data: &[u8]) -> Option<(&[u8], Vec<u32>)> {
+	let mut offset = 4 + read_u32(data, 0)? as usize;
+
+	let soundbank_size = read_u32(data, offset)? as usize;
+	let soundbank = data.get(offset + 4..offset + 4 + soundbank_size)?;
+	offset += 4 + soundbank_size + 4;
+
+	let mut media = vec![];
+
+	let non_streamed_count = read_u32(data, offset)?;
+	offset += 4;
+
+	for _ in 0..non_streamed_count {
+		media.push(read_u32(data, offset)?);
+		offset += 8 + read_u32(data, offset + 4)? as usize;
+	}
+
+	if non_streamed_count == 0 {
+		let streamed_count = read_u32(data, offset)?;
+		offset += 4;
+
+		for _ in 0..streamed_count {
+			media.push(read_u32(data, offset + 4)?);
+			offset += 12 + read_u32(data, offset + 8)? as usize;
+		}
+	}
+
+	Some((soundbank, media))
+}
+
+/// Read the ID of a node's parent, given where the node's base parameters start. Newer versions have an extra flag
+/// before the bus and parent IDs, so the parent is whichever possible position holds the ID of a known node.
+fn read_parent(object: &[u8], offset: usize, nodes: &HashSet<u32>) -> Option<u32> {
+	// Effects are a flag, a count and, if there are any, a bypass bitfield and an index, ID and two flags for each
+	let effect_count = *object.get(offset + 1)? as usize;
+	let offset = offset + 2 + if effect_count > 0 { 1 + effect_count * 7 } else { 0 };
+
+	[offset + 4, offset + 5]
+		.into_iter()
+		.filter_map(|x| read_u32(object, x))
+		.find(|x| nodes.contains(x))
+}
+
+/// Read the media ID of a sound and where its base parameters start.
+fn read_sound(object: &[u8]) -> Option<(u32, usize)> {
+	// The sound's ID, then its source's plugin ID, stream type, media ID, in-memory size and flags
+	let plugin_id = read_u32(object, 4)?;
+	let media_id = read_u32(object, 9)?;
+
+	let mut offset = 4 + 4 + 1 + 4 + 4 + 1;
+
+	if plugin_id & 0x0F == PLUGIN_TYPE_SOURCE {
+		offset += 4 + read_u32(object, offset)? as usize;
+	}
+
+	Some((media_id, offset))
+}
+
+/// Read a switch container's group and switches. They come after the container's base parameters, which vary too much
+/// between versions to skip reliably, so the group is taken to start at the first position from which the rest of the
+/// container can be read consistently.
+fn read_switch_container(object: &[u8], nodes: &HashSet<u32>) -> Option<SwitchContainer> {
+	(4..object.len()).find_map(|offset| read_switch_container_at(object, offset, nodes))
+}
+
+fn read_switch_container_at(object: &[u8], offset: usize, nodes: &HashSet<u32>) -> Option<SwitchContainer> {
+	let group_id = read_u32(object, offset)?;
+
+	// Switch groups are 0 and state groups are 1
+	if *object.get(offset + 4)? > 1 {
+		return None;
+	}
+
+	// The group ID and type, the default switch and whether continuous validation is enabled
+	let mut offset = offset + 4 + 1 + 4 + 1;
+
+	let child_count = read_u32(object, offset)? as usize;
+	offset += 4;
+
+	if child_count == 0 || child_count > object.len() / 4 {
+		return None;
+	}
+
+	let children = (0..child_count)
+		.map(|index| read_u32(object, offset + index * 4).filter(|x| nodes.contains(x)))
+		.collect::<Option<Vec<_>>>()?;
+
+	offset += child_count * 4;
+
+	let package_count = read_u32(object, offset)? as usize;
+	offset += 4;
+
+	if package_count > object.len() / 8 {
+		return None;
+	}
+
+	let mut packages = vec![];
+
+	for _ in 0..package_count {
+		let switch_id = read_u32(object, offset)?;
+		let item_count = read_u32(object, offset + 4)? as usize;
+		offset += 8;
+
+		if item_count > children.len() {
+			return None;
+		}
+
+		let items = (0..item_count)
+			.map(|index| read_u32(object, offset + index * 4).filter(|x| children.contains(x)))
+			.collect::<Option<Vec<_>>>()?;
+
+		offset += item_count * 4;
+
+		packages.push((switch_id, items));
+	}
+
+	Some(SwitchContainer { group_id, packages })
+}
+
+/// Get the media IDs which play for each switch of a group, by their switch IDs, from the switch containers in the
+/// soundbanks which use the group. Media which isn't under any of those containers plays whichever switch is set, so it
+/// isn't listed.
+pub fn get_switch_media(banks: &[&[u8]], group_id: u32, switch_ids: &[u32]) -> HashMap<u32, HashSet<u32>> {
+	let objects = banks.iter().flat_map(|x| get_hierarchy_objects(x)).collect::<Vec<_>>();
+
+	let nodes = objects
+		.iter()
+		.filter(|(object_type, _)| {
+			[
+				HIRC_SOUND,
+				HIRC_RANDOM_SEQUENCE_CONTAINER,
+				HIRC_SWITCH_CONTAINER,
+				HIRC_ACTOR_MIXER,
+				HIRC_LAYER_CONTAINER
+			]
+			.contains(object_type)
+		})
+		.filter_map(|(_, object)| read_u32(object, 0))
+		.collect::<HashSet<_>>();
+
+	let mut parents = HashMap::new();
+	let mut sounds = vec![];
+	let mut containers = HashMap::new();
+
+	for &(object_type, object) in &objects {
+		let Some(id) = read_u32(object, 0) else {
+			continue;
+		};
+
+		let base_offset = match object_type {
+			HIRC_SOUND => {
+				let Some((media_id, base_offset)) = read_sound(object) else {
+					continue;
+				};
+
+				sounds.push((id, media_id));
+
+				base_offset
+			}
+
+			HIRC_SWITCH_CONTAINER => {
+				if let Some(container) = read_switch_container(object, &nodes)
+					&& (container.group_id == group_id
+						|| container
+							.packages
+							.iter()
+							.any(|(switch_id, _)| switch_ids.contains(switch_id)))
+				{
+					containers.insert(id, container);
+				}
+
+				4
+			}
+
+			HIRC_RANDOM_SEQUENCE_CONTAINER | HIRC_ACTOR_MIXER | HIRC_LAYER_CONTAINER => 4,
+
+			_ => continue
+		};
+
+		if let Some(parent) = read_parent(object, base_offset, &nodes) {
+			parents.insert(id, parent);
+		}
+	}
+
+	let mut switch_media: HashMap<u32, HashSet<u32>> = HashMap::new();
+
+	for (sound, media_id) in sounds {
+		let mut node = sound;
+
+		// Bounded by the number of nodes in case the parents form a cycle
+		for _ in 0..parents.len() {
+			let Some(&parent) = parents.get(&node) else {
+				break;
+			};
+
+			if let Some(container) = containers.get(&parent) {
+				for (switch_id, items) in &container.packages {
+					if switch_ids.contains(switch_id) && items.contains(&node) {
+						switch_media.entry(*switch_id).or_default().insert(media_id);
+					}
+				}
+			}
+
+			node = parent;
+		}
+	}
+
+	switch_media
+}
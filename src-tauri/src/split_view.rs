@@ -0,0 +1,77 @@
+use anyhow::{Context, Result, bail};
+use dashmap::mapref::one::{Ref, RefMut};
+use fn_error_context::context;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::{
+	model::{AppState, EditorData, EditorState, EditorType, GlobalRequest, Request},
+	send_request
+};
+
+/// Get the ID of the editor state shown by a tab, which is the tab's own ID unless it's a split view.
+pub fn get_tab_document(app_state: &AppState, tab: Uuid) -> Uuid {
+	app_state.split_views.get(&tab).map(|x| *x.value()).unwrap_or(tab)
+}
+
+/// Get the editor state shown by a tab. Split views have their own IDs, so editors should use this rather than
+/// getting their editor state directly.
+pub fn get_tab_state(app_state: &AppState, tab: Uuid) -> Option<Ref<'_, Uuid, EditorState>> {
+	app_state.editor_states.get(&get_tab_document(app_state, tab))
+}
+
+/// Get the editor state shown by a tab mutably.
+pub fn get_tab_state_mut(app_state: &AppState, tab: Uuid) -> Option<RefMut<'_, Uuid, EditorState>> {
+	app_state.editor_states.get_mut(&get_tab_document(app_state, tab))
+}
+
+/// Open another tab showing the same editor state as an entity editor tab.
+#[try_fn]
+#[context("Couldn't split tab {}", tab)]
+pub fn split_tab(app: &AppHandle, tab: Uuid) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let document = get_tab_document(&app_state, tab);
+
+	let editor_type = match app_state.editor_states.get(&document).context("No such editor")?.data {
+		EditorData::QNEntity { .. } => EditorType::QNEntity,
+		EditorData::QNPatch { .. } => EditorType::QNPatch,
+
+		_ => bail!("Only entity editors can be split")
+	};
+
+	let id = Uuid::new_v4();
+
+	app_state.split_views.insert(id, document);
+
+	send_request(
+		app,
+		Request::Global(GlobalRequest::CreateSplitTab {
+			id,
+			document,
+			editor_type
+		})
+	)?;
+}
+
+/// Stop tracking a closed tab. Split views are closed along with the tab they were split from, so if the tab isn't a
+/// split view itself, the split views of it are returned to be closed as well.
+pub fn close_tab(app_state: &AppState, tab: Uuid) -> Option<Vec<Uuid>> {
+	if app_state.split_views.remove(&tab).is_some() {
+		return None;
+	}
+
+	let mut split_tabs = vec![];
+
+	app_state.split_views.retain(|split_tab, document| {
+		if *document == tab {
+			split_tabs.push(*split_tab);
+			false
+		} else {
+			true
+		}
+	});
+
+	Some(split_tabs)
+}
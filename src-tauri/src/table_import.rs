@@ -0,0 +1,127 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use indexmap::IndexMap;
+use serde_json::{Value, from_str};
+use tryvial::try_fn;
+
+/// A table of new items read from a CSV or JSON file, for importing them in bulk.
+pub struct ImportTable {
+	/// In the order they first appear
+	pub columns: Vec<String>,
+
+	/// Cells of CSV files are always strings; those of JSON files keep their values
+	pub rows: Vec<IndexMap<String, Value>>
+}
+
+/// Parse CSV text into its records, following RFC 4180: fields may be quoted to contain the delimiter, line breaks or
+/// (doubled) quotes.
+fn parse_csv(text: &str, delimiter: char) -> Vec<Vec<String>> {
+	let mut records = vec![];
+	let mut record = vec![];
+	let mut field = String::new();
+	let mut in_quotes = false;
+
+	let mut chars = text.chars().peekable();
+
+	while let Some(char) = chars.next() {
+		if in_quotes {
+			match char {
+				'"' if chars.peek() == Some(&'"') => {
+					chars.next();
+					field.push('"');
+				}
+
+				'"' => in_quotes = false,
+
+				_ => field.push(char)
+			}
+		} else {
+			match char {
+				'"' if field.is_empty() => in_quotes = true,
+
+				'\r' if chars.peek() == Some(&'\n') => {}
+
+				'\n' | '\r' => {
+					record.push(std::mem::take(&mut field));
+					records.push(std::mem::take(&mut record));
+				}
+
+				x if x == delimiter => record.push(std::mem::take(&mut field)),
+
+				_ => field.push(char)
+			}
+		}
+	}
+
+	if !field.is_empty() || !record.is_empty() {
+		record.push(field);
+		records.push(record);
+	}
+
+	// Blank lines don't make records
+	records.retain(|x| !(x.len() == 1 && x[0].is_empty()));
+
+	records
+}
+
+/// Read a table from a CSV file with a header row, or a JSON file containing an array of objects.
+#[try_fn]
+#[context("Couldn't read table from {}", path.display())]
+pub fn read_import_table(path: &Path) -> Result<ImportTable> {
+	let text = fs::read_to_string(path)?;
+	let text = text.trim_start_matches('\u{feff}');
+
+	if path
+		.extension()
+		.is_some_and(|x| x.to_string_lossy().eq_ignore_ascii_case("json"))
+	{
+		let rows = from_str::<Vec<IndexMap<String, Value>>>(text).context("File must be a JSON array of objects")?;
+
+		let mut columns: Vec<String> = vec![];
+
+		for row in &rows {
+			for key in row.keys() {
+				if !columns.contains(key) {
+					columns.push(key.to_owned());
+				}
+			}
+		}
+
+		ImportTable { columns, rows }
+	} else {
+		// Spreadsheets saved in locales which use a decimal comma separate fields with semicolons instead
+		let header_line = text.lines().next().unwrap_or_default();
+		let delimiter = if header_line.matches(';').count() > header_line.matches(',').count() {
+			';'
+		} else {
+			','
+		};
+
+		let mut records = parse_csv(text, delimiter).into_iter();
+
+		let columns = records
+			.next()
+			.context("File has no header row")?
+			.into_iter()
+			.map(|x| x.trim().to_owned())
+			.collect::<Vec<_>>();
+
+		if columns.iter().any(|x| x.is_empty()) {
+			bail!("Every column must have a name in the header row");
+		}
+
+		let rows = records
+			.map(|record| {
+				columns
+					.iter()
+					.zip(record)
+					.map(|(column, value)| (column.to_owned(), Value::String(value)))
+					.collect()
+			})
+			.collect();
+
+		ImportTable { columns, rows }
+	}
+}
@@ -0,0 +1,140 @@
+use std::{
+	collections::VecDeque,
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering}
+	},
+	time::Instant
+};
+
+use anyhow::{Result, bail};
+use dashmap::DashMap;
+use fn_error_context::context;
+use log::trace;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter, Manager};
+use tryvial::try_fn;
+use uuid::Uuid;
+
+use crate::model::AppState;
+
+/// How many finished tasks to keep the timings of.
+const MAX_RECENT_TASKS: usize = 50;
+
+/// How long a task took, or has taken so far if it's still running.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct TaskTiming {
+	pub name: String,
+	pub duration_ms: u32,
+	pub running: bool
+}
+
+/// The cancellation flags of running tasks which can be cancelled, and the timings of running and recent tasks.
+#[derive(Default)]
+pub struct TaskRegistry {
+	tasks: DashMap<Uuid, Arc<AtomicBool>>,
+	started: DashMap<Uuid, (String, Instant)>,
+	recent: Mutex<VecDeque<TaskTiming>>
+}
+
+impl TaskRegistry {
+	fn register(&self, id: Uuid) -> Arc<AtomicBool> {
+		let cancelled = Arc::new(AtomicBool::new(false));
+		self.tasks.insert(id, cancelled.clone());
+		cancelled
+	}
+
+	/// Start timing a task.
+	pub fn record_start(&self, id: Uuid, name: &str) {
+		self.started.insert(id, (name.to_owned(), Instant::now()));
+	}
+
+	/// Ask a task to stop; returns false if there's no such task or it can't be cancelled.
+	pub fn cancel(&self, id: Uuid) -> bool {
+		if let Some(cancelled) = self.tasks.get(&id) {
+			cancelled.store(true, Ordering::SeqCst);
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Forget a finished task, keeping its timing.
+	pub fn remove(&self, id: Uuid) {
+		self.tasks.remove(&id);
+
+		if let Some((_, (name, start))) = self.started.remove(&id) {
+			let mut recent = self.recent.lock();
+
+			recent.push_back(TaskTiming {
+				name,
+				duration_ms: start.elapsed().as_millis() as u32,
+				running: false
+			});
+
+			if recent.len() > MAX_RECENT_TASKS {
+				recent.pop_front();
+			}
+		}
+	}
+
+	/// The timings of running tasks, followed by those of recently finished tasks, most recent first.
+	pub fn timings(&self) -> Vec<TaskTiming> {
+		self.started
+			.iter()
+			.map(|x| TaskTiming {
+				name: x.0.to_owned(),
+				duration_ms: x.1.elapsed().as_millis() as u32,
+				running: true
+			})
+			.chain(self.recent.lock().iter().rev().cloned())
+			.collect()
+	}
+}
+
+/// A handle given to a running task which can be cancelled, which it should poll between steps of its work.
+#[derive(Clone)]
+pub struct CancellableTask {
+	pub id: Uuid,
+	cancelled: Arc<AtomicBool>
+}
+
+impl CancellableTask {
+	pub fn is_cancelled(&self) -> bool {
+		self.cancelled.load(Ordering::SeqCst)
+	}
+
+	/// Returns an error if the task has been cancelled.
+	pub fn check_cancelled(&self) -> Result<()> {
+		if self.is_cancelled() {
+			bail!("Task cancelled");
+		}
+
+		Ok(())
+	}
+}
+
+/// Start a task which the user can cancel from the task bar. Finish it with `finish_task` like any other task, whether
+/// or not it was cancelled.
+#[try_fn]
+#[context("Couldn't send task start event for {} to frontend", name.as_ref())]
+pub fn start_cancellable_task(app: &AppHandle, name: impl AsRef<str>) -> Result<CancellableTask> {
+	let task_id = Uuid::new_v4();
+	trace!("Starting cancellable task {}: {}", task_id, name.as_ref());
+
+	let cancelled = app.state::<AppState>().tasks.register(task_id);
+	app.state::<AppState>().tasks.record_start(task_id, name.as_ref());
+
+	app.emit("start-task", (&task_id, name.as_ref(), true))?;
+
+	CancellableTask { id: task_id, cancelled }
+}
+
+/// Change the name shown for a running task, such as to show its progress.
+#[try_fn]
+#[context("Couldn't send task update event for {:?} to frontend", task)]
+pub fn update_task(app: &AppHandle, task: Uuid, name: impl AsRef<str>) -> Result<()> {
+	app.emit("update-task", (&task, name.as_ref()))?;
+}
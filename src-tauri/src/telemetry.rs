@@ -0,0 +1,43 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use log::warn;
+use serde_json::Value;
+use tauri::AppHandle;
+use tauri_plugin_aptabase::EventTracker;
+
+/// Mirrors the offline mode setting, so it can be checked where the app settings aren't accessible (like the panic hook).
+static OFFLINE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Update the mirrored offline mode setting. Must be called whenever the setting is loaded or changed.
+pub fn set_offline_mode(offline: bool) {
+	OFFLINE_MODE.store(offline, Ordering::Relaxed);
+}
+
+/// Whether offline mode is enabled, in which case no telemetry is sent and no network requests should be made.
+pub fn is_offline() -> bool {
+	OFFLINE_MODE.load(Ordering::Relaxed)
+}
+
+/// Track an event with Aptabase, unless offline mode is enabled.
+pub fn track_event(app: &AppHandle, name: &str, props: Option<Value>) {
+	if is_offline() {
+		return;
+	}
+
+	if let Err(e) = app.track_event(name, props) {
+		warn!("Couldn't track event {name}: {e:?}");
+	}
+}
+
+/// Send any tracked events which haven't been sent yet, unless offline mode is enabled.
+pub fn flush_events_blocking(app: &AppHandle) {
+	if !is_offline() {
+		app.flush_events_blocking();
+	}
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn is_offline_mode() -> bool {
+	is_offline()
+}
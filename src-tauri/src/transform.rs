@@ -0,0 +1,344 @@
+use std::ops::Mul;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::editor_connection::{QNTransform, Vec3};
+
+/// Values closer than this to a whole multiple are treated as equal to it, to avoid floating point noise in the entity JSON.
+const EPSILON: f64 = 1e-6;
+
+/// A rotation, stored as a unit quaternion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+	pub x: f64,
+	pub y: f64,
+	pub z: f64,
+	pub w: f64
+}
+
+impl Quaternion {
+	pub const IDENTITY: Self = Self {
+		x: 0.0,
+		y: 0.0,
+		z: 0.0,
+		w: 1.0
+	};
+
+	/// Convert QuickEntity-style Euler angles (in degrees, applied in XYZ order) to a quaternion.
+	pub fn from_euler(rotation: &Vec3) -> Self {
+		let (s1, c1) = (rotation.x.to_radians() / 2.0).sin_cos();
+		let (s2, c2) = (rotation.y.to_radians() / 2.0).sin_cos();
+		let (s3, c3) = (rotation.z.to_radians() / 2.0).sin_cos();
+
+		Self {
+			x: s1 * c2 * c3 + c1 * s2 * s3,
+			y: c1 * s2 * c3 - s1 * c2 * s3,
+			z: c1 * c2 * s3 + s1 * s2 * c3,
+			w: c1 * c2 * c3 - s1 * s2 * s3
+		}
+	}
+
+	/// Convert the quaternion to QuickEntity-style Euler angles (in degrees, applied in XYZ order).
+	pub fn to_euler(self) -> Vec3 {
+		let Self { x, y, z, w } = self;
+
+		let m11 = 1.0 - 2.0 * (y * y + z * z);
+		let m12 = 2.0 * (x * y - w * z);
+		let m13 = 2.0 * (x * z + w * y);
+		let m22 = 1.0 - 2.0 * (x * x + z * z);
+		let m23 = 2.0 * (y * z - w * x);
+		let m32 = 2.0 * (y * z + w * x);
+		let m33 = 1.0 - 2.0 * (x * x + y * y);
+
+		let pitch = m13.clamp(-1.0, 1.0).asin();
+
+		// Gimbal lock; the X and Z rotations are about the same axis so the Z rotation is folded into X
+		let (roll, yaw) = if m13.abs() < 0.9999999 {
+			(f64::atan2(-m23, m33), f64::atan2(-m12, m11))
+		} else {
+			(f64::atan2(m32, m22), 0.0)
+		};
+
+		Vec3 {
+			x: tidy(roll.to_degrees()),
+			y: tidy(pitch.to_degrees()),
+			z: tidy(yaw.to_degrees())
+		}
+	}
+
+	/// A rotation of the given angle (in radians) about an axis.
+	pub fn from_axis_angle(axis: &Vec3, angle: f64) -> Self {
+		let axis = normalise(axis);
+		let (sin, cos) = (angle / 2.0).sin_cos();
+
+		Self {
+			x: axis.x * sin,
+			y: axis.y * sin,
+			z: axis.z * sin,
+			w: cos
+		}
+	}
+
+	/// The shortest rotation which turns one direction into another.
+	pub fn between(from: &Vec3, to: &Vec3) -> Self {
+		let from = normalise(from);
+		let to = normalise(to);
+
+		let dot = from.x * to.x + from.y * to.y + from.z * to.z;
+
+		if dot > 1.0 - EPSILON {
+			Self::IDENTITY
+		} else if dot < -1.0 + EPSILON {
+			// Opposite directions; any axis perpendicular to the original direction will do
+			let axis = if from.x.abs() < 0.9 {
+				cross(&from, &Vec3 { x: 1.0, y: 0.0, z: 0.0 })
+			} else {
+				cross(&from, &Vec3 { x: 0.0, y: 1.0, z: 0.0 })
+			};
+
+			Self::from_axis_angle(&axis, std::f64::consts::PI)
+		} else {
+			Self::from_axis_angle(&cross(&from, &to), dot.acos())
+		}
+	}
+
+	/// Rotate a vector by the quaternion.
+	pub fn rotate(self, vector: &Vec3) -> Vec3 {
+		let rotated = self
+			* Self {
+				x: vector.x,
+				y: vector.y,
+				z: vector.z,
+				w: 0.0
+			} * self.conjugate();
+
+		Vec3 {
+			x: rotated.x,
+			y: rotated.y,
+			z: rotated.z
+		}
+	}
+
+	pub fn conjugate(self) -> Self {
+		Self {
+			x: -self.x,
+			y: -self.y,
+			z: -self.z,
+			w: self.w
+		}
+	}
+}
+
+impl Mul for Quaternion {
+	type Output = Self;
+
+	/// Combine two rotations, with the right-hand side applied first.
+	fn mul(self, rhs: Self) -> Self {
+		Self {
+			x: self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+			y: self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+			z: self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+			w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z
+		}
+	}
+}
+
+fn cross(a: &Vec3, b: &Vec3) -> Vec3 {
+	Vec3 {
+		x: a.y * b.z - a.z * b.y,
+		y: a.z * b.x - a.x * b.z,
+		z: a.x * b.y - a.y * b.x
+	}
+}
+
+fn normalise(vector: &Vec3) -> Vec3 {
+	let length = (vector.x * vector.x + vector.y * vector.y + vector.z * vector.z).sqrt();
+
+	if length < EPSILON {
+		Vec3 { x: 0.0, y: 0.0, z: 1.0 }
+	} else {
+		Vec3 {
+			x: vector.x / length,
+			y: vector.y / length,
+			z: vector.z / length
+		}
+	}
+}
+
+/// Round away floating point noise, and turn negative zero into zero.
+fn tidy(value: f64) -> f64 {
+	let rounded = (value / EPSILON).round() * EPSILON;
+
+	if rounded == 0.0 { 0.0 } else { rounded }
+}
+
+/// Round a value to the nearest multiple of the given step; a step of zero leaves the value unchanged.
+pub fn snap(value: f64, step: f64) -> f64 {
+	if step > 0.0 {
+		tidy((value / step).round() * step)
+	} else {
+		value
+	}
+}
+
+/// Round the position of a transform to a grid, and its rotation to multiples of an angle (in degrees). Either can be
+/// zero to leave that part of the transform unchanged.
+pub fn snap_to_grid(transform: &QNTransform, grid_size: f64, angle_snap: f64) -> QNTransform {
+	QNTransform {
+		position: Vec3 {
+			x: snap(transform.position.x, grid_size),
+			y: snap(transform.position.y, grid_size),
+			z: snap(transform.position.z, grid_size)
+		},
+		rotation: Vec3 {
+			x: snap(transform.rotation.x, angle_snap),
+			y: snap(transform.rotation.y, angle_snap),
+			z: snap(transform.rotation.z, angle_snap)
+		},
+		scale: transform.scale.to_owned()
+	}
+}
+
+/// Rotate a transform as little as possible so that its up (Z) axis points along the normal of a surface.
+pub fn align_to_surface(transform: &QNTransform, normal: &Vec3) -> QNTransform {
+	let rotation = Quaternion::from_euler(&transform.rotation);
+	let up = rotation.rotate(&Vec3 { x: 0.0, y: 0.0, z: 1.0 });
+
+	QNTransform {
+		position: transform.position.to_owned(),
+		rotation: (Quaternion::between(&up, normal) * rotation).to_euler(),
+		scale: transform.scale.to_owned()
+	}
+}
+
+/// Move a transform by the given amount relative to its parent, leaving its rotation exactly as it was.
+pub fn translate(transform: &QNTransform, offset: &Vec3) -> QNTransform {
+	QNTransform {
+		position: Vec3 {
+			x: tidy(transform.position.x + offset.x),
+			y: tidy(transform.position.y + offset.y),
+			z: tidy(transform.position.z + offset.z)
+		},
+		rotation: transform.rotation.to_owned(),
+		scale: transform.scale.to_owned()
+	}
+}
+
+/// Move and rotate a transform by the given amounts, either relative to the entity's own axes or to its parent's.
+pub fn offset_transform(transform: &QNTransform, position: &Vec3, rotation: &Vec3, local: bool) -> QNTransform {
+	let current = Quaternion::from_euler(&transform.rotation);
+	let offset = Quaternion::from_euler(rotation);
+
+	let position = if local {
+		current.rotate(position)
+	} else {
+		position.to_owned()
+	};
+
+	QNTransform {
+		position: Vec3 {
+			x: tidy(transform.position.x + position.x),
+			y: tidy(transform.position.y + position.y),
+			z: tidy(transform.position.z + position.z)
+		},
+		rotation: (if local { current * offset } else { offset * current }).to_euler(),
+		scale: transform.scale.to_owned()
+	}
+}
+
+/// A pattern to lay out copies of an entity in, relative to the group they're parented to.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type", content = "data")]
+pub enum ArrayPattern {
+	/// Copies spaced evenly along a line, starting at the group's origin
+	Line { count: u32, spacing: Vec3 },
+
+	/// Copies in columns along X and rows along Y, starting at the group's origin
+	Grid {
+		columns: u32,
+		rows: u32,
+		column_spacing: f64,
+		row_spacing: f64
+	},
+
+	/// Copies spaced evenly around a circle in the XY plane, centred on the group's origin; if facing outwards, each
+	/// copy is also turned about Z by its angle around the circle
+	Circle {
+		count: u32,
+		radius: f64,
+		face_outwards: bool
+	}
+}
+
+impl ArrayPattern {
+	pub fn count(&self) -> u32 {
+		match self {
+			ArrayPattern::Line { count, .. } | ArrayPattern::Circle { count, .. } => *count,
+			ArrayPattern::Grid { columns, rows, .. } => columns.saturating_mul(*rows)
+		}
+	}
+}
+
+/// Get the transform of each copy in a pattern, relative to the group; copies keep the rotation and scale of the
+/// original.
+pub fn array_transforms(original: &QNTransform, pattern: &ArrayPattern) -> Vec<QNTransform> {
+	let place = |x: f64, y: f64, z: f64, turn: f64| QNTransform {
+		position: Vec3 {
+			x: tidy(x),
+			y: tidy(y),
+			z: tidy(z)
+		},
+		rotation: if turn == 0.0 {
+			original.rotation.to_owned()
+		} else {
+			(Quaternion::from_euler(&Vec3 {
+				x: 0.0,
+				y: 0.0,
+				z: turn
+			}) * Quaternion::from_euler(&original.rotation))
+			.to_euler()
+		},
+		scale: original.scale.to_owned()
+	};
+
+	match pattern {
+		ArrayPattern::Line { count, spacing } => (0..*count)
+			.map(|index| {
+				let index = index as f64;
+
+				place(spacing.x * index, spacing.y * index, spacing.z * index, 0.0)
+			})
+			.collect(),
+
+		ArrayPattern::Grid {
+			columns,
+			rows,
+			column_spacing,
+			row_spacing
+		} => (0..*rows)
+			.flat_map(|row| {
+				(0..*columns)
+					.map(move |column| place(column as f64 * column_spacing, row as f64 * row_spacing, 0.0, 0.0))
+			})
+			.collect(),
+
+		ArrayPattern::Circle {
+			count,
+			radius,
+			face_outwards
+		} => (0..*count)
+			.map(|index| {
+				let angle = 360.0 * index as f64 / *count as f64;
+				let (sin, cos) = angle.to_radians().sin_cos();
+
+				place(
+					radius * cos,
+					radius * sin,
+					0.0,
+					if *face_outwards { angle } else { 0.0 }
+				)
+			})
+			.collect()
+	}
+}
@@ -0,0 +1,187 @@
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use quickentity_rs::qn_structs::Property;
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value, json};
+use specta::Type;
+use tryvial::try_fn;
+
+/// Property types whose values are made up of numbers which can be scaled, like angles or lengths.
+const SCALABLE_TYPES: [&str; 8] = [
+	"float32",
+	"float64",
+	"SVector2",
+	"SVector3",
+	"SVector4",
+	"TArray<float32>",
+	"TArray<SVector2>",
+	"TArray<SVector3>"
+];
+
+/// A conversion between two representations of a property's value.
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum UnitConversion {
+	DegreesToRadians,
+	RadiansToDegrees,
+	MetresToCentimetres,
+	CentimetresToMetres,
+
+	/// An SVector3 or SVector4 of colour channels from 0 to 1 to an SColorRGB or SColorRGBA
+	FloatsToHexColour,
+
+	/// An SColorRGB or SColorRGBA to an SVector3 or SVector4 of colour channels from 0 to 1
+	HexColourToFloats
+}
+
+/// Round away floating point noise, like `150.00000000000003`, which would otherwise end up in the entity JSON.
+fn round(value: f64) -> f64 {
+	(value * 1e6).round() / 1e6
+}
+
+/// Parse an SColorRGB or SColorRGBA value, like `#ff8000` or `#ff8000ff`, to RGBA channels from 0 to 1. Colours without
+/// alpha are treated as opaque.
+pub fn parse_hex_colour(value: &str) -> Option<[f64; 4]> {
+	let hex = value.trim_start_matches('#');
+
+	if !hex.is_ascii() || (hex.len() != 6 && hex.len() != 8) {
+		return None;
+	}
+
+	let mut colour = [1.0; 4];
+
+	for (index, channel) in colour.iter_mut().enumerate().take(hex.len() / 2) {
+		*channel = round(u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).ok()? as f64 / 255.0);
+	}
+
+	Some(colour)
+}
+
+/// Set the value of an SColorRGB or SColorRGBA property, keeping the case and `#` prefix of its existing value. The
+/// alpha channel is dropped for SColorRGB.
+#[try_fn]
+#[context("Couldn't set colour of {} property", property.property_type)]
+pub fn set_colour(property: &Property, colour: [f64; 4]) -> Result<Property> {
+	let channels = match property.property_type.as_str() {
+		"SColorRGB" => 3,
+		"SColorRGBA" => 4,
+		_ => bail!("Only SColorRGB and SColorRGBA properties can be set to a colour")
+	};
+
+	let existing = property.value.as_str().unwrap_or("#");
+
+	let mut hex = if existing.starts_with('#') {
+		String::from("#")
+	} else {
+		String::new()
+	};
+
+	for channel in &colour[..channels] {
+		hex.push_str(&format!("{:02x}", (channel.clamp(0.0, 1.0) * 255.0).round() as u8));
+	}
+
+	let mut property = property.to_owned();
+
+	property.value = Value::String(if existing.chars().any(|x| x.is_ascii_uppercase()) {
+		hex.to_uppercase()
+	} else {
+		hex
+	});
+
+	property
+}
+
+/// Multiply every number in a value, leaving its structure alone.
+fn scale(value: &mut Value, factor: f64) {
+	match value {
+		Value::Number(number) => {
+			if let Some(scaled) = number.as_f64().and_then(|x| Number::from_f64(round(x * factor))) {
+				*number = scaled;
+			}
+		}
+
+		Value::Array(values) => values.iter_mut().for_each(|x| scale(x, factor)),
+		Value::Object(values) => values.values_mut().for_each(|x| scale(x, factor)),
+
+		_ => {}
+	}
+}
+
+/// Convert a property's value to another representation, changing its type where the representation has a type of its
+/// own (as with colours).
+#[try_fn]
+#[context("Couldn't convert {} property", property.property_type)]
+pub fn convert_property(property: &Property, conversion: UnitConversion) -> Result<Property> {
+	let mut property = property.to_owned();
+
+	match conversion {
+		UnitConversion::DegreesToRadians
+		| UnitConversion::RadiansToDegrees
+		| UnitConversion::MetresToCentimetres
+		| UnitConversion::CentimetresToMetres => {
+			if !SCALABLE_TYPES.contains(&property.property_type.as_str()) {
+				bail!("Only floating point numbers and vectors can be converted between units");
+			}
+
+			scale(
+				&mut property.value,
+				match conversion {
+					UnitConversion::DegreesToRadians => 1f64.to_radians(),
+					UnitConversion::RadiansToDegrees => 1f64.to_degrees(),
+					UnitConversion::MetresToCentimetres => 100.0,
+					UnitConversion::CentimetresToMetres => 0.01,
+					_ => unreachable!()
+				}
+			);
+		}
+
+		UnitConversion::FloatsToHexColour => {
+			let channels: &[&str] = match property.property_type.as_str() {
+				"SVector3" => &["x", "y", "z"],
+				"SVector4" => &["x", "y", "z", "w"],
+				_ => bail!("Only SVector3 and SVector4 values can be converted to a hex colour")
+			};
+
+			let mut hex = String::from("#");
+
+			for channel in channels {
+				let value = property
+					.value
+					.get(channel)
+					.and_then(|x| x.as_f64())
+					.with_context(|| format!("No {} channel", channel))?;
+
+				hex.push_str(&format!("{:02x}", (value.clamp(0.0, 1.0) * 255.0).round() as u8));
+			}
+
+			property.property_type = if channels.len() == 3 { "SColorRGB" } else { "SColorRGBA" }.into();
+			property.value = Value::String(hex);
+		}
+
+		UnitConversion::HexColourToFloats => {
+			if property.property_type != "SColorRGB" && property.property_type != "SColorRGBA" {
+				bail!("Only SColorRGB and SColorRGBA values can be converted to floats");
+			}
+
+			let channels = property
+				.value
+				.as_str()
+				.and_then(parse_hex_colour)
+				.context("Colour is not in #rrggbb or #rrggbbaa form")?;
+
+			property.value = if property.property_type == "SColorRGB" {
+				json!({ "x": channels[0], "y": channels[1], "z": channels[2] })
+			} else {
+				json!({ "x": channels[0], "y": channels[1], "z": channels[2], "w": channels[3] })
+			};
+
+			property.property_type = if property.property_type == "SColorRGB" {
+				"SVector3"
+			} else {
+				"SVector4"
+			}
+			.into();
+		}
+	}
+
+	property
+}
@@ -0,0 +1,207 @@
+use std::{
+	fs,
+	path::{Path, PathBuf}
+};
+
+use anyhow::{Context, Result};
+use arc_swap::ArcSwap;
+use fn_error_context::context;
+use hashbrown::HashMap;
+use hitman_commons::{game::GameVersion, metadata::RuntimeID};
+use rpkg_rs::resource::partition_manager::PartitionManager;
+use serde::{Deserialize, Serialize};
+use serde_json::{from_slice, to_vec};
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::{
+	Notification, NotificationKind, finish_task,
+	model::{AppSettings, AppState},
+	rpkg::{extract_latest_overview_info, extract_latest_resource},
+	send_notification, start_task
+};
+
+/// The most changed resources listed in the notification, so that it stays readable.
+const MAX_LISTED_CHANGES: usize = 8;
+
+/// The state of a watched resource in the game files, recorded so that it can be compared after the game updates.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+struct WatchedResourceSnapshot {
+	/// The chunk and patch the latest copy of the resource is in, like `chunk0patch2`
+	chunk: String,
+
+	size: usize,
+	checksum: String
+}
+
+/// Snapshots of the watched resources for one game, along with the fingerprint of the game files they were taken from.
+#[derive(Serialize, Deserialize, Default, Debug)]
+struct WatchListSnapshots {
+	fingerprint: String,
+
+	/// None if the resource wasn't in the game files
+	resources: HashMap<RuntimeID, Option<WatchedResourceSnapshot>>
+}
+
+#[try_fn]
+fn get_snapshots_path(app: &AppHandle, game_version: GameVersion) -> Result<PathBuf> {
+	app.path()
+		.app_data_dir()
+		.context("Couldn't get app data dir")?
+		.join(format!(
+			"watch_list_{}.json",
+			format!("{:?}", game_version).to_lowercase()
+		))
+}
+
+fn read_snapshots(path: &Path) -> WatchListSnapshots {
+	fs::read(path)
+		.ok()
+		.and_then(|x| from_slice(&x).ok())
+		.unwrap_or_default()
+}
+
+fn take_snapshot(game_files: &PartitionManager, resource: RuntimeID) -> Option<WatchedResourceSnapshot> {
+	let (_, data) = extract_latest_resource(game_files, resource).ok()?;
+	let (_, chunk, _) = extract_latest_overview_info(game_files, resource).ok()?;
+
+	Some(WatchedResourceSnapshot {
+		chunk,
+		size: data.len(),
+		checksum: format!("{:x}", md5::compute(&data))
+	})
+}
+
+fn describe_snapshot(snapshot: Option<&WatchedResourceSnapshot>) -> String {
+	match snapshot {
+		Some(snapshot) => format!("{} ({:.1} KB)", snapshot.chunk, snapshot.size as f64 / 1024.0),
+		None => "not present".into()
+	}
+}
+
+/// Record the current state of any watched resources which haven't been recorded yet, and forget those which are no
+/// longer watched, so that changes to newly watched resources can be noticed after the next game update.
+#[try_fn]
+#[context("Couldn't record watched resources")]
+pub fn snapshot_new_watched_resources(app: &AppHandle, game_version: GameVersion) -> Result<()> {
+	let app_state = app.state::<AppState>();
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+
+	let game_files = app_state.game_files.load();
+	let Some(game_files) = game_files.as_ref() else {
+		return Ok(());
+	};
+
+	let watched = app_settings.load().watched_resources.to_owned();
+
+	let path = get_snapshots_path(app, game_version)?;
+	let mut snapshots = read_snapshots(&path);
+
+	snapshots.resources.retain(|x, _| watched.contains(x));
+
+	for resource in watched {
+		snapshots
+			.resources
+			.entry(resource)
+			.or_insert_with(|| take_snapshot(game_files, resource));
+	}
+
+	fs::write(path, to_vec(&snapshots)?)?;
+}
+
+/// Compare the watched resources against the snapshots taken from the previous version of the game files, if the game
+/// files have changed since, and notify the user of any which changed. The snapshots are then updated to the current
+/// version.
+#[try_fn]
+#[context("Couldn't check watched resources for changes")]
+pub fn check_watched_resources(app: &AppHandle, game_version: GameVersion, fingerprint: &str) -> Result<()> {
+	let app_state = app.state::<AppState>();
+	let app_settings = app.state::<ArcSwap<AppSettings>>();
+
+	let game_files = app_state.game_files.load();
+	let game_files = game_files.as_ref().context("Game files not loaded")?;
+
+	let watched = app_settings.load().watched_resources.to_owned();
+
+	if watched.is_empty() {
+		return Ok(());
+	}
+
+	let path = get_snapshots_path(app, game_version)?;
+	let previous = read_snapshots(&path);
+
+	if previous.fingerprint == fingerprint {
+		return Ok(());
+	}
+
+	let task = start_task(app, "Checking watched resources")?;
+
+	let current = WatchListSnapshots {
+		fingerprint: fingerprint.to_owned(),
+		resources: watched
+			.iter()
+			.map(|&resource| (resource, take_snapshot(game_files, resource)))
+			.collect()
+	};
+
+	let hash_list = app_state.hash_list.load();
+
+	// Resources without a previous snapshot were only just watched, so there's nothing to compare them against
+	let changes = watched
+		.iter()
+		.filter_map(|resource| {
+			let before = previous.resources.get(resource)?.as_ref();
+			let after = current.resources.get(resource)?.as_ref();
+
+			(before != after).then(|| {
+				let resource_path = hash_list
+					.as_ref()
+					.and_then(|x| x.entries.get(resource))
+					.and_then(|x| x.path.as_ref().or(x.hint.as_ref()))
+					.map(|x| format!(" ({})", x))
+					.unwrap_or_default();
+
+				format!(
+					"{}{}: {} → {}",
+					resource,
+					resource_path,
+					describe_snapshot(before),
+					describe_snapshot(after)
+				)
+			})
+		})
+		.collect::<Vec<_>>();
+
+	fs::write(path, to_vec(&current)?)?;
+
+	finish_task(app, task)?;
+
+	if !changes.is_empty() {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Info,
+				title: format!(
+					"{} watched resource{} changed",
+					changes.len(),
+					if changes.len() == 1 { "" } else { "s" }
+				),
+				subtitle: format!(
+					"Since the game files were last loaded: {}{}",
+					changes
+						.iter()
+						.take(MAX_LISTED_CHANGES)
+						.cloned()
+						.collect::<Vec<_>>()
+						.join("; "),
+					if changes.len() > MAX_LISTED_CHANGES {
+						format!("; and {} more", changes.len() - MAX_LISTED_CHANGES)
+					} else {
+						String::new()
+					}
+				),
+				link: None
+			}
+		)?;
+	}
+}
@@ -0,0 +1,81 @@
+use anyhow::{Context, Result, bail};
+use fn_error_context::context;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tryvial::try_fn;
+
+/// How many peaks are computed for a waveform, regardless of the audio's length.
+const WAVEFORM_PEAKS: usize = 1000;
+
+/// The peaks of some audio, so the frontend can draw its waveform without decoding it.
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+pub struct Waveform {
+	/// The highest amplitude of any channel in each equally sized window of the audio, from 0 to 1
+	pub peaks: Vec<f32>,
+
+	/// In seconds
+	pub duration: f32
+}
+
+/// Compute the waveform of a 16-bit PCM WAV file, like those output by vgmstream.
+#[try_fn]
+#[context("Couldn't compute waveform")]
+pub fn get_waveform(wav: &[u8]) -> Result<Waveform> {
+	if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+		bail!("Not a WAV file");
+	}
+
+	let mut format = None;
+	let mut data = None;
+
+	let mut position = 12;
+	while position + 8 <= wav.len() {
+		let size = u32::from_le_bytes(wav[position + 4..position + 8].try_into()?) as usize;
+		let chunk = &wav[position + 8..(position + 8 + size).min(wav.len())];
+
+		match &wav[position..position + 4] {
+			b"fmt " => format = Some(chunk),
+			b"data" => data = Some(chunk),
+			_ => {}
+		}
+
+		// Chunks are padded to an even size
+		position += 8 + size + size % 2;
+	}
+
+	let format = format.filter(|x| x.len() >= 16).context("No valid format chunk")?;
+	let data = data.context("No data chunk")?;
+
+	let audio_format = u16::from_le_bytes([format[0], format[1]]);
+	let channels = u16::from_le_bytes([format[2], format[3]]) as usize;
+	let sample_rate = u32::from_le_bytes([format[4], format[5], format[6], format[7]]);
+	let bits_per_sample = u16::from_le_bytes([format[14], format[15]]);
+
+	// 0xFFFE is WAVE_FORMAT_EXTENSIBLE, which is still PCM here
+	if !(audio_format == 1 || audio_format == 0xFFFE) || bits_per_sample != 16 || channels == 0 || sample_rate == 0 {
+		bail!(
+			"Unsupported WAV format {:#X} with {} bits per sample and {} channels",
+			audio_format,
+			bits_per_sample,
+			channels
+		);
+	}
+
+	let frame_size = 2 * channels;
+	let frames = data.len() / frame_size;
+	let window = frames.div_ceil(WAVEFORM_PEAKS).max(1);
+
+	Waveform {
+		peaks: data[..frames * frame_size]
+			.chunks(window * frame_size)
+			.map(|window| {
+				window
+					.chunks_exact(2)
+					.map(|sample| (i16::from_le_bytes([sample[0], sample[1]]) as f32 / i16::MAX as f32).abs())
+					.fold(0.0, f32::max)
+					.min(1.0)
+			})
+			.collect(),
+		duration: frames as f32 / sample_rate as f32
+	}
+}
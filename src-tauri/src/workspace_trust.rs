@@ -0,0 +1,87 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use fn_error_context::context;
+use indexmap::IndexSet;
+use serde_json::to_vec;
+use tauri::{AppHandle, Manager};
+use tryvial::try_fn;
+
+use crate::{
+	Notification, NotificationKind,
+	model::{AppState, Request, SettingsRequest, ToolRequest},
+	send_notification, send_request
+};
+
+/// Paths of the projects the user has trusted to run external programs, stored in the app data folder.
+pub type TrustedProjects = IndexSet<PathBuf>;
+
+/// Whether the loaded project is trusted to run external programs. Always true if no project is loaded, as nothing
+/// can come from one.
+pub fn is_project_trusted(app: &AppHandle) -> bool {
+	let app_state = app.state::<AppState>();
+
+	app_state
+		.project
+		.load()
+		.as_ref()
+		.is_none_or(|project| app_state.trusted_projects.load().contains(&project.path))
+}
+
+/// Notify the user and return true if the loaded project isn't trusted, meaning the given action (which runs an
+/// external program) can't be done; the caller should then stop without running anything.
+#[try_fn]
+#[context("Couldn't check whether project is trusted")]
+pub fn refuse_if_untrusted(app: &AppHandle, action: &str) -> Result<bool> {
+	let trusted = is_project_trusted(app);
+
+	if !trusted {
+		send_notification(
+			app,
+			Notification {
+				kind: NotificationKind::Warning,
+				title: "Project isn't trusted".into(),
+				subtitle: format!(
+					"{} runs an external program, which is disabled until you trust this project. Only trust projects \
+					 you know the source of; you can do so in the project settings.",
+					action
+				),
+				link: None
+			}
+		)?;
+	}
+
+	!trusted
+}
+
+/// Trust or stop trusting a project, then tell the frontend if it's the loaded one.
+#[try_fn]
+#[context("Couldn't change trust of project {}", path.display())]
+pub fn set_project_trusted(app: &AppHandle, path: PathBuf, trusted: bool) -> Result<()> {
+	let app_state = app.state::<AppState>();
+
+	let mut trusted_projects = (**app_state.trusted_projects.load()).to_owned();
+
+	if trusted {
+		trusted_projects.insert(path.to_owned());
+	} else {
+		trusted_projects.shift_remove(&path);
+	}
+
+	fs::write(
+		app.path()
+			.app_data_dir()
+			.context("Couldn't get app data dir")?
+			.join("trusted_projects.json"),
+		to_vec(&trusted_projects)?
+	)?;
+
+	app_state.trusted_projects.store(trusted_projects.into());
+
+	if app_state.project.load().as_ref().is_some_and(|x| x.path == path) {
+		send_request(
+			app,
+			Request::Tool(ToolRequest::Settings(SettingsRequest::SetProjectTrusted(trusted)))
+		)?;
+	}
+}
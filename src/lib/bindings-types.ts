@@ -1,13 +1,247 @@
 // This file has been generated by Specta. DO NOT EDIT.
 
+/**
+ * A resource which an animation resource depends on, like the rig it animates or the animation data it plays.
+ */
+export type AnimationDependency = {
+	hash: string
+	path: string | null
+	resource_type: string
+	/**
+	 * The number of bones in a BORG, and so the number of bone tracks animations of it have, if it could be parsed
+	 */
+	bone_count: number | null
+}
+
 export type Announcement = { id: string; kind: AnnouncementKind; title: string; description: string; persistent: boolean; until: number | null }
 
 export type AnnouncementKind = "info" | "success" | "warning" | "error"
 
-export type AppSettings = { extractModdedFiles: boolean; gameInstall: string | null; colourblindMode: boolean; editorConnection: boolean; seenAnnouncements: string[] }
+export type AppSettings = {
+	extractModdedFiles: boolean
+	gameInstall: string | null
+	colourblindMode: boolean
+	editorConnection: boolean
+	seenAnnouncements: string[]
+	/**
+	 * Programs that resources and files can be opened with from the resource overview and file browser
+	 */
+	externalTools: ExternalTool[]
+	/**
+	 * Disables telemetry and all network requests, for use without an internet connection
+	 */
+	offlineMode: boolean
+	/**
+	 * Games to connect to the SDK editor servers of, which can be more than one to test with several game instances
+	 */
+	editorConnectionGames: EditorConnectionGame[]
+	/**
+	 * Named sets of extraction options, which resources can be quickly extracted with from the resource overview and
+	 * game browser
+	 */
+	extractionPresets: ExtractionPreset[]
+	/**
+	 * The most entities extracted from the game files to keep in memory before the least recently used are evicted, or
+	 * 0 for no limit
+	 */
+	entityCacheMaxEntries: number
+	/**
+	 * The most memory in megabytes that entities extracted from the game files can take up before the least recently
+	 * used are evicted, or 0 for no limit
+	 */
+	entityCacheMaxMemory: number
+	/**
+	 * The program WAV files are converted to WEM with when importing audio
+	 */
+	wemEncoder: WemEncoder | null
+	/**
+	 * Resources to check for changes whenever the game files are updated
+	 */
+	watchedResources: string[]
+}
 
 export type ArrayPatchOperation = { RemoveItemByValue: JsonValue } | { AddItemAfter: [JsonValue, JsonValue] } | { AddItemBefore: [JsonValue, JsonValue] } | { AddItem: JsonValue }
 
+/**
+ * A pattern to lay out copies of an entity in, relative to the group they're parented to.
+ */
+export type ArrayPattern =
+	/**
+	 * Copies spaced evenly along a line, starting at the group's origin
+	 */
+	| { type: "Line"; data: { count: number; spacing: Vec3 } }
+	/**
+	 * Copies in columns along X and rows along Y, starting at the group's origin
+	 */
+	| { type: "Grid"; data: { columns: number; rows: number; column_spacing: number; row_spacing: number } }
+	/**
+	 * Copies spaced evenly around a circle in the XY plane, centred on the group's origin; if facing outwards, each
+	 * copy is also turned about Z by its angle around the circle
+	 */
+	| { type: "Circle"; data: { count: number; radius: number; face_outwards: boolean } }
+
+export type AudioImportEvent =
+	/**
+	 * Pick a folder and list the WAV files in it
+	 */
+	| { type: "selectFolder" }
+	/**
+	 * Convert WAV files in a folder to WEM and add them to the project as replacements
+	 */
+	| {
+			type: "import"
+			data: {
+				folder: string
+				/**
+				 * File name, hash or path of the WWEM it replaces
+				 */
+				mapping: [string, string][]
+			}
+	  }
+
+export type AudioImportRequest = { type: "setFiles"; data: { folder: string; files: WavFile[] } }
+
+export type AudioNamesEvent =
+	| { type: "initialise" }
+	/**
+	 * Add the IDs of unnamed Wwise objects in a WWEV or SDEF resource's soundbanks to the targets
+	 */
+	| { type: "gatherTargets"; data: string }
+	/**
+	 * Hash every word, alone and after each of the project's prefixes, keeping any names which match a target
+	 */
+	| {
+			type: "bruteForce"
+			data: {
+				targets: number[]
+				words: string[]
+				/**
+				 * A file with one word per line, tried in addition to the given words
+				 */
+				wordlist: string | null
+			}
+	  }
+	| { type: "removeName"; data: number }
+
+export type AudioNamesRequest =
+	/**
+	 * ID, name
+	 */
+	| { type: "setNames"; data: [number, string][] }
+	| { type: "addTargets"; data: number[] }
+	/**
+	 * The names found by the last brute-force; ID, name
+	 */
+	| { type: "setResults"; data: [number, string][] }
+
+/**
+ * A node of a behaviour tree, built from the JSON ResourceLib converts the resource to.
+ */
+export type BehaviourTreeNode = {
+	/**
+	 * The key or index of the node in its parent, with its name if it has one
+	 */
+	label: string
+	/**
+	 * The node's type if it has one, otherwise the kind of JSON value it is (Object, Array, String and so on)
+	 */
+	kind: string
+	/**
+	 * The value of a leaf node
+	 */
+	value: string | null
+	children: BehaviourTreeNode[]
+}
+
+export type BlueprintEntityInfo = {
+	id: string
+	name: string
+	/**
+	 * The hash of the resource that this entity is created from
+	 */
+	factory: string | null
+}
+
+export type BlueprintPinForwarding = {
+	/**
+	 * Whether this is an input pin forwarding (as opposed to an output pin forwarding)
+	 */
+	input: boolean
+	from_entity: string
+	from_pin: string
+	to_entity: string
+	to_pin: string
+}
+
+export type BlueprintSubsetInfo = {
+	/**
+	 * The name of the entity which owns the subset
+	 */
+	owner: string
+	name: string
+	entities: string[]
+}
+
+/**
+ * A bone of a BORG's skeleton in its bind pose.
+ */
+export type Bone = {
+	name: string
+	/**
+	 * The index of the parent bone, or None for a root bone
+	 */
+	parent: number | null
+	/**
+	 * In model space
+	 */
+	position: [number, number, number]
+	/**
+	 * The bind pose relative to the parent bone; the rotation is a quaternion (x, y, z, w)
+	 */
+	local_position: [number, number, number]
+	local_rotation: [number, number, number, number]
+}
+
+/**
+ * The partition a new entity should be loaded in, worked out from the partition of the scenario it's used in.
+ */
+export type ChunkAssignment = {
+	/**
+	 * The entity's template as written in packagedefinition.txt, like `[assembly:/_pro/my_mod.entitytemplate]`
+	 */
+	entity_path: string
+	/**
+	 * The partition as named in packagedefinition.txt, like `season2`
+	 */
+	partition: string
+	/**
+	 * The chunk the partition's resources are stored in
+	 */
+	chunk: number
+	/**
+	 * Whether the manifest already loads the entity in this partition
+	 */
+	already_assigned: boolean
+}
+
+/**
+ * The state of the connection to another GlacierKit instance for co-editing entities.
+ */
+export type CollaborationStatus =
+	| { type: "disconnected" }
+	/**
+	 * Waiting for another instance to connect on the given port
+	 */
+	| { type: "hosting"; data: number }
+	/**
+	 * Connecting to the given address
+	 */
+	| { type: "connecting"; data: string }
+	/**
+	 * Connected to another instance, whose user has the given name
+	 */
+	| { type: "connected"; data: string }
+
 /**
  * A comment entity.
  *
@@ -28,9 +262,43 @@ export type CommentEntity = {
 	text: string
 }
 
-export type ContentSearchEvent = { type: "search"; data: [string, string[], boolean, string[]] }
+/**
+ * An event connection which sends a constant value, as shown in the constant value editor.
+ */
+export type ConstantValueConnection = {
+	output_pin: string
+	input_pin: string
+	/**
+	 * The position of the connection among those from the output pin to the input pin
+	 */
+	index: number
+	target: Ref
+	/**
+	 * The name of the target, if it's a local entity
+	 */
+	target_name: string | null
+	value: SimpleProperty
+	/**
+	 * The type of the target's property with the same name as the input pin, if intellisense knows of one
+	 */
+	expected_type: string | null
+}
+
+export type ContentSearchEvent =
+	/**
+	 * Query, resource types, whether to search entities in QuickEntity format, partitions, and whether to
+	 * convert types without dedicated handling to ResourceLib JSON before matching rather than searching
+	 * their raw data
+	 */
+	| { type: "search"; data: [string, string[], boolean, string[], boolean] }
+	/**
+	 * Save a search to the project, replacing any saved search with the same name
+	 */
+	| { type: "saveSearch"; data: SavedSearch }
+	| { type: "deleteSavedSearch"; data: string }
+	| { type: "runSavedSearch"; data: string }
 
-export type ContentSearchRequest = { type: "setEnabled"; data: boolean } | { type: "setPartitions"; data: [string, string][] }
+export type ContentSearchRequest = { type: "setEnabled"; data: boolean } | { type: "setPartitions"; data: [string, string][] } | { type: "setSavedSearches"; data: SavedSearch[] }
 
 export type ContentSearchResultsEvent = { type: "initialise"; data: { id: string } } | { type: "openResourceOverview"; data: { id: string; hash: string } }
 
@@ -45,6 +313,30 @@ export type ContentSearchResultsRequest = {
 	}
 }
 
+/**
+ * A value converted into another form.
+ */
+export type Conversion = {
+	/**
+	 * What the input was treated as and what it was converted to
+	 */
+	label: string
+	value: string
+}
+
+export type ConvertersEvent = { type: "convert"; data: string }
+
+export type ConvertersRequest = {
+	type: "setConversions"
+	data: {
+		/**
+		 * The input that was converted, so outdated results can be ignored
+		 */
+		input: string
+		conversions: Conversion[]
+	}
+}
+
 export type CopiedEntityData = {
 	/**
 	 * Which entity has been copied (and should be parented to the selection when pasting).
@@ -53,6 +345,61 @@ export type CopiedEntityData = {
 	data: { [key in string]: SubEntity }
 }
 
+export type CrashAnalysis = {
+	path: string
+	/**
+	 * Whether the file was a minidump, in which case only its readable strings could be searched
+	 */
+	minidump: boolean
+	resources: CrashResource[]
+	module_offsets: ModuleOffset[]
+	/**
+	 * Every project file which defines or refers to a resource in the crash
+	 */
+	involved_files: string[]
+}
+
+export type CrashAnalysisEvent =
+	/**
+	 * Pick a crash log or minidump and analyse it
+	 */
+	| { type: "selectFile" }
+	| { type: "analyse"; data: string }
+	| { type: "openFile"; data: string }
+	| { type: "openResourceOverview"; data: string }
+
+export type CrashAnalysisRequest = { type: "setAnalysis"; data: CrashAnalysis }
+
+/**
+ * A resource mentioned in a crash, along with the project files which have to do with it.
+ */
+export type CrashResource = {
+	resource: ResolvedResource
+	/**
+	 * Project files which define or override the resource, like an entity with it as its factory
+	 */
+	defined_in: string[]
+	/**
+	 * Project files which refer to the resource by hash or path
+	 */
+	referenced_in: string[]
+}
+
+/**
+ * A dependency flag, decoded from its modern (HITMAN 3) form.
+ */
+export type DecodedReferenceFlag = {
+	kind: ReferenceKind
+	/**
+	 * Whether the referenced resource is acquired by the game at runtime
+	 */
+	acquired: boolean
+	/**
+	 * The language the reference is for, or None if it applies to every language
+	 */
+	languageCode: number | null
+}
+
 /**
  * A dependency of an entity.
  */
@@ -68,13 +415,135 @@ export type Dependency =
  */
 export type DependencyWithFlag = { resource: string; flag: string }
 
+/**
+ * A container in a DLGE dialogue tree, with its children separated from its other properties.
+ */
+export type DialogueContainer = {
+	container_type: DialogueContainerType
+	/**
+	 * Every property of the container other than its type and children
+	 */
+	properties: JsonValue
+	children: DialogueContainer[]
+}
+
+export type DialogueContainerType = "WavFile" | "Random" | "Switch" | "Sequence"
+
+export type DialogueEditorEvent =
+	| { type: "initialise"; data: { id: string } }
+	/**
+	 * Add a new container as the last child of the container at the given path
+	 */
+	| { type: "addContainer"; data: { id: string; parent: number[]; container_type: DialogueContainerType } }
+	| { type: "removeContainer"; data: { id: string; path: number[] } }
+	/**
+	 * Move a container so that it's at the given index among the children of the new parent
+	 */
+	| { type: "moveContainer"; data: { id: string; path: number[]; new_parent: number[]; index: number } }
+	/**
+	 * Replace the properties of a container, leaving its type and children untouched
+	 */
+	| { type: "updateContainer"; data: { id: string; path: number[]; properties: JsonValue } }
+
+export type DialogueEditorRequest = { type: "setDialogue"; data: { id: string; root: DialogueContainer; issues: DialogueIssue[] } }
+
+export type DialogueIssue = {
+	/**
+	 * The indices of the children to follow from the root container to reach the container with the issue
+	 */
+	path: number[]
+	message: string
+}
+
+/**
+ * Documentation for a property or pin, shown when hovering over it in the entity editor.
+ */
+export type DocumentationEntry = {
+	description: string
+	/**
+	 * The unit the value is in, like `metres` or `seconds`
+	 */
+	unit: string | null
+	example: JsonValue | null
+}
+
+/**
+ * A file dropped into the window and how it would be imported into the project.
+ */
+export type DroppedFile = {
+	path: string
+	kind: DroppedFileKind
+	/**
+	 * Where the file will be written, relative to the project, or None if it can't be imported
+	 */
+	destination: string | null
+	/**
+	 * Shown alongside the file, like why it can't be imported or what to do after importing it
+	 */
+	note: string | null
+}
+
+export type DroppedFileKind =
+	/**
+	 * A ResourceLib TEMP JSON, which is converted to a QuickEntity entity along with its TBLU
+	 */
+	| "ResourceLibEntity"
+	| "Entity"
+	/**
+	 * A TGA texture and its meta file
+	 */
+	| "Texture"
+	| "Audio"
+	| "Unsupported"
+
 export type Dynamics = { announcements: Announcement[] }
 
+/**
+ * The state of the connection to a game's SDK editor server, for troubleshooting.
+ */
+export type EditorConnectionDiagnostics = {
+	game: EditorConnectionGame
+	status: EditorConnectionStatus
+	/**
+	 * The version of the SDK, if it reported one when connecting
+	 */
+	sdk_version: string | null
+	/**
+	 * The features the SDK reported supporting when connecting, if it reported any
+	 */
+	capabilities: string[] | null
+	/**
+	 * Failed attempts to connect since the connection was last established
+	 */
+	failed_attempts: number
+	/**
+	 * Seconds until the next attempt to connect, if one is scheduled
+	 */
+	next_attempt_in: number | null
+	last_error: string | null
+}
+
 export type EditorConnectionEvent =
 	| { type: "entitySelected"; data: [string, string] }
 	| { type: "entityTransformUpdated"; data: [string, string, QNTransform] }
 	| { type: "entityPropertyChanged"; data: [string, string, string, string, JsonValue] }
 
+/**
+ * A game running the SDK's editor server, which the editor connection can connect to.
+ */
+export type EditorConnectionGame = {
+	/**
+	 * Shown in the UI to tell connections apart
+	 */
+	name: string
+	/**
+	 * The WebSocket URL of the game's editor server
+	 */
+	url: string
+}
+
+export type EditorConnectionStatus = "Disabled" | "Disconnected" | "Connected"
+
 export type EditorEvent =
 	| { type: "text"; data: TextEditorEvent }
 	| { type: "entity"; data: EntityEditorEvent }
@@ -82,6 +551,30 @@ export type EditorEvent =
 	| { type: "repositoryPatch"; data: RepositoryPatchEditorEvent }
 	| { type: "unlockablesPatch"; data: UnlockablesPatchEditorEvent }
 	| { type: "contentSearchResults"; data: ContentSearchResultsEvent }
+	| { type: "enum"; data: EnumEditorEvent }
+	| { type: "dialogue"; data: DialogueEditorEvent }
+	| { type: "jsonPatch"; data: JsonPatchEditorEvent }
+	| { type: "jsonResource"; data: JsonResourceEditorEvent }
+
+/**
+ * The approximate memory taken up by an editor's state.
+ */
+export type EditorMemoryUsage = {
+	id: string
+	/**
+	 * The editor's file name, or its kind if it doesn't have a file
+	 */
+	name: string
+	/**
+	 * The split views of this editor
+	 */
+	split_tabs: string[]
+	/**
+	 * Name and approximate size in bytes of each part of the state
+	 */
+	parts: [string, number][]
+	total: number
+}
 
 export type EditorRequest =
 	| { type: "text"; data: TextEditorRequest }
@@ -90,6 +583,10 @@ export type EditorRequest =
 	| { type: "repositoryPatch"; data: RepositoryPatchEditorRequest }
 	| { type: "unlockablesPatch"; data: UnlockablesPatchEditorRequest }
 	| { type: "contentSearchResults"; data: ContentSearchResultsRequest }
+	| { type: "enum"; data: EnumEditorRequest }
+	| { type: "dialogue"; data: DialogueEditorRequest }
+	| { type: "jsonPatch"; data: JsonPatchEditorRequest }
+	| { type: "jsonResource"; data: JsonResourceEditorRequest }
 
 export type EditorType =
 	| { type: "Nil" }
@@ -100,6 +597,10 @@ export type EditorType =
 	| { type: "RepositoryPatch"; data: { patch_type: JsonPatchType } }
 	| { type: "UnlockablesPatch"; data: { patch_type: JsonPatchType } }
 	| { type: "ContentSearchResults" }
+	| { type: "Enum" }
+	| { type: "Dialogue" }
+	| { type: "JsonPatch" }
+	| { type: "JsonResource"; data: { kind: JsonResourceKind } }
 
 export type EditorValidity = { type: "Valid" } | { type: "Invalid"; data: string }
 
@@ -178,6 +679,9 @@ export type EntityEditorEvent =
 	| { type: "metaPane"; data: EntityMetaPaneEvent }
 	| { type: "metadata"; data: EntityMetadataEvent }
 	| { type: "overrides"; data: EntityOverridesEvent }
+	| { type: "stats"; data: EntityStatsEvent }
+	| { type: "problems"; data: EntityProblemsEvent }
+	| { type: "subsets"; data: EntitySubsetsEvent }
 
 export type EntityEditorRequest =
 	| { type: "general"; data: EntityGeneralRequest }
@@ -186,24 +690,97 @@ export type EntityEditorRequest =
 	| { type: "metaPane"; data: EntityMetaPaneRequest }
 	| { type: "metadata"; data: EntityMetadataRequest }
 	| { type: "overrides"; data: EntityOverridesRequest }
+	| { type: "stats"; data: EntityStatsRequest }
+	| { type: "problems"; data: EntityProblemsRequest }
+	| { type: "subsets"; data: EntitySubsetsRequest }
 
 export type EntityGeneralEvent =
 	| { type: "setShowReverseParentRefs"; data: { editor_id: string; show_reverse_parent_refs: boolean } }
 	| { type: "setShowChangesFromOriginal"; data: { editor_id: string; show_changes_from_original: boolean } }
+	/**
+	 * Re-diff a patch against a freshly extracted copy of the original entity, so that saving it only
+	 * writes the operations it actually needs
+	 */
+	| { type: "minimisePatch"; data: { editor_id: string } }
+	/**
+	 * Save an outline of the entity tree, with names, factories and simple property values, as a
+	 * Markdown or HTML document
+	 */
+	| { type: "exportOutline"; data: { editor_id: string; format: OutlineFormat } }
 
 export type EntityGeneralRequest = { type: "setIsPatchEditor"; data: { editor_id: string; is_patch_editor: boolean } }
 
-export type EntityMetaPaneEvent = { type: "jumpToReference"; data: { editor_id: string; reference: string } } | { type: "setNotes"; data: { editor_id: string; entity_id: string; notes: string } }
-
-export type EntityMetaPaneRequest =
-	| { type: "setReverseRefs"; data: { editor_id: string; entity_names: { [key in string]: string }; reverse_refs: ReverseReference[] } }
+export type EntityMetaPaneEvent =
+	| { type: "jumpToReference"; data: { editor_id: string; reference: string } }
 	| { type: "setNotes"; data: { editor_id: string; entity_id: string; notes: string } }
-
-export type EntityMetadataEvent =
-	| { type: "initialise"; data: { editor_id: string } }
-	| { type: "setFactoryHash"; data: { editor_id: string; factory_hash: string } }
-	| { type: "setBlueprintHash"; data: { editor_id: string; blueprint_hash: string } }
-	| { type: "setRootEntity"; data: { editor_id: string; root_entity: string } }
+	/**
+	 * List the overrides in the entity, resolving the names of their targets
+	 */
+	| { type: "requestOverrides"; data: { editor_id: string } }
+	/**
+	 * Select a local override target in the tree, or open the external scene containing it
+	 */
+	| { type: "jumpToOverrideTarget"; data: { editor_id: string; entity_id: string; external_scene: string | null } }
+	| { type: "createPropertyAlias"; data: { editor_id: string; entity_id: string; aliased_name: string; alias: PropertyAlias } }
+	/**
+	 * Replace the alias at the given index among those with the given name, which may be renamed
+	 */
+	| { type: "editPropertyAlias"; data: { editor_id: string; entity_id: string; aliased_name: string; index: number; new_aliased_name: string; alias: PropertyAlias } }
+	| { type: "deletePropertyAlias"; data: { editor_id: string; entity_id: string; aliased_name: string; index: number } }
+	/**
+	 * Add an exposed entity, or replace the one with the same name; the entities it refers to are given
+	 * as local entity IDs or names
+	 */
+	| { type: "setExposedEntity"; data: { editor_id: string; entity_id: string; exposed_name: string; is_array: boolean; refers_to: string[] } }
+	| { type: "removeExposedEntity"; data: { editor_id: string; entity_id: string; exposed_name: string } }
+	/**
+	 * Add an exposed interface, or replace the one with the same name; the entity implementing it is
+	 * given as a local entity ID or name
+	 */
+	| { type: "setExposedInterface"; data: { editor_id: string; entity_id: string; interface: string; refers_to: string } }
+	| { type: "removeExposedInterface"; data: { editor_id: string; entity_id: string; interface: string } }
+
+export type EntityMetaPaneRequest =
+	| { type: "setReverseRefs"; data: { editor_id: string; entity_names: { [key in string]: string }; reverse_refs: ReverseReference[] } }
+	| { type: "setNotes"; data: { editor_id: string; entity_id: string; notes: string } }
+	| { type: "setOverrides"; data: { editor_id: string; overrides: OverrideListing[] } }
+	| {
+			type: "setPropertyAliases"
+			data: {
+				editor_id: string
+				entity_id: string
+				/**
+				 * Aliased name, aliases with that name
+				 */
+				property_aliases: [string, PropertyAlias[]][]
+				/**
+				 * Names of the local entities the aliases refer to
+				 */
+				entity_names: { [key in string]: string }
+			}
+	  }
+	| {
+			type: "setExposedEntities"
+			data: {
+				editor_id: string
+				entity_id: string
+				exposed_entities: [string, ExposedEntity][]
+				/**
+				 * Interface, ID of the entity implementing it
+				 */
+				exposed_interfaces: [string, string][]
+				/**
+				 * Names of the local entities the exposed entities and interfaces refer to
+				 */
+				entity_names: { [key in string]: string }
+			}
+	  }
+
+export type EntityMetadataEvent =
+	| { type: "initialise"; data: { editor_id: string } }
+	| { type: "setFactoryHash"; data: { editor_id: string; factory_hash: string } }
+	| { type: "setBlueprintHash"; data: { editor_id: string; blueprint_hash: string } }
+	| { type: "setRootEntity"; data: { editor_id: string; root_entity: string } }
 	| { type: "setSubType"; data: { editor_id: string; sub_type: SubType } }
 	| { type: "setExternalScenes"; data: { editor_id: string; external_scenes: string[] } }
 
@@ -212,6 +789,10 @@ export type EntityMetadataRequest =
 	| { type: "setHashModificationAllowed"; data: { editor_id: string; hash_modification_allowed: boolean } }
 	| { type: "setFactoryHash"; data: { editor_id: string; factory_hash: string } }
 	| { type: "setBlueprintHash"; data: { editor_id: string; blueprint_hash: string } }
+	/**
+	 * Offer to change the blueprint hash to the one used by the new factory in the game files
+	 */
+	| { type: "suggestBlueprintHash"; data: { editor_id: string; blueprint_hash: string } }
 	| { type: "updateCustomPaths"; data: { editor_id: string; custom_paths: string[] } }
 
 export type EntityMonacoEvent =
@@ -220,13 +801,73 @@ export type EntityMonacoEvent =
 	| { type: "openFactory"; data: { editor_id: string; factory: string } }
 	| { type: "signalPin"; data: { editor_id: string; entity_id: string; pin: string; output: boolean } }
 	| { type: "openResourceOverview"; data: { editor_id: string; resource: string } }
+	/**
+	 * Open the resource referenced by a ZRuntimeResourceID property; TEMPs are opened in the entity editor
+	 * and other resources in a resource overview
+	 */
+	| { type: "followResource"; data: { editor_id: string; resource: string } }
+	/**
+	 * Convert a property of a sub-entity to another unit or representation, like degrees to radians
+	 */
+	| { type: "convertProperty"; data: { editor_id: string; entity_id: string; property: string; conversion: UnitConversion } }
+	/**
+	 * Set an SColorRGB or SColorRGBA property to a colour chosen in the colour picker
+	 */
+	| {
+			type: "setColour"
+			data: {
+				editor_id: string
+				entity_id: string
+				property: string
+				/**
+				 * RGBA channels from 0 to 1
+				 */
+				colour: [number, number, number, number]
+			}
+	  }
 
 export type EntityMonacoRequest =
 	| { type: "deselectIfSelected"; data: { editor_id: string; entity_ids: string[] } }
 	| { type: "replaceContent"; data: { editor_id: string; entity_id: string; content: string } }
 	| { type: "replaceContentIfSameEntityID"; data: { editor_id: string; entity_id: string; content: string } }
-	| { type: "updateIntellisense"; data: { editor_id: string; entity_id: string; properties: [string, string, JsonValue, boolean][]; pins: [string[], string[]] } }
-	| { type: "updateDecorationsAndMonacoInfo"; data: { editor_id: string; entity_id: string; decorations: [string, string][]; local_ref_entity_ids: string[] } }
+	| {
+			type: "updateIntellisense"
+			data: {
+				editor_id: string
+				entity_id: string
+				properties: [string, string, JsonValue, boolean][]
+				pins: [string[], string[]]
+				/**
+				 * Enum name, values for any properties using enums loaded in the enum editor
+				 */
+				enums: [string, string[]][]
+				/**
+				 * Property name, documentation for any documented properties
+				 */
+				property_docs: [string, DocumentationEntry][]
+				/**
+				 * Pin name, documentation for any documented pins
+				 */
+				pin_docs: [string, DocumentationEntry][]
+			}
+	  }
+	| {
+			type: "updateDecorationsAndMonacoInfo"
+			data: {
+				editor_id: string
+				entity_id: string
+				decorations: [string, string][]
+				local_ref_entity_ids: string[]
+				/**
+				 * Property name, RGBA channels from 0 to 1 for each colour property, for the colour picker
+				 */
+				colours: [string, [number, number, number, number]][]
+				/**
+				 * Entity ID, Markdown describing the entity for each reference to an external scene
+				 */
+				external_ref_hovers: [string, string][]
+			}
+	  }
 	| { type: "updateValidity"; data: { editor_id: string; validity: EditorValidity } }
 	| { type: "setEditorConnected"; data: { editor_id: string; connected: boolean } }
 
@@ -241,6 +882,78 @@ export type EntityOverridesRequest =
 	| { type: "initialise"; data: { editor_id: string; property_overrides: string; override_deletes: string; pin_connection_overrides: string; pin_connection_override_deletes: string } }
 	| { type: "updateDecorations"; data: { editor_id: string; decorations: [string, string][] } }
 
+/**
+ * A problem with the name of a sub-entity, found by the entity name lint.
+ */
+export type EntityProblem = { entity_id: string; name: string; message: string }
+
+export type EntityProblemsEvent = { type: "check"; data: { editor_id: string } }
+
+export type EntityProblemsRequest = {
+	type: "setProblems"
+	data: {
+		editor_id: string
+		/**
+		 * Whether entity name linting is turned on in the project settings
+		 */
+		enabled: boolean
+		problems: EntityProblem[]
+	}
+}
+
+export type EntityStats = {
+	entity_count: number
+	/**
+	 * Factory, number of sub-entities using it; most used first
+	 */
+	factory_counts: [string, number][]
+	property_count: number
+	platform_specific_property_count: number
+	pin_connection_count: number
+	external_scene_count: number
+	/**
+	 * The number of resources the converted TEMP and TBLU depend on
+	 */
+	dependency_count: number
+	/**
+	 * The size in bytes of the binary TEMP and TBLU the entity converts to, if they could be generated
+	 */
+	temp_size: number | null
+	tblu_size: number | null
+}
+
+export type EntityStatsEvent = { type: "calculate"; data: { editor_id: string } }
+
+export type EntityStatsRequest =
+	| { type: "setStats"; data: { editor_id: string; stats: EntityStats } }
+	/**
+	 * Sent along with the stats for patch editors
+	 */
+	| { type: "setPatchSummary"; data: { editor_id: string; summary: PatchSummary } }
+
+/**
+ * A subset owned by a sub-entity, with the sub-entities which are members of it.
+ */
+export type EntitySubset = {
+	owner: string
+	owner_name: string
+	name: string
+	/**
+	 * Entity ID, name; sorted by name
+	 */
+	members: [string, string][]
+}
+
+export type EntitySubsetsEvent =
+	| { type: "initialise"; data: { editor_id: string } }
+	/**
+	 * Make an entity a member of a subset, creating the subset if the owner doesn't have it yet.
+	 */
+	| { type: "addMember"; data: { editor_id: string; owner: string; subset: string; entity_id: string } }
+	| { type: "removeMember"; data: { editor_id: string; owner: string; subset: string; entity_id: string } }
+
+export type EntitySubsetsRequest = { type: "setSubsets"; data: { editor_id: string; subsets: EntitySubset[] } }
+
 export type EntityTreeEvent =
 	| { type: "initialise"; data: { editor_id: string } }
 	| { type: "select"; data: { editor_id: string; id: string } }
@@ -250,16 +963,114 @@ export type EntityTreeEvent =
 	| { type: "reparent"; data: { editor_id: string; id: string; new_parent: Ref } }
 	| { type: "copy"; data: { editor_id: string; id: string } }
 	| { type: "paste"; data: { editor_id: string; parent_id: string } }
+	/**
+	 * Duplicate an entity beside itself under the same parent, incrementing the number at the end of
+	 * its name and moving it by an offset
+	 */
+	| { type: "duplicate"; data: { editor_id: string; id: string; include_children: boolean; offset: Vec3 } }
+	/**
+	 * Clone an entity and its children into a line, grid or circle, parented to a new group entity
+	 */
+	| { type: "generateArray"; data: { editor_id: string; id: string; pattern: ArrayPattern } }
+	/**
+	 * Paste the copied entities as a single entity using them as its factory, saving them to a new
+	 * template in the project if needed
+	 */
+	| { type: "pasteAsInstance"; data: { editor_id: string; parent_id: string } }
 	| { type: "search"; data: { editor_id: string; query: string } }
 	| { type: "showHelpMenu"; data: { editor_id: string; entity_id: string } }
-	| { type: "useTemplate"; data: { editor_id: string; parent_id: string; template: CopiedEntityData } }
+	/**
+	 * Paste a built-in template; if it has parameters and no values are given, the frontend is asked for
+	 * them first
+	 */
+	| {
+			type: "useTemplate"
+			data: {
+				editor_id: string
+				parent_id: string
+				template: PastableTemplate
+				/**
+				 * Parameter name, value as entered
+				 */
+				parameter_values: { [key in string]: string } | null
+			}
+	  }
 	| { type: "addGameBrowserItem"; data: { editor_id: string; parent_id: string; file: string } }
+	/**
+	 * Generate a WWEM and WWEV in the project for a new sound, and add a zaudioevententity playing it
+	 */
+	| {
+			type: "addCustomSound"
+			data: {
+				editor_id: string
+				parent_id: string
+				name: string
+				/**
+				 * A WAV or WEM file
+				 */
+				audio: string
+				/**
+				 * Hash or path of a vanilla WWEV playing a single streamed sound, whose soundbank is reused
+				 */
+				template: string
+			}
+	  }
 	| { type: "selectEntityInEditor"; data: { editor_id: string; entity_id: string } }
 	| { type: "moveEntityToPlayer"; data: { editor_id: string; entity_id: string } }
 	| { type: "rotateEntityAsPlayer"; data: { editor_id: string; entity_id: string } }
 	| { type: "moveEntityToCamera"; data: { editor_id: string; entity_id: string } }
 	| { type: "rotateEntityAsCamera"; data: { editor_id: string; entity_id: string } }
 	| { type: "restoreToOriginal"; data: { editor_id: string; entity_id: string } }
+	/**
+	 * Put back an entry of the recycle bin, along with the references to it which can still be restored
+	 */
+	| { type: "restoreDeleted"; data: { editor_id: string; index: number } }
+	/**
+	 * Extract the game template an entity uses as its factory into the project as an editable entity,
+	 * and use that copy as the entity's factory instead
+	 */
+	| { type: "extractFactoryToProject"; data: { editor_id: string; entity_id: string } }
+	/**
+	 * Round an entity's position to a grid and its rotation to multiples of an angle (in degrees); either
+	 * can be zero to leave it unchanged
+	 */
+	| { type: "snapEntityToGrid"; data: { editor_id: string; entity_id: string; grid_size: number; angle_snap: number } }
+	/**
+	 * Rotate an entity as little as possible so that its up axis points along a surface normal
+	 */
+	| { type: "alignEntityToSurface"; data: { editor_id: string; entity_id: string; normal: Vec3 } }
+	/**
+	 * Move an entity and rotate it (in degrees), relative to its own axes if local or otherwise to its
+	 * parent's
+	 */
+	| { type: "offsetEntityTransform"; data: { editor_id: string; entity_id: string; position: Vec3; rotation: Vec3; local: boolean } }
+	/**
+	 * Request the output pins of one entity and the input pins of another, to choose an event
+	 * connection between them
+	 */
+	| { type: "getConnectionPins"; data: { editor_id: string; source_id: string; target_id: string } }
+	/**
+	 * Connect an output pin of one entity to an input pin of another, optionally sending a constant
+	 * value instead of the output's own
+	 */
+	| { type: "connectEntities"; data: { editor_id: string; source_id: string; output_pin: string; target_id: string; input_pin: string; value: SimpleProperty | null } }
+	/**
+	 * Request the connections of an entity which send constant values, to edit them
+	 */
+	| { type: "getConstantValues"; data: { editor_id: string; entity_id: string } }
+	/**
+	 * Change the constant value sent by an event connection; the value is checked against the type of
+	 * the target pin where intellisense knows it
+	 */
+	| { type: "setConstantValue"; data: { editor_id: string; entity_id: string; output_pin: string; input_pin: string; index: number; value: SimpleProperty } }
+	/**
+	 * Request the children of a node in a lazily loaded tree
+	 */
+	| { type: "expandNode"; data: { editor_id: string; id: string } }
+	/**
+	 * Request the ancestors of a node in a lazily loaded tree, so that it can be loaded and selected
+	 */
+	| { type: "revealNode"; data: { editor_id: string; id: string } }
 
 export type EntityTreeRequest =
 	/**
@@ -274,8 +1085,30 @@ export type EntityTreeRequest =
 				 * ID, parent, name, factory, has reverse parent refs
 				 */
 				entities: [string, Ref, string, string, boolean][]
+				/**
+				 * Whether only the root-level entities have been sent, with deeper levels sent as they're expanded
+				 */
+				lazy: boolean
 			}
 	  }
+	/**
+	 * The children of a node in a lazily loaded tree
+	 */
+	| {
+			type: "nodeChildren"
+			data: {
+				editor_id: string
+				id: string
+				/**
+				 * ID, parent, name, factory, has reverse parent refs
+				 */
+				children: [string, Ref, string, string, boolean][]
+			}
+	  }
+	/**
+	 * The ancestors of a node in a lazily loaded tree, starting from the root
+	 */
+	| { type: "revealPath"; data: { editor_id: string; id: string; ancestors: string[] } }
 	/**
 	 * Instructs the frontend to take the list of new entities, add any new ones and update any ones that already exist (by ID) with the new information.
 	 * This is used for pasting, and for ensuring that icons/parent status/name are updated when a sub-entity is updated.
@@ -290,6 +1123,20 @@ export type EntityTreeRequest =
 				new_entities: [string, Ref, string, string, boolean][]
 			}
 	  }
+	| {
+			type: "setRecycleBin"
+			data: {
+				editor_id: string
+				/**
+				 * Most recently deleted first
+				 */
+				entries: RecycleBinEntry[]
+			}
+	  }
+	/**
+	 * Fill the search box with a query and run it
+	 */
+	| { type: "setSearchQuery"; data: { editor_id: string; query: string } }
 	| {
 			type: "searchResults"
 			data: {
@@ -298,14 +1145,45 @@ export type EntityTreeRequest =
 				 * The IDs of the entities matching the query
 				 */
 				results: string[]
+				/**
+				 * Entity ID, paths of the properties matching any property search terms in the query
+				 */
+				property_matches: [string, string[]][]
+				/**
+				 * The ancestors of the results, parents before children; these must be loaded in lazily loaded trees
+				 */
+				ancestors: string[]
 			}
 	  }
 	| { type: "showHelpMenu"; data: { editor_id: string; factory: string; input_pins: string[]; output_pins: string[]; default_properties_json: string } }
+	| { type: "showConnectEntities"; data: { editor_id: string; source_id: string; source_name: string; target_id: string; target_name: string; output_pins: string[]; input_pins: string[] } }
+	| { type: "showConstantValues"; data: { editor_id: string; entity_id: string; entity_name: string; connections: ConstantValueConnection[] } }
 	| { type: "setTemplates"; data: { editor_id: string; templates: PastableTemplateCategory[] } }
+	/**
+	 * Ask the user for the values of a template's parameters, then send UseTemplate again with them
+	 */
+	| { type: "promptTemplateParameters"; data: { editor_id: string; parent_id: string; template: PastableTemplate } }
 	| { type: "setEditorConnectionAvailable"; data: { editor_id: string; editor_connection_available: boolean } }
 	| { type: "setShowDiff"; data: { editor_id: string; show_diff: boolean } }
 	| { type: "setDiffInfo"; data: { editor_id: string; diff_info: [string[], string[], [string, string, Ref, string, boolean][]] } }
 
+export type EnumEditorEvent =
+	| { type: "initialise"; data: { id: string } }
+	| { type: "addEnum"; data: { id: string; name: string } }
+	| { type: "addValue"; data: { id: string; enum_name: string; value: string } }
+	| { type: "removeValue"; data: { id: string; enum_name: string; value: string } }
+
+export type EnumEditorRequest = {
+	type: "setEnums"
+	data: {
+		id: string
+		/**
+		 * Enum name, values
+		 */
+		enums: [string, string[]][]
+	}
+}
+
 export type EphemeralQNSettings = { showReverseParentRefs: boolean; showChangesFromOriginal: boolean }
 
 export type Event = { type: "tool"; data: ToolEvent } | { type: "editor"; data: EditorEvent } | { type: "global"; data: GlobalEvent } | { type: "editorConnection"; data: EditorConnectionEvent }
@@ -326,6 +1204,78 @@ export type ExposedEntity = {
 	refersTo: Ref[]
 }
 
+/**
+ * A user-configured external program (e.g. RPKG Tool) that resources and files can be opened with.
+ */
+export type ExternalTool = {
+	name: string
+	path: string
+	/**
+	 * The arguments to pass to the tool; `{hash}` and `{path}` are replaced with the hash and path of the target.
+	 * Arguments are separated by spaces, and can be wrapped in double quotes to include spaces.
+	 */
+	arguments: string
+}
+
+export type ExtractionFormat =
+	/**
+	 * The resource as it's stored in the game files, along with a binary meta file
+	 */
+	| "Raw"
+	/**
+	 * The resource converted to JSON with ResourceLib, along with a JSON meta file
+	 */
+	| "ResourceLibJson"
+	/**
+	 * Textures and images converted to PNG
+	 */
+	| "Png"
+
+export type ExtractionItem = { id: string; name: string; status: ExtractionStatus }
+
+/**
+ * A named set of extraction options, which lets resources be extracted without choosing where to each time.
+ */
+export type ExtractionPreset = {
+	name: string
+	format: ExtractionFormat
+	/**
+	 * The name of the extracted file, without its extension; `{hash}`, `{name}` and `{type}` are replaced with the
+	 * resource's hash, its name from the hash list (or hash, if it has none) and its resource type.
+	 */
+	namingScheme: string
+	/**
+	 * The folder to extract to; `{project}`, `{folder}` and `{type}` are replaced with the project folder, the folder
+	 * of the resource's path in the hash list and its resource type.
+	 */
+	destination: string
+}
+
+export type ExtractionQueueEvent = { type: "initialise" } | { type: "cancel"; data: string } | { type: "clearFinished" }
+
+export type ExtractionQueueRequest = { type: "setItems"; data: ExtractionItem[] } | { type: "updateItem"; data: ExtractionItem }
+
+export type ExtractionStatus =
+	| { type: "queued" }
+	| { type: "running"; data: { completed: number; total: number } }
+	| { type: "finished" }
+	| { type: "failed"; data: string }
+	| { type: "cancelled" }
+
+/**
+ * A previous version of a project file.
+ */
+export type FileBackup = {
+	/**
+	 * When the version was replaced, in milliseconds since the Unix epoch; identifies the backup
+	 */
+	timestamp: number
+	/**
+	 * In bytes
+	 */
+	size: number
+}
+
 export type FileBrowserEvent =
 	| { type: "select"; data: string | null }
 	| { type: "create"; data: { path: string; is_folder: boolean } }
@@ -338,6 +1288,25 @@ export type FileBrowserEvent =
 	| { type: "convertRepoPatchToJsonPatch"; data: { path: string } }
 	| { type: "convertUnlockablesPatchToMergePatch"; data: { path: string } }
 	| { type: "convertUnlockablesPatchToJsonPatch"; data: { path: string } }
+	| { type: "openWithExternalTool"; data: { path: string; tool: string } }
+	| { type: "requestBackups"; data: { path: string } }
+	| { type: "restoreBackup"; data: { path: string; timestamp: number } }
+	| { type: "duplicate"; data: { path: string } }
+	| { type: "previewBatchRename"; data: { folder: string; pattern: string; replacement: string } }
+	| { type: "batchRename"; data: { folder: string; pattern: string; replacement: string } }
+	| { type: "createFromTemplate"; data: { folder: string; name: string; template: FileTemplate } }
+	/**
+	 * Suggest the partition an entity file should be loaded in, from the scenario it's used in
+	 */
+	| { type: "suggestChunkAssignment"; data: { path: string; scenario: string } }
+	/**
+	 * Load an entity in a partition by adding it to the manifest's packagedefinition changes
+	 */
+	| { type: "assignChunk"; data: { assignment: ChunkAssignment } }
+	/**
+	 * Apply a patch file to the current game files without saving anything, to see which operations apply
+	 */
+	| { type: "testPatch"; data: { path: string } }
 
 export type FileBrowserRequest =
 	| { type: "create"; data: { path: string; is_folder: boolean } }
@@ -356,6 +1325,67 @@ export type FileBrowserRequest =
 				files: [string, boolean][]
 			}
 	  }
+	/**
+	 * Names of the configured external tools files can be opened with
+	 */
+	| { type: "setExternalTools"; data: string[] }
+	/**
+	 * The previous versions of a file, newest first
+	 */
+	| { type: "showBackups"; data: { path: string; backups: FileBackup[] } }
+	| {
+			type: "showBatchRenamePreview"
+			data: {
+				folder: string
+				/**
+				 * Old path, new path of each file or folder that would be renamed
+				 */
+				renames: [string, string][]
+				/**
+				 * Why the rename can't be done, if it can't
+				 */
+				error: string | null
+			}
+	  }
+	| {
+			type: "showChunkAssignment"
+			data: {
+				path: string
+				assignment: ChunkAssignment | null
+				/**
+				 * Why a partition can't be suggested, if it can't
+				 */
+				error: string | null
+			}
+	  }
+	| {
+			type: "showPatchTest"
+			data: {
+				path: string
+				test: PatchTest | null
+				/**
+				 * Why the patch couldn't be tested, if it couldn't
+				 */
+				error: string | null
+			}
+	  }
+
+/**
+ * A kind of file that can be created with some starting content from the file browser.
+ */
+export type FileTemplate =
+	/**
+	 * An entity with just a root spatial entity
+	 */
+	| "Entity"
+	/**
+	 * An empty repository.json merge patch
+	 */
+	| "RepositoryPatch"
+	/**
+	 * An empty unlockables.json merge patch
+	 */
+	| "UnlockablesPatch"
 
 /**
  * A long-form reference to an entity, allowing for the specification of external scenes and/or an exposed entity.
@@ -366,54 +1396,316 @@ export type FullRef = {
 	 */
 	ref: string
 	/**
-	 * The external scene the referenced entity resides in.
+	 * The external scene the referenced entity resides in.
+	 */
+	externalScene: string | null
+	/**
+	 * The sub-entity to reference that is exposed by the referenced entity.
+	 */
+	exposedEntity?: string | null
+}
+
+export type GameBrowserEntry = {
+	hash: string
+	path: string | null
+	hint: string | null
+	filetype: string
+	partition: [string, string]
+	/**
+	 * The DLC the resource belongs to, or None if it's part of the base game
+	 */
+	dlc: string | null
+}
+
+export type GameBrowserEvent =
+	| { type: "select"; data: string }
+	| { type: "search"; data: [string, SearchFilter, PartitionFilter] }
+	| { type: "openInEditor"; data: string }
+	| { type: "removeFromCollection"; data: { collection: string; hash: string } }
+	| { type: "deleteCollection"; data: string }
+	| { type: "extractCollection"; data: string }
+	| { type: "extractWithPreset"; data: { hash: string; preset: string } }
+
+export type GameBrowserRequest =
+	| { type: "setEnabled"; data: boolean }
+	| { type: "newTree"; data: { game_description: string; entries: GameBrowserEntry[] } }
+	| { type: "setCollections"; data: ResourceCollection[] }
+	/**
+	 * Name, ID of each partition in the game files
+	 */
+	| { type: "setPartitions"; data: [string, string][] }
+	/**
+	 * Names of the configured extraction presets
+	 */
+	| { type: "setExtractionPresets"; data: string[] }
+
+export type GameInstall = { version: GameVersion; platform: GamePlatform; path: string }
+
+export type GamePlatform = "steam" | "epic" | "gog" | "microsoft"
+
+export type GameVersion = "h1" | "h2" | "h3"
+
+export type GitChangeKind = "New" | "Modified" | "Deleted" | "Renamed" | "TypeChange" | "Conflicted"
+
+export type GitEvent = { type: "refresh" } | { type: "diff"; data: string } | { type: "commit"; data: { files: string[]; message: string } }
+
+export type GitFileStatus = {
+	path: string
+	/**
+	 * The path relative to the project folder, with forward slashes
+	 */
+	relative_path: string
+	change: GitChangeKind
+	/**
+	 * Whether the change has been staged
+	 */
+	staged: boolean
+}
+
+export type GitRequest =
+	| { type: "setStatus"; data: GitFileStatus[] | null }
+	| {
+			type: "setDiff"
+			data: {
+				path: string
+				/**
+				 * Whether the diff is a QuickEntity patch rather than a textual diff
+				 */
+				is_entity_patch: boolean
+				diff: string
+			}
+	  }
+
+export type GlobalEvent =
+	| { type: "setSeenAnnouncements"; data: string[] }
+	| { type: "loadWorkspace"; data: string }
+	| { type: "selectAndOpenFile" }
+	| { type: "selectTab"; data: string | null }
+	| { type: "removeTab"; data: string }
+	| { type: "saveTab"; data: string }
+	/**
+	 * Open another tab showing the same entity editor, sharing its state
+	 */
+	| { type: "splitTab"; data: string }
+	| { type: "uploadLogAndReport"; data: string }
+	| { type: "uploadLastPanic" }
+	| { type: "clearLastPanic" }
+	/**
+	 * Return to the previously focused tab or selected entity, reopening its tab if it was closed
+	 */
+	| { type: "navigateBack" }
+	/**
+	 * Undo a previous NavigateBack
+	 */
+	| { type: "navigateForward" }
+	/**
+	 * Files dropped into the window, which are offered to be imported into the project
+	 */
+	| { type: "detectDroppedFiles"; data: string[] }
+	| { type: "importDroppedFiles"; data: DroppedFile[] }
+	/**
+	 * Ask a running task to stop; only tasks started as cancellable can be cancelled
+	 */
+	| { type: "cancelTask"; data: string }
+	/**
+	 * Split the saved entity patch of a tab into several patch files by subtree
+	 */
+	| { type: "splitPatch"; data: string }
+
+export type GlobalRequest =
+	| { type: "errorReport"; data: { error: string } }
+	| { type: "setWindowTitle"; data: string }
+	| { type: "initialiseDynamics"; data: { dynamics: Dynamics; seen_announcements: string[] } }
+	| { type: "createTab"; data: { id: string; name: string; editor_type: EditorType } }
+	/**
+	 * Create a tab for a split view of another tab's editor state; requests for that editor state should be
+	 * handled by both tabs
+	 */
+	| { type: "createSplitTab"; data: { id: string; document: string; editor_type: EditorType } }
+	| { type: "renameTab"; data: { id: string; new_name: string } }
+	| { type: "selectTab"; data: string }
+	| { type: "setTabUnsaved"; data: { id: string; unsaved: boolean } }
+	| { type: "removeTab"; data: string }
+	| { type: "computeJSONPatchAndSave"; data: { base: JsonValue; current: JsonValue; save_path: string; file_and_type: [string, string] } }
+	| { type: "requestLastPanicUpload" }
+	| { type: "logUploadRejected" }
+	| { type: "showPatchConflicts"; data: { id: string; conflicts: PatchConflict[] } }
+	| { type: "showDroppedFileImport"; data: DroppedFile[] }
+	/**
+	 * Offer to split an entity patch which was saved over the project's thresholds
+	 */
+	| {
+			type: "offerPatchSplit"
+			data: {
+				id: string
+				operations: number
+				/**
+				 * In bytes
+				 */
+				size: number
+			}
+	  }
+
+export type HashRemappingEvent =
+	/**
+	 * Replace each old hash with its new hash throughout the project, or only report what would be replaced
+	 */
+	{
+		type: "remap"
+		data: {
+			/**
+			 * Old hash or path, new hash or path
+			 */
+			mapping: [string, string][]
+			dry_run: boolean
+		}
+	}
+
+export type HashRemappingRequest = {
+	type: "setReport"
+	data: {
+		/**
+		 * Whether the files were left unchanged
+		 */
+		dry_run: boolean
+		files: RemappedFile[]
+	}
+}
+
+export type HashResolutionEvent =
+	/**
+	 * Resolve every hash and path in some text, like a crash log
+	 */
+	| { type: "resolve"; data: string }
+	| { type: "openResourceOverview"; data: string }
+
+export type HashResolutionRequest = { type: "setResults"; data: ResolvedResource[] }
+
+export type JsonPatchEditorEvent = { type: "initialise"; data: { id: string } } | { type: "updateContent"; data: { id: string; content: string } }
+
+export type JsonPatchEditorRequest =
+	| { type: "replaceContent"; data: { id: string; content: string } }
+	/**
+	 * Show the target resource with the patch applied, and mark the parts of the patch that couldn't be
+	 * applied
+	 */
+	| {
+			type: "setPreview"
+			data: {
+				id: string
+				/**
+				 * None if the target resource couldn't be loaded
+				 */
+				preview: string | null
+				diagnostics: TextDiagnostic[]
+			}
+	  }
+
+export type JsonPatchType = "MergePatch" | "JsonPatch"
+
+export type JsonResourceEditorEvent = { type: "initialise"; data: { id: string } } | { type: "updateContent"; data: { id: string; content: string } }
+
+export type JsonResourceEditorRequest = { type: "replaceContent"; data: { id: string; kind: JsonResourceKind; content: string } }
+
+export type JsonResourceKind =
+	/**
+	 * A location's challenges, in groups
+	 */
+	| "Challenges"
+	/**
+	 * GameChanger definitions, by ID
+	 */
+	| "GameChangers"
+
+export type JsonValue = null | boolean | number | string | JsonValue[] | { [key in string]: JsonValue }
+
+/**
+ * How a file changes the resource or blob it targets.
+ */
+export type ModChangeKind =
+	/**
+	 * The file takes the place of the original entirely, like a raw resource or a full entity
+	 */
+	| "Replace"
+	/**
+	 * The file modifies the original, like an entity patch or a JSON patch
+	 */
+	| "Patch"
+
+/**
+ * A resource or blob changed by both the project and a mod enabled in the framework.
+ */
+export type ModConflict = {
+	severity: ModConflictSeverity
+	/**
+	 * The hash of the resource, or the path of the blob
+	 */
+	target: string
+	/**
+	 * The path of the resource in the hash list, if it has one
+	 */
+	target_path: string | null
+	mod_id: string
+	mod_name: string
+	/**
+	 * Forward-slash separated paths relative to the project
+	 */
+	project_files: [string, ModChangeKind][]
+	/**
+	 * Forward-slash separated paths relative to the mod's folder
+	 */
+	mod_files: [string, ModChangeKind][]
+}
+
+export type ModConflictSeverity =
+	/**
+	 * Both the project and the mod replace the resource, so whichever is deployed last wins outright
+	 */
+	| "High"
+	/**
+	 * One replaces the resource and the other patches it, so the patch is either lost or applied to something it
+	 * wasn't made for
+	 */
+	| "Medium"
+	/**
+	 * Both patch the resource, which usually merges cleanly unless they change the same data
+	 */
+	| "Low"
+
+/**
+ * An address in a module which appeared in the crash.
+ */
+export type ModuleOffset = {
+	module: string
+	/**
+	 * The offset from the start of the module, like `0x1A2B3C`
 	 */
-	externalScene: string | null
+	offset: string
 	/**
-	 * The sub-entity to reference that is exposed by the referenced entity.
+	 * How many times the address appeared
 	 */
-	exposedEntity?: string | null
+	occurrences: number
 }
 
-export type GameBrowserEntry = { hash: string; path: string | null; hint: string | null; filetype: string; partition: [string, string] }
-
-export type GameBrowserEvent = { type: "select"; data: string } | { type: "search"; data: [string, SearchFilter] } | { type: "openInEditor"; data: string }
-
-export type GameBrowserRequest = { type: "setEnabled"; data: boolean } | { type: "newTree"; data: { game_description: string; entries: GameBrowserEntry[] } }
-
-export type GameInstall = { version: GameVersion; platform: GamePlatform; path: string }
-
-export type GamePlatform = "steam" | "epic" | "gog" | "microsoft"
-
-export type GameVersion = "h1" | "h2" | "h3"
+export type OutlineFormat = "Markdown" | "Html"
 
-export type GlobalEvent =
-	| { type: "setSeenAnnouncements"; data: string[] }
-	| { type: "loadWorkspace"; data: string }
-	| { type: "selectAndOpenFile" }
-	| { type: "selectTab"; data: string | null }
-	| { type: "removeTab"; data: string }
-	| { type: "saveTab"; data: string }
-	| { type: "uploadLogAndReport"; data: string }
-	| { type: "uploadLastPanic" }
-	| { type: "clearLastPanic" }
+export type OutfitBrowserEntry = {
+	repository_id: string
+	kind: OutfitBrowserEntryKind
+	name: string
+	/**
+	 * The brick containing the outfit kit for this outfit, if one was found
+	 */
+	outfit_kit_brick: string | null
+	character_templates: string[]
+	image: string | null
+}
 
-export type GlobalRequest =
-	| { type: "errorReport"; data: { error: string } }
-	| { type: "setWindowTitle"; data: string }
-	| { type: "initialiseDynamics"; data: { dynamics: Dynamics; seen_announcements: string[] } }
-	| { type: "createTab"; data: { id: string; name: string; editor_type: EditorType } }
-	| { type: "renameTab"; data: { id: string; new_name: string } }
-	| { type: "selectTab"; data: string }
-	| { type: "setTabUnsaved"; data: { id: string; unsaved: boolean } }
-	| { type: "removeTab"; data: string }
-	| { type: "computeJSONPatchAndSave"; data: { base: JsonValue; current: JsonValue; save_path: string; file_and_type: [string, string] } }
-	| { type: "requestLastPanicUpload" }
-	| { type: "logUploadRejected" }
+export type OutfitBrowserEntryKind = "Outfit" | "NPC"
 
-export type JsonPatchType = "MergePatch" | "JsonPatch"
+export type OutfitBrowserEvent = { type: "initialise" } | { type: "loadImage"; data: string } | { type: "openEntity"; data: string }
 
-export type JsonValue = null | boolean | number | string | JsonValue[] | { [key in string]: JsonValue }
+export type OutfitBrowserRequest = { type: "setEnabled"; data: boolean } | { type: "setEntries"; data: OutfitBrowserEntry[] } | { type: "setImage"; data: { hash: string; image_path: string } }
 
 export type OverriddenProperty = {
 	/**
@@ -426,7 +1718,39 @@ export type OverriddenProperty = {
 	value: JsonValue
 }
 
-export type PastableTemplate = { name: string; icon: string; pasteData: CopiedEntityData }
+export type OverrideKind = "propertyOverride" | "overrideDelete" | "pinConnectionOverride" | "pinConnectionOverrideDelete"
+
+export type OverrideListing = {
+	kind: OverrideKind
+	/**
+	 * The overridden property names, or the pins of a pin connection override
+	 */
+	detail: string
+	targets: OverrideTarget[]
+}
+
+/**
+ * An entity targeted by an override, which may be local or in an external scene.
+ */
+export type OverrideTarget = { entityId: string; externalScene: string | null; name: string | null }
+
+export type PartitionFilter =
+	| "All"
+	| "BaseGame"
+	/**
+	 * Only resources in the partition with the given ID, like chunk27
+	 */
+	| { Partition: string }
+
+export type PastableTemplate = {
+	name: string
+	icon: string
+	pasteData: CopiedEntityData
+	/**
+	 * Values the user is asked for when pasting the template
+	 */
+	parameters?: TemplateParameter[]
+}
 
 export type PastableTemplateCategory = { name: string; icon: string; templates: PastableTemplate[] }
 
@@ -449,6 +1773,26 @@ export type Patch = {
 	patchVersion: number
 }
 
+/**
+ * Another patch which modifies the same resource as a patch being saved.
+ */
+export type PatchConflict = {
+	/**
+	 * The file containing the other patch, if it has been saved
+	 */
+	file: string | null
+	/**
+	 * The editor containing the other patch, if it is open
+	 */
+	editor: string | null
+	operations: PatchConflictOperation[]
+}
+
+/**
+ * A piece of data which two patches both modify, and the operations they apply to it.
+ */
+export type PatchConflictOperation = { target: string; ours: JsonValue; theirs: JsonValue }
+
 export type PatchOperation =
 	| { SetRootEntity: string }
 	| { SetSubType: SubType }
@@ -480,6 +1824,70 @@ export type PatchOperation =
 	| { AddComment: CommentEntity }
 	| { RemoveComment: CommentEntity }
 
+/**
+ * What applying a single patch operation does to its target.
+ */
+export type PatchOperationOutcome =
+	/**
+	 * The operation changes the target
+	 */
+	| { type: "Applies" }
+	/**
+	 * The operation succeeds but leaves the target as it was, like setting a value to what it already is
+	 */
+	| { type: "NoOp" }
+	/**
+	 * The operation can't be applied, and why
+	 */
+	| { type: "Fails"; data: string }
+
+export type PatchOperationResult = {
+	/**
+	 * A short description of the operation, like `replace /Items/3/Name` or `SubEntityOperation abcdef0123456789`
+	 */
+	operation: string
+	outcome: PatchOperationOutcome
+}
+
+/**
+ * What a patch changes in the entity it's based on.
+ */
+export type PatchSummary = {
+	/**
+	 * ID, name
+	 */
+	added_entities: [string, string][]
+	/**
+	 * ID, name
+	 */
+	removed_entities: [string, string][]
+	/**
+	 * ID, original name, new name
+	 */
+	renamed_entities: [string, string, string][]
+	/**
+	 * The number of sub-entities which exist in both and were changed in any way
+	 */
+	modified_entity_count: number
+	/**
+	 * Property type, number of properties of that type which were added, changed or removed; most first
+	 */
+	modified_properties: [string, number][]
+	added_external_scenes: string[]
+	removed_external_scenes: string[]
+}
+
+/**
+ * The result of applying a patch file to the current game files without writing anything.
+ */
+export type PatchTest = {
+	/**
+	 * The hash or path of the resource the patch targets
+	 */
+	target: string
+	operations: PatchOperationResult[]
+}
+
 export type PinConnectionOverride = {
 	/**
 	 * The entity that will trigger the input on the other entity.
@@ -531,7 +1939,53 @@ export type PinConnectionOverrideDelete = {
 	value?: SimpleProperty | null
 }
 
-export type ProjectSettings = { customPaths: string[] }
+export type ProjectSettings = {
+	customPaths: string[]
+	/**
+	 * Glob-style patterns for files to leave out when packaging the mod
+	 */
+	packageIgnore: string[]
+	/**
+	 * Prefixes tried before each word when brute-forcing audio names, like `play_`
+	 */
+	audioNamePrefixes: string[]
+	/**
+	 * Whether to check sub-entity names for problems like duplicates and leftover default names
+	 */
+	lintEntityNames: boolean
+	/**
+	 * Regexes which sub-entity names are expected to match at least one of, if there are any
+	 */
+	entityNamePatterns: string[]
+	/**
+	 * Whether to refuse to write to the project, so that it can be browsed without risking accidental changes
+	 */
+	readOnly: boolean
+	/**
+	 * How many previous versions of each file to keep in the backup folder when saving over it; 0 turns backups off
+	 */
+	backupCount: number
+	/**
+	 * Content search and entity tree queries saved to be run again
+	 */
+	savedSearches: SavedSearch[]
+	/**
+	 * The framework languages that placeholder strings are added for when generating localisation keys
+	 */
+	localisationLanguages: string[]
+	/**
+	 * Whether to remove empty sub-entity keys and sort properties by name when saving entity.json files
+	 */
+	tidyEntitiesOnSave: boolean
+	/**
+	 * Saved entity patches with more operations than this are offered to be split into several files; 0 for no limit
+	 */
+	patchSplitMaxOperations: number
+	/**
+	 * Saved entity patches larger than this many megabytes are offered to be split into several files; 0 for no limit
+	 */
+	patchSplitMaxSize: number
+}
 
 /**
  * A property with a type and a value. Can be marked as post-init.
@@ -601,6 +2055,23 @@ export type PropertyOverrideConnection = {
 
 export type QNTransform = { rotation: Vec3; position: Vec3; scale?: Vec3 | null }
 
+/**
+ * An entry in an entity editor's recycle bin, as shown in the tree.
+ */
+export type RecycleBinEntry = {
+	/**
+	 * The position of the entry in the recycle bin, for restoring it
+	 */
+	index: number
+	id: string
+	name: string
+	/**
+	 * Including the entity itself
+	 */
+	entity_count: number
+	reference_count: number
+}
+
 /**
  * A reference to an entity.
  */
@@ -630,8 +2101,47 @@ export type RefWithConstantValue = {
 
 export type ReferenceFlags = { type?: ReferenceType; acquired: boolean; languageCode: number }
 
+export type ReferenceKind =
+	/**
+	 * Loaded at install time, along with the resource itself
+	 */
+	| "Install"
+	/**
+	 * A strong runtime reference, which keeps the referenced resource loaded
+	 */
+	| "Normal"
+	/**
+	 * A weak runtime reference, which doesn't load the referenced resource by itself
+	 */
+	| "Weak"
+
 export type ReferenceType = "install" | "normal" | "weak" | "media" | "state" | "entityType"
 
+/**
+ * A project file containing hashes which have been (or would be) remapped.
+ */
+export type RemappedFile = {
+	path: string
+	/**
+	 * Old hash or path as written, new hash or path, number of occurrences
+	 */
+	replacements: [string, string, number][]
+}
+
+/**
+ * The kind of value a repository property holds, judging by the items which have it.
+ */
+export type RepositoryColumnType =
+	| "String"
+	| "Number"
+	| "Boolean"
+	| "Array"
+	| "Object"
+	/**
+	 * Different items have different kinds of value for the property
+	 */
+	| "Mixed"
+
 export type RepositoryItemInformation =
 	| { type: "NPC"; data: { name: string } }
 	| { type: "Item"; data: { name: string } }
@@ -651,12 +2161,55 @@ export type RepositoryItemInformation =
 	| { type: "WeaponConfig" }
 	| { type: "Unknown" }
 
+export type RepositoryItemTemplate = "Weapon" | "Outfit" | "Prop"
+
 export type RepositoryPatchEditorEvent =
 	| { type: "initialise"; data: { id: string } }
 	| { type: "createRepositoryItem"; data: { id: string } }
+	| { type: "createRepositoryItemFromTemplate"; data: { id: string; template: RepositoryItemTemplate; name: string; create_unlockable: boolean } }
 	| { type: "resetModifications"; data: { id: string; item: string } }
 	| { type: "modifyItem"; data: { id: string; item: string; data: string } }
 	| { type: "selectItem"; data: { id: string; item: string } }
+	/**
+	 * Generate localisation keys for the item's Title and Description, add placeholder strings for them to
+	 * the manifest and set them on the item
+	 */
+	| { type: "generateLocalisation"; data: { id: string; item: string } }
+	| { type: "findEntitiesUsingItem"; data: { id: string; item: string } }
+	/**
+	 * List the properties of the repository for the table view
+	 */
+	| { type: "getTableColumns"; data: { id: string } }
+	/**
+	 * Get the rows of the table view; the filter is made of space-separated terms which all need to
+	 * match, either `Column:text` to match a column or plain text to match the ID or any shown column
+	 */
+	| { type: "getTable"; data: { id: string; columns: string[]; sort_by: string | null; sort_descending: boolean; filter: string } }
+	/**
+	 * Set a property of an item from the table view; the value is parsed according to the type of the
+	 * column, and an empty value removes properties which aren't strings
+	 */
+	| { type: "editTableCell"; data: { id: string; item: string; column: string; value: string } }
+	/**
+	 * Read a CSV file (with a header row) or a JSON array of objects to import new items from, and show
+	 * its columns to map them to repository properties
+	 */
+	| { type: "readImportFile"; data: { id: string; path: string } }
+	/**
+	 * Add a new item with a generated ID for each row of the file; cells are parsed like those edited in
+	 * the table view, and empty cells are left out
+	 */
+	| {
+			type: "importItems"
+			data: {
+				id: string
+				path: string
+				/**
+				 * Column of the file, repository property; columns mapped to an empty property are skipped
+				 */
+				mapping: [string, string][]
+			}
+	  }
 
 export type RepositoryPatchEditorRequest =
 	| { type: "setRepositoryItems"; data: { id: string; items: [string, RepositoryItemInformation][] } }
@@ -666,30 +2219,195 @@ export type RepositoryPatchEditorRequest =
 	| { type: "setMonacoContent"; data: { id: string; item: string; orig_data: string; data: string } }
 	| { type: "deselectMonaco"; data: { id: string } }
 	| { type: "modifyItemInformation"; data: { id: string; item: string; info: RepositoryItemInformation } }
+	/**
+	 * Property names and types, most common first
+	 */
+	| { type: "setTableColumns"; data: { id: string; columns: [string, RepositoryColumnType][] } }
+	| { type: "setTableRows"; data: { id: string; rows: RepositoryTableRow[] } }
+	| { type: "setTableCell"; data: { id: string; item: string; column: string; value: JsonValue | null } }
+	| {
+			type: "showImportMapping"
+			data: {
+				id: string
+				path: string
+				columns: string[]
+				row_count: number
+				/**
+				 * The first few rows, as text
+				 */
+				preview: string[][]
+				/**
+				 * Properties already used by items in the repository, to map the columns to
+				 */
+				properties: string[]
+			}
+	  }
+
+/**
+ * A repository item in the table view of a repository patch.
+ */
+export type RepositoryTableRow = {
+	item: string
+	modified: boolean
+	/**
+	 * The value of each shown column, or None if the item doesn't have the property
+	 */
+	cells: (JsonValue | null)[]
+}
 
 export type Request = { type: "tool"; data: ToolRequest } | { type: "editor"; data: EditorRequest } | { type: "global"; data: GlobalRequest }
 
+/**
+ * A hash or path from the input, resolved against the hash list and game files.
+ */
+export type ResolvedResource = {
+	/**
+	 * The hash or path as it appeared in the input
+	 */
+	input: string
+	hash: string
+	resource_type: string | null
+	path: string | null
+	hint: string | null
+	/**
+	 * The chunk and patch containing the latest copy of the resource, or None if it isn't in the game files
+	 */
+	chunk: string | null
+	/**
+	 * How many resources in the game files depend on this one, or None if the game files aren't loaded
+	 */
+	reverse_references: number | null
+}
+
 export type ResourceChangelogEntry = { operation: ResourceChangelogOperation; partition: string; patch: string; description: string }
 
 export type ResourceChangelogOperation = "Delete" | "Init" | "Edit"
 
+export type ResourceCollection = { name: string; entries: GameBrowserEntry[] }
+
 export type ResourceOverviewData =
 	| { type: "Generic" }
 	| { type: "Entity"; data: { blueprint_hash: string; blueprint_path_or_hint: string | null } }
-	| { type: "GenericRL"; data: { json: string } }
-	| { type: "Json"; data: { json: string } }
+	| { type: "GenericRL"; data: { json: string; total_lines: number | null } }
+	| { type: "Blueprint"; data: { json: string; factories: [string, string | null][]; entities: BlueprintEntityInfo[]; subsets: BlueprintSubsetInfo[]; pin_forwardings: BlueprintPinForwarding[] } }
+	| {
+			type: "Json"
+			data: {
+				json: string
+				/**
+				 * The kind of resource, if it can be opened in the JSON resource editor
+				 */
+				kind: JsonResourceKind | null
+			}
+	  }
 	| { type: "Ores"; data: { json: string } }
-	| { type: "Image"; data: { image_path: string; dds_data: [string, string] | null } }
-	| { type: "Audio"; data: { wav_path: string } }
-	| { type: "Mesh"; data: { obj: string; bounding_box: [number, number, number, number, number, number] } }
-	| { type: "MultiAudio"; data: { name: string; wav_paths: [string, string][] } }
+	| {
+			type: "Image"
+			data: {
+				image_path: string
+				dds_data: [string, string] | null
+				/**
+				 * The loaded project's replacement for the resource, rendered the same way, if it has one
+				 */
+				modded_image_path: string | null
+			}
+	  }
+	| {
+			type: "Audio"
+			data: {
+				wav_path: string
+				/**
+				 * None if the converted audio couldn't be read, in which case the frontend decodes it itself
+				 */
+				waveform: Waveform | null
+			}
+	  }
+	| {
+			type: "Mesh"
+			data: {
+				obj: string
+				bounding_box: [number, number, number, number, number, number]
+				/**
+				 * The skeleton of a weighted PRIM from the BORG it depends on; empty for other meshes
+				 */
+				bones: Bone[]
+				/**
+				 * The indices of the bones used by each object in the OBJ, in the same order; empty if there's no skeleton
+				 */
+				bone_influences: number[][]
+			}
+	  }
+	| {
+			type: "Scaleform"
+			data: {
+				/**
+				 * The extension of the embedded movie, either swf or gfx
+				 */
+				swf_extension: string
+				/**
+				 * In bytes
+				 */
+				swf_size: number
+				/**
+				 * The names and sizes (in bytes) of the files the movie loads, such as its images
+				 */
+				additional_files: [string, number][]
+			}
+	  }
+	| { type: "Skeleton"; data: { bones: Bone[] } }
+	| {
+			type: "Animation"
+			data: {
+				/**
+				 * In bytes
+				 */
+				size: number
+				/**
+				 * The animation resources and rigs this depends on, directly or through other animation resources
+				 */
+				dependencies: AnimationDependency[]
+			}
+	  }
+	| {
+			type: "MultiAudio"
+			data: {
+				name: string
+				wav_paths: [string, string][]
+				/**
+				 * The waveform of each of the audio objects, in the same order
+				 */
+				waveforms: (Waveform | null)[]
+				/**
+				 * The switch groups the event depends on, for choosing which of its audio objects would play
+				 */
+				switch_groups: WwiseSwitchGroup[]
+				/**
+				 * The IDs of the named Wwise objects the event's soundbanks use, with their names if known
+				 */
+				wwise_names: [number, string | null][]
+			}
+	  }
 	| { type: "Repository" }
 	| { type: "Unlockables" }
 	| { type: "HMLanguages"; data: { json: string } }
 	| { type: "LocalisedLine"; data: { languages: [string, string][] } }
 	| { type: "MaterialInstance"; data: { json: string } }
 	| { type: "MaterialEntity"; data: { json: string } }
-	| { type: "SoundDefinitions"; data: { json: string } }
+	| {
+			type: "SoundDefinitions"
+			data: {
+				json: string
+				/**
+				 * The IDs of the named Wwise objects the definitions' events use, with their names if known
+				 */
+				wwise_names: [number, string | null][]
+			}
+	  }
+	| { type: "BehaviourTree"; data: { tree: BehaviourTreeNode } }
+	/**
+	 * The constituent resources are sent separately, since they can be edited for ASETs in the project
+	 */
+	| { type: "AssetSet" }
 
 export type ResourceOverviewEvent =
 	| { type: "initialise"; data: { id: string } }
@@ -708,30 +2426,179 @@ export type ResourceOverviewEvent =
 	| { type: "extractSpecificMultiWav"; data: { id: string; index: number } }
 	| { type: "extractORESAsJson"; data: { id: string } }
 	| { type: "extractAsHMLanguages"; data: { id: string } }
+	| { type: "extractAsObj"; data: { id: string } }
+	/**
+	 * Extract a BORG's bones as JSON
+	 */
+	| { type: "extractAsSkeletonJson"; data: { id: string } }
+	/**
+	 * Extract an animation resource and the animation resources and rigs it depends on, with their meta
+	 * files, to a folder
+	 */
+	| { type: "extractAnimationWithDependencies"; data: { id: string } }
+	/**
+	 * Extract the movie embedded in a GFXF, and the files it loads, to a folder
+	 */
+	| { type: "extractScaleformMovie"; data: { id: string } }
+	/**
+	 * Replace the movie embedded in a GFXF with a SWF or GFx file, saving the repacked GFXF to the project
+	 */
+	| { type: "repackScaleformMovie"; data: { id: string } }
+	/**
+	 * Export a behaviour tree as a Graphviz DOT graph
+	 */
+	| { type: "exportBehaviourTreeAsDot"; data: { id: string } }
+	| { type: "addToCollection"; data: { id: string; collection: string } }
+	| { type: "getGenericRLPage"; data: { id: string; start_line: number } }
+	| { type: "searchGenericRL"; data: { id: string; query: string } }
+	| { type: "openWithExternalTool"; data: { id: string; tool: string } }
+	| { type: "extractWithPreset"; data: { id: string; preset: string } }
+	/**
+	 * Replace a GFXI with an image, resized and encoded to match the original
+	 */
+	| { type: "replaceWithImage"; data: { id: string } }
+	/**
+	 * Render a TEXT's low mip separately from its TEXD's high mip, to check that they match
+	 */
+	| { type: "compareTextureMips"; data: { id: string } }
+	/**
+	 * Compare the resource at an entry of its changelog with the copy it replaced, as converted JSON
+	 */
+	| { type: "getChangelogDiff"; data: { id: string; index: number } }
+	/**
+	 * Change the flag of a dependency in the resource's JSON meta file in the project
+	 */
+	| { type: "setProjectDependencyFlag"; data: { id: string; index: number; flag: DecodedReferenceFlag } }
+	/**
+	 * Move a constituent resource of an ASET in the project to a different position
+	 */
+	| { type: "moveAssetSetEntry"; data: { id: string; from: number; to: number } }
+	/**
+	 * Add a constituent resource to the end of an ASET in the project, by hash or path
+	 */
+	| { type: "addAssetSetEntry"; data: { id: string; resource: string } }
+	| { type: "removeAssetSetEntry"; data: { id: string; index: number } }
+	/**
+	 * Convert edited material entity JSON back to a MATT and MATB replacing those being viewed, and write
+	 * them to the project
+	 */
+	| { type: "saveMaterialEntity"; data: { id: string; json: string } }
 
-export type ResourceOverviewRequest = {
-	type: "initialise"
-	data: {
-		id: string
-		hash: string
-		filetype: string
-		chunk_patch: string
-		path_or_hint: string | null
-		/**
-		 * Hash, type, path/hint, flag, is actually in current game version
-		 */
-		dependencies: [string, string, string | null, string, boolean][]
-		/**
-		 * Hash, type, path/hint
-		 */
-		reverse_dependencies: [string, string, string | null][]
-		changelog: ResourceChangelogEntry[]
-		data: ResourceOverviewData
-	}
-}
+export type ResourceOverviewRequest =
+	| {
+			type: "initialise"
+			data: {
+				id: string
+				hash: string
+				filetype: string
+				chunk_patch: string
+				/**
+				 * The DLC the resource belongs to, or None if it's part of the base game
+				 */
+				dlc: string | null
+				path_or_hint: string | null
+				/**
+				 * Hash, type, path/hint, flag, is actually in current game version, decoded flag
+				 */
+				dependencies: [string, string, string | null, string, boolean, DecodedReferenceFlag | null][]
+				/**
+				 * Hash, type, path/hint
+				 */
+				reverse_dependencies: [string, string, string | null][]
+				changelog: ResourceChangelogEntry[]
+				/**
+				 * Names of the configured external tools the resource can be opened with
+				 */
+				external_tools: string[]
+				/**
+				 * Names of the configured extraction presets
+				 */
+				extraction_presets: string[]
+				/**
+				 * What can be done with the resource's type in the loaded game version
+				 */
+				capabilities: ResourceTypeCapabilities
+				data: ResourceOverviewData
+			}
+	  }
+	| {
+			type: "setGenericRLPage"
+			data: {
+				id: string
+				/**
+				 * Zero-indexed
+				 */
+				start_line: number
+				json: string
+			}
+	  }
+	| {
+			type: "setGenericRLSearchResults"
+			data: {
+				id: string
+				/**
+				 * Zero-indexed line number, trimmed line
+				 */
+				results: [number, string][]
+			}
+	  }
+	| { type: "setTextureMipComparison"; data: { id: string; comparison: TextureMipComparison } }
+	/**
+	 * The converted JSON of the copy replaced at a changelog entry and the copy it was replaced with; either
+	 * is None if the resource wasn't present
+	 */
+	| { type: "setChangelogDiff"; data: { id: string; index: number; original: string | null; modified: string | null } }
+	/**
+	 * The dependencies of the resource's JSON meta file in the project, if it has one
+	 */
+	| {
+			type: "setProjectDependencyFlags"
+			data: {
+				id: string
+				meta_path: string
+				/**
+				 * Hash, decoded flag
+				 */
+				dependencies: [string, DecodedReferenceFlag][]
+			}
+	  }
+	/**
+	 * The constituent resources of an ASET, from its meta file in the project if it has one and otherwise
+	 * from the game files; only those in the project are editable
+	 */
+	| {
+			type: "setAssetSetEntries"
+			data: {
+				id: string
+				/**
+				 * Hash, type, path or hint
+				 */
+				entries: [string, string, string | null][]
+				editable: boolean
+			}
+	  }
 
 export type ResourceReferenceProxy = string | { resource: string; flags: ReferenceFlags }
 
+/**
+ * What GlacierKit can do with a resource type in a particular game version.
+ */
+export type ResourceTypeCapabilities = {
+	resource_type: string
+	/**
+	 * Whether the resource overview can show the resource's contents
+	 */
+	preview: boolean
+	/**
+	 * Whether the resource can be extracted in a more useful format than its binary data
+	 */
+	convert: boolean
+	/**
+	 * Whether the resource can be rebuilt from a converted or edited form
+	 */
+	rebuild: boolean
+}
+
 export type ReverseReference = { from: string; data: ReverseReferenceData }
 
 export type ReverseReferenceData =
@@ -750,8 +2617,45 @@ export type Rotation = { yaw: number; pitch: number; roll: number }
 
 export type RpkgResourceReference = { hash: string; flag: string }
 
+/**
+ * A named query saved to the project, so that it can be run again without re-entering it.
+ */
+export type SavedSearch = {
+	name: string
+	query: SavedSearchQuery
+	/**
+	 * Whether to run the search again whenever files in the project change
+	 */
+	rerunOnChange: boolean
+}
+
+export type SavedSearchQuery =
+	/**
+	 * A search of the game files, with the same options as the content search tool
+	 */
+	| { type: "contentSearch"; data: { query: string; filetypes: string[]; useQnFormat: boolean; partitions: string[]; convertWithResourcelib: boolean } }
+	/**
+	 * A search of the entity tree of whichever entity editor is focused
+	 */
+	| { type: "entityTree"; data: { query: string } }
+
 export type SearchFilter = "All" | "Templates" | "Classes" | "Models" | "Textures" | "Sound"
 
+/**
+ * Where memory and time are going in the current session, to help track down a session growing unexpectedly large.
+ */
+export type SessionDiagnostics = {
+	/**
+	 * Largest first
+	 */
+	editors: EditorMemoryUsage[]
+	/**
+	 * Name and approximate size in bytes of each shared cache
+	 */
+	caches: [string, number][]
+	tasks: TaskTiming[]
+}
+
 /**
  * A platform, property name, and value to set on an entity.
  */
@@ -768,9 +2672,125 @@ export type SettingsEvent =
 	| { type: "changeExtractModdedFiles"; data: boolean }
 	| { type: "changeColourblind"; data: boolean }
 	| { type: "changeEditorConnection"; data: boolean }
+	| { type: "changeEditorConnectionGames"; data: EditorConnectionGame[] }
+	/**
+	 * Choose which game entity selections and property changes are sent to, or None for every connected game
+	 */
+	| { type: "setEditorConnectionTarget"; data: string | null }
+	/**
+	 * Wait for another instance with the same pairing code to connect and sync entity changes with it
+	 */
+	| {
+			type: "hostCollaboration"
+			data: {
+				/**
+				 * Shown to the other instance's user
+				 */
+				name: string
+				/**
+				 * Pairing code which the other instance has to give to connect
+				 */
+				secret: string
+				/**
+				 * Address of the network interface to listen on, or `0.0.0.0` for all of them
+				 */
+				interface: string
+				port: number
+			}
+	  }
+	/**
+	 * Connect to another instance which is hosting, at an address like `192.168.1.2:46735`
+	 */
+	| {
+			type: "connectCollaboration"
+			data: {
+				/**
+				 * Shown to the other instance's user
+				 */
+				name: string
+				/**
+				 * Pairing code which the host was given
+				 */
+				secret: string
+				address: string
+			}
+	  }
+	| { type: "stopCollaboration" }
+	| { type: "reconnectEditorConnection" }
+	| { type: "changeOfflineMode"; data: boolean }
+	| { type: "changeExternalTools"; data: ExternalTool[] }
+	| { type: "changeExtractionPresets"; data: ExtractionPreset[] }
+	| { type: "changeWemEncoder"; data: WemEncoder | null }
+	| { type: "changeEntityCacheMaxEntries"; data: number }
+	/**
+	 * In megabytes
+	 */
+	| { type: "changeEntityCacheMaxMemory"; data: number }
+	/**
+	 * Hashes or paths of resources to check for changes whenever the game files are updated
+	 */
+	| { type: "changeWatchedResources"; data: string[] }
+	/**
+	 * Clear the cache of entities extracted from the game files and the in-memory intellisense caches
+	 */
+	| { type: "clearCaches" }
+	/**
+	 * Measure the memory taken up by each editor and cache, and get the timings of recent tasks
+	 */
+	| { type: "getDiagnostics" }
 	| { type: "changeCustomPaths"; data: string[] }
+	| { type: "changePackageIgnore"; data: string[] }
+	| { type: "changeAudioNamePrefixes"; data: string[] }
+	| { type: "changeLintEntityNames"; data: boolean }
+	| { type: "changeEntityNamePatterns"; data: string[] }
+	| { type: "changeTidyEntitiesOnSave"; data: boolean }
+	| { type: "changeLocalisationLanguages"; data: string[] }
+	| { type: "changeReadOnly"; data: boolean }
+	| { type: "changeBackupCount"; data: number }
+	| { type: "changePatchSplitMaxOperations"; data: number }
+	| { type: "changePatchSplitMaxSize"; data: number }
+	/**
+	 * Whether the loaded project is allowed to run external programs
+	 */
+	| { type: "changeProjectTrusted"; data: boolean }
+	| { type: "packageMod"; data: VersionBump }
+	| { type: "findOrphanedFiles" }
+	/**
+	 * Paths relative to the project
+	 */
+	| { type: "archiveOrphanedFiles"; data: string[] }
+	/**
+	 * Find resources and blobs changed by both the project and mods enabled in the framework
+	 */
+	| { type: "findModConflicts" }
 
-export type SettingsRequest = { type: "initialise"; data: { game_installs: GameInstall[]; settings: AppSettings } } | { type: "changeProjectSettings"; data: ProjectSettings }
+export type SettingsRequest =
+	| { type: "initialise"; data: { game_installs: GameInstall[]; settings: AppSettings } }
+	| { type: "changeProjectSettings"; data: ProjectSettings }
+	/**
+	 * Whether the loaded project is allowed to run external programs
+	 */
+	| { type: "setProjectTrusted"; data: boolean }
+	| {
+			type: "setEditorConnectionState"
+			data: {
+				games: EditorConnectionDiagnostics[]
+				/**
+				 * The game entity selections and property changes are sent to, or None for every connected game
+				 */
+				target: string | null
+			}
+	  }
+	/**
+	 * Forward-slash separated paths relative to the project of files which aren't referenced by the manifest
+	 */
+	| { type: "setOrphanedFiles"; data: string[] }
+	| { type: "setCollaborationStatus"; data: CollaborationStatus }
+	/**
+	 * Conflicts with mods enabled in the framework, most severe first
+	 */
+	| { type: "setModConflicts"; data: ModConflict[] }
+	| { type: "showDiagnostics"; data: SessionDiagnostics }
 
 /**
  * A simple property.
@@ -902,26 +2922,181 @@ export type SubEntityOperation =
 
 export type SubType = "brick" | "scene" | "template"
 
-export type TextEditorEvent = { type: "initialise"; data: { id: string } } | { type: "updateContent"; data: { id: string; content: string } }
+/**
+ * How long a task took, or has taken so far if it's still running.
+ */
+export type TaskTiming = { name: string; duration_ms: number; running: boolean }
+
+/**
+ * A value substituted into a template when it's pasted. Any string in the paste data which is exactly `{{name}}` is
+ * replaced with the value, and text and repository ID values also replace `{{name}}` within longer strings.
+ */
+export type TemplateParameter = { name: string; kind: TemplateParameterKind; default: string | null }
+
+export type TemplateParameterKind =
+	| "text"
+	/**
+	 * Three numbers separated by commas or spaces, substituted as an object with x, y and z
+	 */
+	| "position"
+	/**
+	 * A repository GUID
+	 */
+	| "repositoryId"
+
+/**
+ * A problem in a text file, with 1-indexed line and column positions.
+ */
+export type TextDiagnostic = { message: string; start_line: number; start_column: number; end_line: number; end_column: number }
+
+export type TextEditorEvent =
+	| { type: "initialise"; data: { id: string } }
+	| { type: "updateContent"; data: { id: string; content: string } }
+	/**
+	 * Search the content held by the backend, for files too large for Monaco's own search; the ID is
+	 * sent back with the results
+	 */
+	| { type: "find"; data: { id: string; search_id: string; query: string; regex: boolean; match_case: boolean } }
+	/**
+	 * Replace every match of a search, or only the one at the given byte offset
+	 */
+	| { type: "replace"; data: { id: string; query: string; regex: boolean; match_case: boolean; replacement: string; offset: number | null } }
 
-export type TextEditorRequest = { type: "replaceContent"; data: { id: string; content: string } } | { type: "setFileType"; data: { id: string; file_type: TextFileType } }
+export type TextEditorRequest =
+	| { type: "replaceContent"; data: { id: string; content: string } }
+	| { type: "setFileType"; data: { id: string; file_type: TextFileType } }
+	| { type: "setDiagnostics"; data: { id: string; diagnostics: TextDiagnostic[] } }
+	/**
+	 * A chunk of the matches of a search; more follow until one is marked as done
+	 */
+	| {
+			type: "addSearchResults"
+			data: {
+				id: string
+				search_id: string
+				matches: TextSearchMatch[]
+				done: boolean
+				/**
+				 * Whether there were too many matches to list them all
+				 */
+				truncated: boolean
+			}
+	  }
 
 export type TextFileType = "Json" | "ManifestJson" | "PlainText" | "Markdown"
 
+/**
+ * A match of a search in a text editor.
+ */
+export type TextSearchMatch = {
+	/**
+	 * Byte offset of the match in the content, which identifies it when replacing it
+	 */
+	offset: number
+	/**
+	 * 1-based, like Monaco's
+	 */
+	line: number
+	/**
+	 * 1-based, in characters
+	 */
+	column: number
+	/**
+	 * In characters
+	 */
+	length: number
+	/**
+	 * The part of the line around the match
+	 */
+	preview: string
+}
+
+/**
+ * A TEXT's low mip rendered separately from the high mip in its TEXD, with any signs that they don't match.
+ */
+export type TextureMipComparison = {
+	format: string
+	low_image_path: string
+	/**
+	 * Width, height
+	 */
+	low_resolution: [number, number]
+	/**
+	 * The TEXD's hash, if the TEXT has one
+	 */
+	texd: string | null
+	/**
+	 * None if there is no TEXD or it couldn't be read
+	 */
+	high_image_path: string | null
+	/**
+	 * Width, height
+	 */
+	high_resolution: [number, number] | null
+	warnings: string[]
+}
+
 export type ToolEvent =
 	| { type: "fileBrowser"; data: FileBrowserEvent }
 	| { type: "gameBrowser"; data: GameBrowserEvent }
 	| { type: "settings"; data: SettingsEvent }
 	| { type: "contentSearch"; data: ContentSearchEvent }
+	| { type: "outfitBrowser"; data: OutfitBrowserEvent }
+	| { type: "git"; data: GitEvent }
+	| { type: "extractionQueue"; data: ExtractionQueueEvent }
+	| { type: "audioNames"; data: AudioNamesEvent }
+	| { type: "converters"; data: ConvertersEvent }
+	| { type: "hashRemapping"; data: HashRemappingEvent }
+	| { type: "hashResolution"; data: HashResolutionEvent }
+	| { type: "audioImport"; data: AudioImportEvent }
+	| { type: "crashAnalysis"; data: CrashAnalysisEvent }
 
 export type ToolRequest =
 	| { type: "fileBrowser"; data: FileBrowserRequest }
 	| { type: "gameBrowser"; data: GameBrowserRequest }
 	| { type: "settings"; data: SettingsRequest }
 	| { type: "contentSearch"; data: ContentSearchRequest }
+	| { type: "outfitBrowser"; data: OutfitBrowserRequest }
+	| { type: "git"; data: GitRequest }
+	| { type: "extractionQueue"; data: ExtractionQueueRequest }
+	| { type: "audioNames"; data: AudioNamesRequest }
+	| { type: "converters"; data: ConvertersRequest }
+	| { type: "hashRemapping"; data: HashRemappingRequest }
+	| { type: "hashResolution"; data: HashResolutionRequest }
+	| { type: "audioImport"; data: AudioImportRequest }
+	| { type: "crashAnalysis"; data: CrashAnalysisRequest }
 
 export type Transform = { position: Vec3; rotation: Rotation; scale: Vec3 }
 
+/**
+ * A conversion between two representations of a property's value.
+ */
+export type UnitConversion =
+	| "DegreesToRadians"
+	| "RadiansToDegrees"
+	| "MetresToCentimetres"
+	| "CentimetresToMetres"
+	/**
+	 * An SVector3 or SVector4 of colour channels from 0 to 1 to an SColorRGB or SColorRGBA
+	 */
+	| "FloatsToHexColour"
+	/**
+	 * An SColorRGB or SColorRGBA to an SVector3 or SVector4 of colour channels from 0 to 1
+	 */
+	| "HexColourToFloats"
+
+/**
+ * Details shown alongside an unlockable, taken from the repository item it unlocks where possible.
+ */
+export type UnlockableDecoration = {
+	name: string | null
+	subtitle: string | null
+	/**
+	 * A preview of the unlockable's image, if it's an image resource or a file in one of the project's blobs folders
+	 */
+	image_path: string | null
+}
+
 export type UnlockableInformation =
 	| { type: "Access"; data: { id: string | null } }
 	| { type: "EvergreenMastery"; data: { id: string | null } }
@@ -940,6 +3115,11 @@ export type UnlockablesPatchEditorEvent =
 	| { type: "resetModifications"; data: { id: string; unlockable: string } }
 	| { type: "modifyUnlockable"; data: { id: string; unlockable: string; data: string } }
 	| { type: "selectUnlockable"; data: { id: string; unlockable: string } }
+	/**
+	 * Generate a localisation key for the unlockable's display name, add placeholder strings for it to the
+	 * manifest and set it on the unlockable
+	 */
+	| { type: "generateLocalisation"; data: { id: string; unlockable: string } }
 
 export type UnlockablesPatchEditorRequest =
 	| { type: "setUnlockables"; data: { id: string; unlockables: [string, UnlockableInformation][] } }
@@ -949,5 +3129,54 @@ export type UnlockablesPatchEditorRequest =
 	| { type: "setMonacoContent"; data: { id: string; unlockable: string; orig_data: string; data: string } }
 	| { type: "deselectMonaco"; data: { id: string } }
 	| { type: "modifyUnlockableInformation"; data: { id: string; unlockable: string; info: UnlockableInformation } }
+	/**
+	 * Merged with the existing decorations
+	 */
+	| { type: "setDecorations"; data: { id: string; decorations: [string, UnlockableDecoration][] } }
 
 export type Vec3 = { x: number; y: number; z: number }
+
+export type VersionBump = "None" | "Patch" | "Minor" | "Major"
+
+/**
+ * A WAV file in a folder being imported, with the WWEM it replaces if that can be worked out from its name.
+ */
+export type WavFile = { name: string; target: string | null }
+
+/**
+ * The peaks of some audio, so the frontend can draw its waveform without decoding it.
+ */
+export type Waveform = {
+	/**
+	 * The highest amplitude of any channel in each equally sized window of the audio, from 0 to 1
+	 */
+	peaks: number[]
+	/**
+	 * In seconds
+	 */
+	duration: number
+}
+
+/**
+ * A switch (or state) group used by a Wwise event. Audio objects which aren't listed under any of the group's switches
+ * play regardless of which switch is set.
+ */
+/**
+ * A user-configured program which converts a WAV file to WEM, like WwiseConsole or a script wrapping it.
+ */
+export type WemEncoder = {
+	path: string
+	/**
+	 * The arguments to pass to the encoder; `{input}` is replaced with the WAV file and `{output}` with where the WEM
+	 * should be written. Arguments are separated by spaces, and can be wrapped in double quotes to include spaces.
+	 */
+	arguments: string
+}
+
+export type WwiseSwitchGroup = {
+	name: string
+	/**
+	 * Switch name, indices of the audio objects which play for it
+	 */
+	switches: [string, number[]][]
+}
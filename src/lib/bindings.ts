@@ -10,6 +10,15 @@ async event(event: Event) : Promise<void> {
 },
 async showInFolder(path: string) : Promise<void> {
     await TAURI_INVOKE("show_in_folder", { path });
+},
+async isOfflineMode() : Promise<boolean> {
+    return await TAURI_INVOKE("is_offline_mode");
+},
+/**
+ * Get what GlacierKit can do with each resource type in the loaded game version, or nothing if no game is loaded.
+ */
+async getLoadedResourceCapabilities() : Promise<ResourceTypeCapabilities[] | null> {
+    return await TAURI_INVOKE("get_loaded_resource_capabilities");
 }
 }
 
@@ -23,22 +32,233 @@ async showInFolder(path: string) : Promise<void> {
 
 /** user-defined types **/
 
-export type ContentSearchEvent = { type: "search"; data: [string, string[], boolean, string[]] }
+/**
+ * A pattern to lay out copies of an entity in, relative to the group they're parented to.
+ */
+export type ArrayPattern = 
+/**
+ * Copies spaced evenly along a line, starting at the group's origin
+ */
+{ type: "Line"; data: { count: number; spacing: Vec3 } } | 
+/**
+ * Copies in columns along X and rows along Y, starting at the group's origin
+ */
+{ type: "Grid"; data: { columns: number; rows: number; column_spacing: number; row_spacing: number } } | 
+/**
+ * Copies spaced evenly around a circle in the XY plane, centred on the group's origin; if facing outwards, each
+ * copy is also turned about Z by its angle around the circle
+ */
+{ type: "Circle"; data: { count: number; radius: number; face_outwards: boolean } }
+export type AudioImportEvent = 
+/**
+ * Pick a folder and list the WAV files in it
+ */
+{ type: "selectFolder" } | 
+/**
+ * Convert WAV files in a folder to WEM and add them to the project as replacements
+ */
+{ type: "import"; data: { folder: string; 
+/**
+ * File name, hash or path of the WWEM it replaces
+ */
+mapping: [string, string][] } }
+export type AudioNamesEvent = { type: "initialise" } | { type: "gatherTargets"; data: string } | { type: "bruteForce"; data: { targets: number[]; words: string[]; wordlist: string | null } } | { type: "removeName"; data: number }
+/**
+ * The partition a new entity should be loaded in, worked out from the partition of the scenario it's used in.
+ */
+export type ChunkAssignment = { 
+/**
+ * The entity's template as written in packagedefinition.txt, like `[assembly:/_pro/my_mod.entitytemplate]`
+ */
+entity_path: string; 
+/**
+ * The partition as named in packagedefinition.txt, like `season2`
+ */
+partition: string; 
+/**
+ * The chunk the partition's resources are stored in
+ */
+chunk: number; 
+/**
+ * Whether the manifest already loads the entity in this partition
+ */
+already_assigned: boolean }
+export type ContentSearchEvent = 
+/**
+ * Query, resource types, whether to search entities in QuickEntity format, partitions, and whether to
+ * convert types without dedicated handling to ResourceLib JSON before matching rather than searching
+ * their raw data
+ */
+{ type: "search"; data: [string, string[], boolean, string[], boolean] } | 
+/**
+ * Save a search to the project, replacing any saved search with the same name
+ */
+{ type: "saveSearch"; data: SavedSearch } | { type: "deleteSavedSearch"; data: string } | { type: "runSavedSearch"; data: string }
 export type ContentSearchResultsEvent = { type: "initialise"; data: { id: string } } | { type: "openResourceOverview"; data: { id: string; hash: string } }
+export type ConvertersEvent = { type: "convert"; data: string }
 export type CopiedEntityData = { 
 /**
  * Which entity has been copied (and should be parented to the selection when pasting).
  */
 rootEntity: string; data: Partial<{ [key in string]: SubEntity }> }
+export type CrashAnalysisEvent = 
+/**
+ * Pick a crash log or minidump and analyse it
+ */
+{ type: "selectFile" } | { type: "analyse"; data: string } | { type: "openFile"; data: string } | { type: "openResourceOverview"; data: string }
+/**
+ * A dependency flag, decoded from its modern (HITMAN 3) form.
+ */
+export type DecodedReferenceFlag = { kind: ReferenceKind; 
+/**
+ * Whether the referenced resource is acquired by the game at runtime
+ */
+acquired: boolean; 
+/**
+ * The language the reference is for, or None if it applies to every language
+ */
+languageCode: number | null }
+export type DialogueContainerType = "WavFile" | "Random" | "Switch" | "Sequence"
+export type DialogueEditorEvent = { type: "initialise"; data: { id: string } } | { type: "addContainer"; data: { id: string; parent: number[]; container_type: DialogueContainerType } } | { type: "removeContainer"; data: { id: string; path: number[] } } | { type: "moveContainer"; data: { id: string; path: number[]; new_parent: number[]; index: number } } | { type: "updateContainer"; data: { id: string; path: number[]; properties: JsonValue } }
+/**
+ * A file dropped into the window and how it would be imported into the project.
+ */
+export type DroppedFile = { path: string; kind: DroppedFileKind; 
+/**
+ * Where the file will be written, relative to the project, or None if it can't be imported
+ */
+destination: string | null; 
+/**
+ * Shown alongside the file, like why it can't be imported or what to do after importing it
+ */
+note: string | null }
+export type DroppedFileKind = 
+/**
+ * A ResourceLib TEMP JSON, which is converted to a QuickEntity entity along with its TBLU
+ */
+"ResourceLibEntity" | "Entity" | 
+/**
+ * A TGA texture and its meta file
+ */
+"Texture" | "Audio" | "Unsupported"
 export type EditorConnectionEvent = { type: "entitySelected"; data: [string, string] } | { type: "entityTransformUpdated"; data: [string, string, QNTransform] } | { type: "entityPropertyChanged"; data: [string, string, string, string, JsonValue] }
-export type EditorEvent = { type: "text"; data: TextEditorEvent } | { type: "entity"; data: EntityEditorEvent } | { type: "resourceOverview"; data: ResourceOverviewEvent } | { type: "repositoryPatch"; data: RepositoryPatchEditorEvent } | { type: "unlockablesPatch"; data: UnlockablesPatchEditorEvent } | { type: "contentSearchResults"; data: ContentSearchResultsEvent }
-export type EntityEditorEvent = { type: "general"; data: EntityGeneralEvent } | { type: "tree"; data: EntityTreeEvent } | { type: "monaco"; data: EntityMonacoEvent } | { type: "metaPane"; data: EntityMetaPaneEvent } | { type: "metadata"; data: EntityMetadataEvent } | { type: "overrides"; data: EntityOverridesEvent }
-export type EntityGeneralEvent = { type: "setShowReverseParentRefs"; data: { editor_id: string; show_reverse_parent_refs: boolean } } | { type: "setShowChangesFromOriginal"; data: { editor_id: string; show_changes_from_original: boolean } }
-export type EntityMetaPaneEvent = { type: "jumpToReference"; data: { editor_id: string; reference: string } } | { type: "setNotes"; data: { editor_id: string; entity_id: string; notes: string } }
+/**
+ * A game running the SDK's editor server, which the editor connection can connect to.
+ */
+export type EditorConnectionGame = { 
+/**
+ * Shown in the UI to tell connections apart
+ */
+name: string; 
+/**
+ * The WebSocket URL of the game's editor server
+ */
+url: string }
+export type EditorEvent = { type: "text"; data: TextEditorEvent } | { type: "entity"; data: EntityEditorEvent } | { type: "resourceOverview"; data: ResourceOverviewEvent } | { type: "repositoryPatch"; data: RepositoryPatchEditorEvent } | { type: "unlockablesPatch"; data: UnlockablesPatchEditorEvent } | { type: "contentSearchResults"; data: ContentSearchResultsEvent } | { type: "enum"; data: EnumEditorEvent } | { type: "dialogue"; data: DialogueEditorEvent } | { type: "jsonPatch"; data: JsonPatchEditorEvent } | { type: "jsonResource"; data: JsonResourceEditorEvent }
+export type EntityEditorEvent = { type: "general"; data: EntityGeneralEvent } | { type: "tree"; data: EntityTreeEvent } | { type: "monaco"; data: EntityMonacoEvent } | { type: "metaPane"; data: EntityMetaPaneEvent } | { type: "metadata"; data: EntityMetadataEvent } | { type: "overrides"; data: EntityOverridesEvent } | { type: "stats"; data: EntityStatsEvent } | { type: "problems"; data: EntityProblemsEvent } | { type: "subsets"; data: EntitySubsetsEvent }
+export type EntityGeneralEvent = { type: "setShowReverseParentRefs"; data: { editor_id: string; show_reverse_parent_refs: boolean } } | { type: "setShowChangesFromOriginal"; data: { editor_id: string; show_changes_from_original: boolean } } | { type: "minimisePatch"; data: { editor_id: string } } | 
+/**
+ * Save an outline of the entity tree, with names, factories and simple property values, as a
+ * Markdown or HTML document
+ */
+{ type: "exportOutline"; data: { editor_id: string; format: OutlineFormat } }
+export type EntityMetaPaneEvent = { type: "jumpToReference"; data: { editor_id: string; reference: string } } | { type: "setNotes"; data: { editor_id: string; entity_id: string; notes: string } } | { type: "requestOverrides"; data: { editor_id: string } } | { type: "jumpToOverrideTarget"; data: { editor_id: string; entity_id: string; external_scene: string | null } } | { type: "createPropertyAlias"; data: { editor_id: string; entity_id: string; aliased_name: string; alias: PropertyAlias } } | 
+/**
+ * Replace the alias at the given index among those with the given name, which may be renamed
+ */
+{ type: "editPropertyAlias"; data: { editor_id: string; entity_id: string; aliased_name: string; index: number; new_aliased_name: string; alias: PropertyAlias } } | { type: "deletePropertyAlias"; data: { editor_id: string; entity_id: string; aliased_name: string; index: number } } | 
+/**
+ * Add an exposed entity, or replace the one with the same name; the entities it refers to are given
+ * as local entity IDs or names
+ */
+{ type: "setExposedEntity"; data: { editor_id: string; entity_id: string; exposed_name: string; is_array: boolean; refers_to: string[] } } | { type: "removeExposedEntity"; data: { editor_id: string; entity_id: string; exposed_name: string } } | 
+/**
+ * Add an exposed interface, or replace the one with the same name; the entity implementing it is
+ * given as a local entity ID or name
+ */
+{ type: "setExposedInterface"; data: { editor_id: string; entity_id: string; interface: string; refers_to: string } } | { type: "removeExposedInterface"; data: { editor_id: string; entity_id: string; interface: string } }
 export type EntityMetadataEvent = { type: "initialise"; data: { editor_id: string } } | { type: "setFactoryHash"; data: { editor_id: string; factory_hash: string } } | { type: "setBlueprintHash"; data: { editor_id: string; blueprint_hash: string } } | { type: "setRootEntity"; data: { editor_id: string; root_entity: string } } | { type: "setSubType"; data: { editor_id: string; sub_type: SubType } } | { type: "setExternalScenes"; data: { editor_id: string; external_scenes: string[] } }
-export type EntityMonacoEvent = { type: "updateContent"; data: { editor_id: string; entity_id: string; content: string } } | { type: "followReference"; data: { editor_id: string; reference: string } } | { type: "openFactory"; data: { editor_id: string; factory: string } } | { type: "signalPin"; data: { editor_id: string; entity_id: string; pin: string; output: boolean } } | { type: "openResourceOverview"; data: { editor_id: string; resource: string } }
+export type EntityMonacoEvent = { type: "updateContent"; data: { editor_id: string; entity_id: string; content: string } } | { type: "followReference"; data: { editor_id: string; reference: string } } | { type: "openFactory"; data: { editor_id: string; factory: string } } | { type: "signalPin"; data: { editor_id: string; entity_id: string; pin: string; output: boolean } } | { type: "openResourceOverview"; data: { editor_id: string; resource: string } } | { type: "followResource"; data: { editor_id: string; resource: string } } | 
+/**
+ * Convert a property of a sub-entity to another unit or representation, like degrees to radians
+ */
+{ type: "convertProperty"; data: { editor_id: string; entity_id: string; property: string; conversion: UnitConversion } } | 
+/**
+ * Set an SColorRGB or SColorRGBA property to a colour chosen in the colour picker
+ */
+{ type: "setColour"; data: { editor_id: string; entity_id: string; property: string; 
+/**
+ * RGBA channels from 0 to 1
+ */
+colour: [number, number, number, number] } }
 export type EntityOverridesEvent = { type: "initialise"; data: { editor_id: string } } | { type: "updatePropertyOverrides"; data: { editor_id: string; content: string } } | { type: "updateOverrideDeletes"; data: { editor_id: string; content: string } } | { type: "updatePinConnectionOverrides"; data: { editor_id: string; content: string } } | { type: "updatePinConnectionOverrideDeletes"; data: { editor_id: string; content: string } }
-export type EntityTreeEvent = { type: "initialise"; data: { editor_id: string } } | { type: "select"; data: { editor_id: string; id: string } } | { type: "create"; data: { editor_id: string; id: string; content: SubEntity } } | { type: "delete"; data: { editor_id: string; id: string } } | { type: "rename"; data: { editor_id: string; id: string; new_name: string } } | { type: "reparent"; data: { editor_id: string; id: string; new_parent: Ref } } | { type: "copy"; data: { editor_id: string; id: string } } | { type: "paste"; data: { editor_id: string; parent_id: string } } | { type: "search"; data: { editor_id: string; query: string } } | { type: "showHelpMenu"; data: { editor_id: string; entity_id: string } } | { type: "useTemplate"; data: { editor_id: string; parent_id: string; template: CopiedEntityData } } | { type: "addGameBrowserItem"; data: { editor_id: string; parent_id: string; file: string } } | { type: "selectEntityInEditor"; data: { editor_id: string; entity_id: string } } | { type: "moveEntityToPlayer"; data: { editor_id: string; entity_id: string } } | { type: "rotateEntityAsPlayer"; data: { editor_id: string; entity_id: string } } | { type: "moveEntityToCamera"; data: { editor_id: string; entity_id: string } } | { type: "rotateEntityAsCamera"; data: { editor_id: string; entity_id: string } } | { type: "restoreToOriginal"; data: { editor_id: string; entity_id: string } }
+export type EntityProblemsEvent = { type: "check"; data: { editor_id: string } }
+export type EntityStatsEvent = { type: "calculate"; data: { editor_id: string } }
+export type EntitySubsetsEvent = { type: "initialise"; data: { editor_id: string } } | 
+/**
+ * Make an entity a member of a subset, creating the subset if the owner doesn't have it yet.
+ */
+{ type: "addMember"; data: { editor_id: string; owner: string; subset: string; entity_id: string } } | { type: "removeMember"; data: { editor_id: string; owner: string; subset: string; entity_id: string } }
+export type EntityTreeEvent = { type: "initialise"; data: { editor_id: string } } | { type: "select"; data: { editor_id: string; id: string } } | { type: "create"; data: { editor_id: string; id: string; content: SubEntity } } | { type: "delete"; data: { editor_id: string; id: string } } | { type: "rename"; data: { editor_id: string; id: string; new_name: string } } | { type: "reparent"; data: { editor_id: string; id: string; new_parent: Ref } } | { type: "copy"; data: { editor_id: string; id: string } } | { type: "paste"; data: { editor_id: string; parent_id: string } } | 
+/**
+ * Duplicate an entity beside itself under the same parent, incrementing the number at the end of
+ * its name and moving it by an offset
+ */
+{ type: "duplicate"; data: { editor_id: string; id: string; include_children: boolean; offset: Vec3 } } | 
+/**
+ * Clone an entity and its children into a line, grid or circle, parented to a new group entity
+ */
+{ type: "generateArray"; data: { editor_id: string; id: string; pattern: ArrayPattern } } | { type: "pasteAsInstance"; data: { editor_id: string; parent_id: string } } | { type: "search"; data: { editor_id: string; query: string } } | { type: "showHelpMenu"; data: { editor_id: string; entity_id: string } } | 
+/**
+ * Paste a built-in template; if it has parameters and no values are given, the frontend is asked for
+ * them first
+ */
+{ type: "useTemplate"; data: { editor_id: string; parent_id: string; template: PastableTemplate; 
+/**
+ * Parameter name, value as entered
+ */
+parameter_values: { [key in string]: string } | null } } | { type: "addGameBrowserItem"; data: { editor_id: string; parent_id: string; file: string } } | 
+/**
+ * Generate a WWEM and WWEV in the project for a new sound, and add a zaudioevententity playing it
+ */
+{ type: "addCustomSound"; data: { editor_id: string; parent_id: string; name: string; 
+/**
+ * A WAV or WEM file
+ */
+audio: string; 
+/**
+ * Hash or path of a vanilla WWEV playing a single streamed sound, whose soundbank is reused
+ */
+template: string } } | { type: "selectEntityInEditor"; data: { editor_id: string; entity_id: string } } | { type: "moveEntityToPlayer"; data: { editor_id: string; entity_id: string } } | { type: "rotateEntityAsPlayer"; data: { editor_id: string; entity_id: string } } | { type: "moveEntityToCamera"; data: { editor_id: string; entity_id: string } } | { type: "rotateEntityAsCamera"; data: { editor_id: string; entity_id: string } } | { type: "restoreToOriginal"; data: { editor_id: string; entity_id: string } } | 
+/**
+ * Put back an entry of the recycle bin, along with the references to it which can still be restored
+ */
+{ type: "restoreDeleted"; data: { editor_id: string; index: number } } | 
+/**
+ * Extract the game template an entity uses as its factory into the project as an editable entity,
+ * and use that copy as the entity's factory instead
+ */
+{ type: "extractFactoryToProject"; data: { editor_id: string; entity_id: string } } | { type: "snapEntityToGrid"; data: { editor_id: string; entity_id: string; grid_size: number; angle_snap: number } } | { type: "alignEntityToSurface"; data: { editor_id: string; entity_id: string; normal: Vec3 } } | { type: "offsetEntityTransform"; data: { editor_id: string; entity_id: string; position: Vec3; rotation: Vec3; local: boolean } } | 
+/**
+ * Request the output pins of one entity and the input pins of another, to choose an event
+ * connection between them
+ */
+{ type: "getConnectionPins"; data: { editor_id: string; source_id: string; target_id: string } } | 
+/**
+ * Connect an output pin of one entity to an input pin of another, optionally sending a constant
+ * value instead of the output's own
+ */
+{ type: "connectEntities"; data: { editor_id: string; source_id: string; output_pin: string; target_id: string; input_pin: string; value: SimpleProperty | null } } | 
+/**
+ * Request the connections of an entity which send constant values, to edit them
+ */
+{ type: "getConstantValues"; data: { editor_id: string; entity_id: string } } | 
+/**
+ * Change the constant value sent by an event connection; the value is checked against the type of
+ * the target pin where intellisense knows it
+ */
+{ type: "setConstantValue"; data: { editor_id: string; entity_id: string; output_pin: string; input_pin: string; index: number; value: SimpleProperty } } | { type: "expandNode"; data: { editor_id: string; id: string } } | { type: "revealNode"; data: { editor_id: string; id: string } }
+export type EnumEditorEvent = { type: "initialise"; data: { id: string } } | { type: "addEnum"; data: { id: string; name: string } } | { type: "addValue"; data: { id: string; enum_name: string; value: string } } | { type: "removeValue"; data: { id: string; enum_name: string; value: string } }
 export type Event = { type: "tool"; data: ToolEvent } | { type: "editor"; data: EditorEvent } | { type: "global"; data: GlobalEvent } | { type: "editorConnection"; data: EditorConnectionEvent }
 /**
  * An exposed entity.
@@ -54,7 +274,72 @@ isArray: boolean;
  * The target entity (or entities) that will be accessed.
  */
 refersTo: Ref[] }
-export type FileBrowserEvent = { type: "select"; data: string | null } | { type: "create"; data: { path: string; is_folder: boolean } } | { type: "delete"; data: string } | { type: "rename"; data: { old_path: string; new_path: string } } | { type: "normaliseQNFile"; data: { path: string } } | { type: "convertEntityToPatch"; data: { path: string } } | { type: "convertPatchToEntity"; data: { path: string } } | { type: "convertRepoPatchToMergePatch"; data: { path: string } } | { type: "convertRepoPatchToJsonPatch"; data: { path: string } } | { type: "convertUnlockablesPatchToMergePatch"; data: { path: string } } | { type: "convertUnlockablesPatchToJsonPatch"; data: { path: string } }
+/**
+ * A user-configured external program (e.g. RPKG Tool) that resources and files can be opened with.
+ */
+export type ExternalTool = { name: string; path: string; 
+/**
+ * The arguments to pass to the tool; `{hash}` and `{path}` are replaced with the hash and path of the target.
+ * Arguments are separated by spaces, and can be wrapped in double quotes to include spaces.
+ */
+arguments: string }
+export type ExtractionFormat = 
+/**
+ * The resource as it's stored in the game files, along with a binary meta file
+ */
+"Raw" | 
+/**
+ * The resource converted to JSON with ResourceLib, along with a JSON meta file
+ */
+"ResourceLibJson" | 
+/**
+ * Textures and images converted to PNG
+ */
+"Png"
+/**
+ * A named set of extraction options, which lets resources be extracted without choosing where to each time.
+ */
+export type ExtractionPreset = { name: string; format: ExtractionFormat; 
+/**
+ * The name of the extracted file, without its extension; `{hash}`, `{name}` and `{type}` are replaced with the
+ * resource's hash, its name from the hash list (or hash, if it has none) and its resource type.
+ */
+namingScheme: string; 
+/**
+ * The folder to extract to; `{project}`, `{folder}` and `{type}` are replaced with the project folder, the folder
+ * of the resource's path in the hash list and its resource type.
+ */
+destination: string }
+export type ExtractionQueueEvent = { type: "initialise" } | { type: "cancel"; data: string } | { type: "clearFinished" }
+export type FileBrowserEvent = { type: "select"; data: string | null } | { type: "create"; data: { path: string; is_folder: boolean } } | { type: "delete"; data: string } | { type: "rename"; data: { old_path: string; new_path: string } } | { type: "normaliseQNFile"; data: { path: string } } | { type: "convertEntityToPatch"; data: { path: string } } | { type: "convertPatchToEntity"; data: { path: string } } | { type: "convertRepoPatchToMergePatch"; data: { path: string } } | { type: "convertRepoPatchToJsonPatch"; data: { path: string } } | { type: "convertUnlockablesPatchToMergePatch"; data: { path: string } } | { type: "convertUnlockablesPatchToJsonPatch"; data: { path: string } } | { type: "openWithExternalTool"; data: { path: string; tool: string } } | { type: "requestBackups"; data: { path: string } } | { type: "restoreBackup"; data: { path: string; timestamp: number } } | { type: "duplicate"; data: { path: string } } | { type: "previewBatchRename"; data: { folder: string; pattern: string; replacement: string } } | { type: "batchRename"; data: { folder: string; pattern: string; replacement: string } } | { type: "createFromTemplate"; data: { folder: string; name: string; template: FileTemplate } } | 
+/**
+ * Suggest the partition an entity file should be loaded in, from the scenario it's used in
+ */
+{ type: "suggestChunkAssignment"; data: { path: string; scenario: string } } | 
+/**
+ * Load an entity in a partition by adding it to the manifest's packagedefinition changes
+ */
+{ type: "assignChunk"; data: { assignment: ChunkAssignment } } | 
+/**
+ * Apply a patch file to the current game files without saving anything, to see which operations apply
+ */
+{ type: "testPatch"; data: { path: string } }
+/**
+ * A kind of file that can be created with some starting content from the file browser.
+ */
+export type FileTemplate = 
+/**
+ * An entity with just a root spatial entity
+ */
+"Entity" | 
+/**
+ * An empty repository.json merge patch
+ */
+"RepositoryPatch" | 
+/**
+ * An empty unlockables.json merge patch
+ */
+"UnlockablesPatch"
 /**
  * A long-form reference to an entity, allowing for the specification of external scenes and/or an exposed entity.
  */
@@ -71,9 +356,52 @@ externalScene: string | null;
  * The sub-entity to reference that is exposed by the referenced entity.
  */
 exposedEntity?: string | null }
-export type GameBrowserEvent = { type: "select"; data: string } | { type: "search"; data: [string, SearchFilter] } | { type: "openInEditor"; data: string }
-export type GlobalEvent = { type: "setSeenAnnouncements"; data: string[] } | { type: "loadWorkspace"; data: string } | { type: "selectAndOpenFile" } | { type: "selectTab"; data: string | null } | { type: "removeTab"; data: string } | { type: "saveTab"; data: string } | { type: "uploadLogAndReport"; data: string } | { type: "uploadLastPanic" } | { type: "clearLastPanic" }
+export type GameBrowserEvent = { type: "select"; data: string } | { type: "search"; data: [string, SearchFilter, PartitionFilter] } | { type: "openInEditor"; data: string } | { type: "removeFromCollection"; data: { collection: string; hash: string } } | { type: "deleteCollection"; data: string } | { type: "extractCollection"; data: string } | { type: "extractWithPreset"; data: { hash: string; preset: string } }
+export type GitEvent = { type: "refresh" } | { type: "diff"; data: string } | { type: "commit"; data: { files: string[]; message: string } }
+export type GlobalEvent = { type: "setSeenAnnouncements"; data: string[] } | { type: "loadWorkspace"; data: string } | { type: "selectAndOpenFile" } | { type: "selectTab"; data: string | null } | { type: "removeTab"; data: string } | { type: "saveTab"; data: string } | { type: "splitTab"; data: string } | { type: "uploadLogAndReport"; data: string } | { type: "uploadLastPanic" } | { type: "clearLastPanic" } | { type: "navigateBack" } | { type: "navigateForward" } | 
+/**
+ * Files dropped into the window, which are offered to be imported into the project
+ */
+{ type: "detectDroppedFiles"; data: string[] } | { type: "importDroppedFiles"; data: DroppedFile[] } | 
+/**
+ * Ask a running task to stop; only tasks started as cancellable can be cancelled
+ */
+{ type: "cancelTask"; data: string } | 
+/**
+ * Split the saved entity patch of a tab into several patch files by subtree
+ */
+{ type: "splitPatch"; data: string }
+export type HashRemappingEvent = 
+/**
+ * Replace each old hash with its new hash throughout the project, or only report what would be replaced
+ */
+{ type: "remap"; data: { 
+/**
+ * Old hash or path, new hash or path
+ */
+mapping: [string, string][]; dry_run: boolean } }
+export type HashResolutionEvent = 
+/**
+ * Resolve every hash and path in some text, like a crash log
+ */
+{ type: "resolve"; data: string } | { type: "openResourceOverview"; data: string }
+export type JsonPatchEditorEvent = { type: "initialise"; data: { id: string } } | { type: "updateContent"; data: { id: string; content: string } }
+export type JsonResourceEditorEvent = { type: "initialise"; data: { id: string } } | { type: "updateContent"; data: { id: string; content: string } }
 export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+export type OutlineFormat = "Markdown" | "Html"
+export type OutfitBrowserEvent = { type: "initialise" } | { type: "loadImage"; data: string } | { type: "openEntity"; data: string }
+export type PartitionFilter =
+	| "All"
+	| "BaseGame"
+	/**
+	 * Only resources in the partition with the given ID, like chunk27
+	 */
+	| { Partition: string }
+export type PastableTemplate = { name: string; icon: string; pasteData: CopiedEntityData; 
+/**
+ * Values the user is asked for when pasting the template
+ */
+parameters?: TemplateParameter[] }
 /**
  * A property with a type and a value. Can be marked as post-init.
  */
@@ -126,10 +454,172 @@ ref: Ref;
  * The constant value accompanying this reference.
  */
 value: SimpleProperty }
-export type RepositoryPatchEditorEvent = { type: "initialise"; data: { id: string } } | { type: "createRepositoryItem"; data: { id: string } } | { type: "resetModifications"; data: { id: string; item: string } } | { type: "modifyItem"; data: { id: string; item: string; data: string } } | { type: "selectItem"; data: { id: string; item: string } }
-export type ResourceOverviewEvent = { type: "initialise"; data: { id: string } } | { type: "followDependency"; data: { id: string; new_hash: string } } | { type: "followDependencyInNewTab"; data: { id: string; hash: string } } | { type: "openInEditor"; data: { id: string } } | { type: "extractAsQN"; data: { id: string } } | { type: "extractAsFile"; data: { id: string } } | { type: "extractTEMPAsRT"; data: { id: string } } | { type: "extractTBLUAsFile"; data: { id: string } } | { type: "extractTBLUAsRT"; data: { id: string } } | { type: "extractAsRTGeneric"; data: { id: string } } | { type: "extractAsImage"; data: { id: string } } | { type: "extractAsWav"; data: { id: string } } | { type: "extractMultiWav"; data: { id: string } } | { type: "extractSpecificMultiWav"; data: { id: string; index: number } } | { type: "extractORESAsJson"; data: { id: string } } | { type: "extractAsHMLanguages"; data: { id: string } }
+export type ReferenceKind = 
+/**
+ * Loaded at install time, along with the resource itself
+ */
+"Install" | 
+/**
+ * A strong runtime reference, which keeps the referenced resource loaded
+ */
+"Normal" | 
+/**
+ * A weak runtime reference, which doesn't load the referenced resource by itself
+ */
+"Weak"
+export type RepositoryItemTemplate = "Weapon" | "Outfit" | "Prop"
+export type RepositoryPatchEditorEvent = { type: "initialise"; data: { id: string } } | { type: "createRepositoryItem"; data: { id: string } } | { type: "createRepositoryItemFromTemplate"; data: { id: string; template: RepositoryItemTemplate; name: string; create_unlockable: boolean } } | { type: "resetModifications"; data: { id: string; item: string } } | { type: "modifyItem"; data: { id: string; item: string; data: string } } | { type: "selectItem"; data: { id: string; item: string } } | 
+/**
+ * Generate localisation keys for the item's Title and Description, add placeholder strings for them to
+ * the manifest and set them on the item
+ */
+{ type: "generateLocalisation"; data: { id: string; item: string } } | { type: "findEntitiesUsingItem"; data: { id: string; item: string } } | 
+/**
+ * List the properties of the repository for the table view
+ */
+{ type: "getTableColumns"; data: { id: string } } | 
+/**
+ * Get the rows of the table view; the filter is made of space-separated terms which all need to
+ * match, either `Column:text` to match a column or plain text to match the ID or any shown column
+ */
+{ type: "getTable"; data: { id: string; columns: string[]; sort_by: string | null; sort_descending: boolean; filter: string } } | 
+/**
+ * Set a property of an item from the table view; the value is parsed according to the type of the
+ * column, and an empty value removes properties which aren't strings
+ */
+{ type: "editTableCell"; data: { id: string; item: string; column: string; value: string } } | 
+/**
+ * Read a CSV file (with a header row) or a JSON array of objects to import new items from, and show
+ * its columns to map them to repository properties
+ */
+{ type: "readImportFile"; data: { id: string; path: string } } | 
+/**
+ * Add a new item with a generated ID for each row of the file; cells are parsed like those edited in
+ * the table view, and empty cells are left out
+ */
+{ type: "importItems"; data: { id: string; path: string; 
+/**
+ * Column of the file, repository property; columns mapped to an empty property are skipped
+ */
+mapping: [string, string][] } }
+export type ResourceOverviewEvent = { type: "initialise"; data: { id: string } } | { type: "followDependency"; data: { id: string; new_hash: string } } | { type: "followDependencyInNewTab"; data: { id: string; hash: string } } | { type: "openInEditor"; data: { id: string } } | { type: "extractAsQN"; data: { id: string } } | { type: "extractAsFile"; data: { id: string } } | { type: "extractTEMPAsRT"; data: { id: string } } | { type: "extractTBLUAsFile"; data: { id: string } } | { type: "extractTBLUAsRT"; data: { id: string } } | { type: "extractAsRTGeneric"; data: { id: string } } | { type: "extractAsImage"; data: { id: string } } | { type: "extractAsWav"; data: { id: string } } | { type: "extractMultiWav"; data: { id: string } } | { type: "extractSpecificMultiWav"; data: { id: string; index: number } } | { type: "extractORESAsJson"; data: { id: string } } | { type: "extractAsHMLanguages"; data: { id: string } } | { type: "extractAsObj"; data: { id: string } } | 
+/**
+ * Extract a BORG's bones as JSON
+ */
+{ type: "extractAsSkeletonJson"; data: { id: string } } | 
+/**
+ * Extract an animation resource and the animation resources and rigs it depends on, with their meta
+ * files, to a folder
+ */
+{ type: "extractAnimationWithDependencies"; data: { id: string } } | 
+/**
+ * Extract the movie embedded in a GFXF, and the files it loads, to a folder
+ */
+{ type: "extractScaleformMovie"; data: { id: string } } | 
+/**
+ * Replace the movie embedded in a GFXF with a SWF or GFx file, saving the repacked GFXF to the project
+ */
+{ type: "repackScaleformMovie"; data: { id: string } } | 
+/**
+ * Export a behaviour tree as a Graphviz DOT graph
+ */
+{ type: "exportBehaviourTreeAsDot"; data: { id: string } } | { type: "addToCollection"; data: { id: string; collection: string } } | { type: "getGenericRLPage"; data: { id: string; start_line: number } } | { type: "searchGenericRL"; data: { id: string; query: string } } | { type: "openWithExternalTool"; data: { id: string; tool: string } } | { type: "extractWithPreset"; data: { id: string; preset: string } } | { type: "replaceWithImage"; data: { id: string } } | { type: "compareTextureMips"; data: { id: string } } | 
+/**
+ * Compare the resource at an entry of its changelog with the copy it replaced, as converted JSON
+ */
+{ type: "getChangelogDiff"; data: { id: string; index: number } } | 
+/**
+ * Change the flag of a dependency in the resource's JSON meta file in the project
+ */
+{ type: "setProjectDependencyFlag"; data: { id: string; index: number; flag: DecodedReferenceFlag } } | 
+/**
+ * Move a constituent resource of an ASET in the project to a different position
+ */
+{ type: "moveAssetSetEntry"; data: { id: string; from: number; to: number } } | 
+/**
+ * Add a constituent resource to the end of an ASET in the project, by hash or path
+ */
+{ type: "addAssetSetEntry"; data: { id: string; resource: string } } | { type: "removeAssetSetEntry"; data: { id: string; index: number } } | 
+/**
+ * Convert edited material entity JSON back to a MATT and MATB replacing those being viewed, and write
+ * them to the project
+ */
+{ type: "saveMaterialEntity"; data: { id: string; json: string } }
+/**
+ * What GlacierKit can do with a resource type in a particular game version.
+ */
+export type ResourceTypeCapabilities = { resource_type: string; 
+/**
+ * Whether the resource overview can show the resource's contents
+ */
+preview: boolean; 
+/**
+ * Whether the resource can be extracted in a more useful format than its binary data
+ */
+convert: boolean; 
+/**
+ * Whether the resource can be rebuilt from a converted or edited form
+ */
+rebuild: boolean }
+/**
+ * A named query saved to the project, so that it can be run again without re-entering it.
+ */
+export type SavedSearch = { name: string; query: SavedSearchQuery; 
+/**
+ * Whether to run the search again whenever files in the project change
+ */
+rerunOnChange: boolean }
+export type SavedSearchQuery = 
+/**
+ * A search of the game files, with the same options as the content search tool
+ */
+{ type: "contentSearch"; data: { query: string; filetypes: string[]; useQnFormat: boolean; partitions: string[]; convertWithResourcelib: boolean } } | 
+/**
+ * A search of the entity tree of whichever entity editor is focused
+ */
+{ type: "entityTree"; data: { query: string } }
 export type SearchFilter = "All" | "Templates" | "Classes" | "Models" | "Textures" | "Sound"
-export type SettingsEvent = { type: "initialise" } | { type: "changeGameInstall"; data: string | null } | { type: "changeExtractModdedFiles"; data: boolean } | { type: "changeColourblind"; data: boolean } | { type: "changeEditorConnection"; data: boolean } | { type: "changeCustomPaths"; data: string[] }
+export type SettingsEvent = { type: "initialise" } | { type: "changeGameInstall"; data: string | null } | { type: "changeExtractModdedFiles"; data: boolean } | { type: "changeColourblind"; data: boolean } | { type: "changeEditorConnection"; data: boolean } | { type: "changeEditorConnectionGames"; data: EditorConnectionGame[] } | { type: "setEditorConnectionTarget"; data: string | null } | 
+/**
+ * Wait for another instance to connect and sync entity changes with it
+ */
+{ type: "hostCollaboration"; data: { 
+/**
+ * Shown to the other instance's user
+ */
+name: string; port: number } } | 
+/**
+ * Connect to another instance which is hosting, at an address like `192.168.1.2:46735`
+ */
+{ type: "connectCollaboration"; data: { 
+/**
+ * Shown to the other instance's user
+ */
+name: string; address: string } } | { type: "stopCollaboration" } | { type: "reconnectEditorConnection" } | { type: "changeOfflineMode"; data: boolean } | { type: "changeExternalTools"; data: ExternalTool[] } | { type: "changeExtractionPresets"; data: ExtractionPreset[] } | { type: "changeWemEncoder"; data: WemEncoder | null } | { type: "changeEntityCacheMaxEntries"; data: number } | 
+/**
+ * In megabytes
+ */
+{ type: "changeEntityCacheMaxMemory"; data: number } | 
+/**
+ * Hashes or paths of resources to check for changes whenever the game files are updated
+ */
+{ type: "changeWatchedResources"; data: string[] } | 
+/**
+ * Clear the cache of entities extracted from the game files and the in-memory intellisense caches
+ */
+{ type: "clearCaches" } | 
+/**
+ * Measure the memory taken up by each editor and cache, and get the timings of recent tasks
+ */
+{ type: "getDiagnostics" } | { type: "changeCustomPaths"; data: string[] } | { type: "changePackageIgnore"; data: string[] } | { type: "changeAudioNamePrefixes"; data: string[] } | { type: "changeLintEntityNames"; data: boolean } | { type: "changeEntityNamePatterns"; data: string[] } | { type: "changeTidyEntitiesOnSave"; data: boolean } | { type: "changeLocalisationLanguages"; data: string[] } | { type: "changeReadOnly"; data: boolean } | { type: "changeBackupCount"; data: number } | { type: "changePatchSplitMaxOperations"; data: number } | { type: "changePatchSplitMaxSize"; data: number } | 
+/**
+ * Whether the loaded project is allowed to run external programs
+ */
+{ type: "changeProjectTrusted"; data: boolean } | { type: "packageMod"; data: VersionBump } | { type: "findOrphanedFiles" } | { type: "archiveOrphanedFiles"; data: string[] } | 
+/**
+ * Find resources and blobs changed by both the project and mods enabled in the framework
+ */
+{ type: "findModConflicts" }
 /**
  * A simple property.
  * 
@@ -212,10 +702,60 @@ exposedInterfaces?: Partial<{ [key in string]: string }> | null;
  */
 subsets?: Partial<{ [key in string]: string[] }> | null }
 export type SubType = "brick" | "scene" | "template"
-export type TextEditorEvent = { type: "initialise"; data: { id: string } } | { type: "updateContent"; data: { id: string; content: string } }
-export type ToolEvent = { type: "fileBrowser"; data: FileBrowserEvent } | { type: "gameBrowser"; data: GameBrowserEvent } | { type: "settings"; data: SettingsEvent } | { type: "contentSearch"; data: ContentSearchEvent }
-export type UnlockablesPatchEditorEvent = { type: "initialise"; data: { id: string } } | { type: "createUnlockable"; data: { id: string } } | { type: "resetModifications"; data: { id: string; unlockable: string } } | { type: "modifyUnlockable"; data: { id: string; unlockable: string; data: string } } | { type: "selectUnlockable"; data: { id: string; unlockable: string } }
+/**
+ * A value substituted into a template when it's pasted. Any string in the paste data which is exactly `{{name}}` is
+ * replaced with the value, and text and repository ID values also replace `{{name}}` within longer strings.
+ */
+export type TemplateParameter = { name: string; kind: TemplateParameterKind; default: string | null }
+export type TemplateParameterKind = "text" | 
+/**
+ * Three numbers separated by commas or spaces, substituted as an object with x, y and z
+ */
+"position" | 
+/**
+ * A repository GUID
+ */
+"repositoryId"
+export type TextEditorEvent = { type: "initialise"; data: { id: string } } | { type: "updateContent"; data: { id: string; content: string } } | 
+/**
+ * Search the content held by the backend, for files too large for Monaco's own search; the ID is
+ * sent back with the results
+ */
+{ type: "find"; data: { id: string; search_id: string; query: string; regex: boolean; match_case: boolean } } | 
+/**
+ * Replace every match of a search, or only the one at the given byte offset
+ */
+{ type: "replace"; data: { id: string; query: string; regex: boolean; match_case: boolean; replacement: string; offset: number | null } }
+export type ToolEvent = { type: "fileBrowser"; data: FileBrowserEvent } | { type: "gameBrowser"; data: GameBrowserEvent } | { type: "settings"; data: SettingsEvent } | { type: "contentSearch"; data: ContentSearchEvent } | { type: "outfitBrowser"; data: OutfitBrowserEvent } | { type: "git"; data: GitEvent } | { type: "extractionQueue"; data: ExtractionQueueEvent } | { type: "audioNames"; data: AudioNamesEvent } | { type: "converters"; data: ConvertersEvent } | { type: "hashRemapping"; data: HashRemappingEvent } | { type: "hashResolution"; data: HashResolutionEvent } | { type: "audioImport"; data: AudioImportEvent } | { type: "crashAnalysis"; data: CrashAnalysisEvent }
+/**
+ * A conversion between two representations of a property's value.
+ */
+export type UnitConversion = "DegreesToRadians" | "RadiansToDegrees" | "MetresToCentimetres" | "CentimetresToMetres" | 
+/**
+ * An SVector3 or SVector4 of colour channels from 0 to 1 to an SColorRGB or SColorRGBA
+ */
+"FloatsToHexColour" | 
+/**
+ * An SColorRGB or SColorRGBA to an SVector3 or SVector4 of colour channels from 0 to 1
+ */
+"HexColourToFloats"
+export type UnlockablesPatchEditorEvent = { type: "initialise"; data: { id: string } } | { type: "createUnlockable"; data: { id: string } } | { type: "resetModifications"; data: { id: string; unlockable: string } } | { type: "modifyUnlockable"; data: { id: string; unlockable: string; data: string } } | { type: "selectUnlockable"; data: { id: string; unlockable: string } } | 
+/**
+ * Generate a localisation key for the unlockable's display name, add placeholder strings for it to the
+ * manifest and set it on the unlockable
+ */
+{ type: "generateLocalisation"; data: { id: string; unlockable: string } }
 export type Vec3 = { x: number; y: number; z: number }
+export type VersionBump = "None" | "Patch" | "Minor" | "Major"
+/**
+ * A user-configured program which converts a WAV file to WEM, like WwiseConsole or a script wrapping it.
+ */
+export type WemEncoder = { path: string; 
+/**
+ * The arguments to pass to the encoder; `{input}` is replaced with the WAV file and `{output}` with where the WEM
+ * should be written. Arguments are separated by spaces, and can be wrapped in double quotes to include spaces.
+ */
+arguments: string }
 
 /** tauri-specta globals **/
 
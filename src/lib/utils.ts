@@ -23,8 +23,24 @@ export async function event(event: Event) {
 	await commands.event(event)
 }
 
+let offlineMode: Promise<boolean> | null = null
+
+/** Whether offline mode is enabled, in which case no telemetry is sent and no network requests should be made. */
+export async function isOfflineMode() {
+	offlineMode ??= commands.isOfflineMode()
+	return await offlineMode
+}
+
+export function setOfflineMode(value: boolean) {
+	offlineMode = Promise.resolve(value)
+}
+
 export function trackEvent(name: string, props?: Record<string, unknown>) {
-	void invoke<string>("plugin:aptabase|track_event", { name, props })
+	void isOfflineMode().then((offline) => {
+		if (!offline) {
+			void invoke<string>("plugin:aptabase|track_event", { name, props })
+		}
+	})
 }
 
 export const showInFolder = commands.showInFolder